@@ -0,0 +1,49 @@
+//! Build script for basilica-api
+//!
+//! Records the git commit, build time and compiler version so the health
+//! endpoint can report what is deployed. `BASILICA_GIT_SHA` and
+//! `SOURCE_DATE_EPOCH` override the detected values, for builds outside a
+//! git checkout (e.g. Docker) and reproducible builds.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=BASILICA_GIT_SHA");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs");
+
+    let git_sha = env::var("BASILICA_GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.is_empty())
+        .or_else(|| command_output("git", &["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let build_time = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<i64>().ok())
+        .and_then(|epoch| chrono::DateTime::from_timestamp(epoch, 0))
+        .unwrap_or_else(chrono::Utc::now);
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version =
+        command_output(&rustc, &["--version"]).unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=BASILICA_API_GIT_SHA={git_sha}");
+    println!(
+        "cargo:rustc-env=BASILICA_API_BUILD_TIMESTAMP={}",
+        build_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    );
+    println!("cargo:rustc-env=BASILICA_API_RUSTC_VERSION={rustc_version}");
+}
+
+/// Trimmed stdout of a successful command
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_string()).filter(|s| !s.is_empty())
+}
@@ -0,0 +1,31 @@
+-- Per-user webhook endpoint for rental lifecycle notifications
+CREATE TABLE IF NOT EXISTS user_webhooks (
+    user_id TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,  -- HMAC-SHA256 signing key shared with the user
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Pending webhook deliveries, drained by the gateway's webhook dispatcher
+CREATE TABLE IF NOT EXISTS webhook_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    event_id TEXT NOT NULL UNIQUE,  -- "<rental_id>:<state>", so a transition is delivered once
+    event_type TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    claimed_at TIMESTAMPTZ,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    delivered_at TIMESTAMPTZ,
+    dead_lettered_at TIMESTAMPTZ,  -- set once delivery gave up after max_attempts
+    last_error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_outbox_ready ON webhook_outbox (delivered_at, dead_lettered_at, next_attempt_at);
+CREATE INDEX IF NOT EXISTS idx_webhook_outbox_user_id ON webhook_outbox (user_id);
+
+-- Last rental state seen by the webhook watcher, used to detect transitions
+ALTER TABLE user_rentals
+ADD COLUMN IF NOT EXISTS last_known_state TEXT;
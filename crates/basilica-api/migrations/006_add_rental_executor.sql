@@ -0,0 +1,20 @@
+-- Record the executor each rental runs on, so a later run can pin to it.
+-- The GPU columns describe that executor, letting a pinned rental fall back
+-- to an equivalent executor while the original is unavailable.
+ALTER TABLE user_rentals
+ADD COLUMN executor_id VARCHAR(255),
+ADD COLUMN gpu_type TEXT,
+ADD COLUMN gpu_count INTEGER,
+ADD COLUMN gpu_memory_gb INTEGER;
+
+ALTER TABLE terminated_user_rentals
+ADD COLUMN executor_id VARCHAR(255),
+ADD COLUMN gpu_type TEXT,
+ADD COLUMN gpu_count INTEGER,
+ADD COLUMN gpu_memory_gb INTEGER;
+
+-- All columns are nullable: rentals created before this migration have no
+-- recorded executor
+
+CREATE INDEX IF NOT EXISTS idx_user_rentals_executor_id ON user_rentals(executor_id);
+CREATE INDEX IF NOT EXISTS idx_terminated_user_rentals_executor_id ON terminated_user_rentals(executor_id);
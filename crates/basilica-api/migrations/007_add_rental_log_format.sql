@@ -0,0 +1,4 @@
+-- How each rental's container writes its logs ('plain' or 'json'), so the
+-- log stream can parse JSON lines into structured entries
+ALTER TABLE user_rentals
+ADD COLUMN log_format TEXT NOT NULL DEFAULT 'plain';
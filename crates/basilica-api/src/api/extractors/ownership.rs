@@ -8,6 +8,7 @@ use axum::{
     extract::{FromRequestParts, Path},
     http::{request::Parts, StatusCode},
 };
+use basilica_sdk::types::LogFormat;
 use sqlx::{FromRow, PgPool};
 use tracing::{debug, warn};
 
@@ -19,6 +20,7 @@ struct UserRentalRow {
     rental_id: String,
     user_id: String,
     ssh_credentials: Option<String>,
+    log_format: String,
     #[allow(dead_code)]
     created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -32,6 +34,8 @@ pub struct OwnedRental {
     pub rental_id: String,
     pub user_id: String,
     pub ssh_credentials: Option<String>,
+    /// How the rental's container writes its logs
+    pub log_format: LogFormat,
 }
 
 #[async_trait]
@@ -70,6 +74,7 @@ impl FromRequestParts<AppState> for OwnedRental {
                     rental_id: row.rental_id,
                     user_id: row.user_id,
                     ssh_credentials: row.ssh_credentials,
+                    log_format: row.log_format.parse().unwrap_or_default(),
                 })
             }
             None => {
@@ -92,7 +97,7 @@ async fn get_rental_ownership(
 ) -> Result<Option<UserRentalRow>, sqlx::Error> {
     let row = sqlx::query_as::<_, UserRentalRow>(
         r#"
-        SELECT rental_id, user_id, ssh_credentials, created_at
+        SELECT rental_id, user_id, ssh_credentials, log_format, created_at
         FROM user_rentals 
         WHERE rental_id = $1 AND user_id = $2
         "#,
@@ -116,16 +121,18 @@ pub async fn store_rental_ownership(
     rental_id: &str,
     user_id: &str,
     ssh_credentials: Option<&str>,
+    log_format: LogFormat,
 ) -> Result<(), sqlx::Error> {
     sqlx::query(
         r#"
-        INSERT INTO user_rentals (rental_id, user_id, ssh_credentials)
-        VALUES ($1, $2, $3)
+        INSERT INTO user_rentals (rental_id, user_id, ssh_credentials, log_format)
+        VALUES ($1, $2, $3, $4)
         "#,
     )
     .bind(rental_id)
     .bind(user_id)
     .bind(ssh_credentials)
+    .bind(log_format.as_str())
     .execute(db)
     .await?;
 
@@ -139,6 +146,91 @@ pub async fn store_rental_ownership(
     Ok(())
 }
 
+/// GPUs of the executor a rental runs on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutorGpus {
+    pub gpu_type: String,
+    pub gpu_count: u32,
+    pub gpu_memory_gb: u32,
+}
+
+impl ExecutorGpus {
+    /// Describe an executor's GPUs, or `None` for an executor without GPUs
+    pub fn of(executor: &basilica_validator::api::types::ExecutorDetails) -> Option<Self> {
+        let first = executor.gpu_specs.first()?;
+        Some(Self {
+            gpu_type: first.name.clone(),
+            gpu_count: executor.gpu_specs.len() as u32,
+            gpu_memory_gb: executor
+                .gpu_specs
+                .iter()
+                .map(|gpu| gpu.memory_gb)
+                .min()
+                .unwrap_or(first.memory_gb),
+        })
+    }
+}
+
+/// Record the executor a rental was started on
+pub async fn record_rental_executor(
+    db: &PgPool,
+    rental_id: &str,
+    executor_id: &str,
+    gpus: Option<&ExecutorGpus>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE user_rentals
+        SET executor_id = $2, gpu_type = $3, gpu_count = $4, gpu_memory_gb = $5
+        WHERE rental_id = $1
+        "#,
+    )
+    .bind(rental_id)
+    .bind(executor_id)
+    .bind(gpus.map(|gpus| gpus.gpu_type.as_str()))
+    .bind(gpus.map(|gpus| gpus.gpu_count as i32))
+    .bind(gpus.map(|gpus| gpus.gpu_memory_gb as i32))
+    .execute(db)
+    .await?;
+
+    debug!("Recorded executor {} for rental {}", executor_id, rental_id);
+
+    Ok(())
+}
+
+/// GPUs most recently recorded for `executor_id` by any rental, active or stopped
+pub async fn find_executor_gpus(
+    db: &PgPool,
+    executor_id: &str,
+) -> Result<Option<ExecutorGpus>, sqlx::Error> {
+    let row: Option<(String, i32, i32)> = sqlx::query_as(
+        r#"
+        SELECT gpu_type, gpu_count, gpu_memory_gb FROM (
+            SELECT gpu_type, gpu_count, gpu_memory_gb, created_at
+            FROM user_rentals
+            WHERE executor_id = $1 AND gpu_type IS NOT NULL
+            UNION ALL
+            SELECT gpu_type, gpu_count, gpu_memory_gb, created_at
+            FROM terminated_user_rentals
+            WHERE executor_id = $1 AND gpu_type IS NOT NULL
+        ) AS recorded
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(executor_id)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(
+        row.map(|(gpu_type, gpu_count, gpu_memory_gb)| ExecutorGpus {
+            gpu_type,
+            gpu_count: gpu_count as u32,
+            gpu_memory_gb: gpu_memory_gb as u32,
+        }),
+    )
+}
+
 /// Get all rentals owned by a specific user
 pub async fn get_user_rental_ids(db: &PgPool, user_id: &str) -> Result<Vec<String>, sqlx::Error> {
     let records: Vec<(String,)> = sqlx::query_as(
@@ -213,8 +305,8 @@ pub async fn archive_rental_ownership(
     // First, copy the rental to terminated_user_rentals table
     sqlx::query(
         r#"
-        INSERT INTO terminated_user_rentals (rental_id, user_id, ssh_credentials, created_at, stopped_at, stop_reason)
-        SELECT rental_id, user_id, ssh_credentials, created_at, NOW(), $2
+        INSERT INTO terminated_user_rentals (rental_id, user_id, ssh_credentials, created_at, stopped_at, stop_reason, executor_id, gpu_type, gpu_count, gpu_memory_gb)
+        SELECT rental_id, user_id, ssh_credentials, created_at, NOW(), $2, executor_id, gpu_type, gpu_count, gpu_memory_gb
         FROM user_rentals
         WHERE rental_id = $1
         "#,
@@ -361,7 +453,7 @@ mod tests {
             .is_none());
 
         // Store ownership with SSH credentials
-        store_rental_ownership(&db, rental_id, user_id, ssh_creds)
+        store_rental_ownership(&db, rental_id, user_id, ssh_creds, LogFormat::Plain)
             .await
             .expect("Failed to store ownership");
 
@@ -0,0 +1,117 @@
+//! CORS policy for the gateway
+
+use super::{
+    IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAY_HEADER, RATE_LIMIT_LIMIT_HEADER,
+    RATE_LIMIT_REMAINING_HEADER, RATE_LIMIT_RESET_HEADER,
+};
+use crate::error::{ApiError, Result};
+use axum::http::{header, HeaderName};
+use basilica_common::config::CorsConfig;
+use tower_http::cors::CorsLayer;
+
+/// Build the CORS layer from the configured allowlist
+///
+/// On top of the shared policy, browsers may send the idempotency key and
+/// read the replay and rate limit headers.
+pub fn cors_layer(config: &CorsConfig) -> Result<CorsLayer> {
+    config
+        .layer(
+            &[HeaderName::from_static(IDEMPOTENCY_KEY_HEADER)],
+            &[
+                HeaderName::from_static(IDEMPOTENT_REPLAY_HEADER),
+                HeaderName::from_static(RATE_LIMIT_LIMIT_HEADER),
+                HeaderName::from_static(RATE_LIMIT_REMAINING_HEADER),
+                HeaderName::from_static(RATE_LIMIT_RESET_HEADER),
+                header::RETRY_AFTER,
+            ],
+        )
+        .map_err(ApiError::ConfigError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(allowed_origins: &[&str], allow_credentials: bool) -> Router {
+        let config = CorsConfig {
+            allowed_origins: allowed_origins.iter().map(|o| o.to_string()).collect(),
+            allow_credentials,
+        };
+        Router::new()
+            .route("/rentals", get(|| async { "ok" }))
+            .layer(cors_layer(&config).unwrap())
+    }
+
+    fn preflight(origin: &str) -> Request<Body> {
+        Request::builder()
+            .method("OPTIONS")
+            .uri("/rentals")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_allowed_origin_gets_cors_headers() {
+        let response = app(&["https://dashboard.example.com"], true)
+            .oneshot(preflight("https://dashboard.example.com"))
+            .await
+            .unwrap();
+
+        let headers = response.headers();
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://dashboard.example.com"
+        );
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+        let methods = headers[header::ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .unwrap();
+        assert!(methods.contains("DELETE"));
+        assert!(!methods.contains("PATCH"));
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_origin_is_rejected() {
+        let response = app(&["https://dashboard.example.com"], true)
+            .oneshot(preflight("https://evil.example.com"))
+            .await
+            .unwrap();
+
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_empty_allowlist_rejects_every_origin() {
+        let response = app(&[], false)
+            .oneshot(preflight("http://localhost:3000"))
+            .await
+            .unwrap();
+
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_is_explicit_opt_in() {
+        let response = app(&["*"], false)
+            .oneshot(preflight("http://localhost:3000"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        assert!(cors_layer(&CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            allow_credentials: true,
+        })
+        .is_err());
+    }
+}
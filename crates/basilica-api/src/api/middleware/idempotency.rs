@@ -0,0 +1,370 @@
+//! Idempotency middleware for mutating routes
+//!
+//! Routes opting in replay the first response for a repeated
+//! `Idempotency-Key` header instead of running the handler again, so client
+//! retries at the edge cannot start a second rental. Responses are keyed by
+//! caller, key and route; reusing a key with a different body is a conflict.
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use moka::future::Cache;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+use super::auth::AuthContext;
+use crate::error::ApiError;
+
+/// Request header carrying the client-supplied idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set on replayed responses
+pub const IDEMPOTENT_REPLAY_HEADER: &str = "idempotent-replayed";
+
+/// Largest request or response body buffered for idempotency
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Longest accepted idempotency key
+const MAX_KEY_LENGTH: usize = 255;
+
+#[derive(Debug, Clone)]
+enum Entry {
+    /// The first request with this key is still being handled
+    InFlight { body_hash: [u8; 32] },
+    /// The first request completed with this response
+    Completed {
+        body_hash: [u8; 32],
+        response: Arc<StoredResponse>,
+    },
+}
+
+impl Entry {
+    fn body_hash(&self) -> &[u8; 32] {
+        match self {
+            Entry::InFlight { body_hash } | Entry::Completed { body_hash, .. } => body_hash,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct StoredResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl StoredResponse {
+    fn replay(&self) -> Response {
+        let mut response = Response::new(Body::from(self.body.clone()));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+        response
+            .headers_mut()
+            .insert(IDEMPOTENT_REPLAY_HEADER, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+/// Responses stored per (caller, idempotency key, route)
+#[derive(Clone)]
+pub struct IdempotencyCache {
+    entries: Cache<String, Entry>,
+}
+
+impl IdempotencyCache {
+    pub fn new(ttl: Duration, max_entries: u64) -> Self {
+        Self {
+            entries: Cache::builder()
+                .time_to_live(ttl)
+                .max_capacity(max_entries)
+                .build(),
+        }
+    }
+}
+
+/// Removes an in-flight entry unless the request stored its response.
+///
+/// The handler future can be dropped mid-request, e.g. when the client
+/// disconnects; without the guard the key would stay in flight until the
+/// entry expires and every retry would be refused.
+struct InFlightGuard {
+    entries: Cache<String, Entry>,
+    cache_key: Option<String>,
+}
+
+impl InFlightGuard {
+    fn new(entries: Cache<String, Entry>, cache_key: String) -> Self {
+        Self {
+            entries,
+            cache_key: Some(cache_key),
+        }
+    }
+
+    /// Drop the in-flight entry so the request can be retried
+    async fn invalidate(mut self) {
+        if let Some(cache_key) = self.cache_key.take() {
+            self.entries.invalidate(&cache_key).await;
+        }
+    }
+
+    /// Store the response, replacing the in-flight entry
+    async fn complete(mut self, entry: Entry) {
+        if let Some(cache_key) = self.cache_key.take() {
+            self.entries.insert(cache_key, entry).await;
+        }
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let Some(cache_key) = self.cache_key.take() else {
+            return;
+        };
+        let entries = self.entries.clone();
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move { entries.invalidate(&cache_key).await });
+        }
+    }
+}
+
+/// Replay stored responses for requests carrying an `Idempotency-Key`.
+///
+/// Requests without the header pass through untouched. Server errors are not
+/// stored, so a retry after a 5xx runs the handler again.
+pub async fn idempotency_middleware(
+    State(cache): State<IdempotencyCache>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(req).await);
+    };
+    let key = key
+        .to_str()
+        .ok()
+        .filter(|k| !k.is_empty() && k.len() <= MAX_KEY_LENGTH)
+        .ok_or_else(|| ApiError::BadRequest {
+            message: format!(
+                "{IDEMPOTENCY_KEY_HEADER} header must be 1-{MAX_KEY_LENGTH} visible ASCII characters"
+            ),
+        })?
+        .to_string();
+
+    let caller = req
+        .extensions()
+        .get::<AuthContext>()
+        .map(|ctx| ctx.user_id.clone())
+        .unwrap_or_default();
+    let cache_key = format!("{caller}\n{key}\n{} {}", req.method(), req.uri().path());
+
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|e| ApiError::BadRequest {
+            message: format!("Failed to read request body: {e}"),
+        })?;
+    let body_hash: [u8; 32] = Sha256::digest(&body).into();
+
+    let entry = cache
+        .entries
+        .entry(cache_key.clone())
+        .or_insert(Entry::InFlight { body_hash })
+        .await;
+
+    if !entry.is_fresh() {
+        let existing = entry.into_value();
+        if existing.body_hash() != &body_hash {
+            return Err(ApiError::Conflict {
+                message: format!(
+                    "{IDEMPOTENCY_KEY_HEADER} was already used with a different request body"
+                ),
+            });
+        }
+        return match existing {
+            Entry::Completed { response, .. } => {
+                debug!("Replaying stored response for idempotency key {}", key);
+                Ok(response.replay())
+            }
+            Entry::InFlight { .. } => Err(ApiError::Conflict {
+                message: format!(
+                    "A request with this {IDEMPOTENCY_KEY_HEADER} is still in progress"
+                ),
+            }),
+        };
+    }
+
+    let guard = InFlightGuard::new(cache.entries.clone(), cache_key);
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    if response.status().is_server_error() {
+        guard.invalidate().await;
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            guard.invalidate().await;
+            return Ok(ApiError::Internal {
+                message: format!("Failed to read response body: {e}"),
+            }
+            .into_response());
+        }
+    };
+
+    let stored = Arc::new(StoredResponse {
+        status: parts.status,
+        headers: parts.headers.clone(),
+        body: body.clone(),
+    });
+    guard
+        .complete(Entry::Completed {
+            body_hash,
+            response: stored,
+        })
+        .await;
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::post, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    fn app(cache: IdempotencyCache, calls: Arc<AtomicUsize>) -> Router {
+        Router::new()
+            .route(
+                "/rentals",
+                post(move |body: Bytes| {
+                    let calls = calls.clone();
+                    async move {
+                        let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                        if body.as_ref() == b"hang" && n == 1 {
+                            std::future::pending::<()>().await;
+                        }
+                        (
+                            StatusCode::CREATED,
+                            format!("rental-{n}:{}", String::from_utf8_lossy(&body)),
+                        )
+                    }
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                cache,
+                idempotency_middleware,
+            ))
+    }
+
+    fn request(key: Option<&str>, body: &'static str) -> Request {
+        let mut builder = axum::http::Request::builder()
+            .method("POST")
+            .uri("/rentals");
+        if let Some(key) = key {
+            builder = builder.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        builder.body(Body::from(body)).unwrap()
+    }
+
+    async fn body_string(response: Response) -> String {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    fn cache() -> IdempotencyCache {
+        IdempotencyCache::new(Duration::from_secs(60), 100)
+    }
+
+    #[tokio::test]
+    async fn test_replay_returns_stored_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(cache(), calls.clone());
+
+        let first = app
+            .clone()
+            .oneshot(request(Some("key-1"), "{\"gpu\":\"h100\"}"))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(first.headers().get(IDEMPOTENT_REPLAY_HEADER).is_none());
+        let first_body = body_string(first).await;
+
+        let replay = app
+            .oneshot(request(Some("key-1"), "{\"gpu\":\"h100\"}"))
+            .await
+            .unwrap();
+        assert_eq!(replay.status(), StatusCode::CREATED);
+        assert_eq!(
+            replay.headers().get(IDEMPOTENT_REPLAY_HEADER).unwrap(),
+            "true"
+        );
+        assert_eq!(body_string(replay).await, first_body);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reused_key_with_different_body_conflicts() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(cache(), calls.clone());
+
+        app.clone()
+            .oneshot(request(Some("key-1"), "{\"gpu\":\"h100\"}"))
+            .await
+            .unwrap();
+        let conflict = app
+            .oneshot(request(Some("key-1"), "{\"gpu\":\"a100\"}"))
+            .await
+            .unwrap();
+
+        assert_eq!(conflict.status(), StatusCode::CONFLICT);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_request_releases_key() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = cache();
+        let app = app(cache.clone(), calls.clone());
+
+        // The client goes away while the handler is still running
+        let dropped = tokio::time::timeout(
+            Duration::from_millis(50),
+            app.clone().oneshot(request(Some("key-1"), "hang")),
+        )
+        .await;
+        assert!(dropped.is_err());
+
+        // The guard releases the key from a spawned task
+        for _ in 0..100 {
+            if cache.entries.iter().next().is_none() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+
+        let retry = app.oneshot(request(Some("key-1"), "hang")).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::CREATED);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_key_are_not_deduplicated() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(cache(), calls.clone());
+
+        for _ in 0..2 {
+            let response = app.clone().oneshot(request(None, "{}")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,20 +1,34 @@
 //! Rate limiting middleware
+//!
+//! Every response carries `RateLimit-Limit`, `RateLimit-Remaining` and
+//! `RateLimit-Reset` describing the caller's token bucket, and throttled
+//! responses add `Retry-After` so clients know when to try again.
 
 use crate::{error::ApiError, server::AppState};
 use axum::{
-    extract::{ConnectInfo, Request},
-    http::StatusCode,
+    extract::{ConnectInfo, Request, State},
+    http::{header::RETRY_AFTER, HeaderMap, HeaderValue},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use dashmap::DashMap;
 use governor::{
-    clock::DefaultClock,
+    clock::{Clock, DefaultClock},
+    middleware::StateInformationMiddleware,
     state::{InMemoryState, NotKeyed},
     Quota, RateLimiter,
 };
 use std::{net::SocketAddr, sync::Arc, time::Duration};
 
+/// Requests the caller's bucket holds when full
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "ratelimit-limit";
+
+/// Requests the caller may still make right now
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "ratelimit-remaining";
+
+/// Seconds until the caller's bucket is full again
+pub const RATE_LIMIT_RESET_HEADER: &str = "ratelimit-reset";
+
 /// Rate limit key type
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub(super) enum RateLimitKey {
@@ -24,17 +38,99 @@ pub(super) enum RateLimitKey {
     ApiKey(String),
 }
 
-/// Type alias for rate limiter
-type RateLimiterType = Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>;
+/// Token bucket for one caller, with the quota it was created from
+#[derive(Clone)]
+struct Bucket {
+    limiter: Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock, StateInformationMiddleware>>,
+    quota: Quota,
+}
+
+impl Bucket {
+    fn per_minute(requests_per_minute: u32) -> Self {
+        let quota = Quota::per_minute(
+            std::num::NonZeroU32::new(requests_per_minute)
+                .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
+        );
+        Self {
+            limiter: Arc::new(
+                RateLimiter::direct(quota).with_middleware::<StateInformationMiddleware>(),
+            ),
+            quota,
+        }
+    }
+
+    /// Take a token if one is available and describe the bucket afterwards
+    fn check(&self) -> RateLimitStatus {
+        let limit = self.quota.burst_size().get();
+        let interval = self.quota.replenish_interval();
+        match self.limiter.check() {
+            Ok(snapshot) => {
+                let remaining = snapshot.remaining_burst_capacity();
+                RateLimitStatus {
+                    limit,
+                    remaining,
+                    reset: ceil_secs(interval * (limit - remaining)),
+                    retry_after: None,
+                }
+            }
+            Err(not_until) => {
+                let wait = not_until.wait_time_from(DefaultClock::default().now());
+                RateLimitStatus {
+                    limit,
+                    remaining: 0,
+                    reset: ceil_secs(wait + interval * (limit - 1)),
+                    retry_after: Some(ceil_secs(wait).max(1)),
+                }
+            }
+        }
+    }
+}
+
+/// State of a caller's token bucket after a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// Requests the bucket holds when full
+    pub limit: u32,
+    /// Requests that may still be made right now
+    pub remaining: u32,
+    /// Seconds until the bucket is full again
+    pub reset: u64,
+    /// Seconds until the next request is allowed, if this one was refused
+    pub retry_after: Option<u64>,
+}
+
+impl RateLimitStatus {
+    /// Whether the request was refused
+    pub fn is_throttled(&self) -> bool {
+        self.retry_after.is_some()
+    }
+
+    /// Add the `RateLimit-*` headers, and `Retry-After` when throttled
+    pub fn apply_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(RATE_LIMIT_LIMIT_HEADER, HeaderValue::from(self.limit));
+        headers.insert(
+            RATE_LIMIT_REMAINING_HEADER,
+            HeaderValue::from(self.remaining),
+        );
+        headers.insert(RATE_LIMIT_RESET_HEADER, HeaderValue::from(self.reset));
+        if let Some(retry_after) = self.retry_after {
+            headers.insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+    }
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
 
 /// Rate limiter storage
 pub struct RateLimitStorage {
     /// Default limiter for anonymous requests
-    default_limiter: RateLimiterType,
+    default_limiter: Bucket,
     /// Per-IP limiters
-    ip_limiters: Arc<DashMap<String, RateLimiterType>>,
+    ip_limiters: Arc<DashMap<String, Bucket>>,
     /// Per-API key limiters
-    api_key_limiters: Arc<DashMap<String, RateLimiterType>>,
+    api_key_limiters: Arc<DashMap<String, Bucket>>,
     /// Configuration
     config: Arc<crate::config::RateLimitConfig>,
 }
@@ -42,13 +138,8 @@ pub struct RateLimitStorage {
 impl RateLimitStorage {
     /// Create new rate limit storage
     pub fn new(config: Arc<crate::config::RateLimitConfig>) -> Self {
-        let default_quota = Quota::per_minute(
-            std::num::NonZeroU32::new(config.default_requests_per_minute)
-                .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
-        );
-
         Self {
-            default_limiter: Arc::new(RateLimiter::direct(default_quota)),
+            default_limiter: Bucket::per_minute(config.default_requests_per_minute),
             ip_limiters: Arc::new(DashMap::new()),
             api_key_limiters: Arc::new(DashMap::new()),
             config,
@@ -56,24 +147,15 @@ impl RateLimitStorage {
     }
 
     /// Get or create limiter for IP
-    fn get_ip_limiter(&self, ip: &str) -> Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>> {
+    fn get_ip_limiter(&self, ip: &str) -> Bucket {
         self.ip_limiters
             .entry(ip.to_string())
-            .or_insert_with(|| {
-                let quota = Quota::per_minute(
-                    std::num::NonZeroU32::new(self.config.default_requests_per_minute)
-                        .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
-                );
-                Arc::new(RateLimiter::direct(quota))
-            })
+            .or_insert_with(|| Bucket::per_minute(self.config.default_requests_per_minute))
             .clone()
     }
 
     /// Get or create limiter for API key
-    fn get_api_key_limiter(
-        &self,
-        api_key: &str,
-    ) -> Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>> {
+    fn get_api_key_limiter(&self, api_key: &str) -> Bucket {
         self.api_key_limiters
             .entry(api_key.to_string())
             .or_insert_with(|| {
@@ -88,27 +170,20 @@ impl RateLimitStorage {
                     self.config.default_requests_per_minute
                 };
 
-                let quota = Quota::per_minute(
-                    std::num::NonZeroU32::new(requests_per_minute)
-                        .unwrap_or(std::num::NonZeroU32::new(60).unwrap()),
-                );
-                Arc::new(RateLimiter::direct(quota))
+                Bucket::per_minute(requests_per_minute)
             })
             .clone()
     }
 
-    /// Check rate limit
-    pub async fn check_limit(&self, key: RateLimitKey) -> Result<(), ApiError> {
+    /// Count a request against the caller's bucket
+    pub(super) fn check_limit(&self, key: RateLimitKey) -> RateLimitStatus {
         let limiter = match &key {
             RateLimitKey::Ip(ip) if self.config.per_ip_limiting => self.get_ip_limiter(ip),
             RateLimitKey::ApiKey(api_key) => self.get_api_key_limiter(api_key),
             _ => self.default_limiter.clone(),
         };
 
-        match limiter.check() {
-            Ok(_) => Ok(()),
-            Err(_) => Err(ApiError::RateLimitExceeded),
-        }
+        limiter.check()
     }
 
     /// Clean up old entries periodically
@@ -186,10 +261,10 @@ impl RateLimitMiddleware {
 
 /// Rate limit handler for axum middleware
 pub async fn rate_limit_middleware(
-    storage: Arc<RateLimitStorage>,
+    State(storage): State<Arc<RateLimitStorage>>,
     req: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
+) -> Response {
     // Extract rate limit key
     let key = match req.headers().get("X-API-Key").and_then(|h| h.to_str().ok()) {
         Some(api_key) => RateLimitKey::ApiKey(api_key.to_string()),
@@ -203,9 +278,101 @@ pub async fn rate_limit_middleware(
         }
     };
 
-    // Check rate limit
-    match storage.check_limit(key).await {
-        Ok(_) => Ok(next.run(req).await),
-        Err(_) => Err(StatusCode::TOO_MANY_REQUESTS),
+    let status = storage.check_limit(key);
+    let mut response = if status.is_throttled() {
+        ApiError::RateLimitExceeded.into_response()
+    } else {
+        next.run(req).await
+    };
+    status.apply_headers(response.headers_mut());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RateLimitConfig;
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn storage(requests_per_minute: u32) -> Arc<RateLimitStorage> {
+        Arc::new(RateLimitStorage::new(Arc::new(RateLimitConfig {
+            default_requests_per_minute: requests_per_minute,
+            ..Default::default()
+        })))
+    }
+
+    fn header(response: &Response, name: &str) -> Option<String> {
+        response
+            .headers()
+            .get(name)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_bucket_state_is_reported() {
+        let storage = storage(2);
+        let key = || RateLimitKey::Ip("10.0.0.1".to_string());
+
+        let first = storage.check_limit(key());
+        assert_eq!((first.limit, first.remaining, first.reset), (2, 1, 30));
+        assert!(!first.is_throttled());
+
+        let second = storage.check_limit(key());
+        assert_eq!((second.remaining, second.reset), (0, 60));
+
+        let third = storage.check_limit(key());
+        assert!(third.is_throttled());
+        assert_eq!(third.remaining, 0);
+        let retry_after = third.retry_after.unwrap();
+        assert!((1..=30).contains(&retry_after));
+        assert!(third.reset >= retry_after);
+
+        // Other callers have their own bucket
+        assert_eq!(
+            storage
+                .check_limit(RateLimitKey::Ip("10.0.0.2".to_string()))
+                .remaining,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_headers_on_allowed_and_throttled_responses() {
+        let app = Router::new().route("/", get(|| async { "ok" })).layer(
+            axum::middleware::from_fn_with_state(storage(1), rate_limit_middleware),
+        );
+        let request = || {
+            axum::http::Request::builder()
+                .uri("/")
+                .header("X-API-Key", "sk_other_key")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let allowed = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(allowed.status(), StatusCode::OK);
+        assert_eq!(
+            header(&allowed, RATE_LIMIT_LIMIT_HEADER).as_deref(),
+            Some("1")
+        );
+        assert_eq!(
+            header(&allowed, RATE_LIMIT_REMAINING_HEADER).as_deref(),
+            Some("0")
+        );
+        assert_eq!(
+            header(&allowed, RATE_LIMIT_RESET_HEADER).as_deref(),
+            Some("60")
+        );
+        assert!(header(&allowed, "Retry-After").is_none());
+
+        let throttled = app.oneshot(request()).await.unwrap();
+        assert_eq!(throttled.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            header(&throttled, RATE_LIMIT_REMAINING_HEADER).as_deref(),
+            Some("0")
+        );
+        let retry_after: u64 = header(&throttled, "Retry-After").unwrap().parse().unwrap();
+        assert!((1..=60).contains(&retry_after));
     }
 }
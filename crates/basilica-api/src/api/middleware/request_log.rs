@@ -0,0 +1,265 @@
+//! Structured request logging for debugging
+//!
+//! Each request gets an `http_request` span carrying its method, path,
+//! allowlisted headers and, when enabled, its JSON body, and a log line with
+//! the response status and latency. Credentials never reach the logs:
+//! sensitive headers are redacted even when allowlisted, secret-looking body
+//! fields are redacted, and bodies that are not JSON are not logged at all.
+
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use basilica_common::config::REDACTED;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{field, info, info_span, Instrument, Span};
+
+use crate::config::RequestLogConfig;
+
+/// Headers whose values are always redacted
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+];
+
+/// Body field names containing any of these are redacted, case-insensitively
+const SENSITIVE_FIELDS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "private_key",
+    "authorization",
+    "credential",
+];
+
+/// Log each request and its outcome according to the request log config
+pub async fn request_log_middleware(
+    State(config): State<Arc<RequestLogConfig>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    // The query string is left out, it may carry tokens
+    let span = info_span!(
+        "http_request",
+        method = %req.method(),
+        path = %req.uri().path(),
+        headers = %logged_headers(req.headers(), &config.header_allowlist),
+        body = field::Empty,
+    );
+
+    let req = if config.log_bodies {
+        record_body(req, config.max_body_bytes, &span).await
+    } else {
+        req
+    };
+
+    let started = Instant::now();
+    let response = next.run(req).instrument(span.clone()).await;
+    span.in_scope(|| {
+        info!(
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_millis() as u64,
+            "Request completed"
+        )
+    });
+    response
+}
+
+/// Render the allowlisted headers present on the request
+fn logged_headers(headers: &HeaderMap, allowlist: &[String]) -> String {
+    let mut logged = Vec::new();
+    for name in allowlist {
+        let name = name.to_ascii_lowercase();
+        for value in headers.get_all(name.as_str()) {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED
+            } else {
+                value.to_str().unwrap_or("<non-utf8>")
+            };
+            logged.push(format!("{name}: {value}"));
+        }
+    }
+    logged.join(", ")
+}
+
+/// Buffer a body of known size within `max_bytes` and record it, redacted,
+/// on the span. Larger and streamed bodies pass through unread.
+async fn record_body(req: Request, max_bytes: usize, span: &Span) -> Request {
+    let size = req.body().size_hint().exact();
+    match size {
+        Some(0) => return req,
+        Some(size) if size <= max_bytes as u64 => {}
+        Some(size) => {
+            span.record(
+                "body",
+                field::display(format!("<{size} bytes, not logged>")),
+            );
+            return req;
+        }
+        None => {
+            span.record("body", field::display("<streamed, not logged>"));
+            return req;
+        }
+    }
+
+    let (parts, body) = req.into_parts();
+    let bytes = match to_bytes(body, max_bytes).await {
+        Ok(bytes) => bytes,
+        // The body cannot be replayed, so an unreadable body reaches the
+        // handler empty and is rejected there
+        Err(_) => {
+            span.record("body", field::display("<unreadable>"));
+            return Request::from_parts(parts, Body::empty());
+        }
+    };
+    match serde_json::from_slice::<Value>(&bytes) {
+        Ok(mut json) => {
+            redact(&mut json);
+            span.record("body", field::display(json));
+        }
+        Err(_) => {
+            span.record(
+                "body",
+                field::display(format!("<{} bytes, not JSON>", bytes.len())),
+            );
+        }
+    }
+    Request::from_parts(parts, Body::from(bytes))
+}
+
+/// Replace the values of secret-looking fields with [`REDACTED`]
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, value) in fields.iter_mut() {
+                if is_sensitive_field(name) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn is_sensitive_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_FIELDS.iter().any(|field| name.contains(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, routing::post, Router};
+    use basilica_common::logging::capture::CapturedLogs;
+    use tower::ServiceExt;
+
+    /// Send `body` through the middleware and return the handler's view of
+    /// the body and the captured log output
+    async fn logged_request(config: RequestLogConfig, body: &'static str) -> (String, String) {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = Router::new()
+            .route(
+                "/rentals",
+                post(|body: String| async move { (StatusCode::CREATED, body) }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(config),
+                request_log_middleware,
+            ));
+        let request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/rentals?token=query-secret")
+            .header("authorization", "Bearer sk-live-abc123")
+            .header("content-type", "application/json")
+            .header("x-request-id", "req-1")
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let echoed = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        let output = logs.contents();
+        (String::from_utf8(echoed.to_vec()).unwrap(), output)
+    }
+
+    const BODY: &str = r#"{"image":"pytorch","env":{"HF_TOKEN":"hf_s3cr3t"},"password":"hunter2"}"#;
+
+    #[tokio::test]
+    async fn test_authorization_is_never_logged() {
+        let mut config = RequestLogConfig {
+            enabled: true,
+            log_bodies: true,
+            ..Default::default()
+        };
+        config.header_allowlist.push("Authorization".to_string());
+
+        let (_, output) = logged_request(config, BODY).await;
+        assert!(output.contains("Request completed"), "{output}");
+        assert!(output.contains("status=201"), "{output}");
+        assert!(output.contains("path=/rentals"), "{output}");
+        assert!(output.contains("x-request-id: req-1"), "{output}");
+        assert!(output.contains("authorization: <redacted>"), "{output}");
+        for secret in ["sk-live-abc123", "query-secret", "hf_s3cr3t", "hunter2"] {
+            assert!(!output.contains(secret), "{secret} leaked into: {output}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bodies_are_not_logged_by_default() {
+        let config = RequestLogConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let (echoed, output) = logged_request(config, BODY).await;
+        assert_eq!(echoed, BODY);
+        assert!(output.contains("Request completed"), "{output}");
+        assert!(!output.contains("pytorch"), "{output}");
+    }
+
+    #[tokio::test]
+    async fn test_body_logging_respects_size_cap() {
+        let config = RequestLogConfig {
+            enabled: true,
+            log_bodies: true,
+            ..Default::default()
+        };
+        let (echoed, output) = logged_request(config, BODY).await;
+        // The handler still sees the original body
+        assert_eq!(echoed, BODY);
+        assert!(output.contains(r#""image":"pytorch""#), "{output}");
+        assert!(output.contains(r#""HF_TOKEN":"<redacted>""#), "{output}");
+
+        let config = RequestLogConfig {
+            enabled: true,
+            log_bodies: true,
+            max_body_bytes: 16,
+            ..Default::default()
+        };
+        let (echoed, output) = logged_request(config, BODY).await;
+        assert_eq!(echoed, BODY);
+        assert!(!output.contains("pytorch"), "{output}");
+        assert!(
+            output.contains(&format!("<{} bytes, not logged>", BODY.len())),
+            "{output}"
+        );
+    }
+}
@@ -90,7 +90,9 @@ fn get_required_scope(req: &Request) -> Option<String> {
         (&Method::DELETE, p) if p.starts_with("/rentals/") && !p.contains("/logs") => {
             Some("rentals:stop".to_string())
         }
-        (&Method::GET, p) if p.starts_with("/rentals/") && p.ends_with("/logs") => {
+        (&Method::GET, p)
+            if p.starts_with("/rentals/") && (p.ends_with("/logs") || p.ends_with("/logs/ws")) =>
+        {
             Some("rentals:logs".to_string())
         }
         (&Method::GET, p) if p.starts_with("/rentals/") => Some("rentals:view".to_string()),
@@ -103,6 +105,12 @@ fn get_required_scope(req: &Request) -> Option<String> {
         (&Method::GET, "/api-keys") => Some("keys:list".to_string()),
         (&Method::DELETE, p) if p.starts_with("/api-keys/") => Some("keys:revoke".to_string()),
 
+        // Webhook endpoints
+        (&Method::GET, "/webhook") => Some("webhooks:view".to_string()),
+        (&Method::PUT, "/webhook") | (&Method::DELETE, "/webhook") => {
+            Some("webhooks:manage".to_string())
+        }
+
         // Health check requires authentication but no specific scope
         // We use an empty string to indicate "authenticated but no specific scope required"
         (&Method::GET, "/health") => Some(String::new()),
@@ -155,6 +163,13 @@ mod tests {
             .unwrap();
         assert_eq!(get_required_scope(&req), Some("rentals:logs".to_string()));
 
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/rentals/123/logs/ws")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(get_required_scope(&req), Some("rentals:logs".to_string()));
+
         // Test executor endpoint
         let req = Request::builder()
             .method(Method::GET)
@@ -163,6 +178,26 @@ mod tests {
             .unwrap();
         assert_eq!(get_required_scope(&req), Some("executors:list".to_string()));
 
+        // Test webhook endpoints
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/webhook")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(get_required_scope(&req), Some("webhooks:view".to_string()));
+
+        for method in [Method::PUT, Method::DELETE] {
+            let req = Request::builder()
+                .method(method)
+                .uri("/webhook")
+                .body(Body::empty())
+                .unwrap();
+            assert_eq!(
+                get_required_scope(&req),
+                Some("webhooks:manage".to_string())
+            );
+        }
+
         // Test health endpoint (requires authentication but no specific scope)
         let req = Request::builder()
             .method(Method::GET)
@@ -7,7 +7,7 @@ pub mod routes;
 
 use crate::server::AppState;
 use axum::{
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Router,
 };
 
@@ -15,26 +15,42 @@ use axum::{
 pub fn routes(state: AppState) -> Router<AppState> {
     // Unprotected routes (for health checks, etc.)
     let public_routes = Router::new()
-        // Health endpoint - no authentication required for ALB health checks
-        .route("/health", get(routes::health::health_check));
+        // Health endpoints - no authentication required for ALB and
+        // Kubernetes probes. /livez is liveness; /readyz and /health are
+        // readiness and fail while the validator is unreachable.
+        .route("/livez", get(routes::health::liveness))
+        .route("/readyz", get(routes::health::health_check))
+        .route("/health", get(routes::health::health_check))
+        // Error code catalog for client libraries
+        .route("/errors", get(routes::errors::list_error_codes));
 
-    // Protected routes with unified authentication and scope validation
-    let protected_routes = Router::new()
-        .route("/rentals", get(routes::rentals::list_rentals_validator))
-        .route("/rentals", post(routes::rentals::start_rental))
-        .route("/rentals/:id", get(routes::rentals::get_rental_status))
-        .route("/rentals/:id", delete(routes::rentals::stop_rental))
-        .route(
-            "/rentals/:id/logs",
-            get(routes::rentals::stream_rental_logs),
-        )
-        .route("/executors", get(routes::rentals::list_available_executors))
+    // Protected routes with unified authentication and scope validation.
+    // Route groups switched off in the config are not mounted at all.
+    let features = &state.config.features;
+    let mut protected_routes = Router::new();
+    if features.enable_rentals {
+        protected_routes = protected_routes.merge(rental_routes(&state));
+    }
+    if features.enable_api_keys {
         // API key management endpoints (JWT auth only)
-        .route(
-            "/api-keys",
-            post(routes::api_keys::create_key).get(routes::api_keys::list_keys),
-        )
-        .route("/api-keys/:name", delete(routes::api_keys::revoke_key))
+        protected_routes = protected_routes
+            .route(
+                "/api-keys",
+                post(routes::api_keys::create_key).get(routes::api_keys::list_keys),
+            )
+            .route("/api-keys/:name", delete(routes::api_keys::revoke_key));
+    }
+    if features.enable_webhooks {
+        // Rental lifecycle webhook configuration
+        protected_routes = protected_routes.route(
+            "/webhook",
+            put(routes::webhooks::set_webhook)
+                .get(routes::webhooks::get_webhook)
+                .delete(routes::webhooks::delete_webhook),
+        );
+    }
+
+    let protected_routes = protected_routes
         // Apply scope validation AFTER auth middleware
         .layer(axum::middleware::from_fn(
             middleware::scope_validation_middleware,
@@ -54,3 +70,166 @@ pub fn routes(state: AppState) -> Router<AppState> {
     // Apply general middleware
     middleware::apply_middleware(router, state)
 }
+
+/// Rental lifecycle and executor listing routes
+fn rental_routes(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/rentals", get(routes::rentals::list_rentals_validator))
+        .route(
+            "/rentals",
+            post(routes::rentals::start_rental).layer(axum::middleware::from_fn_with_state(
+                state.idempotency.clone(),
+                middleware::idempotency_middleware,
+            )),
+        )
+        .route("/rentals/:id", get(routes::rentals::get_rental_status))
+        .route("/rentals/:id", delete(routes::rentals::stop_rental))
+        .route(
+            "/rentals/:id/logs",
+            get(routes::rentals::stream_rental_logs),
+        )
+        .route(
+            "/rentals/:id/logs/ws",
+            get(routes::rentals::stream_rental_logs_ws),
+        )
+        .route("/executors", get(routes::rentals::list_available_executors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::middleware::IdempotencyCache, config::Config};
+    use axum::{body::Body, http::Request, http::StatusCode};
+    use basilica_validator::ValidatorClient;
+    use std::{sync::Arc, time::Duration};
+    use tower::ServiceExt;
+
+    fn state(config: Config) -> AppState {
+        AppState {
+            config: Arc::new(config),
+            validator_client: Arc::new(
+                ValidatorClient::new("http://127.0.0.1:1", Duration::from_secs(1)).unwrap(),
+            ),
+            validator_endpoint: "http://127.0.0.1:1".to_string(),
+            validator_uid: 0,
+            validator_hotkey: String::new(),
+            http_client: reqwest::Client::new(),
+            db: sqlx::postgres::PgPoolOptions::new()
+                .connect_lazy("postgres://basilica@127.0.0.1:1/basilica")
+                .unwrap(),
+            idempotency: IdempotencyCache::new(Duration::from_secs(60), 10),
+            rental_costs: None,
+            validator_health: Arc::new(Default::default()),
+        }
+    }
+
+    async fn status_of(config: Config, method: &str, uri: &str) -> StatusCode {
+        status_with_state(state(config), method, uri).await
+    }
+
+    async fn status_with_state(state: AppState, method: &str, uri: &str) -> StatusCode {
+        let app = routes(state.clone()).with_state(state);
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_disabled_route_groups_are_not_mounted() {
+        // Enabled protected routes reject the unauthenticated request
+        let config = Config::default();
+        assert_eq!(
+            status_of(config.clone(), "GET", "/rentals").await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status_of(config, "GET", "/webhook").await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        let mut config = Config::default();
+        config.features.enable_rentals = false;
+        assert_eq!(
+            status_of(config.clone(), "GET", "/rentals").await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status_of(config.clone(), "GET", "/executors").await,
+            StatusCode::NOT_FOUND
+        );
+        // Other groups are unaffected
+        assert_eq!(
+            status_of(config.clone(), "GET", "/api-keys").await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(status_of(config, "GET", "/livez").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_liveness_independent_of_validator() {
+        // The validator has not been reached
+        let state = state(Config::default());
+        assert_eq!(
+            status_with_state(state.clone(), "GET", "/livez").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status_with_state(state.clone(), "GET", "/readyz").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status_with_state(state.clone(), "GET", "/health").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        state.validator_health.set_reachable(true);
+        assert_eq!(
+            status_with_state(state.clone(), "GET", "/readyz").await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status_with_state(state.clone(), "GET", "/health").await,
+            StatusCode::OK
+        );
+
+        // The validator goes down again
+        state.validator_health.set_reachable(false);
+        assert_eq!(
+            status_with_state(state.clone(), "GET", "/readyz").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status_with_state(state, "GET", "/livez").await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_build_info() {
+        let state = state(Config::default());
+        state.validator_health.set_reachable(true);
+        let app = routes(state.clone()).with_state(state);
+        let request = Request::builder()
+            .uri("/health")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let health: basilica_sdk::types::HealthCheckResponse =
+            serde_json::from_slice(&body).unwrap();
+        let build_info = health.build_info.expect("build_info missing");
+        assert_eq!(build_info.version, crate::VERSION);
+        assert_eq!(health.version, crate::VERSION);
+        assert_eq!(build_info.api_version, crate::API_VERSION);
+        assert_eq!(build_info.git_sha, crate::GIT_SHA);
+        assert!(!build_info.build_timestamp.is_empty());
+        assert!(!build_info.rustc_version.is_empty());
+    }
+}
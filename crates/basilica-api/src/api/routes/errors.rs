@@ -0,0 +1,27 @@
+//! Error catalog route handler
+
+use crate::error::{ErrorCatalogEntry, ERROR_CATALOG};
+use axum::Json;
+use serde::Serialize;
+
+/// Error catalog response
+#[derive(Debug, Serialize)]
+pub struct ErrorCatalogResponse {
+    /// API protocol version the catalog applies to
+    pub api_version: &'static str,
+
+    /// Gateway version
+    pub version: &'static str,
+
+    /// Every error code the gateway can emit
+    pub errors: &'static [ErrorCatalogEntry],
+}
+
+/// List every `error.code` with its status, meaning and retryability
+pub async fn list_error_codes() -> Json<ErrorCatalogResponse> {
+    Json(ErrorCatalogResponse {
+        api_version: crate::API_VERSION,
+        version: crate::VERSION,
+        errors: ERROR_CATALOG,
+    })
+}
@@ -1,18 +1,63 @@
-//! Health check route handler
+//! Health check route handlers
+//!
+//! `/livez` only reports that the process is serving requests. `/readyz`, and
+//! `/health` for existing load balancer checks, also require the validator to
+//! have been reachable at the last background check.
 
 use crate::server::AppState;
-use axum::{extract::State, Json};
+use axum::{extract::State, http::StatusCode, Json};
 use basilica_sdk::types::HealthCheckResponse;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Validator reachability as seen by the background health check
+#[derive(Debug, Default)]
+pub struct ValidatorHealth {
+    reachable: AtomicBool,
+}
+
+impl ValidatorHealth {
+    /// Record the outcome of a validator health check
+    pub fn set_reachable(&self, reachable: bool) {
+        self.reachable.store(reachable, Ordering::Relaxed);
+    }
+
+    /// Whether the last check reached the validator; false until the first
+    /// check completes
+    pub fn is_reachable(&self) -> bool {
+        self.reachable.load(Ordering::Relaxed)
+    }
+}
+
+/// Liveness probe: succeeds whenever the process is serving requests
+pub async fn liveness() -> Json<Value> {
+    Json(json!({
+        "status": "alive",
+        "version": crate::VERSION,
+    }))
+}
+
+/// Readiness probe: 503 while the validator is unreachable
+pub async fn health_check(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<HealthCheckResponse>) {
+    let ready = state.validator_health.is_reachable();
+    let (status, label) = if ready {
+        (StatusCode::OK, "healthy")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unhealthy")
+    };
 
-/// Health check endpoint
-pub async fn health_check(State(_state): State<AppState>) -> Json<HealthCheckResponse> {
     // We always have one configured validator
-    // Health status is monitored in background but doesn't affect API availability
-    Json(HealthCheckResponse {
-        status: "healthy".to_string(),
-        version: crate::VERSION.to_string(),
-        timestamp: chrono::Utc::now(),
-        healthy_validators: 1,
-        total_validators: 1,
-    })
+    (
+        status,
+        Json(HealthCheckResponse {
+            status: label.to_string(),
+            version: crate::VERSION.to_string(),
+            timestamp: chrono::Utc::now(),
+            healthy_validators: usize::from(ready),
+            total_validators: 1,
+            build_info: Some(crate::build_info()),
+        }),
+    )
 }
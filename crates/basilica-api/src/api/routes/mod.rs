@@ -1,5 +1,7 @@
 //! API route handlers
 
 pub mod api_keys;
+pub mod errors;
 pub mod health;
 pub mod rentals;
+pub mod webhooks;
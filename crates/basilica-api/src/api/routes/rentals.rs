@@ -3,35 +3,47 @@
 use crate::{
     api::{
         extractors::ownership::{
-            archive_rental_ownership, get_user_rentals_with_ssh, store_rental_ownership,
-            OwnedRental,
+            archive_rental_ownership, find_executor_gpus, get_user_rentals_with_ssh,
+            record_rental_executor, store_rental_ownership, ExecutorGpus, OwnedRental,
         },
         middleware::AuthContext,
     },
     country_mapping::normalize_country_code,
     error::Result,
     server::AppState,
+    webhooks::{self, WebhookEvent},
 };
 use axum::{
-    extract::{Query, State},
-    http::Uri,
+    body::Body,
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::{header, HeaderMap, Uri},
     response::{sse::Event, IntoResponse, Response, Sse},
     Json,
 };
 use basilica_common::utils::validate_docker_image;
+use basilica_sdk::client::NDJSON_CONTENT_TYPE;
 use basilica_sdk::types::{
-    ApiListRentalsResponse, ApiRentalListItem, ExecutorSelection, ListRentalsQuery, LogStreamQuery,
-    RentalStatusWithSshResponse, StartRentalApiRequest, TerminateRentalRequest,
+    ApiListRentalsResponse, ApiRentalListItem, ExecutorSelection, ListRentalsQuery, LogFormat,
+    LogStreamQuery, RentalLogLine, RentalStatusWithSshResponse, StartRentalApiRequest,
+    TerminateRentalRequest,
 };
 use basilica_validator::{
     api::{
         rental_routes::StartRentalRequest,
-        types::{AvailableExecutor, ListAvailableExecutorsQuery, ListAvailableExecutorsResponse},
+        types::{
+            AvailableExecutor, ExecutorDetails, ListAvailableExecutorsQuery,
+            ListAvailableExecutorsResponse,
+        },
     },
+    rental::RentalState,
     RentalResponse,
 };
-use futures::stream::Stream;
+use futures::stream::{Stream, StreamExt};
 use rand::seq::SliceRandom;
+use std::time::Duration;
 use tracing::{debug, error, info};
 
 /// Get detailed rental status (with ownership validation)
@@ -45,10 +57,16 @@ pub async fn get_rental_status(
     let validator_response = client.get_rental_status(&owned_rental.rental_id).await?;
 
     // Create extended response with SSH credentials from database
-    let response_with_ssh = RentalStatusWithSshResponse::from_validator_response(
+    let mut response_with_ssh = RentalStatusWithSshResponse::from_validator_response(
         validator_response,
         owned_rental.ssh_credentials,
     );
+    if let Some(rental_costs) = &state.rental_costs {
+        if let Some(usage) = rental_costs.usage_so_far(&owned_rental.rental_id).await {
+            response_with_ssh.cost_so_far = usage.cost;
+            response_with_ssh.energy_kwh = usage.energy_kwh;
+        }
+    }
 
     Ok(Json(response_with_ssh))
 }
@@ -81,10 +99,14 @@ pub async fn start_rental(
     }
 
     // Determine executor_id based on the selection strategy
-    let executor_id = match &request.executor_selection {
+    let selected = match &request.executor_selection {
         ExecutorSelection::ExecutorId { executor_id } => {
             info!("Starting rental with specified executor: {}", executor_id);
-            executor_id.clone()
+            SelectedExecutor {
+                id: executor_id.clone(),
+                gpus: None,
+                pin_honored: None,
+            }
         }
         ExecutorSelection::GpuRequirements { gpu_requirements } => {
             info!(
@@ -98,6 +120,8 @@ pub async fn start_rental(
                 min_gpu_memory: Some(gpu_requirements.min_memory_gb),
                 gpu_type: gpu_requirements.gpu_type.clone(),
                 min_gpu_count: Some(gpu_requirements.gpu_count),
+                mig_profile: gpu_requirements.mig_profile.clone(),
+                max_staleness: None,
                 location: None,
             };
 
@@ -117,37 +141,103 @@ pub async fn start_rental(
             }
 
             // Randomly select an executor from those matching GPU requirements
-            let selected_id = select_best_executor(executors_response.available_executors)
+            let selected = select_best_executor(&executors_response.available_executors)
                 .ok_or_else(|| crate::error::ApiError::Internal {
                     message: "Failed to select executor".into(),
                 })?;
 
             info!(
                 "Randomly selected executor {} from available executors matching GPU requirements",
-                selected_id
+                selected.id
             );
-            selected_id
+            SelectedExecutor {
+                id: selected.id.clone(),
+                gpus: ExecutorGpus::of(selected),
+                pin_honored: None,
+            }
+        }
+        ExecutorSelection::Pinned {
+            executor_id,
+            require_exact,
+        } => {
+            info!(
+                "Starting rental pinned to executor {} (exact: {})",
+                executor_id, require_exact
+            );
+
+            let query = ListAvailableExecutorsQuery {
+                available: Some(true),
+                min_gpu_memory: None,
+                gpu_type: None,
+                min_gpu_count: None,
+                mig_profile: None,
+                max_staleness: None,
+                location: None,
+            };
+
+            let available = state
+                .validator_client
+                .list_available_executors(Some(query))
+                .await
+                .map_err(|e| crate::error::ApiError::Internal {
+                    message: format!("Failed to query available executors: {}", e),
+                })?
+                .available_executors;
+
+            // The pinned executor's GPUs are only needed to find a stand-in
+            let pinned_gpus =
+                if *require_exact || available.iter().any(|e| &e.executor.id == executor_id) {
+                    None
+                } else {
+                    find_executor_gpus(&state.db, executor_id)
+                        .await
+                        .map_err(|e| crate::error::ApiError::Internal {
+                            message: format!("Failed to look up pinned executor: {}", e),
+                        })?
+                };
+
+            let selected =
+                select_pinned_executor(executor_id, *require_exact, &available, pinned_gpus)?;
+            if selected.pin_honored == Some(false) {
+                info!(
+                    "Pinned executor {} is unavailable, falling back to {}",
+                    executor_id, selected.id
+                );
+            }
+            selected
         }
     };
 
     // Convert to validator's StartRentalRequest format
     let validator_request = StartRentalRequest {
-        executor_id,
+        executor_id: selected.id.clone(),
         container_image: request.container_image,
         ssh_public_key: request.ssh_public_key,
         environment: request.environment,
+        secrets: request.secrets,
+        secrets_owner: Some(user_id.clone()),
+        registry_auth: request.registry_auth,
         ports: request.ports,
         resources: request.resources,
         command: request.command,
         volumes: request.volumes,
         no_ssh: request.no_ssh,
+        idempotency_key: None,
+        idle_timeout_secs: request.idle_timeout_secs,
+        health_policy: None,
+        pre_stop_command: request.pre_stop_command,
+        pre_stop_timeout_secs: request.pre_stop_timeout_secs,
+        runtime: Default::default(),
+        gpu_device_ids: Vec::new(),
     };
     debug!("Starting rental with request: {:?}", validator_request);
 
-    let validator_response = state
+    let mut validator_response = state
         .validator_client
         .start_rental(validator_request)
         .await?;
+    validator_response.executor_id = selected.id.clone();
+    validator_response.pin_honored = selected.pin_honored;
 
     // Store ownership record in database with SSH credentials
     if let Err(e) = store_rental_ownership(
@@ -155,6 +245,7 @@ pub async fn start_rental(
         &validator_response.rental_id,
         user_id,
         validator_response.ssh_credentials.as_deref(),
+        request.log_format,
     )
     .await
     {
@@ -190,6 +281,21 @@ pub async fn start_rental(
         });
     }
 
+    // Losing the executor record only prevents falling back from a later pin
+    if let Err(e) = record_rental_executor(
+        &state.db,
+        &validator_response.rental_id,
+        &selected.id,
+        selected.gpus.as_ref(),
+    )
+    .await
+    {
+        error!(
+            "Failed to record executor for rental {}: {}",
+            validator_response.rental_id, e
+        );
+    }
+
     info!(
         "User {} started rental {}",
         user_id, validator_response.rental_id
@@ -199,9 +305,12 @@ pub async fn start_rental(
 }
 
 /// Stop a rental (with ownership validation)
+///
+/// An optional `reason` query parameter is recorded with the termination.
 pub async fn stop_rental(
     State(state): State<AppState>,
     owned_rental: OwnedRental,
+    Query(query): Query<TerminateRentalRequest>,
 ) -> Result<Response> {
     info!(
         "User {} stopping rental {}",
@@ -210,7 +319,12 @@ pub async fn stop_rental(
 
     // Use terminate_rental API from validator
     let request = TerminateRentalRequest {
-        reason: Some("User requested stop".to_string()),
+        reason: Some(
+            query
+                .reason
+                .filter(|reason| !reason.trim().is_empty())
+                .unwrap_or_else(|| "User requested stop".to_string()),
+        ),
     };
 
     state
@@ -218,6 +332,19 @@ pub async fn stop_rental(
         .terminate_rental(&owned_rental.rental_id, request.clone())
         .await?;
 
+    // Notify before archiving: the state watcher stops tracking archived rentals
+    if let Some(event) = WebhookEvent::for_transition(
+        &owned_rental.rental_id,
+        RentalState::Stopped,
+        request.reason.clone(),
+    ) {
+        if let Err(e) =
+            webhooks::store::enqueue_event(&state.db, &owned_rental.user_id, &event).await
+        {
+            error!("Failed to enqueue rental stopped webhook: {}", e);
+        }
+    }
+
     // Archive ownership record to terminated_user_rentals table
     if let Err(e) = archive_rental_ownership(
         &state.db,
@@ -233,24 +360,54 @@ pub async fn stop_rental(
     Ok(axum::http::StatusCode::NO_CONTENT.into_response())
 }
 
+/// How often an idle log WebSocket is pinged to keep proxies from closing it
+const LOG_WS_PING_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Stream rental logs (with ownership validation)
 pub async fn stream_rental_logs(
     State(state): State<AppState>,
     owned_rental: OwnedRental,
     Query(query): Query<LogStreamQuery>,
 ) -> Result<Sse<impl Stream<Item = std::result::Result<Event, std::io::Error>>>> {
+    let frames = rental_log_frames(&state, &owned_rental, query).await?;
+    Ok(Sse::new(
+        frames.map(|frame| Ok(Event::default().data(frame.to_string()))),
+    ))
+}
+
+/// Stream rental logs over a WebSocket (with ownership validation)
+///
+/// Sends the same JSON frames as [`stream_rental_logs`], one text message
+/// each, for clients behind proxies that break long-lived SSE responses.
+pub async fn stream_rental_logs_ws(
+    State(state): State<AppState>,
+    owned_rental: OwnedRental,
+    Query(query): Query<LogStreamQuery>,
+    ws: WebSocketUpgrade,
+) -> Result<Response> {
+    // Open the validator stream first so failures are reported before the upgrade
+    let frames = rental_log_frames(&state, &owned_rental, query).await?;
+    Ok(ws.on_upgrade(move |socket| forward_log_frames(socket, frames)))
+}
+
+/// Open the validator's log stream for a rental as JSON log frames
+///
+/// A stream error is sent as a final frame on the `error` stream.
+async fn rental_log_frames(
+    state: &AppState,
+    owned_rental: &OwnedRental,
+    query: LogStreamQuery,
+) -> Result<impl Stream<Item = serde_json::Value>> {
     info!(
         "User {} streaming logs for rental {}",
         owned_rental.user_id, owned_rental.rental_id
     );
 
-    let follow = query.follow.unwrap_or(false);
-    let tail_lines = query.tail;
-
     // Create query parameters for validator
     let log_query = basilica_validator::api::types::LogQuery {
-        follow: Some(follow),
-        tail: tail_lines,
+        follow: Some(query.follow.unwrap_or(false)),
+        tail: query.tail,
+        since: query.since,
     };
 
     // Get SSE stream from validator
@@ -265,39 +422,80 @@ pub async fn stream_rental_logs(
             }
         })?;
 
-    // Convert validator Event stream to axum SSE Events
-    let stream = async_stream::stream! {
-        use futures::StreamExt;
+    // Convert validator events to log frames
+    let log_format = owned_rental.log_format;
+    Ok(async_stream::stream! {
         futures::pin_mut!(validator_stream);
 
         while let Some(result) = validator_stream.next().await {
             match result {
                 Ok(event) => {
-                    // Convert validator Event to SSE data
-                    let data = serde_json::json!({
-                        "timestamp": event.timestamp,
-                        "stream": event.stream,
-                        "message": event.message,
-                    });
-
-                    yield Ok(Event::default().data(data.to_string()));
+                    let line = RentalLogLine {
+                        timestamp: event.timestamp,
+                        stream: event.stream,
+                        message: event.message,
+                        level: None,
+                    };
+                    let line = match log_format {
+                        LogFormat::Json => line.parse_json(),
+                        LogFormat::Plain => line,
+                    };
+                    yield serde_json::to_value(line).unwrap_or_default();
                 }
                 Err(e) => {
                     error!("Error in log stream: {}", e);
-                    // Send error as an SSE event
-                    let data = serde_json::json!({
+                    yield serde_json::json!({
                         "timestamp": chrono::Utc::now(),
                         "stream": "error",
                         "message": format!("Stream error: {}", e),
                     });
-                    yield Ok(Event::default().data(data.to_string()));
                     break;
                 }
             }
         }
-    };
+    })
+}
 
-    Ok(Sse::new(stream))
+/// Send log frames over a WebSocket until the logs end or the client leaves
+///
+/// The socket is pinged while idle and closed normally once the logs end.
+async fn forward_log_frames(
+    mut socket: WebSocket,
+    frames: impl Stream<Item = serde_json::Value> + Send,
+) {
+    futures::pin_mut!(frames);
+    let mut keepalive = tokio::time::interval(LOG_WS_PING_INTERVAL);
+    keepalive.tick().await;
+
+    loop {
+        tokio::select! {
+            frame = frames.next() => {
+                let Some(frame) = frame else {
+                    let _ = socket
+                        .send(Message::Close(Some(CloseFrame {
+                            code: close_code::NORMAL,
+                            reason: "end of logs".into(),
+                        })))
+                        .await;
+                    return;
+                };
+                if socket.send(Message::Text(frame.to_string())).await.is_err() {
+                    debug!("Log WebSocket client went away");
+                    return;
+                }
+            }
+            message = socket.recv() => match message {
+                // Pongs and other client messages need no reply
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+            _ = keepalive.tick() => {
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
 }
 
 /// List rentals with state filter (validator-compatible)
@@ -398,11 +596,15 @@ fn is_valid_ssh_public_key(key: &str) -> bool {
 }
 
 /// List available executors for rentals
+///
+/// Clients sending `Accept: application/x-ndjson` get one executor per line
+/// instead of a single JSON document.
 pub async fn list_available_executors(
     State(state): State<AppState>,
     Query(mut query): Query<ListAvailableExecutorsQuery>,
     uri: Uri,
-) -> Result<Json<ListAvailableExecutorsResponse>> {
+    headers: HeaderMap,
+) -> Result<Response> {
     // Default to available=true for /executors endpoint
     if query.available.is_none() && uri.path() == "/executors" {
         query.available = Some(true);
@@ -417,22 +619,300 @@ pub async fn list_available_executors(
 
     info!("Listing executors with filters: {:?}", query);
 
-    let response = state
+    let mut response = state
         .validator_client
         .list_available_executors(Some(query))
         .await?;
 
-    Ok(Json(response))
+    if let Some(billing) = &state.rental_costs {
+        if let Some(prices) = billing.gpu_prices().await {
+            prices.apply(&mut response.available_executors);
+        }
+    }
+
+    if accepts_ndjson(&headers) {
+        return Ok(ndjson_response(response.available_executors));
+    }
+    Ok(Json::<ListAvailableExecutorsResponse>(response).into_response())
+}
+
+fn accepts_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_type| {
+            media_type
+                .split(';')
+                .next()
+                .is_some_and(|t| t.trim().eq_ignore_ascii_case(NDJSON_CONTENT_TYPE))
+        })
+}
+
+/// Stream `items` as newline-delimited JSON, serializing each one as the
+/// body is sent rather than the whole list up front
+fn ndjson_response<T: serde::Serialize + Send + 'static>(items: Vec<T>) -> Response {
+    let lines = futures::stream::iter(items).map(|item| {
+        serde_json::to_vec(&item).map(|mut line| {
+            line.push(b'\n');
+            line
+        })
+    });
+    (
+        [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(lines),
+    )
+        .into_response()
 }
 
 /// Select a random executor from a list of available executors to distribute
 /// load and allow users to retry with different executors if issues occur
-fn select_best_executor(executors: Vec<AvailableExecutor>) -> Option<String> {
+fn select_best_executor(executors: &[AvailableExecutor]) -> Option<&ExecutorDetails> {
     if executors.is_empty() {
         return None;
     }
 
     // Randomly select an executor from the available list
     let mut rng = rand::thread_rng();
-    executors.choose(&mut rng).map(|e| e.executor.id.clone())
+    executors.choose(&mut rng).map(|e| &e.executor)
+}
+
+/// Executor chosen for a new rental
+#[derive(Debug)]
+struct SelectedExecutor {
+    id: String,
+    /// GPUs of the executor, when the selection listed it
+    gpus: Option<ExecutorGpus>,
+    /// Whether the pinned executor was used, for pinned selections
+    pin_honored: Option<bool>,
+}
+
+/// Choose the executor for a pinned selection from the available executors
+///
+/// `pinned_gpus` describes the pinned executor when it is unavailable; without
+/// it no equivalent executor can be found.
+fn select_pinned_executor(
+    executor_id: &str,
+    require_exact: bool,
+    available: &[AvailableExecutor],
+    pinned_gpus: Option<ExecutorGpus>,
+) -> Result<SelectedExecutor> {
+    if let Some(pinned) = available.iter().find(|e| e.executor.id == executor_id) {
+        return Ok(SelectedExecutor {
+            id: executor_id.to_string(),
+            gpus: ExecutorGpus::of(&pinned.executor),
+            pin_honored: Some(true),
+        });
+    }
+
+    if require_exact {
+        return Err(crate::error::ApiError::Conflict {
+            message: format!("Pinned executor {} is not available", executor_id),
+        });
+    }
+
+    let Some(pinned_gpus) = pinned_gpus else {
+        return Err(crate::error::ApiError::NotFound {
+            message: format!(
+                "executor equivalent to {} (its GPUs were never recorded)",
+                executor_id
+            ),
+        });
+    };
+
+    let equivalent: Vec<&ExecutorDetails> = available
+        .iter()
+        .map(|e| &e.executor)
+        .filter(|e| ExecutorGpus::of(e).as_ref() == Some(&pinned_gpus))
+        .collect();
+    let fallback = equivalent.choose(&mut rand::thread_rng()).ok_or_else(|| {
+        crate::error::ApiError::NotFound {
+            message: format!(
+                "executor with {}x {} equivalent to {}",
+                pinned_gpus.gpu_count, pinned_gpus.gpu_type, executor_id
+            ),
+        }
+    })?;
+
+    Ok(SelectedExecutor {
+        id: fallback.id.clone(),
+        gpus: Some(pinned_gpus),
+        pin_honored: Some(false),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tokio_tungstenite::tungstenite::{self, protocol::frame::coding::CloseCode};
+
+    #[tokio::test]
+    async fn test_log_frames_are_delivered_over_websocket() {
+        let frames = vec![
+            serde_json::json!({
+                "timestamp": "2024-01-01T00:00:00Z",
+                "stream": "stdout",
+                "message": "epoch 1",
+            }),
+            serde_json::json!({
+                "timestamp": "2024-01-01T00:00:01Z",
+                "stream": "stderr",
+                "message": "warning: low memory",
+            }),
+        ];
+        let served = frames.clone();
+        let app = Router::new().route(
+            "/logs/ws",
+            get(move |ws: WebSocketUpgrade| async move {
+                ws.on_upgrade(move |socket| {
+                    forward_log_frames(socket, futures::stream::iter(served))
+                })
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/logs/ws"))
+            .await
+            .unwrap();
+        let mut received = Vec::new();
+        let mut close_code = None;
+        while let Some(message) = socket.next().await {
+            match message.unwrap() {
+                tungstenite::Message::Text(text) => {
+                    received.push(serde_json::from_str::<serde_json::Value>(&text).unwrap())
+                }
+                tungstenite::Message::Close(frame) => {
+                    close_code = frame.map(|frame| frame.code);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        assert_eq!(received, frames);
+        assert_eq!(close_code, Some(CloseCode::Normal));
+    }
+
+    #[test]
+    fn test_accepts_ndjson() {
+        let accept = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, value.parse().unwrap());
+            accepts_ndjson(&headers)
+        };
+        assert!(accept("application/x-ndjson"));
+        assert!(accept("application/json;q=0.5, application/x-ndjson"));
+        assert!(!accept("application/json"));
+        assert!(!accept("*/*"));
+        assert!(!accepts_ndjson(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_response_has_one_object_per_line() {
+        let items = vec![
+            serde_json::json!({"id": "exec-1"}),
+            serde_json::json!({"id": "exec-2"}),
+        ];
+        let response = ndjson_response(items.clone());
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            NDJSON_CONTENT_TYPE
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let lines: Vec<serde_json::Value> = std::str::from_utf8(&body)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, items);
+    }
+
+    fn executor(id: &str, gpu: &str, count: usize) -> AvailableExecutor {
+        serde_json::from_value(serde_json::json!({
+            "executor": {
+                "id": id,
+                "gpu_specs": vec![serde_json::json!({
+                    "name": gpu,
+                    "memory_gb": 80,
+                    "compute_capability": "9.0",
+                }); count],
+                "cpu_specs": { "cores": 32, "model": "EPYC", "memory_gb": 256 },
+                "location": null,
+            },
+            "availability": {
+                "available_until": null,
+                "verification_score": 1.0,
+                "uptime_percentage": 100.0,
+            },
+        }))
+        .unwrap()
+    }
+
+    fn h100x2() -> ExecutorGpus {
+        ExecutorGpus {
+            gpu_type: "NVIDIA H100 80GB HBM3".to_string(),
+            gpu_count: 2,
+            gpu_memory_gb: 80,
+        }
+    }
+
+    #[test]
+    fn test_pinned_executor_is_used_when_available() {
+        let available = vec![
+            executor("miner1__a", "NVIDIA H100 80GB HBM3", 2),
+            executor("miner2__b", "NVIDIA H100 80GB HBM3", 2),
+        ];
+
+        let selected = select_pinned_executor("miner2__b", false, &available, None).unwrap();
+        assert_eq!(selected.id, "miner2__b");
+        assert_eq!(selected.pin_honored, Some(true));
+        assert_eq!(selected.gpus, Some(h100x2()));
+    }
+
+    #[test]
+    fn test_unavailable_pin_falls_back_to_equivalent_executor() {
+        let available = vec![
+            executor("miner1__a100", "NVIDIA A100-SXM4-80GB", 2),
+            executor("miner1__single", "NVIDIA H100 80GB HBM3", 1),
+            executor("miner2__b", "NVIDIA H100 80GB HBM3", 2),
+        ];
+
+        let selected =
+            select_pinned_executor("miner3__gone", false, &available, Some(h100x2())).unwrap();
+        assert_eq!(selected.id, "miner2__b");
+        assert_eq!(selected.pin_honored, Some(false));
+
+        // Nothing to fall back to without knowing the pinned executor's GPUs
+        assert!(matches!(
+            select_pinned_executor("miner3__gone", false, &available, None),
+            Err(crate::error::ApiError::NotFound { .. })
+        ));
+        assert!(matches!(
+            select_pinned_executor("miner3__gone", false, &available[..2], Some(h100x2())),
+            Err(crate::error::ApiError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unavailable_exact_pin_fails() {
+        let available = vec![executor("miner2__b", "NVIDIA H100 80GB HBM3", 2)];
+
+        let result = select_pinned_executor("miner3__gone", true, &available, Some(h100x2()));
+        match result {
+            Err(crate::error::ApiError::Conflict { message }) => {
+                assert!(
+                    message.contains("miner3__gone"),
+                    "unexpected message: {message}"
+                )
+            }
+            other => panic!("expected conflict, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,112 @@
+//! Webhook configuration route handlers
+
+use crate::{
+    api::middleware::AuthContext,
+    error::{ApiError, Result},
+    server::AppState,
+    webhooks::{self, store},
+};
+use axum::{extract::State, http::StatusCode, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Request to register or replace the webhook endpoint
+#[derive(Debug, Deserialize)]
+pub struct SetWebhookRequest {
+    /// HTTPS URL that receives rental lifecycle events
+    pub url: String,
+}
+
+/// Registered webhook endpoint
+#[derive(Debug, Serialize)]
+pub struct WebhookResponse {
+    /// HTTPS URL that receives rental lifecycle events
+    pub url: String,
+
+    /// Signing secret (only returned when the webhook is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+
+    /// Creation timestamp
+    pub created_at: chrono::DateTime<chrono::Utc>,
+
+    /// Last update timestamp
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Register or replace the webhook endpoint
+///
+/// Every call generates a new signing secret, returned only in this response.
+#[instrument(skip(state, auth_context, request), fields(user_id = %auth_context.user_id))]
+pub async fn set_webhook(
+    State(state): State<AppState>,
+    axum::Extension(auth_context): axum::Extension<AuthContext>,
+    Json(request): Json<SetWebhookRequest>,
+) -> Result<Json<WebhookResponse>> {
+    webhooks::validate_url(&request.url).map_err(|message| ApiError::BadRequest { message })?;
+    webhooks::resolve_url_host(&request.url)
+        .await
+        .map_err(|message| ApiError::BadRequest { message })?;
+
+    let secret = webhooks::generate_secret();
+    let webhook = store::upsert_webhook(&state.db, &auth_context.user_id, &request.url, &secret)
+        .await
+        .map_err(|e| ApiError::Internal {
+            message: format!("Failed to store webhook: {}", e),
+        })?;
+
+    info!("Webhook endpoint set");
+
+    Ok(Json(WebhookResponse {
+        url: webhook.url,
+        secret: Some(webhook.secret),
+        created_at: webhook.created_at,
+        updated_at: webhook.updated_at,
+    }))
+}
+
+/// Get the registered webhook endpoint
+#[instrument(skip(state, auth_context), fields(user_id = %auth_context.user_id))]
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    axum::Extension(auth_context): axum::Extension<AuthContext>,
+) -> Result<Json<WebhookResponse>> {
+    let webhook = store::get_webhook(&state.db, &auth_context.user_id)
+        .await
+        .map_err(|e| ApiError::Internal {
+            message: format!("Failed to get webhook: {}", e),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "webhook".into(),
+        })?;
+
+    Ok(Json(WebhookResponse {
+        url: webhook.url,
+        secret: None,
+        created_at: webhook.created_at,
+        updated_at: webhook.updated_at,
+    }))
+}
+
+/// Remove the webhook endpoint and drop its pending deliveries
+#[instrument(skip(state, auth_context), fields(user_id = %auth_context.user_id))]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    axum::Extension(auth_context): axum::Extension<AuthContext>,
+) -> Result<StatusCode> {
+    let deleted = store::delete_webhook(&state.db, &auth_context.user_id)
+        .await
+        .map_err(|e| ApiError::Internal {
+            message: format!("Failed to delete webhook: {}", e),
+        })?;
+
+    if !deleted {
+        return Err(ApiError::NotFound {
+            message: "webhook".into(),
+        });
+    }
+
+    info!("Webhook endpoint removed");
+
+    Ok(StatusCode::NO_CONTENT)
+}
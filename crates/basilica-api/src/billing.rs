@@ -0,0 +1,355 @@
+//! Accrued rental cost, energy and executor prices from the billing service
+//!
+//! Rental status requests are frequent, so each rental's usage is cached for
+//! a short while and lookups that fail are cached too; a slow or unavailable
+//! billing service only means the status comes back without a cost. Package
+//! prices are cached the same way, so executor listings follow package rate
+//! changes within the price cache TTL.
+
+use crate::config::BillingConfig;
+use basilica_protocol::billing::{
+    billing_service_client::BillingServiceClient, BillingPackage, GetBillingPackagesRequest,
+    RentalCostBreakdown, UsageReportRequest, UsageReportResponse,
+};
+use basilica_sdk::types::{AvailableExecutor, CostBreakdown, ExecutorDetails, Money};
+use moka::future::Cache;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::transport::{Channel, Endpoint};
+use tracing::warn;
+
+/// Currency billing package rates are quoted in
+pub const PRICE_CURRENCY: &str = "USD";
+
+/// Cost and energy a rental has accrued so far
+#[derive(Debug, Clone, Default)]
+pub struct RentalUsage {
+    pub cost: Option<CostBreakdown>,
+    /// GPU energy in kWh, when the executor reports it
+    pub energy_kwh: Option<f64>,
+}
+
+impl From<UsageReportResponse> for RentalUsage {
+    fn from(report: UsageReportResponse) -> Self {
+        Self {
+            cost: report.cost_breakdown.and_then(cost_breakdown),
+            energy_kwh: report
+                .summary
+                .map(|summary| summary.energy_kwh)
+                .filter(|kwh| *kwh > 0.0),
+        }
+    }
+}
+
+/// Looks up the cost a rental has accrued so far and the current GPU prices
+#[derive(Clone)]
+pub struct RentalCostClient {
+    client: BillingServiceClient<Channel>,
+    costs: Cache<String, Option<RentalUsage>>,
+    prices: Cache<(), Option<Arc<GpuPriceList>>>,
+}
+
+impl RentalCostClient {
+    /// Create a client for the configured billing endpoint, or `None` when
+    /// no endpoint is configured
+    pub fn from_config(config: &BillingConfig) -> Result<Option<Self>, tonic::transport::Error> {
+        let Some(endpoint) = &config.endpoint else {
+            return Ok(None);
+        };
+        let channel = Endpoint::from_shared(endpoint.clone())?
+            .timeout(Duration::from_secs(config.request_timeout))
+            .connect_lazy();
+        Ok(Some(Self {
+            client: BillingServiceClient::new(channel),
+            costs: Cache::builder()
+                .time_to_live(Duration::from_secs(config.cost_cache_ttl))
+                .max_capacity(10_000)
+                .build(),
+            prices: Cache::builder()
+                .time_to_live(Duration::from_secs(config.price_cache_ttl))
+                .max_capacity(1)
+                .build(),
+        }))
+    }
+
+    /// Cost accrued by `rental_id`, if billing knows the rental
+    pub async fn cost_so_far(&self, rental_id: &str) -> Option<CostBreakdown> {
+        self.usage_so_far(rental_id).await?.cost
+    }
+
+    /// Cost and energy accrued by `rental_id`, if billing knows the rental
+    pub async fn usage_so_far(&self, rental_id: &str) -> Option<RentalUsage> {
+        self.costs
+            .get_with(rental_id.to_string(), self.fetch(rental_id))
+            .await
+    }
+
+    async fn fetch(&self, rental_id: &str) -> Option<RentalUsage> {
+        let request = UsageReportRequest {
+            rental_id: billing_rental_id(rental_id).to_string(),
+            ..Default::default()
+        };
+        match self.client.clone().get_usage_report(request).await {
+            Ok(response) => Some(response.into_inner().into()),
+            Err(status) => {
+                warn!(
+                    "Failed to get cost of rental {} from billing: {}",
+                    rental_id,
+                    status.message()
+                );
+                None
+            }
+        }
+    }
+}
+
+impl RentalCostClient {
+    /// Hourly GPU rates of the billing packages, if billing can be reached
+    pub async fn gpu_prices(&self) -> Option<Arc<GpuPriceList>> {
+        self.prices.get_with((), self.fetch_prices()).await
+    }
+
+    async fn fetch_prices(&self) -> Option<Arc<GpuPriceList>> {
+        let request = GetBillingPackagesRequest::default();
+        match self.client.clone().get_billing_packages(request).await {
+            Ok(response) => Some(Arc::new(GpuPriceList::from_packages(
+                &response.into_inner().packages,
+            ))),
+            Err(status) => {
+                warn!(
+                    "Failed to get billing packages for executor prices: {}",
+                    status.message()
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Hourly rate of a single GPU model
+#[derive(Debug, Clone)]
+struct GpuRate {
+    gpu_model: String,
+    per_gpu_hour: Decimal,
+    priority: u32,
+}
+
+/// Hourly GPU rates of the active billing packages
+#[derive(Debug, Clone, Default)]
+pub struct GpuPriceList {
+    rates: Vec<GpuRate>,
+}
+
+impl GpuPriceList {
+    pub fn from_packages(packages: &[BillingPackage]) -> Self {
+        let rates = packages
+            .iter()
+            .filter(|package| package.is_active)
+            .filter_map(|package| Some((package, package.rates.as_ref()?)))
+            .flat_map(|(package, rates)| {
+                rates.gpu_rates.iter().filter_map(|(gpu_model, rate)| {
+                    if gpu_model.is_empty() {
+                        return None;
+                    }
+                    Some(GpuRate {
+                        gpu_model: gpu_model.to_lowercase(),
+                        per_gpu_hour: Decimal::from_str(rate).ok()?,
+                        priority: package.priority,
+                    })
+                })
+            })
+            .collect();
+        Self { rates }
+    }
+
+    /// Rate for one GPU of `gpu_model`, matched the way billing picks the
+    /// package for a rental: an exact model match first, then the
+    /// highest-priority package whose model names overlap
+    pub fn rate_for(&self, gpu_model: &str) -> Option<Decimal> {
+        let gpu_model = gpu_model.to_lowercase();
+        let best = |matches: &dyn Fn(&GpuRate) -> bool| {
+            self.rates
+                .iter()
+                .filter(|rate| matches(rate))
+                .max_by_key(|rate| rate.priority)
+                .map(|rate| rate.per_gpu_hour)
+        };
+        best(&|rate| rate.gpu_model == gpu_model).or_else(|| {
+            best(&|rate| rate.gpu_model.contains(&gpu_model) || gpu_model.contains(&rate.gpu_model))
+        })
+    }
+
+    /// Price per hour of all of an executor's GPUs, or `None` if it has no
+    /// GPUs or any of them has no package
+    pub fn executor_price(&self, executor: &ExecutorDetails) -> Option<Decimal> {
+        if executor.gpu_specs.is_empty() {
+            return None;
+        }
+        executor
+            .gpu_specs
+            .iter()
+            .map(|gpu| self.rate_for(&gpu.name))
+            .sum()
+    }
+
+    /// Set the price of each executor a package covers
+    pub fn apply(&self, executors: &mut [AvailableExecutor]) {
+        for executor in executors {
+            let price = self.executor_price(&executor.executor);
+            executor.hourly_price = price.map(|price| price.normalize().to_string());
+            executor.currency = price.map(|_| PRICE_CURRENCY.to_string());
+        }
+    }
+}
+
+/// Billing identifies rentals by the bare UUID of the validator's rental ID
+fn billing_rental_id(rental_id: &str) -> &str {
+    rental_id.strip_prefix("rental-").unwrap_or(rental_id)
+}
+
+/// Billing reports costs as decimal credit strings
+fn cost_breakdown(breakdown: RentalCostBreakdown) -> Option<CostBreakdown> {
+    let credits = |amount: &str| match Decimal::from_str(amount) {
+        Ok(amount) => Some(Money::credits(amount)),
+        Err(e) => {
+            warn!("Billing reported an invalid cost {:?}: {}", amount, e);
+            None
+        }
+    };
+    // An unset resource is not billed separately
+    let billed = |amount: &Option<String>| match amount {
+        Some(amount) => credits(amount).map(Some),
+        None => Some(None),
+    };
+    Some(CostBreakdown {
+        compute: credits(&breakdown.compute_cost)?,
+        memory: billed(&breakdown.memory_cost)?,
+        network: billed(&breakdown.network_cost)?,
+        storage: billed(&breakdown.storage_cost)?,
+        total: credits(&breakdown.total_cost)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_billing_rental_id() {
+        assert_eq!(
+            billing_rental_id("rental-6f1c1d9e-2b2a-4c7e-9d53-0a8f5c3b7e21"),
+            "6f1c1d9e-2b2a-4c7e-9d53-0a8f5c3b7e21"
+        );
+        assert_eq!(
+            billing_rental_id("6f1c1d9e-2b2a-4c7e-9d53-0a8f5c3b7e21"),
+            "6f1c1d9e-2b2a-4c7e-9d53-0a8f5c3b7e21"
+        );
+    }
+
+    #[test]
+    fn test_cost_breakdown_in_credits() {
+        let breakdown = RentalCostBreakdown {
+            compute_cost: "8.75".to_string(),
+            memory_cost: Some("1.6".to_string()),
+            network_cost: None,
+            storage_cost: Some("1".to_string()),
+            total_cost: "11.35".to_string(),
+        };
+
+        let cost = cost_breakdown(breakdown.clone()).unwrap();
+        assert_eq!(
+            cost.compute,
+            Money::credits(Decimal::from_str("8.75").unwrap())
+        );
+        assert_eq!(cost.network, None);
+        assert_eq!(cost.total.to_string(), "11.35 credits");
+
+        let invalid = RentalCostBreakdown {
+            storage_cost: Some("n/a".to_string()),
+            ..breakdown
+        };
+        assert!(cost_breakdown(invalid).is_none());
+    }
+
+    fn package(gpu_model: &str, rate: &str, priority: u32, is_active: bool) -> BillingPackage {
+        BillingPackage {
+            package_id: gpu_model.to_lowercase(),
+            name: gpu_model.to_string(),
+            rates: Some(basilica_protocol::billing::PackageRates {
+                gpu_rates: [(gpu_model.to_string(), rate.to_string())].into(),
+                base_rate_per_hour: rate.to_string(),
+                ..Default::default()
+            }),
+            priority,
+            is_active,
+            ..Default::default()
+        }
+    }
+
+    fn executor(gpus: &[&str]) -> AvailableExecutor {
+        serde_json::from_value(serde_json::json!({
+            "executor": {
+                "id": "miner__executor",
+                "gpu_specs": gpus.iter().map(|name| serde_json::json!({
+                    "name": name,
+                    "memory_gb": 80,
+                    "compute_capability": "9.0",
+                })).collect::<Vec<_>>(),
+                "cpu_specs": { "cores": 32, "model": "EPYC", "memory_gb": 256 },
+                "location": null,
+            },
+            "availability": {
+                "available_until": null,
+                "verification_score": 1.0,
+                "uptime_percentage": 100.0,
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_price_populated_from_seeded_package() {
+        let prices = GpuPriceList::from_packages(&[
+            package("H100", "3.5", 100, true),
+            package("H200", "5.0", 100, true),
+            // Retired packages no longer set the price
+            package("H100", "9.99", 200, false),
+        ]);
+
+        let mut executors = vec![executor(&["NVIDIA H100 80GB HBM3"; 2]), executor(&["h200"])];
+        prices.apply(&mut executors);
+
+        assert_eq!(executors[0].hourly_price.as_deref(), Some("7"));
+        assert_eq!(executors[0].currency.as_deref(), Some(PRICE_CURRENCY));
+        assert_eq!(executors[1].hourly_price.as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn test_price_omitted_for_unknown_models() {
+        let prices = GpuPriceList::from_packages(&[package("H100", "3.5", 100, true)]);
+
+        let mut executors = vec![
+            executor(&["NVIDIA A100-SXM4-80GB"]),
+            // One unpriced GPU leaves the whole executor unpriced
+            executor(&["NVIDIA H100 80GB HBM3", "NVIDIA A100-SXM4-80GB"]),
+            executor(&[]),
+        ];
+        prices.apply(&mut executors);
+
+        for executor in &executors {
+            assert_eq!(executor.hourly_price, None);
+            assert_eq!(executor.currency, None);
+        }
+        let json = serde_json::to_value(&executors[0]).unwrap();
+        assert!(json.get("hourly_price").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_no_endpoint_means_no_client() {
+        assert!(RentalCostClient::from_config(&BillingConfig::default())
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,32 @@
+//! Billing service configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Billing service used to report the cost a rental has accrued and the
+/// price of available executors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillingConfig {
+    /// gRPC endpoint of the billing service; rental status carries no cost
+    /// when unset
+    pub endpoint: Option<String>,
+
+    /// How long a rental's cost is reused before billing is asked again, in seconds
+    pub cost_cache_ttl: u64,
+
+    /// How long package prices are reused before billing is asked again, in seconds
+    pub price_cache_ttl: u64,
+
+    /// Timeout for a single cost lookup, in seconds
+    pub request_timeout: u64,
+}
+
+impl Default for BillingConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            cost_cache_ttl: 30,
+            price_cache_ttl: 30,
+            request_timeout: 5,
+        }
+    }
+}
@@ -19,6 +19,9 @@ pub struct CacheConfig {
 
     /// Cache key prefix
     pub key_prefix: String,
+
+    /// How long responses are kept for `Idempotency-Key` replays, in seconds
+    pub idempotency_ttl: u64,
 }
 
 /// Cache backend types
@@ -40,6 +43,7 @@ impl Default for CacheConfig {
             max_size: 10000,
             redis_url: None,
             key_prefix: "basilica:api:".to_string(),
+            idempotency_ttl: 86400,
         }
     }
 }
@@ -0,0 +1,27 @@
+//! Route group toggles
+
+use serde::{Deserialize, Serialize};
+
+/// Route groups the gateway serves; a disabled group returns 404
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeaturesConfig {
+    /// Rental and executor listing routes
+    pub enable_rentals: bool,
+
+    /// API key management routes
+    pub enable_api_keys: bool,
+
+    /// Webhook configuration routes. Delivery itself is controlled by
+    /// `webhook.enabled`.
+    pub enable_webhooks: bool,
+}
+
+impl Default for FeaturesConfig {
+    fn default() -> Self {
+        Self {
+            enable_rentals: true,
+            enable_api_keys: true,
+            enable_webhooks: true,
+        }
+    }
+}
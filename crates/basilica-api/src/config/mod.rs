@@ -1,23 +1,31 @@
 //! Configuration module for the Basilica API gateway
 
+mod billing;
 mod cache;
+mod features;
 mod rate_limit;
+mod request_log;
 mod server;
+mod webhook;
 
+pub use billing::BillingConfig;
 pub use cache::{CacheBackend, CacheConfig};
+pub use features::FeaturesConfig;
 pub use rate_limit::{RateLimitBackend, RateLimitConfig};
+pub use request_log::RequestLogConfig;
 pub use server::ServerConfig;
+pub use webhook::WebhookConfig;
 
-use basilica_common::config::BittensorConfig;
+use basilica_common::config::{loader, redact_url_password, BittensorConfig, CorsConfig, REDACTED};
+use basilica_common::identity::Hotkey;
 use basilica_common::ConfigurationError as ConfigError;
-use figment::{
-    providers::{Env, Format, Serialized, Toml},
-    Figment,
-};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::Duration;
 
+/// Prefix of the environment variables overriding the configuration
+pub const ENV_PREFIX: &str = "BASILICA_API";
+
 /// Bittensor integration configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BittensorIntegrationConfig {
@@ -35,6 +43,9 @@ pub struct BittensorIntegrationConfig {
 
     /// Validator hotkey to connect to (SS58 address) - REQUIRED
     pub validator_hotkey: String,
+
+    /// API key the validator requires to start and stop rentals
+    pub validator_api_key: Option<String>,
 }
 
 impl Default for BittensorIntegrationConfig {
@@ -45,6 +56,7 @@ impl Default for BittensorIntegrationConfig {
             chain_endpoint: None,
             discovery_interval: 60,
             validator_hotkey: String::new(), // Must be provided in config
+            validator_api_key: None,
         }
     }
 }
@@ -85,30 +97,108 @@ pub struct Config {
 
     /// Database configuration
     pub database: DatabaseConfig,
+
+    /// Rental lifecycle webhook configuration
+    pub webhook: WebhookConfig,
+
+    /// Browser origins allowed to call the API
+    pub cors: CorsConfig,
+
+    /// Route groups to serve
+    pub features: FeaturesConfig,
+
+    /// Billing service for accrued rental cost and executor prices
+    pub billing: BillingConfig,
+
+    /// Per-request logging for debugging
+    pub request_log: RequestLogConfig,
 }
 
 impl Config {
     /// Load configuration from file and environment
     pub fn load(path_override: Option<PathBuf>) -> Result<Self, ConfigError> {
-        let default_config = Config::default();
-        let mut figment = Figment::from(Serialized::defaults(default_config));
+        let path = path_override.unwrap_or_else(|| PathBuf::from("basilica-api.toml"));
+        loader::load_layered(Some(&path), ENV_PREFIX)
+    }
 
-        if let Some(path) = path_override {
-            if path.exists() {
-                figment = figment.merge(Toml::file(&path));
+    /// Check the configuration for values the gateway cannot run with
+    ///
+    /// Every problem found is reported in the one error.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if self.bittensor.validator_hotkey.is_empty() {
+            problems.push("bittensor.validator_hotkey is required".to_string());
+        } else if let Err(e) = Hotkey::new(self.bittensor.validator_hotkey.clone()) {
+            problems.push(format!(
+                "bittensor.validator_hotkey is not a valid SS58 address: {e}"
+            ));
+        }
+
+        if self.server.bind_address.port() == 0 {
+            problems.push("server.bind_address must have a non-zero port".to_string());
+        }
+        for (key, value) in [
+            ("server.request_timeout", self.server.request_timeout),
+            ("server.connect_timeout", self.server.connect_timeout),
+            ("server.read_timeout", self.server.read_timeout),
+        ] {
+            if value == 0 {
+                problems.push(format!("{key} must be greater than zero"));
             }
-        } else {
-            let default_path = PathBuf::from("basilica-api.toml");
-            if default_path.exists() {
-                figment = figment.merge(Toml::file(default_path));
+        }
+        if self.server.connect_timeout > self.server.request_timeout {
+            problems.push(format!(
+                "server.connect_timeout ({}) must not exceed server.request_timeout ({})",
+                self.server.connect_timeout, self.server.request_timeout
+            ));
+        }
+
+        if self.webhook.enabled {
+            if self.webhook.poll_interval == 0 {
+                problems.push("webhook.poll_interval must be greater than zero".to_string());
+            }
+            if self.webhook.delivery_timeout == 0 {
+                problems.push("webhook.delivery_timeout must be greater than zero".to_string());
             }
         }
+        if self.billing.endpoint.is_some() && self.billing.request_timeout == 0 {
+            problems.push("billing.request_timeout must be greater than zero".to_string());
+        }
 
-        figment = figment.merge(Env::prefixed("BASILICA_API_").split("__"));
+        // The cache's Redis URL is the only one configured, so both backends need it
+        let has_redis_url = self
+            .cache
+            .redis_url
+            .as_deref()
+            .is_some_and(|url| !url.trim().is_empty());
+        if matches!(self.cache.backend, CacheBackend::Redis) && !has_redis_url {
+            problems.push("cache.redis_url is required for the redis cache backend".to_string());
+        }
+        if matches!(self.rate_limit.storage_backend, RateLimitBackend::Redis) && !has_redis_url {
+            problems
+                .push("cache.redis_url is required for the redis rate limit backend".to_string());
+        }
 
-        figment.extract().map_err(|e| ConfigError::ParseError {
-            details: e.to_string(),
-        })
+        for name in &self.request_log.header_allowlist {
+            if axum::http::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                problems.push(format!(
+                    "request_log.header_allowlist contains invalid header name '{name}'"
+                ));
+            }
+        }
+
+        if let Err(e) = self.cors.validate() {
+            problems.push(format!("cors: {e}"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::ValidationFailed {
+                details: problems.join("; "),
+            })
+        }
     }
 
     /// Generate example configuration file
@@ -119,6 +209,20 @@ impl Config {
         })
     }
 
+    /// Render the loaded configuration as TOML with passwords in URLs and the
+    /// validator API key redacted
+    pub fn to_redacted_toml(&self) -> Result<String, ConfigError> {
+        let mut config = self.clone();
+        config.database.url = redact_url_password(&config.database.url);
+        config.cache.redis_url = config.cache.redis_url.as_deref().map(redact_url_password);
+        if config.bittensor.validator_api_key.is_some() {
+            config.bittensor.validator_api_key = Some(REDACTED.to_string());
+        }
+        toml::to_string_pretty(&config).map_err(|e| ConfigError::ParseError {
+            details: format!("Failed to serialize config: {e}"),
+        })
+    }
+
     /// Get request timeout as Duration
     pub fn request_timeout(&self) -> Duration {
         Duration::from_secs(self.server.request_timeout)
@@ -136,7 +240,17 @@ impl Config {
 
     /// Get connection timeout as Duration
     pub fn connection_timeout(&self) -> Duration {
-        Duration::from_secs(10) // Default 10 seconds
+        Duration::from_secs(self.server.connect_timeout)
+    }
+
+    /// Get log stream read timeout as Duration
+    pub fn read_timeout(&self) -> Duration {
+        Duration::from_secs(self.server.read_timeout)
+    }
+
+    /// Get idempotency replay window as Duration
+    pub fn idempotency_ttl(&self) -> Duration {
+        Duration::from_secs(self.cache.idempotency_ttl)
     }
 
     /// Get validator timeout as Duration
@@ -179,6 +293,130 @@ mod tests {
         assert_eq!(config.bittensor.network, deserialized.bittensor.network);
     }
 
+    fn valid_config() -> Config {
+        let mut config = Config::default();
+        config.bittensor.validator_hotkey =
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string();
+        config
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_config() {
+        valid_config().validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate_requires_validator_hotkey() {
+        let err = Config::default().validate().unwrap_err().to_string();
+        assert!(
+            err.contains("bittensor.validator_hotkey is required"),
+            "{err}"
+        );
+
+        let mut config = valid_config();
+        config.bittensor.validator_hotkey = "not-a-hotkey".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("not a valid SS58 address"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_timeouts() {
+        let mut config = valid_config();
+        config.server.request_timeout = 0;
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("server.request_timeout must be greater than zero"));
+        assert!(err.contains("must not exceed server.request_timeout"));
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let mut config = Config::default();
+        config.server.bind_address = "0.0.0.0:0".parse().unwrap();
+        config.cache.backend = CacheBackend::Redis;
+        config.rate_limit.storage_backend = RateLimitBackend::Redis;
+        let err = config.validate().unwrap_err().to_string();
+        for expected in [
+            "bittensor.validator_hotkey",
+            "server.bind_address",
+            "redis cache backend",
+            "redis rate limit backend",
+        ] {
+            assert!(err.contains(expected), "{expected} missing from: {err}");
+        }
+
+        config.cache.redis_url = Some("redis://localhost:6379".to_string());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(!err.contains("redis"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_logged_header() {
+        let mut config = valid_config();
+        assert!(!config.request_log.enabled);
+        assert!(!config.request_log.log_bodies);
+
+        config.request_log.header_allowlist = vec!["x request id".to_string()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("invalid header name 'x request id'"), "{err}");
+    }
+
+    /// Serializes tests that set `BASILICA_API_*` variables
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[test]
+    fn test_redacted_config_reflects_file_and_env() {
+        let _env = ENV_LOCK.lock().unwrap();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            br#"
+                [server]
+                request_timeout = 120
+
+                [database]
+                url = "postgres://api:hunter2@db:5432/basilica"
+            "#,
+        )
+        .unwrap();
+        std::env::set_var("BASILICA_API_DATABASE__MAX_CONNECTIONS", "17");
+        let config = Config::load(Some(file.path().to_path_buf()));
+        std::env::remove_var("BASILICA_API_DATABASE__MAX_CONNECTIONS");
+
+        let printed = config.unwrap().to_redacted_toml().unwrap();
+        let printed: toml::Value = toml::from_str(&printed).unwrap();
+        assert_eq!(printed["server"]["request_timeout"].as_integer(), Some(120));
+        assert_eq!(
+            printed["database"]["max_connections"].as_integer(),
+            Some(17)
+        );
+        assert_eq!(
+            printed["database"]["url"].as_str(),
+            Some("postgres://api:<redacted>@db:5432/basilica")
+        );
+    }
+
+    #[test]
+    fn test_nested_env_override_beats_file() {
+        let _env = ENV_LOCK.lock().unwrap();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            br#"
+                [server]
+                request_timeout = 120
+                connect_timeout = 5
+            "#,
+        )
+        .unwrap();
+        std::env::set_var("BASILICA_API_SERVER__REQUEST_TIMEOUT", "45");
+        let config = Config::load(Some(file.path().to_path_buf()));
+        std::env::remove_var("BASILICA_API_SERVER__REQUEST_TIMEOUT");
+
+        let config = config.unwrap();
+        assert_eq!(config.server.request_timeout, 45);
+        assert_eq!(config.server.connect_timeout, 5);
+    }
+
     #[test]
     fn test_bittensor_config_conversion() {
         let config = Config::default();
@@ -0,0 +1,37 @@
+//! Request/response logging configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Opt-in structured logging of every request the gateway serves
+///
+/// Credential headers such as `Authorization` are redacted even when
+/// allowlisted, and bodies are only logged when `log_bodies` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestLogConfig {
+    /// Emit a log line per request with method, path, status and latency
+    pub enabled: bool,
+
+    /// Include JSON request bodies, with secret-looking fields redacted
+    pub log_bodies: bool,
+
+    /// Request headers to include, matched case-insensitively
+    pub header_allowlist: Vec<String>,
+
+    /// Bodies larger than this many bytes, or of unknown size, are not logged
+    pub max_body_bytes: usize,
+}
+
+impl Default for RequestLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            log_bodies: false,
+            header_allowlist: vec![
+                "content-type".to_string(),
+                "user-agent".to_string(),
+                "x-request-id".to_string(),
+            ],
+            max_body_bytes: 4096,
+        }
+    }
+}
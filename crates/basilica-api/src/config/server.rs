@@ -15,6 +15,12 @@ pub struct ServerConfig {
     /// Request timeout in seconds
     pub request_timeout: u64,
 
+    /// Timeout for establishing connections to the validator in seconds
+    pub connect_timeout: u64,
+
+    /// How long log streams may wait for the validator to respond, in seconds
+    pub read_timeout: u64,
+
     // /// Enable compression
     // pub enable_compression: bool,
     /// CORS allowed origins
@@ -27,6 +33,8 @@ impl Default for ServerConfig {
             bind_address: "0.0.0.0:8000".parse().unwrap(),
             max_connections: 10000,
             request_timeout: 900,
+            connect_timeout: 10,
+            read_timeout: 60,
             // enable_compression: true,
             cors_origins: vec!["*".to_string()],
         }
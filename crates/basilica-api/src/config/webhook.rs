@@ -0,0 +1,38 @@
+//! Webhook delivery configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Rental lifecycle webhook configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Run the rental state watcher and webhook dispatcher
+    pub enabled: bool,
+
+    /// How often rental states are polled from the validator, in seconds
+    pub poll_interval: u64,
+
+    /// Timeout for a single delivery attempt, in seconds
+    pub delivery_timeout: u64,
+
+    /// Failed attempts after which a delivery is dead-lettered
+    pub max_attempts: u32,
+
+    /// Upper bound on the retry backoff, in seconds
+    pub max_backoff: u64,
+
+    /// Deliveries claimed per dispatcher pass
+    pub batch_size: i64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval: 15,
+            delivery_timeout: 10,
+            max_attempts: 8,
+            max_backoff: 3600,
+            batch_size: 50,
+        }
+    }
+}
@@ -6,6 +6,7 @@ use axum::{
     Json,
 };
 use basilica_common::BasilicaError;
+use basilica_validator::{api::client::ValidatorResponseError, rental::DeploymentTimeoutError};
 use serde_json::json;
 use thiserror::Error;
 
@@ -84,13 +85,31 @@ pub enum ApiError {
     #[error("Conflict: {message}")]
     Conflict { message: String },
 
+    /// Error returned by the validator with its own error code, forwarded
+    /// to the client unchanged
+    #[error("{message}")]
+    Upstream {
+        status: StatusCode,
+        code: String,
+        message: String,
+        retryable: bool,
+    },
+
+    /// Rental deployment did not finish in time
+    #[error("{message}")]
+    DeploymentTimeout {
+        /// Deployment phase that timed out: `pull`, `create` or `start`
+        phase: String,
+        message: String,
+    },
+
     /// Serialization error
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
     /// Other errors
     #[error("{0}")]
-    Other(#[from] anyhow::Error),
+    Other(anyhow::Error),
 }
 
 /// Result type alias
@@ -98,9 +117,51 @@ pub type Result<T> = std::result::Result<T, ApiError>;
 
 impl BasilicaError for ApiError {}
 
+/// Validator client errors keep the validator's status and code; anything
+/// else is a gateway-internal failure
+impl From<anyhow::Error> for ApiError {
+    fn from(error: anyhow::Error) -> Self {
+        if let Some(timeout) = error.downcast_ref::<DeploymentTimeoutError>() {
+            return ApiError::DeploymentTimeout {
+                phase: timeout.phase.as_str().to_string(),
+                message: timeout.to_string(),
+            };
+        }
+        match error.downcast::<ValidatorResponseError>() {
+            Ok(response) => response.into(),
+            Err(error) => ApiError::Other(error),
+        }
+    }
+}
+
+impl From<ValidatorResponseError> for ApiError {
+    fn from(response: ValidatorResponseError) -> Self {
+        let status =
+            StatusCode::from_u16(response.status.as_u16()).unwrap_or(StatusCode::BAD_GATEWAY);
+        let message = response.message().to_string();
+        match response.details {
+            Some(details) => ApiError::Upstream {
+                status,
+                code: details.code,
+                message,
+                retryable: details.retryable,
+            },
+            None => match status {
+                StatusCode::BAD_REQUEST => ApiError::BadRequest { message },
+                StatusCode::FORBIDDEN => ApiError::Authorization { message },
+                StatusCode::NOT_FOUND => ApiError::NotFound { message },
+                StatusCode::CONFLICT => ApiError::Conflict { message },
+                _ => ApiError::ValidatorCommunication {
+                    message: response.to_string(),
+                },
+            },
+        }
+    }
+}
+
 impl ApiError {
     /// Get error code for this error
-    pub fn error_code(&self) -> &'static str {
+    pub fn error_code(&self) -> &str {
         match self {
             ApiError::Config(_) => "BASILICA_API_CONFIG_ERROR",
             ApiError::Bittensor(_) => "BASILICA_API_BITTENSOR_ERROR",
@@ -120,6 +181,8 @@ impl ApiError {
             ApiError::NotFound { .. } => "BASILICA_API_NOT_FOUND",
             ApiError::BadRequest { .. } => "BASILICA_API_BAD_REQUEST",
             ApiError::Conflict { .. } => "BASILICA_API_CONFLICT",
+            ApiError::Upstream { code, .. } => code,
+            ApiError::DeploymentTimeout { .. } => "BASILICA_API_DEPLOYMENT_TIMEOUT",
             ApiError::Serialization(_) => "BASILICA_API_SERIALIZATION_ERROR",
             ApiError::Other(_) => "BASILICA_API_OTHER_ERROR",
         }
@@ -127,17 +190,53 @@ impl ApiError {
 
     /// Check if error is retryable
     pub fn is_retryable(&self) -> bool {
+        if let ApiError::Upstream { retryable, .. } = self {
+            return *retryable;
+        }
         matches!(
             self,
             ApiError::HttpClient(_)
                 | ApiError::ValidatorCommunication { .. }
+                | ApiError::RateLimitExceeded
                 | ApiError::Timeout
                 | ApiError::ServiceUnavailable
+                | ApiError::DeploymentTimeout { .. }
         )
     }
 
+    /// HTTP status returned for this error
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Bittensor(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::HttpClient(_) => StatusCode::BAD_GATEWAY,
+            ApiError::ValidatorCommunication { .. } => StatusCode::BAD_GATEWAY,
+            ApiError::ConfigError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::MissingAuthentication { .. } => StatusCode::UNAUTHORIZED,
+            ApiError::Authentication { .. } => StatusCode::UNAUTHORIZED,
+            ApiError::Authorization { .. } => StatusCode::FORBIDDEN,
+            ApiError::RateLimitExceeded => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::InvalidRequest { .. } => StatusCode::BAD_REQUEST,
+            ApiError::Aggregation { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Cache { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Timeout => StatusCode::REQUEST_TIMEOUT,
+            ApiError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::NotFound { .. } => StatusCode::NOT_FOUND,
+            ApiError::BadRequest { .. } => StatusCode::BAD_REQUEST,
+            ApiError::Conflict { .. } => StatusCode::CONFLICT,
+            ApiError::Upstream { status, .. } => *status,
+            ApiError::DeploymentTimeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            ApiError::Serialization(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Other(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
     /// Check if error is a client error
     pub fn is_client_error(&self) -> bool {
+        if let ApiError::Upstream { status, .. } = self {
+            return status.is_client_error();
+        }
         matches!(
             self,
             ApiError::MissingAuthentication { .. }
@@ -154,45 +253,203 @@ impl ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match &self {
-            ApiError::Config(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
-            ApiError::Bittensor(_) => (StatusCode::SERVICE_UNAVAILABLE, self.to_string()),
-            ApiError::HttpClient(_) => (StatusCode::BAD_GATEWAY, self.to_string()),
-            ApiError::ValidatorCommunication { .. } => (StatusCode::BAD_GATEWAY, self.to_string()),
-            ApiError::ConfigError(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
-            ApiError::MissingAuthentication { .. } => (StatusCode::UNAUTHORIZED, self.to_string()),
-            ApiError::Authentication { .. } => (StatusCode::UNAUTHORIZED, self.to_string()),
-            ApiError::Authorization { .. } => (StatusCode::FORBIDDEN, self.to_string()),
-            ApiError::RateLimitExceeded => (
-                StatusCode::TOO_MANY_REQUESTS,
-                "Too many requests. Please try again later.".to_string(),
-            ),
-            ApiError::InvalidRequest { .. } => (StatusCode::BAD_REQUEST, self.to_string()),
-            ApiError::Aggregation { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
-            ApiError::Cache { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
-            ApiError::Timeout => (StatusCode::REQUEST_TIMEOUT, self.to_string()),
-            ApiError::Internal { .. } => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
-            ApiError::ServiceUnavailable => (StatusCode::SERVICE_UNAVAILABLE, self.to_string()),
-            ApiError::NotFound { .. } => (StatusCode::NOT_FOUND, self.to_string()),
-            ApiError::BadRequest { .. } => (StatusCode::BAD_REQUEST, self.to_string()),
-            ApiError::Conflict { .. } => (StatusCode::CONFLICT, self.to_string()),
-            ApiError::Serialization(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
-            ApiError::Other(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        let status = self.status_code();
+        let error_message = match &self {
+            ApiError::RateLimitExceeded => "Too many requests. Please try again later.".to_string(),
+            _ => self.to_string(),
         };
 
-        let body = Json(json!({
+        let mut body = json!({
             "error": {
                 "code": self.error_code(),
                 "message": error_message,
                 "timestamp": chrono::Utc::now(),
                 "retryable": self.is_retryable(),
             }
-        }));
+        });
+        if let ApiError::DeploymentTimeout { phase, .. } = &self {
+            body["error"]["phase"] = json!(phase);
+        }
+        let body = Json(body);
 
         (status, body).into_response()
     }
 }
 
+/// Entry in the error catalog served to API clients
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ErrorCatalogEntry {
+    /// Value of `error.code` in error responses
+    pub code: &'static str,
+
+    /// HTTP status sent with this code
+    pub status: u16,
+
+    /// What the error means
+    pub description: &'static str,
+
+    /// Whether the request may succeed if retried
+    pub retryable: bool,
+}
+
+/// Every `error.code` the gateway can emit
+pub const ERROR_CATALOG: &[ErrorCatalogEntry] = &[
+    ErrorCatalogEntry {
+        code: "BASILICA_API_CONFIG_ERROR",
+        status: 500,
+        description: "The gateway is misconfigured",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_BITTENSOR_ERROR",
+        status: 503,
+        description: "The Bittensor network could not be queried",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_HTTP_CLIENT_ERROR",
+        status: 502,
+        description: "The request to the validator failed in transit",
+        retryable: true,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_VALIDATOR_COMM_ERROR",
+        status: 502,
+        description: "The validator returned an error or an unreadable response",
+        retryable: true,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_AUTH_MISSING",
+        status: 401,
+        description: "No credentials were provided",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_AUTH_ERROR",
+        status: 401,
+        description: "The token or API key is invalid or expired",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_AUTHZ_ERROR",
+        status: 403,
+        description: "The caller lacks the scope or ownership required",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_RATE_LIMIT",
+        status: 429,
+        description: "Too many requests; back off before retrying",
+        retryable: true,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_INVALID_REQUEST",
+        status: 400,
+        description: "The request is malformed or has invalid fields",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_AGGREGATION_ERROR",
+        status: 500,
+        description: "Results from the validator could not be combined",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_CACHE_ERROR",
+        status: 500,
+        description: "The gateway cache failed",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_TIMEOUT",
+        status: 408,
+        description: "The request took too long",
+        retryable: true,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_INTERNAL_ERROR",
+        status: 500,
+        description: "Unexpected gateway failure",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_SERVICE_UNAVAILABLE",
+        status: 503,
+        description: "The gateway or validator is temporarily unavailable",
+        retryable: true,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_NOT_FOUND",
+        status: 404,
+        description: "The requested resource does not exist",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_BAD_REQUEST",
+        status: 400,
+        description: "The request was rejected by validation",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_CONFLICT",
+        status: 409,
+        description: "The request conflicts with current state, e.g. a reused idempotency key",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_VALIDATOR_EXECUTOR_BUSY",
+        status: 409,
+        description: "Forwarded from the validator: the executor already has a rental",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_VALIDATOR_EXECUTOR_DRAINING",
+        status: 409,
+        description: "Forwarded from the validator: the executor is draining for maintenance",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_VALIDATOR_INSUFFICIENT_COLLATERAL",
+        status: 409,
+        description:
+            "Forwarded from the validator: the executor's collateral is below the required minimum",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_VALIDATOR_IDEMPOTENCY_KEY_IN_USE",
+        status: 409,
+        description:
+            "Forwarded from the validator: a rental with the same idempotency key is still starting",
+        retryable: true,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_VALIDATOR_SECRET_ACCESS_DENIED",
+        status: 403,
+        description:
+            "Forwarded from the validator: the rental references a secret the caller does not own",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_DEPLOYMENT_TIMEOUT",
+        status: 504,
+        description:
+            "The rental container did not deploy in time; `error.phase` names the stalled step",
+        retryable: true,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_SERIALIZATION_ERROR",
+        status: 500,
+        description: "A payload could not be serialized or parsed",
+        retryable: false,
+    },
+    ErrorCatalogEntry {
+        code: "BASILICA_API_OTHER_ERROR",
+        status: 500,
+        description: "Uncategorized failure",
+        retryable: false,
+    },
+];
+
 /// Error response structure for API documentation
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct ErrorResponse {
@@ -214,6 +471,10 @@ pub struct ErrorDetails {
 
     /// Whether the error is retryable
     pub retryable: bool,
+
+    /// Deployment phase that timed out, for deployment timeouts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
 }
 
 #[cfg(test)]
@@ -233,12 +494,179 @@ mod tests {
     fn test_retryable_errors() {
         assert!(ApiError::Timeout.is_retryable());
         assert!(ApiError::ServiceUnavailable.is_retryable());
+        assert!(ApiError::RateLimitExceeded.is_retryable());
         assert!(!ApiError::Authentication {
             message: "test".to_string()
         }
         .is_retryable());
     }
 
+    /// One instance of every variant; the match fails to compile when a
+    /// variant is added without a sample here
+    fn sample_errors() -> Vec<ApiError> {
+        let message = || "test".to_string();
+        let samples = vec![
+            ApiError::Config(basilica_common::ConfigurationError::FileNotFound { path: message() }),
+            ApiError::ConfigError(message()),
+            ApiError::Bittensor(bittensor::BittensorError::TxSubmissionError {
+                message: message(),
+            }),
+            ApiError::HttpClient(reqwest::Client::new().get("not a url").build().unwrap_err()),
+            ApiError::ValidatorCommunication { message: message() },
+            ApiError::MissingAuthentication { message: message() },
+            ApiError::Authentication { message: message() },
+            ApiError::Authorization { message: message() },
+            ApiError::RateLimitExceeded,
+            ApiError::InvalidRequest { message: message() },
+            ApiError::Aggregation { message: message() },
+            ApiError::Cache { message: message() },
+            ApiError::Timeout,
+            ApiError::Internal { message: message() },
+            ApiError::ServiceUnavailable,
+            ApiError::NotFound { message: message() },
+            ApiError::BadRequest { message: message() },
+            ApiError::Conflict { message: message() },
+            ApiError::Upstream {
+                status: StatusCode::CONFLICT,
+                code: "BASILICA_VALIDATOR_EXECUTOR_BUSY".to_string(),
+                message: message(),
+                retryable: false,
+            },
+            ApiError::DeploymentTimeout {
+                phase: "pull".to_string(),
+                message: message(),
+            },
+            ApiError::Serialization(serde_json::from_str::<u8>("x").unwrap_err()),
+            ApiError::Other(anyhow::anyhow!("test")),
+        ];
+        for error in &samples {
+            match error {
+                ApiError::Config(_)
+                | ApiError::ConfigError(_)
+                | ApiError::Bittensor(_)
+                | ApiError::HttpClient(_)
+                | ApiError::ValidatorCommunication { .. }
+                | ApiError::MissingAuthentication { .. }
+                | ApiError::Authentication { .. }
+                | ApiError::Authorization { .. }
+                | ApiError::RateLimitExceeded
+                | ApiError::InvalidRequest { .. }
+                | ApiError::Aggregation { .. }
+                | ApiError::Cache { .. }
+                | ApiError::Timeout
+                | ApiError::Internal { .. }
+                | ApiError::ServiceUnavailable
+                | ApiError::NotFound { .. }
+                | ApiError::BadRequest { .. }
+                | ApiError::Conflict { .. }
+                | ApiError::Upstream { .. }
+                | ApiError::DeploymentTimeout { .. }
+                | ApiError::Serialization(_)
+                | ApiError::Other(_) => {}
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn test_every_error_has_catalog_entry() {
+        for error in sample_errors() {
+            let entry = ERROR_CATALOG
+                .iter()
+                .find(|entry| entry.code == error.error_code())
+                .unwrap_or_else(|| panic!("{} missing from ERROR_CATALOG", error.error_code()));
+            assert_eq!(entry.status, error.status_code().as_u16(), "{}", entry.code);
+            assert_eq!(entry.retryable, error.is_retryable(), "{}", entry.code);
+        }
+    }
+
+    #[test]
+    fn test_catalog_codes_are_unique() {
+        let mut codes: Vec<_> = ERROR_CATALOG.iter().map(|entry| entry.code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), ERROR_CATALOG.len());
+    }
+
+    async fn start_rental_against(
+        response: wiremock::ResponseTemplate,
+    ) -> (StatusCode, serde_json::Value) {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer};
+
+        let validator = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/rentals"))
+            .respond_with(response)
+            .mount(&validator)
+            .await;
+
+        let client = basilica_validator::ValidatorClient::new(
+            validator.uri(),
+            std::time::Duration::from_secs(5),
+        )
+        .unwrap();
+        let error: ApiError = client
+            .start_rental(Default::default())
+            .await
+            .unwrap_err()
+            .into();
+
+        let response = error.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_validator_error_code_is_forwarded() {
+        let (status, body) =
+            start_rental_against(wiremock::ResponseTemplate::new(409).set_body_json(json!({
+                "error": {
+                    "code": "BASILICA_VALIDATOR_EXECUTOR_BUSY",
+                    "message": "Executor exec-1 already has an active rental",
+                    "retryable": false,
+                }
+            })))
+            .await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "BASILICA_VALIDATOR_EXECUTOR_BUSY");
+        assert_eq!(
+            body["error"]["message"],
+            "Executor exec-1 already has an active rental"
+        );
+        assert_eq!(body["error"]["retryable"], false);
+    }
+
+    #[tokio::test]
+    async fn test_uncoded_validator_errors_keep_their_status() {
+        let (status, body) = start_rental_against(wiremock::ResponseTemplate::new(409)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "BASILICA_API_CONFLICT");
+
+        // Validator failures are reported as such rather than as gateway errors
+        let (status, body) = start_rental_against(
+            wiremock::ResponseTemplate::new(500).set_body_string("database is locked"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+        assert_eq!(body["error"]["code"], "BASILICA_API_VALIDATOR_COMM_ERROR");
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("database is locked"));
+    }
+
+    #[test]
+    fn test_gateway_failures_stay_internal() {
+        let error: ApiError = anyhow::anyhow!("connection pool exhausted").into();
+        assert_eq!(error.error_code(), "BASILICA_API_OTHER_ERROR");
+        assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     #[test]
     fn test_client_errors() {
         assert!(ApiError::MissingAuthentication {
@@ -9,14 +9,17 @@
 //! - **Authentication**: API key and JWT-based authentication
 //! - **Rate Limiting**: Configurable rate limits with different tiers
 //! - **Caching**: Response caching with in-memory or Redis backends
+//! - **Webhooks**: Signed notifications for rental lifecycle events
 //! - **OpenAPI Documentation**: Auto-generated API documentation
 
 // Server modules (always available for backward compatibility)
 pub mod api;
+pub mod billing;
 pub mod config;
 pub mod country_mapping;
 pub mod error;
 pub mod server;
+pub mod webhooks;
 
 // Re-export commonly used types
 pub use config::Config;
@@ -29,6 +32,26 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// Protocol version for API compatibility
 pub const API_VERSION: &str = "v1";
 
+/// Git commit the crate was built from, or "unknown"
+pub const GIT_SHA: &str = env!("BASILICA_API_GIT_SHA");
+
+/// RFC 3339 time the crate was built
+pub const BUILD_TIMESTAMP: &str = env!("BASILICA_API_BUILD_TIMESTAMP");
+
+/// Version of the compiler that built the crate
+pub const RUSTC_VERSION: &str = env!("BASILICA_API_RUSTC_VERSION");
+
+/// Build metadata reported by the health endpoint
+pub fn build_info() -> basilica_sdk::types::BuildInfo {
+    basilica_sdk::types::BuildInfo {
+        version: VERSION.to_string(),
+        api_version: API_VERSION.to_string(),
+        git_sha: GIT_SHA.to_string(),
+        build_timestamp: BUILD_TIMESTAMP.to_string(),
+        rustc_version: RUSTC_VERSION.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,4 +60,11 @@ mod tests {
     fn test_version_constants() {
         assert_eq!(API_VERSION, "v1");
     }
+
+    #[test]
+    fn test_build_info_is_recorded() {
+        assert!(!GIT_SHA.is_empty());
+        assert!(chrono::DateTime::parse_from_rfc3339(BUILD_TIMESTAMP).is_ok());
+        assert!(RUSTC_VERSION.starts_with("rustc") || RUSTC_VERSION == "unknown");
+    }
 }
@@ -17,6 +17,11 @@ struct Args {
     #[arg(long)]
     gen_config: bool,
 
+    /// Print the configuration in effect after merging the file and
+    /// BASILICA_API_* environment overrides, with secrets redacted
+    #[arg(long)]
+    print_config: bool,
+
     #[command(flatten)]
     verbosity: Verbosity<InfoLevel>,
 }
@@ -41,6 +46,11 @@ async fn main() -> Result<()> {
 
     // Load configuration
     let config = Config::load(args.config)?;
+
+    if args.print_config {
+        println!("{}", config.to_redacted_toml()?);
+        return Ok(());
+    }
     info!(
         "Configuration loaded, binding to {}",
         config.server.bind_address
@@ -1,21 +1,20 @@
 //! Main server implementation for the Basilica API Gateway
 
 use crate::{
-    api,
+    api::{self, middleware::IdempotencyCache, routes::health::ValidatorHealth},
+    billing::RentalCostClient,
     config::Config,
     error::{ApiError, Result},
+    webhooks::{RentalStateWatcher, WebhookDispatcher},
 };
 use axum::Router;
 use basilica_validator::ValidatorClient;
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::signal;
 use tower::ServiceBuilder;
-use tower_http::{
-    cors::{Any, CorsLayer},
-    timeout::TimeoutLayer,
-    trace::TraceLayer,
-};
+use tower_http::{timeout::TimeoutLayer, trace::TraceLayer};
 use tracing::{info, warn};
 
 /// Main server structure
@@ -47,6 +46,16 @@ pub struct AppState {
 
     /// Database pool for user rental tracking
     pub db: PgPool,
+
+    /// Stored responses for `Idempotency-Key` replays
+    pub idempotency: IdempotencyCache,
+
+    /// Accrued rental cost and executor prices, when a billing endpoint is
+    /// configured
+    pub rental_costs: Option<RentalCostClient>,
+
+    /// Validator reachability, reported by the readiness probe
+    pub validator_health: Arc<ValidatorHealth>,
 }
 
 impl Server {
@@ -56,12 +65,10 @@ impl Server {
 
         let config = Arc::new(config);
 
-        // Validate configuration
-        if config.bittensor.validator_hotkey.is_empty() {
-            return Err(ApiError::ConfigError(
-                "validator_hotkey must be configured in bittensor section".to_string(),
-            ));
-        }
+        // Fail fast on configuration the gateway cannot run with
+        config
+            .validate()
+            .map_err(|e| ApiError::ConfigError(e.to_string()))?;
 
         // Initialize Bittensor service to find validator endpoint
         info!("Connecting to Bittensor network to discover validator endpoint");
@@ -110,13 +117,19 @@ impl Server {
         );
 
         // Create validator client
-        let validator_client = Arc::new(
-            ValidatorClient::new(&validator_endpoint, config.request_timeout()).map_err(|e| {
-                ApiError::Internal {
-                    message: format!("Failed to create validator client: {e}"),
-                }
-            })?,
-        );
+        let validator_client = ValidatorClient::with_timeouts(
+            &validator_endpoint,
+            config.connection_timeout(),
+            config.request_timeout(),
+            config.read_timeout(),
+        )
+        .map_err(|e| ApiError::Internal {
+            message: format!("Failed to create validator client: {e}"),
+        })?;
+        let validator_client = Arc::new(match &config.bittensor.validator_api_key {
+            Some(api_key) => validator_client.with_api_key(api_key),
+            None => validator_client,
+        });
 
         // Create HTTP client for validator communication
         let http_client = reqwest::Client::builder()
@@ -146,6 +159,23 @@ impl Server {
                 message: format!("Failed to run migrations: {}", e),
             })?;
 
+        // Start rental lifecycle webhook tasks
+        if config.webhook.enabled {
+            let watcher = RentalStateWatcher::new(
+                db.clone(),
+                validator_client.clone(),
+                Duration::from_secs(config.webhook.poll_interval),
+            );
+            tokio::spawn(async move { watcher.run().await });
+
+            let dispatcher = WebhookDispatcher::new(db.clone(), config.webhook.clone())
+                .map_err(ApiError::HttpClient)?;
+            tokio::spawn(async move { dispatcher.run().await });
+        }
+
+        let rental_costs = RentalCostClient::from_config(&config.billing)
+            .map_err(|e| ApiError::ConfigError(format!("Invalid billing endpoint: {e}")))?;
+
         // Create application state
         let state = AppState {
             config: config.clone(),
@@ -155,9 +185,16 @@ impl Server {
             validator_hotkey: config.bittensor.validator_hotkey.clone(),
             http_client: http_client.clone(),
             db,
+            idempotency: IdempotencyCache::new(
+                config.idempotency_ttl(),
+                config.cache.max_size as u64,
+            ),
+            rental_costs,
+            validator_health: Arc::new(ValidatorHealth::default()),
         };
 
-        // Start optional health check task using HTTP client
+        // Check validator reachability in the background for readiness
+        let validator_health = state.validator_health.clone();
         let health_http_client = http_client;
         let health_endpoint = validator_endpoint.clone();
         let health_interval = config.health_check_interval();
@@ -166,7 +203,12 @@ impl Server {
             loop {
                 interval.tick().await;
                 let health_url = format!("{health_endpoint}/health");
-                match health_http_client.get(&health_url).send().await {
+                let result = health_http_client.get(&health_url).send().await;
+                validator_health.set_reachable(matches!(
+                    &result,
+                    Ok(response) if response.status().is_success()
+                ));
+                match result {
                     Ok(response) if response.status().is_success() => {
                         tracing::debug!("Validator health check passed");
                     }
@@ -195,10 +237,7 @@ impl Server {
 
     /// Build the application router with all routes and middleware
     fn build_router(state: AppState) -> Result<Router> {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods(Any)
-            .allow_headers(Any);
+        let cors = api::middleware::cors_layer(&state.config.cors)?;
 
         let middleware = ServiceBuilder::new()
             .layer(TraceLayer::new_for_http())
@@ -0,0 +1,163 @@
+//! Webhook destination checks
+//!
+//! Webhook URLs are chosen by users but requested from inside the gateway's
+//! network, so they must not reach loopback, private, link-local (which
+//! includes the cloud metadata service at 169.254.169.254) or other
+//! special-purpose addresses. Hosts are resolved and checked when a webhook
+//! is registered, and every delivery resolves through [`PublicResolver`], so
+//! a name that later points somewhere private is still refused.
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// Whether `ip` is a globally reachable unicast address
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // 0.0.0.0/8, "this network"
+        || a == 0
+        // 100.64.0.0/10, carrier-grade NAT
+        || (a == 100 && (b & 0xc0) == 64)
+        // 192.0.0.0/24, IETF protocol assignments
+        || (a == 192 && b == 0 && c == 0)
+        // 198.18.0.0/15, benchmarking
+        || (a == 198 && (b & 0xfe) == 18)
+        // 240.0.0.0/4, reserved
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ip) = embedded_ipv4(ip) {
+        return is_public_ipv4(ip);
+    }
+    let segments = ip.segments();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7, unique local
+        || (segments[0] & 0xfe00) == 0xfc00
+        // fe80::/10, link-local
+        || (segments[0] & 0xffc0) == 0xfe80
+        // 2001:db8::/32, documentation
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+}
+
+/// IPv4 address reached through an IPv4-mapped (`::ffff:a.b.c.d`),
+/// IPv4-compatible (`::a.b.c.d`) or NAT64 (`64:ff9b::a.b.c.d`) address
+fn embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    if let Some(ip) = ip.to_ipv4_mapped() {
+        return Some(ip);
+    }
+    let segments = ip.segments();
+    let [.., a, b, c, d] = ip.octets();
+    let embeds = segments[..6] == [0; 6] || segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0];
+    (embeds && !ip.is_loopback() && !ip.is_unspecified()).then_some(Ipv4Addr::new(a, b, c, d))
+}
+
+/// Resolve `host`, refusing it unless every address it resolves to is
+/// public
+pub async fn resolve_public(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve {host}: {e}"))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("{host} does not resolve to any address"));
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        return Err(format!(
+            "{host} resolves to non-public address {}",
+            addr.ip()
+        ));
+    }
+    Ok(addrs)
+}
+
+/// DNS resolver for webhook deliveries that refuses names resolving to
+/// non-public addresses
+///
+/// Checking at connect time leaves no gap between the check and the
+/// connection for the name to be re-pointed.
+#[derive(Debug, Default)]
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            // The connector sets the URL's port on the returned addresses
+            let addrs = resolve_public(name.as_str(), 0).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn public(ip: &str) -> bool {
+        is_public_ip(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn test_special_purpose_addresses_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "100.64.0.1",
+            "192.0.0.170",
+            "198.18.0.1",
+            "224.0.0.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "ff02::1",
+            "2001:db8::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "::10.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+        ] {
+            assert!(!public(ip), "{ip} must not be public");
+        }
+    }
+
+    #[test]
+    fn test_global_addresses_are_public() {
+        for ip in [
+            "1.1.1.1",
+            "8.8.8.8",
+            "172.32.0.1",
+            "100.128.0.1",
+            "2606:4700:4700::1111",
+            "::ffff:8.8.8.8",
+        ] {
+            assert!(public(ip), "{ip} must be public");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_localhost_is_refused() {
+        let err = resolve_public("localhost", 443).await.unwrap_err();
+        assert!(err.contains("non-public"), "{err}");
+    }
+}
@@ -0,0 +1,339 @@
+//! Webhook outbox dispatcher
+//!
+//! Claims due deliveries, POSTs them to the user's endpoint and either marks
+//! them delivered, schedules a retry with exponential backoff, or moves them
+//! to the dead letter state once `max_attempts` is reached. Endpoints are
+//! checked again on every delivery, since their host names may have been
+//! re-pointed since registration.
+
+use reqwest::header::CONTENT_TYPE;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, error, info, warn};
+
+use super::address::PublicResolver;
+use super::{store, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+use crate::config::WebhookConfig;
+
+/// Pause between dispatcher passes when the outbox is empty
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Failure of a single delivery attempt
+#[derive(Debug, thiserror::Error)]
+pub enum DeliveryError {
+    #[error("endpoint responded with HTTP {0}")]
+    Status(u16),
+
+    #[error("endpoint refused: {0}")]
+    Refused(String),
+
+    #[error("request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+}
+
+/// What to do with a delivery after a failed attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RetryDecision {
+    /// Try again after this many seconds
+    Retry(i64),
+    /// Stop retrying
+    DeadLetter,
+}
+
+/// Decide how to handle a failed attempt, given the attempts made so far
+fn retry_decision(attempts: i32, max_attempts: u32, max_backoff: u64) -> RetryDecision {
+    if attempts.max(0) as u32 >= max_attempts {
+        return RetryDecision::DeadLetter;
+    }
+    // 2s, 4s, 8s, ... capped at max_backoff
+    let exponent = (attempts.max(1) as u32).min(20);
+    let secs = 2_u64.pow(exponent).min(max_backoff);
+    RetryDecision::Retry(secs as i64)
+}
+
+/// Drains the webhook outbox
+pub struct WebhookDispatcher {
+    db: PgPool,
+    http: reqwest::Client,
+    config: WebhookConfig,
+}
+
+impl WebhookDispatcher {
+    pub fn new(db: PgPool, config: WebhookConfig) -> Result<Self, reqwest::Error> {
+        let http = delivery_client(Duration::from_secs(config.delivery_timeout))?;
+        Ok(Self { db, http, config })
+    }
+
+    /// Run until the task is dropped
+    pub async fn run(&self) {
+        info!("Webhook dispatcher started");
+        loop {
+            let deliveries = match store::claim_batch(&self.db, self.config.batch_size).await {
+                Ok(deliveries) => deliveries,
+                Err(e) => {
+                    error!("Failed to claim webhook deliveries: {}", e);
+                    sleep(IDLE_POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+            if deliveries.is_empty() {
+                sleep(IDLE_POLL_INTERVAL).await;
+                continue;
+            }
+
+            for delivery in deliveries {
+                self.process(delivery).await;
+            }
+        }
+    }
+
+    async fn process(&self, delivery: store::OutboxDelivery) {
+        let body = delivery.payload.to_string();
+        // Host names are checked by the client's resolver, address literals here
+        let result = match super::validate_url(&delivery.url) {
+            Ok(()) => {
+                deliver(
+                    &self.http,
+                    &delivery.url,
+                    &delivery.secret,
+                    &delivery.event_id,
+                    &delivery.event_type,
+                    body.into_bytes(),
+                )
+                .await
+            }
+            Err(reason) => Err(DeliveryError::Refused(reason)),
+        };
+
+        let outcome = match result {
+            Ok(()) => {
+                debug!(
+                    outbox_id = delivery.id,
+                    event_id = %delivery.event_id,
+                    "webhook delivered"
+                );
+                store::mark_delivered(&self.db, delivery.id).await
+            }
+            Err(e) => {
+                let message = e.to_string();
+                match retry_decision(
+                    delivery.attempts,
+                    self.config.max_attempts,
+                    self.config.max_backoff,
+                ) {
+                    RetryDecision::Retry(secs) => {
+                        warn!(
+                            outbox_id = delivery.id,
+                            event_id = %delivery.event_id,
+                            attempts = delivery.attempts,
+                            backoff = secs,
+                            err = %message,
+                            "webhook delivery failed; scheduling retry"
+                        );
+                        store::backoff(&self.db, delivery.id, secs, &message).await
+                    }
+                    RetryDecision::DeadLetter => {
+                        error!(
+                            outbox_id = delivery.id,
+                            event_id = %delivery.event_id,
+                            attempts = delivery.attempts,
+                            err = %message,
+                            "webhook delivery dead-lettered"
+                        );
+                        store::dead_letter(&self.db, delivery.id, &message).await
+                    }
+                }
+            }
+        };
+
+        if let Err(e) = outcome {
+            // The claim expires and the delivery is retried
+            error!(
+                outbox_id = delivery.id,
+                err = %e,
+                "failed to record webhook delivery outcome"
+            );
+        }
+    }
+}
+
+/// HTTP client for deliveries: no redirects, and host names only resolve to
+/// public addresses
+fn delivery_client(timeout: Duration) -> Result<reqwest::Client, reqwest::Error> {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .redirect(reqwest::redirect::Policy::none())
+        .dns_resolver(std::sync::Arc::new(PublicResolver))
+        .build()
+}
+
+/// POST one signed payload; any non-2xx response counts as a failure
+pub async fn deliver(
+    http: &reqwest::Client,
+    url: &str,
+    secret: &str,
+    event_id: &str,
+    event_type: &str,
+    body: Vec<u8>,
+) -> Result<(), DeliveryError> {
+    let signature = super::sign_payload(secret, chrono::Utc::now().timestamp(), &body);
+    let response = http
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, signature)
+        .header(EVENT_HEADER, event_type)
+        .header(DELIVERY_HEADER, event_id)
+        .body(body)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(DeliveryError::Status(response.status().as_u16()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_retry_backoff_grows_and_caps() {
+        assert_eq!(retry_decision(1, 8, 3600), RetryDecision::Retry(2));
+        assert_eq!(retry_decision(2, 8, 3600), RetryDecision::Retry(4));
+        assert_eq!(retry_decision(5, 8, 3600), RetryDecision::Retry(32));
+        assert_eq!(retry_decision(7, 8, 60), RetryDecision::Retry(60));
+    }
+
+    #[test]
+    fn test_dead_letter_after_max_attempts() {
+        assert_eq!(retry_decision(8, 8, 3600), RetryDecision::DeadLetter);
+        assert_eq!(retry_decision(9, 8, 3600), RetryDecision::DeadLetter);
+        assert_eq!(retry_decision(1, 1, 3600), RetryDecision::DeadLetter);
+    }
+
+    #[tokio::test]
+    async fn test_delivery_is_signed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header(EVENT_HEADER, "rental.ready"))
+            .and(header(DELIVERY_HEADER, "r1:Active"))
+            .and(header_exists(SIGNATURE_HEADER))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        deliver(
+            &reqwest::Client::new(),
+            &format!("{}/hook", server.uri()),
+            "whsec_test",
+            "r1:Active",
+            "rental.ready",
+            br#"{"id":"r1:Active"}"#.to_vec(),
+        )
+        .await
+        .unwrap();
+
+        let request = &server.received_requests().await.unwrap()[0];
+        let signature = request.headers.get(SIGNATURE_HEADER).unwrap();
+        let timestamp: i64 = signature
+            .to_str()
+            .unwrap()
+            .strip_prefix("t=")
+            .and_then(|s| s.split(',').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            signature.to_str().unwrap(),
+            crate::webhooks::sign_payload("whsec_test", timestamp, &request.body)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried_until_dead_letter() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let config = WebhookConfig {
+            max_attempts: 3,
+            ..Default::default()
+        };
+        let http = reqwest::Client::new();
+        let mut decisions = Vec::new();
+        for attempts in 1..=3 {
+            let err = deliver(
+                &http,
+                &server.uri(),
+                "s",
+                "r1:Failed",
+                "rental.failed",
+                vec![],
+            )
+            .await
+            .unwrap_err();
+            assert!(matches!(err, DeliveryError::Status(503)));
+            decisions.push(retry_decision(
+                attempts,
+                config.max_attempts,
+                config.max_backoff,
+            ));
+        }
+
+        assert_eq!(
+            decisions,
+            vec![
+                RetryDecision::Retry(2),
+                RetryDecision::Retry(4),
+                RetryDecision::DeadLetter
+            ]
+        );
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_delivery_client_refuses_private_hosts() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let err = deliver(
+            &delivery_client(Duration::from_secs(5)).unwrap(),
+            &format!("http://localhost:{}/hook", server.address().port()),
+            "s",
+            "r1:Active",
+            "rental.ready",
+            vec![],
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(err, DeliveryError::Transport(_)));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_is_a_transport_error() {
+        let err = deliver(
+            &reqwest::Client::new(),
+            "http://127.0.0.1:1/hook",
+            "s",
+            "r1:Stopped",
+            "rental.stopped",
+            vec![],
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, DeliveryError::Transport(_)));
+    }
+}
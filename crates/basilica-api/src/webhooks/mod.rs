@@ -0,0 +1,255 @@
+//! Rental lifecycle webhooks
+//!
+//! Users may register one HTTPS endpoint that receives a signed JSON payload
+//! whenever one of their rentals becomes ready, fails or is stopped. Events
+//! are written to the `webhook_outbox` table, by the stop handler and by the
+//! [`RentalStateWatcher`] that polls the validator, and the
+//! [`WebhookDispatcher`] drains the outbox with retries and backoff.
+//!
+//! Endpoints must resolve to public addresses; see [`address`].
+//!
+//! Every delivery carries a [`SIGNATURE_HEADER`] of the form
+//! `t=<unix seconds>,v1=<hex>`, where the hex value is the HMAC-SHA256 of
+//! `"<t>.<body>"` keyed with the user's webhook secret.
+
+pub mod address;
+mod dispatcher;
+pub mod store;
+mod watcher;
+
+pub use dispatcher::{DeliveryError, WebhookDispatcher};
+pub use watcher::RentalStateWatcher;
+
+use basilica_validator::rental::RentalState;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::net::IpAddr;
+
+/// Header carrying the payload signature
+pub const SIGNATURE_HEADER: &str = "basilica-signature";
+
+/// Header carrying the event type, e.g. `rental.ready`
+pub const EVENT_HEADER: &str = "basilica-event";
+
+/// Header carrying the event id, stable across retries of the same event
+pub const DELIVERY_HEADER: &str = "basilica-delivery";
+
+/// Prefix of generated webhook secrets
+const SECRET_PREFIX: &str = "whsec_";
+
+/// Longest accepted webhook URL
+const MAX_URL_LENGTH: usize = 2048;
+
+/// Rental lifecycle event types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "rental.ready")]
+    RentalReady,
+    #[serde(rename = "rental.failed")]
+    RentalFailed,
+    #[serde(rename = "rental.stopped")]
+    RentalStopped,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::RentalReady => "rental.ready",
+            WebhookEventType::RentalFailed => "rental.failed",
+            WebhookEventType::RentalStopped => "rental.stopped",
+        }
+    }
+
+    /// Event emitted when a rental enters `state`, if any
+    pub fn for_state(state: &RentalState) -> Option<Self> {
+        match state {
+            RentalState::Active => Some(WebhookEventType::RentalReady),
+            RentalState::Failed => Some(WebhookEventType::RentalFailed),
+            RentalState::Stopped => Some(WebhookEventType::RentalStopped),
+            RentalState::Provisioning | RentalState::Unhealthy | RentalState::Stopping => None,
+        }
+    }
+}
+
+/// JSON body POSTed to the user's webhook URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Event id, `<rental_id>:<state>`
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub data: RentalEventData,
+}
+
+/// Rental details carried by a [`WebhookEvent`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RentalEventData {
+    pub rental_id: String,
+    pub state: RentalState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl WebhookEvent {
+    /// Event for `rental_id` entering `state`, or `None` if the state is not notified
+    pub fn for_transition(
+        rental_id: &str,
+        state: RentalState,
+        reason: Option<String>,
+    ) -> Option<Self> {
+        let event_type = WebhookEventType::for_state(&state)?;
+        Some(Self {
+            id: format!("{rental_id}:{state}"),
+            event_type,
+            created_at: chrono::Utc::now(),
+            data: RentalEventData {
+                rental_id: rental_id.to_string(),
+                state,
+                reason,
+            },
+        })
+    }
+}
+
+/// Compute the [`SIGNATURE_HEADER`] value for `body` sent at `timestamp`
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!(
+        "t={timestamp},v1={}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+/// Generate a new webhook signing secret
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{SECRET_PREFIX}{}", hex::encode(bytes))
+}
+
+/// Check that a webhook URL is an absolute HTTPS URL whose host, if it is
+/// an IP address, is public
+///
+/// Host names are checked by [`resolve_url_host`].
+pub fn validate_url(url: &str) -> Result<(), String> {
+    if url.len() > MAX_URL_LENGTH {
+        return Err(format!(
+            "Webhook URL is longer than {MAX_URL_LENGTH} characters"
+        ));
+    }
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {e}"))?;
+    if parsed.scheme() != "https" {
+        return Err("Webhook URL must use https".to_string());
+    }
+    let Some(host) = parsed.host_str() else {
+        return Err("Webhook URL must include a host".to_string());
+    };
+    if let Some(ip) = ip_host(host) {
+        if !address::is_public_ip(ip) {
+            return Err(format!(
+                "Webhook URL must not point to non-public address {ip}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// IP address of a URL host that is an address literal
+fn ip_host(host: &str) -> Option<IpAddr> {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+/// Resolve a validated webhook URL's host name and check that every
+/// address it resolves to is public
+pub async fn resolve_url_host(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {e}"))?;
+    let host = parsed.host_str().unwrap_or_default();
+    if !host.is_empty() && ip_host(host).is_none() {
+        let port = parsed.port_or_known_default().unwrap_or(443);
+        address::resolve_public(host, port)
+            .await
+            .map_err(|e| format!("Webhook URL is not reachable: {e}"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_matches_reference_hmac() {
+        // HMAC-SHA256("whsec_test", "1700000000.{\"id\":\"r1:Active\"}")
+        let signature = sign_payload("whsec_test", 1_700_000_000, br#"{"id":"r1:Active"}"#);
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"whsec_test").unwrap();
+        mac.update(br#"1700000000.{"id":"r1:Active"}"#);
+        let expected = hex::encode(mac.finalize().into_bytes());
+
+        assert_eq!(signature, format!("t=1700000000,v1={expected}"));
+    }
+
+    #[test]
+    fn test_signature_depends_on_secret_timestamp_and_body() {
+        let base = sign_payload("secret-a", 1, b"body");
+        assert_ne!(base, sign_payload("secret-b", 1, b"body"));
+        assert_ne!(base, sign_payload("secret-a", 2, b"body"));
+        assert_ne!(base, sign_payload("secret-a", 1, b"body2"));
+        assert_eq!(base, sign_payload("secret-a", 1, b"body"));
+    }
+
+    #[test]
+    fn test_generated_secrets_are_unique() {
+        let a = generate_secret();
+        let b = generate_secret();
+        assert!(a.starts_with(SECRET_PREFIX));
+        assert_eq!(a.len(), SECRET_PREFIX.len() + 64);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_only_state_changes_users_care_about_emit_events() {
+        let ready = WebhookEvent::for_transition("r1", RentalState::Active, None).unwrap();
+        assert_eq!(ready.id, "r1:Active");
+        assert_eq!(ready.event_type, WebhookEventType::RentalReady);
+
+        let json = serde_json::to_value(&ready).unwrap();
+        assert_eq!(json["type"], "rental.ready");
+        assert_eq!(json["data"]["rental_id"], "r1");
+
+        assert!(WebhookEvent::for_transition("r1", RentalState::Provisioning, None).is_none());
+        assert!(WebhookEvent::for_transition("r1", RentalState::Stopping, None).is_none());
+    }
+
+    #[test]
+    fn test_validate_url() {
+        assert!(validate_url("https://hooks.example.com/basilica").is_ok());
+        assert!(validate_url("http://hooks.example.com").is_err());
+        assert!(validate_url("not a url").is_err());
+        assert!(validate_url("https://8.8.8.8/hook").is_ok());
+        for url in [
+            "https://127.0.0.1/hook",
+            "https://10.0.0.5/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/hook",
+            "https://[fd12::1]/hook",
+            "https://[::ffff:192.168.0.1]/hook",
+        ] {
+            assert!(validate_url(url).is_err(), "{url} must be refused");
+        }
+        assert!(validate_url(&format!(
+            "https://example.com/{}",
+            "a".repeat(MAX_URL_LENGTH)
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,231 @@
+//! Database access for webhook configuration and the delivery outbox
+
+use basilica_validator::rental::RentalState;
+use sqlx::{FromRow, PgPool};
+
+use super::WebhookEvent;
+
+/// A user's registered webhook endpoint
+#[derive(Debug, Clone, FromRow)]
+pub struct UserWebhook {
+    pub user_id: String,
+    pub url: String,
+    pub secret: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// An outbox delivery claimed by the dispatcher, joined with its endpoint
+#[derive(Debug, Clone, FromRow)]
+pub struct OutboxDelivery {
+    pub id: i64,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+/// A tracked rental and the state the watcher last saw for it
+#[derive(Debug, Clone, FromRow)]
+pub struct TrackedRental {
+    pub rental_id: String,
+    pub user_id: String,
+    pub last_known_state: Option<String>,
+}
+
+/// Create or replace the user's webhook endpoint and signing secret
+pub async fn upsert_webhook(
+    db: &PgPool,
+    user_id: &str,
+    url: &str,
+    secret: &str,
+) -> Result<UserWebhook, sqlx::Error> {
+    sqlx::query_as::<_, UserWebhook>(
+        r#"
+        INSERT INTO user_webhooks (user_id, url, secret)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id) DO UPDATE
+           SET url = EXCLUDED.url, secret = EXCLUDED.secret, updated_at = now()
+        RETURNING user_id, url, secret, created_at, updated_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(url)
+    .bind(secret)
+    .fetch_one(db)
+    .await
+}
+
+/// Get the user's webhook endpoint, if one is registered
+pub async fn get_webhook(db: &PgPool, user_id: &str) -> Result<Option<UserWebhook>, sqlx::Error> {
+    sqlx::query_as::<_, UserWebhook>(
+        r#"
+        SELECT user_id, url, secret, created_at, updated_at
+        FROM user_webhooks
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(db)
+    .await
+}
+
+/// Remove the user's webhook endpoint; returns whether one existed
+///
+/// Pending deliveries are dropped with it.
+pub async fn delete_webhook(db: &PgPool, user_id: &str) -> Result<bool, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let result = sqlx::query("DELETE FROM user_webhooks WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        DELETE FROM webhook_outbox
+        WHERE user_id = $1 AND delivered_at IS NULL AND dead_lettered_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Queue an event for delivery if the user has a webhook registered
+///
+/// Events are keyed by id, so enqueueing the same transition twice is a no-op.
+pub async fn enqueue_event(
+    db: &PgPool,
+    user_id: &str,
+    event: &WebhookEvent,
+) -> Result<(), sqlx::Error> {
+    let payload = serde_json::to_value(event).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO webhook_outbox (user_id, event_id, event_type, payload)
+        SELECT user_id, $2, $3, $4 FROM user_webhooks WHERE user_id = $1
+        ON CONFLICT (event_id) DO NOTHING
+        "#,
+    )
+    .bind(user_id)
+    .bind(&event.id)
+    .bind(event.event_type.as_str())
+    .bind(payload)
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Claim up to `limit` deliveries that are due, incrementing their attempt count
+///
+/// Claims older than five minutes are treated as abandoned and reclaimed.
+pub async fn claim_batch(db: &PgPool, limit: i64) -> Result<Vec<OutboxDelivery>, sqlx::Error> {
+    sqlx::query_as::<_, OutboxDelivery>(
+        r#"
+        WITH cte AS (
+          SELECT id
+          FROM webhook_outbox
+          WHERE delivered_at IS NULL
+            AND dead_lettered_at IS NULL
+            AND next_attempt_at <= now()
+            AND (claimed_at IS NULL OR claimed_at < now() - interval '5 minutes')
+          ORDER BY id
+          LIMIT $1
+          FOR UPDATE SKIP LOCKED
+        )
+        UPDATE webhook_outbox o
+           SET claimed_at = now(), attempts = o.attempts + 1
+        FROM cte, user_webhooks w
+        WHERE o.id = cte.id AND w.user_id = o.user_id
+        RETURNING o.id, o.event_id, o.event_type, o.payload, o.attempts, w.url, w.secret
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(db)
+    .await
+}
+
+/// Mark a delivery as successfully delivered
+pub async fn mark_delivered(db: &PgPool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE webhook_outbox SET delivered_at = now(), claimed_at = NULL, last_error = NULL WHERE id = $1",
+    )
+    .bind(id)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Release a failed delivery for another attempt after `secs` seconds
+pub async fn backoff(db: &PgPool, id: i64, secs: i64, error: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE webhook_outbox
+           SET next_attempt_at = now() + make_interval(secs => $2),
+               claimed_at = NULL,
+               last_error = $3
+         WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(secs)
+    .bind(error)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Give up on a delivery; it stays in the outbox for inspection
+pub async fn dead_letter(db: &PgPool, id: i64, error: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE webhook_outbox
+           SET dead_lettered_at = now(), claimed_at = NULL, last_error = $2
+         WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(error)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Tracked rentals the watcher has not yet seen stop or fail, with the
+/// state it last saw
+pub async fn list_tracked_rentals(db: &PgPool) -> Result<Vec<TrackedRental>, sqlx::Error> {
+    let terminal_states = vec![
+        RentalState::Stopped.to_string(),
+        RentalState::Failed.to_string(),
+    ];
+    sqlx::query_as::<_, TrackedRental>(
+        "SELECT rental_id, user_id, last_known_state FROM user_rentals
+        WHERE last_known_state IS NULL OR NOT (last_known_state = ANY($1))",
+    )
+    .bind(terminal_states)
+    .fetch_all(db)
+    .await
+}
+
+/// Record the state the watcher last saw for a rental
+pub async fn set_last_known_state(
+    db: &PgPool,
+    rental_id: &str,
+    state: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE user_rentals SET last_known_state = $2 WHERE rental_id = $1")
+        .bind(rental_id)
+        .bind(state)
+        .execute(db)
+        .await?;
+    Ok(())
+}
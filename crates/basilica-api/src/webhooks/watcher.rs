@@ -0,0 +1,115 @@
+//! Rental state watcher
+//!
+//! The validator does not push rental state changes, so the gateway polls it
+//! and compares each tracked rental with the state it saw last time. Only
+//! rentals that have not yet stopped or failed are polled: they are listed
+//! in one request, and a tracked rental missing from that list has ended
+//! since the last pass, so only its final state is fetched. Every
+//! notified transition is enqueued before the new state is recorded, and
+//! event ids are unique per transition, so a crash between the two steps
+//! never loses or duplicates an event.
+
+use basilica_validator::{api::types::RentalStatus, rental::RentalState, ValidatorClient};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info};
+
+use super::{store, WebhookEvent};
+
+/// Polls the validator for rental state transitions
+pub struct RentalStateWatcher {
+    db: PgPool,
+    validator_client: Arc<ValidatorClient>,
+    poll_interval: Duration,
+}
+
+impl RentalStateWatcher {
+    pub fn new(
+        db: PgPool,
+        validator_client: Arc<ValidatorClient>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            db,
+            validator_client,
+            poll_interval,
+        }
+    }
+
+    /// Run until the task is dropped
+    pub async fn run(&self) {
+        info!(
+            "Rental state watcher started (interval: {:?})",
+            self.poll_interval
+        );
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.poll_once().await {
+                error!("Rental state watcher pass failed: {}", e);
+            }
+        }
+    }
+
+    async fn poll_once(&self) -> anyhow::Result<()> {
+        let tracked = store::list_tracked_rentals(&self.db).await?;
+        if tracked.is_empty() {
+            return Ok(());
+        }
+
+        let mut live: HashMap<String, RentalState> = self
+            .validator_client
+            .list_live_rentals()
+            .await?
+            .rentals
+            .into_iter()
+            .map(|rental| (rental.rental_id, rental.state))
+            .collect();
+
+        for rental in tracked {
+            let state = match live.remove(&rental.rental_id) {
+                Some(state) => state,
+                None => match self.final_state(&rental.rental_id).await {
+                    Some(state) => state,
+                    None => continue,
+                },
+            };
+            let state_name = state.to_string();
+            if rental.last_known_state.as_deref() == Some(state_name.as_str()) {
+                continue;
+            }
+
+            if let Some(event) = WebhookEvent::for_transition(&rental.rental_id, state, None) {
+                debug!(
+                    "Rental {} moved to {}; enqueueing {}",
+                    rental.rental_id,
+                    state_name,
+                    event.event_type.as_str()
+                );
+                store::enqueue_event(&self.db, &rental.user_id, &event).await?;
+            }
+            store::set_last_known_state(&self.db, &rental.rental_id, &state_name).await?;
+        }
+
+        Ok(())
+    }
+
+    /// State of a rental that is no longer live, if the validator knows it
+    async fn final_state(&self, rental_id: &str) -> Option<RentalState> {
+        let status = match self.validator_client.get_rental_status(rental_id).await {
+            Ok(status) => status,
+            Err(e) => {
+                debug!("Failed to get status of rental {}: {}", rental_id, e);
+                return None;
+            }
+        };
+        match status.status {
+            RentalStatus::Terminated => Some(RentalState::Stopped),
+            RentalStatus::Failed => Some(RentalState::Failed),
+            // Still running after all; the next pass lists it
+            RentalStatus::Pending | RentalStatus::Active => None,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+-- Migration: Minimum charge and billing increments for packages
+-- Defaults keep the existing one hour minimum with per-second billing
+ALTER TABLE billing.billing_packages
+ADD COLUMN IF NOT EXISTS min_billing_seconds BIGINT NOT NULL DEFAULT 3600 CHECK (min_billing_seconds >= 0);
+
+ALTER TABLE billing.billing_packages
+ADD COLUMN IF NOT EXISTS billing_increment_seconds BIGINT NOT NULL DEFAULT 1 CHECK (billing_increment_seconds > 0);
+
+COMMENT ON COLUMN billing.billing_packages.min_billing_seconds IS 'Minimum GPU time charged per rental, in seconds';
+COMMENT ON COLUMN billing.billing_packages.billing_increment_seconds IS 'GPU time is rounded up to a multiple of this many seconds';
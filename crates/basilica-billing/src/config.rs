@@ -1,13 +1,13 @@
+use basilica_common::config::{loader, redact_url_password};
 use basilica_common::error::ConfigurationError;
-use figment::{
-    providers::{Env, Format, Serialized, Toml},
-    Figment,
-};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use uuid::Uuid;
 
+/// Prefix of the environment variables overriding the configuration
+pub const ENV_PREFIX: &str = "BILLING";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BillingConfig {
     pub service: ServiceConfig,
@@ -56,6 +56,8 @@ pub struct GrpcConfig {
     pub max_concurrent_requests: Option<usize>,
     pub max_concurrent_streams: Option<u32>,
     pub request_timeout_seconds: Option<u64>,
+    /// How often the database is probed to update the gRPC health status
+    pub health_check_interval_seconds: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,6 +136,7 @@ impl Default for BillingConfig {
                 max_concurrent_requests: Some(1000),
                 max_concurrent_streams: Some(100),
                 request_timeout_seconds: Some(60),
+                health_check_interval_seconds: Some(10),
             },
             http: HttpConfig {
                 listen_address: "0.0.0.0".to_string(),
@@ -173,28 +176,17 @@ impl Default for BillingConfig {
 
 impl BillingConfig {
     pub fn load(path_override: Option<PathBuf>) -> Result<BillingConfig, ConfigurationError> {
-        let default_config = BillingConfig::default();
-
-        let mut figment = Figment::from(Serialized::defaults(default_config));
-
-        if let Some(path) = path_override {
-            if path.exists() {
-                figment = figment.merge(Toml::file(&path));
-            }
-        } else {
-            let default_path = PathBuf::from("billing.toml");
-            if default_path.exists() {
-                figment = figment.merge(Toml::file(default_path));
-            }
-        }
-
-        figment = figment.merge(Env::prefixed("BILLING_").split("__"));
+        let path = path_override.unwrap_or_else(|| PathBuf::from("billing.toml"));
+        loader::load_layered(Some(&path), ENV_PREFIX)
+    }
 
-        figment
-            .extract()
-            .map_err(|e| ConfigurationError::ParseError {
-                details: e.to_string(),
-            })
+    /// Render the configuration as TOML with the database password redacted
+    pub fn to_redacted_toml(&self) -> Result<String, ConfigurationError> {
+        let mut config = self.clone();
+        config.database.url = redact_url_password(&config.database.url);
+        toml::to_string_pretty(&config).map_err(|e| ConfigurationError::ParseError {
+            details: format!("Failed to serialize config: {e}"),
+        })
     }
 
     pub fn load_from_file(path: &Path) -> Result<BillingConfig, ConfigurationError> {
@@ -205,16 +197,7 @@ impl BillingConfig {
         config: &mut BillingConfig,
         prefix: &str,
     ) -> Result<(), ConfigurationError> {
-        let figment = Figment::from(Serialized::defaults(config.clone()))
-            .merge(Env::prefixed(prefix).split("__"));
-
-        *config = figment
-            .extract()
-            .map_err(|e| ConfigurationError::ParseError {
-                details: e.to_string(),
-            })?;
-
-        Ok(())
+        loader::apply_env_overrides(config, prefix)
     }
 
     pub fn validate(&self) -> Result<(), ConfigurationError> {
@@ -303,3 +286,19 @@ impl BillingConfig {
         Duration::from_secs(self.aggregator.processing_interval_seconds)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_env_override() {
+        std::env::set_var("BILLING_GRPC__MAX_CONCURRENT_STREAMS", "64");
+        let config = BillingConfig::load(Some(PathBuf::from("/non/existent/billing.toml")));
+        std::env::remove_var("BILLING_GRPC__MAX_CONCURRENT_STREAMS");
+
+        let config = config.unwrap();
+        assert_eq!(config.grpc.max_concurrent_streams, Some(64));
+        assert_eq!(config.grpc.port, BillingConfig::default().grpc.port);
+    }
+}
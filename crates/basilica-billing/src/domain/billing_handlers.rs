@@ -149,7 +149,9 @@ impl EventHandlers for BillingEventHandlers {
             .get_package(&rental.package_id)
             .await?;
 
-        let cost_breakdown = package.calculate_cost(&usage_metrics);
+        // The minimum charge and billing increment apply to the whole rental
+        // when it ends, not to every sample
+        let cost_breakdown = package.accrued_cost(&usage_metrics);
 
         let user_id = UserId::new(event.user_id.clone());
         self.usage_repository
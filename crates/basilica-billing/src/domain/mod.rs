@@ -15,6 +15,6 @@ pub use processor::{EventHandlers, EventProcessor, UsageAggregation};
 pub use rentals::{Rental, RentalManager, RentalOperations};
 pub use rules_engine::{BillingRule, RulesEngine, RulesEvaluator};
 pub use types::{
-    BillingPeriod, CostBreakdown, CreditBalance, PackageId, RentalId, RentalState, ReservationId,
-    UsageMetrics, UserId,
+    BillingPeriod, CostBreakdown, CreditBalance, Currency, Money, PackageId, RentalId, RentalState,
+    ReservationId, UsageMetrics, UserId,
 };
@@ -1,4 +1,6 @@
-use crate::domain::types::{BillingPeriod, CostBreakdown, CreditBalance, PackageId, UsageMetrics};
+use crate::domain::types::{
+    BillingPeriod, CostBreakdown, CreditBalance, PackageId, ResourceCostBreakdown, UsageMetrics,
+};
 use crate::error::Result;
 use basilica_protocol::billing::{
     BillingPackage as ProtoBillingPackage, IncludedResources as ProtoIncludedResources,
@@ -19,6 +21,20 @@ pub struct BillingPackage {
     pub priority: u32,
     pub active: bool,
     pub metadata: HashMap<String, String>,
+    /// Minimum GPU time charged per rental, in seconds
+    #[serde(default = "default_min_billing_seconds")]
+    pub min_billing_seconds: u64,
+    /// GPU time is billed in whole multiples of this many seconds
+    #[serde(default = "default_billing_increment_seconds")]
+    pub billing_increment_seconds: u64,
+}
+
+fn default_min_billing_seconds() -> u64 {
+    PricingRules::DEFAULT_MIN_BILLING_SECONDS
+}
+
+fn default_billing_increment_seconds() -> u64 {
+    PricingRules::DEFAULT_BILLING_INCREMENT_SECONDS
 }
 
 impl BillingPackage {
@@ -39,14 +55,31 @@ impl BillingPackage {
             priority: 100,
             active: true,
             metadata: HashMap::new(),
+            min_billing_seconds: PricingRules::DEFAULT_MIN_BILLING_SECONDS,
+            billing_increment_seconds: PricingRules::DEFAULT_BILLING_INCREMENT_SECONDS,
         }
     }
 
-    /// Calculate cost for given usage
+    /// Final cost of a rental from its cumulative usage
+    ///
+    /// The minimum and billing increment apply to the rental's total GPU
+    /// time, so this must only be called once usage is complete; per-sample
+    /// costs come from [`accrued_cost`](Self::accrued_cost).
     pub fn calculate_cost(&self, usage: &UsageMetrics) -> CostBreakdown {
-        let total_hours = usage.gpu_hours.max(Decimal::ONE);
-        let total_cost = self.hourly_rate.multiply(total_hours);
+        let total_hours = self.billable_hours(usage.gpu_hours);
+        Self::gpu_cost(self.hourly_rate.multiply(total_hours))
+    }
 
+    /// Cost of a single usage sample while the rental runs
+    ///
+    /// GPU time is charged at the hourly rate exactly as used, with no
+    /// minimum or rounding; those apply once, at finalization.
+    pub fn accrued_cost(&self, usage: &UsageMetrics) -> CostBreakdown {
+        let gpu_hours = usage.gpu_hours.max(Decimal::ZERO);
+        Self::gpu_cost(self.hourly_rate.multiply(gpu_hours))
+    }
+
+    fn gpu_cost(total_cost: CreditBalance) -> CostBreakdown {
         CostBreakdown {
             base_cost: total_cost,
             usage_cost: CreditBalance::zero(),
@@ -56,6 +89,39 @@ impl BillingPackage {
         }
     }
 
+    /// GPU hours charged for `gpu_hours` of use
+    ///
+    /// The duration is rounded up to a whole number of billing increments
+    /// and is never less than the package minimum, so even a rental lasting
+    /// a few seconds is charged the minimum.
+    pub fn billable_hours(&self, gpu_hours: Decimal) -> Decimal {
+        let seconds_per_hour = Decimal::from(3600);
+        // Hours are usually a fraction of seconds, so drop the rounding noise
+        // before rounding up or 10 seconds would bill as 11
+        let seconds = (gpu_hours.max(Decimal::ZERO) * seconds_per_hour).round_dp(6);
+        let increment = Decimal::from(self.billing_increment_seconds.max(1));
+        let billed_seconds =
+            ((seconds / increment).ceil() * increment).max(Decimal::from(self.min_billing_seconds));
+        billed_seconds / seconds_per_hour
+    }
+
+    /// Cost of `usage` so far, split by resource
+    ///
+    /// Packages only charge GPU time; memory, network and storage are
+    /// covered by the hourly rate, so they have no cost of their own. The
+    /// compute cost comes from [`calculate_cost`], so the total is what the
+    /// rental would be charged if it ended now.
+    ///
+    /// [`calculate_cost`]: Self::calculate_cost
+    pub fn resource_costs(&self, usage: &UsageMetrics) -> ResourceCostBreakdown {
+        ResourceCostBreakdown {
+            compute: self.calculate_cost(usage).total_cost.into(),
+            memory: None,
+            network: None,
+            storage: None,
+        }
+    }
+
     /// Convert to protobuf format for gRPC
     pub fn to_proto(&self) -> ProtoBillingPackage {
         ProtoBillingPackage {
@@ -93,6 +159,8 @@ impl PricingRules {
     pub const CUSTOM_HOURLY_RATE: f64 = 0.0;
     pub const DEFAULT_PACKAGE_ID: &'static str = "h100";
     pub const MINIMUM_BILLABLE_HOURS: f64 = 1.0;
+    pub const DEFAULT_MIN_BILLING_SECONDS: u64 = 3600;
+    pub const DEFAULT_BILLING_INCREMENT_SECONDS: u64 = 1;
 
     /// Validate if a price is within acceptable range
     pub fn is_valid_price(price: f64) -> bool {
@@ -174,6 +242,7 @@ impl PackageService for RepositoryPackageService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::types::Currency;
     use rust_decimal::prelude::FromStr;
 
     #[test]
@@ -213,6 +282,92 @@ mod tests {
         assert_eq!(cost.total_cost, CreditBalance::from_f64(35.0).unwrap());
     }
 
+    #[test]
+    fn test_billing_minimum_and_increments() {
+        let mut package = BillingPackage::new(
+            PackageId::h100(),
+            "H100 GPU".to_string(),
+            "NVIDIA H100 GPU instances".to_string(),
+            CreditBalance::from_decimal(Decimal::from_str("3.6").unwrap()),
+            "H100".to_string(),
+        );
+        let seconds = |s: u32| Decimal::from(s) / Decimal::from(3600);
+
+        // Defaults keep the one hour minimum
+        assert_eq!(package.billable_hours(seconds(10)), Decimal::ONE);
+        assert_eq!(
+            package.billable_hours(seconds(5400)),
+            Decimal::from_str("1.5").unwrap()
+        );
+
+        package.min_billing_seconds = 360;
+        package.billing_increment_seconds = 60;
+        assert_eq!(package.billable_hours(Decimal::ZERO), seconds(360));
+        assert_eq!(package.billable_hours(seconds(10)), seconds(360));
+        assert_eq!(package.billable_hours(seconds(360)), seconds(360));
+        assert_eq!(package.billable_hours(seconds(361)), seconds(420));
+        assert_eq!(package.billable_hours(seconds(3600)), Decimal::ONE);
+
+        let usage = UsageMetrics {
+            gpu_hours: seconds(10),
+            ..UsageMetrics::zero()
+        };
+        assert_eq!(
+            package.calculate_cost(&usage).total_cost.as_decimal(),
+            Decimal::from_str("0.36").unwrap()
+        );
+
+        // Samples accrue what was used; the minimum waits for finalization
+        assert_eq!(
+            package
+                .accrued_cost(&usage)
+                .total_cost
+                .as_decimal()
+                .round_dp(2),
+            Decimal::from_str("0.01").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_resource_costs_match_calculated_cost() {
+        let package = BillingPackage::new(
+            PackageId::h100(),
+            "H100 GPU".to_string(),
+            "NVIDIA H100 GPU instances".to_string(),
+            CreditBalance::from_f64(3.5).unwrap(),
+            "H100".to_string(),
+        );
+        let usage = UsageMetrics {
+            gpu_hours: Decimal::from_str("2.5").unwrap(),
+            cpu_hours: Decimal::from(10),
+            memory_gb_hours: Decimal::from(160),
+            storage_gb_hours: Decimal::from(500),
+            network_gb: Decimal::from(12),
+            disk_io_gb: Decimal::ZERO,
+        };
+
+        let costs = package.resource_costs(&usage);
+        assert_eq!(costs.compute.amount, Decimal::from_str("8.75").unwrap());
+        assert_eq!(costs.memory, None);
+        assert_eq!(costs.network, None);
+        assert_eq!(costs.storage, None);
+        assert_eq!(costs.total().currency, Currency::Credits);
+        assert_eq!(
+            costs.total().amount,
+            package.calculate_cost(&usage).total_cost.as_decimal()
+        );
+
+        // A short rental is charged the minimum, and so is its breakdown
+        let usage = UsageMetrics {
+            gpu_hours: Decimal::from_str("0.001").unwrap(),
+            ..usage
+        };
+        assert_eq!(
+            package.resource_costs(&usage).total().amount,
+            package.calculate_cost(&usage).total_cost.as_decimal()
+        );
+    }
+
     #[test]
     fn test_volume_discount() {
         let no_discount = PricingRules::calculate_volume_discount(Decimal::from(100));
@@ -104,6 +104,45 @@ impl Rental {
         Ok(())
     }
 
+    /// Open the billed window at `at`, when the rental's container starts
+    /// running. Returns whether the rental changed; only a pending rental
+    /// is activated.
+    pub fn start_billing(&mut self, at: DateTime<Utc>) -> Result<bool> {
+        if self.state != RentalState::Pending {
+            return Ok(false);
+        }
+
+        self.transition_to(RentalState::Active)?;
+        self.actual_start_time = Some(at);
+        Ok(true)
+    }
+
+    /// Close the billed window at `at`, when the rental's container exits or
+    /// is removed. Returns whether the rental changed; only an active rental
+    /// is completed.
+    pub fn stop_billing(&mut self, at: DateTime<Utc>) -> Result<bool> {
+        if !self.is_active() {
+            return Ok(false);
+        }
+
+        self.transition_to(RentalState::Terminating)?;
+        self.transition_to(RentalState::Completed)?;
+        self.ended_at = Some(at);
+        self.actual_end_time = Some(at);
+        Ok(true)
+    }
+
+    /// Whether usage sampled at `at` falls inside the billed window: after
+    /// the rental started and not after it ended
+    pub fn is_billable_at(&self, at: DateTime<Utc>) -> bool {
+        if self.state == RentalState::Pending {
+            return false;
+        }
+
+        self.actual_start_time.map_or(true, |start| at >= start)
+            && self.ended_at.map_or(true, |end| at <= end)
+    }
+
     pub fn update_usage(&mut self, metrics: UsageMetrics) {
         self.usage_metrics = self.usage_metrics.add(&metrics);
         self.updated_at = Utc::now();
@@ -292,3 +331,61 @@ impl RentalOperations for RentalManager {
         Ok(rental)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn pending_rental() -> Rental {
+        Rental::new(
+            UserId::new("user".to_string()),
+            "executor".to_string(),
+            "validator".to_string(),
+            PackageId::h100(),
+            ResourceSpec {
+                gpu_specs: vec![],
+                cpu_cores: 1,
+                memory_gb: 1,
+                storage_gb: 10,
+                disk_iops: 1000,
+                network_bandwidth_mbps: 100,
+            },
+            None,
+        )
+    }
+
+    #[test]
+    fn test_start_opens_billed_window() {
+        let mut rental = pending_rental();
+        let started = Utc::now();
+        assert!(!rental.is_billable_at(started));
+
+        assert!(rental.start_billing(started).unwrap());
+        assert_eq!(rental.state, RentalState::Active);
+        assert!(!rental.is_billable_at(started - Duration::minutes(1)));
+        assert!(rental.is_billable_at(started + Duration::minutes(1)));
+
+        // A restarted container does not move the window
+        assert!(!rental.start_billing(started + Duration::hours(1)).unwrap());
+        assert_eq!(rental.actual_start_time, Some(started));
+    }
+
+    #[test]
+    fn test_stop_ends_billing() {
+        let mut rental = pending_rental();
+        let started = Utc::now();
+        let stopped = started + Duration::hours(2);
+        rental.start_billing(started).unwrap();
+
+        assert!(rental.stop_billing(stopped).unwrap());
+        assert_eq!(rental.state, RentalState::Completed);
+        assert_eq!(rental.ended_at, Some(stopped));
+        assert!(rental.is_billable_at(started + Duration::hours(1)));
+        assert!(!rental.is_billable_at(stopped + Duration::minutes(1)));
+
+        // A later terminate event does not reopen or move the window
+        assert!(!rental.stop_billing(stopped + Duration::hours(1)).unwrap());
+        assert_eq!(rental.ended_at, Some(stopped));
+    }
+}
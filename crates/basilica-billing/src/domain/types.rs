@@ -1,3 +1,4 @@
+pub use basilica_common::money::{Currency, Money};
 use chrono::{DateTime, Duration, Utc};
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
@@ -206,6 +207,12 @@ impl fmt::Display for CreditBalance {
     }
 }
 
+impl From<CreditBalance> for Money {
+    fn from(balance: CreditBalance) -> Self {
+        Money::credits(balance.as_decimal())
+    }
+}
+
 /// Rental lifecycle states
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -358,6 +365,41 @@ impl CostBreakdown {
     }
 }
 
+/// Accrued cost of a rental split by the resource it was spent on
+///
+/// All parts are in the same currency. A resource is `None` when it is not
+/// billed separately, its use being covered by the package's hourly rate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResourceCostBreakdown {
+    /// GPU time
+    pub compute: Money,
+    pub memory: Option<Money>,
+    pub network: Option<Money>,
+    pub storage: Option<Money>,
+}
+
+impl ResourceCostBreakdown {
+    pub fn total(&self) -> Money {
+        let amount = [self.memory, self.network, self.storage]
+            .into_iter()
+            .flatten()
+            .fold(self.compute.amount, |total, cost| total + cost.amount);
+        Money::new(amount, self.compute.currency)
+    }
+
+    /// The breakdown in `currency`, given the price of one unit of the
+    /// current currency in it
+    pub fn convert(&self, currency: Currency, rate: Decimal) -> Self {
+        let convert = |cost: Option<Money>| cost.map(|cost| cost.convert(currency, rate));
+        Self {
+            compute: self.compute.convert(currency, rate),
+            memory: convert(self.memory),
+            network: convert(self.network),
+            storage: convert(self.storage),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -376,6 +418,22 @@ mod tests {
         assert!(balance2.subtract(balance1).is_none());
     }
 
+    #[test]
+    fn test_resource_costs_convert() {
+        let breakdown = ResourceCostBreakdown {
+            compute: CreditBalance::from_f64(1.5).unwrap().into(),
+            memory: Some(CreditBalance::from_f64(0.25).unwrap().into()),
+            network: None,
+            storage: Some(CreditBalance::from_f64(0.25).unwrap().into()),
+        };
+        assert_eq!(breakdown.total().to_string(), "2.00 credits");
+
+        let usd = breakdown.convert(Currency::Usd, Decimal::from_str("0.5").unwrap());
+        assert_eq!(usd.compute.to_string(), "$0.75");
+        assert_eq!(usd.network, None);
+        assert_eq!(usd.total(), Money::new(Decimal::ONE, Currency::Usd));
+    }
+
     #[test]
     fn test_rental_state_transitions() {
         assert!(RentalState::Pending.can_transition_to(RentalState::Active));
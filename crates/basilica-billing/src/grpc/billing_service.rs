@@ -4,8 +4,8 @@ use crate::domain::{
     rentals::{RentalManager, RentalOperations},
     rules_engine::RulesEngine,
     types::{
-        CreditBalance, GpuSpec, PackageId, RentalId, RentalState, ReservationId, ResourceSpec,
-        UserId,
+        CreditBalance, GpuSpec, Money, PackageId, RentalId, RentalState, ReservationId,
+        ResourceSpec, UserId,
     },
 };
 use crate::error::BillingError;
@@ -22,10 +22,10 @@ use basilica_protocol::billing::{
     ApplyCreditsResponse, FinalizeRentalRequest, FinalizeRentalResponse, GetActiveRentalsRequest,
     GetActiveRentalsResponse, GetBalanceRequest, GetBalanceResponse, GetBillingPackagesRequest,
     GetBillingPackagesResponse, IngestResponse, ReleaseReservationRequest,
-    ReleaseReservationResponse, RentalStatus, ReserveCreditsRequest, ReserveCreditsResponse,
-    SetUserPackageRequest, SetUserPackageResponse, TelemetryData, TrackRentalRequest,
-    TrackRentalResponse, UpdateRentalStatusRequest, UpdateRentalStatusResponse, UsageDataPoint,
-    UsageReportRequest, UsageReportResponse, UsageSummary,
+    ReleaseReservationResponse, RentalCostBreakdown, RentalStatus, ReserveCreditsRequest,
+    ReserveCreditsResponse, SetUserPackageRequest, SetUserPackageResponse, TelemetryData,
+    TrackRentalRequest, TrackRentalResponse, UpdateRentalStatusRequest, UpdateRentalStatusResponse,
+    UsageDataPoint, UsageReportRequest, UsageReportResponse, UsageSummary,
 };
 
 use chrono::Duration;
@@ -119,6 +119,21 @@ impl BillingServiceImpl {
         Self::format_decimal(b.as_decimal())
     }
 
+    /// Energy reported in the rental's telemetry. The executor sends a
+    /// running total, so the largest value is the latest.
+    fn latest_energy_kwh(events: &[UsageEvent]) -> f64 {
+        events
+            .iter()
+            .filter_map(|event| {
+                event
+                    .event_data
+                    .get("custom_metrics")?
+                    .get("energy_kwh")?
+                    .as_f64()
+            })
+            .fold(0.0, f64::max)
+    }
+
     fn rental_status_to_domain(status: RentalStatus) -> RentalState {
         match status {
             RentalStatus::Pending => RentalState::Pending,
@@ -861,6 +876,7 @@ impl BillingService for BillingServiceImpl {
                 0.0
             },
             duration: Some(duration_proto),
+            energy_kwh: Self::latest_energy_kwh(&events),
         };
 
         if data_points.is_empty() {
@@ -873,11 +889,39 @@ impl BillingService for BillingServiceImpl {
             });
         }
 
+        // The breakdown is best effort; the report is still useful without it
+        let cost_breakdown = match self
+            .package_repository
+            .get_package(&rental.package_id)
+            .await
+        {
+            Ok(package) => {
+                let costs = package.resource_costs(&rental.usage_metrics);
+                let billed =
+                    |cost: Option<Money>| cost.map(|cost| Self::format_decimal(cost.amount));
+                Some(RentalCostBreakdown {
+                    compute_cost: Self::format_decimal(costs.compute.amount),
+                    memory_cost: billed(costs.memory),
+                    network_cost: billed(costs.network),
+                    storage_cost: billed(costs.storage),
+                    total_cost: Self::format_decimal(costs.total().amount),
+                })
+            }
+            Err(e) => {
+                error!(
+                    "Failed to load package {} for rental {}: {}",
+                    rental.package_id, rental_id, e
+                );
+                None
+            }
+        };
+
         let response = UsageReportResponse {
             rental_id: rental_id.to_string(),
             data_points,
             summary: Some(summary),
             total_cost: Self::format_credit_balance(rental.cost_breakdown.total_cost),
+            cost_breakdown,
         };
 
         Ok(Response::new(response))
@@ -0,0 +1,147 @@
+//! gRPC health status backed by database connectivity
+//!
+//! Billing cannot do anything useful without its database, so the standard
+//! `grpc.health.v1.Health` service reports `NOT_SERVING` while the database
+//! is unreachable or every pooled connection is in use, and `SERVING` again
+//! once it recovers.
+
+use crate::grpc::BillingServiceImpl;
+use crate::storage::rds::{ConnectionStats, RdsConnection};
+use basilica_protocol::billing::billing_service_server::BillingServiceServer;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::server::NamedService;
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
+use tracing::{info, warn};
+
+/// Result of probing the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DatabaseHealth {
+    Connected,
+    /// Every connection in the pool is checked out
+    PoolExhausted,
+    Unreachable(String),
+}
+
+impl DatabaseHealth {
+    pub fn serving_status(&self) -> ServingStatus {
+        match self {
+            Self::Connected => ServingStatus::Serving,
+            Self::PoolExhausted | Self::Unreachable(_) => ServingStatus::NotServing,
+        }
+    }
+}
+
+/// Periodically probes the database and publishes the result to the gRPC
+/// health service, both for the billing service and the server as a whole
+pub struct DatabaseHealthMonitor {
+    rds_connection: Arc<RdsConnection>,
+    reporter: HealthReporter,
+    interval: Duration,
+    last_status: Option<ServingStatus>,
+}
+
+impl DatabaseHealthMonitor {
+    pub fn new(
+        rds_connection: Arc<RdsConnection>,
+        reporter: HealthReporter,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            rds_connection,
+            reporter,
+            interval,
+            last_status: None,
+        }
+    }
+
+    pub async fn check(&self) -> DatabaseHealth {
+        // An exhausted pool would make the probe wait out the acquire timeout
+        let stats = self.rds_connection.get_connection_stats().await;
+        if pool_exhausted(&stats) {
+            return DatabaseHealth::PoolExhausted;
+        }
+
+        match self.rds_connection.health_check().await {
+            Ok(()) => DatabaseHealth::Connected,
+            Err(e) => DatabaseHealth::Unreachable(e.to_string()),
+        }
+    }
+
+    /// Probe the database once and report the result
+    pub async fn update(&mut self) -> DatabaseHealth {
+        let health = self.check().await;
+        let status = health.serving_status();
+
+        if self.last_status != Some(status) {
+            match &health {
+                DatabaseHealth::Connected => info!("Database reachable, reporting SERVING"),
+                DatabaseHealth::PoolExhausted => {
+                    warn!("Database connection pool exhausted, reporting NOT_SERVING")
+                }
+                DatabaseHealth::Unreachable(e) => {
+                    warn!("Database unreachable, reporting NOT_SERVING: {}", e)
+                }
+            }
+
+            let billing_service = <BillingServiceServer<BillingServiceImpl> as NamedService>::NAME;
+            self.reporter
+                .set_service_status(billing_service, status)
+                .await;
+            self.reporter.set_service_status("", status).await;
+            self.last_status = Some(status);
+        }
+
+        health
+    }
+
+    pub async fn run(mut self) {
+        loop {
+            tokio::time::sleep(self.interval).await;
+            self.update().await;
+        }
+    }
+}
+
+fn pool_exhausted(stats: &ConnectionStats) -> bool {
+    stats.size >= stats.max_size && stats.idle == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(size: u32, idle: u32) -> ConnectionStats {
+        ConnectionStats {
+            size,
+            idle,
+            max_size: 4,
+            min_idle: 0,
+        }
+    }
+
+    #[test]
+    fn test_pool_exhausted_only_when_full_and_busy() {
+        assert!(pool_exhausted(&stats(4, 0)));
+        assert!(!pool_exhausted(&stats(4, 1)));
+        assert!(!pool_exhausted(&stats(3, 0)));
+        assert!(!pool_exhausted(&stats(0, 0)));
+    }
+
+    #[test]
+    fn test_only_connected_database_is_serving() {
+        assert_eq!(
+            DatabaseHealth::Connected.serving_status(),
+            ServingStatus::Serving
+        );
+        assert_eq!(
+            DatabaseHealth::PoolExhausted.serving_status(),
+            ServingStatus::NotServing
+        );
+        assert_eq!(
+            DatabaseHealth::Unreachable("refused".into()).serving_status(),
+            ServingStatus::NotServing
+        );
+    }
+}
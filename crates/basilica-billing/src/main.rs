@@ -18,6 +18,12 @@ struct Args {
     #[arg(long, help = "Generate sample configuration file")]
     gen_config: bool,
 
+    #[arg(
+        long,
+        help = "Print the effective configuration after file and environment overrides, with secrets redacted"
+    )]
+    print_config: bool,
+
     #[arg(long, help = "Dry run mode (validate config without starting)")]
     dry_run: bool,
 
@@ -44,6 +50,11 @@ async fn main() -> Result<()> {
 
     let config = BillingConfig::load(args.config)?;
 
+    if args.print_config {
+        println!("{}", config.to_redacted_toml()?);
+        return Ok(());
+    }
+
     info!("Starting Basilica Billing Service");
     info!("Environment: {}", config.service.environment);
     info!("Service ID: {}", config.service.service_id);
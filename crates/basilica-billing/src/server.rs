@@ -1,5 +1,6 @@
 use crate::config::BillingConfig;
 use crate::grpc::BillingServiceImpl;
+use crate::health::DatabaseHealthMonitor;
 use crate::storage::rds::RdsConnection;
 use crate::telemetry::{TelemetryIngester, TelemetryProcessor};
 
@@ -113,12 +114,25 @@ impl BillingServer {
 
         let mut router = server_builder.add_service(BillingServiceServer::new(billing_service));
 
-        let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
-        health_reporter
-            .set_serving::<BillingServiceServer<BillingServiceImpl>>()
-            .await;
+        let (health_reporter, health_service) = tonic_health::server::health_reporter();
+        let mut health_monitor = DatabaseHealthMonitor::new(
+            self.rds_connection.clone(),
+            health_reporter,
+            std::time::Duration::from_secs(
+                self.config.grpc.health_check_interval_seconds.unwrap_or(10),
+            ),
+        );
+        health_monitor.update().await;
+        let health_handle = tokio::spawn(health_monitor.run());
         router = router.add_service(health_service);
 
+        let reflection_service = tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(basilica_protocol::FILE_DESCRIPTOR_SET)
+            .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build gRPC reflection service: {}", e))?;
+        router = router.add_service(reflection_service);
+
         let incoming = TcpListenerStream::new(listener);
 
         info!("gRPC server listening for shutdown signal");
@@ -132,6 +146,7 @@ impl BillingServer {
         info!("Stopping telemetry consumer task");
         telemetry_handle.abort();
         let _ = telemetry_handle.await;
+        health_handle.abort();
 
         self.shutdown().await?;
 
@@ -59,7 +59,8 @@ impl SqlPackageRepository {
         let rows = sqlx::query(
             r#"
             SELECT package_id, name, description, hourly_rate, gpu_model,
-                   billing_period, priority, active, metadata
+                   billing_period, priority, active, metadata,
+                   min_billing_seconds, billing_increment_seconds
             FROM billing.billing_packages
             WHERE active = true
             "#,
@@ -99,6 +100,8 @@ impl SqlPackageRepository {
                     .flatten()
                     .and_then(|v| serde_json::from_value(v).ok())
                     .unwrap_or_default(),
+                min_billing_seconds: row.get::<i64, _>("min_billing_seconds") as u64,
+                billing_increment_seconds: row.get::<i64, _>("billing_increment_seconds") as u64,
             };
 
             cache.insert(package_id, package);
@@ -112,7 +115,8 @@ impl SqlPackageRepository {
         let row = sqlx::query(
             r#"
             SELECT package_id, name, description, hourly_rate, gpu_model,
-                   billing_period, priority, active, metadata
+                   billing_period, priority, active, metadata,
+                   min_billing_seconds, billing_increment_seconds
             FROM billing.billing_packages
             WHERE package_id = $1
             "#,
@@ -149,6 +153,8 @@ impl SqlPackageRepository {
                     .flatten()
                     .and_then(|v| serde_json::from_value(v).ok())
                     .unwrap_or_default(),
+                min_billing_seconds: row.get::<i64, _>("min_billing_seconds") as u64,
+                billing_increment_seconds: row.get::<i64, _>("billing_increment_seconds") as u64,
             }))
         } else {
             Ok(None)
@@ -161,8 +167,9 @@ impl SqlPackageRepository {
             r#"
             INSERT INTO billing.billing_packages
                 (package_id, name, description, hourly_rate, gpu_model,
-                 billing_period, priority, active, metadata, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
+                 billing_period, priority, active, metadata,
+                 min_billing_seconds, billing_increment_seconds, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())
             ON CONFLICT (package_id) DO UPDATE SET
                 name = EXCLUDED.name,
                 description = EXCLUDED.description,
@@ -172,6 +179,8 @@ impl SqlPackageRepository {
                 priority = EXCLUDED.priority,
                 active = EXCLUDED.active,
                 metadata = EXCLUDED.metadata,
+                min_billing_seconds = EXCLUDED.min_billing_seconds,
+                billing_increment_seconds = EXCLUDED.billing_increment_seconds,
                 updated_at = NOW()
             "#,
         )
@@ -184,6 +193,8 @@ impl SqlPackageRepository {
         .bind(package.priority as i32)
         .bind(package.active)
         .bind(serde_json::to_value(&package.metadata).unwrap_or(serde_json::json!({})))
+        .bind(package.min_billing_seconds as i64)
+        .bind(package.billing_increment_seconds as i64)
         .execute(&self.pool)
         .await
         .map_err(|e| BillingError::DatabaseError {
@@ -223,7 +234,8 @@ impl PackageRepository for SqlPackageRepository {
         let rows = sqlx::query(
             r#"
             SELECT package_id, name, description, hourly_rate, gpu_model,
-                   billing_period, priority, active, metadata
+                   billing_period, priority, active, metadata,
+                   min_billing_seconds, billing_increment_seconds
             FROM billing.billing_packages
             WHERE active = true
             ORDER BY priority, package_id
@@ -261,6 +273,8 @@ impl PackageRepository for SqlPackageRepository {
                     .flatten()
                     .and_then(|v| serde_json::from_value(v).ok())
                     .unwrap_or_default(),
+                min_billing_seconds: row.get::<i64, _>("min_billing_seconds") as u64,
+                billing_increment_seconds: row.get::<i64, _>("billing_increment_seconds") as u64,
             });
         }
 
@@ -413,7 +427,8 @@ impl PackageRepository for SqlPackageRepository {
         let row = sqlx::query(
             r#"
             SELECT package_id, name, description, hourly_rate, gpu_model,
-                   billing_period, priority, active, metadata
+                   billing_period, priority, active, metadata,
+                   min_billing_seconds, billing_increment_seconds
             FROM billing.billing_packages
             WHERE active = true
               AND (
@@ -458,6 +473,8 @@ impl PackageRepository for SqlPackageRepository {
                     .flatten()
                     .and_then(|v| serde_json::from_value(v).ok())
                     .unwrap_or_default(),
+                min_billing_seconds: row.get::<i64, _>("min_billing_seconds") as u64,
+                billing_increment_seconds: row.get::<i64, _>("billing_increment_seconds") as u64,
             });
         }
 
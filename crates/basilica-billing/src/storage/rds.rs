@@ -73,6 +73,14 @@ impl RdsConnection {
         Ok(Self { pool, retry_config })
     }
 
+    /// Wrap an already configured pool
+    pub fn from_pool(pool: PgPool) -> Self {
+        Self {
+            pool,
+            retry_config: RetryConfig::default(),
+        }
+    }
+
     async fn get_connection_from_secret(
         aws_config: &SdkConfig,
         secret_name: &str,
@@ -168,7 +168,8 @@ impl RentalRepository for SqlRentalRepository {
             r#"
             UPDATE billing.rentals
             SET status = $2, resource_spec = $3, hourly_rate = $4,
-                updated_at = $5, end_time = $6, metadata = $7, total_cost = $8
+                updated_at = $5, end_time = $6, metadata = $7, total_cost = $8,
+                start_time = COALESCE($9, start_time)
             WHERE rental_id = $1
             "#,
         )
@@ -184,6 +185,7 @@ impl RentalRepository for SqlRentalRepository {
         } else {
             Some(total_cost)
         })
+        .bind(rental.actual_start_time)
         .execute(self.connection.pool())
         .await
         .map_err(|e| BillingError::DatabaseError {
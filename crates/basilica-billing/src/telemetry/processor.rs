@@ -1,10 +1,11 @@
+use crate::domain::rentals::Rental;
 use crate::domain::types::{RentalId, UsageMetrics};
 use crate::error::{BillingError, Result};
 use crate::storage::events::{EventType, UsageEvent};
 use crate::storage::rds::RdsConnection;
 use crate::storage::{RentalRepository, SqlRentalRepository};
 
-use basilica_protocol::billing::TelemetryData;
+use basilica_protocol::billing::{ContainerState, LifecycleEvent, TelemetryData};
 use chrono;
 use rust_decimal::prelude::*;
 use serde_json::json;
@@ -12,6 +13,16 @@ use std::sync::Arc;
 use tracing::{debug, error, warn};
 use uuid::Uuid;
 
+fn timestamp_to_datetime(ts: &prost_types::Timestamp) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::from_timestamp(ts.seconds, ts.nanos.max(0) as u32)
+}
+
+fn container_state_name(state: i32) -> &'static str {
+    ContainerState::try_from(state)
+        .unwrap_or(ContainerState::Unspecified)
+        .as_str_name()
+}
+
 pub struct TelemetryProcessor {
     event_store: Arc<crate::domain::events::EventStore>,
     rental_repository: Arc<dyn RentalRepository + Send + Sync>,
@@ -52,7 +63,7 @@ impl TelemetryProcessor {
                 message: format!("Invalid rental ID: {}", e),
             })?;
 
-        let rental = self
+        let mut rental = self
             .rental_repository
             .get_rental(&rental_id)
             .await
@@ -71,6 +82,23 @@ impl TelemetryProcessor {
                 }
             })?;
 
+        if let Some(ref event) = data.lifecycle_event {
+            self.apply_lifecycle_event(&mut rental, event).await?;
+        }
+
+        let sampled_at = data
+            .timestamp
+            .as_ref()
+            .and_then(timestamp_to_datetime)
+            .unwrap_or_else(chrono::Utc::now);
+        if data.resource_usage.is_some() && !rental.is_billable_at(sampled_at) {
+            debug!(
+                "Dropping usage for rental {} sampled outside its billed window",
+                rental_id
+            );
+            return Ok(());
+        }
+
         let usage_metrics = if let Some(ref usage) = data.resource_usage {
             UsageMetrics {
                 cpu_hours: Decimal::from_f64(usage.cpu_percent / 100.0).unwrap_or(Decimal::ZERO),
@@ -113,6 +141,13 @@ impl TelemetryProcessor {
                         "memory_used": u.gpu_usage.iter().map(|g| g.memory_used_mb).collect::<Vec<_>>(),
                     })),
                 "custom_metrics": data.custom_metrics,
+                "labels": data.labels,
+                "lifecycle_event": data.lifecycle_event.as_ref().map(|event| json!({
+                    "container_id": event.container_id,
+                    "from": container_state_name(event.from_state),
+                    "to": container_state_name(event.to_state),
+                    "timestamp": event.timestamp.as_ref().map(|ts| ts.seconds),
+                })),
             }),
             timestamp: chrono::Utc::now(),
             processed: false,
@@ -133,6 +168,42 @@ impl TelemetryProcessor {
         Ok(())
     }
 
+    /// Open or close the rental's billed window on a container transition
+    ///
+    /// The container starting to run starts billing, and it exiting, whether
+    /// stopped or terminated, ends billing at the event's timestamp. Pauses
+    /// keep the container's resources reserved and leave the window open.
+    async fn apply_lifecycle_event(
+        &self,
+        rental: &mut Rental,
+        event: &LifecycleEvent,
+    ) -> Result<()> {
+        let at = event
+            .timestamp
+            .as_ref()
+            .and_then(timestamp_to_datetime)
+            .unwrap_or_else(chrono::Utc::now);
+
+        let changed = match ContainerState::try_from(event.to_state) {
+            Ok(ContainerState::Running) => rental.start_billing(at)?,
+            Ok(ContainerState::Exited) => rental.stop_billing(at)?,
+            _ => false,
+        };
+
+        if changed {
+            debug!(
+                "Rental {} is now {} after container {} went to {}",
+                rental.id,
+                rental.state,
+                event.container_id,
+                container_state_name(event.to_state)
+            );
+            self.rental_repository.update_rental(rental).await?;
+        }
+
+        Ok(())
+    }
+
     /// Process a batch of telemetry data
     pub async fn process_batch(&self, batch: Vec<TelemetryData>) -> Result<Vec<Result<()>>> {
         let mut results = Vec::with_capacity(batch.len());
@@ -1,5 +1,36 @@
 use crate::bdd::TestContext;
+use basilica_billing::domain::packages::BillingPackage;
+use basilica_billing::domain::types::{CreditBalance, PackageId, UsageMetrics};
+use basilica_billing::storage::{PackageRepository, SqlPackageRepository};
 use basilica_protocol::billing::{GetBillingPackagesRequest, SetUserPackageRequest};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Package billed at 3.60/hour with a 6 minute minimum in 1 minute increments
+async fn create_increment_package(context: &TestContext, id: &str) -> SqlPackageRepository {
+    let repository = SqlPackageRepository::new(context.pool.clone());
+    let mut package = BillingPackage::new(
+        PackageId::new(id.to_string()),
+        "Per-minute GPU".to_string(),
+        "Billed per minute with a six minute minimum".to_string(),
+        CreditBalance::from_decimal(Decimal::from_str("3.6").unwrap()),
+        "H100".to_string(),
+    );
+    package.min_billing_seconds = 360;
+    package.billing_increment_seconds = 60;
+    repository
+        .create_package(package)
+        .await
+        .expect("Failed to create package");
+    repository
+}
+
+fn gpu_seconds(seconds: u32) -> UsageMetrics {
+    UsageMetrics {
+        gpu_hours: Decimal::from(seconds) / Decimal::from(3600),
+        ..UsageMetrics::zero()
+    }
+}
 
 #[tokio::test]
 async fn test_get_billing_packages_returns_all_available_packages() {
@@ -247,3 +278,107 @@ async fn test_package_effective_date_is_respected() {
 
     context.cleanup().await;
 }
+
+#[tokio::test]
+async fn test_short_rental_is_charged_package_minimum() {
+    let context = TestContext::new().await;
+    let package_id = PackageId::new("test_minimum_charge".to_string());
+    let repository = create_increment_package(&context, package_id.as_str()).await;
+
+    let cost = repository
+        .evaluate_package_cost(&package_id, &gpu_seconds(10))
+        .await
+        .expect("Failed to evaluate cost");
+
+    assert_eq!(
+        cost.total_cost.as_decimal(),
+        Decimal::from_str("0.36").unwrap(),
+        "A 10 second rental should be charged the 6 minute minimum"
+    );
+
+    context.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_sub_increment_duration_rounds_up_to_next_increment() {
+    let context = TestContext::new().await;
+    let package_id = PackageId::new("test_sub_increment".to_string());
+    let repository = create_increment_package(&context, package_id.as_str()).await;
+
+    // 6 minutes and 1 second is billed as 7 minutes
+    let cost = repository
+        .evaluate_package_cost(&package_id, &gpu_seconds(361))
+        .await
+        .expect("Failed to evaluate cost");
+
+    assert_eq!(
+        cost.total_cost.as_decimal().round_dp(2),
+        Decimal::from_str("0.42").unwrap()
+    );
+
+    context.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_multi_increment_duration_is_billed_exactly() {
+    let context = TestContext::new().await;
+    let package_id = PackageId::new("test_multi_increment".to_string());
+    let repository = create_increment_package(&context, package_id.as_str()).await;
+
+    // A whole number of increments above the minimum is not rounded
+    let cost = repository
+        .evaluate_package_cost(&package_id, &gpu_seconds(1800))
+        .await
+        .expect("Failed to evaluate cost");
+
+    assert_eq!(
+        cost.total_cost.as_decimal(),
+        Decimal::from_str("1.8").unwrap()
+    );
+
+    // The package settings survive a round trip through the database
+    let reloaded = SqlPackageRepository::new(context.pool.clone())
+        .get_package(&package_id)
+        .await
+        .expect("Failed to load package");
+    assert_eq!(reloaded.min_billing_seconds, 360);
+    assert_eq!(reloaded.billing_increment_seconds, 60);
+
+    context.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_short_samples_are_charged_one_minimum() {
+    let context = TestContext::new().await;
+    let package_id = PackageId::new("test_sampled_minimum".to_string());
+    let repository = create_increment_package(&context, package_id.as_str()).await;
+    let package = repository
+        .get_package(&package_id)
+        .await
+        .expect("Failed to load package");
+
+    // Four 30 second telemetry samples accrue the GPU time actually used
+    let samples = vec![gpu_seconds(30); 4];
+    let accrued: Decimal = samples
+        .iter()
+        .map(|sample| package.accrued_cost(sample).total_cost.as_decimal())
+        .sum();
+    assert_eq!(accrued.round_dp(2), Decimal::from_str("0.12").unwrap());
+
+    // The minimum is charged once, for the whole two minutes
+    let total = UsageMetrics {
+        gpu_hours: samples.iter().map(|sample| sample.gpu_hours).sum(),
+        ..UsageMetrics::zero()
+    };
+    let cost = repository
+        .evaluate_package_cost(&package_id, &total)
+        .await
+        .expect("Failed to evaluate cost");
+    assert_eq!(
+        cost.total_cost.as_decimal(),
+        Decimal::from_str("0.36").unwrap(),
+        "Two minutes of samples should be charged a single 6 minute minimum"
+    );
+
+    context.cleanup().await;
+}
@@ -202,6 +202,8 @@ async fn test_ingest_telemetry_stream() {
                 gpu_usage: vec![],
             }),
             custom_metrics: std::collections::HashMap::new(),
+            lifecycle_event: None,
+            labels: std::collections::HashMap::new(),
         };
 
         tx.send(telemetry).await.expect("Failed to send telemetry");
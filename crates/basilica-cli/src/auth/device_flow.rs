@@ -4,9 +4,11 @@
 //! devices that lack a web browser or have limited input capabilities.
 
 use super::types::{AuthConfig, AuthError, AuthResult};
-use crate::output::print_info;
+use crate::output::{print_auth, print_info, print_link};
 use basilica_sdk::auth::TokenSet;
 use console::{style, Term};
+use qrcode::render::unicode;
+use qrcode::QrCode;
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
@@ -51,12 +53,22 @@ struct PollResponse {
 /// Device authorization flow implementation
 pub struct DeviceFlow {
     config: AuthConfig,
+    show_qr_code: bool,
 }
 
 impl DeviceFlow {
     /// Create a new device flow instance
     pub fn new(config: AuthConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            show_qr_code: false,
+        }
+    }
+
+    /// Also show the verification link as a QR code
+    pub fn with_qr_code(mut self, show_qr_code: bool) -> Self {
+        self.show_qr_code = show_qr_code;
+        self
     }
 
     /// Initiate device authorization flow
@@ -100,24 +112,39 @@ impl DeviceFlow {
     }
 
     /// Display user instructions for device authorization
-    pub fn display_user_instructions(&self, response: &DeviceAuthResponse) -> AuthResult<()> {
-        let formatted_code = response.user_code.clone();
-
-        println!("1. Visit: {}", style(&response.verification_uri).dim());
-        println!("2. Enter code: {}", style(&formatted_code).bold());
+    ///
+    /// Returns the number of lines printed, so they can be cleared once the
+    /// user has signed in.
+    pub fn display_user_instructions(&self, response: &DeviceAuthResponse) -> AuthResult<usize> {
+        print_auth("Sign in from a browser on any device");
+        print_link("Visit", &response.verification_uri);
+        print_auth(&format!(
+            "Enter code: {}",
+            style(&response.user_code).yellow().bold()
+        ));
+        let mut lines = 3;
 
         if let Some(complete_uri) = &response.verification_uri_complete {
-            println!(
-                "\n   Or visit this direct link: {}",
-                style(complete_uri).dim()
-            );
+            print_link("Or open this link, code included", complete_uri);
+            lines += 1;
+        }
+
+        if self.show_qr_code {
+            let link = response
+                .verification_uri_complete
+                .as_deref()
+                .unwrap_or(&response.verification_uri);
+            let qr_code = render_qr_code(link)?;
+            println!();
+            println!("{qr_code}");
+            lines += 1 + qr_code.lines().count();
         }
 
         println!();
         print_info("Waiting for authentication...");
         print_info("Press Ctrl+C to cancel");
 
-        Ok(())
+        Ok(lines + 3)
     }
 
     /// Poll for device authorization completion
@@ -186,7 +213,7 @@ impl DeviceFlow {
         let device_response = self.initiate_device_auth().await?;
 
         // Step 2: Display instructions to user
-        self.display_user_instructions(&device_response)?;
+        let instruction_lines = self.display_user_instructions(&device_response)?;
 
         // Step 3: Poll for token with the specified interval (default to 5 seconds)
         let poll_interval = Duration::from_secs(device_response.interval.unwrap_or(5));
@@ -196,13 +223,7 @@ impl DeviceFlow {
 
         // Clear the authorization instructions using console crate
         let term = Term::stdout();
-        // We need to clear about 6-8 lines depending on if there's a complete URI
-        let lines_to_clear = if device_response.verification_uri_complete.is_some() {
-            8
-        } else {
-            6
-        };
-        term.clear_last_lines(lines_to_clear)
+        term.clear_last_lines(instruction_lines)
             .map_err(|e| AuthError::ConfigError(format!("Terminal error: {}", e)))?;
 
         Ok(token_set)
@@ -278,3 +299,33 @@ impl DeviceFlow {
         }
     }
 }
+
+/// Render `data` as a QR code for a dark terminal background
+fn render_qr_code(data: &str) -> AuthResult<String> {
+    let code = QrCode::new(data.as_bytes())
+        .map_err(|e| AuthError::ConfigError(format!("Failed to render QR code: {}", e)))?;
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qr_code_renders_square_block() {
+        let qr_code = render_qr_code("https://auth.basilica.ai/activate?user_code=ABCD-EFGH")
+            .expect("QR code should render");
+        let rows: Vec<&str> = qr_code.lines().collect();
+
+        // Each row of text holds two rows of modules, and a QR code with its
+        // quiet zone is an odd number of modules wide
+        assert!(!rows.is_empty());
+        for row in &rows {
+            assert_eq!(row.chars().count(), rows.len() * 2 - 1);
+        }
+    }
+}
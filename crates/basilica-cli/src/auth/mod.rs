@@ -39,6 +39,44 @@ pub fn is_container_runtime() -> bool {
         || std::path::Path::new("/run/.containerenv").exists()
 }
 
+/// Why login uses the device authorization flow instead of a browser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceFlowReason {
+    /// The user passed `--device`
+    Requested,
+    Wsl,
+    SshSession,
+    Container,
+}
+
+/// Detect an environment where the browser callback is unlikely to work
+pub fn detect_headless_environment() -> Option<DeviceFlowReason> {
+    if is_wsl_environment() {
+        Some(DeviceFlowReason::Wsl)
+    } else if is_ssh_session() {
+        Some(DeviceFlowReason::SshSession)
+    } else if is_container_runtime() {
+        Some(DeviceFlowReason::Container)
+    } else {
+        None
+    }
+}
+
+/// Choose the device flow when requested, otherwise when `detect` finds a
+/// headless environment
+///
+/// Detection is skipped entirely when the device flow was requested.
+pub fn device_flow_reason(
+    requested: bool,
+    detect: impl FnOnce() -> Option<DeviceFlowReason>,
+) -> Option<DeviceFlowReason> {
+    if requested {
+        Some(DeviceFlowReason::Requested)
+    } else {
+        detect()
+    }
+}
+
 /// Determine if device flow should be used for authentication
 ///
 /// Device flow is preferred when:
@@ -47,5 +85,29 @@ pub fn is_container_runtime() -> bool {
 /// - Running in container
 /// - Browser cannot be opened (fallback)
 pub fn should_use_device_flow() -> bool {
-    is_wsl_environment() || is_ssh_session() || is_container_runtime()
+    detect_headless_environment().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_flag_forces_device_flow() {
+        // Detection must not even run when the flow was requested
+        let reason = device_flow_reason(true, || panic!("environment detection ran"));
+        assert_eq!(reason, Some(DeviceFlowReason::Requested));
+
+        let reason = device_flow_reason(true, || Some(DeviceFlowReason::SshSession));
+        assert_eq!(reason, Some(DeviceFlowReason::Requested));
+    }
+
+    #[test]
+    fn test_detection_decides_without_device_flag() {
+        assert_eq!(device_flow_reason(false, || None), None);
+        assert_eq!(
+            device_flow_reason(false, || Some(DeviceFlowReason::Container)),
+            Some(DeviceFlowReason::Container)
+        );
+    }
 }
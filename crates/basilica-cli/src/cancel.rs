@@ -0,0 +1,156 @@
+//! Ctrl-C cancellation for long-running commands
+//!
+//! Commands that wait on a rental, follow a stream or transfer files take a
+//! [`CancellationToken`] from [`cancel_on_ctrl_c`]. A single Ctrl-C cancels the
+//! token, the pending await returns [`CliError::Cancelled`] and any SSH child
+//! spawned for the operation is stopped with the process terminator rather
+//! than left running.
+
+use crate::error::{CliError, Result};
+use basilica_validator::os_process::ProcessTerminator;
+use color_eyre::eyre::eyre;
+use std::future::Future;
+use std::process::Output;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Child;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+
+/// Time a cancelled child process gets to exit after SIGTERM before it is killed
+const CHILD_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Token cancelled the first time Ctrl-C is pressed
+pub fn cancel_on_ctrl_c() -> CancellationToken {
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    tokio::spawn(async move {
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => {
+                debug!("Ctrl-C received, cancelling");
+                cancel.cancel();
+            }
+            // Without a signal handler Ctrl-C keeps its default behaviour
+            Err(e) => debug!("Failed to listen for Ctrl-C: {}", e),
+        }
+    });
+    token
+}
+
+/// Run `future` unless `token` is cancelled first
+pub async fn cancellable<T>(
+    token: &CancellationToken,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => Err(CliError::Cancelled),
+        result = future => result,
+    }
+}
+
+/// Wait for `child` to exit and collect its piped output
+///
+/// If `token` is cancelled first the child is terminated, reaped and
+/// [`CliError::Cancelled`] is returned.
+pub async fn wait_for_child(mut child: Child, token: &CancellationToken) -> Result<Output> {
+    let stdout = tokio::spawn(read_to_end(child.stdout.take()));
+    let stderr = tokio::spawn(read_to_end(child.stderr.take()));
+
+    let status = tokio::select! {
+        biased;
+        _ = token.cancelled() => None,
+        status = child.wait() => Some(status),
+    };
+
+    let Some(status) = status else {
+        if let Some(pid) = child.id() {
+            // Keep waiting on the child while it is terminated so it is reaped
+            // rather than lingering as a zombie
+            let (terminated, _) = tokio::join!(
+                ProcessTerminator::terminate(pid as i32, CHILD_GRACE_PERIOD),
+                child.wait()
+            );
+            if let Err(e) = terminated {
+                warn!("Failed to stop child process {}: {}", pid, e);
+            }
+        }
+        return Err(CliError::Cancelled);
+    };
+
+    let status = status.map_err(|e| eyre!("Failed to wait for child process: {}", e))?;
+    Ok(Output {
+        status,
+        stdout: stdout.await.unwrap_or_default(),
+        stderr: stderr.await.unwrap_or_default(),
+    })
+}
+
+async fn read_to_end(pipe: Option<impl AsyncRead + Unpin>) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    if let Some(mut pipe) = pipe {
+        let _ = pipe.read_to_end(&mut buffer).await;
+    }
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use basilica_validator::os_process::{ProcessGroup, ProcessUtils};
+    use std::process::Stdio;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_cancelled_wait_returns_promptly_and_stops_child() {
+        let mut command = tokio::process::Command::new("sleep");
+        command
+            .arg("60")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        ProcessGroup::configure_command(&mut command);
+        let child = command.spawn().unwrap();
+        let pid = child.id().unwrap();
+
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            cancel.cancel();
+        });
+
+        let started = Instant::now();
+        let result = wait_for_child(child, &token).await;
+
+        assert!(matches!(result, Err(CliError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!ProcessUtils::process_exists(pid));
+    }
+
+    #[tokio::test]
+    async fn test_uncancelled_wait_collects_output() {
+        let child = tokio::process::Command::new("sh")
+            .args(["-c", "echo out; echo err >&2"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let output = wait_for_child(child, &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_stops_pending_future() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = cancellable(&token, std::future::pending::<Result<()>>()).await;
+        assert!(matches!(result, Err(CliError::Cancelled)));
+    }
+}
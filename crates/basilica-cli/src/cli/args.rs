@@ -1,6 +1,5 @@
-use crate::auth::should_use_device_flow;
 use crate::cli::{commands::Commands, handlers};
-use crate::config::CliConfig;
+use crate::config::{ApiOverrides, CliConfig};
 use crate::error::CliError;
 use clap::builder::styling::AnsiColor;
 use clap::builder::Styles;
@@ -9,6 +8,7 @@ use clap_verbosity_flag::Verbosity;
 use console::Term;
 use etcetera::{choose_base_strategy, BaseStrategy};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 // Styles are disabled by default in clap v4, this are styles used in clap v3
 const USAGE_STYLES: Styles = Styles::styled()
@@ -32,6 +32,7 @@ QUICK START:
   basilica up <spec>                # Start GPU rental with specification
   basilica exec <uid> \"python train.py\"  # Run your code
   basilica down <uid>               # Terminate specific rental
+  basilica down --status failed     # Terminate all failed rentals
 
 GPU RENTAL:
   basilica ls                       # List available GPUs with pricing
@@ -47,8 +48,10 @@ NETWORK COMPONENTS:
   basilica executor                 # Run executor
 
 AUTHENTICATION:
+  basilica doctor                   # Diagnose setup problems
   basilica login                    # Log in to Basilica
-  basilica login --device-code      # Log in using device flow
+  basilica login --device           # Log in using device flow
+  basilica whoami                   # Show current identity
   basilica logout                   # Log out of Basilica"
 )]
 pub struct Args {
@@ -63,6 +66,14 @@ pub struct Args {
     #[arg(long, global = true)]
     pub json: bool,
 
+    /// Override the API base URL (env: BASILICA_API_URL)
+    #[arg(long, global = true, value_hint = ValueHint::Url)]
+    pub api_url: Option<String>,
+
+    /// Override the API request timeout in seconds (env: BASILICA_API_TIMEOUT)
+    #[arg(long, global = true)]
+    pub api_timeout: Option<u64>,
+
     /// Subcommand to execute
     #[command(subcommand)]
     pub command: Commands,
@@ -71,13 +82,13 @@ pub struct Args {
 impl Args {
     /// Execute the CLI command
     pub async fn run(self) -> Result<(), CliError> {
-        // Load config using the common loader pattern
-        let config = if let Some(path) = &self.config {
-            let expanded_path = expand_tilde(path);
-            CliConfig::load_from_file(&expanded_path)?
-        } else {
-            CliConfig::load()?
-        };
+        let config = self.load_config();
+
+        // The doctor reports a broken config instead of failing on it
+        if matches!(self.command, Commands::Doctor) {
+            return handlers::doctor::handle_doctor(config, self.json).await;
+        }
+        let config = config?;
 
         // Check if command requires authentication and handle auto-login if needed
         if self.command.requires_auth() {
@@ -87,6 +98,22 @@ impl Args {
         }
     }
 
+    /// Load the config file and apply flag and environment overrides
+    fn load_config(&self) -> Result<CliConfig, CliError> {
+        // Load config using the common loader pattern
+        let mut config = if let Some(path) = &self.config {
+            let expanded_path = expand_tilde(path);
+            CliConfig::load_from_file(&expanded_path)?
+        } else {
+            CliConfig::load()?
+        };
+
+        // Flags and environment variables take precedence over the config file
+        let overrides = ApiOverrides::resolve(self.api_url.clone(), self.api_timeout)?;
+        config.apply_api_overrides(&overrides);
+        Ok(config)
+    }
+
     /// Execute command with automatic login retry on authentication failure
     async fn execute_with_auth_retry(&self, config: &CliConfig) -> Result<(), CliError> {
         // First attempt to execute the command
@@ -98,12 +125,9 @@ impl Args {
                     println!("You need to authenticate to continue.");
                     println!();
 
-                    // Determine whether to use device flow based on environment
-                    let use_device_flow = should_use_device_flow();
-
-                    // Attempt login without showing command suggestions
-                    handlers::auth::handle_login_with_options(use_device_flow, config, false)
-                        .await?;
+                    // Attempt login without showing command suggestions; the flow is
+                    // chosen from the environment
+                    handlers::auth::handle_login_with_options(false, false, config, false).await?;
 
                     // Clear the login output lines (approximately 8 lines without suggestions)
                     // Lines: "You need to auth" + empty + banner + empty + success + empty + SSH key messages
@@ -124,10 +148,14 @@ impl Args {
     /// Execute the actual command
     async fn execute_command(&self, config: &CliConfig) -> Result<(), CliError> {
         match &self.command {
-            Commands::Login { device_code } => {
-                handlers::auth::handle_login(*device_code, config).await?;
+            Commands::Login { device, qr } => {
+                handlers::auth::handle_login(*device, *qr, config).await?;
             }
             Commands::Logout => handlers::auth::handle_logout(config).await?,
+            Commands::Whoami => handlers::auth::handle_whoami(self.json, config).await?,
+            Commands::Doctor => {
+                handlers::doctor::handle_doctor(Ok(config.clone()), self.json).await?
+            }
             #[cfg(debug_assertions)]
             Commands::TestAuth { api } => {
                 if *api {
@@ -148,19 +176,38 @@ impl Args {
                 .await?;
             }
             Commands::Up { target, options } => {
-                handlers::gpu_rental::handle_up(target.clone(), options.clone(), config).await?;
+                handlers::gpu_rental::handle_up(target.clone(), options.clone(), self.json, config)
+                    .await?;
             }
             Commands::Ps { filters } => {
                 handlers::gpu_rental::handle_ps(filters.clone(), self.json, config).await?;
             }
-            Commands::Status { target } => {
-                handlers::gpu_rental::handle_status(target.clone(), self.json, config).await?;
+            Commands::Status { target, watch } => {
+                handlers::gpu_rental::handle_status(
+                    target.clone(),
+                    watch.map(Duration::from_secs),
+                    self.json,
+                    config,
+                )
+                .await?;
             }
             Commands::Logs { target, options } => {
                 handlers::gpu_rental::handle_logs(target.clone(), options.clone(), config).await?;
             }
-            Commands::Down { target, all } => {
-                handlers::gpu_rental::handle_down(target.clone(), *all, config).await?;
+            Commands::Down {
+                target,
+                all,
+                status,
+                yes,
+            } => {
+                handlers::gpu_rental::handle_down(
+                    target.clone(),
+                    *all,
+                    status.clone(),
+                    *yes,
+                    config,
+                )
+                .await?;
             }
             Commands::Exec { command, target } => {
                 handlers::gpu_rental::handle_exec(target.clone(), command.clone(), config).await?;
@@ -171,8 +218,18 @@ impl Args {
             Commands::Cp {
                 source,
                 destination,
+                recursive,
+                resume,
             } => {
-                handlers::gpu_rental::handle_cp(source.clone(), destination.clone(), config).await?
+                handlers::gpu_rental::handle_cp(
+                    source.clone(),
+                    destination.clone(),
+                    *recursive,
+                    *resume,
+                    self.json,
+                    config,
+                )
+                .await?
             }
 
             // Network component delegation
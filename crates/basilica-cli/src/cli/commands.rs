@@ -1,4 +1,5 @@
-use basilica_sdk::types::RentalState;
+use basilica_common::MigProfile;
+use basilica_sdk::types::{LogFormat, LogLevel, RentalState};
 use clap::{Subcommand, ValueHint};
 use std::path::PathBuf;
 
@@ -38,6 +39,16 @@ pub enum Commands {
     Status {
         /// Rental UUID (optional)
         target: Option<String>,
+
+        /// Refresh the status every N seconds until Ctrl-C (default: 5)
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "5",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        watch: Option<u64>,
     },
 
     /// View instance logs
@@ -55,9 +66,17 @@ pub enum Commands {
         /// Rental UUID to terminate (optional)
         target: Option<String>,
 
-        /// Stop all active rentals
+        /// Stop all rentals that are not already stopped
         #[arg(long, conflicts_with = "target")]
         all: bool,
+
+        /// Stop all rentals in this state
+        #[arg(long, value_enum, conflicts_with_all = ["target", "all"])]
+        status: Option<RentalState>,
+
+        /// Skip the confirmation prompt when stopping several rentals
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
 
     /// Execute commands on instances
@@ -81,6 +100,9 @@ pub enum Commands {
     },
 
     /// Copy files to/from instances
+    ///
+    /// Exactly one side must be a rental path, written `<rental_id>:<path>` or
+    /// `rental:<rental_id>:<path>`. Wildcards in a local source are expanded.
     Cp {
         /// Source path (local or remote)
         #[arg(value_hint = ValueHint::AnyPath)]
@@ -89,6 +111,15 @@ pub enum Commands {
         /// Destination path (local or remote)
         #[arg(value_hint = ValueHint::AnyPath)]
         destination: String,
+
+        /// Copy directories recursively
+        #[arg(short = 'r', long)]
+        recursive: bool,
+
+        /// Continue interrupted uploads from the bytes already on the rental,
+        /// verifying each file by checksum
+        #[arg(long)]
+        resume: bool,
     },
 
     /// Run validator (delegates to basilica-validator)
@@ -117,14 +148,24 @@ pub enum Commands {
 
     /// Log in to Basilica
     Login {
-        /// Use device authorization flow (for WSL, SSH, containers)
+        /// Sign in with a one-time code instead of opening a browser (for WSL, SSH, containers)
+        #[arg(long, alias = "device-code")]
+        device: bool,
+
+        /// Also show the sign-in link as a QR code when signing in with a code
         #[arg(long)]
-        device_code: bool,
+        qr: bool,
     },
 
     /// Log out of Basilica
     Logout,
 
+    /// Show the identity and scopes of the current login
+    Whoami,
+
+    /// Check config, login, SSH keys and API connectivity
+    Doctor,
+
     /// Test authentication token
     #[cfg(debug_assertions)]
     TestAuth {
@@ -177,11 +218,13 @@ impl Commands {
             | Commands::Exec { .. }
             | Commands::Ssh { .. }
             | Commands::Cp { .. }
+            | Commands::Whoami
             | Commands::Tokens { .. } => true,
 
             // Authentication and delegation commands don't require auth
             Commands::Login { .. }
             | Commands::Logout
+            | Commands::Doctor
             | Commands::Validator { .. }
             | Commands::Miner { .. }
             | Commands::Executor { .. } => false,
@@ -232,6 +275,10 @@ pub struct UpOptions {
     #[arg(long)]
     pub gpu_min: Option<u32>,
 
+    /// Rent MIG slices with this profile instead of whole GPUs (e.g., 1g.10gb)
+    #[arg(long, value_name = "PROFILE")]
+    pub mig_profile: Option<MigProfile>,
+
     /// Docker image to run
     #[arg(long)]
     pub image: Option<String>,
@@ -276,10 +323,22 @@ pub struct UpOptions {
     #[arg(long)]
     pub no_ssh: bool,
 
+    /// Flag the rental once its GPUs have been idle for this many minutes
+    #[arg(long, value_name = "MINUTES", value_parser = clap::value_parser!(u64).range(1..))]
+    pub idle_timeout: Option<u64>,
+
+    /// How the container writes its logs ('plain' or 'json'); JSON logs can be filtered by level
+    #[arg(long, value_name = "FORMAT", default_value_t = LogFormat::Plain)]
+    pub log_format: LogFormat,
+
     /// Create rental in detached mode (don't auto-connect via SSH)
     #[arg(short = 'd', long)]
     pub detach: bool,
 
+    /// Return as soon as the rental is created without waiting for SSH to become reachable
+    #[arg(long)]
+    pub no_wait: bool,
+
     /// Use compact view (group executors by GPU type)
     #[arg(long)]
     pub compact: bool,
@@ -323,6 +382,10 @@ pub struct LogsOptions {
     /// Number of lines to tail
     #[arg(long)]
     pub tail: Option<u32>,
+
+    /// Only show lines at or above this level (e.g. 'warn'); needs a rental started with --log-format json
+    #[arg(long, value_name = "LEVEL")]
+    pub level: Option<LogLevel>,
 }
 
 /// Options for SSH connections
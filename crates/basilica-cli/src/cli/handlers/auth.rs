@@ -1,26 +1,31 @@
 //! Authentication command handlers
 
-use crate::auth::{should_use_device_flow, CallbackServer, DeviceFlow, OAuthFlow, TokenStore};
+use crate::auth::{
+    detect_headless_environment, device_flow_reason, CallbackServer, DeviceFlow, OAuthFlow,
+    TokenStore,
+};
+use crate::client::create_authenticated_client;
 use crate::config::CliConfig;
 use crate::error::CliError;
-use crate::output::{banner, compress_path, print_success};
+use crate::output::{banner, compress_path, json_output, print_success, table_output};
 use crate::progress::{complete_spinner_and_clear, complete_spinner_error, create_spinner};
 use color_eyre::eyre::{eyre, WrapErr};
 use color_eyre::Section;
 use tracing::{debug, warn};
 
 /// Handle login command
-pub async fn handle_login(device_code: bool, config: &CliConfig) -> Result<(), CliError> {
-    handle_login_with_options(device_code, config, true).await
+pub async fn handle_login(device: bool, qr: bool, config: &CliConfig) -> Result<(), CliError> {
+    handle_login_with_options(device, qr, config, true).await
 }
 
 /// Handle login with configurable options
 pub async fn handle_login_with_options(
-    device_code: bool,
+    device: bool,
+    qr: bool,
     config: &CliConfig,
     show_suggestions: bool,
 ) -> Result<(), CliError> {
-    debug!("Starting login process, device_code: {}", device_code);
+    debug!("Starting login process, device: {}", device);
 
     println!(
         "{}",
@@ -31,7 +36,9 @@ pub async fn handle_login_with_options(
     println!();
 
     // Determine which flow to use
-    let use_device_flow = device_code || should_use_device_flow();
+    let device_flow_reason = device_flow_reason(device, detect_headless_environment);
+    debug!("Device flow reason: {:?}", device_flow_reason);
+    let use_device_flow = device_flow_reason.is_some();
 
     // Create authentication configuration with available port
     let auth_config = if use_device_flow {
@@ -42,7 +49,7 @@ pub async fn handle_login_with_options(
         let port = CallbackServer::find_available_port().map_err(|e| {
             eyre!(e)
                 .wrap_err("Failed to find available port")
-                .suggestion("Try 'basilica login --device-code' for device flow")
+                .suggestion("Try 'basilica login --device' for device flow")
                 .note("The standard OAuth flow requires an available local port")
         })?;
         crate::config::create_auth_config_with_port(port)
@@ -54,7 +61,7 @@ pub async fn handle_login_with_options(
 
     let token_set = if use_device_flow {
         let spinner = create_spinner("Requesting device code...");
-        let device_flow = DeviceFlow::new(auth_config);
+        let device_flow = DeviceFlow::new(auth_config).with_qr_code(qr);
 
         match device_flow.start_flow().await {
             Ok(tokens) => {
@@ -193,3 +200,18 @@ pub async fn handle_logout(_config: &CliConfig) -> Result<(), CliError> {
     print_success("⛪ Logout successful!");
     Ok(())
 }
+
+/// Handle whoami command
+pub async fn handle_whoami(json: bool, config: &CliConfig) -> Result<(), CliError> {
+    // Client creation refreshes the stored token first if it has expired
+    let client = create_authenticated_client(config).await?;
+    let claims = client.whoami().await?;
+
+    if json {
+        json_output(&claims)?;
+    } else {
+        table_output::display_token_claims(&claims)?;
+    }
+
+    Ok(())
+}
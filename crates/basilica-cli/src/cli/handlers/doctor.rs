@@ -0,0 +1,316 @@
+//! Diagnostic command handler
+//!
+//! `basilica doctor` runs the checks a first-time user otherwise discovers
+//! one error at a time: config, login, SSH keys, API reachability and a
+//! trivial executor listing. Each check reports a remediation hint when it
+//! does not pass.
+
+use crate::client::create_authenticated_client;
+use crate::config::{CliConfig, SshConfig};
+use crate::error::{CliError, Result};
+use crate::output::{compress_path, json_output};
+use basilica_sdk::BasilicaClient;
+use color_eyre::eyre::eyre;
+use color_eyre::Section;
+use console::style;
+use serde::Serialize;
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// Not fatal, but some commands will not work
+    Warn,
+    Fail,
+    /// Not run because a check it depends on failed
+    Skip,
+}
+
+/// Result of one diagnostic check
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            status: CheckStatus::Warn,
+            ..Self::fail(name, detail, hint)
+        }
+    }
+
+    fn skip(name: &'static str, reason: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skip,
+            detail: reason.into(),
+            hint: None,
+        }
+    }
+}
+
+/// Every check run by `basilica doctor`, in order
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// Checks that failed; any of these makes the command exit non-zero
+    pub fn failures(&self) -> impl Iterator<Item = &DoctorCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Fail)
+    }
+
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+}
+
+/// Handle `basilica doctor`
+///
+/// `config` is the result of loading the configuration, so a broken config
+/// file is reported as a failed check instead of aborting the run.
+pub async fn handle_doctor(config: Result<CliConfig>, json: bool) -> Result<()> {
+    let report = run_checks(config).await;
+
+    if json {
+        json_output(&report)?;
+    } else {
+        print_report(&report);
+    }
+
+    if report.passed() {
+        return Ok(());
+    }
+
+    let failed: Vec<&str> = report.failures().map(|check| check.name).collect();
+    let mut error = eyre!("{} check(s) failed: {}", failed.len(), failed.join(", "));
+    for hint in report.failures().filter_map(|check| check.hint.as_deref()) {
+        error = error.suggestion(hint.to_string());
+    }
+    Err(error.into())
+}
+
+async fn run_checks(config: Result<CliConfig>) -> DoctorReport {
+    let mut report = DoctorReport::default();
+
+    let config = match config {
+        Ok(config) => {
+            report.checks.push(DoctorCheck::pass(
+                "config",
+                format!("Loaded, API at {}", config.api.base_url),
+            ));
+            config
+        }
+        Err(e) => {
+            report.checks.push(DoctorCheck::fail(
+                "config",
+                format!("Failed to load: {e}"),
+                "Fix or remove the config file, or pass a valid one with --config",
+            ));
+            // Carry on with defaults so the remaining checks still run
+            CliConfig::default()
+        }
+    };
+
+    let client = match create_authenticated_client(&config).await {
+        Ok(client) => {
+            let check = check_tokens(&client).await;
+            let usable = check.status == CheckStatus::Pass;
+            report.checks.push(check);
+            usable.then_some(client)
+        }
+        Err(e) => {
+            report.checks.push(DoctorCheck::fail(
+                "auth",
+                format!("No usable login: {e}"),
+                "Run 'basilica login' (or 'basilica login --device' over SSH)",
+            ));
+            None
+        }
+    };
+
+    report.checks.push(check_ssh_keys(&config.ssh));
+
+    match client {
+        Some(client) => {
+            let reachable = check_api(&client, &config.api.base_url).await;
+            let api_up = reachable.status == CheckStatus::Pass;
+            report.checks.push(reachable);
+
+            report.checks.push(if api_up {
+                check_executor_listing(&client).await
+            } else {
+                DoctorCheck::skip("executors", "API is not reachable")
+            });
+        }
+        None => {
+            report
+                .checks
+                .push(DoctorCheck::skip("api", "Requires a valid login"));
+            report
+                .checks
+                .push(DoctorCheck::skip("executors", "Requires a valid login"));
+        }
+    }
+
+    report
+}
+
+/// Tokens are valid if the client can produce (refreshing if needed) an
+/// access token whose claims decode
+async fn check_tokens(client: &BasilicaClient) -> DoctorCheck {
+    match client.whoami().await {
+        Ok(claims) => DoctorCheck::pass(
+            "auth",
+            format!(
+                "Logged in as {}",
+                claims.email.as_deref().unwrap_or(&claims.subject)
+            ),
+        ),
+        Err(e) => DoctorCheck::fail(
+            "auth",
+            format!("Stored login is not usable: {e}"),
+            "Your session may have expired; run 'basilica login' again",
+        ),
+    }
+}
+
+fn check_ssh_keys(ssh: &SshConfig) -> DoctorCheck {
+    let public = compress_path(&ssh.key_path);
+    if ssh.ssh_keys_exist() {
+        DoctorCheck::pass("ssh-keys", format!("Found {public}"))
+    } else if ssh.ssh_keys_incomplete() {
+        DoctorCheck::warn(
+            "ssh-keys",
+            format!(
+                "Only one of {public} and {} exists",
+                compress_path(&ssh.private_key_path)
+            ),
+            "Remove the remaining key file and run 'basilica login' to generate a new pair",
+        )
+    } else {
+        DoctorCheck::warn(
+            "ssh-keys",
+            format!("No key pair at {public}"),
+            "SSH keys are automatically generated during login. Run 'basilica login' to create them",
+        )
+    }
+}
+
+async fn check_api(client: &BasilicaClient, base_url: &str) -> DoctorCheck {
+    match client.health_check().await {
+        Ok(health) => DoctorCheck::pass(
+            "api",
+            format!(
+                "{base_url} is {} (version {}, {} healthy validators)",
+                health.status, health.version, health.healthy_validators
+            ),
+        ),
+        Err(e) => DoctorCheck::fail(
+            "api",
+            format!("{base_url} is not reachable: {e}"),
+            "Check your network connection and the API URL (--api-url or BASILICA_API_URL)",
+        ),
+    }
+}
+
+async fn check_executor_listing(client: &BasilicaClient) -> DoctorCheck {
+    match client.list_available_executors(None).await {
+        Ok(response) => DoctorCheck::pass(
+            "executors",
+            format!("{} executor(s) available", response.total_count),
+        ),
+        Err(e) => DoctorCheck::fail(
+            "executors",
+            format!("Listing executors failed: {e}"),
+            "Check that your login has the 'executors:list' scope; run 'basilica login' again",
+        ),
+    }
+}
+
+fn print_report(report: &DoctorReport) {
+    for check in &report.checks {
+        let marker = match check.status {
+            CheckStatus::Pass => style("✓").green().bold(),
+            CheckStatus::Warn => style("!").yellow().bold(),
+            CheckStatus::Fail => style("✗").red().bold(),
+            CheckStatus::Skip => style("-").dim(),
+        };
+        println!("{marker} {:<10} {}", check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("  {} {}", style("→").cyan(), style(hint).dim());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warnings_do_not_fail_the_report() {
+        let mut report = DoctorReport {
+            checks: vec![
+                DoctorCheck::pass("config", "ok"),
+                DoctorCheck::warn("ssh-keys", "missing", "run login"),
+                DoctorCheck::skip("api", "no login"),
+            ],
+        };
+        assert!(report.passed());
+
+        report
+            .checks
+            .push(DoctorCheck::fail("auth", "expired", "run login"));
+        assert!(!report.passed());
+        assert_eq!(
+            report.failures().map(|c| c.name).collect::<Vec<_>>(),
+            vec!["auth"]
+        );
+    }
+
+    #[test]
+    fn test_ssh_key_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let ssh = SshConfig {
+            key_path: dir.path().join("id.pub"),
+            private_key_path: dir.path().join("id"),
+            connection_timeout: 30,
+        };
+        assert_eq!(check_ssh_keys(&ssh).status, CheckStatus::Warn);
+
+        std::fs::write(&ssh.key_path, "ssh-ed25519 AAAA").unwrap();
+        let incomplete = check_ssh_keys(&ssh);
+        assert_eq!(incomplete.status, CheckStatus::Warn);
+        assert!(incomplete.detail.contains("Only one"));
+
+        std::fs::write(&ssh.private_key_path, "private").unwrap();
+        assert_eq!(check_ssh_keys(&ssh).status, CheckStatus::Pass);
+    }
+}
@@ -1,19 +1,26 @@
 //! GPU rental command handlers
 
+use crate::cancel::{cancel_on_ctrl_c, cancellable};
 use crate::cli::commands::{ListFilters, LogsOptions, PsFilters, UpOptions};
-use crate::cli::handlers::gpu_rental_helpers::resolve_target_rental;
+use crate::cli::handlers::gpu_rental_helpers::{resolve_target_rental, run_watch_loop};
 use crate::client::create_authenticated_client;
 use crate::config::CliConfig;
 use crate::output::{
-    compress_path, json_output, print_error, print_info, print_success, table_output,
+    compress_path, json_line_output, json_output, print_error, print_info, print_success,
+    table_output,
 };
-use crate::progress::{complete_spinner_and_clear, complete_spinner_error, create_spinner};
+use crate::progress::{
+    complete_spinner_and_clear, complete_spinner_error, create_spinner,
+    create_transfer_progress_bar,
+};
+use crate::ssh::transfer::{self, CopyRequest, SshRemoteFs};
 use crate::ssh::{parse_ssh_credentials, SshClient};
 use crate::CliError;
 use basilica_common::utils::{parse_env_vars, parse_port_mappings};
 use basilica_sdk::types::{
-    ExecutorSelection, GpuRequirements, ListAvailableExecutorsQuery, ListRentalsQuery,
-    LocationProfile, RentalState, RentalStatusResponse, ResourceRequirementsRequest, SshAccess,
+    CostBreakdown, ExecutorSelection, GpuRequirements, ListAvailableExecutorsQuery,
+    ListRentalsQuery, LocationProfile, LogLevel, Money, RentalLogLine, RentalState,
+    RentalStatusResponse, RentalStatusWithSshResponse, ResourceRequirementsRequest, SshAccess,
     StartRentalApiRequest,
 };
 use basilica_sdk::ApiError;
@@ -21,11 +28,15 @@ use basilica_validator::gpu::categorization::GpuCategory;
 use color_eyre::eyre::eyre;
 use color_eyre::Section;
 use console::style;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use indicatif::{HumanBytes, ProgressBar};
 use reqwest::StatusCode;
 use std::fmt;
+use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
 use uuid::Uuid;
 
@@ -99,6 +110,8 @@ pub async fn handle_ls(
         min_gpu_memory: filters.memory_min,
         gpu_type,
         min_gpu_count: Some(filters.gpu_min.unwrap_or(0)),
+        mig_profile: None,
+        max_staleness: None,
         location: filters.country.map(|country| LocationProfile {
             city: None,
             region: None,
@@ -147,9 +160,11 @@ pub async fn handle_ls(
 pub async fn handle_up(
     target: Option<TargetType>,
     options: UpOptions,
+    json: bool,
     config: &CliConfig,
 ) -> Result<(), CliError> {
     let api_client = create_authenticated_client(config).await?;
+    let mig_profile = options.mig_profile.map(|profile| profile.to_string());
 
     // Parse the target to determine executor selection strategy
     let executor_selection = if let Some(target_type) = target {
@@ -169,12 +184,22 @@ pub async fn handle_up(
                         min_memory_gb: 0, // Default, no minimum memory requirement
                         gpu_type: Some(gpu_category.as_str()),
                         gpu_count: options.gpu_min.unwrap_or(0),
+                        mig_profile: mig_profile.clone(),
                     },
                 }
             }
         }
     } else {
-        // No target specified - use interactive selection
+        // No target specified - interactive selection needs a terminal
+        if json || !console::Term::stdout().is_term() {
+            return Err(eyre!("No executor or GPU type specified")
+                .suggestion("Pass an executor ID or GPU type, e.g. 'basilica up h100'")
+                .note(
+                    "Interactive executor selection is only available in a terminal without --json",
+                )
+                .into());
+        }
+
         let spinner = create_spinner("Fetching available executors...");
 
         // Build query from options
@@ -183,6 +208,8 @@ pub async fn handle_up(
             min_gpu_memory: None,
             gpu_type: None,
             min_gpu_count: options.gpu_min,
+            mig_profile: mig_profile.clone(),
+            max_staleness: None,
             location: options.country.as_ref().map(|country| LocationProfile {
                 city: None,
                 region: None,
@@ -204,11 +231,15 @@ pub async fn handle_up(
         // Compact mode uses grouped selector, otherwise use detailed selector
         let selector = crate::interactive::InteractiveSelector::new();
         let use_detailed = !options.compact;
-        selector.select_executor(
+        let mut selection = selector.select_executor(
             &response.available_executors,
             use_detailed,
             options.detailed,
-        )?
+        )?;
+        if let ExecutorSelection::GpuRequirements { gpu_requirements } = &mut selection {
+            gpu_requirements.mig_profile = mig_profile.clone();
+        }
+        selection
     };
 
     let spinner = create_spinner("Preparing rental request...");
@@ -219,7 +250,10 @@ pub async fn handle_up(
         complete_spinner_error(spinner.clone(), "SSH key validation failed");
     })?;
 
-    let container_image = options.image.unwrap_or_else(|| config.image.name.clone());
+    let container_image = basilica_common::resolve_container_image(
+        options.image.as_deref(),
+        Some(&config.image.name),
+    );
 
     let env_vars = parse_env_vars(&options.env)
         .map_err(|e| eyre!("Invalid argument: {}", e.to_string()))
@@ -252,6 +286,8 @@ pub async fn handle_up(
         container_image,
         ssh_public_key,
         environment: env_vars,
+        secrets: Vec::new(),
+        registry_auth: None,
         ports: port_mappings,
         resources: ResourceRequirementsRequest {
             cpu_cores: options.cpu_cores.unwrap_or(0.0),
@@ -263,6 +299,10 @@ pub async fn handle_up(
         command,
         volumes: vec![],
         no_ssh: options.no_ssh,
+        idle_timeout_secs: options.idle_timeout.map(|minutes| minutes * 60),
+        log_format: options.log_format,
+        pre_stop_command: Vec::new(),
+        pre_stop_timeout_secs: None,
     };
 
     spinner.set_message("Creating rental...");
@@ -306,63 +346,82 @@ pub async fn handle_up(
         }
     };
 
-    if options.detach {
-        // Detached mode: just show instructions and exit
+    if options.no_wait {
+        // Return immediately without checking reachability
         display_ssh_connection_instructions(
             &response.rental_id,
             ssh_creds,
             config,
             "SSH connection options:",
         )?;
-    } else {
-        // Auto-SSH mode: wait for rental to be active and connect
-        print_info("Waiting for rental to become active...");
-
-        // Poll for rental to become active
-        let rental_active = poll_rental_status(&response.rental_id, &api_client).await?;
-
-        if rental_active {
-            // Parse SSH credentials and connect
-            print_info("Connecting to rental...");
-            let (host, port, username) = parse_ssh_credentials(ssh_creds)?;
-            let ssh_access = SshAccess {
-                host,
-                port,
-                username,
-            };
+        return Ok(());
+    }
 
-            // Use SSH client to open interactive session
-            let ssh_client = SshClient::new(&config.ssh)?;
-            match ssh_client.interactive_session(&ssh_access).await {
-                Ok(_) => {
-                    // SSH session ended normally
-                    print_info("SSH session closed");
-                    display_ssh_connection_instructions(
-                        &response.rental_id,
-                        ssh_creds,
-                        config,
-                        "To reconnect to this rental:",
-                    )?;
-                }
-                Err(e) => {
-                    print_error(&format!("SSH connection failed: {}", e));
-                    display_ssh_connection_instructions(
-                        &response.rental_id,
-                        ssh_creds,
-                        config,
-                        "Try manually connecting using:",
-                    )?;
-                }
-            }
-        } else {
-            // Timeout or error - show manual instructions
-            print_info("Rental is taking longer than expected to become active");
+    // Wait until the rental is active and its SSH port accepts connections
+    let cancel = cancel_on_ctrl_c();
+    let ready = wait_for_ssh_ready(&response.rental_id, ssh_creds, &api_client, &cancel).await;
+    if let Err(CliError::Cancelled) = ready {
+        print_info(&format!(
+            "Stopped waiting, rental {} keeps running. Stop it with 'basilica down {}'",
+            response.rental_id, response.rental_id
+        ));
+    }
+    let ssh_access = match ready? {
+        Some(ssh_access) => ssh_access,
+        None => {
+            print_info(&format!(
+                "SSH was not reachable within {}s, the rental may still be starting",
+                SSH_READY_TIMEOUT.as_secs()
+            ));
             display_ssh_connection_instructions(
                 &response.rental_id,
                 ssh_creds,
                 config,
                 "You can manually connect once it's ready using:",
-            )?
+            )?;
+            return Ok(());
+        }
+    };
+
+    print_success(&format!(
+        "SSH is ready: {}",
+        style(format_ssh_command(&ssh_access, config)).cyan()
+    ));
+
+    if options.detach {
+        // Detached mode: just show instructions and exit
+        display_ssh_connection_instructions(
+            &response.rental_id,
+            ssh_creds,
+            config,
+            "SSH connection options:",
+        )?;
+    } else {
+        // Auto-SSH mode: connect now that SSH is reachable
+        print_info("Connecting to rental...");
+
+        // Use SSH client to open interactive session
+        let ssh_client = SshClient::new(&config.ssh)?;
+        match ssh_client.interactive_session(&ssh_access).await {
+            Ok(_) => {
+                // SSH session ended normally
+                print_info("SSH session closed");
+                display_ssh_connection_instructions(
+                    &response.rental_id,
+                    ssh_creds,
+                    config,
+                    "To reconnect to this rental:",
+                )?;
+            }
+            Err(e) => {
+                print_error(&format!("SSH connection failed: {}", e));
+                display_ssh_connection_instructions(
+                    &response.rental_id,
+                    ssh_creds,
+                    config,
+                    "Try manually connecting using:",
+                )?;
+            }
         }
     }
 
@@ -405,9 +464,10 @@ pub async fn handle_ps(filters: PsFilters, json: bool, config: &CliConfig) -> Re
     Ok(())
 }
 
-/// Handle the `status` command - check rental status
+/// Handle the `status` command - show rental status, optionally refreshing on an interval
 pub async fn handle_status(
     target: Option<String>,
+    watch: Option<Duration>,
     json: bool,
     config: &CliConfig,
 ) -> Result<(), CliError> {
@@ -416,13 +476,87 @@ pub async fn handle_status(
     // Resolve target rental (fetch and prompt if not provided)
     let target = resolve_target_rental(target, &api_client, false).await?;
 
-    let spinner = create_spinner("Checking rental status...");
+    let Some(interval) = watch else {
+        let spinner = create_spinner("Checking rental status...");
+        let status = fetch_rental_status(&api_client, &target)
+            .await
+            .inspect_err(|_| complete_spinner_error(spinner.clone(), "Failed to get status"))?;
+        complete_spinner_and_clear(spinner);
 
-    let status = api_client
-        .get_rental_status(&target)
+        if json {
+            json_output(&status)?;
+        } else {
+            let cost = status.cost_so_far.clone();
+            let energy_kwh = status.energy_kwh;
+            display_rental_status(&into_display_status(status));
+            if let Some(cost) = &cost {
+                display_rental_cost(cost);
+            }
+            if let Some(kwh) = energy_kwh {
+                display_rental_energy(kwh);
+            }
+        }
+        return Ok(());
+    };
+
+    let term = &console::Term::stdout();
+    let api_client = &api_client;
+    let target = target.as_str();
+    let cancel = cancel_on_ctrl_c();
+    run_watch_loop(interval, cancel.cancelled(), move || async move {
+        let status = fetch_rental_status(api_client, target).await?;
+        if json {
+            json_line_output(&status)?;
+            return Ok(());
+        }
+
+        let cost = status.cost_so_far.clone();
+        let energy_kwh = status.energy_kwh;
+        let status = into_display_status(status);
+        let _ = term.clear_screen();
+        table_output::display_rentals(std::slice::from_ref(&status))?;
+        table_output::display_gpu_usage(&status.gpu_usage)?;
+        if let Some(cost) = &cost {
+            display_rental_cost(cost);
+        }
+        if let Some(kwh) = energy_kwh {
+            display_rental_energy(kwh);
+        }
+        println!(
+            "\n{}",
+            style(format!(
+                "Updated {} - refreshing every {}s, press Ctrl-C to stop",
+                chrono::Local::now().format("%H:%M:%S"),
+                interval.as_secs()
+            ))
+            .dim()
+        );
+        Ok::<(), CliError>(())
+    })
+    .await
+}
+
+/// Drop SSH credentials and keep the validator's view of the rental for display
+fn into_display_status(status: RentalStatusWithSshResponse) -> RentalStatusResponse {
+    RentalStatusResponse {
+        rental_id: status.rental_id,
+        status: status.status,
+        executor: status.executor,
+        created_at: status.created_at,
+        updated_at: status.updated_at,
+        gpu_usage: status.gpu_usage,
+        state_history: status.state_history,
+    }
+}
+
+async fn fetch_rental_status(
+    api_client: &basilica_sdk::BasilicaClient,
+    target: &str,
+) -> Result<RentalStatusWithSshResponse, CliError> {
+    api_client
+        .get_rental_status(target)
         .await
         .map_err(|e| -> CliError {
-            complete_spinner_error(spinner.clone(), "Failed to get status");
             let report = match e {
                 ApiError::NotFound { .. } => eyre!("Rental '{}' not found", target)
                     .suggestion("Try 'basilica ps' to see your active rentals")
@@ -430,25 +564,7 @@ pub async fn handle_status(
                 _ => eyre!(e).suggestion("Check your internet connection and try again"),
             };
             CliError::Internal(report)
-        })?;
-
-    complete_spinner_and_clear(spinner);
-
-    if json {
-        json_output(&status)?;
-    } else {
-        // Convert to validator's RentalStatusResponse for display (without SSH credentials)
-        let display_status = RentalStatusResponse {
-            rental_id: status.rental_id,
-            status: status.status,
-            executor: status.executor,
-            created_at: status.created_at,
-            updated_at: status.updated_at,
-        };
-        display_rental_status(&display_status);
-    }
-
-    Ok(())
+        })
 }
 
 /// Handle the `logs` command - view rental logs
@@ -507,14 +623,6 @@ pub async fn handle_logs(
     // Parse and display SSE stream
     use eventsource_stream::Eventsource;
     use futures::StreamExt;
-    use serde::Deserialize;
-
-    #[derive(Debug, Deserialize)]
-    struct LogEntry {
-        timestamp: chrono::DateTime<chrono::Utc>,
-        stream: String,
-        message: String,
-    }
 
     complete_spinner_and_clear(spinner);
 
@@ -527,20 +635,29 @@ pub async fn handle_logs(
 
     futures::pin_mut!(stream);
 
-    while let Some(event) = stream.next().await {
+    // Ctrl-C stops following and closes the stream
+    let cancel = cancel_on_ctrl_c();
+    loop {
+        let event = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => break,
+            event = stream.next() => event,
+        };
+        let Some(event) = event else {
+            break;
+        };
         match event {
             Ok(sse_event) => {
                 // Parse the data field as JSON
-                match serde_json::from_str::<LogEntry>(&sse_event.data) {
+                match serde_json::from_str::<RentalLogLine>(&sse_event.data) {
                     Ok(entry) => {
-                        let timestamp = entry.timestamp.format("%Y-%m-%d %H:%M:%S%.3f");
-                        let stream_indicator = match entry.stream.as_str() {
-                            "stdout" => "OUT",
-                            "stderr" => "ERR",
-                            "error" => "ERR",
-                            _ => &entry.stream,
-                        };
-                        println!("[{} {}] {}", timestamp, stream_indicator, entry.message);
+                        // Stream errors are always shown
+                        if let Some(min) = options.level {
+                            if entry.stream != "error" && !entry.is_at_least(min) {
+                                continue;
+                            }
+                        }
+                        println!("{}", format_log_line(&entry));
                     }
                     Err(e) => {
                         debug!("Failed to parse log event: {}, data: {}", e, sse_event.data);
@@ -557,92 +674,140 @@ pub async fn handle_logs(
     Ok(())
 }
 
+/// Format a log line for the terminal, colored by its level when it has one
+fn format_log_line(entry: &RentalLogLine) -> String {
+    let timestamp = entry.timestamp.format("%Y-%m-%d %H:%M:%S%.3f");
+    let stream_indicator = match entry.stream.as_str() {
+        "stdout" => "OUT",
+        "stderr" => "ERR",
+        "error" => "ERR",
+        _ => &entry.stream,
+    };
+    let Some(level) = entry.level else {
+        return format!("[{} {}] {}", timestamp, stream_indicator, entry.message);
+    };
+
+    let label = format!("{:<5}", level.as_str().to_uppercase());
+    let label = match level {
+        LogLevel::Trace | LogLevel::Debug => style(label).dim(),
+        LogLevel::Info => style(label).green(),
+        LogLevel::Warn => style(label).yellow(),
+        LogLevel::Error => style(label).red().bold(),
+    };
+    format!(
+        "[{} {}] {} {}",
+        timestamp, stream_indicator, label, entry.message
+    )
+}
+
 /// Handle the `down` command - terminate rental
 pub async fn handle_down(
     target: Option<String>,
     all: bool,
+    status: Option<RentalState>,
+    yes: bool,
     config: &CliConfig,
 ) -> Result<(), CliError> {
     let api_client = create_authenticated_client(config).await?;
 
-    if all {
-        // Stop all active rentals
-        let spinner = create_spinner("Fetching active rentals...");
+    if all || status.is_some() {
+        let spinner = create_spinner("Fetching rentals...");
 
-        // Fetch all active rentals
-        let query = Some(ListRentalsQuery {
-            status: Some(RentalState::Active),
+        // Without a status, --all covers every rental that is not already stopped
+        let filter = || ListRentalsQuery {
+            status: status.clone(),
             gpu_type: None,
             min_gpu_count: None,
-        });
+        };
 
-        let rentals_list = api_client
-            .list_rentals(query)
-            .await
-            .map_err(|e| -> CliError {
-                complete_spinner_error(spinner.clone(), "Failed to fetch rentals");
-                CliError::Internal(eyre!(e).wrap_err("Failed to fetch active rentals"))
-            })?;
+        let rentals_list =
+            api_client
+                .list_rentals(Some(filter()))
+                .await
+                .map_err(|e| -> CliError {
+                    complete_spinner_error(spinner.clone(), "Failed to fetch rentals");
+                    CliError::Internal(eyre!(e).wrap_err("Failed to fetch rentals"))
+                })?;
 
         complete_spinner_and_clear(spinner);
 
-        if rentals_list.rentals.is_empty() {
-            println!("No active rentals found.");
+        let matching = rentals_list
+            .rentals
+            .iter()
+            .filter(|rental| match &status {
+                Some(status) => rental.state == *status,
+                None => !matches!(rental.state, RentalState::Stopped | RentalState::Stopping),
+            })
+            .count();
+
+        if matching == 0 {
+            println!("No matching rentals found.");
             return Ok(());
         }
 
-        let total_rentals = rentals_list.rentals.len();
-        println!(
-            "Found {} active rental{} to stop.",
-            total_rentals,
-            if total_rentals == 1 { "" } else { "s" }
-        );
-
-        let mut success_count = 0;
-        let mut failed_rentals = Vec::new();
+        let plural = if matching == 1 { "" } else { "s" };
+        println!("Found {} rental{} to stop.", matching, plural);
+
+        if !yes {
+            if !std::io::stdin().is_terminal() {
+                return Err(CliError::Internal(
+                    eyre!(
+                        "Refusing to stop {} rental{} without confirmation",
+                        matching,
+                        plural
+                    )
+                    .suggestion("Pass --yes to stop rentals from scripts"),
+                ));
+            }
+            let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Stop {} rental{}?", matching, plural))
+                .default(false)
+                .interact()
+                .map_err(|e| CliError::Internal(e.into()))?;
+            if !confirmed {
+                println!("Termination cancelled.");
+                return Ok(());
+            }
+        }
 
-        // Stop each rental one by one
-        for rental in rentals_list.rentals {
-            let rental_id = &rental.rental_id;
-            let spinner = create_spinner(&format!("Terminating rental: {}", rental_id));
+        let spinner = create_spinner(&format!("Terminating {} rental{}...", matching, plural));
+        let report =
+            api_client
+                .terminate_rentals(filter(), None)
+                .await
+                .map_err(|e| -> CliError {
+                    complete_spinner_error(spinner.clone(), "Failed to terminate rentals");
+                    CliError::Internal(eyre!(e).wrap_err("Failed to fetch rentals"))
+                })?;
+        complete_spinner_and_clear(spinner);
 
-            match api_client.stop_rental(rental_id).await {
-                Ok(_) => {
-                    complete_spinner_and_clear(spinner);
-                    print_success(&format!("Successfully stopped rental: {}", rental_id));
-                    success_count += 1;
-                }
-                Err(e) => {
-                    complete_spinner_error(
-                        spinner,
-                        &format!("Failed to terminate rental: {}", rental_id),
-                    );
-                    failed_rentals.push((rental_id.clone(), e));
-                }
-            }
+        for rental_id in &report.terminated {
+            print_success(&format!("Successfully stopped rental: {}", rental_id));
         }
 
         // Print summary
         println!();
-        if failed_rentals.is_empty() {
+        if report.is_success() {
             print_success(&format!(
                 "Successfully stopped all {} rental{}.",
-                success_count,
-                if success_count == 1 { "" } else { "s" }
+                report.terminated.len(),
+                if report.terminated.len() == 1 {
+                    ""
+                } else {
+                    "s"
+                }
             ));
         } else {
             print_success(&format!(
                 "Successfully stopped {} out of {} rental{}.",
-                success_count,
-                total_rentals,
-                if total_rentals == 1 { "" } else { "s" }
+                report.terminated.len(),
+                report.total(),
+                if report.total() == 1 { "" } else { "s" }
             ));
 
-            if !failed_rentals.is_empty() {
-                println!("\nFailed to stop the following rentals:");
-                for (rental_id, error) in failed_rentals {
-                    println!("  - {}: {}", rental_id, error);
-                }
+            println!("\nFailed to stop the following rentals:");
+            for (rental_id, error) in &report.failed {
+                println!("  - {}: {}", rental_id, error);
             }
         }
     } else {
@@ -779,56 +944,24 @@ pub async fn handle_ssh(
     Ok(())
 }
 
-/// Handle the `cp` command - copy files via SSH
+/// Handle the `cp` command - copy files and directories via SSH
 pub async fn handle_cp(
     source: String,
     destination: String,
+    recursive: bool,
+    resume: bool,
+    json: bool,
     config: &CliConfig,
 ) -> Result<(), CliError> {
     debug!("Copying files from {} to {}", source, destination);
 
+    // Exactly one side must name a rental
+    let request = CopyRequest::parse(&source, &destination)?;
+    let rental_id = request.rental_id.clone();
+
     // Create API client
     let api_client = create_authenticated_client(config).await?;
 
-    // Parse source and destination to check if rental ID is provided
-    let (source_rental, source_path) = split_remote_path(&source);
-    let (dest_rental, dest_path) = split_remote_path(&destination);
-
-    // Determine rental_id, handling interactive selection if needed
-    let (rental_id, is_upload, local_path, remote_path) = match (source_rental, dest_rental) {
-        (Some(rental), None) => {
-            // Download: remote -> local
-            (rental, false, dest_path, source_path)
-        }
-        (None, Some(rental)) => {
-            // Upload: local -> remote
-            (rental, true, source_path, dest_path)
-        }
-        (Some(_), Some(_)) => {
-            return Err(CliError::Internal(eyre!(
-                "Remote-to-remote copy not supported"
-            )));
-        }
-        (None, None) => {
-            // No rental ID provided, need to prompt user
-            // First determine if this looks like an upload or download based on path existence
-            let source_exists = std::path::Path::new(&source).exists();
-
-            // Resolve target rental with SSH requirement
-            let selected_rental = resolve_target_rental(None, &api_client, true).await
-                .map_err(|_| eyre!("No rental ID provided. Specify rental ID explicitly: 'basilica cp <rental_id>:<path> <local_path>' or vice versa"))?;
-
-            // Determine direction based on source file existence
-            if source_exists {
-                // Upload: local file exists, so source is local
-                (selected_rental, true, source, destination)
-            } else {
-                // Download: assume source is remote path
-                (selected_rental, false, destination, source)
-            }
-        }
-    };
-
     // Get rental status from API which includes SSH credentials
     let rental_status =
         api_client
@@ -863,42 +996,103 @@ pub async fn handle_cp(
     };
 
     // Use SSH client for file transfer
-    let ssh_client = SshClient::new(&config.ssh).map_err(|e| eyre!(e))?;
+    let cancel = cancel_on_ctrl_c();
+    let ssh_client = SshClient::new(&config.ssh)
+        .map_err(|e| eyre!(e))?
+        .with_cancellation(cancel.clone());
+    let remote = SshRemoteFs::new(&ssh_client, &ssh_access);
+
+    let progress = if json {
+        ProgressBar::hidden()
+    } else {
+        create_transfer_progress_bar(0)
+    };
+    let copy = transfer::copy(&remote, &request, recursive, resume, &progress);
+    let summary = match cancellable(&cancel, copy).await {
+        Ok(summary) => {
+            progress.finish_and_clear();
+            summary
+        }
+        // scp shares our terminal, so Ctrl-C can surface as a failed transfer
+        Err(_) if cancel.is_cancelled() => {
+            progress.finish_and_clear();
+            print_info("Copy interrupted, run it again with --resume to continue");
+            return Err(CliError::Cancelled);
+        }
+        Err(e) => {
+            progress.finish_and_clear();
+            return Err(e);
+        }
+    };
 
-    if is_upload {
-        ssh_client
-            .upload_file(&ssh_access, &local_path, &remote_path)
-            .await?;
-        Ok(())
+    if json {
+        json_output(&summary)?;
     } else {
-        ssh_client
-            .download_file(&ssh_access, &remote_path, &local_path)
-            .await?;
-        Ok(())
+        print_success(&format!(
+            "Copied {} file(s), {} from {} to {}",
+            summary.files,
+            HumanBytes(summary.bytes),
+            summary.source,
+            summary.destination
+        ));
+        if summary.resumed_bytes > 0 {
+            println!(
+                "  Resumed: {} was already on the rental",
+                HumanBytes(summary.resumed_bytes)
+            );
+        }
     }
+
+    Ok(())
 }
 
 // Helper functions
 
-/// Poll rental status until it becomes active or timeout
-async fn poll_rental_status(
+/// Maximum time to wait for a new rental's SSH endpoint to become reachable
+const SSH_READY_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Timeout for a single TCP probe of the SSH port
+const SSH_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Poll rental status until it is active and its SSH port accepts TCP connections
+///
+/// Returns `Ok(None)` if the rental did not become reachable before the timeout,
+/// and [`CliError::Cancelled`] if `cancel` fires first.
+async fn wait_for_ssh_ready(
+    rental_id: &str,
+    initial_credentials: &str,
+    api_client: &basilica_sdk::BasilicaClient,
+    cancel: &CancellationToken,
+) -> Result<Option<SshAccess>, CliError> {
+    let spinner = create_spinner("Waiting for rental to become active...");
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => {
+            complete_spinner_error(spinner, "Cancelled");
+            Err(CliError::Cancelled)
+        }
+        result = poll_ssh_ready(rental_id, initial_credentials, api_client, spinner.clone()) => result,
+    }
+}
+
+async fn poll_ssh_ready(
     rental_id: &str,
+    initial_credentials: &str,
     api_client: &basilica_sdk::BasilicaClient,
-) -> Result<bool, CliError> {
-    const MAX_WAIT_TIME: Duration = Duration::from_secs(60);
+    spinner: ProgressBar,
+) -> Result<Option<SshAccess>, CliError> {
     const INITIAL_INTERVAL: Duration = Duration::from_secs(2);
     const MAX_INTERVAL: Duration = Duration::from_secs(10);
 
-    let spinner = create_spinner("Waiting for rental to become active...");
     let start_time = std::time::Instant::now();
     let mut interval = INITIAL_INTERVAL;
     let mut attempt = 0;
 
     loop {
         // Check if we've exceeded the maximum wait time
-        if start_time.elapsed() > MAX_WAIT_TIME {
-            complete_spinner_error(spinner, "Timeout waiting for rental to become active");
-            return Ok(false);
+        if start_time.elapsed() > SSH_READY_TIMEOUT {
+            complete_spinner_error(spinner, "Timeout waiting for SSH to become reachable");
+            return Ok(None);
         }
 
         attempt += 1;
@@ -910,8 +1104,27 @@ async fn poll_rental_status(
                 use basilica_sdk::types::RentalStatus;
                 match status.status {
                     RentalStatus::Active => {
-                        complete_spinner_and_clear(spinner);
-                        return Ok(true);
+                        let credentials = status
+                            .ssh_credentials
+                            .as_deref()
+                            .unwrap_or(initial_credentials);
+                        let (host, port, username) = parse_ssh_credentials(credentials)?;
+
+                        spinner.set_message(format!(
+                            "Rental is active, waiting for SSH on {}:{}... ({}s elapsed)",
+                            host,
+                            port,
+                            start_time.elapsed().as_secs()
+                        ));
+
+                        if probe_tcp(&host, port).await {
+                            complete_spinner_and_clear(spinner);
+                            return Ok(Some(SshAccess {
+                                host,
+                                port,
+                                username,
+                            }));
+                        }
                     }
                     RentalStatus::Failed => {
                         complete_spinner_error(spinner, "Rental failed to start");
@@ -951,6 +1164,37 @@ async fn poll_rental_status(
     }
 }
 
+/// Check whether a TCP connection to the given address can be established
+async fn probe_tcp(host: &str, port: u16) -> bool {
+    match tokio::time::timeout(
+        SSH_PROBE_TIMEOUT,
+        tokio::net::TcpStream::connect((host, port)),
+    )
+    .await
+    {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => {
+            debug!("SSH port {}:{} not reachable yet: {}", host, port, e);
+            false
+        }
+        Err(_) => {
+            debug!("Timed out probing SSH port {}:{}", host, port);
+            false
+        }
+    }
+}
+
+/// Format the standard SSH command for connecting to a rental
+fn format_ssh_command(ssh_access: &SshAccess, config: &CliConfig) -> String {
+    format!(
+        "ssh -i {} -p {} {}@{}",
+        compress_path(&config.ssh.private_key_path),
+        ssh_access.port,
+        ssh_access.username,
+        ssh_access.host
+    )
+}
+
 /// Display SSH connection instructions after rental creation
 fn display_ssh_connection_instructions(
     rental_id: &str,
@@ -960,9 +1204,11 @@ fn display_ssh_connection_instructions(
 ) -> Result<(), CliError> {
     // Parse SSH credentials to get components
     let (host, port, username) = parse_ssh_credentials(ssh_credentials)?;
-
-    // Get the private key path from config
-    let private_key_path = &config.ssh.private_key_path;
+    let ssh_access = SshAccess {
+        host,
+        port,
+        username,
+    };
 
     println!();
     print_info(message);
@@ -982,15 +1228,9 @@ fn display_ssh_connection_instructions(
     println!("  2. Using standard SSH:");
     println!(
         "     {}",
-        console::style(format!(
-            "ssh -i {} -p {} {}@{}",
-            compress_path(private_key_path),
-            port,
-            username,
-            host
-        ))
-        .cyan()
-        .bold()
+        console::style(format_ssh_command(&ssh_access, config))
+            .cyan()
+            .bold()
     );
 
     Ok(())
@@ -1008,14 +1248,6 @@ fn load_ssh_public_key(key_path: &Option<PathBuf>, config: &CliConfig) -> Result
     })
 }
 
-fn split_remote_path(path: &str) -> (Option<String>, String) {
-    if let Some((rental_id, remote_path)) = path.split_once(':') {
-        (Some(rental_id.to_string()), remote_path.to_string())
-    } else {
-        (None, path.to_string())
-    }
-}
-
 fn display_rental_status(status: &RentalStatusResponse) {
     println!("Rental Status: {}", status.rental_id);
     println!("  Status: {:?}", status.status);
@@ -1028,6 +1260,25 @@ fn display_rental_status(status: &RentalStatusResponse) {
         "  Updated: {}",
         status.updated_at.format("%Y-%m-%d %H:%M:%S UTC")
     );
+    for gpu in &status.gpu_usage {
+        println!(
+            "  GPU {}: {:.0}% utilization, {} MB memory",
+            gpu.gpu_index, gpu.utilization_percent, gpu.memory_mb
+        );
+    }
+
+    if !status.state_history.is_empty() {
+        println!("\nTimeline:");
+        for transition in &status.state_history {
+            println!(
+                "  {}  {} -> {}  {}",
+                transition.at.format("%Y-%m-%d %H:%M:%S UTC"),
+                transition.from,
+                transition.to,
+                transition.reason
+            );
+        }
+    }
 
     // println!("\nExecutor Details:");
     // println!("  GPUs: {} available", status.executor.gpu_specs.len());
@@ -1045,6 +1296,23 @@ fn display_rental_status(status: &RentalStatusResponse) {
     // }
 }
 
+fn display_rental_cost(cost: &CostBreakdown) {
+    let billed = |cost: &Option<Money>| match cost {
+        Some(cost) => cost.to_string(),
+        None => "included".to_string(),
+    };
+    println!("\nCost so far:");
+    println!("  Compute: {}", cost.compute);
+    println!("  Memory:  {}", billed(&cost.memory));
+    println!("  Network: {}", billed(&cost.network));
+    println!("  Storage: {}", billed(&cost.storage));
+    println!("  Total:   {}", cost.total);
+}
+
+fn display_rental_energy(kwh: f64) {
+    println!("\nGPU energy so far: {kwh:.3} kWh");
+}
+
 /// Display quick start commands after ps output
 fn display_ps_quick_start_commands() {
     println!();
@@ -4,6 +4,9 @@ use crate::progress::{complete_spinner_and_clear, complete_spinner_error, create
 use basilica_sdk::types::{ListRentalsQuery, RentalState};
 use basilica_sdk::BasilicaClient;
 use color_eyre::eyre::{eyre, Result};
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
 
 /// Resolve target rental ID - if not provided, fetch active rentals and prompt for selection
 ///
@@ -65,3 +68,110 @@ pub async fn resolve_target_rental(
     let selector = crate::interactive::InteractiveSelector::new();
     Ok(selector.select_rental(&eligible_rentals, false)?)
 }
+
+/// Run `tick` immediately and then every `interval` until `cancel` completes.
+///
+/// Cancellation also interrupts a tick that is still in flight. Errors from `tick` end
+/// the loop and are returned to the caller.
+pub async fn run_watch_loop<F, Fut, E>(
+    interval: Duration,
+    cancel: impl Future<Output = ()>,
+    mut tick: F,
+) -> std::result::Result<(), E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<(), E>>,
+{
+    let mut ticker = tokio::time::interval(interval);
+    // A slow refresh should push the next one back rather than trigger a burst
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    tokio::pin!(cancel);
+
+    loop {
+        tokio::select! {
+            biased;
+            _ = &mut cancel => return Ok(()),
+            _ = ticker.tick() => {}
+        }
+        tokio::select! {
+            biased;
+            _ = &mut cancel => return Ok(()),
+            result = tick() => result?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use tokio::time::Instant;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_loop_honors_interval_and_cancellation() {
+        let start = Instant::now();
+        let ticks = RefCell::new(Vec::new());
+        let recorded = &ticks;
+
+        run_watch_loop(
+            Duration::from_secs(10),
+            tokio::time::sleep(Duration::from_secs(35)),
+            move || async move {
+                recorded.borrow_mut().push(start.elapsed());
+                Ok::<(), ()>(())
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            ticks.into_inner(),
+            vec![
+                Duration::ZERO,
+                Duration::from_secs(10),
+                Duration::from_secs(20),
+                Duration::from_secs(30)
+            ]
+        );
+        assert_eq!(start.elapsed(), Duration::from_secs(35));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_loop_cancels_in_flight_tick() {
+        let start = Instant::now();
+
+        run_watch_loop(
+            Duration::from_secs(1),
+            tokio::time::sleep(Duration::from_secs(5)),
+            || async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok::<(), ()>(())
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(start.elapsed(), Duration::from_secs(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_loop_stops_on_tick_error() {
+        let mut calls = 0;
+
+        let result = run_watch_loop(Duration::from_secs(1), std::future::pending(), || {
+            calls += 1;
+            let n = calls;
+            async move {
+                if n == 3 {
+                    Err("rental gone")
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Err("rental gone"));
+        assert_eq!(calls, 3);
+    }
+}
@@ -1,6 +1,7 @@
 //! Command handlers for the Basilica CLI
 
 pub mod auth;
+pub mod doctor;
 pub mod external;
 pub mod gpu_rental;
 pub mod gpu_rental_helpers;
@@ -6,7 +6,8 @@ use etcetera::{choose_base_strategy, BaseStrategy};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tracing::{debug, info};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info, warn};
 
 use crate::CliError;
 
@@ -46,6 +47,61 @@ impl Default for ApiConfig {
     }
 }
 
+/// Environment variable overriding `api.base_url`
+pub const API_URL_ENV: &str = "BASILICA_API_URL";
+
+/// Environment variable overriding `api.request_timeout` (seconds)
+pub const API_TIMEOUT_ENV: &str = "BASILICA_API_TIMEOUT";
+
+/// Per-invocation overrides for API settings
+///
+/// Precedence is: CLI flag > environment variable > config file > default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiOverrides {
+    /// Override for the API base URL
+    pub base_url: Option<String>,
+    /// Override for the request timeout in seconds
+    pub request_timeout: Option<u64>,
+}
+
+impl ApiOverrides {
+    /// Resolve overrides from CLI flags, falling back to environment variables
+    pub fn resolve(flag_url: Option<String>, flag_timeout: Option<u64>) -> Result<Self, CliError> {
+        Self::resolve_with(flag_url, flag_timeout, |key| std::env::var(key).ok())
+    }
+
+    /// Resolve overrides using the provided environment lookup
+    fn resolve_with(
+        flag_url: Option<String>,
+        flag_timeout: Option<u64>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, CliError> {
+        let base_url = flag_url.or_else(|| env(API_URL_ENV).filter(|v| !v.trim().is_empty()));
+
+        let request_timeout = match flag_timeout {
+            Some(timeout) => Some(timeout),
+            None => env(API_TIMEOUT_ENV)
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| {
+                    v.trim().parse::<u64>().map_err(|_| -> CliError {
+                        eyre!(
+                            "Invalid {} value '{}': expected a number of seconds",
+                            API_TIMEOUT_ENV,
+                            v
+                        )
+                        .into()
+                    })
+                })
+                .transpose()?,
+        };
+
+        Ok(Self {
+            base_url,
+            request_timeout,
+        })
+    }
+}
+
 /// SSH configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SshConfig {
@@ -96,14 +152,14 @@ impl SshConfig {
 /// Docker image configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageConfig {
-    /// Default Docker image name
+    /// Docker image used when a rental does not request one
     pub name: String,
 }
 
 impl Default for ImageConfig {
     fn default() -> Self {
         Self {
-            name: "nvidia/cuda:12.8.0-runtime-ubuntu22.04".to_string(),
+            name: basilica_common::DEFAULT_CONTAINER_IMAGE.to_string(),
         }
     }
 }
@@ -205,6 +261,18 @@ impl CliConfig {
         Ok(config)
     }
 
+    /// Apply per-invocation API overrides on top of the loaded configuration
+    pub fn apply_api_overrides(&mut self, overrides: &ApiOverrides) {
+        if let Some(base_url) = &overrides.base_url {
+            debug!("Overriding API base URL: {}", base_url);
+            self.api.base_url = base_url.trim_end_matches('/').to_string();
+        }
+        if let Some(timeout) = overrides.request_timeout {
+            debug!("Overriding API request timeout: {}s", timeout);
+            self.api.request_timeout = timeout;
+        }
+    }
+
     /// Expand tilde (~) in path fields
     fn expand_paths(&mut self) {
         if let Some(path_str) = self.ssh.key_path.to_str() {
@@ -272,7 +340,7 @@ impl CliConfig {
         let content =
             toml::to_string_pretty(&compressed_config).wrap_err("Failed to serialize config")?;
 
-        tokio::fs::write(path, content)
+        write_atomic(path, content.as_bytes())
             .await
             .map_err(|e| eyre!("Failed to write config file: {}", e))?;
 
@@ -381,6 +449,9 @@ impl CliCache {
     }
 
     /// Load cache from specific path
+    ///
+    /// A cache that cannot be parsed is moved aside and replaced by an empty
+    /// one, so a damaged file never blocks the CLI.
     pub async fn load_from_file(path: &Path) -> Result<Self, CliError> {
         if !path.exists() {
             return Ok(Self::default());
@@ -390,10 +461,25 @@ impl CliCache {
             .await
             .map_err(|e| eyre!("Failed to read cache file: {}", e))?;
 
-        let cache: Self =
-            serde_json::from_str(&content).map_err(|e| eyre!("Failed to parse cache: {}", e))?;
-
-        Ok(cache)
+        match serde_json::from_str(&content) {
+            Ok(cache) => Ok(cache),
+            Err(parse_error) => {
+                let backup = back_up_corrupt_file(path).await.map_err(|e| {
+                    eyre!(
+                        "Failed to parse cache ({}) and to back it up: {}",
+                        parse_error,
+                        e
+                    )
+                })?;
+                warn!(
+                    "Cache file {} is corrupt ({}); moved it to {} and starting with an empty cache",
+                    path.display(),
+                    parse_error,
+                    backup.display()
+                );
+                Ok(Self::default())
+            }
+        }
     }
 
     /// Save cache to default location
@@ -414,10 +500,252 @@ impl CliCache {
         let content = serde_json::to_string_pretty(self)
             .map_err(|e| eyre!("Failed to serialize cache: {}", e))?;
 
-        tokio::fs::write(path, content)
+        write_atomic(path, content.as_bytes())
             .await
             .map_err(|e| eyre!("Failed to write cache file: {}", e))?;
 
         Ok(())
     }
 }
+
+/// Write `contents` to `path` without ever leaving a partially written file
+///
+/// The data goes to a temporary file in the same directory, which is synced
+/// and then renamed over `path`.
+async fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "path has no file name")
+    })?;
+    let tmp_path = path.with_file_name(format!(
+        ".{}.tmp-{}",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let result = async {
+        let mut file = tokio::fs::File::create(&tmp_path).await?;
+        file.write_all(contents).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&tmp_path, path).await
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
+    }
+    result
+}
+
+/// Move an unreadable file aside for inspection and return its new path
+async fn back_up_corrupt_file(path: &Path) -> std::io::Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let backup = path.with_file_name(format!(
+        "{}.corrupt-{}",
+        file_name,
+        chrono::Utc::now().format("%Y%m%dT%H%M%S")
+    ));
+    tokio::fs::rename(path, &backup).await?;
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_config() -> CliConfig {
+        toml::from_str(
+            r#"
+            [api]
+            base_url = "https://file.example.com"
+            request_timeout = 60
+
+            [ssh]
+            key_path = "~/.ssh/id.pub"
+            private_key_path = "~/.ssh/id"
+
+            [image]
+            name = "ubuntu:22.04"
+
+            [wallet]
+            default_wallet = "default"
+            base_wallet_path = "~/.bittensor/wallets"
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn env_with(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn test_defaults_without_overrides() {
+        let overrides = ApiOverrides::resolve_with(None, None, env_with(&[])).unwrap();
+        let mut config = CliConfig::default();
+        config.apply_api_overrides(&overrides);
+
+        assert_eq!(config.api.base_url, ApiConfig::default().base_url);
+        assert_eq!(
+            config.api.request_timeout,
+            ApiConfig::default().request_timeout
+        );
+    }
+
+    #[test]
+    fn test_file_values_without_overrides() {
+        let overrides = ApiOverrides::resolve_with(None, None, env_with(&[])).unwrap();
+        let mut config = file_config();
+        config.apply_api_overrides(&overrides);
+
+        assert_eq!(config.api.base_url, "https://file.example.com");
+        assert_eq!(config.api.request_timeout, 60);
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let env = env_with(&[
+            (API_URL_ENV, "https://env.example.com/"),
+            (API_TIMEOUT_ENV, "30"),
+        ]);
+        let overrides = ApiOverrides::resolve_with(None, None, env).unwrap();
+        let mut config = file_config();
+        config.apply_api_overrides(&overrides);
+
+        assert_eq!(config.api.base_url, "https://env.example.com");
+        assert_eq!(config.api.request_timeout, 30);
+    }
+
+    #[test]
+    fn test_flag_overrides_env() {
+        let env = env_with(&[
+            (API_URL_ENV, "https://env.example.com"),
+            (API_TIMEOUT_ENV, "30"),
+        ]);
+        let overrides =
+            ApiOverrides::resolve_with(Some("https://flag.example.com".to_string()), Some(10), env)
+                .unwrap();
+        let mut config = file_config();
+        config.apply_api_overrides(&overrides);
+
+        assert_eq!(config.api.base_url, "https://flag.example.com");
+        assert_eq!(config.api.request_timeout, 10);
+    }
+
+    #[tokio::test]
+    async fn test_nested_env_override_beats_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        file_config().save_to_path(&config_path).await.unwrap();
+
+        std::env::set_var("BASILCA_API__REQUEST_TIMEOUT", "45");
+        let config = CliConfig::load_from_file(&config_path);
+        std::env::remove_var("BASILCA_API__REQUEST_TIMEOUT");
+
+        let config = config.unwrap();
+        assert_eq!(config.api.request_timeout, 45);
+        assert_eq!(config.api.base_url, "https://file.example.com");
+    }
+
+    #[test]
+    fn test_default_image_matches_sdk_and_validator() {
+        let cli_default = CliConfig::default().image.name;
+        assert_eq!(cli_default, basilica_sdk::client::DEFAULT_CONTAINER_IMAGE);
+        assert_eq!(
+            cli_default,
+            basilica_validator::api::rental_routes::StartRentalRequest::default().container_image
+        );
+        assert_eq!(
+            basilica_common::resolve_container_image(None, Some(&cli_default)),
+            basilica_common::resolve_container_image(None, None)
+        );
+    }
+
+    #[test]
+    fn test_configured_image_used_unless_requested() {
+        let configured = file_config().image.name;
+        assert_eq!(
+            basilica_common::resolve_container_image(None, Some(&configured)),
+            "ubuntu:22.04"
+        );
+        assert_eq!(
+            basilica_common::resolve_container_image(Some("pytorch/pytorch"), Some(&configured)),
+            "pytorch/pytorch"
+        );
+    }
+
+    #[test]
+    fn test_invalid_env_timeout_is_rejected() {
+        let env = env_with(&[(API_TIMEOUT_ENV, "soon")]);
+        assert!(ApiOverrides::resolve_with(None, None, env).is_err());
+    }
+
+    fn registered_cache() -> CliCache {
+        let now = chrono::Utc::now();
+        CliCache {
+            registration: Some(RegistrationCache {
+                hotwallet: "5Fhotwallet".to_string(),
+                created_at: now,
+                last_updated: now,
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_truncated_cache_is_backed_up_and_reset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        registered_cache().save_to_path(&path).await.unwrap();
+
+        // Simulate a write interrupted halfway through
+        let content = std::fs::read_to_string(&path).unwrap();
+        let truncated = &content[..content.len() / 2];
+        std::fs::write(&path, truncated).unwrap();
+
+        let cache = CliCache::load_from_file(&path).await.unwrap();
+        assert!(cache.registration.is_none());
+        assert!(!path.exists());
+
+        let backups: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|p| p.to_string_lossy().contains("cache.json.corrupt-"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(std::fs::read_to_string(&backups[0]).unwrap(), truncated);
+
+        // The recovered cache can be saved and loaded again
+        registered_cache().save_to_path(&path).await.unwrap();
+        let reloaded = CliCache::load_from_file(&path).await.unwrap();
+        assert_eq!(reloaded.registration.unwrap().hotwallet, "5Fhotwallet");
+    }
+
+    #[tokio::test]
+    async fn test_atomic_save_leaves_no_temporary_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("cache.json");
+        let config_path = dir.path().join("config.toml");
+
+        CliCache::default().save_to_path(&cache_path).await.unwrap();
+        registered_cache().save_to_path(&cache_path).await.unwrap();
+        file_config().save_to_path(&config_path).await.unwrap();
+
+        let mut names: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["cache.json", "config.toml"]);
+
+        let cache = CliCache::load_from_file(&cache_path).await.unwrap();
+        assert!(cache.registration.is_some());
+        let config = CliConfig::load_from_file(&config_path).unwrap();
+        assert_eq!(config.api.base_url, "https://file.example.com");
+    }
+}
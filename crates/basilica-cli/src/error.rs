@@ -22,6 +22,10 @@ pub enum CliError {
     #[error("Failed to execute external component")]
     DelegationComponent(#[from] std::io::Error),
 
+    /// Interrupted by Ctrl-C
+    #[error("Operation cancelled")]
+    Cancelled,
+
     /// Everything else (using color-eyre's Report for rich errors)
     #[error(transparent)]
     Internal(#[from] Report),
@@ -1,16 +1,20 @@
 //! Interactive selection utilities
 
 use crate::error::Result;
-use basilica_sdk::types::{ApiRentalListItem, ExecutorSelection};
-use basilica_sdk::GpuRequirements;
+use basilica_sdk::types::{ApiRentalListItem, Currency, ExecutorSelection, Money};
+use basilica_sdk::{GpuRequirements, LocationProfile};
 use basilica_validator::api::types::AvailableExecutor;
 use basilica_validator::gpu::GpuCategory;
 use color_eyre::eyre::eyre;
 use console::Term;
-use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
+use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::str::FromStr;
 
+/// Number of executors above which the user is offered a filter before selection
+const FILTER_PROMPT_THRESHOLD: usize = 10;
+
 /// Interactive selector for CLI operations
 pub struct InteractiveSelector {
     theme: ColorfulTheme,
@@ -57,6 +61,8 @@ impl InteractiveSelector {
             gpu_info: String,
             cpu_info: String,
             ram_info: String,
+            price: String,
+            country: String,
             use_case: String,
         }
 
@@ -81,15 +87,12 @@ impl InteractiveSelector {
                 } else {
                     let gpu = &executor.executor.gpu_specs[0];
                     let gpu_display_name = gpu.name.clone(); // Full name in detailed mode
-                    if executor.executor.gpu_specs.len() > 1 {
-                        format!(
-                            "{}x {}",
-                            executor.executor.gpu_specs.len(),
-                            gpu_display_name
-                        )
-                    } else {
-                        format!("1x {}", gpu_display_name)
-                    }
+                    format!(
+                        "{}x {} ({}GB)",
+                        executor.executor.gpu_specs.len(),
+                        gpu_display_name,
+                        gpu.memory_gb
+                    )
                 };
 
                 // Format CPU info - handle "Unknown" case
@@ -111,6 +114,18 @@ impl InteractiveSelector {
                     format!("{}GB", executor.executor.cpu_specs.memory_gb)
                 };
 
+                // Extract country from the location string
+                let country = executor
+                    .executor
+                    .location
+                    .as_ref()
+                    .and_then(|loc| {
+                        LocationProfile::from_str(loc)
+                            .ok()
+                            .and_then(|profile| profile.country)
+                    })
+                    .unwrap_or_else(|| "Unknown".to_string());
+
                 // Get use case description for GPUs
                 let use_case = if executor.executor.gpu_specs.is_empty() {
                     "General compute".to_string()
@@ -127,6 +142,8 @@ impl InteractiveSelector {
                     gpu_info,
                     cpu_info,
                     ram_info,
+                    price: format_hourly_price(executor),
+                    country,
                     use_case,
                 }
             })
@@ -161,47 +178,88 @@ impl InteractiveSelector {
             .max()
             .unwrap_or(10);
 
+        let price_max_width = display_components
+            .iter()
+            .map(|c| c.price.len())
+            .max()
+            .unwrap_or(10);
+
+        let country_max_width = display_components
+            .iter()
+            .map(|c| c.country.len())
+            .max()
+            .unwrap_or(7);
+
         // Create formatted items for the selector
         let selector_items: Vec<String> = display_components
             .iter()
             .map(|components| {
                 if show_ids {
                     format!(
-                        "{:<id_width$} │ {:<gpu_width$} │ {:<cpu_width$} │ {:<ram_width$} │ {}",
+                        "{:<id_width$} │ {:<gpu_width$} │ {:<cpu_width$} │ {:<ram_width$} │ {:<price_width$} │ {:<country_width$} │ {}",
                         components.executor_id,
                         components.gpu_info,
                         components.cpu_info,
                         components.ram_info,
+                        components.price,
+                        components.country,
                         components.use_case,
                         id_width = id_max_width,
                         gpu_width = gpu_max_width,
                         cpu_width = cpu_max_width,
-                        ram_width = ram_max_width
+                        ram_width = ram_max_width,
+                        price_width = price_max_width,
+                        country_width = country_max_width
                     )
                 } else {
                     format!(
-                        "{:<gpu_width$} │ {:<cpu_width$} │ {:<ram_width$} │ {}",
+                        "{:<gpu_width$} │ {:<cpu_width$} │ {:<ram_width$} │ {:<price_width$} │ {:<country_width$} │ {}",
                         components.gpu_info,
                         components.cpu_info,
                         components.ram_info,
+                        components.price,
+                        components.country,
                         components.use_case,
                         gpu_width = gpu_max_width,
                         cpu_width = cpu_max_width,
-                        ram_width = ram_max_width
+                        ram_width = ram_max_width,
+                        price_width = price_max_width,
+                        country_width = country_max_width
                     )
                 }
             })
             .collect();
 
+        // Offer a filter when the list is too long to scan comfortably
+        let mut candidates: Vec<usize> = (0..selector_items.len()).collect();
+        if selector_items.len() > FILTER_PROMPT_THRESHOLD {
+            let filter: String = Input::with_theme(&self.theme)
+                .with_prompt("Filter executors (e.g. 'h100 US', empty for all)")
+                .allow_empty(true)
+                .interact_text()
+                .map_err(|e| eyre!("Filter input failed: {}", e))?;
+
+            candidates = filter_items(&selector_items, &filter);
+            if candidates.is_empty() {
+                return Err(eyre!("No executors match filter '{}'", filter.trim()).into());
+            }
+
+            let term = Term::stdout();
+            let _ = term.clear_last_lines(1);
+        }
+
+        let candidate_items: Vec<&String> =
+            candidates.iter().map(|&i| &selector_items[i]).collect();
+
         let selection = Select::with_theme(&self.theme)
             .with_prompt("Select executor")
-            .items(&selector_items)
+            .items(&candidate_items)
             .default(0)
             .interact_opt()
             .map_err(|e| eyre!("Selection failed: {}", e))?;
 
         let selection = match selection {
-            Some(s) => s,
+            Some(s) => candidates[s],
             None => return Err(eyre!("Selection cancelled").into()),
         };
 
@@ -323,6 +381,7 @@ impl InteractiveSelector {
                     gpu_type: Some(selected_config.1.clone()),
                     gpu_count: selected_config.2,
                     min_memory_gb: 0, // We match exact memory from the selection
+                    mig_profile: None,
                 },
             })
         }
@@ -481,8 +540,102 @@ impl InteractiveSelector {
     }
 }
 
+/// Hourly price of an executor, e.g. `$2.50/hr`, or `-` when it has none
+fn format_hourly_price(executor: &AvailableExecutor) -> String {
+    let Some(price) = executor.hourly_price.as_deref() else {
+        return "-".to_string();
+    };
+    let currency = executor.currency.as_deref().unwrap_or_default();
+    match (Decimal::from_str(price), Currency::from_str(currency)) {
+        (Ok(amount), Ok(currency)) => format!("{}/hr", Money::new(amount, currency)),
+        _ if currency.is_empty() => format!("{price}/hr"),
+        _ => format!("{price} {currency}/hr"),
+    }
+}
+
+/// Return indices of items matching every whitespace-separated term (case-insensitive)
+fn filter_items(items: &[String], filter: &str) -> Vec<usize> {
+    let terms: Vec<String> = filter
+        .split_whitespace()
+        .map(|t| t.to_lowercase())
+        .collect();
+
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            let item = item.to_lowercase();
+            terms.iter().all(|term| item.contains(term))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
 impl Default for InteractiveSelector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<String> {
+        [
+            "8x NVIDIA H100 80GB HBM3 (80GB) │ $21.60/hr │ US │ Large model training",
+            "1x NVIDIA H100 80GB HBM3 (80GB) │ $2.70/hr  │ DE │ Large model training",
+            "4x NVIDIA A100-SXM4-80GB (80GB) │ -         │ US │ Training",
+        ]
+        .iter()
+        .map(|item| item.to_string())
+        .collect()
+    }
+
+    fn executor(hourly_price: Option<&str>, currency: Option<&str>) -> AvailableExecutor {
+        serde_json::from_value(serde_json::json!({
+            "executor": {
+                "id": "miner1__executor1",
+                "gpu_specs": [],
+                "cpu_specs": {"cores": 16, "model": "AMD EPYC", "memory_gb": 64},
+                "location": null,
+                "network_speed": null,
+            },
+            "availability": {
+                "available_until": null,
+                "verification_score": 1.0,
+                "uptime_percentage": 100.0,
+            },
+            "hourly_price": hourly_price,
+            "currency": currency,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_format_hourly_price() {
+        assert_eq!(
+            format_hourly_price(&executor(Some("2.5"), Some("USD"))),
+            "$2.50/hr"
+        );
+        assert_eq!(
+            format_hourly_price(&executor(Some("2.5"), Some("EUR"))),
+            "2.5 EUR/hr"
+        );
+        assert_eq!(format_hourly_price(&executor(Some("2.5"), None)), "2.5/hr");
+        assert_eq!(format_hourly_price(&executor(None, None)), "-");
+    }
+
+    #[test]
+    fn test_filter_items_matches_every_term() {
+        let items = items();
+
+        assert_eq!(filter_items(&items, ""), vec![0, 1, 2]);
+        assert_eq!(filter_items(&items, "   "), vec![0, 1, 2]);
+        assert_eq!(filter_items(&items, "h100"), vec![0, 1]);
+        assert_eq!(filter_items(&items, "H100 us"), vec![0]);
+        assert_eq!(filter_items(&items, "us  A100"), vec![2]);
+        assert_eq!(filter_items(&items, "$2.70"), vec![1]);
+        assert!(filter_items(&items, "h100 fr").is_empty());
+    }
+}
@@ -16,6 +16,7 @@
 //! - Integration with existing basilica-common utilities
 
 pub mod auth;
+pub mod cancel;
 pub mod cli;
 pub mod client;
 pub mod config;
@@ -45,6 +45,11 @@ async fn main() -> Result<()> {
     if let Err(err) = args.run().await {
         // Extract and format the inner error properly
         match err {
+            basilica_cli::CliError::Cancelled => {
+                // Conventional exit status for a command interrupted by SIGINT
+                eprintln!("Cancelled");
+                std::process::exit(130);
+            }
             basilica_cli::CliError::Internal(report) => {
                 // For Internal errors (which contain eyre Reports with suggestions),
                 // use Debug formatting to show the full error report
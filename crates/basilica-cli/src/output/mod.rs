@@ -15,6 +15,14 @@ pub fn json_output<T: Serialize>(data: &T) -> Result<()> {
     Ok(())
 }
 
+/// Output data as a single line of JSON, for newline-delimited streams
+pub fn json_line_output<T: Serialize>(data: &T) -> Result<()> {
+    let json =
+        serde_json::to_string(data).map_err(|e| eyre!("Failed to serialize to JSON: {}", e))?;
+    println!("{json}");
+    Ok(())
+}
+
 /// Print a success message with green checkmark
 pub fn print_success(message: &str) {
     println!("{} {}", style("✓").green().bold(), message);
@@ -4,7 +4,10 @@ use crate::error::Result;
 use basilica_api::country_mapping::get_country_name_from_code;
 use basilica_common::LocationProfile;
 use basilica_sdk::{
-    types::{ApiKeyInfo, ApiRentalListItem, ExecutorDetails, GpuSpec, RentalStatusResponse},
+    auth::TokenClaims,
+    types::{
+        ApiKeyInfo, ApiRentalListItem, ExecutorDetails, GpuSpec, GpuUsage, RentalStatusResponse,
+    },
     AvailableExecutor,
 };
 use basilica_validator::gpu::GpuCategory;
@@ -104,6 +107,42 @@ pub fn display_rentals(rentals: &[RentalStatusResponse]) -> Result<()> {
     Ok(())
 }
 
+/// Display per-GPU utilization for a rental
+pub fn display_gpu_usage(gpu_usage: &[GpuUsage]) -> Result<()> {
+    #[derive(Tabled)]
+    struct GpuUsageRow {
+        #[tabled(rename = "GPU")]
+        index: u32,
+        #[tabled(rename = "Utilization")]
+        utilization: String,
+        #[tabled(rename = "Memory")]
+        memory: String,
+        #[tabled(rename = "Temp")]
+        temperature: String,
+    }
+
+    if gpu_usage.is_empty() {
+        println!("No GPU telemetry available yet");
+        return Ok(());
+    }
+
+    let rows: Vec<GpuUsageRow> = gpu_usage
+        .iter()
+        .map(|gpu| GpuUsageRow {
+            index: gpu.gpu_index,
+            utilization: format!("{:.0}%", gpu.utilization_percent),
+            memory: format!("{} MB", gpu.memory_mb),
+            temperature: format!("{:.0}°C", gpu.temperature_celsius),
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table.with(Style::modern());
+    println!("{table}");
+
+    Ok(())
+}
+
 /// Display rental items in table format
 pub fn display_rental_items(
     rentals: &[ApiRentalListItem],
@@ -429,6 +468,8 @@ pub fn display_available_executors_compact(executors: &[AvailableExecutor]) -> R
             gpu_type: String,
             #[tabled(rename = "AVAILABLE")]
             available: String,
+            #[tabled(rename = "PRICE")]
+            price: String,
         }
 
         let gpu_groups = country_groups.get(&country).unwrap();
@@ -442,9 +483,19 @@ pub fn display_available_executors_compact(executors: &[AvailableExecutor]) -> R
             let executors_in_group = gpu_groups.get(&gpu_config).unwrap();
             let count = executors_in_group.len();
 
+            // Show the cheapest executor of the group
+            let cheapest = executors_in_group
+                .iter()
+                .filter_map(|executor| Some((executor_price(executor)?, *executor)))
+                .min_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(_, executor)| executor);
+
             rows.push(CompactRow {
                 gpu_type: gpu_config.clone(),
                 available: count.to_string(),
+                price: cheapest
+                    .map(|executor| format!("from {}", format_executor_price(executor)))
+                    .unwrap_or_else(|| "-".to_string()),
             });
         }
 
@@ -491,6 +542,55 @@ pub fn display_api_keys(keys: &[ApiKeyInfo]) -> Result<()> {
     Ok(())
 }
 
+/// Display the claims of the current access token
+pub fn display_token_claims(claims: &TokenClaims) -> Result<()> {
+    #[derive(Tabled)]
+    struct ClaimRow {
+        #[tabled(rename = "Field")]
+        field: &'static str,
+        #[tabled(rename = "Value")]
+        value: String,
+    }
+
+    let or_dash = |value: String| {
+        if value.is_empty() {
+            "-".to_string()
+        } else {
+            value
+        }
+    };
+    let expires = claims
+        .expires_at
+        .and_then(|exp| DateTime::from_timestamp(exp as i64, 0))
+        .map(|expires_at| format_timestamp(&expires_at.to_rfc3339()))
+        .unwrap_or_else(|| "Never".to_string());
+
+    let rows = vec![
+        ClaimRow {
+            field: "Subject",
+            value: claims.subject.clone(),
+        },
+        ClaimRow {
+            field: "Email",
+            value: or_dash(claims.email.clone().unwrap_or_default()),
+        },
+        ClaimRow {
+            field: "Scopes",
+            value: or_dash(claims.scopes.join(", ")),
+        },
+        ClaimRow {
+            field: "Expires",
+            value: expires,
+        },
+    ];
+
+    let mut table = Table::new(rows);
+    table.with(Style::modern());
+    println!("{table}");
+
+    Ok(())
+}
+
 /// Helper function to format GPU info for an executor
 fn format_executor_gpu_info(executor: &AvailableExecutor, show_full_gpu_names: bool) -> String {
     if executor.executor.gpu_specs.is_empty() {
@@ -544,6 +644,20 @@ fn format_executor_gpu_info(executor: &AvailableExecutor, show_full_gpu_names: b
     }
 }
 
+/// Helper function to parse an executor's hourly price for comparison
+fn executor_price(executor: &AvailableExecutor) -> Option<f64> {
+    executor.hourly_price.as_ref()?.parse().ok()
+}
+
+/// Helper function to format an executor's hourly price
+fn format_executor_price(executor: &AvailableExecutor) -> String {
+    match (&executor.hourly_price, &executor.currency) {
+        (Some(price), Some(currency)) => format!("{} {}/hr", price, currency),
+        (Some(price), None) => format!("{}/hr", price),
+        _ => "-".to_string(),
+    }
+}
+
 /// Helper function to format location
 fn format_executor_location(location: &Option<String>) -> String {
     location
@@ -582,6 +696,8 @@ pub fn display_available_executors_detailed(
             ram: String,
             #[tabled(rename = "Location")]
             location: String,
+            #[tabled(rename = "Price")]
+            price: String,
         }
 
         let rows: Vec<DetailedExecutorRowWithId> = executors
@@ -605,6 +721,7 @@ pub fn display_available_executors_detailed(
                     ),
                     ram: format!("{}GB", executor.executor.cpu_specs.memory_gb),
                     location: format_executor_location(&executor.executor.location),
+                    price: format_executor_price(executor),
                 }
             })
             .collect();
@@ -623,6 +740,8 @@ pub fn display_available_executors_detailed(
             ram: String,
             #[tabled(rename = "Location")]
             location: String,
+            #[tabled(rename = "Price")]
+            price: String,
         }
 
         let rows: Vec<DetailedExecutorRow> = executors
@@ -635,6 +754,7 @@ pub fn display_available_executors_detailed(
                 ),
                 ram: format!("{}GB", executor.executor.cpu_specs.memory_gb),
                 location: format_executor_location(&executor.executor.location),
+                price: format_executor_price(executor),
             })
             .collect();
 
@@ -121,6 +121,20 @@ pub fn create_progress_bar(total: u64, message: &str) -> ProgressBar {
     pb
 }
 
+/// Progress bar measuring bytes, for file transfers
+pub fn create_transfer_progress_bar(total_bytes: u64) -> ProgressBar {
+    let pb = ProgressBar::new(total_bytes);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template(
+                "{spinner:.green} [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}) {wide_msg}",
+            )
+            .unwrap()
+            .progress_chars("=>-"),
+    );
+    pb
+}
+
 /// Finish spinner with success message
 pub fn complete_spinner_success(spinner: ProgressBar, message: &str) {
     spinner.finish_with_message(format!("✓ {}", message));
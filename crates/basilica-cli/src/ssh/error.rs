@@ -0,0 +1,232 @@
+//! Classification of SSH connection failures
+//!
+//! OpenSSH exits with status 255 for every failure of its own, so the cause is
+//! read from the diagnostics it writes to stderr.
+
+use crate::error::CliError;
+use color_eyre::eyre::Report;
+use color_eyre::Section;
+use thiserror::Error;
+
+/// Why an SSH connection to a rental failed
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SshConnectionError {
+    /// The rental rejected the key offered
+    #[error("SSH authentication failed: {0}")]
+    AuthFailed(String),
+
+    /// The rental's SSH endpoint could not be reached
+    #[error("SSH host unreachable: {0}")]
+    HostUnreachable(String),
+
+    /// The rental's host key differs from the one known for its address
+    #[error("SSH host key verification failed: {0}")]
+    HostKeyMismatch(String),
+
+    /// The local private key could not be used because of its file permissions
+    #[error("SSH private key is not usable: {0}")]
+    PermissionDenied(String),
+
+    /// Any other failure reported by SSH
+    #[error("SSH connection failed: {0}")]
+    Other(String),
+}
+
+impl SshConnectionError {
+    /// Classify a failure from the stderr output of `ssh`
+    pub fn classify(stderr: &str) -> Self {
+        let detail = diagnostic_line(stderr);
+        let lower = stderr.to_lowercase();
+        let mentions = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+
+        // Key permission problems are reported before authentication is
+        // attempted and usually end in an authentication failure as well
+        if mentions(&[
+            "unprotected private key file",
+            "are too open",
+            "bad permissions",
+            "load key",
+        ]) {
+            Self::PermissionDenied(detail)
+        } else if mentions(&[
+            "remote host identification has changed",
+            "host key verification failed",
+            "host key for",
+        ]) {
+            Self::HostKeyMismatch(detail)
+        } else if mentions(&[
+            "permission denied (",
+            "too many authentication failures",
+            "no supported authentication methods",
+            "authentication failed",
+        ]) {
+            Self::AuthFailed(detail)
+        } else if mentions(&[
+            "could not resolve hostname",
+            "connection refused",
+            "connection timed out",
+            "operation timed out",
+            "no route to host",
+            "network is unreachable",
+            "connection reset",
+            "connection closed by",
+            "kex_exchange_identification",
+        ]) {
+            Self::HostUnreachable(detail)
+        } else {
+            Self::Other(detail)
+        }
+    }
+
+    /// What the user can do about the failure
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            Self::AuthFailed(_) => {
+                "Check that the rental was created with your current SSH public key; run 'basilica login' to regenerate keys"
+            }
+            Self::HostUnreachable(_) => {
+                "Check your network connection and that the rental is active with its SSH port exposed"
+            }
+            Self::HostKeyMismatch(_) => {
+                "The rental's address may have been reused by a new machine; remove the old entry with 'ssh-keygen -R <host>'"
+            }
+            Self::PermissionDenied(_) => {
+                "Restrict the private key to your user with 'chmod 600 <key>'"
+            }
+            Self::Other(_) => "Check if the rental is still active and SSH port is exposed",
+        }
+    }
+}
+
+impl From<SshConnectionError> for CliError {
+    fn from(err: SshConnectionError) -> Self {
+        let suggestion = err.suggestion();
+        Report::new(err)
+            .suggestion(suggestion)
+            .note("Run 'basilica status <rental-id>' to check rental status")
+            .into()
+    }
+}
+
+/// Most specific line of SSH's output, skipping the warning banners it
+/// frames some errors with
+fn diagnostic_line(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("@@@"))
+        .collect();
+    lines
+        .iter()
+        .rev()
+        .find(|line| !line.starts_with("Warning: Permanently added"))
+        .or(lines.last())
+        .map(|line| line.to_string())
+        .unwrap_or_else(|| "no details reported".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_ssh_failures() {
+        let cases = [
+            (
+                "user@203.0.113.5: Permission denied (publickey).\n",
+                SshConnectionError::AuthFailed(
+                    "user@203.0.113.5: Permission denied (publickey).".into(),
+                ),
+            ),
+            (
+                "Received disconnect from 203.0.113.5 port 22:2: Too many authentication failures\n",
+                SshConnectionError::AuthFailed(
+                    "Received disconnect from 203.0.113.5 port 22:2: Too many authentication failures"
+                        .into(),
+                ),
+            ),
+            (
+                "ssh: connect to host 203.0.113.5 port 2222: Connection refused\n",
+                SshConnectionError::HostUnreachable(
+                    "ssh: connect to host 203.0.113.5 port 2222: Connection refused".into(),
+                ),
+            ),
+            (
+                "ssh: connect to host 203.0.113.5 port 22: Connection timed out\n",
+                SshConnectionError::HostUnreachable(
+                    "ssh: connect to host 203.0.113.5 port 22: Connection timed out".into(),
+                ),
+            ),
+            (
+                "ssh: Could not resolve hostname rental.invalid: Name or service not known\n",
+                SshConnectionError::HostUnreachable(
+                    "ssh: Could not resolve hostname rental.invalid: Name or service not known"
+                        .into(),
+                ),
+            ),
+            (
+                "Unable to negotiate with 203.0.113.5 port 22: no matching host key type found\n",
+                SshConnectionError::Other(
+                    "Unable to negotiate with 203.0.113.5 port 22: no matching host key type found"
+                        .into(),
+                ),
+            ),
+        ];
+
+        for (stderr, expected) in cases {
+            assert_eq!(SshConnectionError::classify(stderr), expected);
+        }
+    }
+
+    #[test]
+    fn test_classifies_host_key_mismatch() {
+        let stderr = "\
+@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@
+@    WARNING: REMOTE HOST IDENTIFICATION HAS CHANGED!     @
+@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@
+IT IS POSSIBLE THAT SOMEONE IS DOING SOMETHING NASTY!
+Host key for [203.0.113.5]:2222 has changed and you have requested strict checking.
+Host key verification failed.
+";
+        assert_eq!(
+            SshConnectionError::classify(stderr),
+            SshConnectionError::HostKeyMismatch("Host key verification failed.".into())
+        );
+    }
+
+    #[test]
+    fn test_classifies_key_permission_problems() {
+        let stderr = "\
+@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@
+@         WARNING: UNPROTECTED PRIVATE KEY FILE!          @
+@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@
+Permissions 0644 for '/home/user/.ssh/basilica_ed25519' are too open.
+It is required that your private key files are NOT accessible by others.
+This private key will be ignored.
+Load key \"/home/user/.ssh/basilica_ed25519\": bad permissions
+user@203.0.113.5: Permission denied (publickey).
+";
+        assert!(matches!(
+            SshConnectionError::classify(stderr),
+            SshConnectionError::PermissionDenied(_)
+        ));
+    }
+
+    #[test]
+    fn test_detail_skips_known_hosts_notice() {
+        let stderr = "\
+Warning: Permanently added '[203.0.113.5]:2222' (ED25519) to the list of known hosts.
+";
+        assert_eq!(
+            SshConnectionError::classify(stderr),
+            SshConnectionError::Other(
+                "Warning: Permanently added '[203.0.113.5]:2222' (ED25519) to the list of known hosts."
+                    .into()
+            )
+        );
+        assert_eq!(
+            SshConnectionError::classify(""),
+            SshConnectionError::Other("no details reported".into())
+        );
+    }
+}
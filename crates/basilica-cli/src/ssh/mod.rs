@@ -1,22 +1,33 @@
 //! SSH operations module
 
+mod error;
+pub mod transfer;
+
+pub use error::SshConnectionError;
+
+use crate::cancel::wait_for_child;
 use crate::config::SshConfig;
 use crate::error::{CliError, Result};
 use basilica_common::ssh::{
-    SshConnectionConfig, SshConnectionDetails, SshConnectionManager, SshFileTransferManager,
-    StandardSshClient,
+    parse_credentials, SshConnectionConfig, SshConnectionDetails, SshConnectionManager,
+    SshFileTransferManager, StandardSshClient,
 };
 use basilica_sdk::types::{RentalStatusResponse, SshAccess};
+use basilica_validator::os_process::ProcessGroup;
 use color_eyre::eyre::{eyre, WrapErr};
 use color_eyre::Section;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::process::Stdio;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 /// SSH client for rental operations
 pub struct SshClient {
     client: StandardSshClient,
     config: SshConfig,
+    cancel: CancellationToken,
 }
 
 impl SshClient {
@@ -40,9 +51,16 @@ impl SshClient {
         Ok(Self {
             client: StandardSshClient::with_config(ssh_config),
             config: config.clone(),
+            cancel: CancellationToken::new(),
         })
     }
 
+    /// Stop SSH child processes spawned by this client when `token` is cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
     /// Convert SSH access info to connection details
     fn ssh_access_to_connection_details(
         &self,
@@ -92,6 +110,22 @@ impl SshClient {
         Ok(())
     }
 
+    /// Execute a command via SSH and return its standard output
+    pub async fn capture_command(&self, ssh_access: &SshAccess, command: &str) -> Result<String> {
+        let details = self.ssh_access_to_connection_details(ssh_access)?;
+
+        let output = self
+            .client
+            .execute_command(&details, command, true)
+            .await
+            .map_err(|e| {
+                eyre!("Command execution failed: {}", e)
+                    .suggestion("Check if the rental is still active and SSH port is exposed")
+            })?;
+
+        Ok(output)
+    }
+
     /// Execute a command with rental status (for backward compatibility)
     pub async fn execute_command_with_rental(
         &self,
@@ -132,23 +166,7 @@ impl SshClient {
             .arg("LogLevel=error")
             .arg(format!("{}@{}", details.username, details.host));
 
-        let status = cmd.status().map_err(|e| -> CliError {
-            eyre!("Failed to start SSH session: {}", e)
-                .suggestion("Check your SSH key permissions and network connectivity")
-                .note("Ensure the rental is active and accessible")
-                .into()
-        })?;
-
-        // Only treat exit code 255 as an SSH error (SSH's own error code)
-        // Other exit codes are from the remote command
-        if status.code() == Some(255) {
-            return Err(eyre!("SSH connection failed")
-                .suggestion("Check if the rental is still active and SSH port is exposed")
-                .note("Run 'basilica status <rental-id>' to check rental status")
-                .into());
-        }
-
-        Ok(())
+        run_interactive_ssh(cmd)
     }
 
     /// Parse port forward specification into components
@@ -275,23 +293,7 @@ impl SshClient {
         // Add the target host
         cmd.arg(format!("{}@{}", details.username, details.host));
 
-        let status = cmd.status().map_err(|e| -> CliError {
-            eyre!("Failed to start SSH session: {}", e)
-                .suggestion("Check your SSH key permissions and network connectivity")
-                .note("Ensure the rental is active and accessible")
-                .into()
-        })?;
-
-        // Only treat exit code 255 as an SSH error (SSH's own error code)
-        // Other exit codes are from the remote command and should be ignored
-        if status.code() == Some(255) {
-            return Err(eyre!("SSH connection failed")
-                .suggestion("Check if the rental is still active and SSH port is exposed")
-                .note("Run 'basilica status <rental-id>' to check rental status")
-                .into());
-        }
-
-        Ok(())
+        run_interactive_ssh(cmd)
     }
 
     /// Upload file via SSH
@@ -319,6 +321,54 @@ impl SshClient {
         Ok(())
     }
 
+    /// Append a local file, from byte `offset` onwards, to a file on the rental
+    ///
+    /// Used to resume uploads that were interrupted part way through.
+    pub async fn append_file(
+        &self,
+        ssh_access: &SshAccess,
+        local_path: &Path,
+        offset: u64,
+        remote_path: &str,
+    ) -> Result<()> {
+        let details = self.ssh_access_to_connection_details(ssh_access)?;
+
+        let mut source = std::fs::File::open(local_path)
+            .wrap_err_with(|| format!("Cannot read {}", local_path.display()))?;
+        source
+            .seek(SeekFrom::Start(offset))
+            .wrap_err_with(|| format!("Cannot read {}", local_path.display()))?;
+
+        info!(
+            "Appending {} from byte {} to {} on {}",
+            local_path.display(),
+            offset,
+            remote_path,
+            ssh_access.host
+        );
+
+        let output = self
+            .run_ssh(
+                &details,
+                &format!("cat >> {}", transfer::shell_quote(remote_path)),
+                Stdio::from(source),
+                Stdio::null(),
+            )
+            .await?;
+
+        if !output.status.success() {
+            return Err(eyre!(
+                "Resumed upload failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .suggestion("Run the same copy with --resume again to continue from where it stopped")
+            .into());
+        }
+
+        info!("Resumed upload completed successfully");
+        Ok(())
+    }
+
     /// Download file via SSH
     pub async fn download_file(
         &self,
@@ -343,55 +393,203 @@ impl SshClient {
         info!("Download completed successfully");
         Ok(())
     }
-}
 
-/// Parse SSH credentials string into components
-pub fn parse_ssh_credentials(credentials: &str) -> Result<(String, u16, String)> {
-    debug!("Parsing SSH credentials: {}", credentials);
-    // Expected format: "ssh user@host -p port" or "user@host:port" or "host:port"
-
-    // Try to parse "ssh user@host -p port" format
-    if credentials.starts_with("ssh ") {
-        let parts: Vec<&str> = credentials.split_whitespace().collect();
-        if parts.len() >= 4 && parts[2] == "-p" {
-            let user_host = parts[1];
-            let port = parts[3]
-                .parse::<u16>()
-                .map_err(|_| eyre!("Invalid port in SSH credentials"))?;
-
-            let (user, host) = if let Some((user, host)) = user_host.split_once('@') {
-                (user.to_string(), host.to_string())
-            } else {
-                ("root".to_string(), user_host.to_string())
-            };
+    /// Download `len` bytes of a file on the rental, starting at byte `offset`
+    ///
+    /// Only the requested bytes cross the network, so a slice of a large log
+    /// or checkpoint can be inspected without fetching the whole file. The
+    /// local file is shorter than `len` if the remote file ends first.
+    pub async fn download_range(
+        &self,
+        ssh_access: &SshAccess,
+        remote_path: &str,
+        offset: u64,
+        len: u64,
+        local_path: &Path,
+    ) -> Result<()> {
+        let details = self.ssh_access_to_connection_details(ssh_access)?;
+        let local = std::fs::File::create(local_path)
+            .wrap_err_with(|| format!("Cannot write {}", local_path.display()))?;
+
+        info!(
+            "Downloading {} bytes of {} from byte {} from {}",
+            len, remote_path, offset, ssh_access.host
+        );
 
-            return Ok((host, port, user));
+        let output = self
+            .run_ssh(
+                &details,
+                &range_command(remote_path, offset, len),
+                Stdio::null(),
+                Stdio::from(local),
+            )
+            .await?;
+        if !output.status.success() {
+            return Err(eyre!(
+                "Range download failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .suggestion("Check that the remote file exists and you have read permissions")
+            .into());
         }
+
+        info!("Range download completed successfully");
+        Ok(())
     }
 
-    // Try to parse "user@host:port" or "host:port" format
-    if let Some((left_part, port_str)) = credentials.rsplit_once(':') {
-        let port = port_str
-            .parse::<u16>()
-            .map_err(|_| eyre!("Invalid port in SSH credentials"))?;
+    /// Write the last `bytes` bytes of a file on the rental to stdout
+    pub async fn peek_tail(
+        &self,
+        ssh_access: &SshAccess,
+        remote_path: &str,
+        bytes: u64,
+    ) -> Result<()> {
+        let details = self.ssh_access_to_connection_details(ssh_access)?;
 
-        let (user, host) = if let Some((user, host)) = left_part.split_once('@') {
-            (user.to_string(), host.to_string())
-        } else {
-            ("root".to_string(), left_part.to_string())
-        };
+        let output = self
+            .run_ssh(
+                &details,
+                &tail_command(remote_path, bytes),
+                Stdio::null(),
+                Stdio::inherit(),
+            )
+            .await?;
+        if !output.status.success() {
+            return Err(eyre!(
+                "Failed to read the end of {}: {}",
+                remote_path,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .suggestion("Check that the remote file exists and you have read permissions")
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Run `remote_command` on the rental with the system `ssh` client
+    ///
+    /// The child is stopped if this client's cancellation token fires. Its
+    /// stderr is captured for error reporting, and a failure to connect is
+    /// returned as a [`SshConnectionError`].
+    async fn run_ssh(
+        &self,
+        details: &SshConnectionDetails,
+        remote_command: &str,
+        stdin: Stdio,
+        stdout: Stdio,
+    ) -> Result<std::process::Output> {
+        let mut cmd = tokio::process::Command::new("ssh");
+        cmd.arg("-i")
+            .arg(details.private_key_path.display().to_string())
+            .arg("-p")
+            .arg(details.port.to_string())
+            .arg("-o")
+            .arg("StrictHostKeyChecking=no")
+            .arg("-o")
+            .arg("UserKnownHostsFile=/dev/null")
+            .arg("-o")
+            .arg("LogLevel=error")
+            .arg("-o")
+            .arg(format!("ConnectTimeout={}", details.timeout.as_secs()))
+            .arg(format!("{}@{}", details.username, details.host))
+            .arg(remote_command)
+            .stdin(stdin)
+            .stdout(stdout)
+            .stderr(Stdio::piped());
+        ProcessGroup::configure_command(&mut cmd);
+
+        let child = cmd.spawn().map_err(|e| -> CliError {
+            eyre!("Failed to start SSH: {}", e)
+                .suggestion("Check your SSH key permissions and network connectivity")
+                .into()
+        })?;
+        let output = wait_for_child(child, &self.cancel).await?;
+
+        // Exit code 255 is SSH's own error rather than the remote command's
+        if output.status.code() == Some(255) {
+            return Err(
+                SshConnectionError::classify(&String::from_utf8_lossy(&output.stderr)).into(),
+            );
+        }
+        Ok(output)
+    }
+}
+
+/// Most recent stderr output kept to classify a failed interactive session
+const SSH_STDERR_TAIL_BYTES: usize = 8 * 1024;
+
+/// Run an interactive `ssh` command until the session ends
+///
+/// Stderr is passed through to the terminal while its tail is kept, so that
+/// a failure of SSH itself can be reported as a [`SshConnectionError`].
+fn run_interactive_ssh(mut cmd: std::process::Command) -> Result<()> {
+    let mut child = cmd
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| -> CliError {
+            eyre!("Failed to start SSH session: {}", e)
+                .suggestion("Check your SSH key permissions and network connectivity")
+                .note("Ensure the rental is active and accessible")
+                .into()
+        })?;
 
-        return Ok((host, port, user));
+    let stderr = child.stderr.take();
+    let stderr_tail = std::thread::spawn(move || {
+        let mut tail = Vec::new();
+        if let Some(mut stderr) = stderr {
+            let mut terminal = std::io::stderr();
+            let mut buffer = [0u8; 4096];
+            while let Ok(read @ 1..) = stderr.read(&mut buffer) {
+                let _ = terminal.write_all(&buffer[..read]);
+                tail.extend_from_slice(&buffer[..read]);
+                if tail.len() > SSH_STDERR_TAIL_BYTES {
+                    tail.drain(..tail.len() - SSH_STDERR_TAIL_BYTES);
+                }
+            }
+        }
+        tail
+    });
+
+    let status = child
+        .wait()
+        .map_err(|e| eyre!("Failed to wait for SSH session: {}", e))?;
+    let stderr_tail = stderr_tail.join().unwrap_or_default();
+
+    // Only treat exit code 255 as an SSH error (SSH's own error code)
+    // Other exit codes are from the remote command
+    if status.code() == Some(255) {
+        return Err(SshConnectionError::classify(&String::from_utf8_lossy(&stderr_tail)).into());
     }
 
-    // Try to parse "user@host" or just "host" format (default port 22)
-    let (user, host) = if let Some((user, host)) = credentials.split_once('@') {
-        (user.to_string(), host.to_string())
-    } else {
-        ("root".to_string(), credentials.to_string())
-    };
+    Ok(())
+}
 
-    Ok((host, 22, user))
+/// Shell command printing `len` bytes of `path` from byte `offset`
+///
+/// `tail -c +N` seeks on regular files, so the bytes before `offset` are not
+/// read. The explicit check makes a missing file fail rather than print
+/// nothing, since `head` would hide `tail`'s exit status.
+fn range_command(path: &str, offset: u64, len: u64) -> String {
+    format!(
+        "f={}; [ -r \"$f\" ] || {{ echo \"cannot read $f\" >&2; exit 1; }}; tail -c +{} \"$f\" | head -c {}",
+        transfer::shell_quote(path),
+        offset + 1,
+        len
+    )
+}
+
+/// Shell command printing the last `bytes` bytes of `path`
+fn tail_command(path: &str, bytes: u64) -> String {
+    format!("tail -c {} {}", bytes, transfer::shell_quote(path))
+}
+
+/// Parse SSH credentials string into (host, port, username)
+///
+/// See [`basilica_common::ssh::parse_credentials`] for the accepted formats.
+pub fn parse_ssh_credentials(credentials: &str) -> Result<(String, u16, String)> {
+    debug!("Parsing SSH credentials: {}", credentials);
+    let parsed = parse_credentials(credentials).map_err(|e| eyre!("{}", e))?;
+    Ok((parsed.host, parsed.port, parsed.username))
 }
 
 /// Ensure SSH keys exist at the configured paths, generating them if necessary
@@ -460,3 +658,66 @@ pub async fn ensure_ssh_keys_exist(config: &SshConfig) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run a remote command against the local filesystem, standing in for the
+    /// rental's shell
+    fn run_remote(command: &str) -> std::process::Output {
+        std::process::Command::new("sh")
+            .args(["-c", command])
+            .output()
+            .unwrap()
+    }
+
+    fn remote_file(contents: &[u8]) -> (tempfile::TempDir, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("train log.txt");
+        std::fs::write(&path, contents).unwrap();
+        (dir, path.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn test_range_command_fetches_requested_bytes() {
+        let contents: Vec<u8> = (0..=255u8).cycle().take(10_000).collect();
+        let (_dir, path) = remote_file(&contents);
+
+        for (offset, len) in [(0, 16), (100, 1000), (9_990, 10), (0, 10_000)] {
+            let output = run_remote(&range_command(&path, offset, len));
+            assert!(output.status.success());
+            assert_eq!(
+                output.stdout,
+                &contents[offset as usize..(offset + len) as usize]
+            );
+        }
+
+        // Ranges past the end are cut short
+        let output = run_remote(&range_command(&path, 9_995, 100));
+        assert_eq!(output.stdout, &contents[9_995..]);
+        let output = run_remote(&range_command(&path, 20_000, 100));
+        assert!(output.status.success());
+        assert!(output.stdout.is_empty());
+    }
+
+    #[test]
+    fn test_range_command_fails_for_missing_file() {
+        let (_dir, path) = remote_file(b"");
+        let output = run_remote(&range_command(&format!("{path}.missing"), 0, 10));
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("cannot read"));
+    }
+
+    #[test]
+    fn test_tail_command_fetches_last_bytes() {
+        let (_dir, path) = remote_file(b"epoch 1\nepoch 2\nepoch 3\n");
+
+        let output = run_remote(&tail_command(&path, 8));
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"epoch 3\n");
+
+        let output = run_remote(&tail_command(&path, 1_000));
+        assert_eq!(output.stdout, b"epoch 1\nepoch 2\nepoch 3\n");
+    }
+}
@@ -0,0 +1,997 @@
+//! File copies between the local machine and a rental
+//!
+//! `basilica cp` resolves its arguments into a [`CopyRequest`], plans every file and
+//! directory up front so the total size is known for progress reporting, and then
+//! transfers file by file through a [`RemoteFs`]. Resumed uploads append to whatever
+//! prefix of a file is already on the rental and are verified by checksum.
+
+use crate::error::Result;
+use crate::ssh::SshClient;
+use async_trait::async_trait;
+use basilica_sdk::types::SshAccess;
+use color_eyre::eyre::eyre;
+use color_eyre::Section;
+use indicatif::ProgressBar;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// One side of a copy: a local path or a path on a rental
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyTarget {
+    Local(String),
+    Remote { rental_id: String, path: String },
+}
+
+impl CopyTarget {
+    /// Parse `rental:<id>:<path>` or `<id>:<path>` as a rental path; anything else is local
+    pub fn parse(value: &str) -> Self {
+        let spec = value
+            .strip_prefix("rental:")
+            .filter(|rest| rest.contains(':'))
+            .unwrap_or(value);
+
+        match spec.split_once(':') {
+            // A slash before the colon means a local path such as ./data:v1
+            Some((rental_id, path)) if !rental_id.is_empty() && !rental_id.contains('/') => {
+                Self::Remote {
+                    rental_id: rental_id.to_string(),
+                    path: path.to_string(),
+                }
+            }
+            _ => Self::Local(value.to_string()),
+        }
+    }
+}
+
+/// Direction of a copy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyDirection {
+    Upload,
+    Download,
+}
+
+/// A validated copy with exactly one side on a rental
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyRequest {
+    pub rental_id: String,
+    pub direction: CopyDirection,
+    pub local: String,
+    pub remote: String,
+}
+
+impl CopyRequest {
+    /// Build a request from the `cp` source and destination arguments
+    pub fn parse(source: &str, destination: &str) -> Result<Self> {
+        match (CopyTarget::parse(source), CopyTarget::parse(destination)) {
+            (CopyTarget::Local(local), CopyTarget::Remote { rental_id, path }) => Ok(Self {
+                rental_id,
+                direction: CopyDirection::Upload,
+                local,
+                remote: path,
+            }),
+            (CopyTarget::Remote { rental_id, path }, CopyTarget::Local(local)) => Ok(Self {
+                rental_id,
+                direction: CopyDirection::Download,
+                local,
+                remote: path,
+            }),
+            (CopyTarget::Remote { .. }, CopyTarget::Remote { .. }) => {
+                Err(eyre!("Remote-to-remote copy is not supported")
+                    .suggestion("Copy to a local path first, then upload it to the other rental")
+                    .into())
+            }
+            (CopyTarget::Local(_), CopyTarget::Local(_)) => {
+                Err(eyre!("Neither '{}' nor '{}' is a rental path", source, destination)
+                    .suggestion("Prefix exactly one side with the rental ID: 'basilica cp <rental_id>:<path> <local_path>' or vice versa")
+                    .into())
+            }
+        }
+    }
+}
+
+/// What a rental path refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteTree {
+    File {
+        size: u64,
+    },
+    /// A directory with its subdirectories and files, relative to the directory
+    Directory {
+        dirs: Vec<String>,
+        files: Vec<RemoteFile>,
+    },
+}
+
+/// A regular file below a remote directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteFile {
+    pub path: String,
+    pub size: u64,
+}
+
+/// Filesystem operations on a rental used by [`copy`]
+#[async_trait]
+pub trait RemoteFs: Send + Sync {
+    /// Describe the file or directory tree at `path`
+    async fn stat_tree(&self, path: &str) -> Result<RemoteTree>;
+
+    /// Create directories, including missing parents
+    async fn create_dirs(&self, paths: &[String]) -> Result<()>;
+
+    /// Upload a single file
+    async fn upload(&self, local: &Path, remote: &str) -> Result<()>;
+
+    /// Size of the regular file at `path`, or `None` if there is none
+    async fn file_size(&self, path: &str) -> Result<Option<u64>>;
+
+    /// Append the bytes of `local` from `offset` onwards to the file at `remote`
+    async fn append(&self, local: &Path, offset: u64, remote: &str) -> Result<()>;
+
+    /// Hex SHA-256 digest of the file at `path`
+    async fn sha256(&self, path: &str) -> Result<String>;
+
+    /// Download a single file
+    async fn download(&self, remote: &str, local: &Path) -> Result<()>;
+}
+
+/// [`RemoteFs`] backed by the rental's SSH endpoint
+pub struct SshRemoteFs<'a> {
+    client: &'a SshClient,
+    access: &'a SshAccess,
+}
+
+impl<'a> SshRemoteFs<'a> {
+    pub fn new(client: &'a SshClient, access: &'a SshAccess) -> Self {
+        Self { client, access }
+    }
+}
+
+#[async_trait]
+impl RemoteFs for SshRemoteFs<'_> {
+    async fn stat_tree(&self, path: &str) -> Result<RemoteTree> {
+        let output = self
+            .client
+            .capture_command(self.access, &remote_listing_command(path))
+            .await?;
+        parse_remote_listing(path, &output)
+    }
+
+    async fn create_dirs(&self, paths: &[String]) -> Result<()> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let quoted: Vec<String> = paths.iter().map(|p| shell_quote(p)).collect();
+        self.client
+            .capture_command(self.access, &format!("mkdir -p {}", quoted.join(" ")))
+            .await?;
+        Ok(())
+    }
+
+    async fn upload(&self, local: &Path, remote: &str) -> Result<()> {
+        self.client
+            .upload_file(self.access, &local.to_string_lossy(), remote)
+            .await
+    }
+
+    async fn file_size(&self, path: &str) -> Result<Option<u64>> {
+        let output = self
+            .client
+            .capture_command(
+                self.access,
+                &format!(
+                    "p={}; if [ -f \"$p\" ]; then stat -L -c %s \"$p\"; fi",
+                    shell_quote(path)
+                ),
+            )
+            .await?;
+        let output = output.trim();
+        if output.is_empty() {
+            return Ok(None);
+        }
+        output
+            .parse()
+            .map(Some)
+            .map_err(|_| eyre!("Unexpected size '{}' for {} on the rental", output, path).into())
+    }
+
+    async fn append(&self, local: &Path, offset: u64, remote: &str) -> Result<()> {
+        self.client
+            .append_file(self.access, local, offset, remote)
+            .await
+    }
+
+    async fn sha256(&self, path: &str) -> Result<String> {
+        let output = self
+            .client
+            .capture_command(self.access, &format!("sha256sum {}", shell_quote(path)))
+            .await?;
+        output
+            .split_whitespace()
+            .next()
+            .map(str::to_string)
+            .ok_or_else(|| eyre!("Failed to checksum {} on the rental", path).into())
+    }
+
+    async fn download(&self, remote: &str, local: &Path) -> Result<()> {
+        self.client
+            .download_file(self.access, remote, &local.to_string_lossy())
+            .await
+    }
+}
+
+/// Outcome of a copy, printed by `basilica cp --json`
+#[derive(Debug, Clone, Serialize)]
+pub struct CopySummary {
+    pub rental_id: String,
+    pub direction: CopyDirection,
+    pub source: String,
+    pub destination: String,
+    pub files: usize,
+    pub directories: usize,
+    pub bytes: u64,
+    /// Bytes already on the rental that resumed uploads did not send again
+    pub resumed_bytes: u64,
+}
+
+/// Every directory to create and file to transfer for one copy
+#[derive(Debug, Default)]
+struct TransferPlan {
+    local_dirs: Vec<PathBuf>,
+    remote_dirs: Vec<String>,
+    files: Vec<PlannedFile>,
+}
+
+#[derive(Debug)]
+struct PlannedFile {
+    local: PathBuf,
+    remote: String,
+    size: u64,
+}
+
+impl TransferPlan {
+    fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+}
+
+/// Copy files as described by `request`, advancing `progress` by bytes transferred
+///
+/// With `resume`, uploads continue from the bytes already on the rental instead of
+/// starting over.
+pub async fn copy(
+    remote: &dyn RemoteFs,
+    request: &CopyRequest,
+    recursive: bool,
+    resume: bool,
+    progress: &ProgressBar,
+) -> Result<CopySummary> {
+    if resume && request.direction == CopyDirection::Download {
+        return Err(eyre!("--resume only applies to uploads")
+            .suggestion("Copy from a local path to a rental path to resume an upload")
+            .into());
+    }
+
+    let plan = match request.direction {
+        CopyDirection::Upload => {
+            let sources = expand_local_glob(&request.local)?;
+            plan_upload(&sources, &request.remote, recursive)?
+        }
+        CopyDirection::Download => {
+            let tree = remote.stat_tree(&request.remote).await?;
+            plan_download(&tree, &request.remote, Path::new(&request.local), recursive)?
+        }
+    };
+
+    progress.set_length(plan.total_bytes());
+
+    for dir in &plan.local_dirs {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| eyre!("Failed to create directory {}: {}", dir.display(), e))?;
+    }
+    remote.create_dirs(&plan.remote_dirs).await?;
+
+    let mut resumed_bytes = 0;
+    for file in &plan.files {
+        debug!(
+            "Copying {} ({} bytes) {:?}",
+            file.local.display(),
+            file.size,
+            request.direction
+        );
+        match request.direction {
+            CopyDirection::Upload => {
+                progress.set_message(file.local.display().to_string());
+                if resume {
+                    resumed_bytes += resume_upload(remote, file).await?;
+                } else {
+                    remote.upload(&file.local, &file.remote).await?;
+                }
+            }
+            CopyDirection::Download => {
+                progress.set_message(file.remote.clone());
+                remote.download(&file.remote, &file.local).await?;
+            }
+        }
+        progress.inc(file.size);
+    }
+
+    let (source, destination) = match request.direction {
+        CopyDirection::Upload => (
+            request.local.clone(),
+            format!("{}:{}", request.rental_id, request.remote),
+        ),
+        CopyDirection::Download => (
+            format!("{}:{}", request.rental_id, request.remote),
+            request.local.clone(),
+        ),
+    };
+
+    Ok(CopySummary {
+        rental_id: request.rental_id.clone(),
+        direction: request.direction,
+        source,
+        destination,
+        files: plan.files.len(),
+        directories: plan.local_dirs.len() + plan.remote_dirs.len(),
+        bytes: plan.total_bytes(),
+        resumed_bytes,
+    })
+}
+
+/// Upload `file`, continuing after the prefix of it already on the rental, and
+/// compare checksums once it is complete
+///
+/// Returns the number of bytes that were already on the rental.
+async fn resume_upload(remote: &dyn RemoteFs, file: &PlannedFile) -> Result<u64> {
+    // A remote file longer than the local one cannot be a partial upload of it
+    let offset = match remote.file_size(&file.remote).await? {
+        Some(size) if size <= file.size => size,
+        _ => 0,
+    };
+
+    if offset == 0 {
+        remote.upload(&file.local, &file.remote).await?;
+    } else if offset < file.size {
+        debug!(
+            "Resuming upload of {} at byte {} of {}",
+            file.local.display(),
+            offset,
+            file.size
+        );
+        remote.append(&file.local, offset, &file.remote).await?;
+    }
+
+    let local = file.local.clone();
+    let local_digest = tokio::task::spawn_blocking(move || sha256_file(&local))
+        .await
+        .map_err(|e| eyre!("Checksum task failed: {}", e))??;
+    let remote_digest = remote.sha256(&file.remote).await?;
+    if local_digest != remote_digest {
+        return Err(eyre!(
+            "Checksum mismatch after uploading {} to {}",
+            file.local.display(),
+            file.remote
+        )
+        .note("The partial file on the rental did not match the start of the local file")
+        .suggestion("Run the copy again without --resume to upload the whole file")
+        .into());
+    }
+
+    Ok(offset)
+}
+
+/// Hex SHA-256 digest of a local file
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file =
+        std::fs::File::open(path).map_err(|e| eyre!("Cannot read {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| eyre!("Cannot read {}: {}", path.display(), e))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Plan uploading local `sources` to `destination` on the rental.
+///
+/// A single source is copied to `destination` itself unless it ends with `/`, in which
+/// case it is placed inside; several sources always go inside `destination`.
+fn plan_upload(sources: &[PathBuf], destination: &str, recursive: bool) -> Result<TransferPlan> {
+    let mut plan = TransferPlan::default();
+    let into_directory = sources.len() > 1 || destination.ends_with('/');
+    if sources.len() > 1 && !destination.is_empty() {
+        plan.remote_dirs.push(destination.to_string());
+    }
+
+    for source in sources {
+        let metadata = std::fs::metadata(source)
+            .map_err(|e| eyre!("Cannot read {}: {}", source.display(), e))?;
+        let name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let target = if into_directory || destination.is_empty() {
+            join_remote(destination, &name)
+        } else {
+            destination.to_string()
+        };
+
+        if metadata.is_dir() {
+            if !recursive {
+                return Err(eyre!("'{}' is a directory", source.display())
+                    .suggestion("Pass -r to copy directories recursively")
+                    .into());
+            }
+            plan.remote_dirs.push(target.clone());
+            walk_local_dir(source, &target, &mut plan)?;
+        } else {
+            plan.files.push(PlannedFile {
+                local: source.clone(),
+                remote: target,
+                size: metadata.len(),
+            });
+        }
+    }
+
+    Ok(plan)
+}
+
+fn walk_local_dir(dir: &Path, remote_dir: &str, plan: &mut TransferPlan) -> Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)
+        .map_err(|e| eyre!("Cannot read directory {}: {}", dir.display(), e))?
+        .collect::<std::io::Result<_>>()
+        .map_err(|e| eyre!("Cannot read directory {}: {}", dir.display(), e))?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let remote = join_remote(remote_dir, &entry.file_name().to_string_lossy());
+        // Symlinked directories are not followed to avoid cycles
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            plan.remote_dirs.push(remote.clone());
+            walk_local_dir(&path, &remote, plan)?;
+        } else if let Ok(metadata) = std::fs::metadata(&path) {
+            if metadata.is_file() {
+                plan.files.push(PlannedFile {
+                    local: path,
+                    remote,
+                    size: metadata.len(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Plan downloading `tree`, found at `source` on the rental, to `destination`.
+///
+/// As with `cp`, an existing local directory receives the source inside it.
+fn plan_download(
+    tree: &RemoteTree,
+    source: &str,
+    destination: &Path,
+    recursive: bool,
+) -> Result<TransferPlan> {
+    let target = match remote_basename(source) {
+        Some(name) if destination.is_dir() => destination.join(name),
+        _ => destination.to_path_buf(),
+    };
+    let mut plan = TransferPlan::default();
+
+    match tree {
+        RemoteTree::File { size } => plan.files.push(PlannedFile {
+            local: target,
+            remote: source.to_string(),
+            size: *size,
+        }),
+        RemoteTree::Directory { dirs, files } => {
+            if !recursive {
+                return Err(eyre!("'{}' is a directory on the rental", source)
+                    .suggestion("Pass -r to copy directories recursively")
+                    .into());
+            }
+            plan.local_dirs.push(target.clone());
+            plan.local_dirs
+                .extend(dirs.iter().map(|dir| target.join(dir)));
+            plan.files.extend(files.iter().map(|file| PlannedFile {
+                local: target.join(&file.path),
+                remote: join_remote(source, &file.path),
+                size: file.size,
+            }));
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Shell command printing a listing of `path` for [`parse_remote_listing`]
+fn remote_listing_command(path: &str) -> String {
+    let path = if path.is_empty() { "." } else { path };
+    format!(
+        "p={}; if [ -d \"$p\" ]; then echo dir; \
+         find \"$p\" -mindepth 1 \\( -type d -printf 'd 0 %P\\n' \\) -o \\( -type f -printf 'f %s %P\\n' \\); \
+         elif [ -f \"$p\" ]; then echo file; stat -L -c %s \"$p\"; \
+         else echo missing; fi",
+        shell_quote(path)
+    )
+}
+
+/// Parse the output of [`remote_listing_command`]
+fn parse_remote_listing(path: &str, output: &str) -> Result<RemoteTree> {
+    let mut lines = output.lines();
+    match lines.next().map(str::trim) {
+        Some("file") => {
+            let size = lines
+                .next()
+                .and_then(|line| line.trim().parse().ok())
+                .ok_or_else(|| eyre!("Unexpected listing for {} on the rental", path))?;
+            Ok(RemoteTree::File { size })
+        }
+        Some("dir") => {
+            let mut dirs = Vec::new();
+            let mut files = Vec::new();
+            for line in lines.filter(|line| !line.is_empty()) {
+                let mut fields = line.splitn(3, ' ');
+                match (fields.next(), fields.next(), fields.next()) {
+                    (Some("d"), Some(_), Some(rel)) => dirs.push(rel.to_string()),
+                    (Some("f"), Some(size), Some(rel)) => files.push(RemoteFile {
+                        path: rel.to_string(),
+                        size: size.parse().unwrap_or(0),
+                    }),
+                    _ => debug!("Ignoring unexpected listing line: {}", line),
+                }
+            }
+            dirs.sort();
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(RemoteTree::Directory { dirs, files })
+        }
+        _ => Err(eyre!("'{}' does not exist on the rental", path)
+            .suggestion("Run 'basilica exec \"ls -la\"' to inspect the rental's filesystem")
+            .into()),
+    }
+}
+
+/// Expand `*` and `?` wildcards in a local path, like the shell does for unquoted arguments
+pub fn expand_local_glob(pattern: &str) -> Result<Vec<PathBuf>> {
+    if !has_wildcard(pattern) {
+        return Ok(vec![PathBuf::from(pattern)]);
+    }
+
+    let mut candidates = vec![PathBuf::new()];
+    for component in Path::new(pattern).components() {
+        let part = component.as_os_str().to_string_lossy();
+        if !has_wildcard(&part) {
+            candidates = candidates.into_iter().map(|c| c.join(component)).collect();
+            continue;
+        }
+
+        let mut matched = Vec::new();
+        for dir in &candidates {
+            let read_from = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir.as_path()
+            };
+            let Ok(entries) = std::fs::read_dir(read_from) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                // Hidden files only match patterns that start with a dot
+                if name.starts_with('.') && !part.starts_with('.') {
+                    continue;
+                }
+                if wildcard_match(&part, &name) {
+                    matched.push(dir.join(name));
+                }
+            }
+        }
+        candidates = matched;
+    }
+
+    candidates.retain(|path| path.exists());
+    candidates.sort();
+    if candidates.is_empty() {
+        return Err(eyre!("No local files match '{}'", pattern).into());
+    }
+    Ok(candidates)
+}
+
+fn has_wildcard(value: &str) -> bool {
+    value.contains(['*', '?'])
+}
+
+/// Match `name` against a pattern where `*` matches any run of characters and `?` one
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn join_remote(base: &str, name: &str) -> String {
+    if base.is_empty() {
+        name.to_string()
+    } else if base.ends_with('/') {
+        format!("{base}{name}")
+    } else {
+        format!("{base}/{name}")
+    }
+}
+
+fn remote_basename(path: &str) -> Option<&str> {
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty() && *name != "." && *name != "..")
+}
+
+/// Quote a value for a POSIX shell
+pub(super) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{Read, Seek, SeekFrom, Write};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Remote filesystem rooted in a local temporary directory
+    struct FakeRemote {
+        root: tempfile::TempDir,
+        uploads: AtomicUsize,
+    }
+
+    impl FakeRemote {
+        fn new() -> Self {
+            Self {
+                root: tempfile::tempdir().unwrap(),
+                uploads: AtomicUsize::new(0),
+            }
+        }
+
+        fn resolve(&self, path: &str) -> PathBuf {
+            self.root.path().join(path.trim_start_matches('/'))
+        }
+    }
+
+    #[async_trait]
+    impl RemoteFs for FakeRemote {
+        async fn stat_tree(&self, path: &str) -> Result<RemoteTree> {
+            let local = self.resolve(path);
+            if local.is_file() {
+                return Ok(RemoteTree::File {
+                    size: fs::metadata(&local).unwrap().len(),
+                });
+            }
+            let mut plan = TransferPlan::default();
+            walk_local_dir(&local, "", &mut plan)?;
+            Ok(RemoteTree::Directory {
+                dirs: plan.remote_dirs,
+                files: plan
+                    .files
+                    .into_iter()
+                    .map(|f| RemoteFile {
+                        path: f.remote,
+                        size: f.size,
+                    })
+                    .collect(),
+            })
+        }
+
+        async fn create_dirs(&self, paths: &[String]) -> Result<()> {
+            for path in paths {
+                fs::create_dir_all(self.resolve(path)).unwrap();
+            }
+            Ok(())
+        }
+
+        async fn upload(&self, local: &Path, remote: &str) -> Result<()> {
+            self.uploads.fetch_add(1, Ordering::SeqCst);
+            fs::copy(local, self.resolve(remote)).unwrap();
+            Ok(())
+        }
+
+        async fn file_size(&self, path: &str) -> Result<Option<u64>> {
+            let local = self.resolve(path);
+            Ok(local.is_file().then(|| fs::metadata(&local).unwrap().len()))
+        }
+
+        async fn append(&self, local: &Path, offset: u64, remote: &str) -> Result<()> {
+            let mut source = fs::File::open(local).unwrap();
+            source.seek(SeekFrom::Start(offset)).unwrap();
+            let mut rest = Vec::new();
+            source.read_to_end(&mut rest).unwrap();
+            fs::OpenOptions::new()
+                .append(true)
+                .open(self.resolve(remote))
+                .unwrap()
+                .write_all(&rest)
+                .unwrap();
+            Ok(())
+        }
+
+        async fn sha256(&self, path: &str) -> Result<String> {
+            sha256_file(&self.resolve(path))
+        }
+
+        async fn download(&self, remote: &str, local: &Path) -> Result<()> {
+            fs::copy(self.resolve(remote), local).unwrap();
+            Ok(())
+        }
+    }
+
+    fn request(source: &str, destination: &str) -> CopyRequest {
+        CopyRequest::parse(source, destination).unwrap()
+    }
+
+    #[test]
+    fn test_parse_copy_targets() {
+        assert_eq!(
+            CopyTarget::parse("rental:abc-123:/data/x"),
+            CopyTarget::Remote {
+                rental_id: "abc-123".to_string(),
+                path: "/data/x".to_string()
+            }
+        );
+        assert_eq!(
+            CopyTarget::parse("abc-123:/data/x"),
+            CopyTarget::Remote {
+                rental_id: "abc-123".to_string(),
+                path: "/data/x".to_string()
+            }
+        );
+        assert_eq!(
+            CopyTarget::parse("./data:v1"),
+            CopyTarget::Local("./data:v1".to_string())
+        );
+        assert_eq!(
+            CopyTarget::parse("notes.txt"),
+            CopyTarget::Local("notes.txt".to_string())
+        );
+    }
+
+    #[test]
+    fn test_exactly_one_side_must_be_remote() {
+        assert!(CopyRequest::parse("a.txt", "b.txt").is_err());
+        assert!(CopyRequest::parse("r1:/a", "r2:/b").is_err());
+
+        let upload = request("a.txt", "r1:/b");
+        assert_eq!(upload.direction, CopyDirection::Upload);
+        assert_eq!(upload.rental_id, "r1");
+        let download = request("rental:r1:/b", "a.txt");
+        assert_eq!(download.direction, CopyDirection::Download);
+        assert_eq!(download.remote, "/b");
+    }
+
+    #[tokio::test]
+    async fn test_upload_single_file() {
+        let remote = FakeRemote::new();
+        let local = tempfile::tempdir().unwrap();
+        let file = local.path().join("model.bin");
+        fs::write(&file, b"weights").unwrap();
+        fs::create_dir(remote.resolve("workspace")).unwrap();
+
+        let req = request(file.to_str().unwrap(), "r1:/workspace/");
+        let summary = copy(&remote, &req, false, false, &ProgressBar::hidden())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read(remote.resolve("workspace/model.bin")).unwrap(),
+            b"weights"
+        );
+        assert_eq!(summary.direction, CopyDirection::Upload);
+        assert_eq!(summary.files, 1);
+        assert_eq!(summary.bytes, 7);
+        assert_eq!(summary.destination, "r1:/workspace/");
+    }
+
+    #[tokio::test]
+    async fn test_download_single_file_into_existing_directory() {
+        let remote = FakeRemote::new();
+        fs::create_dir(remote.resolve("out")).unwrap();
+        fs::write(remote.resolve("out/result.csv"), b"a,b\n1,2\n").unwrap();
+        let local = tempfile::tempdir().unwrap();
+
+        let req = request("r1:/out/result.csv", local.path().to_str().unwrap());
+        let summary = copy(&remote, &req, false, false, &ProgressBar::hidden())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read(local.path().join("result.csv")).unwrap(),
+            b"a,b\n1,2\n"
+        );
+        assert_eq!(summary.direction, CopyDirection::Download);
+        assert_eq!(summary.bytes, 8);
+    }
+
+    #[tokio::test]
+    async fn test_recursive_directory_round_trip() {
+        let remote = FakeRemote::new();
+        let local = tempfile::tempdir().unwrap();
+        let project = local.path().join("project");
+        fs::create_dir_all(project.join("src/nested")).unwrap();
+        fs::create_dir(project.join("empty")).unwrap();
+        fs::write(project.join("README"), b"hi").unwrap();
+        fs::write(project.join("src/main.py"), b"print(1)").unwrap();
+        fs::write(project.join("src/nested/util.py"), b"x = 2").unwrap();
+
+        // Directories need -r
+        let req = request(project.to_str().unwrap(), "r1:/work/project");
+        assert!(copy(&remote, &req, false, false, &ProgressBar::hidden())
+            .await
+            .is_err());
+
+        let summary = copy(&remote, &req, true, false, &ProgressBar::hidden())
+            .await
+            .unwrap();
+        assert_eq!(summary.files, 3);
+        assert_eq!(summary.bytes, 15);
+        assert!(remote.resolve("work/project/empty").is_dir());
+        assert_eq!(
+            fs::read(remote.resolve("work/project/src/nested/util.py")).unwrap(),
+            b"x = 2"
+        );
+
+        // Downloading into an existing directory places the tree inside it
+        let back = tempfile::tempdir().unwrap();
+        let req = request("r1:/work/project", back.path().to_str().unwrap());
+        let summary = copy(&remote, &req, true, false, &ProgressBar::hidden())
+            .await
+            .unwrap();
+        assert_eq!(summary.files, 3);
+        assert!(back.path().join("project/empty").is_dir());
+        assert_eq!(
+            fs::read(back.path().join("project/src/main.py")).unwrap(),
+            b"print(1)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_glob_uploads_matches_into_directory() {
+        let remote = FakeRemote::new();
+        let local = tempfile::tempdir().unwrap();
+        for name in ["a.log", "b.log", "c.txt", ".hidden.log"] {
+            fs::write(local.path().join(name), name).unwrap();
+        }
+
+        let pattern = format!("{}/*.log", local.path().display());
+        let req = request(&pattern, "r1:/logs");
+        let summary = copy(&remote, &req, false, false, &ProgressBar::hidden())
+            .await
+            .unwrap();
+
+        assert_eq!(summary.files, 2);
+        assert!(remote.resolve("logs/a.log").is_file());
+        assert!(remote.resolve("logs/b.log").is_file());
+        assert!(!remote.resolve("logs/.hidden.log").exists());
+    }
+
+    #[tokio::test]
+    async fn test_resume_completes_partial_upload() {
+        let remote = FakeRemote::new();
+        let local = tempfile::tempdir().unwrap();
+        let checkpoint: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let file = local.path().join("checkpoint.pt");
+        fs::write(&file, &checkpoint).unwrap();
+
+        // A dropped connection left the first part of the file on the rental
+        fs::write(remote.resolve("checkpoint.pt"), &checkpoint[..4_096]).unwrap();
+
+        let req = request(file.to_str().unwrap(), "r1:/checkpoint.pt");
+        let summary = copy(&remote, &req, false, true, &ProgressBar::hidden())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read(remote.resolve("checkpoint.pt")).unwrap(),
+            checkpoint
+        );
+        assert_eq!(remote.uploads.load(Ordering::SeqCst), 0);
+        assert_eq!(summary.bytes, 10_000);
+        assert_eq!(summary.resumed_bytes, 4_096);
+        assert_eq!(
+            remote.sha256("checkpoint.pt").await.unwrap(),
+            sha256_file(&file).unwrap()
+        );
+
+        // Resuming a finished upload sends nothing
+        let summary = copy(&remote, &req, false, true, &ProgressBar::hidden())
+            .await
+            .unwrap();
+        assert_eq!(summary.resumed_bytes, 10_000);
+        assert_eq!(remote.uploads.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_resume_rejects_mismatched_partial_file() {
+        let remote = FakeRemote::new();
+        let local = tempfile::tempdir().unwrap();
+        let file = local.path().join("data.bin");
+        fs::write(&file, b"the local contents").unwrap();
+        fs::write(remote.resolve("data.bin"), b"other").unwrap();
+
+        let req = request(file.to_str().unwrap(), "r1:/data.bin");
+        assert!(copy(&remote, &req, false, true, &ProgressBar::hidden())
+            .await
+            .is_err());
+
+        // A remote file longer than the local one is replaced outright
+        fs::write(remote.resolve("data.bin"), b"a much longer remote file").unwrap();
+        copy(&remote, &req, false, true, &ProgressBar::hidden())
+            .await
+            .unwrap();
+        assert_eq!(remote.uploads.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            fs::read(remote.resolve("data.bin")).unwrap(),
+            b"the local contents"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_is_upload_only() {
+        let remote = FakeRemote::new();
+        let req = request("r1:/out.csv", "out.csv");
+        assert!(copy(&remote, &req, false, true, &ProgressBar::hidden())
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*.py", "main.py"));
+        assert!(wildcard_match("data-??.csv", "data-01.csv"));
+        assert!(wildcard_match("a*b*c", "axxbyyc"));
+        assert!(!wildcard_match("*.py", "main.pyc"));
+        assert!(!wildcard_match("data-?.csv", "data-01.csv"));
+    }
+
+    #[test]
+    fn test_parse_remote_listing() {
+        let tree =
+            parse_remote_listing("/w", "dir\nd 0 src\nf 12 src/a b.py\nf 3 README\n").unwrap();
+        assert_eq!(
+            tree,
+            RemoteTree::Directory {
+                dirs: vec!["src".to_string()],
+                files: vec![
+                    RemoteFile {
+                        path: "README".to_string(),
+                        size: 3
+                    },
+                    RemoteFile {
+                        path: "src/a b.py".to_string(),
+                        size: 12
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            parse_remote_listing("/f", "file\n42\n").unwrap(),
+            RemoteTree::File { size: 42 }
+        );
+        assert!(parse_remote_listing("/nope", "missing\n").is_err());
+    }
+}
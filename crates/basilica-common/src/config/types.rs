@@ -231,6 +231,105 @@ impl ServerConfig {
     }
 }
 
+/// Entry in [`CorsConfig::allowed_origins`] that allows any origin
+pub const CORS_ANY_ORIGIN: &str = "*";
+
+/// Cross-origin resource sharing policy for HTTP APIs
+///
+/// The default allowlist is empty, so browsers on other origins are refused.
+/// [`CORS_ANY_ORIGIN`] opts in to any origin and is meant for local
+/// development only; it cannot be combined with `allow_credentials`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, e.g. `https://dashboard.basilica.ai`
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+
+    /// Send `Access-Control-Allow-Credentials: true` to allowed origins
+    #[serde(default)]
+    pub allow_credentials: bool,
+}
+
+impl CorsConfig {
+    /// Whether the wildcard entry is configured
+    pub fn allows_any_origin(&self) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|origin| origin == CORS_ANY_ORIGIN)
+    }
+
+    /// Check that every entry is the wildcard or a bare `scheme://host[:port]` origin
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allows_any_origin() {
+            if self.allowed_origins.len() > 1 {
+                return Err(format!(
+                    "\"{CORS_ANY_ORIGIN}\" cannot be combined with other origins"
+                ));
+            }
+            if self.allow_credentials {
+                return Err(format!(
+                    "\"{CORS_ANY_ORIGIN}\" cannot be used with allow_credentials"
+                ));
+            }
+            return Ok(());
+        }
+
+        for origin in &self.allowed_origins {
+            let parsed = reqwest::Url::parse(origin)
+                .map_err(|e| format!("Invalid CORS origin \"{origin}\": {e}"))?;
+            if parsed.origin().ascii_serialization() != *origin {
+                return Err(format!(
+                    "Invalid CORS origin \"{origin}\": expected scheme://host[:port] without a path"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the CORS layer for this allowlist
+    ///
+    /// GET, POST, PUT and DELETE are allowed with the `Authorization`,
+    /// `Content-Type` and `Accept` headers plus `allow_headers`;
+    /// `expose_headers` are made readable to the page. Origins outside the
+    /// allowlist get no CORS headers, so browsers refuse the response.
+    #[cfg(feature = "cors")]
+    pub fn layer(
+        &self,
+        allow_headers: &[http::HeaderName],
+        expose_headers: &[http::HeaderName],
+    ) -> Result<tower_http::cors::CorsLayer, String> {
+        use http::{header, HeaderValue, Method};
+        use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+        self.validate()?;
+
+        let allow_origin = if self.allows_any_origin() {
+            AllowOrigin::from(Any)
+        } else {
+            let origins = self
+                .allowed_origins
+                .iter()
+                .map(|origin| {
+                    HeaderValue::from_str(origin)
+                        .map_err(|e| format!("Invalid CORS origin \"{origin}\": {e}"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            AllowOrigin::list(origins)
+        };
+
+        let mut allowed = vec![header::AUTHORIZATION, header::CONTENT_TYPE, header::ACCEPT];
+        allowed.extend_from_slice(allow_headers);
+
+        Ok(CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+            .allow_headers(allowed)
+            .expose_headers(expose_headers.to_vec())
+            .allow_credentials(self.allow_credentials))
+    }
+}
+
 /// TLS configuration for servers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TlsConfig {
@@ -508,10 +607,172 @@ impl ConfigValidation for GrpcServerConfig {
     }
 }
 
+/// Container image allow/deny policy
+///
+/// Patterns are image references where `*` matches any run of characters,
+/// e.g. `ghcr.io/acme/*`, `nvidia/cuda:12.*` or `*:latest`. Both patterns
+/// and images are normalized before matching, so `ubuntu` means
+/// `docker.io/library/ubuntu` and a pattern without a tag matches every tag.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImagePolicyConfig {
+    /// Images that may run; an empty list allows every image not denied
+    pub allow: Vec<String>,
+
+    /// Images that may never run; takes precedence over `allow`
+    pub deny: Vec<String>,
+}
+
+impl ImagePolicyConfig {
+    /// Check `image` against the policy, describing why it was rejected
+    pub fn check(&self, image: &str) -> Result<(), String> {
+        let (name, suffix) = split_image_reference(image);
+        let reference = format!("{name}{}", suffix.unwrap_or(":latest"));
+
+        if let Some(pattern) = self
+            .deny
+            .iter()
+            .find(|pattern| Self::matches(pattern, &reference))
+        {
+            return Err(format!(
+                "Image '{image}' is denied by image policy pattern '{pattern}'"
+            ));
+        }
+
+        if !self.allow.is_empty()
+            && !self
+                .allow
+                .iter()
+                .any(|pattern| Self::matches(pattern, &reference))
+        {
+            return Err(format!(
+                "Image '{image}' is not in the image policy allowlist: {:?}",
+                self.allow
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn matches(pattern: &str, reference: &str) -> bool {
+        match split_image_reference(pattern) {
+            (name, Some(suffix)) => glob_match(&format!("{name}{suffix}"), reference),
+            // No tag or digest in the pattern: any tag or digest matches
+            (name, None) => {
+                glob_match(&format!("{name}:*"), reference)
+                    || glob_match(&format!("{name}@*"), reference)
+            }
+        }
+    }
+}
+
+/// Image pre-pull and digest pinning configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageCacheConfig {
+    /// Images pulled ahead of time so first rentals start fast
+    pub prepull: Vec<String>,
+
+    /// Resolve `image:tag` to its digest on first pull and create later
+    /// containers for the same reference from that digest
+    pub pin_digests: bool,
+}
+
+/// Split an image reference into its `registry/repository` name and its
+/// `:tag` or `@digest` suffix, if any
+fn split_image_reference(image: &str) -> (String, Option<&str>) {
+    let (name, suffix) = match image.find('@') {
+        Some(i) => image.split_at(i),
+        None => {
+            let last_slash = image.rfind('/').map_or(0, |i| i + 1);
+            match image[last_slash..].rfind(':') {
+                Some(i) => image.split_at(last_slash + i),
+                None => (image, ""),
+            }
+        }
+    };
+    let suffix = (!suffix.is_empty()).then_some(suffix);
+
+    // The first component is a registry only if it looks like a host;
+    // patterns starting with a wildcard are left as written
+    let name = match name.split_once('/') {
+        _ if name.starts_with('*') => name.to_string(),
+        Some((first, _)) if first.contains('.') || first.contains(':') || first == "localhost" => {
+            name.to_string()
+        }
+        Some(_) => format!("docker.io/{name}"),
+        None => format!("docker.io/library/{name}"),
+    };
+
+    (name, suffix)
+}
+
+/// Match `text` against a pattern where `*` matches any run of characters
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text) = (pattern.as_bytes(), text.as_bytes());
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cors_config_defaults_to_empty_allowlist() {
+        let cors = CorsConfig::default();
+        assert!(cors.allowed_origins.is_empty());
+        assert!(!cors.allow_credentials);
+        assert!(!cors.allows_any_origin());
+        assert!(cors.validate().is_ok());
+    }
+
+    #[test]
+    fn test_cors_config_validation() {
+        let origins = |origins: &[&str], allow_credentials: bool| CorsConfig {
+            allowed_origins: origins.iter().map(|o| o.to_string()).collect(),
+            allow_credentials,
+        };
+
+        assert!(origins(&["https://app.example.com"], true)
+            .validate()
+            .is_ok());
+        assert!(origins(&["http://localhost:3000"], false)
+            .validate()
+            .is_ok());
+        assert!(origins(&["*"], false).validate().is_ok());
+
+        assert!(origins(&["*"], true).validate().is_err());
+        assert!(origins(&["*", "https://app.example.com"], false)
+            .validate()
+            .is_err());
+        assert!(origins(&["https://app.example.com/"], false)
+            .validate()
+            .is_err());
+        assert!(origins(&["https://app.example.com/path"], false)
+            .validate()
+            .is_err());
+        assert!(origins(&["app.example.com"], false).validate().is_err());
+    }
+
     #[test]
     fn test_bittensor_config_endpoint_resolution() {
         // Test finney network
@@ -141,6 +141,38 @@ pub fn sign_with_sr25519(pair: &sr25519::Pair, data: &[u8]) -> String {
     hex::encode(signature.0)
 }
 
+/// SS58 address of an SR25519 public key
+///
+/// Used when the private key is held elsewhere, such as in a remote signer,
+/// and only the public key is known.
+pub fn sr25519_address_from_public(public: &[u8], ss58_prefix: u16) -> Result<String, CryptoError> {
+    let public: [u8; 32] = public
+        .try_into()
+        .map_err(|_| CryptoError::InvalidPublicKey {
+            details: format!("expected 32 bytes, got {}", public.len()),
+        })?;
+    Ok(sr25519::Public::from_raw(public)
+        .to_ss58check_with_version(Ss58AddressFormat::custom(ss58_prefix)))
+}
+
+/// Verify a hex SR25519 signature, as produced by [`sign_with_sr25519`]
+pub fn verify_sr25519_signature(public: &[u8], data: &[u8], signature_hex: &str) -> bool {
+    let Ok(public) = <[u8; 32]>::try_from(public) else {
+        return false;
+    };
+    let Some(signature) = hex::decode(signature_hex)
+        .ok()
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+    else {
+        return false;
+    };
+    sr25519::Pair::verify(
+        &sr25519::Signature::from_raw(signature),
+        data,
+        &sr25519::Public::from_raw(public),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,6 +255,25 @@ mod tests {
         assert!(sp_core::sr25519::Pair::verify(&sig, data, &pair.public()));
     }
 
+    #[test]
+    fn test_address_and_verification_from_public_key() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let wallet = generate_sr25519_wallet_from_mnemonic(mnemonic, 42).unwrap();
+        let public = hex::decode(&wallet.public_hex).unwrap();
+
+        assert_eq!(
+            sr25519_address_from_public(&public, 42).unwrap(),
+            wallet.address
+        );
+        assert!(sr25519_address_from_public(&public[..31], 42).is_err());
+
+        let pair = sr25519_pair_from_mnemonic(mnemonic).unwrap();
+        let signature = sign_with_sr25519(&pair, b"payload");
+        assert!(verify_sr25519_signature(&public, b"payload", &signature));
+        assert!(!verify_sr25519_signature(&public, b"tampered", &signature));
+        assert!(!verify_sr25519_signature(&public, b"payload", "not hex"));
+    }
+
     #[test]
     fn test_different_ss58_prefixes() {
         let wallet_generic = generate_sr25519_wallet(42).unwrap();
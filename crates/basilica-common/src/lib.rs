@@ -28,6 +28,7 @@ pub mod identity;
 pub mod journal;
 pub mod logging;
 pub mod metrics;
+pub mod money;
 pub mod network;
 pub mod persistence;
 pub mod ssh;
@@ -41,7 +42,10 @@ pub use config::*;
 pub use crypto::*;
 pub use error::*;
 pub use identity::*;
-pub use types::{ApiKeyName, ApiKeyNameError, LocationProfile};
+pub use types::{
+    resolve_container_image, ApiKeyName, ApiKeyNameError, InvalidMigProfile, LocationProfile,
+    MigProfile, DEFAULT_CONTAINER_IMAGE,
+};
 
 // Re-export from specific modules to avoid ambiguity
 pub use metrics::labels;
@@ -5,6 +5,9 @@
 //! 2. RUST_LOG environment variable
 //! 3. Binary-specific defaults - lowest priority
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod capture;
+
 use anyhow::Result;
 use clap_verbosity_flag::{LogLevel, Verbosity};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
@@ -0,0 +1,182 @@
+//! Amounts of money and the currencies they are quoted in
+//!
+//! Shared by billing, the API gateway and its clients so a cost keeps its
+//! currency and precision all the way to where it is displayed.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Currency a cost is quoted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    /// Platform credits, the unit balances and rental costs are kept in
+    Credits,
+    Usd,
+    Tao,
+}
+
+impl Currency {
+    /// ISO-style code, e.g. `USD`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Credits => "CREDITS",
+            Currency::Usd => "USD",
+            Currency::Tao => "TAO",
+        }
+    }
+
+    /// Number of decimal places amounts are displayed with by default
+    pub fn default_scale(&self) -> u8 {
+        match self {
+            Currency::Credits | Currency::Usd => 2,
+            Currency::Tao => 4,
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "CREDITS" => Ok(Currency::Credits),
+            "USD" => Ok(Currency::Usd),
+            "TAO" => Ok(Currency::Tao),
+            _ => Err(format!("unknown currency: {s}")),
+        }
+    }
+}
+
+/// Amount of money in a currency, with the scale used to display it
+///
+/// The amount keeps its full precision, including when serialized; only
+/// `Display` rounds to `scale` decimal places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Money {
+    pub amount: Decimal,
+    pub currency: Currency,
+    pub scale: u8,
+}
+
+impl Money {
+    /// Amount displayed at the currency's default scale
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        Self {
+            amount,
+            currency,
+            scale: currency.default_scale(),
+        }
+    }
+
+    pub fn credits(amount: Decimal) -> Self {
+        Self::new(amount, Currency::Credits)
+    }
+
+    pub fn with_scale(self, scale: u8) -> Self {
+        Self { scale, ..self }
+    }
+
+    /// Amount rounded half away from zero to the display scale
+    pub fn rounded(&self) -> Decimal {
+        let mut rounded = self
+            .amount
+            .round_dp_with_strategy(self.scale.into(), RoundingStrategy::MidpointAwayFromZero);
+        rounded.rescale(self.scale.into());
+        rounded
+    }
+
+    /// Same value in `currency`, where `rate` is the price of one unit of
+    /// this currency in `currency`
+    pub fn convert(&self, currency: Currency, rate: Decimal) -> Self {
+        Self::new(self.amount * rate, currency)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rounded = self.rounded();
+        let sign = if rounded.is_sign_negative() && !rounded.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        let magnitude = rounded.abs();
+        match self.currency {
+            Currency::Usd => write!(f, "{sign}${magnitude}"),
+            Currency::Tao => write!(f, "{sign}\u{03c4}{magnitude}"),
+            Currency::Credits => write!(f, "{sign}{magnitude} credits"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_money_display() {
+        let usd = Money::new(Decimal::from_str("1234.565").unwrap(), Currency::Usd);
+        assert_eq!(usd.to_string(), "$1234.57");
+        assert_eq!(
+            Money::new(Decimal::from(3), Currency::Usd).to_string(),
+            "$3.00"
+        );
+        assert_eq!(
+            Money::new(Decimal::from_str("-0.5").unwrap(), Currency::Usd).to_string(),
+            "-$0.50"
+        );
+        // Amounts that round to zero are shown without a sign
+        assert_eq!(
+            Money::new(Decimal::from_str("-0.001").unwrap(), Currency::Usd).to_string(),
+            "$0.00"
+        );
+
+        let tao = Money::new(Decimal::from_str("0.123456789").unwrap(), Currency::Tao);
+        assert_eq!(tao.to_string(), "\u{03c4}0.1235");
+        assert_eq!(tao.with_scale(9).to_string(), "\u{03c4}0.123456789");
+
+        let credits = Money::credits(Decimal::from_str("12.5").unwrap());
+        assert_eq!(credits.to_string(), "12.50 credits");
+    }
+
+    #[test]
+    fn test_money_conversion() {
+        let credits = Money::credits(Decimal::from(10));
+        let tao = credits.convert(Currency::Tao, Decimal::from_str("0.0025").unwrap());
+        assert_eq!(tao.currency, Currency::Tao);
+        assert_eq!(tao.amount, Decimal::from_str("0.025").unwrap());
+        assert_eq!(tao.scale, 4);
+
+        assert_eq!("usd".parse::<Currency>(), Ok(Currency::Usd));
+        assert!("eur".parse::<Currency>().is_err());
+    }
+
+    #[test]
+    fn test_money_json_round_trip() {
+        let money = Money::new(
+            Decimal::from_str("0.000000123456789012").unwrap(),
+            Currency::Tao,
+        );
+        let json = serde_json::to_value(money).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "amount": "0.000000123456789012",
+                "currency": "TAO",
+                "scale": 4,
+            })
+        );
+        let parsed: Money = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, money);
+        assert_eq!(parsed.amount.scale(), 18);
+    }
+}
@@ -0,0 +1,187 @@
+//! SSH credential string parsing
+//!
+//! Rentals and SSH sessions hand out credentials as plain strings such as
+//! `root@203.0.113.7:2222` or `ssh ubuntu@host -p 2222`. Every component
+//! parses them through [`parse_credentials`] so a username is never dropped
+//! in one place and honored in another.
+
+use super::types::{SshError, SshResult};
+use std::fmt;
+
+/// Username used when the credentials do not name one
+pub const DEFAULT_SSH_USERNAME: &str = "root";
+
+/// Port used when the credentials do not name one
+pub const DEFAULT_SSH_PORT: u16 = 22;
+
+/// Connection target parsed from an SSH credentials string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshCredentials {
+    pub username: String,
+    pub host: String,
+    pub port: u16,
+}
+
+/// Formats as `user@host:port`, with IPv6 hosts in brackets so the result
+/// parses back to the same credentials
+impl fmt::Display for SshCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "{}@[{}]:{}", self.username, self.host, self.port)
+        } else {
+            write!(f, "{}@{}:{}", self.username, self.host, self.port)
+        }
+    }
+}
+
+/// Parse an SSH credentials string
+///
+/// Accepted formats, with IPv6 hosts written in brackets (`[2001:db8::1]`):
+/// - `ssh [user@]host [-p port]`
+/// - `[user@]host:port`
+/// - `[user@]host`
+///
+/// A bare IPv6 host (`user@2001:db8::1`) is read as a host without a port.
+///
+/// A username present in the string is always kept; otherwise it defaults to
+/// [`DEFAULT_SSH_USERNAME`]. The port defaults to [`DEFAULT_SSH_PORT`].
+pub fn parse_credentials(credentials: &str) -> SshResult<SshCredentials> {
+    let invalid = |reason: &str| SshError::InvalidCredentials {
+        credentials: credentials.to_string(),
+        reason: reason.to_string(),
+    };
+    let parse_port = |port: &str| {
+        port.parse::<u16>()
+            .map_err(|_| invalid(&format!("invalid port number '{port}'")))
+    };
+
+    let trimmed = credentials.trim();
+    let (target, flag_port) = match trimmed.strip_prefix("ssh ") {
+        Some(command) => match command.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [target] => (*target, None),
+            [target, "-p", port] => (*target, Some(parse_port(port)?)),
+            _ => return Err(invalid("expected 'ssh [user@]host [-p port]'")),
+        },
+        None => (trimmed, None),
+    };
+
+    let (username, host_port) = match target.split_once('@') {
+        Some((username, host_port)) => (username, host_port),
+        None => (DEFAULT_SSH_USERNAME, target),
+    };
+    if username.is_empty() {
+        return Err(invalid("empty username"));
+    }
+
+    let (host, inline_port) = if let Some(rest) = host_port.strip_prefix('[') {
+        let (host, after) = rest
+            .split_once(']')
+            .ok_or_else(|| invalid("invalid IPv6 address, missing ']'"))?;
+        let port = match after {
+            "" => None,
+            _ => Some(
+                after
+                    .strip_prefix(':')
+                    .ok_or_else(|| invalid("unexpected characters after IPv6 address"))?,
+            ),
+        };
+        (host, port)
+    } else if host_port.matches(':').count() > 1 {
+        (host_port, None)
+    } else {
+        match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (host_port, None),
+        }
+    };
+
+    if host.is_empty() || host.contains(char::is_whitespace) {
+        return Err(invalid("missing or malformed host"));
+    }
+
+    let port = match (inline_port.map(parse_port).transpose()?, flag_port) {
+        (Some(_), Some(_)) => return Err(invalid("port given twice")),
+        (inline, flag) => inline.or(flag).unwrap_or(DEFAULT_SSH_PORT),
+    };
+
+    Ok(SshCredentials {
+        username: username.to_string(),
+        host: host.to_string(),
+        port,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn creds(username: &str, host: &str, port: u16) -> SshCredentials {
+        SshCredentials {
+            username: username.to_string(),
+            host: host.to_string(),
+            port,
+        }
+    }
+
+    #[test]
+    fn test_parses_every_supported_format() {
+        let cases = [
+            ("ubuntu@10.0.0.1:2222", creds("ubuntu", "10.0.0.1", 2222)),
+            ("10.0.0.1:2222", creds("root", "10.0.0.1", 2222)),
+            ("ubuntu@10.0.0.1", creds("ubuntu", "10.0.0.1", 22)),
+            ("10.0.0.1", creds("root", "10.0.0.1", 22)),
+            ("ssh ubuntu@host -p 2222", creds("ubuntu", "host", 2222)),
+            ("ssh host -p 2222", creds("root", "host", 2222)),
+            ("ssh ubuntu@host", creds("ubuntu", "host", 22)),
+            (
+                "user@[2001:db8::1]:2222",
+                creds("user", "2001:db8::1", 2222),
+            ),
+            ("[2001:db8::1]", creds("root", "2001:db8::1", 22)),
+            ("user@2001:db8::1", creds("user", "2001:db8::1", 22)),
+            ("ssh user@fe80::1 -p 2222", creds("user", "fe80::1", 2222)),
+            (
+                "  executor@host.example:22 \n",
+                creds("executor", "host.example", 22),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_credentials(input).unwrap(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_display_parses_back() {
+        for credentials in [
+            creds("ubuntu", "10.0.0.1", 2222),
+            creds("root", "host.example", 22),
+            creds("root", "2001:db8::1", 2222),
+        ] {
+            let formatted = credentials.to_string();
+            assert_eq!(parse_credentials(&formatted).unwrap(), credentials);
+        }
+        assert_eq!(
+            creds("root", "2001:db8::1", 2222).to_string(),
+            "root@[2001:db8::1]:2222"
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_credentials() {
+        for (input, reason) in [
+            ("user@host:abc", "invalid port number"),
+            ("user@host:70000", "invalid port number"),
+            ("ssh user@host -p x", "invalid port number"),
+            ("user@[2001:db8::1", "invalid IPv6 address"),
+            ("user@[::1]x", "unexpected characters"),
+            ("@host", "empty username"),
+            ("user@", "missing or malformed host"),
+            ("", "missing or malformed host"),
+            ("ssh user@host:2222 -p 22", "port given twice"),
+            ("ssh user@host -i key", "expected 'ssh"),
+        ] {
+            let err = parse_credentials(input).unwrap_err().to_string();
+            assert!(err.contains(reason), "{input}: {err}");
+        }
+    }
+}
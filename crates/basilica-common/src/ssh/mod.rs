@@ -9,6 +9,7 @@
 
 pub mod config;
 pub mod connection;
+pub mod credentials;
 pub mod manager;
 pub mod package_manager;
 pub mod simple;
@@ -20,6 +21,7 @@ mod tests;
 
 pub use config::*;
 pub use connection::*;
+pub use credentials::*;
 pub use manager::*;
 pub use package_manager::*;
 pub use simple::*;
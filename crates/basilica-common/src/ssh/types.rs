@@ -218,4 +218,8 @@ pub enum SshError {
     /// Command execution failed
     #[error("SSH command execution failed: {0}")]
     CommandFailed(String),
+
+    /// Credentials string could not be parsed
+    #[error("Invalid SSH credentials '{credentials}': {reason}")]
+    InvalidCredentials { credentials: String, reason: String },
 }
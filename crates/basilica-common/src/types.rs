@@ -180,10 +180,99 @@ impl fmt::Display for LocationProfile {
     }
 }
 
+/// Container image rentals run when neither the request nor the user's
+/// configuration names one
+pub const DEFAULT_CONTAINER_IMAGE: &str = "nvidia/cuda:12.8.0-runtime-ubuntu22.04";
+
+/// Pick the container image for a rental
+///
+/// An explicitly requested image wins over the user's configured image, which
+/// wins over [`DEFAULT_CONTAINER_IMAGE`]. Blank values count as unset.
+pub fn resolve_container_image(requested: Option<&str>, configured: Option<&str>) -> String {
+    [requested, configured]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|image| !image.is_empty())
+        .unwrap_or(DEFAULT_CONTAINER_IMAGE)
+        .to_string()
+}
+
+/// Error for a string that is not a MIG profile
+#[derive(Debug, Error)]
+#[error("Invalid MIG profile '{0}', expected a profile such as 1g.10gb")]
+pub struct InvalidMigProfile(String);
+
+/// Size of a MIG (Multi-Instance GPU) slice, written as `1g.10gb`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigProfile {
+    /// GPU compute slices given to the instance
+    pub compute_slices: u32,
+    /// Memory given to the instance, in GB
+    pub memory_gb: u32,
+}
+
+impl MigProfile {
+    /// Profile of a MIG device from its NVML name, or `None` for a whole GPU
+    ///
+    /// NVML names MIG devices after their parent GPU with the profile
+    /// appended, as in `NVIDIA A100-SXM4-80GB MIG 1g.10gb`.
+    pub fn from_device_name(name: &str) -> Option<Self> {
+        let (_, profile) = name.rsplit_once(" MIG ")?;
+        profile.parse().ok()
+    }
+}
+
+impl FromStr for MigProfile {
+    type Err = InvalidMigProfile;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidMigProfile(s.to_string());
+        // Media extensions (`+me`) do not change the slice size, and compute
+        // instance profiles (`1c.3g.20gb`) are sized by their GPU instance
+        let profile = s.trim().to_ascii_lowercase();
+        let profile = profile.split('+').next().unwrap_or_default();
+        let mut parts = profile.rsplit('.');
+        let memory = parts.next().and_then(|part| part.strip_suffix("gb"));
+        let slices = parts.next().and_then(|part| part.strip_suffix('g'));
+
+        match (slices, memory) {
+            (Some(slices), Some(memory)) => Ok(Self {
+                compute_slices: slices.parse().map_err(|_| invalid())?,
+                memory_gb: memory.parse().map_err(|_| invalid())?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for MigProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}g.{}gb", self.compute_slices, self.memory_gb)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_resolve_container_image_order() {
+        assert_eq!(
+            resolve_container_image(Some("ubuntu:22.04"), Some("pytorch/pytorch")),
+            "ubuntu:22.04"
+        );
+        assert_eq!(
+            resolve_container_image(None, Some("pytorch/pytorch")),
+            "pytorch/pytorch"
+        );
+        assert_eq!(
+            resolve_container_image(Some(" "), Some("")),
+            DEFAULT_CONTAINER_IMAGE
+        );
+        assert_eq!(resolve_container_image(None, None), DEFAULT_CONTAINER_IMAGE);
+    }
+
     #[test]
     fn test_location_profile_from_str() {
         // Full location
@@ -347,4 +436,32 @@ mod tests {
         let result: Result<ApiKeyName, _> = serde_json::from_str("\"invalid key\"");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_mig_profile_parsing() {
+        let profile: MigProfile = "1g.10gb".parse().unwrap();
+        assert_eq!(
+            profile,
+            MigProfile {
+                compute_slices: 1,
+                memory_gb: 10
+            }
+        );
+        assert_eq!(profile.to_string(), "1g.10gb");
+        assert_eq!("1g.10gb+me".parse::<MigProfile>().unwrap(), profile);
+        assert_eq!(
+            "1c.3g.40gb".parse::<MigProfile>().unwrap().to_string(),
+            "3g.40gb"
+        );
+        assert!("a100".parse::<MigProfile>().is_err());
+
+        assert_eq!(
+            MigProfile::from_device_name("NVIDIA A100-SXM4-80GB MIG 3g.40gb"),
+            Some(MigProfile {
+                compute_slices: 3,
+                memory_gb: 40
+            })
+        );
+        assert_eq!(MigProfile::from_device_name("NVIDIA A100-SXM4-80GB"), None);
+    }
 }
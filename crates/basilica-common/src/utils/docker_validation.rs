@@ -3,7 +3,7 @@
 //! Provides security validation for Docker image references to prevent
 //! command injection attacks when image names are used in shell commands.
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use oci_client::Reference;
 
 /// Validate Docker image reference
@@ -46,6 +46,56 @@ pub fn parse_docker_image(image: &str) -> Result<Reference> {
         })
 }
 
+/// Strip an `https://` scheme from `registry`, rejecting any other scheme
+/// so credentials never travel in clear text
+pub fn tls_registry_host(registry: &str) -> Result<String> {
+    let host = match registry.split_once("://") {
+        Some(("https", host)) => host,
+        Some((scheme, _)) => {
+            bail!("Registry credentials may only be sent over TLS, got {scheme}:// for {registry}")
+        }
+        None => registry,
+    };
+    let host = host.trim_end_matches('/');
+    if host.is_empty() || host.contains('/') {
+        bail!("Invalid registry host '{registry}'");
+    }
+    Ok(normalize_host(host))
+}
+
+/// Registry host an image is pulled from; images without one come from Docker Hub
+pub fn image_registry_host(image: &str) -> String {
+    match image.split_once('/') {
+        Some((first, _)) if first.contains('.') || first.contains(':') || first == "localhost" => {
+            normalize_host(first)
+        }
+        _ => "docker.io".to_string(),
+    }
+}
+
+/// Pick the repository digest belonging to `image`'s repository, falling
+/// back to the first one when the image is tagged into several repositories
+pub fn select_repo_digest(image: &str, digests: Vec<String>) -> Option<String> {
+    let last_slash = image.rfind('/').map_or(0, |i| i + 1);
+    let repository = match image[last_slash..].find([':', '@']) {
+        Some(i) => &image[..last_slash + i],
+        None => image,
+    };
+
+    digests
+        .iter()
+        .find(|digest| digest.split('@').next() == Some(repository))
+        .or(digests.first())
+        .cloned()
+}
+
+fn normalize_host(host: &str) -> String {
+    match host.to_ascii_lowercase().as_str() {
+        "index.docker.io" | "registry-1.docker.io" => "docker.io".to_string(),
+        host => host.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -15,7 +15,7 @@ use std::collections::HashMap;
 ///
 /// This function will return an error if:
 /// - Any string is not in KEY=VALUE format (missing '=' separator)
-/// - A key is empty
+/// - A key is empty or otherwise not a valid variable name (see [`validate_env_key`])
 ///
 /// # Examples
 ///
@@ -47,6 +47,7 @@ pub fn parse_env_vars(env_vars: &[String]) -> Result<HashMap<String, String>> {
                     env_var
                 ));
             }
+            validate_env_key(key)?;
             result.insert(key.to_string(), value.to_string());
         } else {
             return Err(anyhow!(
@@ -59,6 +60,39 @@ pub fn parse_env_vars(env_vars: &[String]) -> Result<HashMap<String, String>> {
     Ok(result)
 }
 
+/// Check that `key` can be used as an environment variable name
+///
+/// Names must be non-empty, must not contain `=` or NUL, and must not start
+/// with a digit, so that shells and `docker run -e` read them unambiguously.
+///
+/// # Examples
+///
+/// ```
+/// use basilica_common::utils::validate_env_key;
+///
+/// assert!(validate_env_key("HF_TOKEN").is_ok());
+/// assert!(validate_env_key("1PASSWORD").is_err());
+/// assert!(validate_env_key("A=B").is_err());
+/// ```
+pub fn validate_env_key(key: &str) -> Result<()> {
+    if key.is_empty() {
+        return Err(anyhow!("Environment variable name cannot be empty"));
+    }
+    if key.contains('=') || key.contains('\0') {
+        return Err(anyhow!(
+            "Invalid environment variable name '{}'. Names cannot contain '=' or NUL",
+            key.escape_debug()
+        ));
+    }
+    if key.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(anyhow!(
+            "Invalid environment variable name '{}'. Names cannot start with a digit",
+            key
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,6 +159,27 @@ mod tests {
             .contains("Key cannot be empty"));
     }
 
+    #[test]
+    fn test_key_starting_with_digit() {
+        let vars = vec!["9LIVES=cat".to_string()];
+
+        let result = parse_env_vars(&vars);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("cannot start with a digit"));
+    }
+
+    #[test]
+    fn test_validate_env_key() {
+        for key in ["PATH", "_PRIVATE", "HF_TOKEN", "lower_case", "V2"] {
+            assert!(validate_env_key(key).is_ok(), "{key}");
+        }
+        for key in ["", "A=B", "=", "1ABC", "NUL\0KEY"] {
+            assert!(validate_env_key(key).is_err(), "{key:?}");
+        }
+    }
+
     #[test]
     fn test_empty_input() {
         let vars: Vec<String> = vec![];
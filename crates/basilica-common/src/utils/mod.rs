@@ -7,6 +7,9 @@ pub mod docker_validation;
 pub mod env_vars;
 pub mod port_mapping;
 
-pub use docker_validation::{parse_docker_image, validate_docker_image};
-pub use env_vars::parse_env_vars;
+pub use docker_validation::{
+    image_registry_host, parse_docker_image, select_repo_digest, tls_registry_host,
+    validate_docker_image,
+};
+pub use env_vars::{parse_env_vars, validate_env_key};
 pub use port_mapping::{parse_port_mappings, PortMapping};
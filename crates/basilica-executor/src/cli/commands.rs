@@ -5,7 +5,8 @@
 use super::{CliCommand, CliContext};
 use anyhow::Result;
 use async_trait::async_trait;
-use clap::Subcommand;
+use clap::{Args, Subcommand};
+use std::time::Duration;
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum ValidatorCommands {
@@ -127,6 +128,36 @@ pub enum ServiceCommands {
         #[arg(short, long)]
         follow: bool,
     },
+    /// Drain the running executor for maintenance
+    Drain {
+        /// Seconds to let running rentals continue before stopping them;
+        /// waits for them to finish when omitted
+        #[arg(short, long)]
+        deadline: Option<u64>,
+        /// Cancel a drain in progress and accept rentals again
+        #[arg(long, conflicts_with = "deadline")]
+        cancel: bool,
+    },
+}
+
+/// Pre-flight checks for Docker, GPUs and network ports
+#[derive(Args, Debug, Clone)]
+pub struct SelftestCommand {
+    /// Image used to run `nvidia-smi` through the NVIDIA container runtime
+    #[arg(long, default_value = "nvidia/cuda:12.2.0-base-ubuntu22.04")]
+    pub image: String,
+    /// Skip GPU checks on hosts without GPUs
+    #[arg(long)]
+    pub skip_gpu: bool,
+    /// Timeout in seconds for pulling and running the GPU container
+    #[arg(long, default_value = "300")]
+    pub timeout: u64,
+}
+
+impl SelftestCommand {
+    pub fn timeout_duration(&self) -> Duration {
+        Duration::from_secs(self.timeout)
+    }
 }
 
 #[async_trait]
@@ -170,3 +201,10 @@ impl CliCommand for ServiceCommands {
         crate::cli::handlers::service::handle_service_command(self, context).await
     }
 }
+
+#[async_trait]
+impl CliCommand for SelftestCommand {
+    async fn execute(&self, context: &CliContext) -> Result<()> {
+        crate::cli::handlers::selftest::handle_selftest_command(self, context).await
+    }
+}
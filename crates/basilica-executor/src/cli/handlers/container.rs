@@ -76,7 +76,7 @@ async fn create_container(
 
     let container_id = state
         .container_manager
-        .create_container(image, &command_args, limits)
+        .create_container(image, &command_args, limits, None)
         .await?;
 
     if stream {
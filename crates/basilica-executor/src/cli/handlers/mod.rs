@@ -7,6 +7,7 @@ use anyhow::Result;
 pub mod container;
 pub mod network;
 pub mod resource;
+pub mod selftest;
 pub mod service;
 pub mod system;
 pub mod validator;
@@ -40,6 +40,12 @@ async fn show_resources(context: &CliContext) -> Result<()> {
         "  Total: {} GB",
         system_info.memory.total_bytes / (1024 * 1024 * 1024)
     );
+    if system_info.memory.effective_limit_bytes < system_info.memory.host_total_bytes {
+        println!(
+            "  Host Total: {} GB (container limited)",
+            system_info.memory.host_total_bytes / (1024 * 1024 * 1024)
+        );
+    }
     println!(
         "  Used: {} GB ({:.1}%)",
         system_info.memory.used_bytes / (1024 * 1024 * 1024),
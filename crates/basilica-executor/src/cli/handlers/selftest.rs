@@ -0,0 +1,353 @@
+//! Pre-flight self-test for Docker, the NVIDIA runtime and network ports
+//!
+//! Runs the checks an executor needs to pass before it can serve rentals and
+//! prints a pass/fail report, so misconfiguration shows up before the first
+//! rental fails rather than after.
+
+use super::HandlerUtils;
+use crate::cli::{commands::SelftestCommand, CliContext};
+use crate::config::ExecutorConfig;
+use crate::container_manager::config_builder::ContainerConfigBuilder;
+use crate::system_monitor::gpu::GpuMonitor;
+use anyhow::{anyhow, Context, Result};
+use bollard::{
+    container::{
+        CreateContainerOptions, LogsOptions, RemoveContainerOptions, WaitContainerOptions,
+    },
+    image::CreateImageOptions,
+    Docker,
+};
+use futures_util::stream::StreamExt;
+use std::fmt;
+use std::net::TcpListener;
+use std::path::Path;
+use std::time::Duration;
+
+/// Outcome of a single self-test check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Fail => write!(f, "FAIL"),
+            Self::Skip => write!(f, "SKIP"),
+        }
+    }
+}
+
+/// Result of a single self-test check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    pub fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, detail)
+    }
+
+    pub fn fail(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail)
+    }
+
+    pub fn skip(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Skip, detail)
+    }
+
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Collected self-test results
+#[derive(Debug, Clone, Default)]
+pub struct SelfTestReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl SelfTestReport {
+    pub fn push(&mut self, check: CheckResult) {
+        self.checks.push(check);
+    }
+
+    /// Number of failed checks; skipped checks do not count
+    pub fn failures(&self) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Fail)
+            .count()
+    }
+
+    pub fn passed(&self) -> bool {
+        self.failures() == 0
+    }
+
+    /// Render the report as one line per check
+    pub fn render(&self) -> String {
+        let width = self
+            .checks
+            .iter()
+            .map(|check| check.name.len())
+            .max()
+            .unwrap_or(0);
+
+        self.checks
+            .iter()
+            .map(|check| {
+                format!(
+                    "  [{}] {:<width$}  {}\n",
+                    check.status, check.name, check.detail
+                )
+            })
+            .collect()
+    }
+}
+
+pub async fn handle_selftest_command(cmd: &SelftestCommand, context: &CliContext) -> Result<()> {
+    HandlerUtils::print_info("Running executor self-test...");
+
+    let config = HandlerUtils::load_config(&context.config_path)?;
+    let report = run_selftest(&config, cmd).await;
+
+    println!("Executor Self-Test:");
+    print!("{}", report.render());
+
+    if report.passed() {
+        HandlerUtils::print_success("All self-test checks passed");
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{} of {} self-test checks failed",
+            report.failures(),
+            report.checks.len()
+        ))
+    }
+}
+
+/// Run every self-test check against the given configuration
+pub async fn run_selftest(config: &ExecutorConfig, cmd: &SelftestCommand) -> SelfTestReport {
+    let mut report = SelfTestReport::default();
+
+    let (docker_check, docker) = check_docker_socket(&config.docker.socket_path).await;
+    report.push(docker_check);
+
+    if cmd.skip_gpu {
+        report.push(CheckResult::skip("GPU devices", "skipped (--skip-gpu)"));
+        report.push(CheckResult::skip("GPU container", "skipped (--skip-gpu)"));
+    } else {
+        report.push(check_gpu_devices().await);
+        report.push(match docker {
+            Some(docker) => {
+                check_gpu_container(&docker, config, &cmd.image, cmd.timeout_duration()).await
+            }
+            None => CheckResult::fail("GPU container", "skipped: Docker is not reachable"),
+        });
+    }
+
+    for check in check_ports_bindable(config) {
+        report.push(check);
+    }
+
+    report
+}
+
+/// Verify the Docker socket exists and the daemon answers
+pub async fn check_docker_socket(socket_path: &str) -> (CheckResult, Option<Docker>) {
+    const NAME: &str = "Docker socket";
+
+    let path = socket_path.strip_prefix("unix://").unwrap_or(socket_path);
+    if !Path::new(path).exists() {
+        return (
+            CheckResult::fail(NAME, format!("{path} does not exist (docker.socket_path)")),
+            None,
+        );
+    }
+
+    let docker = match Docker::connect_with_unix(path, 10, bollard::API_DEFAULT_VERSION) {
+        Ok(docker) => docker,
+        Err(e) => return (CheckResult::fail(NAME, format!("{path}: {e}")), None),
+    };
+
+    match docker.version().await {
+        Ok(version) => (
+            CheckResult::pass(
+                NAME,
+                format!(
+                    "connected to Docker {} at {path}",
+                    version.version.unwrap_or_default()
+                ),
+            ),
+            Some(docker),
+        ),
+        Err(e) => (
+            CheckResult::fail(NAME, format!("cannot talk to Docker at {path}: {e}")),
+            None,
+        ),
+    }
+}
+
+/// Verify the GPU monitor can see at least one device
+pub async fn check_gpu_devices() -> CheckResult {
+    const NAME: &str = "GPU devices";
+
+    match GpuMonitor::with_required_gpus(true).get_gpu_info().await {
+        Ok(gpus) => {
+            let names: Vec<&str> = gpus.iter().map(|gpu| gpu.name.as_str()).collect();
+            CheckResult::pass(NAME, format!("{} GPU(s): {}", gpus.len(), names.join(", ")))
+        }
+        Err(e) => CheckResult::fail(NAME, format!("{e:#}")),
+    }
+}
+
+/// Run `nvidia-smi` in a short-lived container built like a rental container
+pub async fn check_gpu_container(
+    docker: &Docker,
+    config: &ExecutorConfig,
+    image: &str,
+    timeout: Duration,
+) -> CheckResult {
+    const NAME: &str = "GPU container";
+
+    if !config.docker.enable_gpu_passthrough {
+        return CheckResult::fail(NAME, "docker.enable_gpu_passthrough is disabled");
+    }
+
+    match tokio::time::timeout(timeout, run_nvidia_smi(docker, config, image)).await {
+        Ok(Ok(summary)) => CheckResult::pass(NAME, summary),
+        Ok(Err(e)) => CheckResult::fail(NAME, format!("{e:#}")),
+        Err(_) => CheckResult::fail(NAME, format!("timed out after {}s", timeout.as_secs())),
+    }
+}
+
+async fn run_nvidia_smi(docker: &Docker, config: &ExecutorConfig, image: &str) -> Result<String> {
+    let mut pull = docker.create_image(
+        Some(CreateImageOptions {
+            from_image: image,
+            ..Default::default()
+        }),
+        None,
+        None,
+    );
+    while let Some(progress) = pull.next().await {
+        progress.with_context(|| format!("Failed to pull {image}"))?;
+    }
+
+    let container_config = ContainerConfigBuilder::new(config.docker.clone()).build(
+        image,
+        &["nvidia-smi".to_string()],
+        &config.docker.resource_limits,
+    )?;
+
+    let uuid_str = uuid::Uuid::new_v4().to_string();
+    let name = format!("basilca-selftest-{}", &uuid_str[..8]);
+    let container = docker
+        .create_container(
+            Some(CreateContainerOptions {
+                name: name.clone(),
+                platform: None,
+            }),
+            container_config,
+        )
+        .await
+        .context("Failed to create container")?;
+
+    let result = wait_and_collect_output(docker, &container.id).await;
+
+    let _ = docker
+        .remove_container(
+            &container.id,
+            Some(RemoveContainerOptions {
+                force: true,
+                v: true,
+                link: false,
+            }),
+        )
+        .await;
+
+    let (exit_code, output) = result?;
+    if exit_code != 0 {
+        let tail: Vec<&str> = output.lines().rev().take(3).collect();
+        return Err(anyhow!(
+            "nvidia-smi exited with code {exit_code}: {}",
+            tail.into_iter().rev().collect::<Vec<_>>().join(" | ")
+        ));
+    }
+
+    let summary = output
+        .lines()
+        .find(|line| line.contains("Driver Version"))
+        .map(|line| line.trim_matches(|c: char| c == '|' || c.is_whitespace()))
+        .unwrap_or("nvidia-smi succeeded");
+    Ok(summary.to_string())
+}
+
+async fn wait_and_collect_output(docker: &Docker, container_id: &str) -> Result<(i64, String)> {
+    docker
+        .start_container::<String>(container_id, None)
+        .await
+        .context("Failed to start container")?;
+
+    let exit_code = match docker
+        .wait_container(
+            container_id,
+            Some(WaitContainerOptions {
+                condition: "not-running",
+            }),
+        )
+        .next()
+        .await
+    {
+        Some(Ok(result)) => result.status_code,
+        Some(Err(bollard::errors::Error::DockerContainerWaitError { code, .. })) => code,
+        Some(Err(e)) => return Err(e.into()),
+        None => return Err(anyhow!("Container wait stream ended unexpectedly")),
+    };
+
+    let mut logs = docker.logs(
+        container_id,
+        Some(LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            ..Default::default()
+        }),
+    );
+    let mut output = String::new();
+    while let Some(chunk) = logs.next().await {
+        output.push_str(&chunk?.to_string());
+    }
+
+    Ok((exit_code, output))
+}
+
+/// Verify the ports the executor listens on can be bound
+pub fn check_ports_bindable(config: &ExecutorConfig) -> Vec<CheckResult> {
+    let host = config.server.host.as_str();
+    let mut ports = vec![("gRPC port", config.server.port)];
+    if let Some(health) = config.advertised_endpoint.port_mappings.get("health") {
+        ports.push(("health port", *health));
+    }
+
+    ports
+        .into_iter()
+        .map(|(name, port)| match TcpListener::bind((host, port)) {
+            Ok(_) => CheckResult::pass(name, format!("{host}:{port} is bindable")),
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => CheckResult::fail(
+                name,
+                format!("{host}:{port} is already in use (is the executor already running?)"),
+            ),
+            Err(e) => CheckResult::fail(name, format!("cannot bind {host}:{port}: {e}")),
+        })
+        .collect()
+}
@@ -2,6 +2,9 @@ use super::HandlerUtils;
 use crate::cli::{commands::ServiceCommands, CliContext};
 use anyhow::Result;
 use basilica_common::config::ConfigValidation;
+use basilica_protocol::executor_management::{
+    executor_management_client::ExecutorManagementClient, DrainModeRequest,
+};
 
 pub async fn handle_service_command(cmd: &ServiceCommands, context: &CliContext) -> Result<()> {
     match cmd {
@@ -12,6 +15,9 @@ pub async fn handle_service_command(cmd: &ServiceCommands, context: &CliContext)
         ServiceCommands::Health => run_health_check(context).await,
         ServiceCommands::Reload => reload_config(context).await,
         ServiceCommands::Logs { lines, follow } => show_logs(*lines, *follow, context).await,
+        ServiceCommands::Drain { deadline, cancel } => {
+            set_drain_mode(*deadline, *cancel, context).await
+        }
     }
 }
 
@@ -111,6 +117,37 @@ async fn show_status(context: &CliContext) -> Result<()> {
     Ok(())
 }
 
+async fn set_drain_mode(deadline: Option<u64>, cancel: bool, context: &CliContext) -> Result<()> {
+    let config = HandlerUtils::load_config(&context.config_path)?;
+    let addr = format!("http://{}:{}", config.server.host, config.server.port);
+
+    let mut client = ExecutorManagementClient::connect(addr.clone())
+        .await
+        .map_err(|e| anyhow::anyhow!("Executor service not reachable at {addr}: {e}"))?;
+    let response = client
+        .set_drain_mode(DrainModeRequest {
+            enabled: !cancel,
+            deadline_seconds: deadline.unwrap_or(0),
+            miner_hotkey: config.managing_miner_hotkey.to_string(),
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to set drain mode: {}", e.message()))?
+        .into_inner();
+
+    if !response.draining {
+        HandlerUtils::print_success("Executor is accepting rentals");
+        return Ok(());
+    }
+
+    HandlerUtils::print_success("Executor is draining and refusing new rentals");
+    println!("  Active Rentals: {}", response.active_rentals);
+    match response.estimated_drain_seconds {
+        Some(seconds) => println!("  Estimated Drain Time: {seconds}s"),
+        None => println!("  Estimated Drain Time: until running rentals finish"),
+    }
+    Ok(())
+}
+
 async fn run_health_check(context: &CliContext) -> Result<()> {
     HandlerUtils::print_info("Running comprehensive health check...");
 
@@ -53,6 +53,8 @@ pub enum Commands {
     Network(NetworkCommands),
     #[command(subcommand)]
     Service(ServiceCommands),
+    /// Validate Docker, GPU and port setup before serving
+    Selftest(SelftestCommand),
 }
 
 pub async fn execute_cli() -> Result<()> {
@@ -70,6 +72,7 @@ pub async fn execute_command(command: Commands, context: &CliContext) -> Result<
         Commands::Resource(cmd) => cmd.execute(context).await,
         Commands::Network(cmd) => cmd.execute(context).await,
         Commands::Service(cmd) => cmd.execute(context).await,
+        Commands::Selftest(cmd) => cmd.execute(context).await,
     }
 }
 
@@ -83,6 +86,7 @@ impl CliCommand for Commands {
             Commands::Resource(cmd) => cmd.execute(context).await,
             Commands::Network(cmd) => cmd.execute(context).await,
             Commands::Service(cmd) => cmd.execute(context).await,
+            Commands::Selftest(cmd) => cmd.execute(context).await,
         }
     }
 }
@@ -1,6 +1,8 @@
 //! Docker configuration types and validation
 
+pub use basilica_common::config::{ImageCacheConfig, ImagePolicyConfig};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// Docker configuration
@@ -31,6 +33,14 @@ pub struct DockerConfig {
 
     /// Container registry configuration
     pub registry: ContainerRegistryConfig,
+
+    /// Which container images renters may run
+    #[serde(default)]
+    pub image_policy: ImagePolicyConfig,
+
+    /// Image pre-pulling and digest pinning
+    #[serde(default)]
+    pub image_cache: ImageCacheConfig,
 }
 
 /// Container resource limits
@@ -98,6 +108,28 @@ pub struct ContainerRegistryConfig {
 
     /// Allowed image registries
     pub allowed_registries: Vec<String>,
+
+    /// Hex-encoded AES-256 key the stored registry secrets are encrypted with
+    #[serde(default)]
+    pub secrets_key_hex: Option<String>,
+
+    /// Registry secrets container specs may reference by name
+    #[serde(default)]
+    pub secrets: HashMap<String, StoredRegistrySecret>,
+}
+
+/// Registry credentials kept in the config for container specs to reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredRegistrySecret {
+    /// Registry host the secret belongs to
+    pub registry: String,
+
+    /// Registry username
+    pub username: String,
+
+    /// Token encrypted with `secrets_key_hex` in the `Aead` format
+    /// (`<base64_nonce>:<base64_ciphertext>`)
+    pub encrypted_token: String,
 }
 
 impl Default for DockerConfig {
@@ -111,6 +143,8 @@ impl Default for DockerConfig {
             max_concurrent_containers: 10,
             enable_gpu_passthrough: true,
             registry: ContainerRegistryConfig::default(),
+            image_policy: ImagePolicyConfig::default(),
+            image_cache: ImageCacheConfig::default(),
         }
     }
 }
@@ -150,6 +184,8 @@ impl Default for ContainerRegistryConfig {
                 "ghcr.io".to_string(),
                 "quay.io".to_string(),
             ],
+            secrets_key_hex: None,
+            secrets: HashMap::new(),
         }
     }
 }
@@ -7,6 +7,7 @@
 //! - Dependency Inversion: Abstractions over concrete implementations
 
 pub mod docker;
+pub mod reload;
 pub mod system;
 pub mod types;
 pub mod validation;
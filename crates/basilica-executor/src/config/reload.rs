@@ -0,0 +1,265 @@
+//! Runtime configuration reload
+//!
+//! Re-reads the executor configuration file, validates it and applies the
+//! subset of settings that can change without restarting the executor.
+//! Everything else is reported so operators know a restart is needed.
+
+use anyhow::{Context, Result};
+use basilica_common::config::ConfigValidation;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use super::types::{ExecutorConfig, TelemetryMonitorConfig};
+use crate::system_monitor::SystemMonitor;
+
+/// Settings that take effect without a restart, as dotted config paths
+pub const HOT_RELOADABLE: &[&str] = &[
+    "system.update_interval",
+    "system.max_cpu_usage",
+    "system.max_memory_usage",
+    "system.max_gpu_memory_usage",
+    "system.min_disk_space_gb",
+    "system.enable_metrics_recording",
+    "system.telemetry_monitor.host_interval_secs",
+    "system.telemetry_monitor.container_sample_secs",
+    "server.advertised_host",
+    "server.advertised_port",
+    "server.advertised_tls",
+    "advertised_endpoint",
+    "miner_registration",
+];
+
+/// Outcome of a reload
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// Changed settings that were applied
+    pub applied: Vec<String>,
+    /// Changed settings that only take effect after a restart
+    pub requires_restart: Vec<String>,
+}
+
+impl ReloadReport {
+    /// Whether any applied change affects what is registered with the miner
+    pub fn registration_changed(&self) -> bool {
+        self.applied.iter().any(|path| {
+            path.starts_with("server.advertised_")
+                || path.starts_with("advertised_endpoint")
+                || path.starts_with("miner_registration")
+        })
+    }
+
+    /// Whether any applied change affects the telemetry collector
+    pub fn telemetry_changed(&self) -> bool {
+        self.applied
+            .iter()
+            .any(|path| path.starts_with("system.telemetry_monitor."))
+    }
+}
+
+/// Reloads the executor configuration from disk on demand
+pub struct ConfigReloader {
+    path: PathBuf,
+    current: ExecutorConfig,
+    system_monitor: Arc<SystemMonitor>,
+    telemetry_updates: Option<watch::Sender<TelemetryMonitorConfig>>,
+}
+
+impl ConfigReloader {
+    pub fn new(path: PathBuf, current: ExecutorConfig, system_monitor: Arc<SystemMonitor>) -> Self {
+        Self {
+            path,
+            current,
+            system_monitor,
+            telemetry_updates: None,
+        }
+    }
+
+    /// Publish telemetry interval changes to a running collector
+    pub fn with_telemetry_updates(
+        mut self,
+        telemetry_updates: watch::Sender<TelemetryMonitorConfig>,
+    ) -> Self {
+        self.telemetry_updates = Some(telemetry_updates);
+        self
+    }
+
+    /// The configuration currently in effect
+    pub fn current(&self) -> &ExecutorConfig {
+        &self.current
+    }
+
+    /// Re-read the configuration file and apply hot-reloadable changes.
+    ///
+    /// An invalid file leaves the running configuration untouched.
+    pub fn reload(&mut self) -> Result<ReloadReport> {
+        let updated = load_validated(&self.path)?;
+        let (effective, report) = merge_hot_reloadable(&self.current, &updated)?;
+
+        for path in &report.applied {
+            info!("Config reload: applied {}", path);
+        }
+        for path in &report.requires_restart {
+            warn!("Config reload: {} changed but requires a restart", path);
+        }
+
+        self.system_monitor.update_config(effective.system.clone());
+        if report.telemetry_changed() {
+            if let Some(ref tx) = self.telemetry_updates {
+                tx.send_replace(effective.system.telemetry_monitor.clone());
+            }
+        }
+
+        self.current = effective;
+        Ok(report)
+    }
+}
+
+/// Load a configuration file and run the same validation as startup
+pub fn load_validated(path: &Path) -> Result<ExecutorConfig> {
+    let config = ExecutorConfig::load_from_file(path)
+        .with_context(|| format!("Failed to load config from {}", path.display()))?;
+    config
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid configuration: {e}"))?;
+    config
+        .validate_advertised_endpoints()
+        .map_err(|e| anyhow::anyhow!("Invalid advertised endpoints: {e}"))?;
+    Ok(config)
+}
+
+/// Compute the configuration that results from applying only the
+/// hot-reloadable changes in `updated` on top of `current`
+pub fn merge_hot_reloadable(
+    current: &ExecutorConfig,
+    updated: &ExecutorConfig,
+) -> Result<(ExecutorConfig, ReloadReport)> {
+    let mut merged = serde_json::to_value(current)?;
+    let updated_value = serde_json::to_value(updated)?;
+
+    let mut changed = Vec::new();
+    collect_changes("", &merged, &updated_value, &mut changed);
+
+    let mut report = ReloadReport::default();
+    for path in changed {
+        if is_hot_reloadable(&path) {
+            report.applied.push(path);
+        } else {
+            report.requires_restart.push(path);
+        }
+    }
+
+    for path in HOT_RELOADABLE {
+        let pointer = format!("/{}", path.replace('.', "/"));
+        if let Some(value) = updated_value.pointer(&pointer) {
+            set_pointer(&mut merged, path, value.clone());
+        }
+    }
+
+    let effective = serde_json::from_value(merged)?;
+    Ok((effective, report))
+}
+
+fn is_hot_reloadable(path: &str) -> bool {
+    HOT_RELOADABLE.iter().any(|prefix| {
+        path == *prefix
+            || path
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+/// Collect the dotted paths of every leaf that differs between two values
+fn collect_changes(prefix: &str, old: &Value, new: &Value, changed: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let mut keys: Vec<&String> = old_map.keys().chain(new_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                let old_child = old_map.get(key).unwrap_or(&Value::Null);
+                let new_child = new_map.get(key).unwrap_or(&Value::Null);
+                collect_changes(&path, old_child, new_child, changed);
+            }
+        }
+        _ if old != new => changed.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
+fn set_pointer(root: &mut Value, dotted_path: &str, value: Value) {
+    let mut node = root;
+    let mut segments = dotted_path.split('.').peekable();
+
+    while let Some(segment) = segments.next() {
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        let Value::Object(map) = node else {
+            return;
+        };
+
+        if segments.peek().is_none() {
+            map.insert(segment.to_string(), value);
+            return;
+        }
+        node = map.entry(segment.to_string()).or_insert(Value::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_change_is_hot_reloadable() {
+        let current = ExecutorConfig::default();
+        let mut updated = current.clone();
+        updated.system.max_cpu_usage = 75.0;
+
+        let (effective, report) = merge_hot_reloadable(&current, &updated).unwrap();
+
+        assert_eq!(effective.system.max_cpu_usage, 75.0);
+        assert_eq!(report.applied, vec!["system.max_cpu_usage".to_string()]);
+        assert!(report.requires_restart.is_empty());
+    }
+
+    #[test]
+    fn test_restart_only_change_is_not_applied() {
+        let current = ExecutorConfig::default();
+        let mut updated = current.clone();
+        updated.server.port = 60000;
+        updated.system.telemetry_monitor.host_interval_secs = 15;
+
+        let (effective, report) = merge_hot_reloadable(&current, &updated).unwrap();
+
+        assert_eq!(effective.server.port, current.server.port);
+        assert_eq!(effective.system.telemetry_monitor.host_interval_secs, 15);
+        assert_eq!(report.requires_restart, vec!["server.port".to_string()]);
+        assert!(report.telemetry_changed());
+        assert!(!report.registration_changed());
+    }
+
+    #[test]
+    fn test_advertised_endpoint_change_triggers_registration() {
+        let current = ExecutorConfig::default();
+        let mut updated = current.clone();
+        updated.advertised_endpoint.grpc_endpoint = Some("http://203.0.113.5:50051".to_string());
+
+        let (effective, report) = merge_hot_reloadable(&current, &updated).unwrap();
+
+        assert_eq!(
+            effective.advertised_endpoint.grpc_endpoint.as_deref(),
+            Some("http://203.0.113.5:50051")
+        );
+        assert!(report.registration_changed());
+    }
+}
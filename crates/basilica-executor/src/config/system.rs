@@ -16,6 +16,11 @@ pub struct SystemConfig {
     /// GPU monitoring enabled
     pub enable_gpu_monitoring: bool,
 
+    /// Fail system info collection when no GPU can be detected instead of
+    /// reporting zero GPUs
+    #[serde(default)]
+    pub require_gpus: bool,
+
     /// Network monitoring enabled
     pub enable_network_monitoring: bool,
 
@@ -54,6 +59,7 @@ impl Default for SystemConfig {
         Self {
             update_interval: Duration::from_secs(5),
             enable_gpu_monitoring: true,
+            require_gpus: false,
             enable_network_monitoring: true,
             enable_memory_monitoring: true,
             enable_cpu_monitoring: true,
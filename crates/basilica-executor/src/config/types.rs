@@ -3,7 +3,8 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 
 use basilica_common::config::{loader, LoggingConfig, MetricsConfig, ServerConfig};
 use basilica_common::identity::Hotkey;
@@ -27,6 +28,71 @@ pub struct ExecutorAdvertisedEndpoint {
     /// Custom port mappings for different services
     #[serde(default)]
     pub port_mappings: HashMap<String, u16>,
+    /// How strictly advertised addresses are checked for reachability
+    #[serde(default)]
+    pub address_check: AdvertisedAddressCheck,
+}
+
+/// Reachability checks applied to advertised endpoint addresses
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvertisedAddressCheck {
+    /// Reject loopback and unspecified addresses once a miner endpoint is
+    /// configured on another host; also reject private addresses when that
+    /// miner is on a public IP
+    #[default]
+    Auto,
+    /// Always reject loopback, unspecified and private addresses
+    Strict,
+    /// Skip address checks, for local development
+    Off,
+}
+
+/// Why an advertised address cannot be reached by a remote miner
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UnreachableAddress {
+    Loopback,
+    Unspecified,
+    Private,
+}
+
+impl UnreachableAddress {
+    fn describe(&self) -> &'static str {
+        match self {
+            Self::Loopback => "a loopback address",
+            Self::Unspecified => "an unspecified (wildcard) address",
+            Self::Private => "a private address",
+        }
+    }
+}
+
+/// Registration with the managing miner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MinerRegistrationConfig {
+    /// Miner gRPC endpoint serving the ExecutorRegistration service.
+    /// Registration is skipped when unset.
+    pub endpoint: Option<String>,
+    /// Heartbeat interval used when the miner does not specify one
+    pub heartbeat_interval_secs: u64,
+    /// Initial delay between failed registration attempts
+    pub initial_backoff_ms: u64,
+    /// Upper bound for the registration retry delay
+    pub max_backoff_secs: u64,
+    /// Timeout applied to each registration/heartbeat request
+    pub request_timeout_secs: u64,
+}
+
+impl Default for MinerRegistrationConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            heartbeat_interval_secs: 30,
+            initial_backoff_ms: 500,
+            max_backoff_secs: 60,
+            request_timeout_secs: 10,
+        }
+    }
 }
 
 /// Telemetry service configuration
@@ -42,6 +108,29 @@ pub struct TelemetryConfig {
     pub api_key_header: String,
 }
 
+/// Behaviour of the telemetry queue when the billing stream falls behind
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueuePolicy {
+    /// Evict the oldest queued sample to make room for the newest
+    DropOldest,
+    /// Discard the incoming sample
+    DropNewest,
+    /// Wait for the stream to drain before accepting more samples
+    #[default]
+    Block,
+}
+
+impl QueuePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DropOldest => "drop_oldest",
+            Self::DropNewest => "drop_newest",
+            Self::Block => "block",
+        }
+    }
+}
+
 /// Telemetry monitor configuration
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TelemetryMonitorConfig {
@@ -52,10 +141,27 @@ pub struct TelemetryMonitorConfig {
     pub host_interval_secs: u64,
     #[serde(default = "default_queue_capacity")]
     pub queue_capacity: usize,
+    /// What to do with samples when the queue is full
+    #[serde(default)]
+    pub queue_policy: QueuePolicy,
     #[serde(default = "default_container_sample_secs")]
     pub container_sample_secs: u64,
     #[serde(default = "default_update_lifecycle")]
     pub update_lifecycle_status: bool,
+    /// Interfaces counted towards billed network traffic. When empty, every
+    /// interface except loopback and container bridges is counted.
+    #[serde(default)]
+    pub billed_interfaces: Vec<String>,
+    /// Directory for spooling samples to disk while billing is unreachable.
+    /// Spooling is disabled when unset.
+    #[serde(default)]
+    pub spool_dir: Option<PathBuf>,
+    /// Maximum size of the spool; the oldest samples are dropped beyond it
+    #[serde(default = "default_spool_max_bytes")]
+    pub spool_max_bytes: u64,
+    /// Spooled samples older than this are discarded instead of replayed
+    #[serde(default = "default_spool_max_age_secs")]
+    pub spool_max_age_secs: u64,
 }
 
 // Default functions for telemetry configuration
@@ -77,6 +183,12 @@ fn default_container_sample_secs() -> u64 {
 fn default_update_lifecycle() -> bool {
     true
 }
+fn default_spool_max_bytes() -> u64 {
+    64 * 1024 * 1024
+}
+fn default_spool_max_age_secs() -> u64 {
+    24 * 60 * 60
+}
 
 impl Default for TelemetryMonitorConfig {
     fn default() -> Self {
@@ -84,8 +196,13 @@ impl Default for TelemetryMonitorConfig {
             enabled: default_telemetry_enabled(),
             host_interval_secs: default_host_interval_secs(),
             queue_capacity: default_queue_capacity(),
+            queue_policy: QueuePolicy::default(),
             container_sample_secs: default_container_sample_secs(),
             update_lifecycle_status: default_update_lifecycle(),
+            billed_interfaces: Vec::new(),
+            spool_dir: None,
+            spool_max_bytes: default_spool_max_bytes(),
+            spool_max_age_secs: default_spool_max_age_secs(),
         }
     }
 }
@@ -117,6 +234,10 @@ pub struct ExecutorConfig {
     /// Managing miner hotkey (for authentication)
     pub managing_miner_hotkey: Hotkey,
 
+    /// Registration with the managing miner
+    #[serde(default)]
+    pub miner_registration: MinerRegistrationConfig,
+
     /// Advertised endpoint configuration
     #[serde(default)]
     pub advertised_endpoint: ExecutorAdvertisedEndpoint,
@@ -125,6 +246,11 @@ pub struct ExecutorConfig {
     /// If not specified, will be generated from ExecutorState
     #[serde(default)]
     pub executor_id: Option<String>,
+
+    /// Labels added to every metric and telemetry sample, e.g. cluster,
+    /// region or rack. Names must be valid Prometheus label names.
+    #[serde(default, deserialize_with = "deserialize_static_labels")]
+    pub static_labels: HashMap<String, String>,
 }
 
 impl Default for ExecutorConfig {
@@ -145,12 +271,40 @@ impl Default for ExecutorConfig {
                 "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
             )
             .unwrap(), // Default Alice hotkey
+            miner_registration: MinerRegistrationConfig::default(),
             advertised_endpoint: ExecutorAdvertisedEndpoint::default(),
             executor_id: None,
+            static_labels: HashMap::new(),
         }
     }
 }
 
+/// Whether `name` is a legal Prometheus label name
+///
+/// Names starting with `__` are reserved for Prometheus itself.
+pub fn is_valid_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_legally = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    starts_legally
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
+}
+
+fn deserialize_static_labels<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let labels = HashMap::<String, String>::deserialize(deserializer)?;
+    if let Some(name) = labels.keys().find(|name| !is_valid_label_name(name)) {
+        return Err(serde::de::Error::custom(format!(
+            "invalid static label name '{name}': label names must match [a-zA-Z_][a-zA-Z0-9_]* and must not start with '__'"
+        )));
+    }
+    Ok(labels)
+}
+
 impl ExecutorConfig {
     /// Load configuration using common loader
     pub fn load() -> Result<Self> {
@@ -235,6 +389,109 @@ impl ExecutorConfig {
             }
         }
 
+        self.validate_advertised_reachability()
+    }
+
+    /// Reject advertised addresses a remote miner or validator cannot reach
+    fn validate_advertised_reachability(&self) -> Result<(), String> {
+        let reject_private = match self.advertised_endpoint.address_check {
+            AdvertisedAddressCheck::Off => return Ok(()),
+            AdvertisedAddressCheck::Strict => true,
+            AdvertisedAddressCheck::Auto => {
+                let Some(miner_host) = self
+                    .miner_registration
+                    .endpoint
+                    .as_deref()
+                    .map(endpoint_host)
+                else {
+                    return Ok(());
+                };
+                match classify_host(miner_host) {
+                    // Miner on the same host: local addresses are reachable
+                    Some(UnreachableAddress::Loopback) => return Ok(()),
+                    // Miner on a public IP cannot reach private addresses
+                    None => miner_host.parse::<IpAddr>().is_ok(),
+                    Some(_) => false,
+                }
+            }
+        };
+
+        let endpoints = [
+            ("gRPC", self.get_advertised_grpc_endpoint()),
+            ("SSH", self.get_advertised_ssh_endpoint()),
+            ("health", self.get_advertised_health_endpoint()),
+        ];
+
+        for (service, endpoint) in endpoints {
+            let host = endpoint_host(&endpoint);
+            let Some(kind) = classify_host(host) else {
+                continue;
+            };
+            if kind == UnreachableAddress::Private && !reject_private {
+                continue;
+            }
+
+            return Err(format!(
+                "Advertised {service} endpoint {endpoint} uses {} ({host}) that a remote \
+                 miner cannot reach. Set server.advertised_host or \
+                 advertised_endpoint.{}_endpoint to a reachable address, or set \
+                 advertised_endpoint.address_check = \"off\" for local development",
+                kind.describe(),
+                service.to_lowercase(),
+            ));
+        }
+
         Ok(())
     }
 }
+
+/// Extract the host from an endpoint such as `http://1.2.3.4:50051/health`
+fn endpoint_host(endpoint: &str) -> &str {
+    let without_scheme = endpoint
+        .split_once("://")
+        .map_or(endpoint, |(_, rest)| rest);
+    let authority = without_scheme
+        .split(['/', '?'])
+        .next()
+        .unwrap_or(without_scheme);
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+
+    if let Some(bracketed) = authority.strip_prefix('[') {
+        return bracketed.split(']').next().unwrap_or(bracketed);
+    }
+    match authority.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') && port.parse::<u16>().is_ok() => host,
+        _ => authority,
+    }
+}
+
+/// Classify a host that is not reachable from other machines; `None` means
+/// the host is public or a DNS name that cannot be judged locally
+fn classify_host(host: &str) -> Option<UnreachableAddress> {
+    if host.is_empty() {
+        return Some(UnreachableAddress::Unspecified);
+    }
+    if host.eq_ignore_ascii_case("localhost") {
+        return Some(UnreachableAddress::Loopback);
+    }
+
+    match host.parse::<IpAddr>().ok()? {
+        ip if ip.is_loopback() => Some(UnreachableAddress::Loopback),
+        ip if ip.is_unspecified() => Some(UnreachableAddress::Unspecified),
+        IpAddr::V4(v4) if v4.is_private() || v4.is_link_local() => {
+            Some(UnreachableAddress::Private)
+        }
+        IpAddr::V6(v6) => {
+            let first = v6.segments()[0];
+            // Unique local (fc00::/7) and link-local (fe80::/10)
+            if (first & 0xfe00) == 0xfc00 || (first & 0xffc0) == 0xfe80 {
+                Some(UnreachableAddress::Private)
+            } else {
+                None
+            }
+        }
+        IpAddr::V4(_) => None,
+    }
+}
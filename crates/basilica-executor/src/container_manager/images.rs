@@ -0,0 +1,305 @@
+//! Image pulling, pre-pulling and digest pinning
+//!
+//! Large CUDA images dominate rental start time on first use. The cache
+//! pulls configured images at startup, skips pulls for images already on the
+//! host and, when digest pinning is enabled, resolves `image:tag` to the
+//! digest it was first pulled at so identical rentals reuse the same layers.
+//! Pulls from private registries pass the credentials through to the
+//! runtime; they are never logged. Pull progress is published to
+//! subscribers, which forward it onto the telemetry stream.
+
+use super::registry_auth::RegistryCredentials;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use basilica_common::utils::select_repo_digest;
+use basilica_protocol::billing::TelemetryData;
+use bollard::{image::CreateImageOptions, Docker};
+use futures_util::stream::StreamExt;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, info, warn};
+
+/// Progress of an image pull, aggregated over its layers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImagePullProgress {
+    pub image: String,
+    pub layers_total: usize,
+    pub layers_done: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+impl ImagePullProgress {
+    /// Telemetry sample reporting this progress for the executor host
+    pub fn to_telemetry(
+        &self,
+        executor_id: &str,
+        timestamp: prost_types::Timestamp,
+    ) -> TelemetryData {
+        let custom_metrics = HashMap::from([
+            (
+                "image_pull.layers_total".to_string(),
+                self.layers_total as f64,
+            ),
+            (
+                "image_pull.layers_done".to_string(),
+                self.layers_done as f64,
+            ),
+            ("image_pull.bytes_done".to_string(), self.bytes_done as f64),
+            (
+                "image_pull.bytes_total".to_string(),
+                self.bytes_total as f64,
+            ),
+        ]);
+
+        TelemetryData {
+            rental_id: String::new(),
+            executor_id: executor_id.to_string(),
+            timestamp: Some(timestamp),
+            resource_usage: None,
+            custom_metrics,
+            lifecycle_event: None,
+            labels: HashMap::from([("image".to_string(), self.image.clone())]),
+        }
+    }
+
+    /// Whether every layer seen so far has finished downloading
+    pub fn is_complete(&self) -> bool {
+        self.layers_done == self.layers_total
+    }
+}
+
+/// Pull progress updates buffered per subscriber; slow subscribers skip
+/// ahead rather than holding up the pull
+const PROGRESS_CHANNEL_CAPACITY: usize = 256;
+
+/// Callback receiving pull progress updates
+pub type PullProgressFn<'a> = &'a (dyn Fn(&ImagePullProgress) + Send + Sync);
+
+/// Image operations the cache needs from the container runtime
+#[async_trait]
+pub trait ImageBackend: Send + Sync {
+    /// Repository digests (`name@sha256:...`) of a local image, or `None`
+    /// if the image is not present on the host
+    async fn local_digests(&self, image: &str) -> Result<Option<Vec<String>>>;
+
+    /// Pull an image, authenticating with `auth` when given and reporting
+    /// progress as layers download
+    async fn pull(
+        &self,
+        image: &str,
+        auth: Option<&RegistryCredentials>,
+        on_progress: PullProgressFn<'_>,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+impl ImageBackend for Docker {
+    async fn local_digests(&self, image: &str) -> Result<Option<Vec<String>>> {
+        match self.inspect_image(image).await {
+            Ok(inspect) => Ok(Some(inspect.repo_digests.unwrap_or_default())),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn pull(
+        &self,
+        image: &str,
+        auth: Option<&RegistryCredentials>,
+        on_progress: PullProgressFn<'_>,
+    ) -> Result<()> {
+        let mut stream = self.create_image(
+            Some(CreateImageOptions {
+                from_image: image,
+                ..Default::default()
+            }),
+            None,
+            auth.map(RegistryCredentials::to_docker_credentials),
+        );
+
+        // Per-layer (current, total) bytes and completion
+        let mut layers: HashMap<String, (u64, u64, bool)> = HashMap::new();
+
+        while let Some(info) = stream.next().await {
+            let info = info.with_context(|| format!("Failed to pull image {image}"))?;
+            let Some(layer_id) = info.id else {
+                continue;
+            };
+
+            let layer = layers.entry(layer_id).or_default();
+            if let Some(detail) = info.progress_detail {
+                layer.0 = detail.current.unwrap_or(0).max(0) as u64;
+                layer.1 = detail.total.unwrap_or(0).max(0) as u64;
+            }
+            if matches!(
+                info.status.as_deref(),
+                Some("Pull complete") | Some("Already exists")
+            ) {
+                layer.2 = true;
+                layer.0 = layer.1;
+            }
+
+            on_progress(&ImagePullProgress {
+                image: image.to_string(),
+                layers_total: layers.len(),
+                layers_done: layers.values().filter(|l| l.2).count(),
+                bytes_done: layers.values().map(|l| l.0).sum(),
+                bytes_total: layers.values().map(|l| l.1).sum(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Ensures images are present locally and optionally pins them by digest
+#[derive(Clone)]
+pub struct ImageCache {
+    backend: Arc<dyn ImageBackend>,
+    pin_digests: bool,
+    /// Requested reference -> digest reference recorded on first pull
+    pinned: Arc<RwLock<HashMap<String, String>>>,
+    progress: broadcast::Sender<ImagePullProgress>,
+}
+
+impl fmt::Debug for ImageCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageCache")
+            .field("pin_digests", &self.pin_digests)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ImageCache {
+    pub fn new(backend: Arc<dyn ImageBackend>, pin_digests: bool) -> Self {
+        Self {
+            backend,
+            pin_digests,
+            pinned: Arc::new(RwLock::new(HashMap::new())),
+            progress: broadcast::channel(PROGRESS_CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Receive progress updates for every pull the cache makes
+    pub fn subscribe_progress(&self) -> broadcast::Receiver<ImagePullProgress> {
+        self.progress.subscribe()
+    }
+
+    /// Digest reference recorded for `image`, if pinned
+    pub async fn pinned_digest(&self, image: &str) -> Option<String> {
+        self.pinned.read().await.get(image).cloned()
+    }
+
+    /// Make `image` available locally, pulling only when it is missing.
+    ///
+    /// Returns the reference containers should be created from: the pinned
+    /// digest when pinning is enabled, otherwise `image` itself.
+    pub async fn ensure(&self, image: &str) -> Result<String> {
+        self.ensure_with_auth(image, None).await
+    }
+
+    /// Like [`ensure`](Self::ensure), authenticating any pull with `auth`
+    pub async fn ensure_with_auth(
+        &self,
+        image: &str,
+        auth: Option<&RegistryCredentials>,
+    ) -> Result<String> {
+        if let Some(auth) = auth {
+            auth.check_image(image)?;
+        }
+
+        if let Some(pinned) = self.pinned_digest(image).await {
+            if self.backend.local_digests(&pinned).await?.is_some() {
+                debug!("Using pinned image {} for {}", pinned, image);
+                return Ok(pinned);
+            }
+            info!("Pinned image {} for {} is gone, pulling", pinned, image);
+            self.pull(&pinned, auth).await?;
+            return Ok(pinned);
+        }
+
+        let digests = match self.backend.local_digests(image).await? {
+            Some(digests) => {
+                debug!("Image {} already available locally", image);
+                digests
+            }
+            None => {
+                info!("Image {} not found locally, pulling...", image);
+                self.pull(image, auth).await?;
+                self.backend.local_digests(image).await?.unwrap_or_default()
+            }
+        };
+
+        if !self.pin_digests {
+            return Ok(image.to_string());
+        }
+
+        match select_repo_digest(image, digests) {
+            Some(digest) => {
+                info!("Pinned {} to {}", image, digest);
+                self.pinned
+                    .write()
+                    .await
+                    .insert(image.to_string(), digest.clone());
+                Ok(digest)
+            }
+            None => {
+                // Locally built images have no repository digest to pin
+                debug!("Image {} has no repository digest, not pinning", image);
+                Ok(image.to_string())
+            }
+        }
+    }
+
+    /// Pull every image in `images`, logging failures instead of aborting
+    pub async fn prepull(&self, images: &[String]) {
+        for image in images {
+            match self.ensure(image).await {
+                Ok(reference) => info!("Pre-pulled image {} ({})", image, reference),
+                Err(e) => warn!("Failed to pre-pull image {}: {:#}", image, e),
+            }
+        }
+    }
+
+    async fn pull(&self, image: &str, auth: Option<&RegistryCredentials>) -> Result<()> {
+        let labels = vec![("image".to_string(), image.to_string())];
+        let report = |progress: &ImagePullProgress| {
+            metrics::gauge!("executor_image_pull_bytes_done", labels.as_slice())
+                .set(progress.bytes_done as f64);
+            metrics::gauge!("executor_image_pull_bytes_total", labels.as_slice())
+                .set(progress.bytes_total as f64);
+            debug!(
+                "Pulling {}: {}/{} layers, {}/{} bytes",
+                progress.image,
+                progress.layers_done,
+                progress.layers_total,
+                progress.bytes_done,
+                progress.bytes_total
+            );
+            // Nobody listening is fine; progress is best effort
+            let _ = self.progress.send(progress.clone());
+        };
+
+        if let Some(auth) = auth {
+            debug!("Pulling {} with credentials {:?}", image, auth);
+        }
+        let result = self.backend.pull(image, auth, &report).await;
+        let outcome = if result.is_ok() { "success" } else { "failure" };
+        metrics::counter!(
+            "executor_image_pulls_total",
+            "image" => image.to_string(),
+            "outcome" => outcome
+        )
+        .increment(1);
+
+        if result.is_ok() {
+            info!("Successfully pulled image: {}", image);
+        }
+        result
+    }
+}
@@ -1,9 +1,13 @@
 //! Container log streaming functionality
 
-use super::types::{ContainerLogEntry, LogLevel};
+use super::types::{ContainerLogEntry, ContainerLogLine, LogLevel};
 use anyhow::Result;
-use bollard::{container::LogsOptions, Docker};
-use futures_util::stream::StreamExt;
+use bollard::{
+    container::{LogOutput, LogsOptions},
+    Docker,
+};
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt};
 use tracing::info;
 
 #[derive(Debug, Clone)]
@@ -61,4 +65,185 @@ impl LogStreamer {
 
         Ok(stream)
     }
+    /// Stream a container's stdout and stderr as individual timestamped lines
+    ///
+    /// Docker only applies `since` at whole-second granularity, so lines are
+    /// filtered again against the exact timestamp Docker recorded for them.
+    pub fn stream_container_logs(
+        &self,
+        container_id: &str,
+        follow: bool,
+        tail_lines: Option<u32>,
+        since: Option<DateTime<Utc>>,
+    ) -> impl futures_util::Stream<Item = Result<ContainerLogLine>> {
+        info!(
+            "Streaming log lines for container: {} (follow: {}, tail: {:?}, since: {:?})",
+            container_id, follow, tail_lines, since
+        );
+
+        let logs_options = LogsOptions {
+            follow,
+            stdout: true,
+            stderr: true,
+            since: since.map(|t| t.timestamp()).unwrap_or(0),
+            tail: tail_lines
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| "all".to_string()),
+            timestamps: true,
+            ..Default::default()
+        };
+
+        self.docker
+            .logs(container_id, Some(logs_options))
+            .flat_map(|log_result| {
+                let lines: Vec<Result<ContainerLogLine>> = match log_result {
+                    Ok(output) => split_log_output(output).into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e.into())],
+                };
+                stream::iter(lines)
+            })
+            .filter(move |line| {
+                let keep = match (line, since) {
+                    (Ok(line), Some(since)) => line.timestamp >= since,
+                    _ => true,
+                };
+                futures_util::future::ready(keep)
+            })
+    }
+}
+
+/// Split one Docker log frame into lines
+fn split_log_output(output: LogOutput) -> Vec<ContainerLogLine> {
+    let (stream, message) = match output {
+        LogOutput::StdOut { message } | LogOutput::Console { message } => ("stdout", message),
+        LogOutput::StdErr { message } => ("stderr", message),
+        LogOutput::StdIn { .. } => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&message)
+        .lines()
+        .map(|line| parse_timestamped_line(stream, line))
+        .collect()
+}
+
+/// Parse a `<RFC 3339 timestamp> <message>` line produced with `timestamps: true`
+fn parse_timestamped_line(stream: &str, line: &str) -> ContainerLogLine {
+    let parsed = line.split_once(' ').and_then(|(timestamp, message)| {
+        DateTime::parse_from_rfc3339(timestamp)
+            .ok()
+            .map(|timestamp| (timestamp.with_timezone(&Utc), message))
+    });
+
+    match parsed {
+        Some((timestamp, message)) => ContainerLogLine {
+            timestamp,
+            stream: stream.to_string(),
+            message: message.to_string(),
+        },
+        None => ContainerLogLine {
+            timestamp: Utc::now(),
+            stream: stream.to_string(),
+            message: line.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::API_DEFAULT_VERSION;
+    use wiremock::matchers::{method, path_regex, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Encode `payload` as one frame of Docker's multiplexed log stream
+    fn frame(stream_type: u8, payload: &str) -> Vec<u8> {
+        let mut frame = vec![stream_type, 0, 0, 0];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload.as_bytes());
+        frame
+    }
+
+    fn docker_for(server: &MockServer) -> Docker {
+        Docker::connect_with_http(&server.uri(), 5, API_DEFAULT_VERSION).unwrap()
+    }
+
+    fn logs_response(frames: &[(u8, &str)]) -> ResponseTemplate {
+        let body: Vec<u8> = frames
+            .iter()
+            .flat_map(|(stream_type, payload)| frame(*stream_type, payload))
+            .collect();
+        ResponseTemplate::new(200).set_body_raw(body, "application/vnd.docker.raw-stream")
+    }
+
+    #[tokio::test]
+    async fn test_stream_container_logs_preserves_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path_regex(r"/containers/c1/logs$"))
+            .and(query_param("timestamps", "true"))
+            .respond_with(logs_response(&[
+                (1, "2024-05-01T10:00:00.100000000Z first\n"),
+                (2, "2024-05-01T10:00:00.200000000Z second\n"),
+                (1, "2024-05-01T10:00:00.300000000Z third\n"),
+            ]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let streamer = LogStreamer::new(docker_for(&server));
+        let lines: Vec<ContainerLogLine> = streamer
+            .stream_container_logs("c1", false, None, None)
+            .map(|line| line.unwrap())
+            .collect()
+            .await;
+
+        let messages: Vec<(&str, &str)> = lines
+            .iter()
+            .map(|line| (line.stream.as_str(), line.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                ("stdout", "first"),
+                ("stderr", "second"),
+                ("stdout", "third")
+            ]
+        );
+        assert!(lines.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+    }
+
+    #[tokio::test]
+    async fn test_stream_container_logs_filters_since_within_the_second() {
+        let server = MockServer::start().await;
+        let since: DateTime<Utc> = "2024-05-01T10:00:00.250Z".parse().unwrap();
+
+        // Docker only filters to the second, so it still returns the first line
+        Mock::given(method("GET"))
+            .and(path_regex(r"/containers/c1/logs$"))
+            .and(query_param("since", since.timestamp().to_string()))
+            .respond_with(logs_response(&[
+                (1, "2024-05-01T10:00:00.100000000Z before\n"),
+                (1, "2024-05-01T10:00:00.250000000Z at\n"),
+                (1, "2024-05-01T10:00:01.000000000Z after\n"),
+            ]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let streamer = LogStreamer::new(docker_for(&server));
+        let messages: Vec<String> = streamer
+            .stream_container_logs("c1", false, Some(10), Some(since))
+            .map(|line| line.unwrap().message)
+            .collect()
+            .await;
+
+        assert_eq!(messages, vec!["at", "after"]);
+    }
+
+    #[test]
+    fn test_unparseable_line_is_kept_whole() {
+        let line = parse_timestamped_line("stderr", "no timestamp here");
+        assert_eq!(line.message, "no timestamp here");
+        assert_eq!(line.stream, "stderr");
+    }
 }
@@ -4,29 +4,48 @@
 
 pub mod config_builder;
 pub mod health;
+pub mod images;
 pub mod logs;
 pub mod operations;
+pub mod registry_auth;
 pub mod types;
 
 use health::HealthChecker;
 use logs::LogStreamer;
 use operations::ContainerOperations;
+use registry_auth::RegistrySecretStore;
 pub use types::*;
 
 use crate::config::{ContainerResourceLimits, DockerConfig};
 use anyhow::Result;
+use basilica_protocol::common::RegistryAuth;
 use bollard::Docker;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::info;
 
+/// Label validators put on rental containers
+const RENTAL_ID_LABEL: &str = "basilica.rental_id";
+
+/// Label naming the validator that deployed a rental container
+const VALIDATOR_HOTKEY_LABEL: &str = "basilica.validator_hotkey";
+
+/// Whether container `labels` mark a rental deployed by `validator_hotkey`
+fn is_rental_of(labels: &HashMap<String, String>, validator_hotkey: &str) -> bool {
+    labels.contains_key(RENTAL_ID_LABEL)
+        && labels
+            .get(VALIDATOR_HOTKEY_LABEL)
+            .is_some_and(|owner| !owner.is_empty() && owner == validator_hotkey)
+}
+
 #[derive(Debug, Clone)]
 pub struct ContainerManager {
     active_containers: Arc<RwLock<HashMap<String, ContainerStatus>>>,
     operations: ContainerOperations,
     log_streamer: LogStreamer,
     health_checker: HealthChecker,
+    registry_secrets: RegistrySecretStore,
 }
 
 impl ContainerManager {
@@ -48,28 +67,48 @@ impl ContainerManager {
             version.version.unwrap_or_default()
         );
 
+        let registry_secrets = RegistrySecretStore::from_config(&config.registry)?;
         let active_containers = Arc::new(RwLock::new(HashMap::new()));
         let operations =
             ContainerOperations::new(docker.clone(), config.clone(), active_containers.clone());
         let log_streamer = LogStreamer::new(docker.clone());
         let health_checker = HealthChecker::new(docker.clone());
 
+        if !config.image_cache.prepull.is_empty() {
+            let images = operations.images().clone();
+            let prepull = config.image_cache.prepull.clone();
+            info!("Pre-pulling {} image(s) in the background", prepull.len());
+            tokio::spawn(async move { images.prepull(&prepull).await });
+        }
+
         Ok(Self {
             active_containers,
             operations,
             log_streamer,
             health_checker,
+            registry_secrets,
         })
     }
 
+    /// Image cache used for every pull this executor makes
+    pub fn images(&self) -> &images::ImageCache {
+        self.operations.images()
+    }
+
+    /// Create a container, authenticating the image pull with
+    /// `registry_auth` when the image lives in a private registry
     pub async fn create_container(
         &self,
         image: &str,
         command: &[String],
         resource_limits: Option<ContainerResourceLimits>,
+        registry_auth: Option<&RegistryAuth>,
     ) -> Result<String> {
+        let credentials = registry_auth
+            .map(|auth| self.registry_secrets.resolve(auth))
+            .transpose()?;
         self.operations
-            .create_container(image, command, resource_limits)
+            .create_container(image, command, resource_limits, credentials.as_ref())
             .await
     }
 
@@ -99,6 +138,17 @@ impl ContainerManager {
             .await
     }
 
+    pub fn stream_container_logs(
+        &self,
+        container_id: &str,
+        follow: bool,
+        tail_lines: Option<u32>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> impl futures_util::Stream<Item = Result<ContainerLogLine>> {
+        self.log_streamer
+            .stream_container_logs(container_id, follow, tail_lines, since)
+    }
+
     pub async fn get_container_status(
         &self,
         container_id: &str,
@@ -106,11 +156,32 @@ impl ContainerManager {
         self.operations.get_container_status(container_id).await
     }
 
+    /// Whether `container_id` is a rental container deployed by
+    /// `validator_hotkey`; unknown containers belong to no one
+    pub async fn is_rental_of(&self, container_id: &str, validator_hotkey: &str) -> Result<bool> {
+        Ok(self
+            .operations
+            .container_labels(container_id)
+            .await?
+            .is_some_and(|labels| is_rental_of(&labels, validator_hotkey)))
+    }
+
     pub async fn list_containers(&self) -> Result<Vec<ContainerStatus>> {
         let containers = self.active_containers.read().await;
         Ok(containers.values().cloned().collect())
     }
 
+    /// Running rental containers, which validators deploy over SSH with a
+    /// rental ID label rather than through this manager
+    pub async fn list_rental_containers(&self) -> Result<Vec<ContainerStatus>> {
+        let filters = HashMap::from([("label".to_string(), vec![RENTAL_ID_LABEL.to_string()])]);
+        self.operations.list_containers(false, Some(filters)).await
+    }
+
+    pub async fn stop_container(&self, container_id: &str) -> Result<()> {
+        self.operations.stop_container(container_id, None).await
+    }
+
     pub async fn cleanup_inactive_containers(&self) -> Result<()> {
         self.operations.cleanup_inactive_containers().await
     }
@@ -137,3 +208,36 @@ impl ContainerManager {
         self.health_checker.health_check().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_rental_belongs_to_deploying_validator() {
+        let rental = labels(&[
+            (RENTAL_ID_LABEL, "rental-1"),
+            (VALIDATOR_HOTKEY_LABEL, "validator-a"),
+        ]);
+        assert!(is_rental_of(&rental, "validator-a"));
+        assert!(!is_rental_of(&rental, "validator-b"));
+        assert!(!is_rental_of(&rental, ""));
+
+        // Containers without a rental ID or owner are not served
+        assert!(!is_rental_of(
+            &labels(&[(VALIDATOR_HOTKEY_LABEL, "validator-a")]),
+            "validator-a"
+        ));
+        assert!(!is_rental_of(
+            &labels(&[(RENTAL_ID_LABEL, "rental-1")]),
+            "validator-a"
+        ));
+    }
+}
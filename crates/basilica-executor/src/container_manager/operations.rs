@@ -1,6 +1,8 @@
 //! Container operations and lifecycle management
 
 use super::config_builder::ContainerConfigBuilder;
+use super::images::ImageCache;
+use super::registry_auth::RegistryCredentials;
 use super::types::{ContainerExecutionResult, ContainerResourceUsage, ContainerStatus};
 use crate::config::{ContainerResourceLimits, DockerConfig};
 use anyhow::Result;
@@ -11,7 +13,6 @@ use bollard::{
         WaitContainerOptions,
     },
     exec::{CreateExecOptions, StartExecResults},
-    image::CreateImageOptions,
     Docker,
 };
 use futures_util::stream::StreamExt;
@@ -27,6 +28,7 @@ pub struct ContainerOperations {
     active_containers: Arc<RwLock<HashMap<String, ContainerStatus>>>,
     config_builder: ContainerConfigBuilder,
     lifecycle: ContainerLifecycle,
+    images: ImageCache,
 }
 
 impl ContainerOperations {
@@ -37,6 +39,7 @@ impl ContainerOperations {
     ) -> Self {
         let config_builder = ContainerConfigBuilder::new(config.clone());
         let lifecycle = ContainerLifecycle::new(docker.clone(), active_containers.clone());
+        let images = ImageCache::new(Arc::new(docker.clone()), config.image_cache.pin_digests);
 
         Self {
             docker,
@@ -44,27 +47,34 @@ impl ContainerOperations {
             active_containers,
             config_builder,
             lifecycle,
+            images,
         }
     }
 
+    /// Image cache shared by every container this executor creates
+    pub fn images(&self) -> &ImageCache {
+        &self.images
+    }
+
     pub async fn create_container(
         &self,
         image: &str,
         command: &[String],
         resource_limits: Option<ContainerResourceLimits>,
+        registry_auth: Option<&RegistryCredentials>,
     ) -> Result<String> {
         info!(
             "Creating container with image: {} and command: {:?}",
             image, command
         );
 
-        self.ensure_image_available(image).await?;
+        let image_ref = self.ensure_image_available(image, registry_auth).await?;
 
         let uuid_str = uuid::Uuid::new_v4().to_string();
         let container_name = format!("basilca-{}", &uuid_str[..8]);
 
         let limits = resource_limits.unwrap_or_else(|| self.config.resource_limits.clone());
-        let container_config = self.config_builder.build(image, command, &limits)?;
+        let container_config = self.config_builder.build(&image_ref, command, &limits)?;
 
         let create_options = CreateContainerOptions {
             name: container_name.clone(),
@@ -345,6 +355,25 @@ impl ContainerOperations {
         }
     }
 
+    /// Labels of `container_id`, or `None` if Docker does not know it
+    pub async fn container_labels(
+        &self,
+        container_id: &str,
+    ) -> Result<Option<HashMap<String, String>>> {
+        match self.docker.inspect_container(container_id, None).await {
+            Ok(container) => Ok(Some(
+                container
+                    .config
+                    .and_then(|config| config.labels)
+                    .unwrap_or_default(),
+            )),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// List all containers with optional filtering
     pub async fn list_containers(
         &self,
@@ -629,47 +658,23 @@ impl ContainerOperations {
         Ok(())
     }
 
-    async fn ensure_image_available(&self, image: &str) -> Result<()> {
+    /// Check the image against policy, then make it available locally.
+    /// Returns the reference to create the container from.
+    async fn ensure_image_available(
+        &self,
+        image: &str,
+        registry_auth: Option<&RegistryCredentials>,
+    ) -> Result<String> {
         debug!("Ensuring image is available: {}", image);
 
-        // Validate image registry
+        // Validate image registry and policy before pulling anything
         self.validate_image_registry(image)?;
+        self.config
+            .image_policy
+            .check(image)
+            .map_err(|e| anyhow::anyhow!(e))?;
 
-        match self.docker.inspect_image(image).await {
-            Ok(_) => {
-                debug!("Image {} already available locally", image);
-                return Ok(());
-            }
-            Err(_) => {
-                info!("Image {} not found locally, pulling...", image);
-            }
-        }
-
-        let create_image_options = CreateImageOptions {
-            from_image: image,
-            ..Default::default()
-        };
-
-        let mut pull_stream = self
-            .docker
-            .create_image(Some(create_image_options), None, None);
-
-        while let Some(pull_result) = pull_stream.next().await {
-            match pull_result {
-                Ok(info) => {
-                    if let Some(status) = info.status {
-                        debug!("Pull status: {}", status);
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to pull image {}: {}", image, e);
-                    return Err(e.into());
-                }
-            }
-        }
-
-        info!("Successfully pulled image: {}", image);
-        Ok(())
+        self.images.ensure_with_auth(image, registry_auth).await
     }
 }
 
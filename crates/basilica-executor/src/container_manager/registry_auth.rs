@@ -0,0 +1,230 @@
+//! Private registry credentials for image pulls
+//!
+//! A container spec may carry credentials inline or name a secret stored in
+//! the executor config. Stored tokens are kept encrypted with the shared
+//! [`Aead`] and decrypted only when a pull needs them. Credentials are only
+//! ever sent to the registry the image lives in, and only over TLS.
+
+use crate::config::{ContainerRegistryConfig, StoredRegistrySecret};
+use anyhow::{anyhow, bail, Context, Result};
+use basilica_common::crypto::Aead;
+use basilica_common::utils::{image_registry_host, tls_registry_host};
+use basilica_protocol::common::RegistryAuth;
+use bollard::auth::DockerCredentials;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Registry credentials resolved for a single pull
+#[derive(Clone, PartialEq, Eq)]
+pub struct RegistryCredentials {
+    /// Registry host without scheme, e.g. `ghcr.io`
+    pub registry: String,
+    pub username: String,
+    token: String,
+}
+
+impl RegistryCredentials {
+    /// Build credentials for `registry`, rejecting registries that would not
+    /// be reached over TLS
+    pub fn new(registry: &str, username: &str, token: &str) -> Result<Self> {
+        let registry = tls_registry_host(registry)?;
+        if username.is_empty() || token.is_empty() {
+            bail!("Registry credentials for {registry} need both a username and a token");
+        }
+        Ok(Self {
+            registry,
+            username: username.to_string(),
+            token: token.to_string(),
+        })
+    }
+
+    /// Password or access token sent to the registry
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Check that `image` is hosted on this registry so the credentials are
+    /// never handed to a different one
+    pub fn check_image(&self, image: &str) -> Result<()> {
+        let host = image_registry_host(image);
+        if host != self.registry {
+            bail!(
+                "Registry credentials for {} cannot be used to pull {} from {}",
+                self.registry,
+                image,
+                host
+            );
+        }
+        Ok(())
+    }
+
+    /// Credentials in the form the Docker API expects
+    pub fn to_docker_credentials(&self) -> DockerCredentials {
+        DockerCredentials {
+            username: Some(self.username.clone()),
+            password: Some(self.token.clone()),
+            serveraddress: Some(self.registry.clone()),
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Debug for RegistryCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistryCredentials")
+            .field("registry", &self.registry)
+            .field("username", &self.username)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Resolves the registry auth of a container spec into credentials
+#[derive(Clone, Default)]
+pub struct RegistrySecretStore {
+    aead: Option<Arc<Aead>>,
+    secrets: HashMap<String, StoredRegistrySecret>,
+}
+
+impl fmt::Debug for RegistrySecretStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistrySecretStore")
+            .field("secrets", &self.secrets.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RegistrySecretStore {
+    pub fn new(aead: Option<Aead>, secrets: HashMap<String, StoredRegistrySecret>) -> Self {
+        Self {
+            aead: aead.map(Arc::new),
+            secrets,
+        }
+    }
+
+    /// Build the store from the registry config, failing on a malformed key
+    pub fn from_config(config: &ContainerRegistryConfig) -> Result<Self> {
+        let aead = config
+            .secrets_key_hex
+            .as_deref()
+            .map(Aead::new)
+            .transpose()
+            .context("Invalid registry secrets key")?;
+        if aead.is_none() && !config.secrets.is_empty() {
+            bail!("Registry secrets are configured but no secrets key is set");
+        }
+        Ok(Self::new(aead, config.secrets.clone()))
+    }
+
+    /// Resolve inline credentials or a stored secret reference
+    pub fn resolve(&self, auth: &RegistryAuth) -> Result<RegistryCredentials> {
+        if auth.secret_ref.is_empty() {
+            return RegistryCredentials::new(&auth.registry, &auth.username, &auth.token);
+        }
+        if !auth.token.is_empty() {
+            bail!(
+                "Registry auth must set either inline credentials or a secret reference, not both"
+            );
+        }
+
+        let secret = self
+            .secrets
+            .get(&auth.secret_ref)
+            .ok_or_else(|| anyhow!("Unknown registry secret '{}'", auth.secret_ref))?;
+        let aead = self
+            .aead
+            .as_ref()
+            .ok_or_else(|| anyhow!("No registry secrets key is configured"))?;
+        let token = aead
+            .decrypt(&secret.encrypted_token)
+            .with_context(|| format!("Failed to decrypt registry secret '{}'", auth.secret_ref))?;
+
+        let credentials = RegistryCredentials::new(&secret.registry, &secret.username, &token)?;
+        if !auth.registry.is_empty() && tls_registry_host(&auth.registry)? != credentials.registry {
+            bail!(
+                "Registry secret '{}' belongs to {}, not {}",
+                auth.secret_ref,
+                credentials.registry,
+                auth.registry
+            );
+        }
+        Ok(credentials)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn inline(registry: &str) -> RegistryAuth {
+        RegistryAuth {
+            registry: registry.to_string(),
+            username: "ci".to_string(),
+            token: "s3cr3t-token".to_string(),
+            secret_ref: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_plain_http_registry_is_rejected() {
+        let store = RegistrySecretStore::default();
+        let err = store
+            .resolve(&inline("http://registry.example.com"))
+            .unwrap_err();
+        assert!(err.to_string().contains("TLS"));
+
+        let creds = store
+            .resolve(&inline("https://registry.example.com/"))
+            .unwrap();
+        assert_eq!(creds.registry, "registry.example.com");
+    }
+
+    #[test]
+    fn test_credentials_only_apply_to_their_registry() {
+        let creds = RegistryCredentials::new("ghcr.io", "ci", "s3cr3t-token").unwrap();
+        assert!(creds.check_image("ghcr.io/acme/trainer:1.0").is_ok());
+        assert!(creds.check_image("nvidia/cuda:12.2").is_err());
+        assert!(creds.check_image("evil.example.com/acme/trainer").is_err());
+
+        let hub = RegistryCredentials::new("index.docker.io", "ci", "s3cr3t-token").unwrap();
+        assert!(hub.check_image("acme/private:latest").is_ok());
+    }
+
+    #[test]
+    fn test_stored_secret_is_decrypted() {
+        let aead = Aead::new(KEY).unwrap();
+        let secret = StoredRegistrySecret {
+            registry: "ghcr.io".to_string(),
+            username: "ci".to_string(),
+            encrypted_token: aead.encrypt("s3cr3t-token").unwrap(),
+        };
+        let store =
+            RegistrySecretStore::new(Some(aead), HashMap::from([("acme".to_string(), secret)]));
+
+        let auth = RegistryAuth {
+            secret_ref: "acme".to_string(),
+            ..Default::default()
+        };
+        let creds = store.resolve(&auth).unwrap();
+        assert_eq!(creds.token(), "s3cr3t-token");
+        assert_eq!(creds.registry, "ghcr.io");
+
+        let missing = RegistryAuth {
+            secret_ref: "other".to_string(),
+            ..Default::default()
+        };
+        assert!(store.resolve(&missing).is_err());
+    }
+
+    #[test]
+    fn test_debug_output_redacts_token() {
+        let auth = inline("ghcr.io");
+        let creds = RegistrySecretStore::default().resolve(&auth).unwrap();
+
+        assert!(!format!("{auth:?}").contains("s3cr3t-token"));
+        assert!(!format!("{creds:?}").contains("s3cr3t-token"));
+    }
+}
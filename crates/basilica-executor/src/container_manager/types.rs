@@ -20,6 +20,15 @@ pub struct ContainerLogEntry {
     pub container_id: String,
 }
 
+/// A single line of container output, as recorded by Docker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerLogLine {
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Output stream the line was written to (`stdout` or `stderr`)
+    pub stream: String,
+    pub message: String,
+}
+
 /// Log level enumeration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LogLevel {
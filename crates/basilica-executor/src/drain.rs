@@ -0,0 +1,213 @@
+//! Maintenance drain
+//!
+//! Draining takes an executor out of rotation without cutting off renters.
+//! A draining executor reports itself as such to its miner, so it is no
+//! longer offered to validators, and refuses new rentals. Rentals already
+//! running continue until they finish or the drain deadline passes, at which
+//! point the rental containers still running are stopped.
+
+use crate::container_manager::ContainerManager;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tracing::{info, warn};
+
+/// Error returned for new rentals while the executor is draining
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("ExecutorDraining: executor is draining for maintenance and not accepting new rentals")]
+pub struct ExecutorDraining;
+
+/// A drain in progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Drain {
+    pub started_at: SystemTime,
+    /// When rentals still running are stopped, if the drain has a deadline
+    pub deadline: Option<SystemTime>,
+}
+
+impl Drain {
+    /// Estimated time until the drain completes with `active_rentals` still
+    /// running: immediately once they are gone, otherwise at the deadline
+    pub fn estimated_remaining(&self, active_rentals: usize, now: SystemTime) -> Option<Duration> {
+        if active_rentals == 0 {
+            return Some(Duration::ZERO);
+        }
+        self.deadline
+            .map(|deadline| deadline.duration_since(now).unwrap_or(Duration::ZERO))
+    }
+}
+
+/// Drain progress reported by health checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrainStatus {
+    pub draining: bool,
+    pub active_rentals: u32,
+    /// Seconds until the drain completes, when known
+    pub estimated_drain_seconds: Option<u64>,
+}
+
+/// Whether the executor is draining, shared by its gRPC services
+#[derive(Debug, Default)]
+pub struct DrainState {
+    drain: RwLock<Option<Drain>>,
+}
+
+impl DrainState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start draining, stopping rentals still running after `deadline`
+    ///
+    /// Starting a drain while one is in progress replaces its deadline.
+    pub fn start(&self, deadline: Option<Duration>) -> Drain {
+        let started_at = SystemTime::now();
+        let drain = Drain {
+            started_at,
+            deadline: deadline.map(|deadline| started_at + deadline),
+        };
+        *self.drain.write().unwrap() = Some(drain);
+        drain
+    }
+
+    /// Stop draining, returning whether a drain was in progress
+    pub fn cancel(&self) -> bool {
+        self.drain.write().unwrap().take().is_some()
+    }
+
+    pub fn current(&self) -> Option<Drain> {
+        *self.drain.read().unwrap()
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.current().is_some()
+    }
+
+    /// Fail with [`ExecutorDraining`] when new rentals must be refused
+    pub fn check_accepting(&self) -> Result<(), ExecutorDraining> {
+        match self.current() {
+            Some(_) => Err(ExecutorDraining),
+            None => Ok(()),
+        }
+    }
+
+    /// Check whether a container operation may proceed
+    ///
+    /// Only creating containers is refused while draining; existing rentals
+    /// can still be inspected, given keys and torn down.
+    pub fn check_container_operation(&self, operation: &str) -> Result<(), ExecutorDraining> {
+        match operation {
+            "create" => self.check_accepting(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Drain progress with `active_rentals` still running
+    pub fn status(&self, active_rentals: usize) -> DrainStatus {
+        let drain = self.current();
+        DrainStatus {
+            draining: drain.is_some(),
+            active_rentals: active_rentals as u32,
+            estimated_drain_seconds: drain
+                .and_then(|drain| drain.estimated_remaining(active_rentals, SystemTime::now()))
+                .map(|remaining| remaining.as_secs()),
+        }
+    }
+}
+
+/// Stop the rental containers still running when `drain` reaches its deadline
+///
+/// Does nothing if the drain is cancelled or replaced before then.
+pub async fn enforce_deadline(state: Arc<DrainState>, containers: ContainerManager, drain: Drain) {
+    let Some(deadline) = drain.deadline else {
+        return;
+    };
+    if let Ok(wait) = deadline.duration_since(SystemTime::now()) {
+        tokio::time::sleep(wait).await;
+    }
+    if state.current() != Some(drain) {
+        return;
+    }
+
+    let rentals = match containers.list_rental_containers().await {
+        Ok(rentals) => rentals,
+        Err(e) => {
+            warn!("Failed to list rental containers at drain deadline: {}", e);
+            return;
+        }
+    };
+    info!(
+        "Drain deadline reached, stopping {} rental container(s)",
+        rentals.len()
+    );
+    for rental in rentals {
+        if let Err(e) = containers.stop_container(&rental.id).await {
+            warn!("Failed to stop rental container {}: {}", rental.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_rentals_refused_while_draining() {
+        let state = DrainState::new();
+        assert_eq!(state.check_accepting(), Ok(()));
+        assert_eq!(state.check_container_operation("create"), Ok(()));
+
+        state.start(None);
+        assert_eq!(state.check_accepting(), Err(ExecutorDraining));
+        assert_eq!(
+            state.check_container_operation("create"),
+            Err(ExecutorDraining)
+        );
+
+        assert!(state.cancel());
+        assert_eq!(state.check_accepting(), Ok(()));
+        assert!(!state.cancel());
+    }
+
+    #[test]
+    fn test_existing_rentals_allowed_while_draining() {
+        let state = DrainState::new();
+        state.start(Some(Duration::from_secs(3600)));
+
+        for operation in ["get_status", "add_key", "delete", "destroy"] {
+            assert_eq!(state.check_container_operation(operation), Ok(()));
+        }
+
+        // Running rentals keep the drain open until the deadline
+        let status = state.status(2);
+        assert!(status.draining);
+        assert_eq!(status.active_rentals, 2);
+        let remaining = status.estimated_drain_seconds.unwrap();
+        assert!(remaining > 3590 && remaining <= 3600);
+    }
+
+    #[test]
+    fn test_estimated_drain_time() {
+        let now = SystemTime::now();
+        let open_ended = Drain {
+            started_at: now,
+            deadline: None,
+        };
+        assert_eq!(open_ended.estimated_remaining(1, now), None);
+        assert_eq!(open_ended.estimated_remaining(0, now), Some(Duration::ZERO));
+
+        let overdue = Drain {
+            started_at: now - Duration::from_secs(120),
+            deadline: Some(now - Duration::from_secs(60)),
+        };
+        assert_eq!(overdue.estimated_remaining(1, now), Some(Duration::ZERO));
+
+        assert_eq!(
+            DrainState::new().status(3),
+            DrainStatus {
+                draining: false,
+                active_rentals: 3,
+                estimated_drain_seconds: None,
+            }
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Container operations service
 
 use super::types::{GrpcResult, SharedExecutorState};
+use basilica_protocol::common::RegistryAuth;
 use tracing::info;
 
 /// Container operations handler
@@ -15,13 +16,18 @@ impl ContainerOperationsService {
     }
 
     /// Create container
-    pub async fn create_container(&self, image: &str, command: &[String]) -> GrpcResult<String> {
+    pub async fn create_container(
+        &self,
+        image: &str,
+        command: &[String],
+        registry_auth: Option<&RegistryAuth>,
+    ) -> GrpcResult<String> {
         info!("Creating container with image: {}", image);
 
         let state = self.state.clone();
         let container_id = state
             .container_manager
-            .create_container(image, command, None)
+            .create_container(image, command, None, registry_auth)
             .await
             .map_err(|e| tonic::Status::internal(format!("Failed to create container: {e}")))?;
 
@@ -218,7 +224,7 @@ impl ContainerOperationsService {
                     },
                     "system_context": {
                         "host_cpu_cores": system_info.cpu.cores,
-                        "host_memory_total_gb": system_info.memory.total_bytes / (1024 * 1024 * 1024),
+                        "host_memory_total_gb": system_info.memory.host_total_bytes / (1024 * 1024 * 1024),
                         "container_cpu_share": resource_stats.cpu_usage_percent / 100.0 * system_info.cpu.cores as f64,
                         "container_memory_share": (resource_stats.memory_usage_bytes as f64 /
                             system_info.memory.total_bytes as f64) * 100.0
@@ -2,11 +2,13 @@
 
 use super::types::SharedExecutorState;
 use basilica_protocol::executor_management::{
-    executor_management_server::ExecutorManagement, HealthCheckRequest, HealthCheckResponse,
-    SshKeyUpdate, SshKeyUpdateResponse, StatusRequest, StatusResponse,
+    executor_management_server::ExecutorManagement, DrainModeRequest, DrainModeResponse,
+    HealthCheckRequest, HealthCheckResponse, SshKeyUpdate, SshKeyUpdateResponse, StatusRequest,
+    StatusResponse,
 };
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::time::Duration;
 use tonic::{Request, Response, Status};
 use tracing::info;
 
@@ -90,13 +92,23 @@ impl ExecutorManagement for ExecutorManagementService {
             .active_challenges
             .load(std::sync::atomic::Ordering::Relaxed);
 
+        let drain = self.state.drain_status().await;
+
         Ok(Response::new(HealthCheckResponse {
-            status: "healthy".to_string(),
+            status: if drain.draining {
+                "draining"
+            } else {
+                "healthy"
+            }
+            .to_string(),
             resource_status,
             docker_status,
             uptime_seconds: system_info.system.uptime_seconds,
             container_count,
             active_challenges,
+            draining: drain.draining,
+            active_rentals: drain.active_rentals,
+            estimated_drain_seconds: drain.estimated_drain_seconds,
         }))
     }
 
@@ -150,7 +162,7 @@ impl ExecutorManagement for ExecutorManagementService {
                 .map(|g| basilica_protocol::common::GpuSpec {
                     model: g.name.clone(),
                     memory_mb: g.memory_total_bytes / (1024 * 1024),
-                    uuid: String::new(), // Not tracked in system monitor
+                    uuid: g.uuid.clone(),
                     driver_version: g.driver_version.clone(),
                     cuda_version: g.cuda_version.clone().unwrap_or_default(),
                     utilization_percent: g.utilization_percent as f64,
@@ -160,6 +172,8 @@ impl ExecutorManagement for ExecutorManagementService {
                     core_clock_mhz: 0,
                     memory_clock_mhz: 0,
                     compute_capability: String::new(),
+                    pci_bus_id: g.pci_bus_id.clone(),
+                    numa_node: g.numa_node,
                 })
                 .collect(),
             cpu: Some(basilica_protocol::common::CpuSpec {
@@ -224,9 +238,15 @@ impl ExecutorManagement for ExecutorManagementService {
             hostname: system_info.system.hostname.clone(),
         };
 
+        let status = if self.state.drain.is_draining() {
+            "draining"
+        } else {
+            "operational"
+        };
+
         Ok(Response::new(StatusResponse {
             executor_id: self.state.id.to_string(),
-            status: "operational".to_string(),
+            status: status.to_string(),
             machine_info: Some(machine_info),
             resource_usage: None, // Could be populated if needed
             total_containers: containers.len() as u32,
@@ -235,6 +255,42 @@ impl ExecutorManagement for ExecutorManagementService {
             uptime_seconds: system_info.system.uptime_seconds,
         }))
     }
+
+    async fn set_drain_mode(
+        &self,
+        request: Request<DrainModeRequest>,
+    ) -> Result<Response<DrainModeResponse>, Status> {
+        let req = request.into_inner();
+
+        // Verify miner is our configured miner
+        if req.miner_hotkey != self.state.config.managing_miner_hotkey.to_string() {
+            return Err(Status::permission_denied("Not authorized miner"));
+        }
+
+        if req.enabled {
+            let deadline =
+                (req.deadline_seconds > 0).then(|| Duration::from_secs(req.deadline_seconds));
+            let drain = self.state.drain.start(deadline);
+            info!(
+                "Executor draining for maintenance (deadline: {:?})",
+                deadline
+            );
+            tokio::spawn(crate::drain::enforce_deadline(
+                self.state.drain.clone(),
+                self.state.container_manager.clone(),
+                drain,
+            ));
+        } else if self.state.drain.cancel() {
+            info!("Executor drain cancelled, accepting rentals again");
+        }
+
+        let drain = self.state.drain_status().await;
+        Ok(Response::new(DrainModeResponse {
+            draining: drain.draining,
+            active_rentals: drain.active_rentals,
+            estimated_drain_seconds: drain.estimated_drain_seconds,
+        }))
+    }
 }
 
 impl ExecutorManagementService {
@@ -30,9 +30,10 @@ use validator_access::ValidatorAccessService;
 use basilica_protocol::common::LogEntry;
 use basilica_protocol::executor_control::{
     executor_control_server::{ExecutorControl, ExecutorControlServer},
-    BenchmarkRequest, BenchmarkResponse, ContainerOpRequest, ContainerOpResponse,
-    HealthCheckRequest, HealthCheckResponse, LogSubscriptionRequest, ProvisionAccessRequest,
-    ProvisionAccessResponse, SystemProfileRequest, SystemProfileResponse,
+    BenchmarkRequest, BenchmarkResponse, ContainerLogLine, ContainerLogsRequest,
+    ContainerOpRequest, ContainerOpResponse, HealthCheckRequest, HealthCheckResponse,
+    LogSubscriptionRequest, ProvisionAccessRequest, ProvisionAccessResponse, SystemProfileRequest,
+    SystemProfileResponse,
 };
 use tokio_stream::wrappers::ReceiverStream;
 
@@ -200,6 +201,14 @@ impl ExecutorControl for ExecutorControlService {
             .and_then(|v| v.get("rental_mode").and_then(|rm| rm.as_bool()))
             .unwrap_or(false);
 
+        // New rentals are refused while the executor is draining
+        if is_rental {
+            self.state
+                .drain
+                .check_accepting()
+                .map_err(|e| tonic::Status::failed_precondition(e.to_string()))?;
+        }
+
         // We need this for logging for better support.
         if is_rental {
             tracing::info!(
@@ -302,7 +311,8 @@ impl ExecutorControl for ExecutorControlService {
                     "utilization_percent": gpu.utilization_percent,
                     "temperature_celsius": gpu.temperature_celsius,
                     "driver_version": gpu.driver_version,
-                    "cuda_version": gpu.cuda_version
+                    "cuda_version": gpu.cuda_version,
+                    "mig_profile": gpu.mig.as_ref().map(|mig| mig.profile.to_string())
                 })
             }).collect::<Vec<_>>(),
             "disk": system_info.disk.iter().map(|disk| {
@@ -510,13 +520,18 @@ impl ExecutorControl for ExecutorControlService {
 
         info!("Container operation requested: {}", req.operation);
 
+        self.state
+            .drain
+            .check_container_operation(&req.operation)
+            .map_err(|e| tonic::Status::failed_precondition(e.to_string()))?;
+
         let container_ops = ContainerOperationsService::new(self.state.clone());
 
         match req.operation.as_str() {
             "create" => {
                 if let Some(spec) = req.container_spec {
                     let container_id = container_ops
-                        .create_container(&spec.image, &spec.command)
+                        .create_container(&spec.image, &spec.command, spec.registry_auth.as_ref())
                         .await
                         .map_err(|e| {
                             tonic::Status::internal(format!("Failed to create container: {e}"))
@@ -729,6 +744,96 @@ impl ExecutorControl for ExecutorControlService {
         Ok(tonic::Response::new(ReceiverStream::new(rx)))
     }
 
+    type StreamContainerLogsStream =
+        tokio_stream::wrappers::ReceiverStream<Result<ContainerLogLine, tonic::Status>>;
+
+    async fn stream_container_logs(
+        &self,
+        request: tonic::Request<ContainerLogsRequest>,
+    ) -> Result<tonic::Response<Self::StreamContainerLogsStream>, tonic::Status> {
+        use chrono::TimeZone;
+        use futures_util::StreamExt;
+
+        let req = request.into_inner();
+
+        // Verify miner or validator authentication first
+        crate::miner_auth::verify_logs_request(&self.state.miner_auth_service, &req).await?;
+
+        info!(
+            "Container log streaming requested for container: {} (follow: {}, tail: {})",
+            req.container_id, req.follow, req.tail_lines
+        );
+
+        if req.container_id.is_empty() {
+            return Err(tonic::Status::invalid_argument("container_id is required"));
+        }
+        if req.validator_hotkey.is_empty() {
+            return Err(tonic::Status::invalid_argument(
+                "validator_hotkey is required",
+            ));
+        }
+
+        // Validators may only read the logs of rentals they deployed
+        let owned = self
+            .state
+            .container_manager
+            .is_rental_of(&req.container_id, &req.validator_hotkey)
+            .await
+            .map_err(|e| tonic::Status::internal(format!("Failed to inspect container: {e}")))?;
+        if !owned {
+            return Err(tonic::Status::permission_denied(format!(
+                "Container {} is not a rental of validator {}",
+                req.container_id, req.validator_hotkey
+            )));
+        }
+
+        let since = match req.since.and_then(|since| since.value) {
+            Some(ts) => Some(
+                chrono::Utc
+                    .timestamp_opt(ts.seconds, ts.nanos.max(0) as u32)
+                    .single()
+                    .ok_or_else(|| tonic::Status::invalid_argument("Invalid since timestamp"))?,
+            ),
+            None => None,
+        };
+        let tail_lines = (req.tail_lines > 0).then_some(req.tail_lines);
+
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+        let container_manager = self.state.container_manager.clone();
+
+        tokio::spawn(async move {
+            let log_stream = container_manager.stream_container_logs(
+                &req.container_id,
+                req.follow,
+                tail_lines,
+                since,
+            );
+            futures_util::pin_mut!(log_stream);
+
+            while let Some(line) = log_stream.next().await {
+                let item = line
+                    .map(|line| ContainerLogLine {
+                        timestamp: Some(basilica_protocol::common::Timestamp {
+                            value: Some(prost_types::Timestamp {
+                                seconds: line.timestamp.timestamp(),
+                                nanos: line.timestamp.timestamp_subsec_nanos() as i32,
+                            }),
+                        }),
+                        stream: line.stream,
+                        message: line.message,
+                    })
+                    .map_err(|e| tonic::Status::internal(format!("Failed to stream logs: {e}")));
+                let failed = item.is_err();
+
+                if tx.send(item).await.is_err() || failed {
+                    break; // Client disconnected or Docker stream failed
+                }
+            }
+        });
+
+        Ok(tonic::Response::new(ReceiverStream::new(rx)))
+    }
+
     async fn health_check(
         &self,
         request: tonic::Request<HealthCheckRequest>,
@@ -758,15 +863,34 @@ impl ExecutorControl for ExecutorControlService {
                     health_status.details.disk_usage_percent.to_string(),
                 );
 
+                let drain = self.state.drain_status().await;
+                let mut metrics = std::collections::HashMap::new();
+                metrics.insert("draining".to_string(), drain.draining.to_string());
+                metrics.insert(
+                    "active_rentals".to_string(),
+                    drain.active_rentals.to_string(),
+                );
+                if let Some(seconds) = drain.estimated_drain_seconds {
+                    metrics.insert("estimated_drain_seconds".to_string(), seconds.to_string());
+                }
+
+                // A draining executor is still serving its rentals, but
+                // must not be offered for new ones
+                let status = if drain.draining && health_status.status == "healthy" {
+                    "draining".to_string()
+                } else {
+                    health_status.status
+                };
+
                 Ok(tonic::Response::new(HealthCheckResponse {
-                    status: health_status.status,
+                    status,
                     resource_status,
                     docker_status: "running".to_string(),
                     uptime_seconds: health_status.details.uptime_seconds,
                     last_update: Some(basilica_protocol::common::Timestamp {
                         value: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
                     }),
-                    metrics: std::collections::HashMap::new(),
+                    metrics,
                 }))
             }
             Err(e) => Err(tonic::Status::internal(e.to_string())),
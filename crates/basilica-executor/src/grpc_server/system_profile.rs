@@ -40,7 +40,8 @@ impl SystemProfileService {
                 },
                 "docker": {
                     "version": system_profile.docker.version
-                }
+                },
+                "gpus": system_profile.gpus
             },
             "current_state": {
                 "cpu": {
@@ -103,6 +104,8 @@ impl SystemProfileService {
                 "used_bytes": system_info.memory.used_bytes,
                 "total_bytes": system_info.memory.total_bytes,
                 "available_bytes": system_info.memory.available_bytes,
+                "host_total_bytes": system_info.memory.host_total_bytes,
+                "effective_limit_bytes": system_info.memory.effective_limit_bytes,
                 "used_mb": system_info.memory.used_bytes / (1024 * 1024),
                 "total_mb": system_info.memory.total_bytes / (1024 * 1024)
             },
@@ -5,21 +5,24 @@
 pub mod cli;
 pub mod config;
 pub mod container_manager;
+pub mod drain;
 pub mod grpc_server;
 pub mod journal;
 pub mod metrics_recorder;
 pub mod miner_auth;
+pub mod miner_registration;
 pub mod system_monitor;
 pub mod validation_session;
 
 pub use config::ExecutorConfig;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use basilica_common::identity::ExecutorId;
+use drain::DrainState;
 use miner_auth::{MinerAuthConfig, MinerAuthService};
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 use validation_session::ValidationSessionService;
 
 pub struct ExecutorState {
@@ -31,6 +34,7 @@ pub struct ExecutorState {
     pub validation_session: Arc<validation_session::ValidationSessionService>,
     pub miner_auth_service: Arc<MinerAuthService>,
     pub active_challenges: Arc<AtomicU32>,
+    pub drain: Arc<DrainState>,
 }
 
 impl ExecutorState {
@@ -39,6 +43,12 @@ impl ExecutorState {
         info!("Initializing executor with ID: {}", id);
 
         let system_monitor = Arc::new(system_monitor::SystemMonitor::new(config.system.clone())?);
+        if config.system.enable_gpu_monitoring && config.system.require_gpus {
+            system_monitor
+                .get_system_info()
+                .await
+                .context("GPUs are required by system.require_gpus but could not be detected")?;
+        }
 
         let container_manager =
             container_manager::ContainerManager::new(config.docker.clone()).await?;
@@ -64,6 +74,7 @@ impl ExecutorState {
             validation_session,
             miner_auth_service,
             active_challenges: Arc::new(AtomicU32::new(0)),
+            drain: Arc::new(DrainState::new()),
         })
     }
 
@@ -87,4 +98,16 @@ impl ExecutorState {
         info!("All executor components healthy");
         Ok(())
     }
+
+    /// Drain progress, counting the rentals still running
+    pub async fn drain_status(&self) -> drain::DrainStatus {
+        let active_rentals = match self.container_manager.list_rental_containers().await {
+            Ok(rentals) => rentals.len(),
+            Err(e) => {
+                warn!("Failed to count rental containers: {}", e);
+                0
+            }
+        };
+        self.drain.status(active_rentals)
+    }
 }
@@ -8,12 +8,16 @@ use anyhow::Result;
 use std::net::SocketAddr;
 use std::path::Path;
 use tokio::signal;
-use tracing::{error, info};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
 
 use basilica_executor::cli::{
     execute_command, AppConfig, AppConfigResolver, CliContext, ExecutorArgs,
 };
+use basilica_executor::config::reload::ConfigReloader;
 use basilica_executor::grpc_server::ExecutorServer;
+use basilica_executor::miner_registration::{MinerRegistrar, RegistrationDetails};
+use basilica_executor::system_monitor::SystemProfile;
 use basilica_executor::{ExecutorConfig, ExecutorState};
 
 #[tokio::main]
@@ -68,12 +72,14 @@ async fn run_server_mode(config: basilica_executor::cli::args::ServerConfig) ->
     let metrics_recorder = if config.metrics_enabled {
         init_metrics(config.metrics_addr).await?;
         info!("Metrics server started on: {}", config.metrics_addr);
-        Some(std::sync::Arc::new(
-            basilica_executor::metrics_recorder::PrometheusMetricsRecorder::new(),
+        Some(
+            basilica_executor::metrics_recorder::StaticLabelsRecorder::wrap(
+                std::sync::Arc::new(
+                    basilica_executor::metrics_recorder::PrometheusMetricsRecorder::new(),
+                ),
+                &executor_config.static_labels,
+            ),
         )
-            as std::sync::Arc<
-                dyn basilica_common::metrics::traits::MetricsRecorder,
-            >)
     } else {
         None
     };
@@ -86,6 +92,7 @@ async fn run_server_mode(config: basilica_executor::cli::args::ServerConfig) ->
     }
 
     // Start telemetry collection if configured
+    let mut telemetry_updates = None;
     if let Some(telemetry_config) = state.config.system.telemetry.clone() {
         if state.config.system.telemetry_monitor.enabled {
             info!("Starting telemetry collection");
@@ -103,13 +110,15 @@ async fn run_server_mode(config: basilica_executor::cli::args::ServerConfig) ->
             let monitor_cfg = state.config.system.telemetry_monitor.clone();
 
             // Start monitoring (spawns tasks internally)
-            basilica_executor::system_monitor::spawn_monitoring(
+            telemetry_updates = Some(basilica_executor::system_monitor::spawn_monitoring(
                 executor_id,
                 docker_host,
                 monitor_cfg,
                 telemetry_config,
                 metrics_recorder.clone(),
-            );
+                state.config.static_labels.clone(),
+                Some(state.container_manager.images().subscribe_progress()),
+            ));
         }
     }
 
@@ -137,9 +146,34 @@ async fn run_server_mode(config: basilica_executor::cli::args::ServerConfig) ->
         return Err(anyhow::anyhow!("Configuration validation failed: {}", e));
     }
 
-    // In SPEC v1.6, executors are statically configured on the miner side
-    // Register with miner for discovery using advertised endpoints
-    register_with_miner(&state.config).await?;
+    // Register with miner for discovery using advertised endpoints. Runs in
+    // the background so the gRPC server is reachable while retrying.
+    let executor_id = state
+        .config
+        .executor_id
+        .clone()
+        .unwrap_or_else(|| state.id.to_string());
+    // GPU topology is fixed for the life of the process, so profile it once
+    let system_profile = match state.system_monitor.get_system_profile().await {
+        Ok(profile) => Some(profile),
+        Err(e) => {
+            warn!("Failed to profile system for miner registration: {}", e);
+            None
+        }
+    };
+    let registration =
+        register_with_miner(executor_id.clone(), &state.config, system_profile.as_ref())?;
+
+    // Reload the hot-reloadable subset of the config on SIGHUP
+    let mut reloader = ConfigReloader::new(
+        config.config_path.clone(),
+        state.config.clone(),
+        state.system_monitor.clone(),
+    );
+    if let Some(tx) = telemetry_updates {
+        reloader = reloader.with_telemetry_updates(tx);
+    }
+    spawn_config_reload(reloader, executor_id, system_profile, registration);
 
     let server = ExecutorServer::new(state);
 
@@ -161,28 +195,89 @@ async fn run_server_mode(config: basilica_executor::cli::args::ServerConfig) ->
     Ok(())
 }
 
-/// Register executor's advertised endpoint with miner
-async fn register_with_miner(config: &ExecutorConfig) -> Result<()> {
-    let advertised_endpoint = config.get_advertised_grpc_endpoint();
+/// Register executor's advertised endpoints with the managing miner and
+/// keep the registration alive with heartbeats
+fn register_with_miner(
+    executor_id: String,
+    config: &ExecutorConfig,
+    system_profile: Option<&SystemProfile>,
+) -> Result<Option<JoinHandle<()>>> {
+    let Some(miner_endpoint) = config.miner_registration.endpoint.as_deref() else {
+        warn!("miner_registration.endpoint not set; skipping registration with miner");
+        return Ok(None);
+    };
 
     info!(
-        "Registering executor advertised endpoint with miner: {}",
-        advertised_endpoint
+        "Registering executor {} with miner at {}",
+        executor_id, miner_endpoint
     );
-
-    // Implementation would depend on miner-executor communication protocol
-    // This could involve:
-    // 1. gRPC call to miner's registration endpoint
-    // 2. Configuration file update
-    // 3. Service discovery registration
-
-    // For now, just log the endpoints that would be registered
-    info!("Advertised endpoints registered:");
     info!("  gRPC: {}", config.get_advertised_grpc_endpoint());
     info!("  SSH: {}", config.get_advertised_ssh_endpoint());
     info!("  Health: {}", config.get_advertised_health_endpoint());
 
-    Ok(())
+    let mut details = RegistrationDetails::from_config(executor_id, config);
+    if let Some(profile) = system_profile {
+        details = details.with_gpus_from_profile(profile);
+    }
+    let handle = MinerRegistrar::new(config.miner_registration.clone(), details)?.spawn();
+
+    Ok(Some(handle))
+}
+
+/// Listen for SIGHUP and reload the configuration file. Running containers
+/// are untouched; registration is restarted when advertised endpoints change.
+fn spawn_config_reload(
+    mut reloader: ConfigReloader,
+    executor_id: String,
+    system_profile: Option<SystemProfile>,
+    mut registration: Option<JoinHandle<()>>,
+) {
+    tokio::spawn(async move {
+        let mut hangup = match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                warn!(
+                    "Failed to install SIGHUP handler, config reload disabled: {}",
+                    e
+                );
+                return;
+            }
+        };
+
+        while hangup.recv().await.is_some() {
+            info!("Received SIGHUP, reloading configuration");
+
+            let report = match reloader.reload() {
+                Ok(report) => report,
+                Err(e) => {
+                    error!(
+                        "Config reload failed, keeping current configuration: {:#}",
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            if report.applied.is_empty() && report.requires_restart.is_empty() {
+                info!("Configuration unchanged");
+            }
+
+            if report.registration_changed() {
+                if let Some(handle) = registration.take() {
+                    handle.abort();
+                }
+                registration = register_with_miner(
+                    executor_id.clone(),
+                    reloader.current(),
+                    system_profile.as_ref(),
+                )
+                .unwrap_or_else(|e| {
+                    error!("Failed to restart miner registration: {:#}", e);
+                    None
+                });
+            }
+        }
+    });
 }
 
 async fn run_cli_mode(config: basilica_executor::cli::args::CliConfig) -> Result<()> {
@@ -1,5 +1,7 @@
 use async_trait::async_trait;
 use basilica_common::metrics::traits::{MetricTimer, MetricsRecorder};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Implementation of MetricsRecorder that uses the metrics crate
 pub struct PrometheusMetricsRecorder;
@@ -61,3 +63,144 @@ impl MetricsRecorder for PrometheusMetricsRecorder {
         MetricTimer::new(name.to_string(), labels)
     }
 }
+
+/// Recorder adding the executor's static labels to every metric
+///
+/// Labels passed with a metric take precedence over a static label of the
+/// same name.
+pub struct StaticLabelsRecorder {
+    inner: Arc<dyn MetricsRecorder>,
+    labels: Vec<(String, String)>,
+}
+
+impl StaticLabelsRecorder {
+    /// Wrap `inner`, or return it unchanged when there are no labels to add
+    pub fn wrap(
+        inner: Arc<dyn MetricsRecorder>,
+        static_labels: &HashMap<String, String>,
+    ) -> Arc<dyn MetricsRecorder> {
+        if static_labels.is_empty() {
+            return inner;
+        }
+        let mut labels: Vec<(String, String)> = static_labels
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        labels.sort();
+        Arc::new(Self { inner, labels })
+    }
+
+    fn merged<'a>(&'a self, labels: &[(&'a str, &'a str)]) -> Vec<(&'a str, &'a str)> {
+        let mut merged = labels.to_vec();
+        for (name, value) in &self.labels {
+            if !labels.iter().any(|(existing, _)| existing == name) {
+                merged.push((name.as_str(), value.as_str()));
+            }
+        }
+        merged
+    }
+}
+
+#[async_trait]
+impl MetricsRecorder for StaticLabelsRecorder {
+    async fn record_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+        self.inner
+            .record_counter(name, value, &self.merged(labels))
+            .await;
+    }
+
+    async fn record_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.inner
+            .record_gauge(name, value, &self.merged(labels))
+            .await;
+    }
+
+    async fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.inner
+            .record_histogram(name, value, &self.merged(labels))
+            .await;
+    }
+
+    fn start_timer(&self, name: &str, labels: Vec<(&str, &str)>) -> MetricTimer {
+        self.inner.start_timer(name, self.merged(&labels))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    type Recorded = (String, f64, Vec<(String, String)>);
+
+    #[derive(Default)]
+    struct CapturingRecorder(Mutex<Vec<Recorded>>);
+
+    #[async_trait]
+    impl MetricsRecorder for CapturingRecorder {
+        async fn record_counter(&self, name: &str, value: u64, labels: &[(&str, &str)]) {
+            self.record_gauge(name, value as f64, labels).await;
+        }
+
+        async fn record_gauge(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+            let labels = labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            self.0
+                .lock()
+                .unwrap()
+                .push((name.to_string(), value, labels));
+        }
+
+        async fn record_histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+            self.record_gauge(name, value, labels).await;
+        }
+
+        fn start_timer(&self, name: &str, labels: Vec<(&str, &str)>) -> MetricTimer {
+            MetricTimer::new(name.to_string(), labels)
+        }
+    }
+
+    fn label(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_static_labels_added_to_metrics() {
+        let inner = Arc::new(CapturingRecorder::default());
+        let static_labels = HashMap::from([
+            ("cluster".to_string(), "eu-1".to_string()),
+            ("region".to_string(), "fra".to_string()),
+        ]);
+        let recorder = StaticLabelsRecorder::wrap(inner.clone(), &static_labels);
+
+        recorder.record_gauge("executor_cpu_usage", 42.0, &[]).await;
+        recorder
+            .record_gauge(
+                "gpu_utilization_percent",
+                90.0,
+                &[("gpu_index", "0"), ("region", "override")],
+            )
+            .await;
+
+        let recorded = inner.0.lock().unwrap();
+        assert_eq!(
+            recorded[0],
+            (
+                "executor_cpu_usage".to_string(),
+                42.0,
+                vec![label("cluster", "eu-1"), label("region", "fra")]
+            )
+        );
+        // Per-resource labels win over static ones of the same name
+        assert_eq!(
+            recorded[1].2,
+            vec![
+                label("gpu_index", "0"),
+                label("region", "override"),
+                label("cluster", "eu-1")
+            ]
+        );
+    }
+}
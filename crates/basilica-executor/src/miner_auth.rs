@@ -5,7 +5,10 @@
 
 use anyhow::{anyhow, Result};
 use basilica_common::{crypto::verify_bittensor_signature, identity::Hotkey};
-use basilica_protocol::{common::MinerAuthentication, executor_control};
+use basilica_protocol::{
+    common::MinerAuthentication,
+    executor_control::{self, ValidatorAuthentication},
+};
 use blake3::Hasher;
 use chrono::{Duration, Utc};
 use std::collections::HashMap;
@@ -65,42 +68,7 @@ impl MinerAuthService {
             ));
         }
 
-        // Check timestamp age
-        let timestamp = chrono::DateTime::from_timestamp_millis(auth.timestamp_ms as i64)
-            .ok_or_else(|| anyhow!("Invalid timestamp"))?;
-        let now = Utc::now();
-        let request_age = now - timestamp;
-
-        if request_age > self.config.max_request_age {
-            return Err(anyhow!("Request too old: {:?}", request_age));
-        }
-
-        if request_age < Duration::zero() {
-            // Allow small clock skew (up to 1 minute in the future)
-            if request_age.abs() > Duration::minutes(1) {
-                return Err(anyhow!("Request timestamp is in the future"));
-            }
-        }
-
-        // Validate nonce is a valid UUID (security requirement to prevent replay attacks)
-        let nonce_str = String::from_utf8_lossy(&auth.nonce);
-        let nonce_uuid = Uuid::parse_str(&nonce_str)
-            .map_err(|_| anyhow!("Nonce must be a valid UUID format"))?;
-
-        // Check nonce for replay attack prevention
-        let mut used_nonces = self.used_nonces.write().await;
-
-        if used_nonces.contains_key(&nonce_uuid) {
-            return Err(anyhow!("Nonce already used"));
-        }
-
-        // Store nonce with expiration
-        used_nonces.insert(nonce_uuid, now);
-
-        // Clean up old nonces
-        let cutoff = now - self.config.max_request_age - Duration::hours(1);
-        used_nonces.retain(|_, timestamp| *timestamp > cutoff);
-        drop(used_nonces);
+        self.check_freshness(auth.timestamp_ms, &auth.nonce).await?;
 
         // Verify signature if enabled
         if self.config.verify_signatures {
@@ -138,6 +106,83 @@ impl MinerAuthService {
         Ok(())
     }
 
+    /// Verify a validator's signature on a request it sent without the miner
+    ///
+    /// `payload` is the request's validator signing payload, which must be
+    /// signed by `validator_hotkey`. Timestamps and nonces are checked the
+    /// same way as for miner requests.
+    pub async fn verify_validator_auth(
+        &self,
+        validator_hotkey: &str,
+        auth: &ValidatorAuthentication,
+        payload: &[u8],
+    ) -> Result<()> {
+        let hotkey = Hotkey::new(validator_hotkey.to_string())
+            .map_err(|e| anyhow!("Invalid validator hotkey: {e}"))?;
+
+        self.check_freshness(auth.timestamp_ms, &auth.nonce).await?;
+
+        if self.config.verify_signatures {
+            let signature_hex = hex::encode(&auth.signature);
+            if let Err(e) = verify_bittensor_signature(&hotkey, &signature_hex, payload) {
+                warn!(
+                    "Signature verification failed for validator {}: {}",
+                    validator_hotkey, e
+                );
+                return Err(anyhow!("Invalid signature"));
+            }
+        }
+
+        debug!(
+            "Request verified successfully from validator: {}",
+            validator_hotkey
+        );
+
+        Ok(())
+    }
+
+    /// Reject requests that are too old, too far in the future, or reuse a
+    /// nonce, and remember the nonce
+    async fn check_freshness(&self, timestamp_ms: u64, nonce: &[u8]) -> Result<()> {
+        // Check timestamp age
+        let timestamp = chrono::DateTime::from_timestamp_millis(timestamp_ms as i64)
+            .ok_or_else(|| anyhow!("Invalid timestamp"))?;
+        let now = Utc::now();
+        let request_age = now - timestamp;
+
+        if request_age > self.config.max_request_age {
+            return Err(anyhow!("Request too old: {:?}", request_age));
+        }
+
+        if request_age < Duration::zero() {
+            // Allow small clock skew (up to 1 minute in the future)
+            if request_age.abs() > Duration::minutes(1) {
+                return Err(anyhow!("Request timestamp is in the future"));
+            }
+        }
+
+        // Validate nonce is a valid UUID (security requirement to prevent replay attacks)
+        let nonce_str = String::from_utf8_lossy(nonce);
+        let nonce_uuid = Uuid::parse_str(&nonce_str)
+            .map_err(|_| anyhow!("Nonce must be a valid UUID format"))?;
+
+        // Check nonce for replay attack prevention
+        let mut used_nonces = self.used_nonces.write().await;
+
+        if used_nonces.contains_key(&nonce_uuid) {
+            return Err(anyhow!("Nonce already used"));
+        }
+
+        // Store nonce with expiration
+        used_nonces.insert(nonce_uuid, now);
+
+        // Clean up old nonces
+        let cutoff = now - self.config.max_request_age - Duration::hours(1);
+        used_nonces.retain(|_, timestamp| *timestamp > cutoff);
+
+        Ok(())
+    }
+
     /// Create canonical data for verification (must match miner's creation)
     fn create_canonical_data(
         &self,
@@ -235,6 +280,18 @@ impl AuthenticatedRequest for executor_control::ContainerOpRequest {
     }
 }
 
+impl AuthenticatedRequest for executor_control::ContainerLogsRequest {
+    fn get_auth(&self) -> Option<&MinerAuthentication> {
+        self.auth.as_ref()
+    }
+
+    fn without_auth(&self) -> Self {
+        let mut clone = self.clone();
+        clone.auth = None;
+        clone
+    }
+}
+
 impl AuthenticatedRequest for executor_control::HealthCheckRequest {
     fn get_auth(&self) -> Option<&MinerAuthentication> {
         self.auth.as_ref()
@@ -277,6 +334,34 @@ where
     Ok(())
 }
 
+/// Verify a log stream request, signed either by the managing miner or by
+/// the validator named in the request
+pub async fn verify_logs_request(
+    auth_service: &MinerAuthService,
+    request: &executor_control::ContainerLogsRequest,
+) -> Result<(), Status> {
+    if request.auth.is_some() {
+        return verify_miner_request(auth_service, request).await;
+    }
+
+    let auth = request
+        .validator_auth
+        .as_ref()
+        .ok_or_else(|| Status::unauthenticated("Missing authentication"))?;
+
+    auth_service
+        .verify_validator_auth(
+            &request.validator_hotkey,
+            auth,
+            &request.validator_signing_payload(),
+        )
+        .await
+        .map_err(|e| {
+            debug!("Validator authentication failed: {}", e);
+            Status::unauthenticated(format!("Authentication failed: {e}"))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use basilica_protocol::executor_control;
@@ -608,8 +693,15 @@ mod tests {
         // HealthCheckRequest
         let mut req5 = executor_control::HealthCheckRequest::default();
         assert!(req5.get_auth().is_none());
-        req5.auth = Some(auth);
+        req5.auth = Some(auth.clone());
         assert!(req5.get_auth().is_some());
+
+        // ContainerLogsRequest
+        let mut req6 = executor_control::ContainerLogsRequest::default();
+        assert!(req6.get_auth().is_none());
+        req6.auth = Some(auth);
+        assert!(req6.get_auth().is_some());
+        assert!(req6.without_auth().auth.is_none());
     }
 
     #[test]
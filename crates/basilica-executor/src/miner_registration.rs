@@ -0,0 +1,239 @@
+//! # Miner Registration Module
+//!
+//! Announces this executor's advertised endpoints to the managing miner over
+//! the `ExecutorRegistration` gRPC service and keeps the registration alive
+//! with periodic heartbeats, so the miner can rediscover the executor after a
+//! restart and notice when it goes away.
+
+use crate::config::{ExecutorConfig, MinerRegistrationConfig};
+use crate::system_monitor::stream::ts_now;
+use crate::system_monitor::SystemProfile as ExecutorSystemProfile;
+use anyhow::{anyhow, Context, Result};
+use basilica_protocol::common::{GpuSpec, SystemProfile, Timestamp};
+use basilica_protocol::executor_registration::{
+    executor_registration_client::ExecutorRegistrationClient, HeartbeatRequest,
+    RegisterExecutorRequest,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tonic::transport::{Channel, Endpoint};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Identity and endpoints announced to the miner
+#[derive(Debug, Clone)]
+pub struct RegistrationDetails {
+    pub executor_id: String,
+    pub grpc_endpoint: String,
+    pub ssh_endpoint: String,
+    pub health_endpoint: String,
+    pub miner_hotkey: String,
+    /// Per-device GPU identity and topology
+    pub gpus: Vec<GpuSpec>,
+}
+
+impl RegistrationDetails {
+    /// Build registration details from the executor's advertised endpoints
+    pub fn from_config(executor_id: String, config: &ExecutorConfig) -> Self {
+        Self {
+            executor_id,
+            grpc_endpoint: config.get_advertised_grpc_endpoint(),
+            ssh_endpoint: config.get_advertised_ssh_endpoint(),
+            health_endpoint: config.get_advertised_health_endpoint(),
+            miner_hotkey: config.managing_miner_hotkey.to_string(),
+            gpus: Vec::new(),
+        }
+    }
+
+    /// Announce the GPUs described by the executor's system profile
+    pub fn with_gpus_from_profile(mut self, profile: &ExecutorSystemProfile) -> Self {
+        self.gpus = profile
+            .gpus
+            .iter()
+            .map(|gpu| GpuSpec {
+                model: gpu.name.clone(),
+                memory_mb: gpu.memory_total_bytes / (1024 * 1024),
+                uuid: gpu.uuid.clone(),
+                pci_bus_id: gpu.pci_bus_id.clone(),
+                numa_node: gpu.numa_node,
+                ..Default::default()
+            })
+            .collect();
+        self
+    }
+
+    /// Build the RegisterExecutor request sent to the miner
+    pub fn to_request(&self) -> RegisterExecutorRequest {
+        let mut metadata = HashMap::new();
+        metadata.insert("ssh_endpoint".to_string(), self.ssh_endpoint.clone());
+        metadata.insert("health_endpoint".to_string(), self.health_endpoint.clone());
+
+        RegisterExecutorRequest {
+            executor_id: self.executor_id.clone(),
+            grpc_address: self.grpc_endpoint.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            miner_hotkey: self.miner_hotkey.clone(),
+            nonce: Uuid::new_v4().to_string(),
+            metadata,
+            system_profile: (!self.gpus.is_empty()).then(|| SystemProfile {
+                gpus: self.gpus.clone(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+/// Registration accepted by the miner
+#[derive(Debug, Clone)]
+pub struct Registration {
+    pub token: String,
+    pub heartbeat_interval: Duration,
+}
+
+/// Registers the executor with its miner and maintains the registration
+pub struct MinerRegistrar {
+    config: MinerRegistrationConfig,
+    endpoint: String,
+    details: RegistrationDetails,
+}
+
+impl MinerRegistrar {
+    /// Create a registrar; fails when no miner endpoint is configured
+    pub fn new(config: MinerRegistrationConfig, details: RegistrationDetails) -> Result<Self> {
+        let endpoint = config
+            .endpoint
+            .clone()
+            .ok_or_else(|| anyhow!("miner_registration.endpoint is not configured"))?;
+
+        Ok(Self {
+            config,
+            endpoint,
+            details,
+        })
+    }
+
+    async fn connect(&self) -> Result<ExecutorRegistrationClient<Channel>> {
+        let timeout = Duration::from_secs(self.config.request_timeout_secs);
+        let channel = Endpoint::from_shared(self.endpoint.clone())
+            .with_context(|| format!("Invalid miner endpoint: {}", self.endpoint))?
+            .connect_timeout(timeout)
+            .timeout(timeout)
+            .connect()
+            .await
+            .with_context(|| format!("Failed to connect to miner at {}", self.endpoint))?;
+
+        Ok(ExecutorRegistrationClient::new(channel))
+    }
+
+    /// Attempt a single registration
+    pub async fn register(&self) -> Result<Registration> {
+        let mut client = self.connect().await?;
+        let response = client
+            .register_executor(self.details.to_request())
+            .await
+            .context("RegisterExecutor call failed")?
+            .into_inner();
+
+        if !response.success {
+            let reason = response
+                .error
+                .map(|e| format!("{}: {}", e.code, e.message))
+                .unwrap_or_else(|| "no reason given".to_string());
+            return Err(anyhow!("Miner rejected registration: {reason}"));
+        }
+
+        let heartbeat_secs = if response.heartbeat_interval_seconds > 0 {
+            response.heartbeat_interval_seconds
+        } else {
+            self.config.heartbeat_interval_secs
+        };
+
+        Ok(Registration {
+            token: response.registration_token,
+            heartbeat_interval: Duration::from_secs(heartbeat_secs.max(1)),
+        })
+    }
+
+    /// Register, retrying with exponential backoff until the miner acknowledges
+    pub async fn register_with_retry(&self) -> Registration {
+        let max_backoff = Duration::from_secs(self.config.max_backoff_secs);
+        let mut backoff = Duration::from_millis(self.config.initial_backoff_ms).min(max_backoff);
+        let mut attempt = 1u32;
+
+        loop {
+            match self.register().await {
+                Ok(registration) => {
+                    info!(
+                        "Registered executor {} with miner at {} (heartbeat every {}s)",
+                        self.details.executor_id,
+                        self.endpoint,
+                        registration.heartbeat_interval.as_secs()
+                    );
+                    return registration;
+                }
+                Err(e) => {
+                    warn!(
+                        "Registration attempt {} with miner at {} failed: {:#}. Retrying in {:?}",
+                        attempt, self.endpoint, e, backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(max_backoff);
+                    attempt = attempt.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    /// Send a single heartbeat; returns whether the miner acknowledged it
+    pub async fn heartbeat(&self, registration: &Registration) -> Result<bool> {
+        let mut client = self.connect().await?;
+
+        let mut health_status = HashMap::new();
+        health_status.insert("status".to_string(), "healthy".to_string());
+
+        let response = client
+            .heartbeat(HeartbeatRequest {
+                executor_id: self.details.executor_id.clone(),
+                registration_token: registration.token.clone(),
+                health_status,
+                timestamp: Some(Timestamp {
+                    value: Some(ts_now()),
+                }),
+                ..Default::default()
+            })
+            .await
+            .context("Heartbeat call failed")?
+            .into_inner();
+
+        Ok(response.acknowledged)
+    }
+
+    /// Register and then heartbeat forever, re-registering whenever the
+    /// miner stops acknowledging heartbeats
+    pub async fn run(self) {
+        let mut registration = self.register_with_retry().await;
+
+        loop {
+            tokio::time::sleep(registration.heartbeat_interval).await;
+
+            match self.heartbeat(&registration).await {
+                Ok(true) => debug!("Heartbeat acknowledged by miner"),
+                Ok(false) => {
+                    warn!("Miner did not acknowledge heartbeat, re-registering");
+                    registration = self.register_with_retry().await;
+                }
+                Err(e) => {
+                    warn!("Heartbeat to miner failed: {:#}. Re-registering", e);
+                    registration = self.register_with_retry().await;
+                }
+            }
+        }
+    }
+
+    /// Run registration and heartbeats in a background task
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+}
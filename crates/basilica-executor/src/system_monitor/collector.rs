@@ -1,6 +1,6 @@
 use super::docker_utils;
 use super::metrics::{Metrics, MetricsChannel};
-use super::types::{ContainerMetrics, DiskUsage, GpuMetrics, SystemMetrics};
+use super::types::{ContainerMetrics, DiskUsage, GpuMetrics, InterfaceUsage, SystemMetrics};
 use super::volumes::VolumeMonitor;
 use super::{cpu::CpuMonitor, disk::DiskMonitor, memory::MemoryMonitor, network::NetworkMonitor};
 use crate::config::types::TelemetryMonitorConfig;
@@ -13,7 +13,7 @@ use nvml_wrapper::Nvml;
 use std::collections::HashMap;
 use std::time::SystemTime;
 use sysinfo::System;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 use tokio::time::{interval, Duration};
 use tracing::{debug, info, warn};
 
@@ -28,6 +28,7 @@ pub struct Collector {
     volume_monitor: Option<VolumeMonitor>,
     broadcast_tx: MetricsChannel,
     config: TelemetryMonitorConfig,
+    config_updates: Option<watch::Receiver<TelemetryMonitorConfig>>,
 }
 
 impl Collector {
@@ -54,15 +55,24 @@ impl Collector {
             cpu_monitor: CpuMonitor::new(),
             memory_monitor: MemoryMonitor::new(),
             disk_monitor: DiskMonitor::new(),
-            network_monitor: NetworkMonitor::new(),
+            network_monitor: NetworkMonitor::with_billed_interfaces(
+                config.billed_interfaces.clone(),
+            ),
             volume_monitor,
             broadcast_tx,
             config,
+            config_updates: None,
         };
 
         Ok((collector, tx_clone))
     }
 
+    /// Apply sampling interval changes published on `updates` while running
+    pub fn with_config_updates(mut self, updates: watch::Receiver<TelemetryMonitorConfig>) -> Self {
+        self.config_updates = Some(updates);
+        self
+    }
+
     /// Start the unified collection loop
     pub async fn start(mut self) {
         info!(
@@ -80,9 +90,21 @@ impl Collector {
         let mut container_interval =
             interval(Duration::from_secs(self.config.container_sample_secs));
         let mut volume_interval = interval(Duration::from_secs(60)); // Check volumes every minute
+        let mut config_updates = self.config_updates.take();
 
         loop {
             tokio::select! {
+                Some(updated) = Self::next_config_update(&mut config_updates) => {
+                    if updated.host_interval_secs != self.config.host_interval_secs {
+                        info!("Host metrics interval changed to {}s", updated.host_interval_secs);
+                        system_interval = interval(Duration::from_secs(updated.host_interval_secs));
+                    }
+                    if updated.container_sample_secs != self.config.container_sample_secs {
+                        info!("Container sample interval changed to {}s", updated.container_sample_secs);
+                        container_interval = interval(Duration::from_secs(updated.container_sample_secs));
+                    }
+                    self.config = updated;
+                }
                 _ = system_interval.tick() => {
                     if let Err(e) = self.collect_and_broadcast_system().await {
                         warn!("Failed to collect system metrics: {}", e);
@@ -102,6 +124,23 @@ impl Collector {
         }
     }
 
+    /// Wait for the next published configuration. Never resolves when no
+    /// update channel is attached or its sender is gone.
+    async fn next_config_update(
+        updates: &mut Option<watch::Receiver<TelemetryMonitorConfig>>,
+    ) -> Option<TelemetryMonitorConfig> {
+        let Some(rx) = updates.as_mut() else {
+            return std::future::pending().await;
+        };
+
+        if rx.changed().await.is_err() {
+            *updates = None;
+            return None;
+        }
+
+        Some(rx.borrow_and_update().clone())
+    }
+
     /// Collect system-wide metrics
     async fn collect_and_broadcast_system(&mut self) -> Result<()> {
         self.system.refresh_all();
@@ -123,6 +162,16 @@ impl Collector {
             },
             network_rx_bytes: network_info.total_bytes_received,
             network_tx_bytes: network_info.total_bytes_sent,
+            network_interfaces: network_info
+                .interfaces
+                .iter()
+                .map(|i| InterfaceUsage {
+                    name: i.name.clone(),
+                    rx_mbps: i.rx_mbps,
+                    tx_mbps: i.tx_mbps,
+                    billed: i.billed,
+                })
+                .collect(),
             disk_usage: disk_info
                 .into_iter()
                 .map(|d| DiskUsage {
@@ -130,6 +179,7 @@ impl Collector {
                     total_bytes: d.total_bytes,
                     used_bytes: d.used_bytes,
                     available_bytes: d.available_bytes,
+                    io: d.io,
                 })
                 .collect(),
         };
@@ -1,14 +1,54 @@
 //! Disk monitoring functionality
 
-use super::types::{DiskInfo, DiskSummary};
+use super::types::{DiskInfo, DiskIoRates, DiskSummary};
 use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
 use sysinfo::Disks;
 use tracing::debug;
 
+/// Kernel I/O statistics source
+const DISKSTATS_PATH: &str = "/proc/diskstats";
+
+/// /proc/diskstats always reports sectors in 512-byte units
+const SECTOR_SIZE_BYTES: u64 = 512;
+
+/// Cumulative I/O counters for a block device
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiskIoCounters {
+    pub reads_completed: u64,
+    pub writes_completed: u64,
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+impl DiskIoCounters {
+    /// Compute rates between an earlier sample and this one.
+    ///
+    /// Counters that went backwards (device reset or wrap) yield zero rather
+    /// than a negative rate.
+    pub fn rates_since(&self, previous: &DiskIoCounters, elapsed_secs: f64) -> DiskIoRates {
+        if elapsed_secs <= 0.0 {
+            return DiskIoRates::default();
+        }
+
+        let rate = |current: u64, prev: u64| current.saturating_sub(prev) as f64 / elapsed_secs;
+
+        DiskIoRates {
+            read_bytes_per_sec: rate(self.read_bytes, previous.read_bytes),
+            write_bytes_per_sec: rate(self.write_bytes, previous.write_bytes),
+            read_iops: rate(self.reads_completed, previous.reads_completed),
+            write_iops: rate(self.writes_completed, previous.writes_completed),
+        }
+    }
+}
+
 /// Disk monitoring handler
 #[derive(Debug)]
 pub struct DiskMonitor {
     include_virtual: bool,
+    io_samples: Mutex<HashMap<String, (Instant, DiskIoCounters)>>,
 }
 
 impl DiskMonitor {
@@ -16,6 +56,7 @@ impl DiskMonitor {
     pub fn new() -> Self {
         Self {
             include_virtual: false,
+            io_samples: Mutex::new(HashMap::new()),
         }
     }
 
@@ -23,12 +64,21 @@ impl DiskMonitor {
     pub fn with_virtual_filesystems() -> Self {
         Self {
             include_virtual: true,
+            io_samples: Mutex::new(HashMap::new()),
         }
     }
 
     /// Get disk information
     pub fn get_disk_info(&self) -> Result<Vec<DiskInfo>> {
+        self.collect_disk_info(self.include_virtual)
+    }
+
+    /// Collect disks, sampling I/O counters into this monitor's history
+    fn collect_disk_info(&self, include_virtual: bool) -> Result<Vec<DiskInfo>> {
         let mut disks = Vec::new();
+        let io_counters = read_diskstats();
+        let sampled_at = Instant::now();
+        let mut pass_rates = HashMap::new();
 
         // For sysinfo 0.30+, disks are accessed via Disks struct
         let disk_manager = Disks::new_with_refreshed_list();
@@ -37,7 +87,7 @@ impl DiskMonitor {
             let mount_point = disk.mount_point().to_string_lossy().to_string();
 
             // Skip virtual filesystems unless explicitly included
-            if !self.include_virtual && self.is_virtual_filesystem(&filesystem, &mount_point) {
+            if !include_virtual && self.is_virtual_filesystem(&filesystem, &mount_point) {
                 debug!(
                     "Skipping virtual filesystem: {} at {}",
                     filesystem, mount_point
@@ -54,24 +104,70 @@ impl DiskMonitor {
                 0.0
             };
 
+            let name = disk.name().to_string_lossy().to_string();
+            let device = device_name(&name);
+            let io = io_counters
+                .get(device)
+                .map(|counters| self.pass_io_rates(device, *counters, sampled_at, &mut pass_rates))
+                .unwrap_or_default();
+
             disks.push(DiskInfo {
-                name: disk.name().to_string_lossy().to_string(),
+                name,
                 mount_point,
                 total_bytes: total,
                 used_bytes: used,
                 available_bytes: available,
                 usage_percent,
                 filesystem,
+                io,
             });
         }
 
         Ok(disks)
     }
 
+    /// Rates for a device within one collection pass. A device mounted in
+    /// several places is sampled once, and every mount reports its rates.
+    fn pass_io_rates(
+        &self,
+        device: &str,
+        counters: DiskIoCounters,
+        sampled_at: Instant,
+        pass_rates: &mut HashMap<String, DiskIoRates>,
+    ) -> DiskIoRates {
+        *pass_rates
+            .entry(device.to_string())
+            .or_insert_with(|| self.sample_io_rates(device, counters, sampled_at))
+    }
+
+    /// Record a counter sample for a device and return the rates since the
+    /// previous sample. The first sample for a device reports zero.
+    fn sample_io_rates(
+        &self,
+        device: &str,
+        counters: DiskIoCounters,
+        sampled_at: Instant,
+    ) -> DiskIoRates {
+        let mut samples = match self.io_samples.lock() {
+            Ok(samples) => samples,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let rates = samples
+            .get(device)
+            .map(|(previous_at, previous)| {
+                let elapsed = sampled_at.duration_since(*previous_at).as_secs_f64();
+                counters.rates_since(previous, elapsed)
+            })
+            .unwrap_or_default();
+
+        samples.insert(device.to_string(), (sampled_at, counters));
+        rates
+    }
+
     /// Get all disk information including virtual filesystems
     pub fn get_all_disk_info(&self) -> Result<Vec<DiskInfo>> {
-        let monitor = Self::with_virtual_filesystems();
-        monitor.get_disk_info()
+        self.collect_disk_info(true)
     }
 
     /// Get disk usage summary
@@ -158,8 +254,171 @@ impl DiskMonitor {
     }
 }
 
+/// Strip the `/dev/` prefix so sysinfo disk names match /proc/diskstats entries
+fn device_name(name: &str) -> &str {
+    name.strip_prefix("/dev/").unwrap_or(name)
+}
+
+/// Read cumulative I/O counters for every block device. Returns an empty map
+/// on platforms without /proc/diskstats.
+fn read_diskstats() -> HashMap<String, DiskIoCounters> {
+    match std::fs::read_to_string(DISKSTATS_PATH) {
+        Ok(content) => parse_diskstats(&content),
+        Err(e) => {
+            debug!("Unable to read {}: {}", DISKSTATS_PATH, e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Parse the contents of /proc/diskstats keyed by device name
+fn parse_diskstats(content: &str) -> HashMap<String, DiskIoCounters> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+
+            let field = |index: usize| fields[index].parse::<u64>().ok();
+
+            Some((
+                fields[2].to_string(),
+                DiskIoCounters {
+                    reads_completed: field(3)?,
+                    read_bytes: field(5)?.saturating_mul(SECTOR_SIZE_BYTES),
+                    writes_completed: field(7)?,
+                    write_bytes: field(9)?.saturating_mul(SECTOR_SIZE_BYTES),
+                },
+            ))
+        })
+        .collect()
+}
+
 impl Default for DiskMonitor {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rates_between_two_samples() {
+        let first = DiskIoCounters {
+            reads_completed: 100,
+            writes_completed: 50,
+            read_bytes: 1_000_000,
+            write_bytes: 400_000,
+        };
+        let second = DiskIoCounters {
+            reads_completed: 300,
+            writes_completed: 90,
+            read_bytes: 3_000_000,
+            write_bytes: 1_200_000,
+        };
+
+        let rates = second.rates_since(&first, 2.0);
+
+        assert_eq!(rates.read_bytes_per_sec, 1_000_000.0);
+        assert_eq!(rates.write_bytes_per_sec, 400_000.0);
+        assert_eq!(rates.read_iops, 100.0);
+        assert_eq!(rates.write_iops, 20.0);
+    }
+
+    #[test]
+    fn test_counter_reset_clamps_to_zero() {
+        let before_reset = DiskIoCounters {
+            reads_completed: 1_000,
+            writes_completed: 1_000,
+            read_bytes: 10_000_000,
+            write_bytes: 10_000_000,
+        };
+        let after_reset = DiskIoCounters {
+            reads_completed: 10,
+            writes_completed: 2_000,
+            read_bytes: 4_096,
+            write_bytes: 20_000_000,
+        };
+
+        let rates = after_reset.rates_since(&before_reset, 1.0);
+
+        assert_eq!(rates.read_bytes_per_sec, 0.0);
+        assert_eq!(rates.read_iops, 0.0);
+        assert_eq!(rates.write_bytes_per_sec, 10_000_000.0);
+        assert_eq!(rates.write_iops, 1_000.0);
+        assert_eq!(
+            after_reset.rates_since(&before_reset, 0.0),
+            DiskIoRates::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_diskstats() {
+        let content = "\
+   8       0 sda 2000 10 40000 500 1000 20 80000 900 0 1200 1400 0 0 0 0
+   8       1 sda1 1500 5 30000 400 800 10 64000 700 0 1000 1100 0 0 0 0
+ 259       0 nvme0n1 42 0 84 1 7 0 14 2 0 3 3
+";
+        let stats = parse_diskstats(content);
+
+        assert_eq!(stats.len(), 3);
+        let sda1 = stats[device_name("/dev/sda1")];
+        assert_eq!(sda1.reads_completed, 1500);
+        assert_eq!(sda1.read_bytes, 30000 * SECTOR_SIZE_BYTES);
+        assert_eq!(sda1.writes_completed, 800);
+        assert_eq!(sda1.write_bytes, 64000 * SECTOR_SIZE_BYTES);
+        assert_eq!(stats["nvme0n1"].write_bytes, 14 * SECTOR_SIZE_BYTES);
+    }
+
+    #[test]
+    fn test_first_sample_reports_zero() {
+        let monitor = DiskMonitor::new();
+        let start = Instant::now();
+        let counters = DiskIoCounters {
+            reads_completed: 10,
+            writes_completed: 10,
+            read_bytes: 4096,
+            write_bytes: 4096,
+        };
+
+        let first = monitor.sample_io_rates("sda", counters, start);
+        assert_eq!(first, DiskIoRates::default());
+
+        let later = DiskIoCounters {
+            reads_completed: 20,
+            ..counters
+        };
+        let second =
+            monitor.sample_io_rates("sda", later, start + std::time::Duration::from_secs(5));
+        assert_eq!(second.read_iops, 2.0);
+    }
+
+    #[test]
+    fn test_device_mounted_twice_reports_rates_on_both_mounts() {
+        let monitor = DiskMonitor::new();
+        let start = Instant::now();
+        let counters = DiskIoCounters {
+            reads_completed: 10,
+            writes_completed: 10,
+            read_bytes: 4096,
+            write_bytes: 4096,
+        };
+        monitor.sample_io_rates("sda1", counters, start);
+
+        let later = DiskIoCounters {
+            reads_completed: 20,
+            ..counters
+        };
+        let sampled_at = start + std::time::Duration::from_secs(5);
+        let mut pass_rates = HashMap::new();
+        let first_mount = monitor.pass_io_rates("sda1", later, sampled_at, &mut pass_rates);
+        let second_mount = monitor.pass_io_rates("sda1", later, sampled_at, &mut pass_rates);
+
+        assert_eq!(first_mount.read_iops, 2.0);
+        assert_eq!(second_mount, first_mount);
+    }
+}
@@ -0,0 +1,249 @@
+//! GPU energy accounting for cost and carbon reporting
+//!
+//! Each GPU's power samples are integrated over the time between consecutive
+//! samples (trapezoidal rule) to give the energy it has used. A gap longer
+//! than the configured maximum, such as a stalled collector or an NVML
+//! failure, is not integrated: the power drawn in between is unknown, and
+//! assuming either neighbouring sample would over- or under-count.
+//!
+//! Rentals are charged the energy their GPUs used from the moment the rental
+//! is first seen, so every rental starts from zero.
+
+use super::types::GpuMetrics;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+/// Longest interval between two power samples that is still integrated
+pub const DEFAULT_MAX_SAMPLE_GAP: Duration = Duration::from_secs(30);
+
+/// How long a rental that is no longer sampled keeps its energy total
+pub const DEFAULT_RENTAL_RETENTION: Duration = Duration::from_secs(600);
+
+const JOULES_PER_KWH: f64 = 3_600_000.0;
+
+#[derive(Debug, Default)]
+struct GpuEnergy {
+    joules: f64,
+    last_sample: Option<(SystemTime, f64)>,
+}
+
+/// Running energy total of each GPU
+#[derive(Debug)]
+pub struct GpuEnergyAccumulator {
+    max_gap: Duration,
+    gpus: HashMap<u32, GpuEnergy>,
+}
+
+impl GpuEnergyAccumulator {
+    pub fn new(max_gap: Duration) -> Self {
+        Self {
+            max_gap,
+            gpus: HashMap::new(),
+        }
+    }
+
+    /// Add the power drawn by GPU `index` at `at`
+    ///
+    /// Samples older than the previous one for the same GPU are ignored.
+    pub fn record(&mut self, index: u32, power_watts: f64, at: SystemTime) {
+        let gpu = self.gpus.entry(index).or_default();
+        if let Some((last_at, last_watts)) = gpu.last_sample {
+            let Ok(elapsed) = at.duration_since(last_at) else {
+                return;
+            };
+            if elapsed <= self.max_gap {
+                gpu.joules += (last_watts + power_watts) / 2.0 * elapsed.as_secs_f64();
+            }
+        }
+        gpu.last_sample = Some((at, power_watts));
+    }
+
+    /// Energy GPU `index` has used, in kWh
+    pub fn gpu_kwh(&self, index: u32) -> f64 {
+        self.gpus
+            .get(&index)
+            .map_or(0.0, |gpu| gpu.joules / JOULES_PER_KWH)
+    }
+
+    /// Energy used by every GPU, in kWh
+    pub fn total_kwh(&self) -> f64 {
+        self.gpus.values().map(|gpu| gpu.joules).sum::<f64>() / JOULES_PER_KWH
+    }
+
+    fn indices(&self) -> impl Iterator<Item = u32> + '_ {
+        self.gpus.keys().copied()
+    }
+}
+
+impl Default for GpuEnergyAccumulator {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SAMPLE_GAP)
+    }
+}
+
+/// Energy a rental has used so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct RentalEnergy {
+    pub total_kwh: f64,
+    /// Energy per GPU index, sorted by index
+    pub per_gpu_kwh: Vec<(u32, f64)>,
+}
+
+#[derive(Debug)]
+struct RentalBaseline {
+    /// GPU totals when the rental was first seen
+    start_kwh: HashMap<u32, f64>,
+    last_seen: SystemTime,
+}
+
+/// Energy used by the executor's GPUs, attributed to the rentals using them
+///
+/// Rental containers are given every GPU on the executor, so a rental's
+/// energy is what all GPUs used since it was first seen.
+#[derive(Debug)]
+pub struct RentalEnergyMeter {
+    gpus: GpuEnergyAccumulator,
+    rentals: HashMap<String, RentalBaseline>,
+    retention: Duration,
+}
+
+impl RentalEnergyMeter {
+    pub fn new(max_gap: Duration, retention: Duration) -> Self {
+        Self {
+            gpus: GpuEnergyAccumulator::new(max_gap),
+            rentals: HashMap::new(),
+            retention,
+        }
+    }
+
+    /// Integrate a GPU sample taken at `at`
+    pub fn record_gpu_metrics(&mut self, gpus: &[GpuMetrics], at: SystemTime) {
+        for gpu in gpus {
+            self.gpus.record(gpu.index, gpu.power_watts as f64, at);
+        }
+    }
+
+    /// Energy `rental_id` has used up to `at`
+    ///
+    /// A rental not seen before starts from zero. Rentals not seen within the
+    /// retention period are forgotten.
+    pub fn rental_energy(&mut self, rental_id: &str, at: SystemTime) -> RentalEnergy {
+        let retention = self.retention;
+        self.rentals.retain(|_, rental| {
+            at.duration_since(rental.last_seen)
+                .map_or(true, |idle| idle <= retention)
+        });
+
+        let gpus = &self.gpus;
+        let rental = self
+            .rentals
+            .entry(rental_id.to_string())
+            .or_insert_with(|| RentalBaseline {
+                start_kwh: gpus.indices().map(|i| (i, gpus.gpu_kwh(i))).collect(),
+                last_seen: at,
+            });
+        rental.last_seen = rental.last_seen.max(at);
+
+        let mut per_gpu_kwh: Vec<(u32, f64)> = gpus
+            .indices()
+            .map(|index| {
+                let start = rental.start_kwh.get(&index).copied().unwrap_or(0.0);
+                (index, gpus.gpu_kwh(index) - start)
+            })
+            .collect();
+        per_gpu_kwh.sort_by_key(|(index, _)| *index);
+
+        RentalEnergy {
+            total_kwh: per_gpu_kwh.iter().map(|(_, kwh)| kwh).sum(),
+            per_gpu_kwh,
+        }
+    }
+}
+
+impl Default for RentalEnergyMeter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SAMPLE_GAP, DEFAULT_RENTAL_RETENTION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gpu(index: u32, power_watts: u64) -> GpuMetrics {
+        GpuMetrics {
+            index,
+            name: format!("GPU {index}"),
+            utilization_percent: 100.0,
+            memory_used_mb: 0,
+            memory_total_mb: 81920,
+            temperature_celsius: 60.0,
+            power_watts,
+        }
+    }
+
+    fn assert_kwh(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected} kWh, got {actual}"
+        );
+    }
+
+    #[test]
+    fn test_constant_power_integrates_to_known_energy() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut accumulator = GpuEnergyAccumulator::default();
+
+        // 300 W sampled every 2 seconds for an hour
+        for step in 0..=1800 {
+            accumulator.record(0, 300.0, start + Duration::from_secs(step * 2));
+        }
+
+        assert_kwh(accumulator.gpu_kwh(0), 0.3);
+        assert_kwh(accumulator.total_kwh(), 0.3);
+        assert_kwh(accumulator.gpu_kwh(1), 0.0);
+    }
+
+    #[test]
+    fn test_sampling_gaps_are_not_integrated() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut accumulator = GpuEnergyAccumulator::new(Duration::from_secs(10));
+
+        accumulator.record(0, 360.0, start);
+        accumulator.record(0, 360.0, start + Duration::from_secs(10));
+        // Nothing is known about the next five minutes
+        accumulator.record(0, 360.0, start + Duration::from_secs(310));
+        accumulator.record(0, 360.0, start + Duration::from_secs(320));
+        // Out of order samples are dropped
+        accumulator.record(0, 360.0, start + Duration::from_secs(315));
+
+        // 20 seconds at 360 W
+        assert_kwh(accumulator.gpu_kwh(0), 0.002);
+    }
+
+    #[test]
+    fn test_rental_energy_starts_from_zero() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut meter = RentalEnergyMeter::new(Duration::from_secs(30), Duration::from_secs(600));
+
+        // Energy used before the rental is not charged to it
+        meter.record_gpu_metrics(&[gpu(0, 720), gpu(1, 360)], at(0));
+        meter.record_gpu_metrics(&[gpu(0, 720), gpu(1, 360)], at(10));
+        let energy = meter.rental_energy("rental-a", at(10));
+        assert_kwh(energy.total_kwh, 0.0);
+
+        meter.record_gpu_metrics(&[gpu(0, 720), gpu(1, 360)], at(20));
+        let energy = meter.rental_energy("rental-a", at(20));
+        assert_kwh(energy.total_kwh, 0.003);
+        assert_eq!(energy.per_gpu_kwh.len(), 2);
+        assert_eq!(energy.per_gpu_kwh[0].0, 0);
+        assert_kwh(energy.per_gpu_kwh[0].1, 0.002);
+        assert_kwh(energy.per_gpu_kwh[1].1, 0.001);
+
+        // A rental forgotten after the retention period starts over
+        meter.record_gpu_metrics(&[gpu(0, 720), gpu(1, 360)], at(30));
+        assert_kwh(meter.rental_energy("rental-b", at(900)).total_kwh, 0.0);
+        assert_kwh(meter.rental_energy("rental-a", at(900)).total_kwh, 0.0);
+    }
+}
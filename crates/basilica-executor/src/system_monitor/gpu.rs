@@ -1,42 +1,114 @@
 //! GPU monitoring functionality
 
-use super::types::GpuInfo;
+use super::types::{GpuInfo, MigInstance};
 use anyhow::{Context, Result};
+use basilica_common::MigProfile;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::{debug, info, warn};
 
+/// Directory listing PCI devices and their NUMA affinity
+const SYSFS_PCI_DEVICES: &str = "/sys/bus/pci/devices";
+
+/// NVML's `NVML_DEVICE_MIG_ENABLE` mode
+const MIG_MODE_ENABLED: u32 = 1;
+
+/// GPU collection is not possible on this host (no NVML, no driver, no devices)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuUnavailable(pub String);
+
+impl std::fmt::Display for GpuUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GPU unavailable: {}", self.0)
+    }
+}
+
+impl std::error::Error for GpuUnavailable {}
+
 /// GPU monitoring handler
-#[derive(Debug)]
-pub struct GpuMonitor;
+#[derive(Debug, Default)]
+pub struct GpuMonitor {
+    require_gpus: bool,
+    unavailable_logged: AtomicBool,
+}
 
 impl GpuMonitor {
-    /// Create new GPU monitor
+    /// Create new GPU monitor that tolerates hosts without GPUs
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Create new GPU monitor, optionally failing when no GPU can be collected
+    pub fn with_required_gpus(require_gpus: bool) -> Self {
+        Self {
+            require_gpus,
+            unavailable_logged: AtomicBool::new(false),
+        }
     }
 
-    /// Get GPU information using NVIDIA ML
+    /// Get GPU information using NVIDIA ML.
+    ///
+    /// When GPUs are unavailable this returns an empty list (logging the
+    /// reason once) unless the monitor was configured to require GPUs, in
+    /// which case the [`GpuUnavailable`] error is returned.
     pub async fn get_gpu_info(&self) -> Result<Vec<GpuInfo>> {
-        debug!("Starting GPU detection with NVML...");
-        let mut gpus = Vec::new();
+        let detection = self.detect_gpus().await;
+        self.resolve_detection(detection)
+    }
 
-        match self.get_nvidia_device_count() {
-            Ok(device_count) => {
-                info!("NVML detected {} NVIDIA GPU(s)", device_count);
-                for i in 0..device_count {
-                    match self.get_nvidia_gpu_info(i).await {
-                        Ok(gpu_info) => {
-                            debug!("Successfully got NVML info for GPU {}", i);
-                            gpus.push(gpu_info);
-                        }
-                        Err(e) => warn!("Failed to get NVML info for GPU {}: {}", i, e),
-                    }
+    /// Apply the availability policy to a detection result
+    fn resolve_detection(
+        &self,
+        detection: std::result::Result<Vec<GpuInfo>, GpuUnavailable>,
+    ) -> Result<Vec<GpuInfo>> {
+        match detection {
+            Ok(gpus) => {
+                self.unavailable_logged.store(false, Ordering::Relaxed);
+                Ok(gpus)
+            }
+            Err(unavailable) if self.require_gpus => Err(unavailable.into()),
+            Err(unavailable) => {
+                if !self.unavailable_logged.swap(true, Ordering::Relaxed) {
+                    info!("{}; reporting zero GPUs", unavailable);
+                    debug!("This is normal in environments without NVIDIA driver access (like some containers or WSL setups)");
                 }
+                Ok(Vec::new())
             }
-            Err(e) => {
-                info!("NVML unavailable: {}", e);
-                debug!("This is normal in environments without NVIDIA driver access (like some containers or WSL setups)");
+        }
+    }
+
+    /// Detect GPUs via NVML
+    async fn detect_gpus(&self) -> std::result::Result<Vec<GpuInfo>, GpuUnavailable> {
+        debug!("Starting GPU detection with NVML...");
+
+        let device_count = self
+            .get_nvidia_device_count()
+            .map_err(|e| GpuUnavailable(format!("{e:#}")))?;
+        if device_count == 0 {
+            return Err(GpuUnavailable("NVML reported no devices".to_string()));
+        }
+
+        info!("NVML detected {} NVIDIA GPU(s)", device_count);
+        let mut gpus = Vec::new();
+        for i in 0..device_count {
+            match self.get_nvidia_gpu_info(i).await {
+                Ok(logical) => {
+                    debug!("Successfully got NVML info for GPU {}", i);
+                    gpus.extend(logical);
+                }
+                Err(e) => warn!("Failed to get NVML info for GPU {}: {}", i, e),
             }
         }
+        // Logical GPUs are numbered in order, MIG slices taking their parent's place
+        for (index, gpu) in gpus.iter_mut().enumerate() {
+            gpu.index = index as u32;
+        }
+
+        if gpus.is_empty() {
+            return Err(GpuUnavailable(format!(
+                "failed to read any of {device_count} NVML device(s)"
+            )));
+        }
 
         debug!("GPU detection completed, found {} GPUs", gpus.len());
         Ok(gpus)
@@ -55,7 +127,9 @@ impl GpuMonitor {
         Ok(device_count)
     }
 
-    async fn get_nvidia_gpu_info(&self, index: u32) -> Result<GpuInfo> {
+    /// Logical GPUs of NVML device `index`: its MIG slices when MIG is
+    /// enabled, otherwise the whole GPU
+    async fn get_nvidia_gpu_info(&self, index: u32) -> Result<Vec<GpuInfo>> {
         use nvml_wrapper::Nvml;
 
         let nvml = Nvml::init().context("Failed to initialize NVML")?;
@@ -91,29 +165,270 @@ impl GpuMonitor {
             .ok()
             .map(|v| format!("{}.{}", v / 1000, (v % 1000) / 10));
 
-        let memory_usage_percent = if memory_info.total > 0 {
-            (memory_info.used as f32 / memory_info.total as f32) * 100.0
-        } else {
-            0.0
-        };
+        let uuid = device.uuid().unwrap_or_default();
+        let pci_bus_id = device.pci_info().ok().map(|pci| pci.bus_id);
+        let numa_node = pci_bus_id
+            .as_deref()
+            .and_then(|bus_id| numa_node_for_bus_id(Path::new(SYSFS_PCI_DEVICES), bus_id));
 
-        Ok(GpuInfo {
+        let physical = GpuInfo {
             index,
             name,
             memory_total_bytes: memory_info.total,
             memory_used_bytes: memory_info.used,
-            memory_usage_percent,
+            memory_usage_percent: memory_usage_percent(memory_info.used, memory_info.total),
             utilization_percent: utilization,
             temperature_celsius: temperature,
             power_usage_watts: power_usage,
             driver_version,
             cuda_version,
+            uuid,
+            pci_bus_id,
+            numa_node,
+            mig: None,
+        };
+
+        Ok(logical_gpus(physical, read_mig_devices(&device, index)))
+    }
+}
+
+/// MIG device as read from NVML
+#[derive(Debug, Clone)]
+struct MigDevice {
+    uuid: String,
+    name: String,
+    memory_total_bytes: u64,
+    memory_used_bytes: u64,
+}
+
+/// MIG devices of a physical GPU, or `None` when MIG is disabled or unsupported
+fn read_mig_devices(device: &nvml_wrapper::Device, index: u32) -> Option<Vec<MigDevice>> {
+    let mode = device.mig_mode().ok()?;
+    if mode.current != MIG_MODE_ENABLED {
+        return None;
+    }
+
+    let max_count = match device.max_mig_device_count() {
+        Ok(count) => count,
+        Err(e) => {
+            warn!("Failed to get MIG device count for GPU {}: {}", index, e);
+            return Some(Vec::new());
+        }
+    };
+
+    let mut devices = Vec::new();
+    for mig_index in 0..max_count {
+        // Slots without a configured instance have no handle
+        let Ok(mig) = device.mig_device_by_index(mig_index) else {
+            continue;
+        };
+        match mig.memory_info() {
+            Ok(memory) => devices.push(MigDevice {
+                uuid: mig.uuid().unwrap_or_default(),
+                name: mig.name().unwrap_or_default(),
+                memory_total_bytes: memory.total,
+                memory_used_bytes: memory.used,
+            }),
+            Err(e) => warn!(
+                "Failed to get memory info for MIG device {} of GPU {}: {}",
+                mig_index, index, e
+            ),
+        }
+    }
+    Some(devices)
+}
+
+/// Split a physical GPU into the logical GPUs renters can select
+///
+/// Each MIG slice becomes a GPU of its own; with MIG disabled (`None`) the
+/// whole GPU is reported.
+fn logical_gpus(physical: GpuInfo, mig_devices: Option<Vec<MigDevice>>) -> Vec<GpuInfo> {
+    let Some(mig_devices) = mig_devices else {
+        return vec![physical];
+    };
+    if mig_devices.is_empty() {
+        warn!(
+            "GPU {} has MIG enabled but no MIG instances configured, it cannot be rented",
+            physical.index
+        );
+    }
+
+    mig_devices
+        .into_iter()
+        .filter_map(|mig| {
+            let Some(profile) = MigProfile::from_device_name(&mig.name) else {
+                warn!("Skipping MIG device with unrecognised name '{}'", mig.name);
+                return None;
+            };
+            Some(GpuInfo {
+                index: physical.index,
+                name: mig.name,
+                memory_total_bytes: mig.memory_total_bytes,
+                memory_used_bytes: mig.memory_used_bytes,
+                memory_usage_percent: memory_usage_percent(
+                    mig.memory_used_bytes,
+                    mig.memory_total_bytes,
+                ),
+                // NVML does not report utilization or power per slice
+                utilization_percent: 0.0,
+                temperature_celsius: physical.temperature_celsius,
+                power_usage_watts: 0.0,
+                driver_version: physical.driver_version.clone(),
+                cuda_version: physical.cuda_version.clone(),
+                uuid: mig.uuid,
+                pci_bus_id: physical.pci_bus_id.clone(),
+                numa_node: physical.numa_node,
+                mig: Some(MigInstance {
+                    profile,
+                    parent_index: physical.index,
+                    parent_uuid: physical.uuid.clone(),
+                }),
+            })
         })
+        .collect()
+}
+
+fn memory_usage_percent(used: u64, total: u64) -> f32 {
+    if total > 0 {
+        (used as f32 / total as f32) * 100.0
+    } else {
+        0.0
     }
 }
 
-impl Default for GpuMonitor {
-    fn default() -> Self {
-        Self::new()
+/// Convert an NVML bus ID (`00000000:3B:00.0`) to the sysfs form (`0000:3b:00.0`)
+fn sysfs_pci_address(bus_id: &str) -> Option<String> {
+    let (domain, rest) = bus_id.trim().split_once(':')?;
+    let domain = u32::from_str_radix(domain, 16).ok()?;
+    Some(format!("{:04x}:{}", domain, rest.to_ascii_lowercase()))
+}
+
+/// NUMA node of the PCI device, or `None` when the host has no NUMA topology
+fn numa_node_for_bus_id(pci_devices: &Path, bus_id: &str) -> Option<i32> {
+    let address = sysfs_pci_address(bus_id)?;
+    let contents = std::fs::read_to_string(pci_devices.join(address).join("numa_node")).ok()?;
+    // The kernel reports -1 for devices without NUMA affinity
+    contents.trim().parse().ok().filter(|node: &i32| *node >= 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nvml_missing() -> std::result::Result<Vec<GpuInfo>, GpuUnavailable> {
+        Err(GpuUnavailable(
+            "Failed to initialize NVML: a libloading error occurred".to_string(),
+        ))
+    }
+
+    #[test]
+    fn test_nvml_unavailable_reports_zero_gpus() {
+        let monitor = GpuMonitor::new();
+
+        let gpus = monitor.resolve_detection(nvml_missing()).unwrap();
+        assert!(gpus.is_empty());
+        assert!(monitor.unavailable_logged.load(Ordering::Relaxed));
+
+        // Subsequent failures are still tolerated without re-logging
+        assert!(monitor
+            .resolve_detection(nvml_missing())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_numa_node_is_read_from_sysfs() {
+        let dir = tempfile::tempdir().unwrap();
+        let device = dir.path().join("0000:3b:00.0");
+        std::fs::create_dir(&device).unwrap();
+        std::fs::write(device.join("numa_node"), "1\n").unwrap();
+        let unaffined = dir.path().join("0000:af:00.0");
+        std::fs::create_dir(&unaffined).unwrap();
+        std::fs::write(unaffined.join("numa_node"), "-1\n").unwrap();
+
+        assert_eq!(
+            sysfs_pci_address("00000000:3B:00.0").as_deref(),
+            Some("0000:3b:00.0")
+        );
+        assert_eq!(
+            numa_node_for_bus_id(dir.path(), "00000000:3B:00.0"),
+            Some(1)
+        );
+        assert_eq!(numa_node_for_bus_id(dir.path(), "00000000:AF:00.0"), None);
+        assert_eq!(numa_node_for_bus_id(dir.path(), "00000000:18:00.0"), None);
+    }
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn a100(index: u32) -> GpuInfo {
+        GpuInfo {
+            index,
+            name: "NVIDIA A100-SXM4-80GB".to_string(),
+            memory_total_bytes: 80 * GIB,
+            memory_used_bytes: 0,
+            memory_usage_percent: 0.0,
+            utilization_percent: 40.0,
+            temperature_celsius: 45.0,
+            power_usage_watts: 250.0,
+            driver_version: "550.54.15".to_string(),
+            cuda_version: Some("12.4".to_string()),
+            uuid: format!("GPU-a100-{index}"),
+            pci_bus_id: Some("00000000:3B:00.0".to_string()),
+            numa_node: Some(0),
+            mig: None,
+        }
+    }
+
+    fn mig_device(uuid: &str, profile: &str, memory_gb: u64) -> MigDevice {
+        MigDevice {
+            uuid: uuid.to_string(),
+            name: format!("NVIDIA A100-SXM4-80GB MIG {profile}"),
+            memory_total_bytes: memory_gb * GIB,
+            memory_used_bytes: GIB,
+        }
+    }
+
+    #[test]
+    fn test_mig_instances_reported_as_logical_gpus() {
+        let instances = vec![
+            mig_device("MIG-aaaa", "3g.40gb", 40),
+            mig_device("MIG-bbbb", "1g.10gb", 10),
+            mig_device("MIG-cccc", "1g.10gb", 10),
+        ];
+
+        let gpus = logical_gpus(a100(1), Some(instances));
+
+        assert_eq!(gpus.len(), 3);
+        assert_eq!(gpus[0].uuid, "MIG-aaaa");
+        assert_eq!(gpus[0].memory_total_bytes, 40 * GIB);
+        assert_eq!(gpus[0].numa_node, Some(0));
+        let mig = gpus[0].mig.as_ref().unwrap();
+        assert_eq!(mig.profile.to_string(), "3g.40gb");
+        assert_eq!(mig.parent_index, 1);
+        assert_eq!(mig.parent_uuid, "GPU-a100-1");
+        assert!(gpus[1..]
+            .iter()
+            .all(|gpu| gpu.mig.as_ref().unwrap().profile.to_string() == "1g.10gb"));
+        assert_eq!(gpus[1].memory_usage_percent, 10.0);
+    }
+
+    #[test]
+    fn test_whole_gpu_reported_when_mig_disabled() {
+        let gpus = logical_gpus(a100(0), None);
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].uuid, "GPU-a100-0");
+        assert!(gpus[0].mig.is_none());
+
+        // MIG enabled without instances leaves nothing to rent
+        assert!(logical_gpus(a100(0), Some(Vec::new())).is_empty());
+    }
+
+    #[test]
+    fn test_nvml_unavailable_fails_when_gpus_required() {
+        let monitor = GpuMonitor::with_required_gpus(true);
+
+        let err = monitor.resolve_detection(nvml_missing()).unwrap_err();
+        let unavailable = err.downcast_ref::<GpuUnavailable>().unwrap();
+        assert!(unavailable.0.contains("NVML"));
     }
 }
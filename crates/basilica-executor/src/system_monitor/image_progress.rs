@@ -0,0 +1,136 @@
+//! Image pull progress on the telemetry stream
+//!
+//! Docker reports progress for every downloaded chunk, far more often than
+//! billing needs it. Updates are throttled per image; the final update of a
+//! pull always goes out so consumers see it finish.
+
+use super::stream::ts_now;
+use super::telemetry_queue::{PushOutcome, TelemetrySender};
+use crate::container_manager::images::ImagePullProgress;
+use basilica_protocol::billing::TelemetryData;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+/// Minimum time between two progress samples for the same image
+pub const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Decides which progress updates are worth a telemetry sample
+#[derive(Debug)]
+pub struct ProgressThrottle {
+    interval: Duration,
+    last_sent: HashMap<String, Instant>,
+}
+
+impl ProgressThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_sent: HashMap::new(),
+        }
+    }
+
+    /// Whether `progress`, observed at `now`, should be sent
+    pub fn should_send(&mut self, progress: &ImagePullProgress, now: Instant) -> bool {
+        if progress.is_complete() {
+            self.last_sent.remove(&progress.image);
+            return true;
+        }
+
+        match self.last_sent.get(&progress.image) {
+            Some(last) if now.duration_since(*last) < self.interval => false,
+            _ => {
+                self.last_sent.insert(progress.image.clone(), now);
+                true
+            }
+        }
+    }
+}
+
+/// Forward pull progress onto the billing telemetry queue until the image
+/// cache goes away
+pub async fn forward(
+    mut progress_rx: broadcast::Receiver<ImagePullProgress>,
+    executor_id: String,
+    telemetry_tx: TelemetrySender<TelemetryData>,
+) {
+    let mut throttle = ProgressThrottle::new(PROGRESS_INTERVAL);
+
+    loop {
+        let progress = match progress_rx.recv().await {
+            Ok(progress) => progress,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                debug!("Skipped {} image pull progress updates", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+
+        if !throttle.should_send(&progress, Instant::now()) {
+            continue;
+        }
+
+        let outcome = telemetry_tx
+            .push(progress.to_telemetry(&executor_id, ts_now()))
+            .await;
+        if outcome.is_drop() || outcome == PushOutcome::Closed {
+            warn!(
+                "Failed to queue pull progress for {}: {:?}",
+                progress.image, outcome
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(layers_done: usize, layers_total: usize) -> ImagePullProgress {
+        ImagePullProgress {
+            image: "nvidia/cuda:12.2.0-base-ubuntu22.04".to_string(),
+            layers_total,
+            layers_done,
+            bytes_done: layers_done as u64 * 1024,
+            bytes_total: layers_total as u64 * 1024,
+        }
+    }
+
+    #[test]
+    fn test_updates_are_throttled_per_image() {
+        let mut throttle = ProgressThrottle::new(Duration::from_secs(1));
+        let start = Instant::now();
+
+        assert!(throttle.should_send(&progress(0, 3), start));
+        assert!(!throttle.should_send(&progress(1, 3), start + Duration::from_millis(500)));
+        assert!(throttle.should_send(&progress(1, 3), start + Duration::from_secs(1)));
+
+        let mut other = progress(0, 3);
+        other.image = "ubuntu:22.04".to_string();
+        assert!(throttle.should_send(&other, start + Duration::from_millis(1100)));
+    }
+
+    #[test]
+    fn test_completion_is_always_sent() {
+        let mut throttle = ProgressThrottle::new(Duration::from_secs(1));
+        let start = Instant::now();
+
+        assert!(throttle.should_send(&progress(2, 3), start));
+        assert!(throttle.should_send(&progress(3, 3), start));
+    }
+
+    #[test]
+    fn test_progress_telemetry_names_the_image() {
+        let telemetry = progress(1, 2).to_telemetry("executor-1", ts_now());
+
+        assert_eq!(telemetry.executor_id, "executor-1");
+        assert!(telemetry.rental_id.is_empty());
+        assert_eq!(
+            telemetry.labels.get("image").map(String::as_str),
+            Some("nvidia/cuda:12.2.0-base-ubuntu22.04")
+        );
+        assert_eq!(telemetry.custom_metrics["image_pull.bytes_done"], 1024.0);
+        assert_eq!(telemetry.custom_metrics["image_pull.layers_total"], 2.0);
+    }
+}
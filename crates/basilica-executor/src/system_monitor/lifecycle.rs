@@ -1,9 +1,15 @@
 // Container lifecycle management module
-// Only handles lifecycle status updates, not monitoring
+// Only handles lifecycle status updates and transition events, not monitoring
 
 use super::docker_utils;
-use basilica_protocol::billing::RentalStatus;
+use super::stream::ts_now;
+use super::telemetry_queue::{PushOutcome, TelemetrySender};
+use basilica_protocol::billing::{
+    ContainerState as ProtoContainerState, LifecycleEvent, RentalStatus, TelemetryData,
+};
 use bollard::container::ListContainersOptions;
+use bollard::models::ContainerSummary;
+use prost_types::Timestamp;
 use std::collections::HashMap;
 use tracing::{info, warn};
 
@@ -23,6 +29,150 @@ pub struct LifecycleConfig {
     pub enabled: bool,
 }
 
+/// Coarse container state as seen by billing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerState {
+    Created,
+    Running,
+    Paused,
+    Exited,
+}
+
+impl ContainerState {
+    /// Map a Docker state string (`created`, `running`, `exited`, ...)
+    pub fn from_docker(state: &str) -> Option<Self> {
+        match state {
+            "created" => Some(Self::Created),
+            "running" | "restarting" => Some(Self::Running),
+            "paused" => Some(Self::Paused),
+            "exited" | "dead" | "removing" => Some(Self::Exited),
+            _ => None,
+        }
+    }
+}
+
+impl From<ContainerState> for ProtoContainerState {
+    fn from(state: ContainerState) -> Self {
+        match state {
+            ContainerState::Created => Self::Created,
+            ContainerState::Running => Self::Running,
+            ContainerState::Paused => Self::Paused,
+            ContainerState::Exited => Self::Exited,
+        }
+    }
+}
+
+/// A container state transition, emitted onto the telemetry stream so
+/// billing can start charging at `Running` and stop at `Exited`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerLifecycleEvent {
+    pub container_id: String,
+    pub rental_id: String,
+    pub from: ContainerState,
+    pub to: ContainerState,
+    pub timestamp: Timestamp,
+}
+
+impl ContainerLifecycleEvent {
+    /// Wrap the event in a telemetry sample for the billing stream
+    pub fn to_telemetry(&self, executor_id: &str) -> TelemetryData {
+        TelemetryData {
+            rental_id: self.rental_id.clone(),
+            executor_id: executor_id.to_string(),
+            timestamp: Some(self.timestamp.clone()),
+            resource_usage: None,
+            custom_metrics: HashMap::new(),
+            lifecycle_event: Some(LifecycleEvent {
+                container_id: self.container_id.clone(),
+                rental_id: self.rental_id.clone(),
+                from_state: ProtoContainerState::from(self.from) as i32,
+                to_state: ProtoContainerState::from(self.to) as i32,
+                timestamp: Some(self.timestamp.clone()),
+            }),
+            labels: HashMap::new(),
+        }
+    }
+}
+
+/// Last observed state of a tracked container
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TrackedContainer {
+    rental_id: String,
+    state: ContainerState,
+}
+
+/// Tracks container states between polls and derives transitions
+#[derive(Debug, Default)]
+pub struct LifecycleTracker {
+    containers: HashMap<String, TrackedContainer>,
+}
+
+impl LifecycleTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the current states without emitting transitions
+    pub fn seed(&mut self, observed: impl IntoIterator<Item = (String, String, ContainerState)>) {
+        self.containers = observed
+            .into_iter()
+            .map(|(container_id, rental_id, state)| {
+                (container_id, TrackedContainer { rental_id, state })
+            })
+            .collect();
+    }
+
+    /// Apply a poll of `(container_id, rental_id, state)` and return the
+    /// transitions since the previous poll. A container that disappears
+    /// while not yet exited is reported as exited.
+    pub fn observe(
+        &mut self,
+        observed: impl IntoIterator<Item = (String, String, ContainerState)>,
+        timestamp: Timestamp,
+    ) -> Vec<ContainerLifecycleEvent> {
+        let mut current = HashMap::new();
+        let mut events = Vec::new();
+
+        for (container_id, rental_id, state) in observed {
+            let previous = self.containers.get(&container_id).map(|c| c.state);
+            let from = match previous {
+                Some(from) if from != state => Some(from),
+                Some(_) => None,
+                // Containers first seen already past creation still get a
+                // transition so billing learns when they started running
+                None if state != ContainerState::Created => Some(ContainerState::Created),
+                None => None,
+            };
+
+            if let Some(from) = from {
+                events.push(ContainerLifecycleEvent {
+                    container_id: container_id.clone(),
+                    rental_id: rental_id.clone(),
+                    from,
+                    to: state,
+                    timestamp: timestamp.clone(),
+                });
+            }
+            current.insert(container_id, TrackedContainer { rental_id, state });
+        }
+
+        for (container_id, tracked) in &self.containers {
+            if !current.contains_key(container_id) && tracked.state != ContainerState::Exited {
+                events.push(ContainerLifecycleEvent {
+                    container_id: container_id.clone(),
+                    rental_id: tracked.rental_id.clone(),
+                    from: tracked.state,
+                    to: ContainerState::Exited,
+                    timestamp: timestamp.clone(),
+                });
+            }
+        }
+
+        self.containers = current;
+        events
+    }
+}
+
 /// Check if container should be tracked
 fn should_track(labels: &Option<HashMap<String, String>>) -> bool {
     if let Some(labels) = labels {
@@ -45,10 +195,26 @@ fn get_entity_id(labels: &Option<HashMap<String, String>>) -> Option<String> {
     })
 }
 
-/// Manage container lifecycle status updates
+/// Tracked containers as `(container_id, entity_id, state)`
+fn tracked_states(containers: Vec<ContainerSummary>) -> Vec<(String, String, ContainerState)> {
+    containers
+        .into_iter()
+        .filter(|container| should_track(&container.labels))
+        .filter_map(|container| {
+            let entity_id = get_entity_id(&container.labels)?;
+            let state = ContainerState::from_docker(container.state.as_deref()?)?;
+            Some((container.id?, entity_id, state))
+        })
+        .collect()
+}
+
+/// Manage container lifecycle status updates and emit transition events
+/// onto the telemetry stream
 pub async fn run(
     cfg: LifecycleConfig,
     stream_cfg: super::stream::StreamConfig,
+    executor_id: String,
+    events_tx: TelemetrySender<TelemetryData>,
 ) -> anyhow::Result<()> {
     if !cfg.enabled {
         info!("Container lifecycle management disabled");
@@ -56,80 +222,65 @@ pub async fn run(
     }
 
     let docker = docker_utils::connect_docker(&cfg.docker_host).await?;
+    let list_options = || {
+        Some(ListContainersOptions::<String> {
+            all: true,
+            ..Default::default()
+        })
+    };
 
-    let mut previous_containers = std::collections::HashSet::new();
+    let mut tracker = LifecycleTracker::new();
+    let initial = tracked_states(docker.list_containers(list_options()).await?);
 
-    let containers = docker
-        .list_containers(Some(ListContainersOptions::<String> {
-            all: false,
-            ..Default::default()
-        }))
-        .await?;
-
-    for container in containers {
-        if should_track(&container.labels) {
-            if let Some(entity_id) = get_entity_id(&container.labels) {
-                previous_containers.insert(entity_id.clone());
-                update_status(
-                    &stream_cfg,
-                    &entity_id,
-                    RentalStatus::Active,
-                    "initial_scan",
-                )
-                .await;
-            }
+    for (_, entity_id, state) in &initial {
+        if *state == ContainerState::Running {
+            update_status(&stream_cfg, entity_id, RentalStatus::Active, "initial_scan").await;
         }
     }
+    tracker.seed(initial);
 
     // Main lifecycle tracking loop
     loop {
         tokio::time::sleep(std::time::Duration::from_secs(cfg.check_interval_secs)).await;
 
-        let containers = docker
-            .list_containers(Some(ListContainersOptions::<String> {
-                all: false,
-                ..Default::default()
-            }))
-            .await?;
-
-        let mut current_containers = std::collections::HashSet::new();
-
-        // Check for new containers
-        for container in containers {
-            if should_track(&container.labels) {
-                if let Some(entity_id) = get_entity_id(&container.labels) {
-                    current_containers.insert(entity_id.clone());
-
-                    if !previous_containers.contains(&entity_id) {
-                        info!("New container detected: {}", entity_id);
-                        update_status(
-                            &stream_cfg,
-                            &entity_id,
-                            RentalStatus::Active,
-                            "container_started",
-                        )
-                        .await;
-                    }
+        let observed = tracked_states(docker.list_containers(list_options()).await?);
+
+        for event in tracker.observe(observed, ts_now()) {
+            info!(
+                "Container {} ({}) {:?} -> {:?}",
+                event.container_id, event.rental_id, event.from, event.to
+            );
+
+            match event.to {
+                ContainerState::Running => {
+                    update_status(
+                        &stream_cfg,
+                        &event.rental_id,
+                        RentalStatus::Active,
+                        "container_started",
+                    )
+                    .await
                 }
+                ContainerState::Exited => {
+                    update_status(
+                        &stream_cfg,
+                        &event.rental_id,
+                        RentalStatus::Stopped,
+                        "container_stopped",
+                    )
+                    .await
+                }
+                ContainerState::Created | ContainerState::Paused => {}
             }
-        }
 
-        // Check for stopped containers
-        for entity_id in &previous_containers {
-            if !current_containers.contains(entity_id) {
-                info!("Container stopped: {}", entity_id);
-
-                update_status(
-                    &stream_cfg,
-                    entity_id,
-                    RentalStatus::Stopped,
-                    "container_stopped",
-                )
-                .await;
+            let outcome = events_tx.push(event.to_telemetry(&executor_id)).await;
+            if outcome.is_drop() || outcome == PushOutcome::Closed {
+                warn!(
+                    "Failed to queue lifecycle event for {}: {:?}",
+                    event.container_id, outcome
+                );
             }
         }
-
-        previous_containers = current_containers;
     }
 }
 
@@ -145,3 +296,79 @@ async fn update_status(
         warn!("Failed to update lifecycle status for {}: {}", entity_id, e);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(seconds: i64) -> Timestamp {
+        Timestamp { seconds, nanos: 0 }
+    }
+
+    fn observed(state: ContainerState) -> Vec<(String, String, ContainerState)> {
+        vec![("c1".to_string(), "rental-1".to_string(), state)]
+    }
+
+    #[test]
+    fn test_created_running_exited_transitions() {
+        let mut tracker = LifecycleTracker::new();
+
+        assert!(tracker
+            .observe(observed(ContainerState::Created), ts(1))
+            .is_empty());
+
+        let started = tracker.observe(observed(ContainerState::Running), ts(2));
+        assert_eq!(
+            started,
+            vec![ContainerLifecycleEvent {
+                container_id: "c1".to_string(),
+                rental_id: "rental-1".to_string(),
+                from: ContainerState::Created,
+                to: ContainerState::Running,
+                timestamp: ts(2),
+            }]
+        );
+
+        assert!(tracker
+            .observe(observed(ContainerState::Running), ts(3))
+            .is_empty());
+
+        let stopped = tracker.observe(observed(ContainerState::Exited), ts(4));
+        assert_eq!(stopped.len(), 1);
+        assert_eq!(stopped[0].from, ContainerState::Running);
+        assert_eq!(stopped[0].to, ContainerState::Exited);
+
+        let telemetry = stopped[0].to_telemetry("executor-1");
+        let event = telemetry.lifecycle_event.unwrap();
+        assert_eq!(telemetry.rental_id, "rental-1");
+        assert_eq!(event.from_state, ProtoContainerState::Running as i32);
+        assert_eq!(event.to_state, ProtoContainerState::Exited as i32);
+        assert_eq!(event.timestamp, Some(ts(4)));
+    }
+
+    #[test]
+    fn test_removed_container_reported_as_exited() {
+        let mut tracker = LifecycleTracker::new();
+        tracker.seed(observed(ContainerState::Running));
+
+        let events = tracker.observe(Vec::new(), ts(5));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].from, ContainerState::Running);
+        assert_eq!(events[0].to, ContainerState::Exited);
+
+        assert!(tracker.observe(Vec::new(), ts(6)).is_empty());
+    }
+
+    #[test]
+    fn test_docker_state_mapping() {
+        assert_eq!(
+            ContainerState::from_docker("running"),
+            Some(ContainerState::Running)
+        );
+        assert_eq!(
+            ContainerState::from_docker("dead"),
+            Some(ContainerState::Exited)
+        );
+        assert_eq!(ContainerState::from_docker("unknown"), None);
+    }
+}
@@ -1,33 +1,114 @@
 //! Memory monitoring functionality
+//!
+//! When the executor runs inside a container, `sysinfo` reports the host's memory rather
+//! than what the container may actually use. If a cgroup v2 or v1 memory limit is found,
+//! usage and the effective total are taken from the cgroup instead.
 
 use super::types::MemoryInfo;
 use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
 use sysinfo::System;
 
+/// Default mount point of the cgroup filesystem
+const DEFAULT_CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Memory limit and usage read from the executor's own cgroup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CgroupMemory {
+    pub limit_bytes: u64,
+    pub usage_bytes: u64,
+}
+
 /// Memory monitoring handler
 #[derive(Debug)]
-pub struct MemoryMonitor;
+pub struct MemoryMonitor {
+    cgroup_root: PathBuf,
+}
 
 impl MemoryMonitor {
     /// Create new memory monitor
     pub fn new() -> Self {
-        Self
+        Self::with_cgroup_root(DEFAULT_CGROUP_ROOT)
+    }
+
+    /// Create a memory monitor that reads cgroup files below `cgroup_root`
+    pub fn with_cgroup_root(cgroup_root: impl Into<PathBuf>) -> Self {
+        Self {
+            cgroup_root: cgroup_root.into(),
+        }
     }
 
     /// Get memory information
     pub fn get_memory_info(&self, system: &System) -> Result<MemoryInfo> {
-        let total = system.total_memory();
-        let used = system.used_memory();
-        let available = system.available_memory();
-        let usage_percent = (used as f32 / total as f32) * 100.0;
+        Ok(self.build_memory_info(
+            system.total_memory(),
+            system.used_memory(),
+            system.available_memory(),
+            system.total_swap(),
+            system.used_swap(),
+        ))
+    }
+
+    fn build_memory_info(
+        &self,
+        host_total: u64,
+        host_used: u64,
+        host_available: u64,
+        swap_total: u64,
+        swap_used: u64,
+    ) -> MemoryInfo {
+        let (effective_limit, used, available) = match self.read_cgroup_memory(host_total) {
+            Some(cgroup) => (
+                cgroup.limit_bytes,
+                cgroup.usage_bytes,
+                cgroup.limit_bytes.saturating_sub(cgroup.usage_bytes),
+            ),
+            None => (host_total, host_used, host_available),
+        };
+
+        let usage_percent = if effective_limit > 0 {
+            (used as f32 / effective_limit as f32) * 100.0
+        } else {
+            0.0
+        };
 
-        Ok(MemoryInfo {
-            total_bytes: total,
+        MemoryInfo {
+            total_bytes: effective_limit,
             used_bytes: used,
             available_bytes: available,
             usage_percent,
-            swap_total_bytes: system.total_swap(),
-            swap_used_bytes: system.used_swap(),
+            swap_total_bytes: swap_total,
+            swap_used_bytes: swap_used,
+            host_total_bytes: host_total,
+            effective_limit_bytes: effective_limit,
+        }
+    }
+
+    /// Read the cgroup memory limit, trying cgroup v2 before v1.
+    ///
+    /// Returns `None` when no limit is set or the limit is not below `host_total`,
+    /// in which case host figures are the accurate ones.
+    pub fn read_cgroup_memory(&self, host_total: u64) -> Option<CgroupMemory> {
+        let v2 = (
+            self.cgroup_root.join("memory.max"),
+            self.cgroup_root.join("memory.current"),
+        );
+        let v1 = (
+            self.cgroup_root.join("memory/memory.limit_in_bytes"),
+            self.cgroup_root.join("memory/memory.usage_in_bytes"),
+        );
+
+        [v2, v1].iter().find_map(|(limit_path, usage_path)| {
+            let limit_bytes = read_limit(limit_path)?;
+            if limit_bytes == 0 || (host_total > 0 && limit_bytes >= host_total) {
+                return None;
+            }
+            let usage_bytes = read_u64(usage_path)?;
+            Some(CgroupMemory {
+                limit_bytes,
+                usage_bytes,
+            })
         })
     }
 }
@@ -37,3 +118,94 @@ impl Default for MemoryMonitor {
         Self::new()
     }
 }
+
+/// Read a cgroup limit file; `max` (v2) means unlimited
+fn read_limit(path: &Path) -> Option<u64> {
+    let contents = fs::read_to_string(path).ok()?;
+    match contents.trim() {
+        "max" => None,
+        value => value.parse().ok(),
+    }
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_cgroup_v2_limit_is_used_as_effective_total() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("memory.max"), format!("{}\n", 4 * GIB)).unwrap();
+        fs::write(dir.path().join("memory.current"), format!("{}\n", GIB)).unwrap();
+
+        let monitor = MemoryMonitor::with_cgroup_root(dir.path());
+        let info = monitor.build_memory_info(64 * GIB, 48 * GIB, 16 * GIB, 0, 0);
+
+        assert_eq!(info.host_total_bytes, 64 * GIB);
+        assert_eq!(info.effective_limit_bytes, 4 * GIB);
+        assert_eq!(info.total_bytes, 4 * GIB);
+        assert_eq!(info.used_bytes, GIB);
+        assert_eq!(info.available_bytes, 3 * GIB);
+        assert_eq!(info.usage_percent, 25.0);
+    }
+
+    #[test]
+    fn test_cgroup_v1_limit_is_used_as_effective_total() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("memory")).unwrap();
+        fs::write(
+            dir.path().join("memory/memory.limit_in_bytes"),
+            (8 * GIB).to_string(),
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("memory/memory.usage_in_bytes"),
+            (6 * GIB).to_string(),
+        )
+        .unwrap();
+
+        let monitor = MemoryMonitor::with_cgroup_root(dir.path());
+        let info = monitor.build_memory_info(64 * GIB, 10 * GIB, 54 * GIB, 0, 0);
+
+        assert_eq!(info.effective_limit_bytes, 8 * GIB);
+        assert_eq!(info.used_bytes, 6 * GIB);
+        assert_eq!(info.usage_percent, 75.0);
+    }
+
+    #[test]
+    fn test_unlimited_cgroup_falls_back_to_host() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("memory.max"), "max\n").unwrap();
+        fs::write(dir.path().join("memory.current"), GIB.to_string()).unwrap();
+        fs::create_dir(dir.path().join("memory")).unwrap();
+        // cgroup v1 reports "no limit" as a huge page-aligned number
+        fs::write(
+            dir.path().join("memory/memory.limit_in_bytes"),
+            "9223372036854771712",
+        )
+        .unwrap();
+        fs::write(dir.path().join("memory/memory.usage_in_bytes"), "0").unwrap();
+
+        let monitor = MemoryMonitor::with_cgroup_root(dir.path());
+        let info = monitor.build_memory_info(64 * GIB, 16 * GIB, 48 * GIB, 0, 0);
+
+        assert_eq!(info.host_total_bytes, 64 * GIB);
+        assert_eq!(info.effective_limit_bytes, 64 * GIB);
+        assert_eq!(info.used_bytes, 16 * GIB);
+        assert_eq!(info.usage_percent, 25.0);
+    }
+
+    #[test]
+    fn test_missing_cgroup_files_fall_back_to_host() {
+        let dir = tempfile::tempdir().unwrap();
+        let monitor = MemoryMonitor::with_cgroup_root(dir.path());
+
+        assert_eq!(monitor.read_cgroup_memory(64 * GIB), None);
+    }
+}
@@ -1,8 +1,12 @@
 use super::types::{ContainerMetrics, GpuMetrics, SystemMetrics, VolumeMetrics};
+use basilica_common::metrics::traits::MetricsRecorder;
 use basilica_protocol::billing::{GpuUsage as BillingGpuUsage, ResourceUsage, TelemetryData};
 use prost_types::Timestamp;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
 
 /// Metrics that can be converted to both Prometheus and Telemetry formats
 #[derive(Debug, Clone)]
@@ -70,6 +74,8 @@ impl Metrics {
             timestamp,
             resource_usage,
             custom_metrics,
+            lifecycle_event: None,
+            labels: HashMap::new(),
         }
     }
 
@@ -92,6 +98,39 @@ impl Metrics {
             custom_metrics.insert("host.load15".to_string(), sys.load_average.2);
             custom_metrics.insert("host.net_rx_bytes".to_string(), sys.network_rx_bytes as f64);
             custom_metrics.insert("host.net_tx_bytes".to_string(), sys.network_tx_bytes as f64);
+            for interface in &sys.network_interfaces {
+                custom_metrics.insert(
+                    format!("host.net.{}.rx_mbps", interface.name),
+                    interface.rx_mbps,
+                );
+                custom_metrics.insert(
+                    format!("host.net.{}.tx_mbps", interface.name),
+                    interface.tx_mbps,
+                );
+                custom_metrics.insert(
+                    format!("host.net.{}.billed", interface.name),
+                    if interface.billed { 1.0 } else { 0.0 },
+                );
+            }
+            custom_metrics.insert(
+                "host.disk_read_bytes_per_sec".to_string(),
+                sys.disk_usage.iter().map(|d| d.io.read_bytes_per_sec).sum(),
+            );
+            custom_metrics.insert(
+                "host.disk_write_bytes_per_sec".to_string(),
+                sys.disk_usage
+                    .iter()
+                    .map(|d| d.io.write_bytes_per_sec)
+                    .sum(),
+            );
+            custom_metrics.insert(
+                "host.disk_read_iops".to_string(),
+                sys.disk_usage.iter().map(|d| d.io.read_iops).sum(),
+            );
+            custom_metrics.insert(
+                "host.disk_write_iops".to_string(),
+                sys.disk_usage.iter().map(|d| d.io.write_iops).sum(),
+            );
         }
 
         TelemetryData {
@@ -100,6 +139,8 @@ impl Metrics {
             timestamp,
             resource_usage: None,
             custom_metrics,
+            lifecycle_event: None,
+            labels: HashMap::new(),
         }
     }
 }
@@ -107,3 +148,100 @@ impl Metrics {
 /// Channel type for broadcasting metrics
 pub type MetricsChannel = tokio::sync::broadcast::Sender<Metrics>;
 pub type MetricsReceiver = tokio::sync::broadcast::Receiver<Metrics>;
+
+/// Receive the next metrics sample for `consumer`.
+///
+/// A consumer that falls behind the collector misses the oldest samples; the
+/// skipped count is logged and recorded, and it carries on from the oldest
+/// sample still buffered. Returns `None` once the collector has shut down.
+pub async fn recv_metrics(
+    rx: &mut MetricsReceiver,
+    consumer: &str,
+    recorder: Option<&Arc<dyn MetricsRecorder>>,
+) -> Option<Metrics> {
+    loop {
+        match rx.recv().await {
+            Ok(metrics) => return Some(metrics),
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(
+                    "Metrics consumer '{}' fell behind, skipped {} samples",
+                    consumer, skipped
+                );
+                if let Some(recorder) = recorder {
+                    recorder
+                        .record_counter(
+                            "executor_metrics_consumer_lagged_samples_total",
+                            skipped,
+                            &[("consumer", consumer)],
+                        )
+                        .await;
+                }
+            }
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use basilica_common::metrics::traits::MetricTimer;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Default)]
+    struct LagCounter(AtomicU64);
+
+    #[async_trait]
+    impl MetricsRecorder for LagCounter {
+        async fn record_counter(&self, _name: &str, value: u64, _labels: &[(&str, &str)]) {
+            self.0.fetch_add(value, Ordering::SeqCst);
+        }
+
+        async fn record_histogram(&self, _name: &str, _value: f64, _labels: &[(&str, &str)]) {}
+
+        async fn record_gauge(&self, _name: &str, _value: f64, _labels: &[(&str, &str)]) {}
+
+        fn start_timer(&self, name: &str, labels: Vec<(&str, &str)>) -> MetricTimer {
+            MetricTimer::new(name.to_string(), labels)
+        }
+    }
+
+    fn sample(executor_id: &str) -> Metrics {
+        Metrics {
+            timestamp: SystemTime::now(),
+            executor_id: executor_id.to_string(),
+            system_metrics: None,
+            container_metrics: Vec::new(),
+            gpu_metrics: Vec::new(),
+            volume_metrics: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lagging_consumer_keeps_receiving() {
+        let (tx, mut rx) = tokio::sync::broadcast::channel(2);
+        let counter = Arc::new(LagCounter::default());
+        let recorder: Arc<dyn MetricsRecorder> = counter.clone();
+
+        // Overflow the buffer before the consumer reads anything
+        for i in 0..5 {
+            tx.send(sample(&format!("sample-{i}"))).unwrap();
+        }
+
+        let next = recv_metrics(&mut rx, "test", Some(&recorder)).await;
+        assert_eq!(next.unwrap().executor_id, "sample-3");
+        assert_eq!(counter.0.load(Ordering::SeqCst), 3);
+
+        let next = recv_metrics(&mut rx, "test", Some(&recorder)).await;
+        assert_eq!(next.unwrap().executor_id, "sample-4");
+
+        // New samples still arrive after the lag
+        tx.send(sample("sample-5")).unwrap();
+        let next = recv_metrics(&mut rx, "test", None).await;
+        assert_eq!(next.unwrap().executor_id, "sample-5");
+
+        drop(tx);
+        assert!(recv_metrics(&mut rx, "test", None).await.is_none());
+    }
+}
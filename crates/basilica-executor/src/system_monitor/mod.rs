@@ -6,12 +6,16 @@ pub mod collector;
 pub mod cpu;
 pub mod disk;
 pub mod docker_utils;
+pub mod energy;
 pub mod gpu;
+pub mod image_progress;
 pub mod lifecycle;
 pub mod memory;
 pub mod metrics;
 pub mod network;
+pub mod spool;
 pub mod stream;
+pub mod telemetry_queue;
 pub mod types;
 pub mod volumes;
 
@@ -27,7 +31,8 @@ use disk::DiskMonitor;
 use gpu::GpuMonitor;
 use memory::MemoryMonitor;
 use network::NetworkMonitor;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use sysinfo::System;
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
@@ -37,7 +42,7 @@ pub use types::*;
 
 /// System monitoring service
 pub struct SystemMonitor {
-    config: SystemConfig,
+    config: RwLock<SystemConfig>,
     system: System,
     cpu_monitor: CpuMonitor,
     memory_monitor: MemoryMonitor,
@@ -53,14 +58,19 @@ impl SystemMonitor {
         let mut system = System::new_all();
         system.refresh_all();
 
+        let gpu_monitor = GpuMonitor::with_required_gpus(config.require_gpus);
+        let network_monitor = NetworkMonitor::with_billed_interfaces(
+            config.telemetry_monitor.billed_interfaces.clone(),
+        );
+
         Ok(Self {
-            config,
+            config: RwLock::new(config),
             system,
             cpu_monitor: CpuMonitor::new(),
             memory_monitor: MemoryMonitor::new(),
-            gpu_monitor: GpuMonitor::new(),
+            gpu_monitor,
             disk_monitor: DiskMonitor::new(),
-            network_monitor: NetworkMonitor::new(),
+            network_monitor,
             metrics_recorder: None,
         })
     }
@@ -80,18 +90,50 @@ impl SystemMonitor {
         self.metrics_recorder = Some(recorder);
     }
 
+    /// Snapshot of the active monitoring configuration
+    pub fn config(&self) -> SystemConfig {
+        match self.config.read() {
+            Ok(config) => config.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Replace the monitoring configuration at runtime.
+    ///
+    /// Thresholds, the update interval and metrics recording take effect on
+    /// the next check. GPU requirements and network interface selection are
+    /// fixed when the monitor is created.
+    pub fn update_config(&self, config: SystemConfig) {
+        match self.config.write() {
+            Ok(mut current) => *current = config,
+            Err(poisoned) => *poisoned.into_inner() = config,
+        }
+    }
+
     /// Start monitoring loop
     pub async fn start_monitoring(&mut self) -> Result<()> {
+        let mut update_interval = self.config().update_interval;
         info!(
             "Starting system monitoring with interval: {}s",
-            self.config.update_interval.as_secs()
+            update_interval.as_secs()
         );
 
-        let mut interval = interval(self.config.update_interval);
+        let mut interval = interval(update_interval);
 
         loop {
             interval.tick().await;
 
+            let configured_interval = self.config().update_interval;
+            if configured_interval != update_interval {
+                info!(
+                    "System monitoring interval changed to {}s",
+                    configured_interval.as_secs()
+                );
+                update_interval = configured_interval;
+                interval = tokio::time::interval(update_interval);
+                interval.tick().await;
+            }
+
             if let Err(e) = self.update_system_info().await {
                 error!("Failed to update system info: {}", e);
             }
@@ -109,7 +151,7 @@ impl SystemMonitor {
 
     /// Record system metrics to recorder
     async fn record_system_metrics(&self) -> Result<()> {
-        if self.config.enable_metrics_recording && self.metrics_recorder.is_some() {
+        if self.config().enable_metrics_recording && self.metrics_recorder.is_some() {
             let recorder = self.metrics_recorder.as_ref().unwrap();
             let system_info = self.get_system_info().await?;
 
@@ -188,7 +230,7 @@ impl SystemMonitor {
         self.network_monitor.refresh();
 
         // Check if we need to collect GPU info
-        if self.config.enable_gpu_monitoring {
+        if self.config().enable_gpu_monitoring {
             // GPU monitoring will be collected in get_gpu_info()
         }
 
@@ -198,29 +240,30 @@ impl SystemMonitor {
     /// Check if system resources are within limits
     async fn check_resource_limits(&self) -> Result<()> {
         let system_info = self.get_system_info().await?;
+        let config = self.config();
 
         // Check CPU usage
-        if system_info.cpu.usage_percent > self.config.max_cpu_usage {
+        if system_info.cpu.usage_percent > config.max_cpu_usage {
             warn!(
                 "CPU usage ({:.1}%) exceeds limit ({:.1}%)",
-                system_info.cpu.usage_percent, self.config.max_cpu_usage
+                system_info.cpu.usage_percent, config.max_cpu_usage
             );
         }
 
         // Check memory usage
-        if system_info.memory.usage_percent > self.config.max_memory_usage {
+        if system_info.memory.usage_percent > config.max_memory_usage {
             warn!(
                 "Memory usage ({:.1}%) exceeds limit ({:.1}%)",
-                system_info.memory.usage_percent, self.config.max_memory_usage
+                system_info.memory.usage_percent, config.max_memory_usage
             );
         }
 
         // Check GPU memory usage
         for gpu in &system_info.gpu {
-            if gpu.memory_usage_percent > self.config.max_gpu_memory_usage {
+            if gpu.memory_usage_percent > config.max_gpu_memory_usage {
                 warn!(
                     "GPU {} memory usage ({:.1}%) exceeds limit ({:.1}%)",
-                    gpu.index, gpu.memory_usage_percent, self.config.max_gpu_memory_usage
+                    gpu.index, gpu.memory_usage_percent, config.max_gpu_memory_usage
                 );
             }
         }
@@ -228,10 +271,10 @@ impl SystemMonitor {
         // Check disk space
         for disk in &system_info.disk {
             let available_gb = disk.available_bytes / (1024 * 1024 * 1024);
-            if available_gb < self.config.min_disk_space_gb {
+            if available_gb < config.min_disk_space_gb {
                 warn!(
                     "Disk {} available space ({} GB) below minimum ({} GB)",
-                    disk.mount_point, available_gb, self.config.min_disk_space_gb
+                    disk.mount_point, available_gb, config.min_disk_space_gb
                 );
             }
         }
@@ -242,16 +285,17 @@ impl SystemMonitor {
     /// Get current system information
     pub async fn get_system_info(&self) -> Result<SystemInfo> {
         let timestamp = chrono::Utc::now().timestamp();
+        let config = self.config();
 
         let cpu = self.cpu_monitor.get_cpu_info(&self.system)?;
         let memory = self.memory_monitor.get_memory_info(&self.system)?;
-        let gpu = if self.config.enable_gpu_monitoring {
+        let gpu = if config.enable_gpu_monitoring {
             self.gpu_monitor.get_gpu_info().await?
         } else {
             vec![]
         };
         let disk = self.disk_monitor.get_disk_info()?;
-        let network = if self.config.enable_network_monitoring {
+        let network = if config.enable_network_monitoring {
             self.network_monitor.get_network_info().await?
         } else {
             NetworkInfo {
@@ -325,34 +369,12 @@ impl SystemMonitor {
     /// Get system profile for registration
     pub async fn get_system_profile(&self) -> Result<SystemProfile> {
         let info = self.get_system_info().await?;
+        let docker_version = self
+            .get_docker_version()
+            .await
+            .unwrap_or_else(|_| "unknown".to_string());
 
-        Ok(SystemProfile {
-            cpu: CpuProfile {
-                model: info.cpu.model,
-                cores: info.cpu.cores,
-                vendor: info.cpu.vendor,
-            },
-            memory: MemoryProfile {
-                total_gb: (info.memory.total_bytes / (1024 * 1024 * 1024)) as f32,
-            },
-            storage: StorageProfile {
-                total_gb: info
-                    .disk
-                    .iter()
-                    .map(|d| d.total_bytes / (1024 * 1024 * 1024))
-                    .sum::<u64>() as f32,
-            },
-            os: OsProfile {
-                os_type: info.system.os_name,
-                version: info.system.os_version,
-            },
-            docker: DockerProfile {
-                version: self
-                    .get_docker_version()
-                    .await
-                    .unwrap_or_else(|_| "unknown".to_string()),
-            },
-        })
+        Ok(SystemProfile::from_system_info(info, docker_version))
     }
 
     /// Get current available resources
@@ -415,20 +437,21 @@ impl SystemMonitor {
         let mut status = std::collections::HashMap::new();
 
         let info = self.get_system_info().await?;
+        let config = self.config();
 
         status.insert(
             "cpu_healthy".to_string(),
-            serde_json::Value::Bool(info.cpu.usage_percent < self.config.max_cpu_usage),
+            serde_json::Value::Bool(info.cpu.usage_percent < config.max_cpu_usage),
         );
         status.insert(
             "memory_healthy".to_string(),
-            serde_json::Value::Bool(info.memory.usage_percent < self.config.max_memory_usage),
+            serde_json::Value::Bool(info.memory.usage_percent < config.max_memory_usage),
         );
         status.insert(
             "disk_healthy".to_string(),
             serde_json::Value::Bool(info.disk.iter().all(|d| {
                 let available_gb = d.available_bytes / (1024 * 1024 * 1024);
-                available_gb >= self.config.min_disk_space_gb
+                available_gb >= config.min_disk_space_gb
             })),
         );
         status.insert(
@@ -436,7 +459,7 @@ impl SystemMonitor {
             serde_json::Value::Bool(
                 info.gpu
                     .iter()
-                    .all(|g| g.memory_usage_percent < self.config.max_gpu_memory_usage),
+                    .all(|g| g.memory_usage_percent < config.max_gpu_memory_usage),
             ),
         );
         status.insert(
@@ -468,7 +491,7 @@ impl SystemMetricsProvider for SystemMonitor {
     }
 
     async fn network_stats(&self) -> Result<(u64, u64), anyhow::Error> {
-        let network_info = if self.config.enable_network_monitoring {
+        let network_info = if self.config().enable_network_monitoring {
             self.network_monitor.get_network_info().await?
         } else {
             NetworkInfo {
@@ -484,7 +507,7 @@ impl SystemMetricsProvider for SystemMonitor {
     }
 
     async fn collect_gpu_metrics(&self) -> Result<Option<CommonGpuMetrics>, anyhow::Error> {
-        if !self.config.enable_gpu_monitoring {
+        if !self.config().enable_gpu_monitoring {
             return Ok(None);
         }
 
@@ -525,33 +548,32 @@ impl Default for SystemMonitor {
 /// - Uses centralized collector to eliminate duplicate monitoring
 /// - Collects system, container, and GPU metrics from single source
 /// - Fans out metrics to both billing stream and Prometheus endpoint
-/// - Manages container lifecycle status updates separately
+/// - Emits container lifecycle transitions onto the billing stream
+/// - Forwards image pull progress from `image_progress` onto the billing stream
+///
+/// `static_labels` are added to every telemetry sample sent to billing.
+/// Rental samples also carry the GPU energy the rental has used so far as the
+/// `energy_kwh` custom metric, with `gpu.<index>.energy_kwh` per GPU.
 ///
-/// This function returns immediately after spawning all tasks.
+/// This function returns immediately after spawning all tasks. The returned
+/// sender publishes updated sampling intervals to the running collector.
 pub fn spawn_monitoring(
     executor_id: String,
     docker_host: String,
     monitor_cfg: crate::config::types::TelemetryMonitorConfig,
     telemetry_cfg_raw: crate::config::types::TelemetryConfig,
     metrics_recorder: Option<Arc<dyn MetricsRecorder>>,
-) {
+    static_labels: HashMap<String, String>,
+    image_progress: Option<
+        tokio::sync::broadcast::Receiver<crate::container_manager::images::ImagePullProgress>,
+    >,
+) -> tokio::sync::watch::Sender<crate::config::types::TelemetryMonitorConfig> {
+    let (config_tx, config_rx) = tokio::sync::watch::channel(monitor_cfg.clone());
+
     let mut stream_cfg: stream::StreamConfig = telemetry_cfg_raw.into();
     stream_cfg.queue_capacity = monitor_cfg.queue_capacity;
-
-    // Start lifecycle management if enabled
-    if monitor_cfg.update_lifecycle_status {
-        let lifecycle_cfg = lifecycle::LifecycleConfig {
-            docker_host: docker_host.clone(),
-            check_interval_secs: monitor_cfg.container_sample_secs,
-            enabled: true,
-        };
-        let stream_cfg_lifecycle = stream_cfg.clone();
-        tokio::spawn(async move {
-            if let Err(e) = lifecycle::run(lifecycle_cfg, stream_cfg_lifecycle).await {
-                warn!("Lifecycle management error: {}", e);
-            }
-        });
-    }
+    stream_cfg.queue_policy = monitor_cfg.queue_policy;
+    stream_cfg.static_labels = static_labels;
 
     // Create metrics collector
     let collector_result = tokio::runtime::Handle::current().block_on(async {
@@ -567,34 +589,127 @@ pub fn spawn_monitoring(
         Ok((c, tx)) => (c, tx),
         Err(e) => {
             error!("Failed to create metrics collector: {}", e);
-            return;
+            return config_tx;
         }
     };
 
-    // Channel for billing stream
-    let (billing_tx, billing_rx) = tokio::sync::mpsc::channel::<
+    // Optional on-disk spool for samples produced while billing is unreachable
+    let spool = spool::SpoolConfig::from_monitor_config(&monitor_cfg).and_then(|spool_cfg| {
+        match spool::TelemetrySpool::open(spool_cfg) {
+            Ok(spool) => Some(Arc::new(spool)),
+            Err(e) => {
+                error!("Failed to open telemetry spool, spooling disabled: {:#}", e);
+                None
+            }
+        }
+    });
+
+    // Queue for billing stream
+    let (billing_tx, billing_rx) = telemetry_queue::bounded::<
         basilica_protocol::billing::TelemetryData,
-    >(stream_cfg.queue_capacity);
+    >(stream_cfg.queue_capacity, stream_cfg.queue_policy);
+
+    // Start lifecycle management if enabled; transitions share the billing queue
+    if monitor_cfg.update_lifecycle_status {
+        let lifecycle_cfg = lifecycle::LifecycleConfig {
+            docker_host: docker_host.clone(),
+            check_interval_secs: monitor_cfg.container_sample_secs,
+            enabled: true,
+        };
+        let stream_cfg_lifecycle = stream_cfg.clone();
+        let lifecycle_executor_id = executor_id.clone();
+        let lifecycle_tx = billing_tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = lifecycle::run(
+                lifecycle_cfg,
+                stream_cfg_lifecycle,
+                lifecycle_executor_id,
+                lifecycle_tx,
+            )
+            .await
+            {
+                warn!("Lifecycle management error: {}", e);
+            }
+        });
+    }
+
+    // Pull progress shares the billing queue with host and rental samples
+    if let Some(progress_rx) = image_progress {
+        tokio::spawn(image_progress::forward(
+            progress_rx,
+            executor_id.clone(),
+            billing_tx.clone(),
+        ));
+    }
 
     // Subscribe to metrics and convert to TelemetryData for billing
     let mut metrics_rx = broadcast_tx.subscribe();
-    let billing_tx_clone = billing_tx;
+    let drop_recorder = metrics_recorder.clone();
+    let queue_policy = stream_cfg.queue_policy;
+    let host_spool = spool.clone();
     tokio::spawn(async move {
-        while let Ok(metrics) = metrics_rx.recv().await {
-            // Convert metrics to telemetry data
-            // Send host metrics
+        let mut energy = energy::RentalEnergyMeter::default();
+        while let Some(metrics) =
+            metrics::recv_metrics(&mut metrics_rx, "billing", drop_recorder.as_ref()).await
+        {
+            energy.record_gpu_metrics(&metrics.gpu_metrics, metrics.timestamp);
+
+            let mut samples = Vec::with_capacity(metrics.container_metrics.len() + 1);
+
+            // Host metrics carry the running drop count so billing can
+            // account for samples that never arrived
             if metrics.system_metrics.is_some() {
-                let telemetry = metrics.to_host_telemetry();
-                if billing_tx_clone.send(telemetry).await.is_err() {
-                    warn!("Failed to send host telemetry to billing: channel full or closed");
+                let mut telemetry = metrics.to_host_telemetry();
+                telemetry.custom_metrics.insert(
+                    "host.telemetry_dropped_samples".to_string(),
+                    billing_tx.dropped() as f64,
+                );
+                if let Some(ref spool) = host_spool {
+                    telemetry.custom_metrics.insert(
+                        "host.telemetry_spool_dropped_samples".to_string(),
+                        spool.dropped() as f64,
+                    );
+                    telemetry.custom_metrics.insert(
+                        "host.telemetry_spool_bytes".to_string(),
+                        spool.size_bytes() as f64,
+                    );
                 }
+                samples.push(telemetry);
             }
 
-            // Send container metrics
             for container in &metrics.container_metrics {
-                let telemetry = metrics.to_container_telemetry(container);
-                if billing_tx_clone.send(telemetry).await.is_err() {
-                    warn!("Failed to send container telemetry to billing: channel full or closed");
+                let mut telemetry = metrics.to_container_telemetry(container);
+                if let Some(ref rental_id) = container.rental_id {
+                    let used = energy.rental_energy(rental_id, metrics.timestamp);
+                    telemetry
+                        .custom_metrics
+                        .insert("energy_kwh".to_string(), used.total_kwh);
+                    for (index, kwh) in used.per_gpu_kwh {
+                        telemetry
+                            .custom_metrics
+                            .insert(format!("gpu.{index}.energy_kwh"), kwh);
+                    }
+                }
+                samples.push(telemetry);
+            }
+
+            for telemetry in samples {
+                let outcome = billing_tx.push(telemetry).await;
+                if outcome.is_drop() {
+                    warn!(
+                        "Telemetry queue full, dropped a sample ({} dropped so far)",
+                        billing_tx.dropped()
+                    );
+                    if let Some(ref recorder) = drop_recorder {
+                        recorder
+                            .increment_counter(
+                                "executor_telemetry_samples_dropped_total",
+                                &[("policy", queue_policy.as_str())],
+                            )
+                            .await;
+                    }
+                } else if outcome == telemetry_queue::PushOutcome::Closed {
+                    warn!("Failed to send telemetry to billing: stream closed");
                 }
             }
         }
@@ -604,7 +719,9 @@ pub fn spawn_monitoring(
     if let Some(recorder) = metrics_recorder {
         let mut prom_rx = broadcast_tx.subscribe();
         tokio::spawn(async move {
-            while let Ok(metrics) = prom_rx.recv().await {
+            while let Some(metrics) =
+                metrics::recv_metrics(&mut prom_rx, "prometheus", Some(&recorder)).await
+            {
                 // Record system metrics
                 if let Some(ref sys) = metrics.system_metrics {
                     recorder
@@ -630,6 +747,41 @@ pub fn spawn_monitoring(
                             &[],
                         )
                         .await;
+
+                    for interface in &sys.network_interfaces {
+                        let billed = if interface.billed { "true" } else { "false" };
+                        let labels = &[("interface", interface.name.as_str()), ("billed", billed)];
+                        recorder
+                            .record_gauge("executor_network_rx_mbps", interface.rx_mbps, labels)
+                            .await;
+                        recorder
+                            .record_gauge("executor_network_tx_mbps", interface.tx_mbps, labels)
+                            .await;
+                    }
+
+                    for disk in &sys.disk_usage {
+                        let labels = &[("mount_point", disk.mount_point.as_str())];
+                        recorder
+                            .record_gauge(
+                                "executor_disk_read_bytes_per_sec",
+                                disk.io.read_bytes_per_sec,
+                                labels,
+                            )
+                            .await;
+                        recorder
+                            .record_gauge(
+                                "executor_disk_write_bytes_per_sec",
+                                disk.io.write_bytes_per_sec,
+                                labels,
+                            )
+                            .await;
+                        recorder
+                            .record_gauge("executor_disk_read_iops", disk.io.read_iops, labels)
+                            .await;
+                        recorder
+                            .record_gauge("executor_disk_write_iops", disk.io.write_iops, labels)
+                            .await;
+                    }
                 }
 
                 // Record container metrics
@@ -683,14 +835,17 @@ pub fn spawn_monitoring(
     }
 
     // Start metrics collector
+    let collector = collector.with_config_updates(config_rx);
     tokio::spawn(async move {
         collector.start().await;
     });
 
     // Start billing data stream
     tokio::spawn(async move {
-        if let Err(e) = stream::run(stream_cfg, billing_rx).await {
+        if let Err(e) = stream::run(stream_cfg, billing_rx, spool).await {
             warn!("data stream error: {e}");
         }
     });
+
+    config_tx
 }
@@ -2,23 +2,54 @@
 
 use super::types::{NetworkInfo, NetworkInterface};
 use anyhow::Result;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 use sysinfo::Networks;
 
+/// Interface name prefixes excluded from billed totals by default:
+/// loopback, docker/bridge networks and container veth pairs
+const DEFAULT_EXCLUDED_PREFIXES: &[&str] =
+    &["lo", "docker", "br-", "veth", "virbr", "cni", "flannel"];
+
+/// Last observed counters for an interface
+#[derive(Debug, Clone, Copy)]
+struct InterfaceSample {
+    taken_at: Instant,
+    tx_bytes: u64,
+    rx_bytes: u64,
+}
+
+/// Receive/transmit rates for an interface in Mbps
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterfaceRate {
+    pub rx_mbps: f64,
+    pub tx_mbps: f64,
+}
+
 /// Network monitoring handler
 #[derive(Debug)]
 pub struct NetworkMonitor {
     networks: Networks,
-    last_measurement: Option<(Instant, u64, u64)>, // (time, tx_bytes, rx_bytes)
+    billed_interfaces: Vec<String>,
+    samples: HashMap<String, InterfaceSample>,
+    rates: HashMap<String, InterfaceRate>,
 }
 
 impl NetworkMonitor {
     /// Create new network monitor
     pub fn new() -> Self {
+        Self::with_billed_interfaces(Vec::new())
+    }
+
+    /// Create new network monitor that only bills the given interfaces.
+    /// An empty list falls back to excluding loopback and container bridges.
+    pub fn with_billed_interfaces(billed_interfaces: Vec<String>) -> Self {
         let networks = Networks::new_with_refreshed_list();
         Self {
             networks,
-            last_measurement: None,
+            billed_interfaces,
+            samples: HashMap::new(),
+            rates: HashMap::new(),
         }
     }
 
@@ -29,6 +60,9 @@ impl NetworkMonitor {
         let mut total_received = 0;
 
         for (interface_name, network) in &self.networks {
+            let rate = self.rates.get(interface_name).copied().unwrap_or_default();
+            let billed = self.is_billed(interface_name);
+
             let interface_info = NetworkInterface {
                 name: interface_name.clone(),
                 bytes_sent: network.total_transmitted(),
@@ -38,10 +72,15 @@ impl NetworkMonitor {
                 errors_sent: network.total_errors_on_transmitted(),
                 errors_received: network.total_errors_on_received(),
                 is_up: true,
+                rx_mbps: rate.rx_mbps,
+                tx_mbps: rate.tx_mbps,
+                billed,
             };
 
-            total_sent += interface_info.bytes_sent;
-            total_received += interface_info.bytes_received;
+            if billed {
+                total_sent += interface_info.bytes_sent;
+                total_received += interface_info.bytes_received;
+            }
             interfaces.push(interface_info);
         }
 
@@ -52,48 +91,189 @@ impl NetworkMonitor {
         })
     }
 
-    /// Refresh network data
+    /// Refresh network data and update per-interface rates
+    ///
+    /// Interfaces that have gone away, such as the veth pair of a removed
+    /// container, are forgotten.
     pub fn refresh(&mut self) {
-        self.networks.refresh();
-    }
+        // Unlike `refresh`, this also drops interfaces that no longer exist
+        self.networks.refresh_list();
 
-    /// Calculate current network bandwidth in Mbps based on time-delta measurements
-    pub fn calculate_bandwidth_mbps(&mut self) -> f64 {
-        self.networks.refresh();
+        let now = Instant::now();
+        let counters: Vec<(String, u64, u64)> = self
+            .networks
+            .iter()
+            .map(|(name, network)| {
+                (
+                    name.clone(),
+                    network.total_transmitted(),
+                    network.total_received(),
+                )
+            })
+            .collect();
 
-        let current_time = Instant::now();
-        let mut total_tx = 0u64;
-        let mut total_rx = 0u64;
+        self.record_samples(now, counters);
+    }
+
+    /// Record one reading of every interface, dropping the samples and rates
+    /// of interfaces missing from it
+    fn record_samples(&mut self, taken_at: Instant, counters: Vec<(String, u64, u64)>) {
+        let present: HashSet<&str> = counters.iter().map(|(name, _, _)| name.as_str()).collect();
+        self.samples
+            .retain(|name, _| present.contains(name.as_str()));
+        self.rates.retain(|name, _| present.contains(name.as_str()));
 
-        for (_, network) in &self.networks {
-            total_tx += network.total_transmitted();
-            total_rx += network.total_received();
+        for (name, tx_bytes, rx_bytes) in &counters {
+            self.record_sample(name, taken_at, *tx_bytes, *rx_bytes);
         }
+    }
 
-        if self.last_measurement.is_none() {
-            self.last_measurement = Some((current_time, total_tx, total_rx));
-            return 0.0;
+    /// Record a counter sample for an interface and update its rate
+    fn record_sample(&mut self, name: &str, taken_at: Instant, tx_bytes: u64, rx_bytes: u64) {
+        let current = InterfaceSample {
+            taken_at,
+            tx_bytes,
+            rx_bytes,
+        };
+
+        if let Some(previous) = self.samples.insert(name.to_string(), current) {
+            let elapsed = taken_at.duration_since(previous.taken_at).as_secs_f64();
+            if elapsed > 0.0 {
+                let rx_delta = counter_delta(previous.rx_bytes, rx_bytes);
+                let tx_delta = counter_delta(previous.tx_bytes, tx_bytes);
+                self.rates.insert(
+                    name.to_string(),
+                    InterfaceRate {
+                        rx_mbps: bytes_to_mbps(rx_delta, elapsed),
+                        tx_mbps: bytes_to_mbps(tx_delta, elapsed),
+                    },
+                );
+            }
         }
+    }
 
-        let (last_time, last_tx, last_rx) = self.last_measurement.unwrap();
-        let time_delta = current_time.duration_since(last_time).as_secs_f64();
+    /// Current rate for an interface, if at least two samples have been taken
+    pub fn interface_rate(&self, name: &str) -> Option<InterfaceRate> {
+        self.rates.get(name).copied()
+    }
 
-        if time_delta == 0.0 {
-            return 0.0;
+    /// Whether an interface counts towards billed totals
+    pub fn is_billed(&self, name: &str) -> bool {
+        if !self.billed_interfaces.is_empty() {
+            return self.billed_interfaces.iter().any(|allowed| allowed == name);
         }
 
-        let bytes_delta = (total_tx - last_tx) + (total_rx - last_rx);
-        let bits_per_second = (bytes_delta as f64 * 8.0) / time_delta;
-        let mbps = bits_per_second / 1_000_000.0;
+        !DEFAULT_EXCLUDED_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+    }
+
+    /// Calculate current network bandwidth in Mbps across billed interfaces
+    pub fn calculate_bandwidth_mbps(&mut self) -> f64 {
+        self.refresh();
+        self.billed_bandwidth_mbps()
+    }
 
-        self.last_measurement = Some((current_time, total_tx, total_rx));
+    /// Sum of the last computed rates across billed interfaces
+    fn billed_bandwidth_mbps(&self) -> f64 {
+        self.rates
+            .iter()
+            .filter(|(name, _)| self.is_billed(name))
+            .map(|(_, rate)| rate.rx_mbps + rate.tx_mbps)
+            .sum()
+    }
+}
 
-        mbps
+/// Difference between two cumulative counter readings.
+///
+/// A reading lower than the previous one is treated as a 32-bit wrap when the
+/// previous value fits in 32 bits, otherwise as a counter reset (zero delta).
+fn counter_delta(previous: u64, current: u64) -> u64 {
+    if current >= previous {
+        current - previous
+    } else if previous <= u32::MAX as u64 {
+        (u32::MAX as u64 - previous) + current + 1
+    } else {
+        0
     }
 }
 
+fn bytes_to_mbps(bytes: u64, elapsed_secs: f64) -> f64 {
+    (bytes as f64 * 8.0) / elapsed_secs / 1_000_000.0
+}
+
 impl Default for NetworkMonitor {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_per_interface_rates_exclude_virtual() {
+        let mut monitor = NetworkMonitor::new();
+        let start = Instant::now();
+        let later = start + Duration::from_secs(2);
+
+        monitor.record_sample("eth0", start, 0, 0);
+        monitor.record_sample("docker0", start, 0, 0);
+        monitor.record_sample("eth0", later, 500_000, 1_000_000);
+        monitor.record_sample("docker0", later, 10_000_000, 10_000_000);
+
+        let eth0 = monitor.interface_rate("eth0").unwrap();
+        assert_eq!(eth0.rx_mbps, 4.0);
+        assert_eq!(eth0.tx_mbps, 2.0);
+        assert_eq!(monitor.interface_rate("docker0").unwrap().rx_mbps, 40.0);
+
+        assert!(monitor.is_billed("eth0"));
+        assert!(!monitor.is_billed("docker0"));
+        assert!(!monitor.is_billed("lo"));
+
+        assert_eq!(monitor.billed_bandwidth_mbps(), 6.0);
+    }
+
+    #[test]
+    fn test_vanished_interfaces_are_dropped() {
+        let mut monitor = NetworkMonitor::new();
+        let start = Instant::now();
+        let reading = |tx: u64| {
+            vec![
+                ("eth0".to_string(), tx, tx),
+                ("veth1a2b3c".to_string(), tx, tx),
+            ]
+        };
+
+        monitor.record_samples(start, reading(0));
+        monitor.record_samples(start + Duration::from_secs(1), reading(1_000_000));
+        assert!(monitor.interface_rate("veth1a2b3c").is_some());
+
+        // The container's veth pair is gone from the next reading
+        monitor.record_samples(
+            start + Duration::from_secs(2),
+            vec![("eth0".to_string(), 2_000_000, 2_000_000)],
+        );
+        assert!(monitor.interface_rate("veth1a2b3c").is_none());
+        assert!(!monitor.samples.contains_key("veth1a2b3c"));
+        assert_eq!(monitor.samples.len(), 1);
+        assert_eq!(monitor.rates.len(), 1);
+        assert_eq!(monitor.billed_bandwidth_mbps(), 16.0);
+    }
+
+    #[test]
+    fn test_allowlist_overrides_default_exclusions() {
+        let monitor = NetworkMonitor::with_billed_interfaces(vec!["docker0".to_string()]);
+        assert!(monitor.is_billed("docker0"));
+        assert!(!monitor.is_billed("eth0"));
+    }
+
+    #[test]
+    fn test_counter_wraparound() {
+        assert_eq!(counter_delta(100, 250), 150);
+        assert_eq!(counter_delta(u32::MAX as u64 - 9, 10), 20);
+        assert_eq!(counter_delta(u64::MAX / 2, 10), 0);
+    }
+}
@@ -0,0 +1,493 @@
+//! On-disk spool for telemetry produced while billing is unreachable
+//!
+//! Samples are appended to a write-ahead file as length-prefixed protobuf
+//! records and replayed in order once the billing stream reconnects. The spool
+//! is bounded by size: when full, the oldest records are evicted and counted so
+//! the loss is visible. Eviction frees a tenth of the cap at once, so a spool
+//! that stays full rewrites its file once per batch of evictions rather than on
+//! every append. Records older than the configured max age are discarded
+//! instead of replayed.
+//!
+//! Delivery is at-least-once: records are only removed after billing has
+//! acknowledged the replay, so a replay interrupted midway is retried in full.
+
+use anyhow::{Context, Result};
+use basilica_protocol::billing::TelemetryData;
+use prost::Message;
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+const SPOOL_FILE: &str = "telemetry.spool";
+
+/// Bytes preceding each payload: spool timestamp (u64) and payload length (u32)
+const RECORD_HEADER_LEN: u64 = 12;
+
+/// Share of the cap, in percent, left free when a full spool evicts records
+const EVICTION_HEADROOM_PERCENT: u64 = 10;
+
+/// Spool location and bounds
+#[derive(Debug, Clone)]
+pub struct SpoolConfig {
+    pub dir: PathBuf,
+    pub max_bytes: u64,
+    pub max_age: Duration,
+}
+
+impl SpoolConfig {
+    /// Build a spool configuration from the telemetry monitor settings, if
+    /// spooling is enabled
+    pub fn from_monitor_config(cfg: &crate::config::types::TelemetryMonitorConfig) -> Option<Self> {
+        cfg.spool_dir.as_ref().map(|dir| Self {
+            dir: dir.clone(),
+            max_bytes: cfg.spool_max_bytes,
+            max_age: Duration::from_secs(cfg.spool_max_age_secs),
+        })
+    }
+}
+
+struct SpoolRecord {
+    seq: u64,
+    spooled_at: u64,
+    payload: Vec<u8>,
+}
+
+impl SpoolRecord {
+    fn size(&self) -> u64 {
+        RECORD_HEADER_LEN + self.payload.len() as u64
+    }
+
+    fn write_to(&self, out: &mut impl Write) -> std::io::Result<()> {
+        out.write_all(&self.spooled_at.to_le_bytes())?;
+        out.write_all(&(self.payload.len() as u32).to_le_bytes())?;
+        out.write_all(&self.payload)
+    }
+}
+
+struct Inner {
+    records: VecDeque<SpoolRecord>,
+    bytes: u64,
+    next_seq: u64,
+    file: File,
+}
+
+/// Samples pending replay, in the order they were spooled
+pub struct SpoolBatch {
+    pub samples: Vec<TelemetryData>,
+    /// Sequence number to acknowledge once the samples have been delivered
+    last_seq: Option<u64>,
+}
+
+/// Size-bounded write-ahead spool of telemetry samples
+pub struct TelemetrySpool {
+    config: SpoolConfig,
+    path: PathBuf,
+    inner: Mutex<Inner>,
+    dropped: AtomicU64,
+    expired: AtomicU64,
+}
+
+impl TelemetrySpool {
+    /// Open the spool, recovering records left by a previous run
+    pub fn open(config: SpoolConfig) -> Result<Self> {
+        fs::create_dir_all(&config.dir)
+            .with_context(|| format!("Failed to create spool dir {}", config.dir.display()))?;
+        let path = config.dir.join(SPOOL_FILE);
+
+        let contents = match fs::read(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read spool {}", path.display()))
+            }
+        };
+        let (records, torn) = parse_records(&contents);
+        if torn {
+            warn!(
+                "Discarding incomplete trailing record in telemetry spool {}",
+                path.display()
+            );
+        }
+
+        let bytes = records.iter().map(SpoolRecord::size).sum();
+        let next_seq = records.len() as u64;
+        let file = open_append(&path)?;
+        let spool = Self {
+            config,
+            path,
+            inner: Mutex::new(Inner {
+                records,
+                bytes,
+                next_seq,
+                file,
+            }),
+            dropped: AtomicU64::new(0),
+            expired: AtomicU64::new(0),
+        };
+
+        if torn {
+            let mut inner = spool.lock();
+            spool.rewrite(&mut inner)?;
+        }
+        Ok(spool)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Append a sample, evicting the oldest records if the spool is full
+    pub fn append(&self, sample: &TelemetryData) -> Result<()> {
+        self.append_at(sample, unix_now())
+    }
+
+    fn append_at(&self, sample: &TelemetryData, spooled_at: u64) -> Result<()> {
+        let mut inner = self.lock();
+        let record = SpoolRecord {
+            seq: inner.next_seq,
+            spooled_at,
+            payload: sample.encode_to_vec(),
+        };
+        inner.next_seq += 1;
+
+        if record.size() > self.config.max_bytes {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            warn!("Telemetry sample larger than the spool cap, dropping it");
+            return Ok(());
+        }
+
+        let mut evicted = 0;
+        if inner.bytes + record.size() > self.config.max_bytes {
+            let headroom = self.config.max_bytes * EVICTION_HEADROOM_PERCENT / 100;
+            let target = self.config.max_bytes - headroom;
+            while inner.bytes + record.size() > target {
+                match inner.records.pop_front() {
+                    Some(oldest) => {
+                        inner.bytes -= oldest.size();
+                        evicted += 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        inner.bytes += record.size();
+        inner.records.push_back(record);
+
+        if evicted > 0 {
+            let total = self.dropped.fetch_add(evicted, Ordering::Relaxed) + evicted;
+            warn!(
+                "Telemetry spool full, dropped {} oldest samples ({} dropped so far)",
+                evicted, total
+            );
+            return self.rewrite(&mut inner);
+        }
+
+        let Inner { records, file, .. } = &mut *inner;
+        if let Some(record) = records.back() {
+            record
+                .write_to(file)
+                .with_context(|| format!("Failed to append to spool {}", self.path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Snapshot the samples awaiting replay.
+    ///
+    /// Expired records are discarded first. Identical samples spooled more than
+    /// once are only returned once.
+    pub fn replay(&self) -> Result<SpoolBatch> {
+        let mut inner = self.lock();
+        self.discard_expired(&mut inner)?;
+
+        let mut seen = HashSet::new();
+        let mut samples = Vec::with_capacity(inner.records.len());
+        for record in &inner.records {
+            if !seen.insert(record.payload.as_slice()) {
+                continue;
+            }
+            match TelemetryData::decode(record.payload.as_slice()) {
+                Ok(sample) => samples.push(sample),
+                Err(e) => warn!("Skipping undecodable spooled telemetry sample: {}", e),
+            }
+        }
+
+        Ok(SpoolBatch {
+            samples,
+            last_seq: inner.records.back().map(|r| r.seq),
+        })
+    }
+
+    /// Remove the records covered by a delivered batch
+    pub fn acknowledge(&self, batch: &SpoolBatch) -> Result<()> {
+        let Some(last_seq) = batch.last_seq else {
+            return Ok(());
+        };
+
+        let mut inner = self.lock();
+        let before = inner.records.len();
+        while inner.records.front().is_some_and(|r| r.seq <= last_seq) {
+            if let Some(record) = inner.records.pop_front() {
+                inner.bytes -= record.size();
+            }
+        }
+
+        if inner.records.len() != before {
+            self.rewrite(&mut inner)?;
+        }
+        Ok(())
+    }
+
+    fn discard_expired(&self, inner: &mut Inner) -> Result<()> {
+        let cutoff = unix_now().saturating_sub(self.config.max_age.as_secs());
+        let mut expired = 0;
+        while inner.records.front().is_some_and(|r| r.spooled_at < cutoff) {
+            if let Some(record) = inner.records.pop_front() {
+                inner.bytes -= record.size();
+                expired += 1;
+            }
+        }
+
+        if expired > 0 {
+            self.expired.fetch_add(expired, Ordering::Relaxed);
+            warn!(
+                "Discarded {} spooled telemetry samples older than {:?}",
+                expired, self.config.max_age
+            );
+            self.rewrite(inner)?;
+        }
+        Ok(())
+    }
+
+    /// Replace the spool file with the in-memory records
+    fn rewrite(&self, inner: &mut Inner) -> Result<()> {
+        let tmp_path = self.path.with_extension("spool.tmp");
+        let mut buf = Vec::with_capacity(inner.bytes as usize);
+        for record in &inner.records {
+            record.write_to(&mut buf)?;
+        }
+        fs::write(&tmp_path, &buf)
+            .with_context(|| format!("Failed to write spool {}", tmp_path.display()))?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("Failed to replace spool {}", self.path.display()))?;
+        inner.file = open_append(&self.path)?;
+        Ok(())
+    }
+
+    /// Number of records currently spooled
+    pub fn len(&self) -> usize {
+        self.lock().records.len()
+    }
+
+    /// Whether the spool is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Size of the spooled records in bytes
+    pub fn size_bytes(&self) -> u64 {
+        self.lock().bytes
+    }
+
+    /// Total samples evicted because the spool was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Total samples discarded for exceeding the max age
+    pub fn expired(&self) -> u64 {
+        self.expired.load(Ordering::Relaxed)
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open spool {}", path.display()))
+}
+
+/// Parse spool records, reporting whether the file ended in a partial record
+fn parse_records(mut contents: &[u8]) -> (VecDeque<SpoolRecord>, bool) {
+    let mut records = VecDeque::new();
+    while !contents.is_empty() {
+        if contents.len() < RECORD_HEADER_LEN as usize {
+            return (records, true);
+        }
+        let (header, rest) = contents.split_at(RECORD_HEADER_LEN as usize);
+        let spooled_at = u64::from_le_bytes(header[..8].try_into().unwrap_or_default());
+        let len = u32::from_le_bytes(header[8..].try_into().unwrap_or_default()) as usize;
+        if rest.len() < len {
+            return (records, true);
+        }
+        let (payload, rest) = rest.split_at(len);
+        records.push_back(SpoolRecord {
+            seq: records.len() as u64,
+            spooled_at,
+            payload: payload.to_vec(),
+        });
+        contents = rest;
+    }
+    (records, false)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(rental_id: &str, seconds: i64) -> TelemetryData {
+        TelemetryData {
+            rental_id: rental_id.to_string(),
+            executor_id: "executor-1".to_string(),
+            timestamp: Some(prost_types::Timestamp { seconds, nanos: 0 }),
+            ..Default::default()
+        }
+    }
+
+    fn spool(dir: &std::path::Path, max_bytes: u64) -> TelemetrySpool {
+        TelemetrySpool::open(SpoolConfig {
+            dir: dir.to_path_buf(),
+            max_bytes,
+            max_age: Duration::from_secs(3600),
+        })
+        .unwrap()
+    }
+
+    fn rental_ids(batch: &SpoolBatch) -> Vec<&str> {
+        batch.samples.iter().map(|s| s.rental_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_replay_in_order_and_acknowledge() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = spool(dir.path(), 1024 * 1024);
+        for (i, rental) in ["a", "b", "c"].iter().enumerate() {
+            spool.append(&sample(rental, i as i64)).unwrap();
+        }
+
+        let batch = spool.replay().unwrap();
+        assert_eq!(rental_ids(&batch), ["a", "b", "c"]);
+
+        // Samples spooled after the snapshot survive the acknowledgement
+        spool.append(&sample("d", 3)).unwrap();
+        spool.acknowledge(&batch).unwrap();
+        assert_eq!(rental_ids(&spool.replay().unwrap()), ["d"]);
+    }
+
+    #[test]
+    fn test_duplicates_are_replayed_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = spool(dir.path(), 1024 * 1024);
+        spool.append(&sample("a", 1)).unwrap();
+        spool.append(&sample("a", 1)).unwrap();
+        spool.append(&sample("a", 2)).unwrap();
+
+        let batch = spool.replay().unwrap();
+        assert_eq!(batch.samples.len(), 2);
+        spool.acknowledge(&batch).unwrap();
+        assert!(spool.is_empty());
+    }
+
+    #[test]
+    fn test_full_spool_drops_oldest() {
+        let dir = tempfile::tempdir().unwrap();
+        let record_size = RECORD_HEADER_LEN + sample("a", 0).encoded_len() as u64;
+        let spool = spool(dir.path(), record_size * 2);
+
+        for rental in ["a", "b", "c", "d"] {
+            spool.append(&sample(rental, 0)).unwrap();
+        }
+
+        assert_eq!(spool.dropped(), 2);
+        assert_eq!(spool.size_bytes(), record_size * 2);
+        assert_eq!(rental_ids(&spool.replay().unwrap()), ["c", "d"]);
+    }
+
+    #[test]
+    fn test_full_spool_evicts_in_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        // Timestamps below 128 encode to the same size
+        let record_size = RECORD_HEADER_LEN + sample("a", 100).encoded_len() as u64;
+        let full = spool(dir.path(), record_size * 10);
+
+        for i in 0..11 {
+            full.append(&sample("a", 100 + i)).unwrap();
+        }
+        // Room is made for the new sample plus a tenth of the cap
+        assert_eq!(full.dropped(), 2);
+        assert_eq!(full.len(), 9);
+
+        // The next sample fits without evicting anything
+        full.append(&sample("a", 111)).unwrap();
+        assert_eq!(full.dropped(), 2);
+        assert_eq!(full.len(), 10);
+
+        drop(full);
+        let reopened = spool(dir.path(), record_size * 10);
+        let seconds: Vec<i64> = reopened
+            .replay()
+            .unwrap()
+            .samples
+            .iter()
+            .filter_map(|s| s.timestamp.as_ref().map(|t| t.seconds))
+            .collect();
+        assert_eq!(seconds, (102..112).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_spool_survives_restart_and_torn_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let spool = spool(dir.path(), 1024 * 1024);
+            spool.append(&sample("a", 1)).unwrap();
+            spool.append(&sample("b", 2)).unwrap();
+        }
+
+        // Simulate a crash partway through writing a record
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(SPOOL_FILE))
+            .unwrap();
+        file.write_all(&[1, 2, 3]).unwrap();
+        drop(file);
+
+        let spool = spool(dir.path(), 1024 * 1024);
+        assert_eq!(rental_ids(&spool.replay().unwrap()), ["a", "b"]);
+        spool.append(&sample("c", 3)).unwrap();
+        assert_eq!(rental_ids(&spool.replay().unwrap()), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_expired_records_are_not_replayed() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = TelemetrySpool::open(SpoolConfig {
+            dir: dir.path().to_path_buf(),
+            max_bytes: 1024 * 1024,
+            max_age: Duration::from_secs(60),
+        })
+        .unwrap();
+        spool
+            .append_at(&sample("stale", 0), unix_now() - 120)
+            .unwrap();
+        spool.append(&sample("fresh", 1)).unwrap();
+
+        assert_eq!(rental_ids(&spool.replay().unwrap()), ["fresh"]);
+        assert_eq!(spool.expired(), 1);
+    }
+}
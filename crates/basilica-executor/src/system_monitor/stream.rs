@@ -8,8 +8,12 @@ use tonic::transport::{Channel, Endpoint};
 use tonic::Request;
 use tracing::{error, info, warn};
 
-use std::time::{SystemTime, UNIX_EPOCH};
-use tokio_stream::wrappers::ReceiverStream;
+use super::spool::TelemetrySpool;
+use super::telemetry_queue::TelemetryReceiver;
+use crate::config::types::QueuePolicy;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Configuration for data streaming
 #[derive(Clone)]
@@ -18,6 +22,9 @@ pub struct StreamConfig {
     pub api_key: Option<String>,
     pub api_key_header: String,
     pub queue_capacity: usize,
+    pub queue_policy: QueuePolicy,
+    /// Labels added to every sample sent to billing
+    pub static_labels: HashMap<String, String>,
 }
 
 impl From<crate::config::types::TelemetryConfig> for StreamConfig {
@@ -27,6 +34,8 @@ impl From<crate::config::types::TelemetryConfig> for StreamConfig {
             api_key: c.api_key,
             api_key_header: c.api_key_header,
             queue_capacity: 4096,
+            queue_policy: QueuePolicy::default(),
+            static_labels: HashMap::new(),
         }
     }
 }
@@ -79,26 +88,55 @@ async fn make_channel(cfg: &StreamConfig) -> anyhow::Result<Channel> {
     Ok(ep.connect().await?)
 }
 
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+type SharedReceiver = Arc<tokio::sync::Mutex<TelemetryReceiver<TelemetryData>>>;
+
 /// Consumes data from the channel and streams it to the remote service.
+///
+/// Reconnects with backoff whenever the service is unreachable or the stream
+/// breaks. With a spool, samples produced while disconnected are written to
+/// disk and replayed in order before live streaming resumes; without one they
+/// wait in the queue, subject to its backpressure policy.
 pub async fn run(
     cfg: StreamConfig,
-    rx: tokio::sync::mpsc::Receiver<TelemetryData>,
+    rx: TelemetryReceiver<TelemetryData>,
+    spool: Option<Arc<TelemetrySpool>>,
 ) -> anyhow::Result<()> {
-    let mut backoff = std::time::Duration::from_millis(250);
+    let rx: SharedReceiver = Arc::new(tokio::sync::Mutex::new(rx));
+    let mut backoff = INITIAL_BACKOFF;
 
     loop {
         let ch = match make_channel(&cfg).await {
             Ok(c) => c,
             Err(e) => {
                 warn!("stream connect failed: {e}");
-                tokio::time::sleep(backoff).await;
-                backoff = std::cmp::min(backoff * 2, std::time::Duration::from_secs(30));
+                wait_disconnected(&rx, spool.as_deref(), backoff).await;
+                backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
                 continue;
             }
         };
 
         let mut client = BillingServiceClient::new(ch);
-        let stream = ReceiverStream::new(rx);
+
+        if let Some(ref spool) = spool {
+            if let Err(e) = replay_spool(&mut client, &cfg, spool).await {
+                warn!("telemetry spool replay failed: {e}");
+                wait_disconnected(&rx, Some(spool), backoff).await;
+                backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+                continue;
+            }
+        }
+
+        let labels = Arc::new(cfg.static_labels.clone());
+        let stream = futures_util::stream::unfold(rx.clone(), move |rx| {
+            let labels = labels.clone();
+            async move {
+                let next = rx.lock().await.recv().await;
+                next.map(|item| (with_static_labels(item, &labels), rx))
+            }
+        });
 
         let mut req = Request::new(stream);
         if let Err(e) = inject_api_key(&mut req, &cfg) {
@@ -116,17 +154,92 @@ pub async fn run(
                     ..
                 } = resp.into_inner();
                 info!("stream closed: recv={events_received} ok={events_processed} fail={events_failed}");
+                backoff = INITIAL_BACKOFF;
             }
             Err(e) => {
                 warn!("ingest_telemetry error: {e}");
-                backoff = std::cmp::min(backoff * 2, std::time::Duration::from_secs(30));
-                tokio::time::sleep(backoff).await;
             }
         }
 
-        return Err(anyhow::anyhow!(
-            "Data stream disconnected, restart required"
-        ));
+        if rx.lock().await.is_closed() {
+            return Ok(());
+        }
+        wait_disconnected(&rx, spool.as_deref(), backoff).await;
+        backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+    }
+}
+
+/// Deliver spooled samples in a single ingest call, then drop them from the spool
+async fn replay_spool(
+    client: &mut BillingServiceClient<Channel>,
+    cfg: &StreamConfig,
+    spool: &TelemetrySpool,
+) -> anyhow::Result<()> {
+    let mut batch = spool.replay()?;
+    let samples = std::mem::take(&mut batch.samples);
+    if samples.is_empty() {
+        return spool.acknowledge(&batch);
+    }
+
+    let count = samples.len();
+    let samples: Vec<TelemetryData> = samples
+        .into_iter()
+        .map(|sample| with_static_labels(sample, &cfg.static_labels))
+        .collect();
+    let mut req = Request::new(futures_util::stream::iter(samples));
+    if let Err(e) = inject_api_key(&mut req, cfg) {
+        warn!("Failed to inject API key for spool replay: {}", e);
+    }
+
+    let resp = client.ingest_telemetry(req).await?.into_inner();
+    spool.acknowledge(&batch)?;
+    info!(
+        "replayed {count} spooled telemetry samples: ok={} fail={}",
+        resp.events_processed, resp.events_failed
+    );
+    Ok(())
+}
+
+/// Add the configured static labels to `sample`, keeping any label it already has
+fn with_static_labels(
+    mut sample: TelemetryData,
+    static_labels: &HashMap<String, String>,
+) -> TelemetryData {
+    for (name, value) in static_labels {
+        sample
+            .labels
+            .entry(name.clone())
+            .or_insert_with(|| value.clone());
+    }
+    sample
+}
+
+/// Wait out a reconnect delay, moving queued samples into the spool if there is one
+async fn wait_disconnected(rx: &SharedReceiver, spool: Option<&TelemetrySpool>, delay: Duration) {
+    let Some(spool) = spool else {
+        tokio::time::sleep(delay).await;
+        return;
+    };
+
+    let deadline = tokio::time::sleep(delay);
+    tokio::pin!(deadline);
+    let mut rx = rx.lock().await;
+
+    loop {
+        tokio::select! {
+            _ = &mut deadline => return,
+            next = rx.recv() => match next {
+                Some(sample) => {
+                    if let Err(e) = spool.append(&sample) {
+                        warn!("Failed to spool telemetry sample: {e}");
+                    }
+                }
+                None => {
+                    deadline.await;
+                    return;
+                }
+            },
+        }
     }
 }
 
@@ -0,0 +1,267 @@
+//! Bounded telemetry queue with an explicit backpressure policy
+//!
+//! Sits between the metrics collector and the billing stream. Unlike a plain
+//! `mpsc` channel it can evict the oldest sample under backpressure, and it
+//! counts every sample it discards so losses are visible to billing.
+
+use crate::config::types::QueuePolicy;
+use futures_util::Stream;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::Notify;
+
+/// Result of pushing a sample onto the queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    /// Sample was queued without loss
+    Enqueued,
+    /// Queue was full and the new sample was discarded
+    DroppedNewest,
+    /// Queue was full and the oldest queued sample was evicted
+    DroppedOldest,
+    /// Receiver is gone; the sample was discarded
+    Closed,
+}
+
+impl PushOutcome {
+    /// Whether a sample was lost because of backpressure
+    pub fn is_drop(&self) -> bool {
+        matches!(self, Self::DroppedNewest | Self::DroppedOldest)
+    }
+}
+
+struct Shared<T> {
+    buffer: Mutex<VecDeque<T>>,
+    capacity: usize,
+    policy: QueuePolicy,
+    dropped: AtomicU64,
+    senders: AtomicUsize,
+    receiver_closed: AtomicBool,
+    items_available: Notify,
+    space_available: Notify,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, VecDeque<T>> {
+        match self.buffer.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Producer half of a telemetry queue
+pub struct TelemetrySender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Consumer half of a telemetry queue
+pub struct TelemetryReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Create a bounded telemetry queue
+pub fn bounded<T>(
+    capacity: usize,
+    policy: QueuePolicy,
+) -> (TelemetrySender<T>, TelemetryReceiver<T>) {
+    let shared = Arc::new(Shared {
+        buffer: Mutex::new(VecDeque::with_capacity(capacity)),
+        capacity: capacity.max(1),
+        policy,
+        dropped: AtomicU64::new(0),
+        senders: AtomicUsize::new(1),
+        receiver_closed: AtomicBool::new(false),
+        items_available: Notify::new(),
+        space_available: Notify::new(),
+    });
+
+    (
+        TelemetrySender {
+            shared: shared.clone(),
+        },
+        TelemetryReceiver { shared },
+    )
+}
+
+impl<T> TelemetrySender<T> {
+    /// Push a sample, applying the configured policy when the queue is full
+    pub async fn push(&self, item: T) -> PushOutcome {
+        let mut item = Some(item);
+
+        loop {
+            let space = self.shared.space_available.notified();
+
+            if self.shared.receiver_closed.load(Ordering::Acquire) {
+                return PushOutcome::Closed;
+            }
+
+            {
+                let mut buffer = self.shared.lock();
+                if buffer.len() < self.shared.capacity {
+                    buffer.extend(item.take());
+                    drop(buffer);
+                    self.shared.items_available.notify_one();
+                    return PushOutcome::Enqueued;
+                }
+
+                match self.shared.policy {
+                    QueuePolicy::DropNewest => {
+                        self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                        return PushOutcome::DroppedNewest;
+                    }
+                    QueuePolicy::DropOldest => {
+                        buffer.pop_front();
+                        buffer.extend(item.take());
+                        drop(buffer);
+                        self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                        self.shared.items_available.notify_one();
+                        return PushOutcome::DroppedOldest;
+                    }
+                    QueuePolicy::Block => {}
+                }
+            }
+
+            space.await;
+        }
+    }
+
+    /// Total samples discarded because of backpressure
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Number of samples currently queued
+    pub fn len(&self) -> usize {
+        self.shared.lock().len()
+    }
+
+    /// Whether the queue is currently empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for TelemetrySender<T> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::AcqRel);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for TelemetrySender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.items_available.notify_one();
+        }
+    }
+}
+
+impl<T> TelemetryReceiver<T> {
+    /// Receive the next sample, or `None` once every sender is gone and the
+    /// queue has drained
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            let items = self.shared.items_available.notified();
+
+            let next = self.shared.lock().pop_front();
+            if let Some(item) = next {
+                self.shared.space_available.notify_one();
+                return Some(item);
+            }
+
+            if self.shared.senders.load(Ordering::Acquire) == 0 {
+                return None;
+            }
+
+            items.await;
+        }
+    }
+
+    /// Whether every sender is gone and the queue has drained
+    pub fn is_closed(&self) -> bool {
+        self.shared.senders.load(Ordering::Acquire) == 0 && self.shared.lock().is_empty()
+    }
+
+    /// Convert the receiver into a stream for the billing client
+    pub fn into_stream(self) -> impl Stream<Item = T> + Send + 'static
+    where
+        T: Send + 'static,
+    {
+        futures_util::stream::unfold(self, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        })
+    }
+}
+
+impl<T> Drop for TelemetryReceiver<T> {
+    fn drop(&mut self) {
+        self.shared.receiver_closed.store(true, Ordering::Release);
+        self.shared.space_available.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_drop_newest_counts_drops() {
+        let (tx, mut rx) = bounded(2, QueuePolicy::DropNewest);
+
+        assert_eq!(tx.push(1).await, PushOutcome::Enqueued);
+        assert_eq!(tx.push(2).await, PushOutcome::Enqueued);
+        assert_eq!(tx.push(3).await, PushOutcome::DroppedNewest);
+        assert_eq!(tx.push(4).await, PushOutcome::DroppedNewest);
+        assert_eq!(tx.dropped(), 2);
+
+        assert_eq!(rx.recv().await, Some(1));
+        assert_eq!(rx.recv().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_keeps_latest_samples() {
+        let (tx, mut rx) = bounded(2, QueuePolicy::DropOldest);
+
+        for sample in 1..=4 {
+            tx.push(sample).await;
+        }
+        assert_eq!(tx.dropped(), 2);
+
+        drop(tx);
+        assert_eq!(rx.recv().await, Some(3));
+        assert_eq!(rx.recv().await, Some(4));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_block_waits_for_space() {
+        let (tx, mut rx) = bounded(1, QueuePolicy::Block);
+        tx.push(1).await;
+
+        let blocked = tokio::time::timeout(Duration::from_millis(50), tx.push(2)).await;
+        assert!(blocked.is_err());
+
+        let producer = tokio::spawn(async move {
+            let outcome = tx.push(3).await;
+            (outcome, tx.dropped())
+        });
+
+        assert_eq!(rx.recv().await, Some(1));
+        let (outcome, dropped) = producer.await.unwrap();
+        assert_eq!(outcome, PushOutcome::Enqueued);
+        assert_eq!(dropped, 0);
+        assert_eq!(rx.recv().await, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_push_after_receiver_dropped() {
+        let (tx, rx) = bounded(1, QueuePolicy::Block);
+        drop(rx);
+        assert_eq!(tx.push(1).await, PushOutcome::Closed);
+    }
+}
@@ -1,3 +1,4 @@
+use basilica_common::MigProfile;
 use serde::{Deserialize, Serialize};
 
 /// System information snapshot
@@ -32,6 +33,13 @@ pub struct MemoryInfo {
     pub usage_percent: f32,
     pub swap_total_bytes: u64,
     pub swap_used_bytes: u64,
+    /// Physical memory of the host, regardless of any cgroup limit
+    #[serde(default)]
+    pub host_total_bytes: u64,
+    /// Memory the executor may actually use: the cgroup limit when one is set,
+    /// otherwise the host total. `total_bytes` and `usage_percent` are based on this.
+    #[serde(default)]
+    pub effective_limit_bytes: u64,
 }
 
 /// GPU information
@@ -47,6 +55,28 @@ pub struct GpuInfo {
     pub power_usage_watts: f32,
     pub driver_version: String,
     pub cuda_version: Option<String>,
+    /// NVML device UUID, e.g. `GPU-5f1c...`
+    #[serde(default)]
+    pub uuid: String,
+    /// PCI bus ID as reported by NVML, e.g. `00000000:3B:00.0`
+    #[serde(default)]
+    pub pci_bus_id: Option<String>,
+    /// NUMA node the device is attached to, when the host exposes one
+    #[serde(default)]
+    pub numa_node: Option<i32>,
+    /// Set when this logical GPU is a MIG slice of a physical GPU
+    #[serde(default)]
+    pub mig: Option<MigInstance>,
+}
+
+/// MIG slice of a physical GPU, rented as a GPU of its own
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigInstance {
+    pub profile: MigProfile,
+    /// NVML index of the physical GPU the slice is carved from
+    pub parent_index: u32,
+    /// UUID of the physical GPU the slice is carved from
+    pub parent_uuid: String,
 }
 
 /// Disk information
@@ -59,6 +89,17 @@ pub struct DiskInfo {
     pub available_bytes: u64,
     pub usage_percent: f32,
     pub filesystem: String,
+    #[serde(default)]
+    pub io: DiskIoRates,
+}
+
+/// Per-device disk I/O throughput computed between two samples
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiskIoRates {
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    pub read_iops: f64,
+    pub write_iops: f64,
 }
 
 /// Disk usage summary
@@ -76,7 +117,9 @@ pub struct DiskSummary {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkInfo {
     pub interfaces: Vec<NetworkInterface>,
+    /// Bytes sent across billed interfaces
     pub total_bytes_sent: u64,
+    /// Bytes received across billed interfaces
     pub total_bytes_received: u64,
 }
 
@@ -91,6 +134,13 @@ pub struct NetworkInterface {
     pub errors_sent: u64,
     pub errors_received: u64,
     pub is_up: bool,
+    #[serde(default)]
+    pub rx_mbps: f64,
+    #[serde(default)]
+    pub tx_mbps: f64,
+    /// Whether this interface counts towards the billed totals
+    #[serde(default)]
+    pub billed: bool,
 }
 
 /// Basic system information
@@ -113,6 +163,50 @@ pub struct SystemProfile {
     pub storage: StorageProfile,
     pub os: OsProfile,
     pub docker: DockerProfile,
+    /// Per-device GPU topology, for pinning processes next to their GPU
+    #[serde(default)]
+    pub gpus: Vec<GpuProfile>,
+}
+
+impl SystemProfile {
+    /// Build a registration profile from a system snapshot
+    pub fn from_system_info(info: SystemInfo, docker_version: String) -> Self {
+        const GIB: u64 = 1024 * 1024 * 1024;
+
+        Self {
+            cpu: CpuProfile {
+                model: info.cpu.model,
+                cores: info.cpu.cores,
+                vendor: info.cpu.vendor,
+            },
+            memory: MemoryProfile {
+                total_gb: (info.memory.total_bytes / GIB) as f32,
+            },
+            storage: StorageProfile {
+                total_gb: info.disk.iter().map(|d| d.total_bytes / GIB).sum::<u64>() as f32,
+            },
+            os: OsProfile {
+                os_type: info.system.os_name,
+                version: info.system.os_version,
+            },
+            docker: DockerProfile {
+                version: docker_version,
+            },
+            gpus: info
+                .gpu
+                .into_iter()
+                .map(|gpu| GpuProfile {
+                    index: gpu.index,
+                    name: gpu.name,
+                    memory_total_bytes: gpu.memory_total_bytes,
+                    uuid: gpu.uuid,
+                    pci_bus_id: gpu.pci_bus_id,
+                    numa_node: gpu.numa_node,
+                    mig: gpu.mig,
+                })
+                .collect(),
+        }
+    }
 }
 
 /// CPU profile
@@ -142,6 +236,20 @@ pub struct OsProfile {
     pub version: String,
 }
 
+/// GPU device profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuProfile {
+    pub index: u32,
+    pub name: String,
+    pub memory_total_bytes: u64,
+    pub uuid: String,
+    pub pci_bus_id: Option<String>,
+    pub numa_node: Option<i32>,
+    /// Set when the device is a MIG slice
+    #[serde(default)]
+    pub mig: Option<MigInstance>,
+}
+
 /// Docker profile
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockerProfile {
@@ -178,6 +286,7 @@ pub struct SystemMetrics {
     pub load_average: (f64, f64, f64),
     pub network_rx_bytes: u64,
     pub network_tx_bytes: u64,
+    pub network_interfaces: Vec<InterfaceUsage>,
     pub disk_usage: Vec<DiskUsage>,
 }
 
@@ -206,12 +315,21 @@ pub struct GpuMetrics {
     pub power_watts: u64,
 }
 
+#[derive(Debug, Clone)]
+pub struct InterfaceUsage {
+    pub name: String,
+    pub rx_mbps: f64,
+    pub tx_mbps: f64,
+    pub billed: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct DiskUsage {
     pub mount_point: String,
     pub total_bytes: u64,
     pub used_bytes: u64,
     pub available_bytes: u64,
+    pub io: DiskIoRates,
 }
 
 #[derive(Debug, Clone)]
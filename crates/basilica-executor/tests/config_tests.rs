@@ -4,7 +4,7 @@ use basilica_executor::config::docker::DockerConfigValidation;
 use basilica_executor::config::system::SystemConfigValidation;
 use basilica_executor::config::{
     ContainerNetworkConfig, ContainerRegistryConfig, ContainerResourceLimits, DockerConfig,
-    ExecutorConfig, PortMapping, SystemConfig,
+    ExecutorConfig, ImagePolicyConfig, PortMapping, SystemConfig,
 };
 use basilica_executor::validation_session::{
     AccessControlConfig, HotkeyVerificationConfig, RateLimitConfig, ValidatorConfig, ValidatorRole,
@@ -319,3 +319,196 @@ fn test_validator_config_custom() {
         .required_permissions
         .contains_key("execute"));
 }
+
+#[test]
+fn test_sighup_reload_applies_threshold_change() {
+    use basilica_executor::config::reload::ConfigReloader;
+    use basilica_executor::system_monitor::SystemMonitor;
+    use std::sync::Arc;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("executor.toml");
+
+    let initial = ExecutorConfig::default();
+    std::fs::write(&path, toml::to_string_pretty(&initial).unwrap()).unwrap();
+
+    let monitor = Arc::new(SystemMonitor::new(initial.system.clone()).unwrap());
+    let mut reloader = ConfigReloader::new(path.clone(), initial.clone(), monitor.clone());
+
+    let mut edited = initial.clone();
+    edited.system.max_memory_usage = 70.0;
+    edited.server.port = 60001;
+    std::fs::write(&path, toml::to_string_pretty(&edited).unwrap()).unwrap();
+
+    let report = reloader.reload().unwrap();
+
+    assert_eq!(monitor.config().max_memory_usage, 70.0);
+    assert_eq!(reloader.current().system.max_memory_usage, 70.0);
+    assert!(report
+        .applied
+        .contains(&"system.max_memory_usage".to_string()));
+    assert_eq!(report.requires_restart, vec!["server.port".to_string()]);
+    assert_eq!(reloader.current().server.port, initial.server.port);
+
+    // An invalid file is rejected and leaves the running config in place
+    let mut invalid = edited.clone();
+    invalid.system.max_cpu_usage = 150.0;
+    std::fs::write(&path, toml::to_string_pretty(&invalid).unwrap()).unwrap();
+
+    assert!(reloader.reload().is_err());
+    assert_eq!(monitor.config().max_cpu_usage, initial.system.max_cpu_usage);
+}
+
+fn remote_executor_config(advertised_host: &str) -> ExecutorConfig {
+    let mut config = ExecutorConfig::default();
+    config.server.advertised_host = Some(advertised_host.to_string());
+    config.miner_registration.endpoint = Some("http://198.51.100.7:8080".to_string());
+    config
+}
+
+#[test]
+fn test_advertised_loopback_rejected_for_remote_miner() {
+    let config = remote_executor_config("127.0.0.1");
+
+    let err = config.validate_advertised_endpoints().unwrap_err();
+    assert!(err.contains("loopback"), "unexpected error: {err}");
+    assert!(err.contains("127.0.0.1"), "unexpected error: {err}");
+
+    let config = remote_executor_config("localhost");
+    assert!(config.validate_advertised_endpoints().is_err());
+}
+
+#[test]
+fn test_advertised_unspecified_rejected_for_remote_miner() {
+    let mut config = ExecutorConfig::default();
+    config.miner_registration.endpoint = Some("http://198.51.100.7:8080".to_string());
+
+    // No advertised_host: the 0.0.0.0 bind address would be advertised
+    let err = config.validate_advertised_endpoints().unwrap_err();
+    assert!(err.contains("unspecified"), "unexpected error: {err}");
+
+    let mut config = remote_executor_config("203.0.113.10");
+    config.advertised_endpoint.ssh_endpoint = Some("ssh://[::]:22".to_string());
+    let err = config.validate_advertised_endpoints().unwrap_err();
+    assert!(err.contains("SSH"), "unexpected error: {err}");
+}
+
+#[test]
+fn test_advertised_private_address_depends_on_strictness() {
+    use basilica_executor::config::AdvertisedAddressCheck;
+
+    // Miner on a public IP cannot reach a private advertised address
+    let config = remote_executor_config("10.0.0.5");
+    let err = config.validate_advertised_endpoints().unwrap_err();
+    assert!(err.contains("private"), "unexpected error: {err}");
+
+    // Miner on the same private network can
+    let mut config = remote_executor_config("10.0.0.5");
+    config.miner_registration.endpoint = Some("http://10.0.0.2:8080".to_string());
+    assert!(config.validate_advertised_endpoints().is_ok());
+
+    config.advertised_endpoint.address_check = AdvertisedAddressCheck::Strict;
+    assert!(config.validate_advertised_endpoints().is_err());
+
+    config.advertised_endpoint.address_check = AdvertisedAddressCheck::Off;
+    assert!(config.validate_advertised_endpoints().is_ok());
+}
+
+#[test]
+fn test_advertised_public_endpoints_accepted() {
+    let mut config = remote_executor_config("203.0.113.10");
+    assert!(config.validate_advertised_endpoints().is_ok());
+
+    config.advertised_endpoint.grpc_endpoint = Some("https://executor.example.com:443".to_string());
+    config.advertised_endpoint.ssh_endpoint = Some("ssh://203.0.113.10:2222".to_string());
+    assert!(config.validate_advertised_endpoints().is_ok());
+
+    // Local development without a remote miner is not checked
+    let config = ExecutorConfig::default();
+    assert!(config.validate_advertised_endpoints().is_ok());
+}
+
+#[test]
+fn test_image_policy_allows_everything_by_default() {
+    let policy = ImagePolicyConfig::default();
+
+    assert!(policy.check("ubuntu:22.04").is_ok());
+    assert!(policy.check("ghcr.io/anyone/anything").is_ok());
+}
+
+#[test]
+fn test_image_policy_allowlist() {
+    let policy = ImagePolicyConfig {
+        allow: vec!["nvidia/cuda".to_string(), "ubuntu:22.04".to_string()],
+        deny: vec![],
+    };
+
+    assert!(policy.check("nvidia/cuda:12.2.0-base-ubuntu22.04").is_ok());
+    assert!(policy.check("docker.io/nvidia/cuda@sha256:abcd").is_ok());
+    assert!(policy.check("docker.io/library/ubuntu:22.04").is_ok());
+
+    let err = policy.check("ubuntu:20.04").unwrap_err();
+    assert!(err.contains("not in the image policy allowlist"));
+    assert!(policy.check("ubuntu").is_err());
+}
+
+#[test]
+fn test_image_policy_denylist_takes_precedence() {
+    let policy = ImagePolicyConfig {
+        allow: vec!["docker.io/*".to_string()],
+        deny: vec!["alpine/socat".to_string()],
+    };
+
+    let err = policy.check("alpine/socat:latest").unwrap_err();
+    assert!(err.contains("denied by image policy pattern 'alpine/socat'"));
+    assert!(policy.check("alpine:3.19").is_ok());
+}
+
+#[test]
+fn test_image_policy_glob_patterns() {
+    let policy = ImagePolicyConfig {
+        allow: vec!["ghcr.io/acme/*".to_string(), "nvidia/cuda:12.*".to_string()],
+        deny: vec!["*:latest".to_string()],
+    };
+
+    assert!(policy.check("ghcr.io/acme/trainer:v1").is_ok());
+    assert!(policy.check("ghcr.io/acme/team/inference:2024.06").is_ok());
+    assert!(policy
+        .check("nvidia/cuda:12.4.1-runtime-ubuntu22.04")
+        .is_ok());
+
+    assert!(policy.check("ghcr.io/other/trainer:v1").is_err());
+    assert!(policy.check("nvidia/cuda:11.8.0-base-ubuntu22.04").is_err());
+    // Untagged images resolve to `latest`, which is denied
+    assert!(policy.check("ghcr.io/acme/trainer").is_err());
+}
+
+#[test]
+fn test_static_labels_loaded_from_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("executor.toml");
+    std::fs::write(
+        &path,
+        "[static_labels]\ncluster = \"eu-1\"\nrack_id = \"r12\"\n",
+    )
+    .unwrap();
+
+    let config = ExecutorConfig::load_from_file(&path).unwrap();
+    assert_eq!(config.static_labels["cluster"], "eu-1");
+    assert_eq!(config.static_labels["rack_id"], "r12");
+}
+
+#[test]
+fn test_illegal_static_label_name_rejected_at_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("executor.toml");
+
+    for name in ["\"cluster-name\"", "\"1rack\"", "__reserved"] {
+        std::fs::write(&path, format!("[static_labels]\n{name} = \"x\"\n")).unwrap();
+        let err = ExecutorConfig::load_from_file(&path).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("invalid static label name"),
+            "unexpected error for {name}: {err:#}"
+        );
+    }
+}
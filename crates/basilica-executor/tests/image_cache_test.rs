@@ -0,0 +1,184 @@
+//! Tests for image pre-pulling, digest pinning and authenticated pulls
+
+use anyhow::Result;
+use async_trait::async_trait;
+use basilica_common::logging::capture::CapturedLogs;
+use basilica_executor::container_manager::images::{
+    ImageBackend, ImageCache, ImagePullProgress, PullProgressFn,
+};
+use basilica_executor::container_manager::registry_auth::{
+    RegistryCredentials, RegistrySecretStore,
+};
+use basilica_protocol::common::RegistryAuth;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const DIGEST: &str = "nvidia/cuda@sha256:0123456789abcdef";
+
+/// In-memory registry and local image store that records pulls
+#[derive(Default)]
+struct FakeDocker {
+    local: Mutex<HashMap<String, Vec<String>>>,
+    pulls: Mutex<Vec<String>>,
+    auths: Mutex<Vec<Option<RegistryCredentials>>>,
+}
+
+impl FakeDocker {
+    fn pulls(&self) -> Vec<String> {
+        self.pulls.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl ImageBackend for FakeDocker {
+    async fn local_digests(&self, image: &str) -> Result<Option<Vec<String>>> {
+        Ok(self.local.lock().unwrap().get(image).cloned())
+    }
+
+    async fn pull(
+        &self,
+        image: &str,
+        auth: Option<&RegistryCredentials>,
+        on_progress: PullProgressFn<'_>,
+    ) -> Result<()> {
+        self.pulls.lock().unwrap().push(image.to_string());
+        self.auths.lock().unwrap().push(auth.cloned());
+        on_progress(&ImagePullProgress {
+            image: image.to_string(),
+            layers_total: 1,
+            layers_done: 1,
+            bytes_done: 1024,
+            bytes_total: 1024,
+        });
+
+        let mut local = self.local.lock().unwrap();
+        local.insert(image.to_string(), vec![DIGEST.to_string()]);
+        local.insert(DIGEST.to_string(), vec![DIGEST.to_string()]);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_prepulled_image_skips_pull_on_rental_start() {
+    let docker = Arc::new(FakeDocker::default());
+    let cache = ImageCache::new(docker.clone(), false);
+    let image = "nvidia/cuda:12.2.0-base-ubuntu22.04";
+
+    cache.prepull(&[image.to_string()]).await;
+    assert_eq!(docker.pulls(), vec![image.to_string()]);
+
+    let reference = cache.ensure(image).await.unwrap();
+    assert_eq!(reference, image);
+    assert_eq!(docker.pulls().len(), 1, "rental start must not pull again");
+}
+
+#[tokio::test]
+async fn test_pinned_digest_is_reused() {
+    let docker = Arc::new(FakeDocker::default());
+    let cache = ImageCache::new(docker.clone(), true);
+    let image = "nvidia/cuda:12.2.0-base-ubuntu22.04";
+
+    assert_eq!(cache.ensure(image).await.unwrap(), DIGEST);
+    assert_eq!(cache.pinned_digest(image).await.as_deref(), Some(DIGEST));
+
+    // The tag moving on the host does not change what rentals get
+    docker.local.lock().unwrap().insert(
+        image.to_string(),
+        vec!["nvidia/cuda@sha256:ffff".to_string()],
+    );
+    assert_eq!(cache.ensure(image).await.unwrap(), DIGEST);
+    assert_eq!(docker.pulls().len(), 1);
+}
+
+#[tokio::test]
+async fn test_pull_progress_is_published() {
+    let docker = Arc::new(FakeDocker::default());
+    let cache = ImageCache::new(docker.clone(), false);
+    let mut progress = cache.subscribe_progress();
+    let image = "nvidia/cuda:12.2.0-base-ubuntu22.04";
+
+    cache.ensure(image).await.unwrap();
+
+    let update = progress.try_recv().expect("pull should report progress");
+    assert_eq!(update.image, image);
+    assert_eq!(update.bytes_done, 1024);
+    assert!(update.is_complete());
+}
+
+#[tokio::test]
+async fn test_failed_prepull_does_not_abort() {
+    struct Offline;
+
+    #[async_trait]
+    impl ImageBackend for Offline {
+        async fn local_digests(&self, _image: &str) -> Result<Option<Vec<String>>> {
+            Ok(None)
+        }
+
+        async fn pull(
+            &self,
+            image: &str,
+            _auth: Option<&RegistryCredentials>,
+            _on_progress: PullProgressFn<'_>,
+        ) -> Result<()> {
+            anyhow::bail!("registry unreachable for {image}")
+        }
+    }
+
+    let cache = ImageCache::new(Arc::new(Offline), false);
+    cache
+        .prepull(&["ubuntu:22.04".to_string(), "ubuntu:24.04".to_string()])
+        .await;
+    assert!(cache.ensure("ubuntu:22.04").await.is_err());
+}
+
+#[tokio::test]
+async fn test_registry_auth_is_passed_to_pull_and_redacted_in_logs() {
+    const TOKEN: &str = "ghp_very-secret-token";
+
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(logs.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let auth = RegistryAuth {
+        registry: "https://ghcr.io".to_string(),
+        username: "acme-ci".to_string(),
+        token: TOKEN.to_string(),
+        secret_ref: String::new(),
+    };
+    let credentials = RegistrySecretStore::default().resolve(&auth).unwrap();
+
+    let docker = Arc::new(FakeDocker::default());
+    let cache = ImageCache::new(docker.clone(), false);
+    let image = "ghcr.io/acme/trainer:1.0";
+    cache
+        .ensure_with_auth(image, Some(&credentials))
+        .await
+        .unwrap();
+
+    let auths = docker.auths.lock().unwrap().clone();
+    assert_eq!(auths.len(), 1);
+    let passed = auths[0].as_ref().expect("pull must be authenticated");
+    assert_eq!(passed.registry, "ghcr.io");
+    assert_eq!(passed.username, "acme-ci");
+    assert_eq!(passed.token(), TOKEN);
+    let docker_credentials = passed.to_docker_credentials();
+    assert_eq!(docker_credentials.password.as_deref(), Some(TOKEN));
+    assert_eq!(docker_credentials.serveraddress.as_deref(), Some("ghcr.io"));
+
+    let output = logs.contents();
+    assert!(output.contains(image), "pull should be logged: {output}");
+    assert!(output.contains("<redacted>"));
+    assert!(!output.contains(TOKEN), "token leaked into logs: {output}");
+
+    // Credentials are never handed to a registry they do not belong to
+    assert!(cache
+        .ensure_with_auth("docker.io/library/ubuntu:22.04", Some(&credentials))
+        .await
+        .is_err());
+    assert_eq!(docker.pulls().len(), 1);
+}
@@ -0,0 +1,220 @@
+//! Tests for executor registration against a mock miner gRPC server
+
+use basilica_executor::config::MinerRegistrationConfig;
+use basilica_executor::miner_registration::{MinerRegistrar, RegistrationDetails};
+use basilica_executor::system_monitor::SystemProfile;
+use basilica_protocol::executor_registration::{
+    executor_registration_server::{ExecutorRegistration, ExecutorRegistrationServer},
+    HeartbeatRequest, HeartbeatResponse, RegisterExecutorRequest, RegisterExecutorResponse,
+    UnregisterExecutorRequest, UnregisterExecutorResponse, UpdateExecutorStatusRequest,
+    UpdateExecutorStatusResponse,
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status};
+
+const TOKEN: &str = "registration-token";
+
+/// Calls observed by the mock miner
+#[derive(Default)]
+struct MinerState {
+    registrations: AtomicU32,
+    heartbeats: AtomicU32,
+    last_request: Mutex<Option<RegisterExecutorRequest>>,
+}
+
+/// Mock miner that rejects the first `reject_first` registrations
+struct MockMiner {
+    reject_first: u32,
+    state: Arc<MinerState>,
+}
+
+#[tonic::async_trait]
+impl ExecutorRegistration for MockMiner {
+    async fn register_executor(
+        &self,
+        request: Request<RegisterExecutorRequest>,
+    ) -> Result<Response<RegisterExecutorResponse>, Status> {
+        let attempt = self.state.registrations.fetch_add(1, Ordering::SeqCst) + 1;
+        *self.state.last_request.lock().unwrap() = Some(request.into_inner());
+
+        if attempt <= self.reject_first {
+            return Err(Status::unavailable("miner starting up"));
+        }
+
+        Ok(Response::new(RegisterExecutorResponse {
+            success: true,
+            registration_token: TOKEN.to_string(),
+            heartbeat_interval_seconds: 1,
+            ..Default::default()
+        }))
+    }
+
+    async fn update_executor_status(
+        &self,
+        _request: Request<UpdateExecutorStatusRequest>,
+    ) -> Result<Response<UpdateExecutorStatusResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn unregister_executor(
+        &self,
+        _request: Request<UnregisterExecutorRequest>,
+    ) -> Result<Response<UnregisterExecutorResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn heartbeat(
+        &self,
+        request: Request<HeartbeatRequest>,
+    ) -> Result<Response<HeartbeatResponse>, Status> {
+        let request = request.into_inner();
+        let acknowledged = request.registration_token == TOKEN;
+        if acknowledged {
+            self.state.heartbeats.fetch_add(1, Ordering::SeqCst);
+        }
+
+        Ok(Response::new(HeartbeatResponse {
+            acknowledged,
+            ..Default::default()
+        }))
+    }
+}
+
+async fn start_mock_miner(reject_first: u32) -> (String, Arc<MinerState>) {
+    let state = Arc::new(MinerState::default());
+    let miner = MockMiner {
+        reject_first,
+        state: state.clone(),
+    };
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .add_service(ExecutorRegistrationServer::new(miner))
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+
+    (format!("http://{addr}"), state)
+}
+
+fn test_registrar(endpoint: String) -> MinerRegistrar {
+    let config = MinerRegistrationConfig {
+        endpoint: Some(endpoint),
+        initial_backoff_ms: 10,
+        max_backoff_secs: 1,
+        ..Default::default()
+    };
+    let details = RegistrationDetails {
+        executor_id: "executor-1".to_string(),
+        grpc_endpoint: "http://203.0.113.10:50051".to_string(),
+        ssh_endpoint: "ssh://203.0.113.10:22".to_string(),
+        health_endpoint: "http://203.0.113.10:50052/health".to_string(),
+        miner_hotkey: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+        gpus: Vec::new(),
+    };
+
+    MinerRegistrar::new(config, details).unwrap()
+}
+
+#[tokio::test]
+async fn test_registration_retries_until_acknowledged() {
+    let (endpoint, miner) = start_mock_miner(2).await;
+
+    let registration = tokio::time::timeout(
+        Duration::from_secs(10),
+        test_registrar(endpoint).register_with_retry(),
+    )
+    .await
+    .expect("registration should succeed after retries");
+
+    assert_eq!(registration.token, TOKEN);
+    assert_eq!(registration.heartbeat_interval, Duration::from_secs(1));
+    assert_eq!(miner.registrations.load(Ordering::SeqCst), 3);
+
+    let request = miner.last_request.lock().unwrap().clone().unwrap();
+    assert_eq!(request.executor_id, "executor-1");
+    assert_eq!(request.grpc_address, "http://203.0.113.10:50051");
+    assert_eq!(request.metadata["ssh_endpoint"], "ssh://203.0.113.10:22");
+    assert_eq!(
+        request.metadata["health_endpoint"],
+        "http://203.0.113.10:50052/health"
+    );
+}
+
+#[tokio::test]
+async fn test_background_registration_sends_heartbeats() {
+    let (endpoint, miner) = start_mock_miner(0).await;
+
+    let handle = test_registrar(endpoint).spawn();
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    while miner.heartbeats.load(Ordering::SeqCst) < 2 {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "expected heartbeats from the executor"
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    handle.abort();
+
+    assert_eq!(miner.registrations.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_registration_request_carries_gpu_topology() {
+    let profile: SystemProfile = serde_json::from_value(serde_json::json!({
+        "cpu": {"model": "AMD EPYC 9354", "cores": 64, "vendor": "AMD"},
+        "memory": {"total_gb": 512.0},
+        "storage": {"total_gb": 2048.0},
+        "os": {"os_type": "Linux", "version": "22.04"},
+        "docker": {"version": "24.0.7"},
+        "gpus": [{
+            "index": 0,
+            "name": "NVIDIA H100 80GB HBM3",
+            "memory_total_bytes": 85_899_345_920u64,
+            "uuid": "GPU-00000000-aaaa-bbbb-cccc-dddddddddddd",
+            "pci_bus_id": "00000000:18:00.0",
+            "numa_node": 0
+        }]
+    }))
+    .unwrap();
+
+    let details = RegistrationDetails {
+        executor_id: "executor-1".to_string(),
+        grpc_endpoint: String::new(),
+        ssh_endpoint: String::new(),
+        health_endpoint: String::new(),
+        miner_hotkey: String::new(),
+        gpus: Vec::new(),
+    };
+    assert!(details.to_request().system_profile.is_none());
+
+    let request = details.with_gpus_from_profile(&profile).to_request();
+    let gpus = request.system_profile.unwrap().gpus;
+    assert_eq!(gpus.len(), 1);
+    assert_eq!(gpus[0].uuid, "GPU-00000000-aaaa-bbbb-cccc-dddddddddddd");
+    assert_eq!(gpus[0].pci_bus_id.as_deref(), Some("00000000:18:00.0"));
+    assert_eq!(gpus[0].numa_node, Some(0));
+    assert_eq!(gpus[0].memory_mb, 81_920);
+}
+
+#[test]
+fn test_registrar_requires_endpoint() {
+    let details = RegistrationDetails {
+        executor_id: "executor-1".to_string(),
+        grpc_endpoint: String::new(),
+        ssh_endpoint: String::new(),
+        health_endpoint: String::new(),
+        miner_hotkey: String::new(),
+        gpus: Vec::new(),
+    };
+
+    assert!(MinerRegistrar::new(MinerRegistrationConfig::default(), details).is_err());
+}
@@ -1,4 +1,4 @@
-use basilica_executor::config::types::{TelemetryConfig, TelemetryMonitorConfig};
+use basilica_executor::config::types::{QueuePolicy, TelemetryConfig, TelemetryMonitorConfig};
 use basilica_protocol::billing::TelemetryData;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -15,8 +15,10 @@ async fn test_monitoring_sends_data() {
         enabled: true,
         host_interval_secs: 1, // Fast interval for testing
         queue_capacity: 100,
+        queue_policy: QueuePolicy::default(),
         container_sample_secs: 1,
         update_lifecycle_status: false, // Disable lifecycle updates for test
+        billed_interfaces: vec![],
     };
 
     // This will fail to connect to a telemetry service, but we can at least
@@ -34,6 +36,8 @@ async fn test_monitoring_sends_data() {
         monitor_cfg,
         telemetry_cfg,
         None, // No metrics recorder for this test
+        Default::default(),
+        None,
     );
 
     // Wait a bit to see if we receive any data
@@ -67,7 +71,9 @@ async fn test_collector_generates_host_data() {
         host_interval_secs: 1,
         container_sample_secs: 1,
         queue_capacity: 100,
+        queue_policy: QueuePolicy::default(),
         update_lifecycle_status: false,
+        billed_interfaces: vec![],
     };
 
     // Create collector
@@ -0,0 +1,83 @@
+//! Tests for the non-GPU executor self-test checks
+
+use basilica_executor::cli::handlers::selftest::{
+    check_docker_socket, check_ports_bindable, CheckResult, CheckStatus, SelfTestReport,
+};
+use basilica_executor::config::ExecutorConfig;
+use std::net::TcpListener;
+
+fn config_with_port(port: u16) -> ExecutorConfig {
+    let mut config = ExecutorConfig::default();
+    config.server.host = "127.0.0.1".to_string();
+    config.server.port = port;
+    config
+}
+
+#[tokio::test]
+async fn test_missing_docker_socket_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = dir.path().join("docker.sock");
+
+    let (check, docker) = check_docker_socket(&format!("unix://{}", socket.display())).await;
+
+    assert_eq!(check.status, CheckStatus::Fail);
+    assert!(check.detail.contains("does not exist"));
+    assert!(docker.is_none());
+}
+
+#[tokio::test]
+async fn test_non_socket_docker_path_fails() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+
+    let (check, docker) = check_docker_socket(&file.path().to_string_lossy()).await;
+
+    assert_eq!(check.status, CheckStatus::Fail);
+    assert!(docker.is_none());
+}
+
+#[test]
+fn test_free_port_is_bindable() {
+    let port = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    };
+
+    let checks = check_ports_bindable(&config_with_port(port));
+
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks[0].status, CheckStatus::Pass);
+}
+
+#[test]
+fn test_port_in_use_fails() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let mut config = config_with_port(port);
+    config
+        .advertised_endpoint
+        .port_mappings
+        .insert("health".to_string(), port);
+    let checks = check_ports_bindable(&config);
+
+    assert_eq!(checks.len(), 2);
+    assert!(checks.iter().all(|check| check.status == CheckStatus::Fail));
+    assert!(checks[0].detail.contains("already in use"));
+}
+
+#[test]
+fn test_report_counts_failures_but_not_skips() {
+    let mut report = SelfTestReport::default();
+    report.push(CheckResult::pass("Docker socket", "connected"));
+    report.push(CheckResult::skip("GPU devices", "skipped (--skip-gpu)"));
+    assert!(report.passed());
+
+    report.push(CheckResult::fail("gRPC port", "in use"));
+    assert!(!report.passed());
+    assert_eq!(report.failures(), 1);
+
+    let rendered = report.render();
+    assert_eq!(rendered.lines().count(), 3);
+    assert!(rendered.contains("[FAIL] gRPC port"));
+    assert!(rendered.contains("[SKIP] GPU devices"));
+}
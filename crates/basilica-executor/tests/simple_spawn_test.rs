@@ -10,8 +10,10 @@ async fn test_spawn_monitoring_compiles() {
         enabled: true,
         host_interval_secs: 5,
         queue_capacity: 100,
+        queue_policy: basilica_executor::config::types::QueuePolicy::default(),
         container_sample_secs: 2,
         update_lifecycle_status: true,
+        billed_interfaces: vec![],
     };
 
     let telemetry_cfg = basilica_executor::config::types::TelemetryConfig {
@@ -28,6 +30,8 @@ async fn test_spawn_monitoring_compiles() {
         monitor_cfg,
         telemetry_cfg,
         None, // No metrics recorder for this test
+        Default::default(),
+        None,
     );
 
     // Give it a moment to start then stop the test
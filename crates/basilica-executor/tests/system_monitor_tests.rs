@@ -2,8 +2,8 @@
 
 use basilica_executor::config::SystemConfig;
 use basilica_executor::system_monitor::{
-    BasicSystemInfo, CpuInfo, DiskInfo, GpuInfo, MemoryInfo, NetworkInfo, NetworkInterface,
-    SystemInfo, SystemMonitor,
+    BasicSystemInfo, CpuInfo, DiskInfo, DiskIoRates, GpuInfo, MemoryInfo, NetworkInfo,
+    NetworkInterface, SystemInfo, SystemMonitor, SystemProfile,
 };
 use std::time::Duration;
 
@@ -69,6 +69,8 @@ fn test_memory_info_struct() {
         usage_percent: 50.0,
         swap_total_bytes: 8 * 1024 * 1024 * 1024, // 8GB
         swap_used_bytes: 0,
+        host_total_bytes: 16 * 1024 * 1024 * 1024,
+        effective_limit_bytes: 16 * 1024 * 1024 * 1024,
     };
 
     assert_eq!(memory_info.total_bytes, 16 * 1024 * 1024 * 1024);
@@ -92,6 +94,10 @@ fn test_gpu_info_struct() {
         power_usage_watts: 250.0,
         driver_version: "525.60.13".to_string(),
         cuda_version: Some("12.0".to_string()),
+        uuid: "GPU-12345678-1234-1234-1234-123456789012".to_string(),
+        pci_bus_id: Some("00000000:3B:00.0".to_string()),
+        numa_node: Some(0),
+        mig: None,
     };
 
     assert_eq!(gpu_info.index, 0);
@@ -116,6 +122,7 @@ fn test_disk_info_struct() {
         available_bytes: 400 * 1024 * 1024 * 1024, // 400GB
         usage_percent: 20.0,
         filesystem: "ext4".to_string(),
+        io: DiskIoRates::default(),
     };
 
     assert_eq!(disk_info.name, "/dev/sda1");
@@ -138,6 +145,9 @@ fn test_network_interface_struct() {
         errors_sent: 0,
         errors_received: 0,
         is_up: true,
+        rx_mbps: 0.0,
+        tx_mbps: 0.0,
+        billed: true,
     };
 
     assert_eq!(interface.name, "eth0");
@@ -161,6 +171,9 @@ fn test_network_info_struct() {
         errors_sent: 0,
         errors_received: 0,
         is_up: true,
+        rx_mbps: 0.0,
+        tx_mbps: 0.0,
+        billed: true,
     };
 
     let network_info = NetworkInfo {
@@ -230,6 +243,8 @@ fn test_system_info_struct() {
         usage_percent: 50.0,
         swap_total_bytes: 8 * 1024 * 1024 * 1024,
         swap_used_bytes: 0,
+        host_total_bytes: 16 * 1024 * 1024 * 1024,
+        effective_limit_bytes: 16 * 1024 * 1024 * 1024,
     };
 
     let basic_info = BasicSystemInfo {
@@ -262,6 +277,84 @@ fn test_system_info_struct() {
     assert_eq!(system_info.timestamp, 1234567890);
 }
 
+fn gpu(index: u32, bus: &str, numa_node: Option<i32>) -> GpuInfo {
+    GpuInfo {
+        index,
+        name: "NVIDIA H100 80GB HBM3".to_string(),
+        memory_total_bytes: 80 * 1024 * 1024 * 1024,
+        memory_used_bytes: 0,
+        memory_usage_percent: 0.0,
+        utilization_percent: 0.0,
+        temperature_celsius: 40.0,
+        power_usage_watts: 70.0,
+        driver_version: "550.54.15".to_string(),
+        cuda_version: Some("12.4".to_string()),
+        uuid: format!("GPU-0000000{index}-aaaa-bbbb-cccc-dddddddddddd"),
+        pci_bus_id: Some(bus.to_string()),
+        numa_node,
+    }
+}
+
+#[test]
+fn test_system_profile_includes_per_device_gpu_topology() {
+    let system_info = SystemInfo {
+        cpu: CpuInfo {
+            usage_percent: 0.0,
+            cores: 64,
+            frequency_mhz: 2000,
+            model: "AMD EPYC 9354".to_string(),
+            vendor: "AMD".to_string(),
+            temperature_celsius: None,
+        },
+        memory: MemoryInfo {
+            total_bytes: 512 * 1024 * 1024 * 1024,
+            used_bytes: 0,
+            available_bytes: 512 * 1024 * 1024 * 1024,
+            usage_percent: 0.0,
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            host_total_bytes: 512 * 1024 * 1024 * 1024,
+            effective_limit_bytes: 512 * 1024 * 1024 * 1024,
+        },
+        gpu: vec![
+            gpu(0, "00000000:18:00.0", Some(0)),
+            gpu(1, "00000000:9A:00.0", Some(1)),
+        ],
+        disk: vec![],
+        network: NetworkInfo {
+            interfaces: vec![],
+            total_bytes_sent: 0,
+            total_bytes_received: 0,
+        },
+        system: BasicSystemInfo {
+            hostname: "gpu-host".to_string(),
+            os_name: "Linux".to_string(),
+            os_version: "22.04".to_string(),
+            kernel_version: "6.5.0".to_string(),
+            uptime_seconds: 0,
+            boot_time: 0,
+            load_average: vec![],
+        },
+        timestamp: 0,
+    };
+
+    let profile = SystemProfile::from_system_info(system_info, "24.0.7".to_string());
+
+    assert_eq!(profile.gpus.len(), 2);
+    let second = &profile.gpus[1];
+    assert_eq!(second.index, 1);
+    assert_eq!(second.uuid, "GPU-00000001-aaaa-bbbb-cccc-dddddddddddd");
+    assert_eq!(second.pci_bus_id.as_deref(), Some("00000000:9A:00.0"));
+    assert_eq!(second.numa_node, Some(1));
+    assert_ne!(profile.gpus[0].uuid, second.uuid);
+
+    // Older profiles without GPU topology still deserialize
+    let mut json = serde_json::to_value(&profile).unwrap();
+    json.as_object_mut().unwrap().remove("gpus");
+    let legacy: SystemProfile = serde_json::from_value(json).unwrap();
+    assert!(legacy.gpus.is_empty());
+}
+
 #[tokio::test]
 async fn test_monitor_with_disabled_features() {
     let config = SystemConfig {
@@ -287,6 +380,8 @@ fn test_memory_calculations() {
         usage_percent: 75.0,
         swap_total_bytes: 8 * 1024 * 1024 * 1024, // 8GB
         swap_used_bytes: 2 * 1024 * 1024 * 1024,  // 2GB
+        host_total_bytes: 16 * 1024 * 1024 * 1024,
+        effective_limit_bytes: 16 * 1024 * 1024 * 1024,
     };
 
     // Verify calculations
@@ -315,6 +410,7 @@ fn test_disk_usage_calculations() {
         available_bytes: 700 * 1024 * 1024 * 1024, // 700GB
         usage_percent: 30.0,
         filesystem: "ext4".to_string(),
+        io: DiskIoRates::default(),
     };
 
     // Verify calculations
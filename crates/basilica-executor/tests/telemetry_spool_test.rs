@@ -0,0 +1,234 @@
+//! Tests for spooling telemetry to disk while billing is unreachable
+
+use basilica_executor::config::types::QueuePolicy;
+use basilica_executor::system_monitor::spool::{SpoolConfig, TelemetrySpool};
+use basilica_executor::system_monitor::stream::{self, StreamConfig};
+use basilica_executor::system_monitor::telemetry_queue;
+use basilica_protocol::billing::{
+    billing_service_server::{BillingService, BillingServiceServer},
+    ApplyCreditsRequest, ApplyCreditsResponse, FinalizeRentalRequest, FinalizeRentalResponse,
+    GetActiveRentalsRequest, GetActiveRentalsResponse, GetBalanceRequest, GetBalanceResponse,
+    GetBillingPackagesRequest, GetBillingPackagesResponse, IngestResponse,
+    ReleaseReservationRequest, ReleaseReservationResponse, ReserveCreditsRequest,
+    ReserveCreditsResponse, SetUserPackageRequest, SetUserPackageResponse, TelemetryData,
+    TrackRentalRequest, TrackRentalResponse, UpdateRentalStatusRequest, UpdateRentalStatusResponse,
+    UsageReportRequest, UsageReportResponse,
+};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{Request, Response, Status, Streaming};
+
+/// Mock billing service that records every telemetry sample it receives
+#[derive(Clone, Default)]
+struct MockBilling {
+    received: Arc<Mutex<Vec<TelemetryData>>>,
+}
+
+#[tonic::async_trait]
+impl BillingService for MockBilling {
+    async fn apply_credits(
+        &self,
+        _request: Request<ApplyCreditsRequest>,
+    ) -> Result<Response<ApplyCreditsResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn get_balance(
+        &self,
+        _request: Request<GetBalanceRequest>,
+    ) -> Result<Response<GetBalanceResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn reserve_credits(
+        &self,
+        _request: Request<ReserveCreditsRequest>,
+    ) -> Result<Response<ReserveCreditsResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn release_reservation(
+        &self,
+        _request: Request<ReleaseReservationRequest>,
+    ) -> Result<Response<ReleaseReservationResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn track_rental(
+        &self,
+        _request: Request<TrackRentalRequest>,
+    ) -> Result<Response<TrackRentalResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn update_rental_status(
+        &self,
+        _request: Request<UpdateRentalStatusRequest>,
+    ) -> Result<Response<UpdateRentalStatusResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn get_active_rentals(
+        &self,
+        _request: Request<GetActiveRentalsRequest>,
+    ) -> Result<Response<GetActiveRentalsResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn finalize_rental(
+        &self,
+        _request: Request<FinalizeRentalRequest>,
+    ) -> Result<Response<FinalizeRentalResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn ingest_telemetry(
+        &self,
+        request: Request<Streaming<TelemetryData>>,
+    ) -> Result<Response<IngestResponse>, Status> {
+        let mut stream = request.into_inner();
+        let mut count = 0;
+        while let Some(sample) = stream.message().await? {
+            self.received.lock().unwrap().push(sample);
+            count += 1;
+        }
+
+        Ok(Response::new(IngestResponse {
+            events_received: count,
+            events_processed: count,
+            ..Default::default()
+        }))
+    }
+
+    async fn get_usage_report(
+        &self,
+        _request: Request<UsageReportRequest>,
+    ) -> Result<Response<UsageReportResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn get_billing_packages(
+        &self,
+        _request: Request<GetBillingPackagesRequest>,
+    ) -> Result<Response<GetBillingPackagesResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn set_user_package(
+        &self,
+        _request: Request<SetUserPackageRequest>,
+    ) -> Result<Response<SetUserPackageResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+}
+
+async fn start_mock_billing(addr: SocketAddr) -> MockBilling {
+    let billing = MockBilling::default();
+    let listener = TcpListener::bind(addr).await.unwrap();
+    let service = billing.clone();
+
+    tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .add_service(BillingServiceServer::new(service))
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+
+    billing
+}
+
+fn sample(seq: i64) -> TelemetryData {
+    TelemetryData {
+        rental_id: format!("rental-{seq}"),
+        executor_id: "executor-1".to_string(),
+        timestamp: Some(prost_types::Timestamp {
+            seconds: 1_700_000_000 + seq,
+            nanos: 0,
+        }),
+        ..Default::default()
+    }
+}
+
+async fn wait_for(mut condition: impl FnMut() -> bool) {
+    tokio::time::timeout(Duration::from_secs(15), async {
+        while !condition() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("condition not met in time");
+}
+
+#[tokio::test]
+async fn test_samples_from_outage_are_replayed_after_recovery() {
+    // Reserve an address with nothing listening on it to simulate billing being down
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let spool_dir = tempfile::tempdir().unwrap();
+    let spool = Arc::new(
+        TelemetrySpool::open(SpoolConfig {
+            dir: spool_dir.path().to_path_buf(),
+            max_bytes: 1024 * 1024,
+            max_age: Duration::from_secs(3600),
+        })
+        .unwrap(),
+    );
+
+    let (tx, rx) = telemetry_queue::bounded(16, QueuePolicy::DropNewest);
+    let cfg = StreamConfig {
+        url: format!("http://{addr}"),
+        api_key: None,
+        api_key_header: "x-api-key".to_string(),
+        queue_capacity: 16,
+        queue_policy: QueuePolicy::DropNewest,
+        static_labels: [("cluster".to_string(), "eu-1".to_string())].into(),
+    };
+    let runner = tokio::spawn(stream::run(cfg, rx, Some(spool.clone())));
+
+    for seq in 0..5 {
+        tx.push(sample(seq)).await;
+    }
+    wait_for(|| spool.len() == 5).await;
+
+    // Billing comes back; spooled samples go first, then live ones
+    let billing = start_mock_billing(addr).await;
+    for seq in 5..7 {
+        tx.push(sample(seq)).await;
+    }
+    wait_for(|| billing.received.lock().unwrap().len() >= 7).await;
+
+    let received: Vec<String> = billing
+        .received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|s| s.rental_id.clone())
+        .collect();
+    let expected: Vec<String> = (0..7).map(|seq| format!("rental-{seq}")).collect();
+    assert_eq!(received, expected);
+    // Static labels are added to live and replayed samples alike
+    assert!(billing.received.lock().unwrap().iter().all(|s| s
+        .labels
+        .get("cluster")
+        .map(String::as_str)
+        == Some("eu-1")));
+    // The replay is acknowledged only after billing responds
+    wait_for(|| spool.is_empty()).await;
+    assert_eq!(spool.dropped(), 0);
+
+    // Closing the queue ends the stream cleanly
+    drop(tx);
+    tokio::time::timeout(Duration::from_secs(5), runner)
+        .await
+        .expect("stream should finish once the queue closes")
+        .unwrap()
+        .unwrap();
+    assert_eq!(billing.received.lock().unwrap().len(), 7);
+}
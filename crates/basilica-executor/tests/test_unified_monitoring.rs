@@ -1,5 +1,7 @@
-use basilica_executor::config::types::{TelemetryConfig, TelemetryMonitorConfig};
-use basilica_executor::system_monitor::{collector, lifecycle, metrics, stream, types};
+use basilica_executor::config::types::{QueuePolicy, TelemetryConfig, TelemetryMonitorConfig};
+use basilica_executor::system_monitor::{
+    collector, lifecycle, metrics, stream, telemetry_queue, types,
+};
 use std::time::Duration;
 use tokio::time::timeout;
 
@@ -12,7 +14,9 @@ async fn test_collector_initialization() {
         host_interval_secs: 5,
         container_sample_secs: 3,
         queue_capacity: 100,
+        queue_policy: QueuePolicy::default(),
         update_lifecycle_status: true,
+        billed_interfaces: vec![],
     };
 
     // Create collector
@@ -58,11 +62,13 @@ async fn test_metrics_to_telemetry_conversion() {
             load_average: (1.0, 0.8, 0.5),
             network_rx_bytes: 1000000,
             network_tx_bytes: 500000,
+            network_interfaces: vec![],
             disk_usage: vec![types::DiskUsage {
                 mount_point: "/".to_string(),
                 total_bytes: 500 * 1024 * 1024 * 1024,
                 used_bytes: 100 * 1024 * 1024 * 1024,
                 available_bytes: 400 * 1024 * 1024 * 1024,
+                io: types::DiskIoRates::default(),
             }],
         }),
         container_metrics: vec![types::ContainerMetrics {
@@ -120,11 +126,21 @@ async fn test_lifecycle_manager() {
         api_key: Some("test-key".to_string()),
         api_key_header: "x-api-key".to_string(),
         queue_capacity: 100,
+        queue_policy: QueuePolicy::default(),
+        static_labels: Default::default(),
     };
 
+    let (events_tx, _events_rx) = telemetry_queue::bounded(100, QueuePolicy::DropOldest);
+
     // Start lifecycle manager in background
     let handle = tokio::spawn(async move {
-        let _ = lifecycle::run(config, stream_config).await;
+        let _ = lifecycle::run(
+            config,
+            stream_config,
+            "test-executor".to_string(),
+            events_tx,
+        )
+        .await;
     });
 
     // Let it run for a bit
@@ -146,7 +162,9 @@ async fn test_metrics_broadcast_fanout() {
         host_interval_secs: 1,
         container_sample_secs: 1,
         queue_capacity: 100,
+        queue_policy: QueuePolicy::default(),
         update_lifecycle_status: false,
+        billed_interfaces: vec![],
     };
 
     // Create collector
@@ -1,4 +1,4 @@
-use basilica_executor::config::types::TelemetryMonitorConfig;
+use basilica_executor::config::types::{QueuePolicy, TelemetryMonitorConfig};
 use basilica_executor::system_monitor::{collector, volumes::VolumeMonitor};
 use std::time::Duration;
 use tokio::time::timeout;
@@ -32,7 +32,10 @@ async fn test_collector_with_volume_monitoring() {
         host_interval_secs: 60,
         container_sample_secs: 60,
         queue_capacity: 100,
+        queue_policy: QueuePolicy::default(),
         update_lifecycle_status: false,
+        billed_interfaces: vec![],
+        ..Default::default()
     };
 
     // Create collector with volume monitoring
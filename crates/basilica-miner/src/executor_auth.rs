@@ -130,6 +130,13 @@ impl AuthenticatedRequest for executor_control::ContainerOpRequest {
     }
 }
 
+impl AuthenticatedRequest for executor_control::ContainerLogsRequest {
+    fn with_auth(mut self, auth: MinerAuthentication) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+}
+
 impl AuthenticatedRequest for executor_control::HealthCheckRequest {
     fn with_auth(mut self, auth: MinerAuthentication) -> Self {
         self.auth = Some(auth);
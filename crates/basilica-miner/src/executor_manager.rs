@@ -117,6 +117,7 @@ struct ExecutorState {
     machine: RemoteMachine,
     grpc_client: Option<ExecutorControlClient<Channel>>,
     is_healthy: bool,
+    is_draining: bool,
     last_health_check: Option<Instant>,
     failed_checks: u32,
     resources: Option<ResourceUsageStats>,
@@ -241,6 +242,7 @@ impl ExecutorManager {
                     machine: machine.clone(),
                     grpc_client: None,
                     is_healthy: true,
+                    is_draining: false,
                     last_health_check: None,
                     failed_checks: 0,
                     resources: None,
@@ -419,6 +421,7 @@ impl ExecutorManager {
                 grpc_address: format!("{}:{}", s.machine.host, s.machine.executor_port),
                 resources: s.resources.clone(),
                 gpu_count: s.machine.gpu_count.unwrap_or(0),
+                draining: s.is_draining,
             })
             .collect())
     }
@@ -521,7 +524,9 @@ impl ExecutorManager {
                 response.status,
                 response.status.len()
             );
-            let is_healthy = response.status.trim() == "healthy";
+            // A draining executor is healthy, it just takes no new rentals
+            let is_draining = response.status.trim() == "draining";
+            let is_healthy = response.status.trim() == "healthy" || is_draining;
             info!(
                 "Health check result for {}: is_healthy={}",
                 machine_id, is_healthy
@@ -529,6 +534,14 @@ impl ExecutorManager {
 
             let was_healthy = executor_state.is_healthy;
             executor_state.is_healthy = is_healthy;
+            if is_draining != executor_state.is_draining {
+                info!(
+                    "Executor {} {} draining for maintenance",
+                    machine_id,
+                    if is_draining { "started" } else { "stopped" }
+                );
+            }
+            executor_state.is_draining = is_draining;
             executor_state.last_health_check = Some(Instant::now());
             executor_state.failed_checks = 0;
             executor_state.resources = self.parse_resources(&response.resource_status);
@@ -1491,6 +1504,9 @@ pub struct AvailableExecutor {
     pub grpc_address: String,
     pub resources: Option<ResourceUsageStats>,
     pub gpu_count: u32,
+    /// Draining for maintenance: still serving its rentals, but not to be
+    /// offered for new ones
+    pub draining: bool,
 }
 
 /// Deployment result
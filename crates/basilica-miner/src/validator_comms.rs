@@ -634,7 +634,12 @@ impl MinerDiscovery for MinerDiscoveryService {
                             max_gpus: exec.gpu_count,
                         }
                     }),
-                    status: "available".to_string(),
+                    status: if exec.draining {
+                        "draining"
+                    } else {
+                        "available"
+                    }
+                    .to_string(),
                 }
             })
             .collect();
@@ -869,6 +874,8 @@ fn create_gpu_spec_from_executor(
             core_clock_mhz: 0,
             memory_clock_mhz: 0,
             compute_capability: "unknown".to_string(),
+            pci_bus_id: None,
+            numa_node: None,
         })
     } else {
         // Fallback for executors without resource stats
@@ -885,6 +892,8 @@ fn create_gpu_spec_from_executor(
             core_clock_mhz: 0,
             memory_clock_mhz: 0,
             compute_capability: "unknown".to_string(),
+            pci_bus_id: None,
+            numa_node: None,
         })
     }
 }
@@ -629,6 +629,7 @@ mod tests {
                 grpc_address: "".to_string(),
                 resources: None,
                 gpu_count: 1,
+                draining: false,
             },
             AvailableExecutor {
                 id: "e2".to_string(),
@@ -636,6 +637,7 @@ mod tests {
                 grpc_address: "".to_string(),
                 resources: None,
                 gpu_count: 1,
+                draining: false,
             },
             AvailableExecutor {
                 id: "e3".to_string(),
@@ -643,6 +645,7 @@ mod tests {
                 grpc_address: "".to_string(),
                 resources: None,
                 gpu_count: 1,
+                draining: false,
             },
         ];
 
@@ -764,6 +767,7 @@ mod tests {
                 grpc_address: "".to_string(),
                 resources: None,
                 gpu_count: 1,
+                draining: false,
             },
             AvailableExecutor {
                 id: "exec2".to_string(),
@@ -771,6 +775,7 @@ mod tests {
                 grpc_address: "".to_string(),
                 resources: None,
                 gpu_count: 1,
+                draining: false,
             },
         ];
 
@@ -805,6 +810,7 @@ mod tests {
             grpc_address: "".to_string(),
             resources: None,
             gpu_count: 1,
+            draining: false,
         }];
 
         let strategy = HighestStakeAssignment::new(pool, 100.0, None);
@@ -1,7 +1,9 @@
 use crate::domain::types::Treasury;
 use anyhow::Result;
 use async_trait::async_trait;
-use basilica_common::crypto::wallet::generate_sr25519_wallet;
+use basilica_common::crypto::wallet::{
+    generate_sr25519_wallet, sign_with_sr25519, sr25519_pair_from_mnemonic,
+};
 
 pub struct LocalTreasury {
     ss58_prefix: u16,
@@ -26,11 +28,18 @@ impl Treasury for LocalTreasury {
             wallet.mnemonic,
         ))
     }
+
+    async fn sign(&self, secret: &str, payload: &[u8]) -> Result<String> {
+        let pair = sr25519_pair_from_mnemonic(secret)
+            .map_err(|e| anyhow::anyhow!("Failed to load hotkey: {}", e))?;
+        Ok(sign_with_sr25519(&pair, payload))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use basilica_common::crypto::wallet::verify_sr25519_signature;
 
     #[tokio::test]
     async fn test_local_treasury_wallet_generation() {
@@ -60,6 +69,18 @@ mod tests {
         assert_ne!(mnemonic, mnemonic2);
     }
 
+    #[tokio::test]
+    async fn test_local_treasury_signs_with_generated_hotkey() {
+        let treasury = LocalTreasury::new(42);
+        let (_, _, public_hex, mnemonic) = treasury.generate_hotkey().await.unwrap();
+
+        let signature = treasury.sign(&mnemonic, b"sweep").await.unwrap();
+        let public = hex::decode(public_hex).unwrap();
+        assert!(verify_sr25519_signature(&public, b"sweep", &signature));
+
+        assert!(treasury.sign("not a mnemonic", b"sweep").await.is_err());
+    }
+
     #[tokio::test]
     async fn test_local_treasury_different_prefixes() {
         let treasury_substrate = LocalTreasury::new(42);
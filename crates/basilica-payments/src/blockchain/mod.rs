@@ -1,2 +1,3 @@
 pub mod local_treasury;
 pub mod monitor;
+pub mod remote_treasury;
@@ -0,0 +1,352 @@
+//! Treasury backed by a remote signer
+//!
+//! Deposit hotkeys are generated and held by the signer, typically a service
+//! in front of an HSM, so their private keys never enter the payments
+//! process. The signing secret stored for each deposit account is only a
+//! reference to the signer's key. The signer is reached over TLS and every
+//! request carries a bearer token.
+
+use crate::config::{RemoteSignerConfig, RemoteSignerTlsConfig};
+use crate::domain::types::Treasury;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use basilica_common::crypto::wallet::sr25519_address_from_public;
+use basilica_protocol::signer::{
+    remote_signer_client::RemoteSignerClient, GenerateKeyRequest, SignRequest,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::interceptor::InterceptedService;
+use tonic::service::Interceptor;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
+use tonic::{Request, Status};
+
+#[cfg(any(test, feature = "testing"))]
+pub use mock::MockRemoteSigner;
+
+/// Prefix marking a stored signing secret as a remote signer key reference
+const KEY_REF_PREFIX: &str = "remote-signer:";
+const SR25519: &str = "sr25519";
+
+/// Key generated by a remote signer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteKey {
+    /// Opaque ID the signer knows the key by
+    pub key_id: String,
+    pub public_key: Vec<u8>,
+}
+
+/// Signer holding sr25519 keys outside the payments process
+#[async_trait]
+pub trait RemoteSigner: Send + Sync {
+    async fn generate_key(&self) -> Result<RemoteKey>;
+
+    /// Sign `payload` with `key_id`, returning the raw signature
+    async fn sign(&self, key_id: &str, payload: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Adds the signer's bearer token to every request
+#[derive(Clone)]
+struct BearerToken(Option<MetadataValue<Ascii>>);
+
+impl Interceptor for BearerToken {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(token) = &self.0 {
+            request
+                .metadata_mut()
+                .insert("authorization", token.clone());
+        }
+        Ok(request)
+    }
+}
+
+/// Remote signer reached over the `basilica.signer.v1.RemoteSigner` gRPC API
+pub struct GrpcRemoteSigner {
+    client: RemoteSignerClient<InterceptedService<Channel, BearerToken>>,
+}
+
+impl GrpcRemoteSigner {
+    /// Create a signer for the configured endpoint, connecting on first use
+    ///
+    /// Fails unless the endpoint uses https and TLS settings and an auth
+    /// token are configured.
+    pub fn connect_lazy(config: &RemoteSignerConfig) -> Result<Self> {
+        if !config.endpoint.starts_with("https://") {
+            anyhow::bail!("Remote signer endpoint must use https");
+        }
+        let tls = config
+            .tls_config
+            .as_ref()
+            .context("Remote signer requires tls_config")?;
+        let token = config
+            .auth_token
+            .as_deref()
+            .filter(|token| !token.trim().is_empty())
+            .context("Remote signer requires an auth_token")?;
+        let token = MetadataValue::try_from(format!("Bearer {}", token))
+            .context("Remote signer auth_token is not a valid header value")?;
+
+        let channel = Endpoint::from_shared(config.endpoint.clone())
+            .with_context(|| format!("Invalid remote signer endpoint: {}", config.endpoint))?
+            .tls_config(client_tls_config(tls)?)
+            .context("Invalid remote signer TLS configuration")?
+            .timeout(Duration::from_secs(config.request_timeout_seconds))
+            .connect_lazy();
+        Ok(Self::new(channel, Some(token)))
+    }
+
+    /// Create a signer for `endpoint` over plaintext without a token, for
+    /// tests against [`MockRemoteSigner`]
+    #[cfg(any(test, feature = "testing"))]
+    pub fn connect_plaintext(endpoint: &str, request_timeout: Duration) -> Result<Self> {
+        let channel = Endpoint::from_shared(endpoint.to_string())
+            .with_context(|| format!("Invalid remote signer endpoint: {}", endpoint))?
+            .timeout(request_timeout)
+            .connect_lazy();
+        Ok(Self::new(channel, None))
+    }
+
+    fn new(channel: Channel, token: Option<MetadataValue<Ascii>>) -> Self {
+        Self {
+            client: RemoteSignerClient::with_interceptor(channel, BearerToken(token)),
+        }
+    }
+}
+
+fn client_tls_config(tls: &RemoteSignerTlsConfig) -> Result<ClientTlsConfig> {
+    let read = |path: &std::path::Path| {
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))
+    };
+
+    let mut config =
+        ClientTlsConfig::new().ca_certificate(Certificate::from_pem(read(&tls.ca_cert_path)?));
+    if let (Some(cert), Some(key)) = (&tls.client_cert_path, &tls.client_key_path) {
+        config = config.identity(Identity::from_pem(read(cert)?, read(key)?));
+    }
+    if let Some(domain_name) = &tls.domain_name {
+        config = config.domain_name(domain_name);
+    }
+    Ok(config)
+}
+
+#[async_trait]
+impl RemoteSigner for GrpcRemoteSigner {
+    async fn generate_key(&self) -> Result<RemoteKey> {
+        let response = self
+            .client
+            .clone()
+            .generate_key(GenerateKeyRequest {
+                scheme: SR25519.to_string(),
+            })
+            .await
+            .map_err(|status| {
+                anyhow::anyhow!("Remote signer failed to generate key: {}", status.message())
+            })?
+            .into_inner();
+        Ok(RemoteKey {
+            key_id: response.key_id,
+            public_key: response.public_key,
+        })
+    }
+
+    async fn sign(&self, key_id: &str, payload: &[u8]) -> Result<Vec<u8>> {
+        let response = self
+            .client
+            .clone()
+            .sign(SignRequest {
+                key_id: key_id.to_string(),
+                payload: payload.to_vec(),
+            })
+            .await
+            .map_err(|status| {
+                anyhow::anyhow!(
+                    "Remote signer failed to sign with key {}: {}",
+                    key_id,
+                    status.message()
+                )
+            })?
+            .into_inner();
+        Ok(response.signature)
+    }
+}
+
+/// Treasury whose hotkeys are generated and used by a [`RemoteSigner`]
+pub struct RemoteTreasury {
+    signer: Arc<dyn RemoteSigner>,
+    ss58_prefix: u16,
+}
+
+impl RemoteTreasury {
+    pub fn new(signer: Arc<dyn RemoteSigner>, ss58_prefix: u16) -> Self {
+        Self {
+            signer,
+            ss58_prefix,
+        }
+    }
+}
+
+#[async_trait]
+impl Treasury for RemoteTreasury {
+    async fn generate_hotkey(&self) -> Result<(String, String, String, String)> {
+        let key = self.signer.generate_key().await?;
+        let address = sr25519_address_from_public(&key.public_key, self.ss58_prefix)
+            .map_err(|e| anyhow::anyhow!("Remote signer returned an invalid key: {}", e))?;
+        let public_hex = hex::encode(&key.public_key);
+
+        Ok((
+            address,
+            public_hex.clone(),
+            public_hex,
+            format!("{}{}", KEY_REF_PREFIX, key.key_id),
+        ))
+    }
+
+    async fn sign(&self, secret: &str, payload: &[u8]) -> Result<String> {
+        let key_id = secret
+            .strip_prefix(KEY_REF_PREFIX)
+            .ok_or_else(|| anyhow::anyhow!("Hotkey is not held by the remote signer"))?;
+        let signature = self.signer.sign(key_id, payload).await?;
+        Ok(hex::encode(signature))
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+mod mock {
+    use super::{RemoteKey, RemoteSigner, SR25519};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use basilica_common::crypto::wallet::{
+        generate_sr25519_wallet, sign_with_sr25519, sr25519_pair_from_mnemonic,
+    };
+    use basilica_protocol::signer::{
+        remote_signer_server, GenerateKeyRequest, GenerateKeyResponse, SignRequest, SignResponse,
+    };
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tonic::{Request, Response, Status};
+
+    /// In-memory signer standing in for a remote signer in tests
+    ///
+    /// It can be used directly as a [`RemoteSigner`] or served over gRPC with
+    /// [`remote_signer_server::RemoteSignerServer`].
+    #[derive(Default)]
+    pub struct MockRemoteSigner {
+        /// Mnemonic of each key by key ID
+        keys: Mutex<HashMap<String, String>>,
+    }
+
+    impl MockRemoteSigner {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn key_count(&self) -> usize {
+            self.keys.lock().unwrap().len()
+        }
+    }
+
+    #[async_trait]
+    impl RemoteSigner for MockRemoteSigner {
+        async fn generate_key(&self) -> Result<RemoteKey> {
+            let wallet = generate_sr25519_wallet(42)
+                .map_err(|e| anyhow::anyhow!("Failed to generate key: {}", e))?;
+            let key_id = uuid::Uuid::new_v4().to_string();
+            self.keys
+                .lock()
+                .unwrap()
+                .insert(key_id.clone(), wallet.mnemonic);
+
+            Ok(RemoteKey {
+                key_id,
+                public_key: hex::decode(wallet.public_hex)?,
+            })
+        }
+
+        async fn sign(&self, key_id: &str, payload: &[u8]) -> Result<Vec<u8>> {
+            let mnemonic = self
+                .keys
+                .lock()
+                .unwrap()
+                .get(key_id)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("Unknown key {}", key_id))?;
+            let pair = sr25519_pair_from_mnemonic(&mnemonic)
+                .map_err(|e| anyhow::anyhow!("Failed to load key {}: {}", key_id, e))?;
+            Ok(hex::decode(sign_with_sr25519(&pair, payload))?)
+        }
+    }
+
+    #[tonic::async_trait]
+    impl remote_signer_server::RemoteSigner for MockRemoteSigner {
+        async fn generate_key(
+            &self,
+            request: Request<GenerateKeyRequest>,
+        ) -> Result<Response<GenerateKeyResponse>, Status> {
+            let scheme = request.into_inner().scheme;
+            if scheme != SR25519 {
+                return Err(Status::invalid_argument(format!(
+                    "Unsupported scheme: {}",
+                    scheme
+                )));
+            }
+            let key = RemoteSigner::generate_key(self)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?;
+
+            Ok(Response::new(GenerateKeyResponse {
+                key_id: key.key_id,
+                public_key: key.public_key,
+            }))
+        }
+
+        async fn sign(
+            &self,
+            request: Request<SignRequest>,
+        ) -> Result<Response<SignResponse>, Status> {
+            let request = request.into_inner();
+            let signature = RemoteSigner::sign(self, &request.key_id, &request.payload)
+                .await
+                .map_err(|e| Status::not_found(e.to_string()))?;
+
+            Ok(Response::new(SignResponse { signature }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use basilica_common::crypto::wallet::{sr25519_pair_from_mnemonic, verify_sr25519_signature};
+
+    #[tokio::test]
+    async fn test_remote_treasury_keeps_keys_in_signer() {
+        let signer = Arc::new(MockRemoteSigner::new());
+        let treasury = RemoteTreasury::new(signer.clone(), 42);
+
+        let (address, account_hex, public_hex, secret) = treasury.generate_hotkey().await.unwrap();
+        assert_eq!(signer.key_count(), 1);
+        assert_eq!(account_hex, public_hex);
+        let public = hex::decode(&public_hex).unwrap();
+        assert_eq!(sr25519_address_from_public(&public, 42).unwrap(), address);
+
+        // The stored secret is a key reference, not key material
+        assert!(secret.starts_with(KEY_REF_PREFIX));
+        assert!(sr25519_pair_from_mnemonic(&secret).is_err());
+
+        let signature = treasury.sign(&secret, b"sweep").await.unwrap();
+        assert!(verify_sr25519_signature(&public, b"sweep", &signature));
+    }
+
+    #[tokio::test]
+    async fn test_remote_treasury_rejects_unknown_keys() {
+        let treasury = RemoteTreasury::new(Arc::new(MockRemoteSigner::new()), 42);
+
+        // Mnemonics of hotkeys generated locally are not signer key references
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert!(treasury.sign(mnemonic, b"sweep").await.is_err());
+
+        let unknown = format!("{}missing", KEY_REF_PREFIX);
+        assert!(treasury.sign(&unknown, b"sweep").await.is_err());
+    }
+}
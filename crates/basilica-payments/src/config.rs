@@ -1,14 +1,14 @@
 use anyhow::Result;
+use basilica_common::config::{loader, redact_url_password, REDACTED};
 use basilica_common::error::ConfigurationError;
-use figment::{
-    providers::{Env, Format, Serialized, Toml},
-    Figment,
-};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use uuid::Uuid;
 
+/// Prefix of the environment variables overriding the configuration
+pub const ENV_PREFIX: &str = "PAYMENTS";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentsConfig {
     pub service: ServiceConfig,
@@ -64,6 +64,8 @@ pub struct HttpConfig {
     pub port: u16,
     pub cors_enabled: bool,
     pub cors_allowed_origins: Vec<String>,
+    /// Shared bearer token for service callers of the `/deposits` routes; unset disables them
+    pub deposits_auth_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +80,50 @@ pub struct BlockchainConfig {
 pub struct TreasuryConfig {
     pub aead_key_hex: String,
     pub tao_decimals: u32,
+    /// Where deposit hotkeys are generated and used for signing
+    pub backend: TreasuryBackend,
+    /// Signer used by the `remote` backend
+    pub remote_signer: RemoteSignerConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TreasuryBackend {
+    /// Keys are generated in process and stored encrypted in the database
+    Local,
+    /// Keys are held by a remote signer, such as one in front of an HSM
+    Remote,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteSignerConfig {
+    /// gRPC endpoint of the `basilica.signer.v1.RemoteSigner` service; must
+    /// be `https` for the remote backend
+    pub endpoint: String,
+    pub request_timeout_seconds: u64,
+    /// TLS settings for the connection to the signer; required for the
+    /// remote backend
+    #[serde(default)]
+    pub tls_config: Option<RemoteSignerTlsConfig>,
+    /// Bearer token sent with every signer request; required for the remote
+    /// backend
+    #[serde(default)]
+    pub auth_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteSignerTlsConfig {
+    /// PEM CA certificate the signer's certificate must chain to
+    pub ca_cert_path: PathBuf,
+    /// PEM client certificate for mutual TLS; set together with the key
+    #[serde(default)]
+    pub client_cert_path: Option<PathBuf>,
+    #[serde(default)]
+    pub client_key_path: Option<PathBuf>,
+    /// Name the signer's certificate is verified against, when it differs
+    /// from the endpoint host
+    #[serde(default)]
+    pub domain_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,6 +138,11 @@ pub struct BillingConfig {
     pub grpc_endpoint: String,
     pub connection_timeout_seconds: u64,
     pub request_timeout_seconds: u64,
+    /// Startup connection attempts before continuing with a lazy channel
+    pub connect_max_attempts: u32,
+    /// Delay before the second attempt; doubles after each failure
+    pub connect_initial_backoff_ms: u64,
+    pub connect_max_backoff_seconds: u64,
 }
 
 impl Default for PaymentsConfig {
@@ -133,6 +184,7 @@ impl Default for PaymentsConfig {
                 port: 8082,
                 cors_enabled: true,
                 cors_allowed_origins: vec!["*".to_string()],
+                deposits_auth_token: None,
             },
             blockchain: BlockchainConfig {
                 websocket_url: "ws://localhost:9944".to_string(),
@@ -144,6 +196,13 @@ impl Default for PaymentsConfig {
                 aead_key_hex: "0000000000000000000000000000000000000000000000000000000000000000"
                     .to_string(),
                 tao_decimals: 9,
+                backend: TreasuryBackend::Local,
+                remote_signer: RemoteSignerConfig {
+                    endpoint: String::new(),
+                    request_timeout_seconds: 10,
+                    tls_config: None,
+                    auth_token: None,
+                },
             },
             price_oracle: PriceOracleConfig {
                 update_interval_seconds: 60,
@@ -154,6 +213,9 @@ impl Default for PaymentsConfig {
                 grpc_endpoint: "http://localhost:50051".to_string(),
                 connection_timeout_seconds: 30,
                 request_timeout_seconds: 60,
+                connect_max_attempts: 5,
+                connect_initial_backoff_ms: 500,
+                connect_max_backoff_seconds: 30,
             },
         }
     }
@@ -161,28 +223,27 @@ impl Default for PaymentsConfig {
 
 impl PaymentsConfig {
     pub fn load(path_override: Option<PathBuf>) -> Result<PaymentsConfig, ConfigurationError> {
-        let default_config = PaymentsConfig::default();
-
-        let mut figment = Figment::from(Serialized::defaults(default_config));
-
-        if let Some(path) = path_override {
-            if path.exists() {
-                figment = figment.merge(Toml::file(&path));
-            }
-        } else {
-            let default_path = PathBuf::from("payments.toml");
-            if default_path.exists() {
-                figment = figment.merge(Toml::file(default_path));
-            }
-        }
-
-        figment = figment.merge(Env::prefixed("PAYMENTS_").split("__"));
+        let path = path_override.unwrap_or_else(|| PathBuf::from("payments.toml"));
+        loader::load_layered(Some(&path), ENV_PREFIX)
+    }
 
-        figment
-            .extract()
-            .map_err(|e| ConfigurationError::ParseError {
-                details: e.to_string(),
-            })
+    /// Render the configuration as TOML with the database password, deposits
+    /// token, treasury key and signer token redacted
+    pub fn to_redacted_toml(&self) -> Result<String, ConfigurationError> {
+        let mut config = self.clone();
+        config.database.url = redact_url_password(&config.database.url);
+        if config.http.deposits_auth_token.is_some() {
+            config.http.deposits_auth_token = Some(REDACTED.to_string());
+        }
+        if !config.treasury.aead_key_hex.is_empty() {
+            config.treasury.aead_key_hex = REDACTED.to_string();
+        }
+        if config.treasury.remote_signer.auth_token.is_some() {
+            config.treasury.remote_signer.auth_token = Some(REDACTED.to_string());
+        }
+        toml::to_string_pretty(&config).map_err(|e| ConfigurationError::ParseError {
+            details: format!("Failed to serialize config: {e}"),
+        })
     }
 
     pub fn load_from_file(path: &Path) -> Result<PaymentsConfig, ConfigurationError> {
@@ -193,16 +254,7 @@ impl PaymentsConfig {
         config: &mut PaymentsConfig,
         prefix: &str,
     ) -> Result<(), ConfigurationError> {
-        let figment = Figment::from(Serialized::defaults(config.clone()))
-            .merge(Env::prefixed(prefix).split("__"));
-
-        *config = figment
-            .extract()
-            .map_err(|e| ConfigurationError::ParseError {
-                details: e.to_string(),
-            })?;
-
-        Ok(())
+        loader::apply_env_overrides(config, prefix)
     }
 
     pub fn validate(&self) -> Result<(), ConfigurationError> {
@@ -237,12 +289,22 @@ impl PaymentsConfig {
             });
         }
 
+        if matches!(&self.http.deposits_auth_token, Some(token) if token.trim().is_empty()) {
+            return Err(ConfigurationError::ValidationFailed {
+                details: "http.deposits_auth_token must not be empty when set".to_string(),
+            });
+        }
+
         if self.treasury.aead_key_hex.is_empty() {
             return Err(ConfigurationError::ValidationFailed {
                 details: "treasury.aead_key_hex must not be empty".to_string(),
             });
         }
 
+        if self.treasury.backend == TreasuryBackend::Remote {
+            self.validate_remote_signer()?;
+        }
+
         if self.blockchain.websocket_url.is_empty() {
             return Err(ConfigurationError::ValidationFailed {
                 details: "blockchain.websocket_url must not be empty".to_string(),
@@ -252,6 +314,40 @@ impl PaymentsConfig {
         Ok(())
     }
 
+    /// The signer holds the treasury's keys, so it is only reached over TLS
+    /// with a bearer token
+    fn validate_remote_signer(&self) -> Result<(), ConfigurationError> {
+        let signer = &self.treasury.remote_signer;
+        let invalid = |details: &str| {
+            Err(ConfigurationError::ValidationFailed {
+                details: details.to_string(),
+            })
+        };
+
+        if signer.endpoint.is_empty() {
+            return invalid("treasury.remote_signer.endpoint is required for the remote backend");
+        }
+        if !signer.endpoint.starts_with("https://") {
+            return invalid("treasury.remote_signer.endpoint must use https");
+        }
+        let Some(tls) = &signer.tls_config else {
+            return invalid("treasury.remote_signer.tls_config is required for the remote backend");
+        };
+        if tls.client_cert_path.is_some() != tls.client_key_path.is_some() {
+            return invalid(
+                "treasury.remote_signer.tls_config client_cert_path and client_key_path must be set together",
+            );
+        }
+        if !signer
+            .auth_token
+            .as_deref()
+            .is_some_and(|token| !token.trim().is_empty())
+        {
+            return invalid("treasury.remote_signer.auth_token is required for the remote backend");
+        }
+        Ok(())
+    }
+
     pub fn warnings(&self) -> Vec<String> {
         let mut warnings = Vec::new();
 
@@ -316,4 +412,62 @@ impl PaymentsConfig {
     pub fn billing_request_timeout(&self) -> Duration {
         Duration::from_secs(self.billing.request_timeout_seconds)
     }
+
+    pub fn billing_connect_initial_backoff(&self) -> Duration {
+        Duration::from_millis(self.billing.connect_initial_backoff_ms)
+    }
+
+    pub fn billing_connect_max_backoff(&self) -> Duration {
+        Duration::from_secs(self.billing.connect_max_backoff_seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_env_override() {
+        std::env::set_var("PAYMENTS_BLOCKCHAIN__CONNECTION_TIMEOUT_SECONDS", "45");
+        let config = PaymentsConfig::load(Some(PathBuf::from("/non/existent/payments.toml")));
+        std::env::remove_var("PAYMENTS_BLOCKCHAIN__CONNECTION_TIMEOUT_SECONDS");
+
+        let config = config.unwrap();
+        assert_eq!(config.blockchain.connection_timeout_seconds, 45);
+        assert_eq!(
+            config.blockchain.websocket_url,
+            PaymentsConfig::default().blockchain.websocket_url
+        );
+    }
+
+    #[test]
+    fn test_remote_backend_requires_tls_and_token() {
+        let mut config = PaymentsConfig::default();
+        config.treasury.backend = TreasuryBackend::Remote;
+        config.treasury.remote_signer.endpoint = "https://signer.internal:50070".to_string();
+        assert!(config.validate().is_err());
+
+        config.treasury.remote_signer.tls_config = Some(RemoteSignerTlsConfig {
+            ca_cert_path: PathBuf::from("/etc/basilica/signer-ca.pem"),
+            client_cert_path: None,
+            client_key_path: None,
+            domain_name: None,
+        });
+        assert!(config.validate().is_err());
+
+        config.treasury.remote_signer.auth_token = Some("signer-token".to_string());
+        assert!(config.validate().is_ok());
+
+        config.treasury.remote_signer.endpoint = "http://signer.internal:50070".to_string();
+        assert!(config.validate().is_err());
+        config.treasury.remote_signer.endpoint = "https://signer.internal:50070".to_string();
+
+        if let Some(tls) = config.treasury.remote_signer.tls_config.as_mut() {
+            tls.client_cert_path = Some(PathBuf::from("/etc/basilica/payments.pem"));
+        }
+        assert!(config.validate().is_err());
+
+        let redacted = config.to_redacted_toml().unwrap();
+        assert!(!redacted.contains("signer-token"));
+    }
 }
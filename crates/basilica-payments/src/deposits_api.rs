@@ -0,0 +1,317 @@
+//! Deposit endpoints on the payments HTTP server.
+//!
+//! These routes are called by trusted services (the API gateway) rather than end users.
+//! Callers authenticate with the shared bearer token from `http.deposits_auth_token` and
+//! name the user they already authenticated in the `X-User-Id` header.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Extension, Router,
+};
+use basilica_common::crypto::{secure_compare, Aead};
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    domain::{deposits::get_or_create_deposit_account, types::Treasury},
+    storage::{ObservedDepositsRepo, ObservedRow, PgRepos},
+};
+
+pub const USER_ID_HEADER: &str = "x-user-id";
+pub const DEFAULT_PAGE_LIMIT: i64 = 20;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+#[derive(Clone)]
+pub struct DepositsApiState {
+    repos: PgRepos,
+    treasury: Arc<dyn Treasury>,
+    aead: Arc<Aead>,
+    auth_token: Arc<str>,
+}
+
+impl DepositsApiState {
+    pub fn new(
+        repos: PgRepos,
+        treasury: Arc<dyn Treasury>,
+        aead: Arc<Aead>,
+        auth_token: impl Into<Arc<str>>,
+    ) -> Self {
+        Self {
+            repos,
+            treasury,
+            aead,
+            auth_token: auth_token.into(),
+        }
+    }
+}
+
+/// Build the `/deposits` routes; every route requires service authentication
+pub fn router(state: DepositsApiState) -> Router {
+    Router::new()
+        .route("/deposits/address", get(get_deposit_address))
+        .route("/deposits/history", get(get_deposit_history))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_service_auth,
+        ))
+        .with_state(state)
+}
+
+/// User on whose behalf an authenticated service is calling
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser(pub String);
+
+#[derive(Debug)]
+pub enum DepositsApiError {
+    Unauthorized(&'static str),
+    BadRequest(String),
+    Internal,
+}
+
+impl IntoResponse for DepositsApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message.to_string()),
+            Self::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            Self::Internal => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal server error".to_string(),
+            ),
+        };
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
+}
+
+async fn require_service_auth(
+    State(state): State<DepositsApiState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, DepositsApiError> {
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or(DepositsApiError::Unauthorized(
+            "Missing bearer token in Authorization header",
+        ))?;
+
+    if !secure_compare(token.as_bytes(), state.auth_token.as_bytes()) {
+        return Err(DepositsApiError::Unauthorized("Invalid service token"));
+    }
+
+    let user_id = req
+        .headers()
+        .get(USER_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| DepositsApiError::BadRequest(format!("Missing {} header", USER_ID_HEADER)))?
+        .to_string();
+
+    req.extensions_mut().insert(AuthenticatedUser(user_id));
+    Ok(next.run(req).await)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepositAddressResponse {
+    pub user_id: String,
+    pub address_ss58: String,
+    pub hotkey_public_hex: String,
+    pub created: bool,
+}
+
+async fn get_deposit_address(
+    State(state): State<DepositsApiState>,
+    Extension(AuthenticatedUser(user_id)): Extension<AuthenticatedUser>,
+) -> Result<Json<DepositAddressResponse>, DepositsApiError> {
+    let account =
+        get_or_create_deposit_account(&state.repos, state.treasury.as_ref(), &state.aead, &user_id)
+            .await
+            .map_err(|e| {
+                error!("Failed to get deposit account for {}: {}", user_id, e);
+                DepositsApiError::Internal
+            })?;
+
+    Ok(Json(DepositAddressResponse {
+        user_id: account.user_id,
+        address_ss58: account.address_ss58,
+        hotkey_public_hex: account.hotkey_public_hex,
+        created: account.created,
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct HistoryQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl Pagination {
+    /// Apply defaults and clamp the limit to `MAX_PAGE_LIMIT`
+    pub fn from_query(query: &HistoryQuery) -> Result<Self, DepositsApiError> {
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if limit < 1 {
+            return Err(DepositsApiError::BadRequest(
+                "limit must be at least 1".to_string(),
+            ));
+        }
+        let offset = query.offset.unwrap_or(0);
+        if offset < 0 {
+            return Err(DepositsApiError::BadRequest(
+                "offset must not be negative".to_string(),
+            ));
+        }
+        Ok(Self {
+            limit: limit.min(MAX_PAGE_LIMIT),
+            offset,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepositHistoryItem {
+    pub transaction_id: String,
+    pub block_number: i64,
+    pub event_index: i32,
+    pub from_account_hex: String,
+    pub to_account_hex: String,
+    pub amount_plancks: String,
+    /// FINALIZED until billing has applied the credit, then CREDITED
+    pub status: String,
+    pub observed_at: String,
+    pub credited_at: Option<String>,
+    pub billing_credit_id: Option<String>,
+}
+
+impl From<ObservedRow> for DepositHistoryItem {
+    fn from(r: ObservedRow) -> Self {
+        Self {
+            transaction_id: format!(
+                "b{}#e{}#{}",
+                r.block_number, r.event_index, r.to_account_hex
+            ),
+            block_number: r.block_number,
+            event_index: r.event_index,
+            from_account_hex: r.from_account_hex,
+            to_account_hex: r.to_account_hex,
+            amount_plancks: r.amount_plancks,
+            status: r.status,
+            observed_at: r.observed_at_rfc3339,
+            credited_at: Some(r.credited_at_rfc3339).filter(|s| !s.is_empty()),
+            billing_credit_id: Some(r.billing_credit_id).filter(|s| !s.is_empty()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepositHistoryResponse {
+    pub items: Vec<DepositHistoryItem>,
+    pub limit: i64,
+    pub offset: i64,
+    pub has_more: bool,
+}
+
+async fn get_deposit_history(
+    State(state): State<DepositsApiState>,
+    Extension(AuthenticatedUser(user_id)): Extension<AuthenticatedUser>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<DepositHistoryResponse>, DepositsApiError> {
+    let page = Pagination::from_query(&query)?;
+
+    // Fetch one extra row to learn whether another page exists
+    let mut rows = state
+        .repos
+        .list_by_user(&user_id, page.limit + 1, page.offset)
+        .await
+        .map_err(|e| {
+            error!("Failed to list deposits for {}: {}", user_id, e);
+            DepositsApiError::Internal
+        })?;
+    let has_more = rows.len() as i64 > page.limit;
+    rows.truncate(page.limit as usize);
+
+    Ok(Json(DepositHistoryResponse {
+        items: rows.into_iter().map(DepositHistoryItem::from).collect(),
+        limit: page.limit,
+        offset: page.offset,
+        has_more,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pagination_defaults() {
+        let page = Pagination::from_query(&HistoryQuery::default()).unwrap();
+        assert_eq!(
+            page,
+            Pagination {
+                limit: DEFAULT_PAGE_LIMIT,
+                offset: 0
+            }
+        );
+    }
+
+    #[test]
+    fn pagination_clamps_limit() {
+        let page = Pagination::from_query(&HistoryQuery {
+            limit: Some(10_000),
+            offset: Some(40),
+        })
+        .unwrap();
+        assert_eq!(page.limit, MAX_PAGE_LIMIT);
+        assert_eq!(page.offset, 40);
+    }
+
+    #[test]
+    fn pagination_rejects_invalid_values() {
+        for query in [
+            HistoryQuery {
+                limit: Some(0),
+                offset: None,
+            },
+            HistoryQuery {
+                limit: None,
+                offset: Some(-1),
+            },
+        ] {
+            assert!(matches!(
+                Pagination::from_query(&query),
+                Err(DepositsApiError::BadRequest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn history_item_maps_empty_optionals_to_none() {
+        let item = DepositHistoryItem::from(ObservedRow {
+            block_number: 7,
+            event_index: 2,
+            from_account_hex: "aa".into(),
+            to_account_hex: "bb".into(),
+            amount_plancks: "1000".into(),
+            status: "FINALIZED".into(),
+            observed_at_rfc3339: "2025-01-01T00:00:00Z".into(),
+            credited_at_rfc3339: String::new(),
+            billing_credit_id: String::new(),
+        });
+        assert_eq!(item.transaction_id, "b7#e2#bb");
+        assert!(item.credited_at.is_none());
+        assert!(item.billing_credit_id.is_none());
+    }
+}
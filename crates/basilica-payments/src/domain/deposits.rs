@@ -0,0 +1,70 @@
+use anyhow::Result;
+use basilica_common::crypto::Aead;
+
+use crate::{
+    domain::types::Treasury,
+    storage::{DepositAccountsRepo, PgRepos},
+};
+
+/// Public view of a user's deposit account; the encrypted mnemonic never leaves storage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepositAddress {
+    pub user_id: String,
+    pub address_ss58: String,
+    pub hotkey_public_hex: String,
+    /// Whether this call generated the account
+    pub created: bool,
+}
+
+/// Return the user's deposit account, generating and storing a new hotkey on first use.
+///
+/// Concurrent first calls for the same user race on the `user_id` primary key;
+/// the loser discards its generated hotkey and returns the stored account.
+pub async fn get_or_create_deposit_account(
+    repos: &PgRepos,
+    treasury: &(impl Treasury + ?Sized),
+    aead: &Aead,
+    user_id: &str,
+) -> Result<DepositAddress> {
+    if let Some(existing) = find_deposit_account(repos, user_id).await? {
+        return Ok(existing);
+    }
+
+    let (addr, acct_hex, pub_hex, mnemonic) = treasury.generate_hotkey().await?;
+    let mnemonic_ct = aead.encrypt(&mnemonic)?;
+
+    let mut tx = repos.begin().await?;
+    match repos
+        .insert_tx(&mut tx, user_id, &addr, &acct_hex, &pub_hex, &mnemonic_ct)
+        .await
+    {
+        Ok(()) => {}
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            drop(tx);
+            return find_deposit_account(repos, user_id).await?.ok_or_else(|| {
+                anyhow::anyhow!("Deposit account for {} vanished after conflict", user_id)
+            });
+        }
+        Err(e) => return Err(e.into()),
+    }
+    tx.commit().await?;
+
+    Ok(DepositAddress {
+        user_id: user_id.to_string(),
+        address_ss58: addr,
+        hotkey_public_hex: pub_hex,
+        created: true,
+    })
+}
+
+async fn find_deposit_account(repos: &PgRepos, user_id: &str) -> Result<Option<DepositAddress>> {
+    Ok(repos
+        .get_by_user(user_id)
+        .await?
+        .map(|(addr, _, pub_hex, _)| DepositAddress {
+            user_id: user_id.to_string(),
+            address_ss58: addr,
+            hotkey_public_hex: pub_hex,
+            created: false,
+        }))
+}
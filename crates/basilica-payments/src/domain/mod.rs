@@ -1,2 +1,3 @@
+pub mod deposits;
 pub mod price;
 pub mod types;
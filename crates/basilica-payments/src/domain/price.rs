@@ -1,9 +1,13 @@
 use crate::price_oracle::PriceOracle;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use sqlx::types::BigDecimal;
+use std::cmp::Ordering;
 use std::str::FromStr;
 use std::sync::Arc;
 
+/// Decimal places kept on USD credit amounts
+pub const USD_SCALE: i64 = 6;
+
 #[derive(Clone)]
 pub struct PriceConverter {
     oracle: Arc<PriceOracle>,
@@ -21,18 +25,9 @@ impl PriceConverter {
 
     /// Convert TAO to USD credits using current exchange rate
     pub async fn tao_to_credits(&self, tao_dec: &str) -> Result<String> {
-        let amt = BigDecimal::from_str(tao_dec)?;
-        let scale = BigDecimal::from_str(&format!("1e{}", self.decimals)).unwrap();
-        let tao = amt / scale;
-
-        // Get current TAO/USD rate
-        let tao_usd = match self.oracle.get_tao_usd_price().await {
-            Ok(price) => price,
-            Err(e) => return Err(e),
-        };
-
-        let credits = tao * tao_usd;
-        Ok(Self::clamp_precision(&credits.to_string(), 6))
+        let plancks = BigDecimal::from_str(tao_dec)?;
+        let tao_usd = self.oracle.get_tao_usd_price().await?;
+        Ok(self.convert_tao_to_usd(&plancks, &tao_usd).to_string())
     }
 
     /// Convert TAO plancks to USD credits using a specific exchange rate (for testing)
@@ -41,21 +36,214 @@ impl PriceConverter {
         plancks_dec: &str,
         tao_usd_rate: &str,
     ) -> Result<String> {
-        let amt = BigDecimal::from_str(plancks_dec)?;
-        let scale = BigDecimal::from_str(&format!("1e{}", self.decimals)).unwrap();
-        let tao = amt / scale;
+        let plancks = BigDecimal::from_str(plancks_dec)?;
         let tao_usd = BigDecimal::from_str(tao_usd_rate)?;
-        let credits = tao * tao_usd;
-        Ok(Self::clamp_precision(&credits.to_string(), 6))
+        Ok(self.convert_tao_to_usd(&plancks, &tao_usd).to_string())
+    }
+
+    /// Convert an amount in plancks to USD.
+    ///
+    /// The product is computed exactly and then rounded half-to-even to `USD_SCALE`
+    /// decimal places, so the result differs from the exact value by at most half a
+    /// micro-dollar.
+    pub fn convert_tao_to_usd(
+        &self,
+        plancks: &BigDecimal,
+        tao_usd_rate: &BigDecimal,
+    ) -> BigDecimal {
+        let tao = plancks.clone() * self.planck_unit();
+        round_half_even(&(tao * tao_usd_rate.clone()), USD_SCALE)
+    }
+
+    /// Convert a USD amount to plancks.
+    ///
+    /// The quotient is rounded half-to-even to a whole number of plancks, the smallest
+    /// unit TAO can be transferred in.
+    pub fn convert_usd_to_tao(
+        &self,
+        usd: &BigDecimal,
+        tao_usd_rate: &BigDecimal,
+    ) -> Result<BigDecimal> {
+        if *tao_usd_rate <= BigDecimal::from(0) {
+            return Err(anyhow!(
+                "TAO/USD rate must be positive, got {}",
+                tao_usd_rate
+            ));
+        }
+        let tao = usd.clone() / tao_usd_rate.clone();
+        Ok(round_half_even(&(tao / self.planck_unit()), 0))
+    }
+
+    /// Value of one planck in TAO
+    fn planck_unit(&self) -> BigDecimal {
+        BigDecimal::new(1.into(), self.decimals as i64)
+    }
+}
+
+/// Round `value` to `scale` decimal places, resolving exact ties to the even neighbour
+pub fn round_half_even(value: &BigDecimal, scale: i64) -> BigDecimal {
+    // with_scale truncates toward zero
+    let truncated = value.with_scale(scale);
+    let remainder = (value.clone() - truncated.clone()).abs();
+    let ulp = BigDecimal::new(1.into(), scale);
+    let half_ulp = ulp.clone() / BigDecimal::from(2);
+
+    let round_away = match remainder.cmp(&half_ulp) {
+        Ordering::Less => false,
+        Ordering::Greater => true,
+        Ordering::Equal => is_odd_at_scale(&truncated, scale),
+    };
+    if !round_away {
+        return truncated;
+    }
+
+    let rounded = if *value < BigDecimal::from(0) {
+        truncated - ulp
+    } else {
+        truncated + ulp
+    };
+    rounded.with_scale(scale)
+}
+
+/// Whether the last digit of `value` at `scale` decimal places is odd
+fn is_odd_at_scale(value: &BigDecimal, scale: i64) -> bool {
+    let units = (value.clone() * BigDecimal::new(1.into(), -scale)).abs();
+    let two = BigDecimal::from(2);
+    (units.clone() / two.clone()).with_scale(0) * two != units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price_oracle::PriceOracleConfig;
+
+    fn converter() -> PriceConverter {
+        PriceConverter::new(Arc::new(PriceOracle::new(PriceOracleConfig::default())), 9)
+    }
+
+    fn dec(s: &str) -> BigDecimal {
+        BigDecimal::from_str(s).unwrap()
+    }
+
+    /// Deterministic xorshift generator so failures are reproducible
+    struct Rng(u64);
+
+    impl Rng {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_round_half_even() {
+        let cases = [
+            ("0.0000005", 6, "0.000000"),
+            ("0.0000015", 6, "0.000002"),
+            ("0.0000025", 6, "0.000002"),
+            ("0.00000251", 6, "0.000003"),
+            ("1.2345674999", 6, "1.234567"),
+            ("-0.0000025", 6, "-0.000002"),
+            ("-0.0000035", 6, "-0.000004"),
+            ("2.5", 0, "2"),
+            ("3.5", 0, "4"),
+            ("7", 6, "7.000000"),
+        ];
+        for (input, scale, expected) in cases {
+            let rounded = round_half_even(&dec(input), scale);
+            assert_eq!(rounded, dec(expected), "rounding {} to {}", input, scale);
+        }
+    }
+
+    #[test]
+    fn test_convert_tao_to_usd_uses_fixed_scale() {
+        let c = converter();
+        // 1.5 TAO at $400.123456 = $600.185184
+        assert_eq!(
+            c.convert_tao_to_usd(&dec("1500000000"), &dec("400.123456")),
+            dec("600.185184")
+        );
+        // 1 planck at $0.5 rounds to zero micro-dollars
+        assert_eq!(c.convert_tao_to_usd(&dec("1"), &dec("0.5")), dec("0"));
+        // 2.5 micro-dollars is a tie and rounds to even
+        assert_eq!(
+            c.convert_tao_to_usd(&dec("2500"), &dec("1")),
+            dec("0.000002")
+        );
+        // Credits always carry exactly USD_SCALE decimals
+        assert_eq!(
+            c.plancks_to_credits_with_rate("7000000000", "1").unwrap(),
+            "7.000000"
+        );
+    }
+
+    #[test]
+    fn test_convert_usd_to_tao_rejects_non_positive_rate() {
+        let c = converter();
+        assert!(c.convert_usd_to_tao(&dec("10"), &dec("0")).is_err());
+        assert!(c.convert_usd_to_tao(&dec("10"), &dec("-1")).is_err());
     }
 
-    fn clamp_precision(s: &str, max_decimals: usize) -> String {
-        if let Some(dot_pos) = s.find('.') {
-            let decimals = &s[dot_pos + 1..];
-            if decimals.len() > max_decimals {
-                return format!("{}.{}", &s[..dot_pos], &decimals[..max_decimals]);
-            }
+    #[test]
+    fn test_large_amounts_keep_full_precision() {
+        let c = converter();
+        // 10^21 TAO plus a fraction, far beyond what f64 can represent exactly
+        let plancks = dec("1000000000000000000000123456789");
+        let usd = c.convert_tao_to_usd(&plancks, &dec("2"));
+        assert_eq!(usd.to_string(), "2000000000000000000000.246914");
+
+        let back = c.convert_usd_to_tao(&usd, &dec("2")).unwrap();
+        assert_eq!(back.to_string(), "1000000000000000000000123457000");
+    }
+
+    #[test]
+    fn test_usd_round_trip_within_one_ulp() {
+        let c = converter();
+        let ulp = BigDecimal::new(1.into(), USD_SCALE);
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+
+        for _ in 0..2000 {
+            // Up to $10M at micro-dollar scale, rates between $1 and $1000
+            let usd = BigDecimal::new((rng.next_u64() % 10_000_000_000_000).into(), USD_SCALE);
+            let rate = BigDecimal::new((1_000_000 + rng.next_u64() % 999_000_000).into(), 6);
+
+            let plancks = c.convert_usd_to_tao(&usd, &rate).unwrap();
+            let back = c.convert_tao_to_usd(&plancks, &rate);
+
+            assert!(
+                (back.clone() - usd.clone()).abs() <= ulp,
+                "usd {} at rate {} came back as {}",
+                usd,
+                rate,
+                back
+            );
+        }
+    }
+
+    #[test]
+    fn test_planck_round_trip_within_one_planck_per_micro_dollar() {
+        let c = converter();
+        let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+
+        for _ in 0..2000 {
+            let plancks = BigDecimal::from(rng.next_u64() % 100_000_000_000_000);
+            let rate = BigDecimal::new((1_000_000 + rng.next_u64() % 999_000_000).into(), 6);
+
+            let usd = c.convert_tao_to_usd(&plancks, &rate);
+            let back = c.convert_usd_to_tao(&usd, &rate).unwrap();
+
+            // USD rounding moves the value by at most half a micro-dollar, which is
+            // worth 0.5e-6 / rate TAO; one extra planck covers the final rounding
+            let bound = c.convert_usd_to_tao(&dec("0.0000005"), &rate).unwrap() + dec("1");
+            assert!(
+                (back.clone() - plancks.clone()).abs() <= bound,
+                "{} plancks at rate {} came back as {}",
+                plancks,
+                rate,
+                back
+            );
         }
-        s.to_string()
     }
 }
@@ -27,7 +27,17 @@ pub enum PaymentsError {
 
 #[async_trait::async_trait]
 pub trait Treasury: Send + Sync {
+    /// Generate a deposit hotkey, returning its SS58 address, account ID hex,
+    /// public key hex and signing secret
+    ///
+    /// The secret is stored encrypted and passed back to [`Treasury::sign`].
+    /// It is the mnemonic for keys held in process and a key reference for
+    /// keys held by a remote signer.
     async fn generate_hotkey(&self) -> Result<(String, String, String, String)>;
+
+    /// Sign `payload` with the hotkey whose signing secret is `secret`,
+    /// returning the signature as hex
+    async fn sign(&self, secret: &str, payload: &[u8]) -> Result<String>;
 }
 
 #[async_trait::async_trait]
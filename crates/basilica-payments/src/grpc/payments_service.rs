@@ -3,7 +3,7 @@ use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
 use crate::{
-    domain::types::Treasury,
+    domain::{deposits::get_or_create_deposit_account, types::Treasury},
     storage::{DepositAccountsRepo, ObservedDepositsRepo, PgRepos},
 };
 use basilica_common::crypto::Aead;
@@ -13,13 +13,13 @@ use basilica_protocol::payments::{
     GetDepositAccountRequest, GetDepositAccountResponse, ListDepositsRequest, ListDepositsResponse,
 };
 
-pub struct PaymentsServer<T: Treasury + 'static> {
+pub struct PaymentsServer<T: Treasury + ?Sized + 'static> {
     repos: PgRepos,
     treasury: Arc<T>,
     aead: Arc<Aead>,
 }
 
-impl<T: Treasury> PaymentsServer<T> {
+impl<T: Treasury + ?Sized> PaymentsServer<T> {
     pub fn new(repos: PgRepos, treasury: Arc<T>, aead: Arc<Aead>) -> Self {
         Self {
             repos,
@@ -34,38 +34,26 @@ impl<T: Treasury> PaymentsServer<T> {
 }
 
 #[tonic::async_trait]
-impl<T: Treasury + Send + Sync> PaymentsService for PaymentsServer<T> {
+impl<T: Treasury + ?Sized> PaymentsService for PaymentsServer<T> {
     async fn create_deposit_account(
         &self,
         req: Request<CreateDepositAccountRequest>,
     ) -> Result<Response<CreateDepositAccountResponse>, Status> {
         let user_id = req.into_inner().user_id;
 
-        if let Some((addr, _, pub_hex, _mn_ct)) =
-            self.repos.get_by_user(&user_id).await.map_err(internal)?
-        {
-            return Ok(Response::new(CreateDepositAccountResponse {
-                user_id,
-                address_ss58: addr,
-                hotkey_public: pub_hex,
-            }));
-        }
-
-        let (addr, acct_hex, pub_hex, mnemonic) =
-            self.treasury.generate_hotkey().await.map_err(internal)?;
-        let mnemonic_ct = self.aead.encrypt(&mnemonic).map_err(internal)?;
-
-        let mut tx = self.repos.begin().await.map_err(internal)?;
-        self.repos
-            .insert_tx(&mut tx, &user_id, &addr, &acct_hex, &pub_hex, &mnemonic_ct)
-            .await
-            .map_err(internal)?;
-        tx.commit().await.map_err(internal)?;
+        let account = get_or_create_deposit_account(
+            &self.repos,
+            self.treasury.as_ref(),
+            &self.aead,
+            &user_id,
+        )
+        .await
+        .map_err(internal)?;
 
         Ok(Response::new(CreateDepositAccountResponse {
-            user_id,
-            address_ss58: addr,
-            hotkey_public: pub_hex,
+            user_id: account.user_id,
+            address_ss58: account.address_ss58,
+            hotkey_public: account.hotkey_public_hex,
         }))
     }
 
@@ -1,5 +1,6 @@
 pub mod blockchain;
 pub mod config;
+pub mod deposits_api;
 pub mod domain;
 pub mod error;
 pub mod grpc;
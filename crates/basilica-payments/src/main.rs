@@ -1,10 +1,18 @@
 use basilica_payments::{
-    blockchain::{local_treasury::LocalTreasury, monitor::ChainMonitor},
-    config::PaymentsConfig,
-    domain::price::PriceConverter,
+    blockchain::{
+        local_treasury::LocalTreasury,
+        monitor::ChainMonitor,
+        remote_treasury::{GrpcRemoteSigner, RemoteTreasury},
+    },
+    config::{PaymentsConfig, TreasuryBackend},
+    deposits_api::DepositsApiState,
+    domain::{price::PriceConverter, types::Treasury},
     grpc::payments_service::PaymentsServer as GrpcPaymentsServer,
     price_oracle::{PriceOracle, PriceOracleConfig},
-    processor::{billing_client::GrpcBillingClient, dispatcher::OutboxDispatcher},
+    processor::{
+        billing_client::{BillingConnectOptions, GrpcBillingClient},
+        dispatcher::OutboxDispatcher,
+    },
     server::PaymentsServer,
     storage::PgRepos,
 };
@@ -34,6 +42,12 @@ struct Args {
     #[arg(long, help = "Generate sample configuration file")]
     gen_config: bool,
 
+    #[arg(
+        long,
+        help = "Print the effective configuration after file and environment overrides, with secrets redacted"
+    )]
+    print_config: bool,
+
     #[arg(long, help = "Dry run mode (validate config without starting)")]
     dry_run: bool,
 
@@ -59,6 +73,11 @@ async fn main() -> Result<()> {
     }
 
     let cfg = PaymentsConfig::load(args.config).context("Failed to load configuration")?;
+
+    if args.print_config {
+        println!("{}", cfg.to_redacted_toml()?);
+        return Ok(());
+    }
     cfg.validate().context("Configuration validation failed")?;
 
     let warnings = cfg.warnings();
@@ -127,15 +146,32 @@ async fn main() -> Result<()> {
     let aead = Arc::new(
         Aead::new(&cfg.treasury.aead_key_hex).context("Failed to initialize AEAD encryption")?,
     );
-    let treasury = Arc::new(LocalTreasury::new(cfg.blockchain.ss58_prefix));
+    let treasury: Arc<dyn Treasury> = match cfg.treasury.backend {
+        TreasuryBackend::Local => Arc::new(LocalTreasury::new(cfg.blockchain.ss58_prefix)),
+        TreasuryBackend::Remote => {
+            info!(
+                "Using remote signer at {} for treasury keys",
+                cfg.treasury.remote_signer.endpoint
+            );
+            let signer = GrpcRemoteSigner::connect_lazy(&cfg.treasury.remote_signer)?;
+            Arc::new(RemoteTreasury::new(
+                Arc::new(signer),
+                cfg.blockchain.ss58_prefix,
+            ))
+        }
+    };
 
     info!(
         "Connecting to billing service at: {}",
         cfg.billing.grpc_endpoint
     );
-    let billing = GrpcBillingClient::connect(&cfg.billing.grpc_endpoint)
-        .await
-        .context("Failed to connect to billing service")?;
+    let billing = GrpcBillingClient::connect(
+        &cfg.billing.grpc_endpoint,
+        &BillingConnectOptions::from_config(&cfg),
+    )
+    .await
+    .context("Failed to connect to billing service")?;
+    let billing_state = billing.connection_state();
 
     let oracle_config = PriceOracleConfig {
         update_interval: cfg.price_oracle.update_interval_seconds,
@@ -149,6 +185,10 @@ async fn main() -> Result<()> {
 
     let price = PriceConverter::new(oracle, cfg.treasury.tao_decimals);
 
+    let deposits_api =
+        cfg.http.deposits_auth_token.clone().map(|token| {
+            DepositsApiState::new(repos.clone(), treasury.clone(), aead.clone(), token)
+        });
     let grpc_svc = GrpcPaymentsServer::new(repos.clone(), treasury, aead).into_service();
     let dispatcher = OutboxDispatcher::new(repos.clone(), billing, price);
 
@@ -162,7 +202,7 @@ async fn main() -> Result<()> {
 
     let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
     health_reporter
-        .set_serving::<PaymentsServiceServer<GrpcPaymentsServer<LocalTreasury>>>()
+        .set_serving::<PaymentsServiceServer<GrpcPaymentsServer<dyn Treasury>>>()
         .await;
 
     let grpc_bind: SocketAddr = format!("{}:{}", cfg.grpc.listen_address, cfg.grpc.port)
@@ -172,7 +212,10 @@ async fn main() -> Result<()> {
     info!("Starting gRPC server on {}", grpc_bind);
 
     // Start HTTP server
-    let http_server = PaymentsServer::new(cfg.clone(), Arc::new(pool));
+    let mut http_server = PaymentsServer::new(cfg.clone(), Arc::new(pool), billing_state);
+    if let Some(deposits_api) = deposits_api {
+        http_server = http_server.with_deposits_api(deposits_api);
+    }
     let http_handle = tokio::spawn(async move { http_server.serve(shutdown_signal()).await });
 
     tokio::select! {
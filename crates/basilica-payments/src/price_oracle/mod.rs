@@ -1,6 +1,7 @@
 use anyhow::{anyhow, Result};
 use reqwest::Client;
 use serde::Deserialize;
+use serde_json::value::RawValue;
 use sqlx::types::BigDecimal;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -44,7 +45,8 @@ struct CoinGeckoResponse {
 
 #[derive(Debug, Deserialize)]
 struct CoinGeckoPrice {
-    usd: f64,
+    /// Kept as the raw JSON number so the price never passes through f64
+    usd: Box<RawValue>,
 }
 
 /// Cached price information
@@ -166,19 +168,12 @@ impl PriceOracle {
             ));
         }
 
-        let data: CoinGeckoResponse = response
-            .json()
+        let body = response
+            .text()
             .await
-            .map_err(|e| anyhow!("Failed to parse CoinGecko response: {}", e))?;
+            .map_err(|e| anyhow!("Failed to read CoinGecko response: {}", e))?;
 
-        let price_str = data.bittensor.usd.to_string();
-        let price = BigDecimal::from_str(&price_str)
-            .map_err(|e| anyhow!("Failed to parse price as BigDecimal: {}", e))?;
-
-        if price <= BigDecimal::from(0u8) {
-            return Err(anyhow!("Invalid TAO/USD price returned (<= 0)"));
-        }
-        Ok(price)
+        parse_coingecko_price(&body)
     }
 
     /// Start background price update task
@@ -218,6 +213,20 @@ impl PriceOracle {
     }
 }
 
+/// Parse the TAO/USD price from a CoinGecko response body without losing precision
+fn parse_coingecko_price(body: &str) -> Result<BigDecimal> {
+    let data: CoinGeckoResponse = serde_json::from_str(body)
+        .map_err(|e| anyhow!("Failed to parse CoinGecko response: {}", e))?;
+
+    let price = BigDecimal::from_str(data.bittensor.usd.get())
+        .map_err(|e| anyhow!("Failed to parse price as BigDecimal: {}", e))?;
+
+    if price <= BigDecimal::from(0u8) {
+        return Err(anyhow!("Invalid TAO/USD price returned (<= 0)"));
+    }
+    Ok(price)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,6 +260,26 @@ mod tests {
         assert!(error_msg.contains("No price available"));
     }
 
+    #[test]
+    fn test_parse_coingecko_price_keeps_all_digits() {
+        let price =
+            parse_coingecko_price(r#"{"bittensor":{"usd":412.12345678901234567890}}"#).unwrap();
+        assert_eq!(
+            price,
+            BigDecimal::from_str("412.12345678901234567890").unwrap()
+        );
+
+        let price = parse_coingecko_price(r#"{"bittensor":{"usd":3.5e-5}}"#).unwrap();
+        assert_eq!(price, BigDecimal::from_str("0.000035").unwrap());
+    }
+
+    #[test]
+    fn test_parse_coingecko_price_rejects_invalid_prices() {
+        assert!(parse_coingecko_price(r#"{"bittensor":{"usd":0}}"#).is_err());
+        assert!(parse_coingecko_price(r#"{"bittensor":{"usd":"abc"}}"#).is_err());
+        assert!(parse_coingecko_price(r#"{"bittensor":{}}"#).is_err());
+    }
+
     #[test]
     fn test_cached_price_staleness() {
         let price = BigDecimal::from_str("50.0").unwrap();
@@ -1,19 +1,112 @@
-use crate::domain::types::BillingClient;
+use crate::{config::PaymentsConfig, domain::types::BillingClient};
 use anyhow::Result;
 use std::collections::HashMap;
-use tonic::transport::Channel;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+use tonic::transport::{Channel, Endpoint};
+use tracing::{info, warn};
+
+/// How the billing client establishes its connection
+#[derive(Debug, Clone)]
+pub struct BillingConnectOptions {
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+    /// Startup connection attempts before continuing with a lazy channel
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl BillingConnectOptions {
+    pub fn from_config(cfg: &PaymentsConfig) -> Self {
+        Self {
+            connect_timeout: cfg.billing_connection_timeout(),
+            request_timeout: cfg.billing_request_timeout(),
+            max_attempts: cfg.billing.connect_max_attempts,
+            initial_backoff: cfg.billing_connect_initial_backoff(),
+            max_backoff: cfg.billing_connect_max_backoff(),
+        }
+    }
+}
+
+/// Whether the billing service answered the most recent connection or call
+#[derive(Debug, Clone, Default)]
+pub struct BillingConnectionState(Arc<AtomicBool>);
+
+impl BillingConnectionState {
+    pub fn is_connected(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, connected: bool) {
+        self.0.store(connected, Ordering::Relaxed);
+    }
+}
 
 pub struct GrpcBillingClient {
     inner: basilica_protocol::billing::billing_service_client::BillingServiceClient<Channel>,
+    state: BillingConnectionState,
 }
 
 impl GrpcBillingClient {
-    pub async fn connect(uri: &str) -> Result<Self> {
+    /// Connect to billing, retrying with exponential backoff
+    ///
+    /// If billing is still unreachable after `max_attempts`, the client falls
+    /// back to a lazy channel that connects on first use, so the service
+    /// starts during a billing outage. The channel reconnects on its own after
+    /// later outages; failed calls are retried by the outbox dispatcher.
+    pub async fn connect(uri: &str, options: &BillingConnectOptions) -> Result<Self> {
         use basilica_protocol::billing::billing_service_client::BillingServiceClient;
+
+        let endpoint = Endpoint::from_shared(uri.to_string())?
+            .connect_timeout(options.connect_timeout)
+            .timeout(options.request_timeout);
+        let state = BillingConnectionState::default();
+
+        let max_attempts = options.max_attempts.max(1);
+        let mut backoff = options.initial_backoff;
+        let mut channel = None;
+        for attempt in 1..=max_attempts {
+            match endpoint.connect().await {
+                Ok(connected) => {
+                    channel = Some(connected);
+                    break;
+                }
+                Err(e) => {
+                    warn!(attempt, max_attempts, err = %e, "billing service unavailable");
+                    if attempt < max_attempts {
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(options.max_backoff);
+                    }
+                }
+            }
+        }
+
+        let channel = match channel {
+            Some(channel) => {
+                info!("Connected to billing service");
+                state.set(true);
+                channel
+            }
+            None => {
+                warn!("Billing service still unavailable; will connect on first use");
+                endpoint.connect_lazy()
+            }
+        };
+
         Ok(Self {
-            inner: BillingServiceClient::connect(uri.to_string()).await?,
+            inner: BillingServiceClient::new(channel),
+            state,
         })
     }
+
+    /// Shared connection state, for health checks and metrics
+    pub fn connection_state(&self) -> BillingConnectionState {
+        self.state.clone()
+    }
 }
 
 #[async_trait::async_trait]
@@ -38,7 +131,73 @@ impl BillingClient for GrpcBillingClient {
             metadata: md,
         };
 
-        let resp = self.inner.clone().apply_credits(req).await?.into_inner();
-        Ok(resp.credit_id)
+        match self.inner.clone().apply_credits(req).await {
+            Ok(resp) => {
+                self.state.set(true);
+                Ok(resp.into_inner().credit_id)
+            }
+            Err(status) => {
+                // Any other status means billing answered
+                self.state.set(status.code() != tonic::Code::Unavailable);
+                Err(status.into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(max_attempts: u32) -> BillingConnectOptions {
+        BillingConnectOptions {
+            connect_timeout: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(1),
+            max_attempts,
+            initial_backoff: Duration::from_millis(50),
+            max_backoff: Duration::from_millis(200),
+        }
+    }
+
+    /// Reserve a local port that nothing is listening on yet
+    fn unused_addr() -> std::net::SocketAddr {
+        std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_connects_once_billing_comes_up() {
+        let addr = unused_addr();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let (_reporter, health_service) = tonic_health::server::health_reporter();
+            tonic::transport::Server::builder()
+                .add_service(health_service)
+                .serve(addr)
+                .await
+                .unwrap();
+        });
+
+        let client = GrpcBillingClient::connect(&format!("http://{addr}"), &options(20))
+            .await
+            .unwrap();
+
+        assert!(client.connection_state().is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_starts_with_lazy_channel_when_billing_stays_down() {
+        let addr = unused_addr();
+
+        let client = GrpcBillingClient::connect(&format!("http://{addr}"), &options(2))
+            .await
+            .unwrap();
+        assert!(!client.connection_state().is_connected());
+
+        let err = client.apply_credits("u1", "1.0", "tx1").await.unwrap_err();
+        assert!(err.downcast_ref::<tonic::Status>().is_some());
     }
 }
@@ -1,4 +1,8 @@
-use crate::config::PaymentsConfig;
+use crate::{
+    config::PaymentsConfig,
+    deposits_api::{self, DepositsApiState},
+    processor::billing_client::BillingConnectionState,
+};
 use axum::{http::StatusCode, response::Json, routing::get, Router};
 use chrono;
 use serde_json::Value;
@@ -7,17 +11,34 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Payments server that hosts both HTTP and gRPC services
 pub struct PaymentsServer {
     config: PaymentsConfig,
     db_pool: Arc<PgPool>,
+    billing: BillingConnectionState,
+    deposits: Option<DepositsApiState>,
 }
 
 impl PaymentsServer {
-    pub fn new(config: PaymentsConfig, db_pool: Arc<PgPool>) -> Self {
-        Self { config, db_pool }
+    pub fn new(
+        config: PaymentsConfig,
+        db_pool: Arc<PgPool>,
+        billing: BillingConnectionState,
+    ) -> Self {
+        Self {
+            config,
+            db_pool,
+            billing,
+            deposits: None,
+        }
+    }
+
+    /// Serve the authenticated `/deposits` routes alongside health and metrics
+    pub fn with_deposits_api(mut self, state: DepositsApiState) -> Self {
+        self.deposits = Some(state);
+        self
     }
 
     pub async fn serve(
@@ -37,13 +58,17 @@ impl PaymentsServer {
 
         let (http_tx, http_rx) = tokio::sync::oneshot::channel();
 
-        let db_pool = self.db_pool.clone();
+        let state = AppState {
+            db_pool: self.db_pool.clone(),
+            billing: self.billing.clone(),
+        };
+
+        let deposits = self.deposits;
 
         // Start HTTP server
-        let http_handle =
-            tokio::spawn(
-                async move { Self::start_http_server(http_listener, http_rx, db_pool).await },
-            );
+        let http_handle = tokio::spawn(async move {
+            Self::start_http_server(http_listener, http_rx, state, deposits).await
+        });
 
         // Wait for shutdown signal and propagate to HTTP server
         tokio::spawn(async move {
@@ -61,20 +86,27 @@ impl PaymentsServer {
     async fn start_http_server(
         listener: tokio::net::TcpListener,
         shutdown_signal: tokio::sync::oneshot::Receiver<()>,
-        db_pool: Arc<PgPool>,
+        state: AppState,
+        deposits: Option<DepositsApiState>,
     ) -> anyhow::Result<()> {
         let addr = listener.local_addr()?;
         info!("Starting payments HTTP server on {}", addr);
 
-        let app = Router::new()
+        let mut app = Router::new()
             .route("/health", get(health_check))
             .route("/metrics", get(metrics_handler))
-            .layer(
-                ServiceBuilder::new()
-                    .layer(CorsLayer::permissive())
-                    .into_inner(),
-            )
-            .with_state(AppState { db_pool });
+            .with_state(state);
+
+        match deposits {
+            Some(deposits) => app = app.merge(deposits_api::router(deposits)),
+            None => warn!("http.deposits_auth_token is not set; /deposits routes are disabled"),
+        }
+
+        let app = app.layer(
+            ServiceBuilder::new()
+                .layer(CorsLayer::permissive())
+                .into_inner(),
+        );
 
         let server = axum::serve(listener, app);
 
@@ -93,6 +125,7 @@ impl PaymentsServer {
 #[derive(Clone)]
 struct AppState {
     db_pool: Arc<PgPool>,
+    billing: BillingConnectionState,
 }
 
 async fn health_check(
@@ -103,12 +136,17 @@ async fn health_check(
         .fetch_one(state.db_pool.as_ref())
         .await
     {
-        Ok(_) => Ok(Json(serde_json::json!({
-            "status": "healthy",
-            "service": "basilica-payments",
-            "timestamp": chrono::Utc::now().to_rfc3339(),
-            "database": "connected"
-        }))),
+        Ok(_) => {
+            // Deposits queue in the outbox while billing is down, so stay up
+            let billing_connected = state.billing.is_connected();
+            Ok(Json(serde_json::json!({
+                "status": if billing_connected { "healthy" } else { "degraded" },
+                "service": "basilica-payments",
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "database": "connected",
+                "billing": if billing_connected { "connected" } else { "unavailable" }
+            })))
+        }
         Err(e) => {
             error!("Health check database error: {}", e);
             Err(StatusCode::SERVICE_UNAVAILABLE)
@@ -116,6 +154,14 @@ async fn health_check(
     }
 }
 
-async fn metrics_handler() -> Result<String, StatusCode> {
-    Ok("# Payments service metrics endpoint\n".to_string())
+async fn metrics_handler(
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> Result<String, StatusCode> {
+    Ok(format!(
+        "# Payments service metrics endpoint\n\
+         # HELP basilica_payments_billing_connected Whether the billing service is reachable\n\
+         # TYPE basilica_payments_billing_connected gauge\n\
+         basilica_payments_billing_connected {}\n",
+        u8::from(state.billing.is_connected())
+    ))
 }
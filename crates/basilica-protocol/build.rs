@@ -54,6 +54,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "ResourceLimits",
             "#[derive(serde::Serialize, serde::Deserialize)]",
         )
+        // Registry credentials get a hand-written Debug that redacts the token
+        .skip_debug(["RegistryAuth"])
         .compile(
             &[
                 "proto/common.proto",
@@ -65,6 +67,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/gpu_pow.proto",
                 "proto/billing.proto",
                 "proto/payments.proto",
+                "proto/signer.proto",
             ],
             &["proto"],
         )?;
@@ -53,6 +53,12 @@ pub struct GpuSpec {
     /// Compute capability (for CUDA GPUs)
     #[prost(string, tag = "12")]
     pub compute_capability: ::prost::alloc::string::String,
+    /// PCI bus ID (e.g., "00000000:3B:00.0"), if known
+    #[prost(string, optional, tag = "13")]
+    pub pci_bus_id: ::core::option::Option<::prost::alloc::string::String>,
+    /// NUMA node the GPU is attached to, if the host has NUMA topology
+    #[prost(int32, optional, tag = "14")]
+    pub numa_node: ::core::option::Option<i32>,
 }
 /// CPU specification details
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -403,6 +409,29 @@ pub struct ContainerSpec {
     /// Network mode
     #[prost(string, tag = "10")]
     pub network_mode: ::prost::alloc::string::String,
+    /// Credentials for pulling the image from a private registry
+    #[prost(message, optional, tag = "11")]
+    pub registry_auth: ::core::option::Option<RegistryAuth>,
+}
+/// Private registry credentials for an image pull. Either `username` and
+/// `token` are set inline, or `secret_ref` names a secret stored encrypted on
+/// the executor. The token is never logged.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+#[prost(skip_debug)]
+pub struct RegistryAuth {
+    /// Registry host such as "ghcr.io"; a scheme, if given, must be https
+    #[prost(string, tag = "1")]
+    pub registry: ::prost::alloc::string::String,
+    /// Registry username
+    #[prost(string, tag = "2")]
+    pub username: ::prost::alloc::string::String,
+    /// Registry password or access token
+    #[prost(string, tag = "3")]
+    pub token: ::prost::alloc::string::String,
+    /// Name of a stored registry secret to use instead of inline credentials
+    #[prost(string, tag = "4")]
+    pub secret_ref: ::prost::alloc::string::String,
 }
 /// Container status information
 #[allow(clippy::derive_partial_eq_without_eq)]
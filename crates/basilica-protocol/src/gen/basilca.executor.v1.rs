@@ -205,6 +205,60 @@ pub struct LogSubscriptionRequest {
     #[prost(string, tag = "6")]
     pub validator_hotkey: ::prost::alloc::string::String,
 }
+/// Request to stream a single container's logs
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContainerLogsRequest {
+    /// Container ID to stream logs from
+    #[prost(string, tag = "1")]
+    pub container_id: ::prost::alloc::string::String,
+    /// Keep the stream open and send new lines as they are written
+    #[prost(bool, tag = "2")]
+    pub follow: bool,
+    /// Number of historical lines to include (0 for all)
+    #[prost(uint32, tag = "3")]
+    pub tail_lines: u32,
+    /// Only include lines written at or after this time
+    #[prost(message, optional, tag = "4")]
+    pub since: ::core::option::Option<super::super::common::v1::Timestamp>,
+    /// Requesting validator hotkey
+    #[prost(string, tag = "5")]
+    pub validator_hotkey: ::prost::alloc::string::String,
+    /// Authentication data from miner
+    #[prost(message, optional, tag = "6")]
+    pub auth: ::core::option::Option<super::super::common::v1::MinerAuthentication>,
+    /// Signature by validator_hotkey, for requests sent without the miner
+    #[prost(message, optional, tag = "7")]
+    pub validator_auth: ::core::option::Option<ValidatorAuthentication>,
+}
+/// Validator signature over a request sent directly to the executor
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidatorAuthentication {
+    /// Unix timestamp in milliseconds when the request was signed
+    #[prost(uint64, tag = "1")]
+    pub timestamp_ms: u64,
+    /// Unique UUID nonce to prevent replay attacks
+    #[prost(bytes = "vec", tag = "2")]
+    pub nonce: ::prost::alloc::vec::Vec<u8>,
+    /// Signature over the request's validator signing payload
+    #[prost(bytes = "vec", tag = "3")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// A single container log line
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ContainerLogLine {
+    /// Time Docker recorded the line
+    #[prost(message, optional, tag = "1")]
+    pub timestamp: ::core::option::Option<super::super::common::v1::Timestamp>,
+    /// Output stream (stdout, stderr)
+    #[prost(string, tag = "2")]
+    pub stream: ::prost::alloc::string::String,
+    /// Line content without the trailing newline
+    #[prost(string, tag = "3")]
+    pub message: ::prost::alloc::string::String,
+}
 /// Health check request
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -491,6 +545,37 @@ pub mod executor_control_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        /// Stream a container's stdout and stderr, read directly from the Docker API
+        pub async fn stream_container_logs(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ContainerLogsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ContainerLogLine>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/basilca.executor.v1.ExecutorControl/StreamContainerLogs",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "basilca.executor.v1.ExecutorControl",
+                        "StreamContainerLogs",
+                    ),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
         /// Health check and heartbeat
         pub async fn health_check(
             &mut self,
@@ -576,6 +661,20 @@ pub mod executor_control_server {
             &self,
             request: tonic::Request<super::LogSubscriptionRequest>,
         ) -> std::result::Result<tonic::Response<Self::StreamLogsStream>, tonic::Status>;
+        /// Server streaming response type for the StreamContainerLogs method.
+        type StreamContainerLogsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::ContainerLogLine, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Stream a container's stdout and stderr, read directly from the Docker API
+        async fn stream_container_logs(
+            &self,
+            request: tonic::Request<super::ContainerLogsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamContainerLogsStream>,
+            tonic::Status,
+        >;
         /// Health check and heartbeat
         async fn health_check(
             &self,
@@ -909,6 +1008,57 @@ pub mod executor_control_server {
                     };
                     Box::pin(fut)
                 }
+                "/basilca.executor.v1.ExecutorControl/StreamContainerLogs" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamContainerLogsSvc<T: ExecutorControl>(pub Arc<T>);
+                    impl<
+                        T: ExecutorControl,
+                    > tonic::server::ServerStreamingService<super::ContainerLogsRequest>
+                    for StreamContainerLogsSvc<T> {
+                        type Response = super::ContainerLogLine;
+                        type ResponseStream = T::StreamContainerLogsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ContainerLogsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ExecutorControl>::stream_container_logs(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamContainerLogsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/basilca.executor.v1.ExecutorControl/HealthCheck" => {
                     #[allow(non_camel_case_types)]
                     struct HealthCheckSvc<T: ExecutorControl>(pub Arc<T>);
@@ -37,6 +37,15 @@ pub struct HealthCheckResponse {
     /// Active challenges
     #[prost(uint32, tag = "6")]
     pub active_challenges: u32,
+    /// Whether the executor is draining for maintenance
+    #[prost(bool, tag = "7")]
+    pub draining: bool,
+    /// Rentals still running on the executor
+    #[prost(uint32, tag = "8")]
+    pub active_rentals: u32,
+    /// Seconds until the drain completes, when known
+    #[prost(uint64, optional, tag = "9")]
+    pub estimated_drain_seconds: ::core::option::Option<u64>,
 }
 /// SSH key update request
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -107,6 +116,34 @@ pub struct StatusResponse {
     #[prost(uint64, tag = "8")]
     pub uptime_seconds: u64,
 }
+/// Drain mode request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DrainModeRequest {
+    /// Start draining, or cancel a drain in progress
+    #[prost(bool, tag = "1")]
+    pub enabled: bool,
+    /// Seconds existing rentals may keep running before they are stopped;
+    /// 0 waits for them to finish
+    #[prost(uint64, tag = "2")]
+    pub deadline_seconds: u64,
+    /// Miner authorization
+    #[prost(string, tag = "3")]
+    pub miner_hotkey: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DrainModeResponse {
+    /// Whether the executor is now draining
+    #[prost(bool, tag = "1")]
+    pub draining: bool,
+    /// Rentals still running on the executor
+    #[prost(uint32, tag = "2")]
+    pub active_rentals: u32,
+    /// Seconds until the drain completes, when known
+    #[prost(uint64, optional, tag = "3")]
+    pub estimated_drain_seconds: ::core::option::Option<u64>,
+}
 /// Generated client implementations.
 pub mod executor_management_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -285,6 +322,37 @@ pub mod executor_management_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Start or cancel draining the executor for maintenance
+        pub async fn set_drain_mode(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DrainModeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DrainModeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/basilca.executor_management.v1.ExecutorManagement/SetDrainMode",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "basilca.executor_management.v1.ExecutorManagement",
+                        "SetDrainMode",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -315,6 +383,14 @@ pub mod executor_management_server {
             &self,
             request: tonic::Request<super::StatusRequest>,
         ) -> std::result::Result<tonic::Response<super::StatusResponse>, tonic::Status>;
+        /// Start or cancel draining the executor for maintenance
+        async fn set_drain_mode(
+            &self,
+            request: tonic::Request<super::DrainModeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DrainModeResponse>,
+            tonic::Status,
+        >;
     }
     /// ExecutorManagement service for miner-executor management
     /// Implemented by: executor
@@ -538,6 +614,52 @@ pub mod executor_management_server {
                     };
                     Box::pin(fut)
                 }
+                "/basilca.executor_management.v1.ExecutorManagement/SetDrainMode" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetDrainModeSvc<T: ExecutorManagement>(pub Arc<T>);
+                    impl<
+                        T: ExecutorManagement,
+                    > tonic::server::UnaryService<super::DrainModeRequest>
+                    for SetDrainModeSvc<T> {
+                        type Response = super::DrainModeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DrainModeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ExecutorManagement>::set_drain_mode(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetDrainModeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
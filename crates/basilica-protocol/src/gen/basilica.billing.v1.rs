@@ -295,6 +295,29 @@ pub struct TelemetryData {
     pub resource_usage: ::core::option::Option<ResourceUsage>,
     #[prost(map = "string, double", tag = "5")]
     pub custom_metrics: ::std::collections::HashMap<::prost::alloc::string::String, f64>,
+    #[prost(message, optional, tag = "6")]
+    pub lifecycle_event: ::core::option::Option<LifecycleEvent>,
+    /// Static labels configured on the executor, e.g. cluster or region
+    #[prost(map = "string, string", tag = "7")]
+    pub labels: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+}
+/// Discrete container state transition used to bound billable intervals
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LifecycleEvent {
+    #[prost(string, tag = "1")]
+    pub container_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub rental_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "ContainerState", tag = "3")]
+    pub from_state: i32,
+    #[prost(enumeration = "ContainerState", tag = "4")]
+    pub to_state: i32,
+    #[prost(message, optional, tag = "5")]
+    pub timestamp: ::core::option::Option<::prost_types::Timestamp>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -364,6 +387,25 @@ pub struct UsageReportResponse {
     /// Decimal string
     #[prost(string, tag = "4")]
     pub total_cost: ::prost::alloc::string::String,
+    /// Accrued cost split by resource
+    #[prost(message, optional, tag = "5")]
+    pub cost_breakdown: ::core::option::Option<RentalCostBreakdown>,
+}
+/// All amounts are decimal strings; total_cost is the sum of the others.
+/// Resources without a cost are not billed separately from GPU time.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RentalCostBreakdown {
+    #[prost(string, tag = "1")]
+    pub compute_cost: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub memory_cost: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub network_cost: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub storage_cost: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "5")]
+    pub total_cost: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -391,6 +433,9 @@ pub struct UsageSummary {
     pub avg_gpu_utilization: f64,
     #[prost(message, optional, tag = "6")]
     pub duration: ::core::option::Option<::prost_types::Duration>,
+    /// GPU energy used so far, as reported by the executor
+    #[prost(double, tag = "7")]
+    pub energy_kwh: f64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -542,6 +587,41 @@ impl RentalStatus {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum ContainerState {
+    Unspecified = 0,
+    Created = 1,
+    Running = 2,
+    Paused = 3,
+    Exited = 4,
+}
+impl ContainerState {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ContainerState::Unspecified => "CONTAINER_STATE_UNSPECIFIED",
+            ContainerState::Created => "CONTAINER_STATE_CREATED",
+            ContainerState::Running => "CONTAINER_STATE_RUNNING",
+            ContainerState::Paused => "CONTAINER_STATE_PAUSED",
+            ContainerState::Exited => "CONTAINER_STATE_EXITED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CONTAINER_STATE_UNSPECIFIED" => Some(Self::Unspecified),
+            "CONTAINER_STATE_CREATED" => Some(Self::Created),
+            "CONTAINER_STATE_RUNNING" => Some(Self::Running),
+            "CONTAINER_STATE_PAUSED" => Some(Self::Paused),
+            "CONTAINER_STATE_EXITED" => Some(Self::Exited),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum UsageAggregation {
     Unspecified = 0,
     Minute = 1,
@@ -47,4 +47,9 @@ pub mod basilica {
             include!("basilica.payments.v1.rs");
         }
     }
+    pub mod signer {
+        pub mod v1 {
+            include!("basilica.signer.v1.rs");
+        }
+    }
 }
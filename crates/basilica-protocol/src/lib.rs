@@ -63,6 +63,9 @@
 //!     .await?;
 //! ```
 
+/// Encoded descriptors of every service, for gRPC server reflection
+pub const FILE_DESCRIPTOR_SET: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/descriptor.bin"));
+
 // Create proper module hierarchy for generated protobuf code
 pub mod basilca {
     pub mod common {
@@ -118,12 +121,36 @@ pub mod basilca {
             include!("gen/basilica.payments.v1.rs");
         }
     }
+
+    pub mod signer {
+        pub mod v1 {
+            include!("gen/basilica.signer.v1.rs");
+        }
+    }
 }
 
 // Structured re-exports for better organization
 pub mod common {
     //! Common types and data structures used across all services
     pub use crate::basilca::common::v1::*;
+
+    impl std::fmt::Debug for RegistryAuth {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RegistryAuth")
+                .field("registry", &self.registry)
+                .field("username", &self.username)
+                .field(
+                    "token",
+                    &if self.token.is_empty() {
+                        ""
+                    } else {
+                        "<redacted>"
+                    },
+                )
+                .field("secret_ref", &self.secret_ref)
+                .finish()
+        }
+    }
 }
 
 pub mod executor_control {
@@ -136,6 +163,39 @@ pub mod executor_control {
     //! - Real-time log streaming
     //! - Health monitoring and heartbeat
     pub use crate::basilca::executor::v1::*;
+
+    impl ContainerLogsRequest {
+        /// Bytes a validator signs to authenticate this request without its
+        /// miner
+        ///
+        /// Covers the validator hotkey, the timestamp and nonce from
+        /// `validator_auth`, and the request encoded without either
+        /// authentication field, so none of them can be changed after signing.
+        pub fn validator_signing_payload(&self) -> Vec<u8> {
+            use prost::Message;
+
+            let (timestamp_ms, nonce) = self
+                .validator_auth
+                .as_ref()
+                .map(|auth| (auth.timestamp_ms, auth.nonce.as_slice()))
+                .unwrap_or_default();
+            let unsigned = Self {
+                auth: None,
+                validator_auth: None,
+                ..self.clone()
+            };
+
+            let mut payload = format!(
+                "VALIDATOR_AUTH:{}:{}:{}:",
+                self.validator_hotkey,
+                timestamp_ms,
+                String::from_utf8_lossy(nonce)
+            )
+            .into_bytes();
+            payload.extend(unsigned.encode_to_vec());
+            payload
+        }
+    }
 }
 
 pub mod miner_discovery {
@@ -200,6 +260,15 @@ pub mod payments {
     pub use crate::basilca::payments::v1::*;
 }
 
+pub mod signer {
+    //! Remote signer for treasury keys kept outside the payments process
+    //!
+    //! Lets payments use keys held in an HSM or signing service:
+    //! - Key generation returning only an opaque key ID and public key
+    //! - Signing payloads by key ID
+    pub use crate::basilca::signer::v1::*;
+}
+
 // Re-export common types at crate root for convenience
 pub use basilica_common::*;
 
@@ -409,6 +478,8 @@ mod tests {
             core_clock_mhz: 2205,
             memory_clock_mhz: 10501,
             compute_capability: "8.9".to_string(),
+            pci_bus_id: None,
+            numa_node: None,
         };
 
         assert!(utils::validate_gpu_spec(&gpu).is_ok());
@@ -430,6 +501,7 @@ mod tests {
             user: "root".to_string(),
             gpu_requirements: vec!["nvidia".to_string()],
             network_mode: "bridge".to_string(),
+            registry_auth: None,
         };
 
         assert!(utils::validate_container_spec(&spec).is_ok());
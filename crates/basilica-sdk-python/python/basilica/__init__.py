@@ -4,8 +4,9 @@ Basilica SDK for Python
 A Python SDK for interacting with the Basilica GPU rental network.
 """
 
+import asyncio
 import os
-from typing import Optional, Dict, Any, List
+from typing import Optional, Dict, Any, List, AsyncIterator, Tuple, Union
 
 from basilica._basilica import (
     BasilicaClient as _BasilicaClient,
@@ -25,6 +26,7 @@ from basilica._basilica import (
     AvailabilityInfo,
     # Request types
     StartRentalApiRequest,
+    StartRentalApiRequestBuilder,
     ExecutorSelection,
     GpuRequirements,
     PortMappingRequest,
@@ -32,6 +34,7 @@ from basilica._basilica import (
     VolumeMountRequest,
     ListAvailableExecutorsQuery,
     ListRentalsQuery,
+    RentalState,
     # Constants from Rust
     DEFAULT_API_URL,
     DEFAULT_TIMEOUT_SECS,
@@ -42,6 +45,8 @@ from basilica._basilica import (
     DEFAULT_CPU_CORES,
     DEFAULT_MEMORY_MB,
     DEFAULT_STORAGE_MB,
+    DEFAULT_PORT_PROTOCOL,
+    PORT_PROTOCOLS,
 )
 
 # Default command is a list in Python
@@ -66,11 +71,13 @@ __all__ = [
     "AvailabilityInfo",
     # Request types
     "StartRentalApiRequest",
+    "StartRentalApiRequestBuilder",
     "ExecutorSelection",
     "GpuRequirements",
     "PortMappingRequest",
     "ListAvailableExecutorsQuery",
     "ListRentalsQuery",
+    "RentalState",
 ]
 
 
@@ -88,15 +95,22 @@ class BasilicaClient:
     def __init__(
         self,
         base_url: Optional[str] = None,
-        api_key: Optional[str] = None
+        api_key: Optional[str] = None,
+        auto_auth: bool = True
     ):
         """
         Initialize a new Basilica client.
 
+        Credentials are chosen in this order:
+            1. api_key, when given
+            2. BASILICA_API_TOKEN, when auto_auth is True
+
         Args:
             base_url: The base URL of the Basilica API (default: from BASILICA_API_URL env or DEFAULT_API_URL)
             api_key: Optional authentication token (default: from BASILICA_API_TOKEN env)
                 Create token using: basilica tokens create
+            auto_auth: Look up BASILICA_API_TOKEN when api_key is not given. With
+                auto_auth=False and no api_key, every call raises PermissionError.
         """
         # Auto-detect base_url if not provided
         if base_url is None:
@@ -104,7 +118,27 @@ class BasilicaClient:
 
         # Pass api_key directly to Rust binding
         # The Rust binding will check BASILICA_API_TOKEN env var if api_key is None
-        self._client = _BasilicaClient(base_url, api_key)
+        self._client = _BasilicaClient(base_url, api_key, auto_auth)
+
+    @classmethod
+    def from_env(cls) -> "BasilicaClient":
+        """
+        Create a client from BASILICA_API_URL and BASILICA_API_TOKEN only.
+
+        Nothing is read from the filesystem. If BASILICA_API_TOKEN is unset,
+        every call raises PermissionError.
+        """
+        client = cls.__new__(cls)
+        client._client = _BasilicaClient.from_env()
+        return client
+
+    def disable_auto_auth(self) -> None:
+        """
+        Stop using a token the client picked up from BASILICA_API_TOKEN.
+
+        Later calls raise PermissionError unless an api_key was passed explicitly.
+        """
+        self._client.disable_auto_auth()
     
     def health_check(self) -> HealthCheckResponse:
         """
@@ -120,7 +154,8 @@ class BasilicaClient:
         available: Optional[bool] = None,
         gpu_type: Optional[str] = None,
         min_gpu_count: Optional[int] = None,
-        min_gpu_memory: Optional[int] = None
+        min_gpu_memory: Optional[int] = None,
+        max_staleness: Optional[int] = None
     ) -> List[AvailableExecutor]:
         """
         List available executors.
@@ -130,16 +165,18 @@ class BasilicaClient:
             gpu_type: Filter by GPU type
             min_gpu_count: Filter by minimum GPU count
             min_gpu_memory: Filter by minimum GPU memory in GB
+            max_staleness: Exclude executors whose availability data is older than this many seconds
             
         Returns:
             List[AvailableExecutor]: List of typed executor objects with details
         """
-        if any([available is not None, gpu_type is not None, min_gpu_count is not None, min_gpu_memory is not None]):
+        if any([available is not None, gpu_type is not None, min_gpu_count is not None, min_gpu_memory is not None, max_staleness is not None]):
             query = ListAvailableExecutorsQuery(
                 available=available,
                 gpu_type=gpu_type,
                 min_gpu_count=min_gpu_count,
-                min_gpu_memory=min_gpu_memory
+                min_gpu_memory=min_gpu_memory,
+                max_staleness=max_staleness
             )
             return self._client.list_executors(query)
         else:
@@ -291,7 +328,7 @@ class BasilicaClient:
     
     def list_rentals(
         self,
-        status: Optional[str] = None,
+        status: Optional[Union[RentalState, str]] = None,
         gpu_type: Optional[str] = None,
         min_gpu_count: Optional[int] = None
     ) -> Dict[str, Any]:
@@ -299,7 +336,8 @@ class BasilicaClient:
         List rentals.
         
         Args:
-            status: Filter by status (e.g., "active", "provisioning")
+            status: Filter by state (e.g., RentalState.Active). Strings are
+                deprecated and raise ValueError for unknown states
             gpu_type: Filter by GPU type
             min_gpu_count: Filter by minimum GPU count
             
@@ -314,4 +352,28 @@ class BasilicaClient:
             )
             return self._client.list_rentals(query)
         else:
-            return self._client.list_rentals(None)
\ No newline at end of file
+            return self._client.list_rentals(None)
+    
+    async def stream_logs_multi(
+        self, rental_ids: List[str]
+    ) -> AsyncIterator[Tuple[str, Union[str, Exception]]]:
+        """
+        Follow the logs of several rentals at once.
+        
+        Lines from all rentals are interleaved in the order they arrive. Each item
+        is a ``(rental_id, line)`` pair; a rental whose log stream cannot be opened
+        yields its exception in place of a line while the others continue.
+        
+        Args:
+            rental_ids: The rental IDs to follow
+            
+        Yields:
+            (rental_id, line) pairs until every followed rental's stream ends
+        """
+        stream = self._client.stream_logs_multi(list(rental_ids))
+        while True:
+            # Reading blocks until the next line arrives, so keep it off the event loop
+            item = await asyncio.to_thread(next, stream, None)
+            if item is None:
+                return
+            yield item
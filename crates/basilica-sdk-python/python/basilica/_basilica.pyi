@@ -15,6 +15,8 @@ class AvailabilityInfo:
     def verification_score(self) -> builtins.float: ...
     @property
     def uptime_percentage(self) -> builtins.float: ...
+    @property
+    def as_of(self) -> typing.Optional[builtins.str]: ...
 
 class AvailableExecutor:
     r"""
@@ -24,6 +26,10 @@ class AvailableExecutor:
     def executor(self) -> ExecutorDetails: ...
     @property
     def availability(self) -> AvailabilityInfo: ...
+    @property
+    def hourly_price(self) -> typing.Optional[builtins.str]: ...
+    @property
+    def currency(self) -> typing.Optional[builtins.str]: ...
 
 class BasilicaClient:
     r"""
@@ -58,6 +64,11 @@ class ExecutorDetails:
 class GpuRequirements:
     r"""
     GPU requirements for executor selection
+    
+    `gpu_count` must be at least 1 and `min_memory_gb` must not be negative.
+    `gpu_type` is a GPU model such as "h100" or "b200", matched
+    case-insensitively against the GPU names `list_executors()` reports; leave
+    it as None to accept any GPU. Invalid values raise `ValueError`.
     """
     @property
     def gpu_count(self) -> builtins.int: ...
@@ -65,13 +76,17 @@ class GpuRequirements:
     def gpu_type(self) -> typing.Optional[builtins.str]: ...
     @property
     def min_memory_gb(self) -> builtins.int: ...
+    @property
+    def mig_profile(self) -> typing.Optional[builtins.str]: ...
     @gpu_count.setter
     def gpu_count(self, value: builtins.int) -> None: ...
     @gpu_type.setter
     def gpu_type(self, value: typing.Optional[builtins.str]) -> None: ...
     @min_memory_gb.setter
     def min_memory_gb(self, value: builtins.int) -> None: ...
-    def __new__(cls, gpu_count:builtins.int, min_memory_gb:builtins.int, gpu_type:typing.Optional[builtins.str]=None) -> GpuRequirements: ...
+    @mig_profile.setter
+    def mig_profile(self, value: typing.Optional[builtins.str]) -> None: ...
+    def __new__(cls, gpu_count:builtins.int, min_memory_gb:builtins.int, gpu_type:typing.Optional[builtins.str]=None, mig_profile:typing.Optional[builtins.str]=None) -> GpuRequirements: ...
 
 class GpuSpec:
     r"""
@@ -111,6 +126,8 @@ class ListAvailableExecutorsQuery:
     def gpu_type(self) -> typing.Optional[builtins.str]: ...
     @property
     def min_gpu_count(self) -> typing.Optional[builtins.int]: ...
+    @property
+    def max_staleness(self) -> typing.Optional[builtins.int]: ...
     @available.setter
     def available(self, value: typing.Optional[builtins.bool]) -> None: ...
     @min_gpu_memory.setter
@@ -119,25 +136,36 @@ class ListAvailableExecutorsQuery:
     def gpu_type(self, value: typing.Optional[builtins.str]) -> None: ...
     @min_gpu_count.setter
     def min_gpu_count(self, value: typing.Optional[builtins.int]) -> None: ...
-    def __new__(cls, available:typing.Optional[builtins.bool]=None, min_gpu_memory:typing.Optional[builtins.int]=None, gpu_type:typing.Optional[builtins.str]=None, min_gpu_count:typing.Optional[builtins.int]=None) -> ListAvailableExecutorsQuery: ...
+    @max_staleness.setter
+    def max_staleness(self, value: typing.Optional[builtins.int]) -> None: ...
+    def __new__(cls, available:typing.Optional[builtins.bool]=None, min_gpu_memory:typing.Optional[builtins.int]=None, gpu_type:typing.Optional[builtins.str]=None, min_gpu_count:typing.Optional[builtins.int]=None, max_staleness:typing.Optional[builtins.int]=None) -> ListAvailableExecutorsQuery: ...
 
 class ListRentalsQuery:
     r"""
     Query parameters for listing rentals
     """
     @property
-    def status(self) -> typing.Optional[builtins.str]: ...
+    def status(self) -> typing.Optional[RentalState]:
+        r"""
+        Only list rentals in this state, or every rental when `None`
+        """
     @property
     def gpu_type(self) -> typing.Optional[builtins.str]: ...
     @property
     def min_gpu_count(self) -> typing.Optional[builtins.int]: ...
     @status.setter
-    def status(self, value: typing.Optional[builtins.str]) -> None: ...
+    def status(self, value: typing.Optional[typing.Union[RentalState, builtins.str]]) -> None: ...
     @gpu_type.setter
     def gpu_type(self, value: typing.Optional[builtins.str]) -> None: ...
     @min_gpu_count.setter
     def min_gpu_count(self, value: typing.Optional[builtins.int]) -> None: ...
-    def __new__(cls, status:typing.Optional[builtins.str]=None, gpu_type:typing.Optional[builtins.str]=None, min_gpu_count:typing.Optional[builtins.int]=None) -> ListRentalsQuery: ...
+    def __new__(cls, status:typing.Optional[typing.Union[RentalState, builtins.str]]=None, gpu_type:typing.Optional[builtins.str]=None, min_gpu_count:typing.Optional[builtins.int]=None) -> ListRentalsQuery: ...
+
+class LogStream:
+    r"""
+    Iterator over `(rental_id, line)` pairs merged from several rentals' logs
+    """
+    ...
 
 class PortMappingRequest:
     r"""
@@ -171,6 +199,10 @@ class RentalResponse:
     def container_name(self) -> builtins.str: ...
     @property
     def status(self) -> builtins.str: ...
+    @property
+    def executor_id(self) -> builtins.str: ...
+    @property
+    def pin_honored(self) -> typing.Optional[builtins.bool]: ...
 
 class RentalStatus:
     r"""
@@ -257,6 +289,8 @@ class StartRentalApiRequest:
     def volumes(self) -> builtins.list[VolumeMountRequest]: ...
     @property
     def no_ssh(self) -> builtins.bool: ...
+    @property
+    def idle_timeout_secs(self) -> typing.Optional[builtins.int]: ...
     @executor_selection.setter
     def executor_selection(self, value: ExecutorSelection) -> None: ...
     @container_image.setter
@@ -275,7 +309,30 @@ class StartRentalApiRequest:
     def volumes(self, value: builtins.list[VolumeMountRequest]) -> None: ...
     @no_ssh.setter
     def no_ssh(self, value: builtins.bool) -> None: ...
-    def __new__(cls, executor_selection:ExecutorSelection, container_image:builtins.str, ssh_public_key:builtins.str, environment:typing.Optional[typing.Mapping[builtins.str, builtins.str]]=None, ports:typing.Optional[typing.Sequence[PortMappingRequest]]=None, resources:typing.Optional[ResourceRequirementsRequest]=None, command:typing.Optional[typing.Sequence[builtins.str]]=None, volumes:typing.Optional[typing.Sequence[VolumeMountRequest]]=None, no_ssh:builtins.bool=False) -> StartRentalApiRequest: ...
+    @idle_timeout_secs.setter
+    def idle_timeout_secs(self, value: typing.Optional[builtins.int]) -> None: ...
+    def __new__(cls, executor_selection:ExecutorSelection, container_image:builtins.str, ssh_public_key:builtins.str, environment:typing.Optional[typing.Mapping[builtins.str, builtins.str]]=None, ports:typing.Optional[typing.Sequence[PortMappingRequest]]=None, resources:typing.Optional[ResourceRequirementsRequest]=None, command:typing.Optional[typing.Sequence[builtins.str]]=None, volumes:typing.Optional[typing.Sequence[VolumeMountRequest]]=None, no_ssh:builtins.bool=False, idle_timeout_secs:typing.Optional[builtins.int]=None) -> StartRentalApiRequest: ...
+
+class StartRentalApiRequestBuilder:
+    r"""
+    Fluent builder for StartRentalApiRequest that validates before any
+    network call
+    """
+    def __new__(cls, container_image:builtins.str) -> StartRentalApiRequestBuilder: ...
+    def executor_id(self, executor_id:builtins.str) -> StartRentalApiRequestBuilder: ...
+    def gpu_requirements(self, gpu_requirements:GpuRequirements) -> StartRentalApiRequestBuilder: ...
+    def ssh_public_key(self, key:builtins.str) -> StartRentalApiRequestBuilder: ...
+    def env(self, name:builtins.str, value:builtins.str) -> StartRentalApiRequestBuilder: ...
+    def port(self, container_port:builtins.int, host_port:builtins.int, protocol:typing.Optional[builtins.str]=None) -> StartRentalApiRequestBuilder: ...
+    def resources(self, resources:ResourceRequirementsRequest) -> StartRentalApiRequestBuilder: ...
+    def command(self, command:typing.Sequence[builtins.str]) -> StartRentalApiRequestBuilder: ...
+    def volume(self, host_path:builtins.str, container_path:builtins.str, read_only:builtins.bool=False) -> StartRentalApiRequestBuilder: ...
+    def no_ssh(self, no_ssh:builtins.bool) -> StartRentalApiRequestBuilder: ...
+    def idle_timeout_secs(self, secs:builtins.int) -> StartRentalApiRequestBuilder: ...
+    def build(self) -> StartRentalApiRequest:
+        r"""
+        Validate and return the request, raising ValueError if it is invalid
+        """
 
 class VolumeMountRequest:
     r"""
@@ -295,6 +352,17 @@ class VolumeMountRequest:
     def read_only(self, value: builtins.bool) -> None: ...
     def __new__(cls, host_path:builtins.str, container_path:builtins.str, read_only:builtins.bool=False) -> VolumeMountRequest: ...
 
+class RentalState(Enum):
+    r"""
+    Rental state to filter `list_rentals` by
+    """
+    Provisioning = ...
+    Active = ...
+    Unhealthy = ...
+    Stopping = ...
+    Stopped = ...
+    Failed = ...
+
 class ExecutorSelection(Enum):
     r"""
     Executor selection strategy
@@ -305,6 +373,9 @@ class ExecutorSelection(Enum):
 def executor_by_gpu(gpu_requirements:GpuRequirements) -> ExecutorSelection:
     r"""
     Helper function to create executor selection by GPU requirements
+    
+    Raises `ValueError` if the requirements ask for fewer than one GPU or an
+    empty GPU type.
     """
 
 def executor_by_id(executor_id:builtins.str) -> ExecutorSelection:
@@ -4,21 +4,24 @@
 mod types;
 
 use basilica_sdk::{
-    client::{DEFAULT_API_URL, DEFAULT_TIMEOUT_SECS},
+    client::{DEFAULT_API_URL, DEFAULT_CONTAINER_IMAGE, DEFAULT_TIMEOUT_SECS},
+    types::{DEFAULT_PORT_PROTOCOL, PORT_PROTOCOLS},
     BasilicaClient as RustClient, ClientBuilder,
 };
+use futures_util::{stream::BoxStream, StreamExt};
 use pyo3::exceptions::{
     PyConnectionError, PyKeyError, PyPermissionError, PyRuntimeError, PyValueError,
 };
 use pyo3::prelude::*;
+use pyo3::types::PyString;
 #[cfg(feature = "stub-gen")]
 use pyo3_stub_gen::define_stub_info_gatherer;
 #[cfg(feature = "stub-gen")]
 use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pyfunction};
 use pythonize::pythonize;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tokio::runtime::Runtime;
+use tokio::runtime::{Handle, Runtime};
 
 use crate::types::{
     AvailableExecutor, HealthCheckResponse, ListAvailableExecutorsQuery, ListRentalsQuery,
@@ -31,6 +34,9 @@ use crate::types::{
 struct BasilicaClient {
     inner: Arc<RustClient>,
     runtime: Runtime,
+    base_url: String,
+    /// The API key came from `BASILICA_API_TOKEN` rather than the caller
+    auto_detected_key: bool,
 }
 
 // Small helper to convert serializable Rust values into PyObject without
@@ -40,45 +46,105 @@ fn to_pyobject<T: serde::Serialize>(py: Python<'_>, value: &T) -> PyResult<Py<py
     Ok(pythonize(py, value)?.unbind())
 }
 
+fn new_runtime() -> PyResult<Runtime> {
+    Runtime::new().map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))
+}
+
+/// Build a client that only ever uses `api_key`; without one, calls fail
+/// with an authentication error
+fn build_client(runtime: &Runtime, builder: ClientBuilder) -> PyResult<RustClient> {
+    runtime
+        .block_on(async {
+            builder
+                .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+                .without_auto_auth()
+                .build()
+        })
+        .map_err(|e| PyRuntimeError::new_err(format!("Failed to create client: {}", e)))
+}
+
 #[pymethods]
 impl BasilicaClient {
     /// Create a new BasilicaClient
     ///
+    /// Credentials are chosen in this order:
+    ///     1. `api_key`, when given
+    ///     2. The BASILICA_API_TOKEN environment variable, when `auto_auth` is true
+    ///
+    /// With `auto_auth=False` and no `api_key`, the client is created without
+    /// credentials and every call raises PermissionError.
+    ///
     /// Args:
     ///     base_url: The base URL of the Basilica API
     ///     api_key: Optional authentication token from 'basilica tokens create'
+    ///     auto_auth: Look up BASILICA_API_TOKEN when no api_key is given
     #[new]
-    #[pyo3(signature = (base_url, api_key=None))]
-    fn new(base_url: String, api_key: Option<String>) -> PyResult<Self> {
-        let runtime = Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        // Check for API key - either provided directly or from BASILICA_API_TOKEN env var
-        let api_key = api_key.or_else(|| std::env::var("BASILICA_API_TOKEN").ok());
-
-        let api_key = api_key.ok_or_else(|| {
-            PyRuntimeError::new_err(
-                "No API key provided. Please provide an API key directly or set BASILICA_API_TOKEN environment variable. \
-                Create a key using: basilica tokens create"
-            )
-        })?;
+    #[pyo3(signature = (base_url, api_key=None, auto_auth=true))]
+    fn new(base_url: String, api_key: Option<String>, auto_auth: bool) -> PyResult<Self> {
+        let runtime = new_runtime()?;
+
+        let auto_detected_key = api_key.is_none() && auto_auth;
+        let api_key = match api_key {
+            Some(api_key) => Some(api_key),
+            None if auto_auth => {
+                let api_key = std::env::var("BASILICA_API_TOKEN").map_err(|_| {
+                    PyRuntimeError::new_err(
+                        "No API key provided. Please provide an API key directly or set BASILICA_API_TOKEN environment variable. \
+                        Create a key using: basilica tokens create"
+                    )
+                })?;
+                Some(api_key)
+            }
+            None => None,
+        };
 
-        let client = runtime
-            .block_on(async {
-                ClientBuilder::default()
-                    .base_url(base_url)
-                    .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-                    .with_api_key(&api_key)
-                    .build()
-            })
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create client: {}", e)))?;
+        let mut builder = ClientBuilder::default().base_url(&base_url);
+        if let Some(api_key) = &api_key {
+            builder = builder.with_api_key(api_key);
+        }
+        let client = build_client(&runtime, builder)?;
+
+        Ok(Self {
+            inner: Arc::new(client),
+            runtime,
+            base_url,
+            auto_detected_key,
+        })
+    }
+
+    /// Create a client from BASILICA_API_URL and BASILICA_API_TOKEN only
+    ///
+    /// Nothing is read from the filesystem. If BASILICA_API_TOKEN is unset the
+    /// client has no credentials and every call raises PermissionError.
+    #[staticmethod]
+    fn from_env() -> PyResult<Self> {
+        let runtime = new_runtime()?;
+        let base_url =
+            std::env::var("BASILICA_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+        let client = build_client(&runtime, ClientBuilder::from_env().base_url(&base_url))?;
 
         Ok(Self {
             inner: Arc::new(client),
             runtime,
+            base_url,
+            auto_detected_key: false,
         })
     }
 
+    /// Stop using credentials the client found on its own
+    ///
+    /// A key read from BASILICA_API_TOKEN is dropped, so later calls raise
+    /// PermissionError. A key passed explicitly is kept.
+    fn disable_auto_auth(&mut self) -> PyResult<()> {
+        if !self.auto_detected_key {
+            return Ok(());
+        }
+        let builder = ClientBuilder::default().base_url(&self.base_url);
+        self.inner = Arc::new(build_client(&self.runtime, builder)?);
+        self.auto_detected_key = false;
+        Ok(())
+    }
+
     // Python SDK uses API key authentication
     // Users should create API keys via CLI: `basilica tokens create`
     // Then use the Python SDK with the key directly or via BASILICA_API_TOKEN environment variable
@@ -200,27 +266,79 @@ impl BasilicaClient {
         // Keep list_rentals as PyObject for now since it returns a complex structure
         to_pyobject(py, &response)
     }
+
+    /// Follow the logs of several rentals at once
+    ///
+    /// Args:
+    ///     rental_ids: The rental IDs to follow
+    ///
+    /// Returns an iterator of `(rental_id, line)` pairs in arrival order. A rental whose
+    /// stream cannot be opened yields its exception in place of a line.
+    fn stream_logs_multi(&self, rental_ids: Vec<String>) -> LogStream {
+        LogStream {
+            stream: Mutex::new(self.inner.stream_logs_multi(rental_ids).boxed()),
+            runtime: self.runtime.handle().clone(),
+        }
+    }
 }
 
 impl BasilicaClient {
     /// Map Rust errors to appropriate Python exception types
     fn map_error_to_python(&self, error: basilica_sdk::ApiError) -> PyErr {
-        use basilica_sdk::ApiError;
+        api_error_to_python(error)
+    }
+}
 
-        match error {
-            ApiError::InvalidRequest { message } => PyValueError::new_err(message),
-            ApiError::NotFound { resource } => {
-                PyKeyError::new_err(format!("Not found: {}", resource))
-            }
-            ApiError::Authentication { message } | ApiError::MissingAuthentication { message } => {
-                PyPermissionError::new_err(format!("Authentication error: {}. Please provide a valid API key or set BASILICA_API_TOKEN environment variable.", message))
-            }
-            ApiError::Authorization { message } => PyPermissionError::new_err(message),
-            ApiError::HttpClient(e) => PyConnectionError::new_err(e.to_string()),
-            ApiError::BadRequest { message } => PyValueError::new_err(message),
-            ApiError::Internal { message } => PyRuntimeError::new_err(message),
-            _ => PyRuntimeError::new_err(error.to_string()),
+/// Iterator over `(rental_id, line)` pairs merged from several rentals' logs
+#[cfg_attr(feature = "stub-gen", gen_stub_pyclass)]
+#[pyclass]
+struct LogStream {
+    stream: Mutex<BoxStream<'static, (String, basilica_sdk::Result<String>)>>,
+    runtime: Handle,
+}
+
+#[pymethods]
+impl LogStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python) -> PyResult<Option<(String, Py<PyAny>)>> {
+        let next = py.detach(|| {
+            let mut stream = self
+                .stream
+                .lock()
+                .map_err(|_| PyRuntimeError::new_err("Log stream lock poisoned"))?;
+            Ok::<_, PyErr>(self.runtime.block_on(stream.next()))
+        })?;
+
+        Ok(next.map(|(rental_id, line)| {
+            let line = match line {
+                Ok(line) => PyString::new(py, &line).into_any().unbind(),
+                Err(e) => api_error_to_python(e).into_value(py).into_any(),
+            };
+            (rental_id, line)
+        }))
+    }
+}
+
+/// Map Rust errors to appropriate Python exception types
+fn api_error_to_python(error: basilica_sdk::ApiError) -> PyErr {
+    use basilica_sdk::ApiError;
+
+    match error {
+        ApiError::InvalidRequest { message } => PyValueError::new_err(message),
+        ApiError::NotFound { resource } => {
+            PyKeyError::new_err(format!("Not found: {}", resource))
+        }
+        ApiError::Authentication { message } | ApiError::MissingAuthentication { message } => {
+            PyPermissionError::new_err(format!("Authentication error: {}. Please provide a valid API key or set BASILICA_API_TOKEN environment variable.", message))
         }
+        ApiError::Authorization { message } => PyPermissionError::new_err(message),
+        ApiError::HttpClient(e) => PyConnectionError::new_err(e.to_string()),
+        ApiError::BadRequest { message } => PyValueError::new_err(message),
+        ApiError::Internal { message } => PyRuntimeError::new_err(message),
+        _ => PyRuntimeError::new_err(error.to_string()),
     }
 }
 
@@ -232,10 +350,14 @@ fn executor_by_id(executor_id: String) -> types::ExecutorSelection {
 }
 
 /// Helper function to create executor selection by GPU requirements
+///
+/// Raises `ValueError` if the requirements ask for fewer than one GPU or an
+/// empty GPU type.
 #[cfg_attr(feature = "stub-gen", gen_stub_pyfunction)]
 #[pyfunction]
-fn executor_by_gpu(gpu_requirements: types::GpuRequirements) -> types::ExecutorSelection {
-    types::ExecutorSelection::GpuRequirements { gpu_requirements }
+fn executor_by_gpu(gpu_requirements: types::GpuRequirements) -> PyResult<types::ExecutorSelection> {
+    gpu_requirements.validate()?;
+    Ok(types::ExecutorSelection::GpuRequirements { gpu_requirements })
 }
 
 /// Python module for Basilica SDK
@@ -244,22 +366,21 @@ fn _basilica(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Add constants
     m.add("DEFAULT_API_URL", DEFAULT_API_URL)?;
     m.add("DEFAULT_TIMEOUT_SECS", DEFAULT_TIMEOUT_SECS)?;
-    m.add(
-        "DEFAULT_CONTAINER_IMAGE",
-        "nvidia/cuda:12.2.0-base-ubuntu22.04",
-    )?;
+    m.add("DEFAULT_CONTAINER_IMAGE", DEFAULT_CONTAINER_IMAGE)?;
     m.add("DEFAULT_GPU_TYPE", "b200")?;
     m.add("DEFAULT_GPU_COUNT", 1)?;
     m.add("DEFAULT_GPU_MIN_MEMORY_GB", 0)?;
     m.add("DEFAULT_CPU_CORES", 0.0)?;
     m.add("DEFAULT_MEMORY_MB", 0)?;
     m.add("DEFAULT_STORAGE_MB", 0)?;
-    m.add("DEFAULT_PORT_PROTOCOL", "tcp")?;
+    m.add("DEFAULT_PORT_PROTOCOL", DEFAULT_PORT_PROTOCOL)?;
+    m.add("PORT_PROTOCOLS", PORT_PROTOCOLS.to_vec())?;
     m.add("DEFAULT_SSH_USER", "root")?;
     m.add("DEFAULT_SSH_PORT", 22)?;
 
     // Core client
     m.add_class::<BasilicaClient>()?;
+    m.add_class::<LogStream>()?;
 
     // Response types
     m.add_class::<types::HealthCheckResponse>()?;
@@ -275,6 +396,7 @@ fn _basilica(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Request types
     m.add_class::<types::StartRentalApiRequest>()?;
+    m.add_class::<types::StartRentalApiRequestBuilder>()?;
     m.add_class::<types::ExecutorSelection>()?;
     m.add_class::<types::GpuRequirements>()?;
     m.add_class::<types::PortMappingRequest>()?;
@@ -282,6 +404,7 @@ fn _basilica(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<types::VolumeMountRequest>()?;
     m.add_class::<types::ListAvailableExecutorsQuery>()?;
     m.add_class::<types::ListRentalsQuery>()?;
+    m.add_class::<types::RentalStateFilter>()?;
 
     // Helper functions
     m.add_function(wrap_pyfunction!(executor_by_id, m)?)?;
@@ -160,6 +160,10 @@ pub struct RentalResponse {
     pub container_name: String,
     #[pyo3(get)]
     pub status: String,
+    #[pyo3(get)]
+    pub executor_id: String,
+    #[pyo3(get)]
+    pub pin_honored: Option<bool>,
 }
 
 impl From<SdkRentalResponse> for RentalResponse {
@@ -170,6 +174,8 @@ impl From<SdkRentalResponse> for RentalResponse {
             container_id: response.container_info.container_id,
             container_name: response.container_info.container_name,
             status: response.container_info.status,
+            executor_id: response.executor_id,
+            pin_honored: response.pin_honored,
         }
     }
 }
@@ -217,6 +223,8 @@ pub struct AvailabilityInfo {
     pub verification_score: f64,
     #[pyo3(get)]
     pub uptime_percentage: f64,
+    #[pyo3(get)]
+    pub as_of: Option<String>,
 }
 
 impl From<SdkAvailabilityInfo> for AvailabilityInfo {
@@ -225,6 +233,7 @@ impl From<SdkAvailabilityInfo> for AvailabilityInfo {
             available_until: info.available_until.map(|dt| dt.to_rfc3339()),
             verification_score: info.verification_score,
             uptime_percentage: info.uptime_percentage,
+            as_of: info.as_of.map(|dt| dt.to_rfc3339()),
         }
     }
 }
@@ -238,6 +247,10 @@ pub struct AvailableExecutor {
     pub executor: ExecutorDetails,
     #[pyo3(get)]
     pub availability: AvailabilityInfo,
+    #[pyo3(get)]
+    pub hourly_price: Option<String>,
+    #[pyo3(get)]
+    pub currency: Option<String>,
 }
 
 impl From<SdkAvailableExecutor> for AvailableExecutor {
@@ -245,6 +258,8 @@ impl From<SdkAvailableExecutor> for AvailableExecutor {
         Self {
             executor: executor.executor.into(),
             availability: executor.availability.into(),
+            hourly_price: executor.hourly_price,
+            currency: executor.currency,
         }
     }
 }
@@ -281,29 +296,104 @@ impl From<basilica_sdk::types::HealthCheckResponse> for HealthCheckResponse {
 // Request types for Python bindings
 
 /// GPU requirements for executor selection
+///
+/// `gpu_count` must be at least 1 and `min_memory_gb` must not be negative.
+/// `gpu_type` is a GPU model such as "h100" or "b200", matched
+/// case-insensitively against the GPU names `list_executors()` reports; leave
+/// it as None to accept any GPU. Invalid values raise `ValueError`.
 #[cfg_attr(feature = "stub-gen", gen_stub_pyclass)]
 #[pyclass]
 #[derive(Clone)]
 pub struct GpuRequirements {
-    #[pyo3(get, set)]
+    #[pyo3(get)]
     pub gpu_count: u32,
-    #[pyo3(get, set)]
+    #[pyo3(get)]
     pub gpu_type: Option<String>,
-    #[pyo3(get, set)]
+    #[pyo3(get)]
     pub min_memory_gb: u32,
+    #[pyo3(get, set)]
+    pub mig_profile: Option<String>,
 }
 
 #[cfg_attr(feature = "stub-gen", gen_stub_pymethods)]
 #[pymethods]
 impl GpuRequirements {
     #[new]
-    #[pyo3(signature = (gpu_count, min_memory_gb, gpu_type=None))]
-    fn new(gpu_count: u32, min_memory_gb: u32, gpu_type: Option<String>) -> Self {
-        Self {
-            gpu_count,
-            gpu_type,
-            min_memory_gb,
+    #[pyo3(signature = (gpu_count, min_memory_gb, gpu_type=None, mig_profile=None))]
+    fn new(
+        gpu_count: i64,
+        min_memory_gb: i64,
+        gpu_type: Option<String>,
+        mig_profile: Option<String>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            gpu_count: checked_gpu_count(gpu_count)?,
+            gpu_type: checked_gpu_type(gpu_type)?,
+            min_memory_gb: checked_min_memory_gb(min_memory_gb)?,
+            mig_profile,
+        })
+    }
+
+    #[setter]
+    fn set_gpu_count(&mut self, gpu_count: i64) -> PyResult<()> {
+        self.gpu_count = checked_gpu_count(gpu_count)?;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_gpu_type(&mut self, gpu_type: Option<String>) -> PyResult<()> {
+        self.gpu_type = checked_gpu_type(gpu_type)?;
+        Ok(())
+    }
+
+    #[setter]
+    fn set_min_memory_gb(&mut self, min_memory_gb: i64) -> PyResult<()> {
+        self.min_memory_gb = checked_min_memory_gb(min_memory_gb)?;
+        Ok(())
+    }
+}
+
+impl GpuRequirements {
+    /// Check requirements that may have been built on the Rust side
+    pub fn validate(&self) -> PyResult<()> {
+        checked_gpu_count(self.gpu_count.into())?;
+        checked_gpu_type(self.gpu_type.clone())?;
+        Ok(())
+    }
+}
+
+fn checked_gpu_count(gpu_count: i64) -> PyResult<u32> {
+    if gpu_count < 1 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "gpu_count must be at least 1, got {gpu_count}"
+        )));
+    }
+    u32::try_from(gpu_count).map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err(format!("gpu_count {gpu_count} is too large"))
+    })
+}
+
+fn checked_min_memory_gb(min_memory_gb: i64) -> PyResult<u32> {
+    if min_memory_gb < 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "min_memory_gb must not be negative, got {min_memory_gb}"
+        )));
+    }
+    u32::try_from(min_memory_gb).map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "min_memory_gb {min_memory_gb} is too large"
+        ))
+    })
+}
+
+fn checked_gpu_type(gpu_type: Option<String>) -> PyResult<Option<String>> {
+    match gpu_type {
+        Some(gpu_type) if gpu_type.trim().is_empty() => {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "gpu_type must not be empty; pass None to accept any GPU",
+            ))
         }
+        gpu_type => Ok(gpu_type),
     }
 }
 
@@ -313,6 +403,7 @@ impl From<GpuRequirements> for SdkGpuRequirements {
             gpu_count: req.gpu_count,
             gpu_type: req.gpu_type,
             min_memory_gb: req.min_memory_gb,
+            mig_profile: req.mig_profile,
         }
     }
 }
@@ -501,13 +592,15 @@ pub struct StartRentalApiRequest {
     pub volumes: Vec<VolumeMountRequest>,
     #[pyo3(get, set)]
     pub no_ssh: bool,
+    #[pyo3(get, set)]
+    pub idle_timeout_secs: Option<u64>,
 }
 
 #[cfg_attr(feature = "stub-gen", gen_stub_pymethods)]
 #[pymethods]
 impl StartRentalApiRequest {
     #[new]
-    #[pyo3(signature = (executor_selection, container_image, ssh_public_key, environment=None, ports=None, resources=None, command=None, volumes=None, no_ssh=false))]
+    #[pyo3(signature = (executor_selection, container_image, ssh_public_key, environment=None, ports=None, resources=None, command=None, volumes=None, no_ssh=false, idle_timeout_secs=None))]
     #[allow(clippy::too_many_arguments)]
     fn new(
         executor_selection: ExecutorSelection,
@@ -519,6 +612,7 @@ impl StartRentalApiRequest {
         command: Option<Vec<String>>,
         volumes: Option<Vec<VolumeMountRequest>>,
         no_ssh: bool,
+        idle_timeout_secs: Option<u64>,
     ) -> Self {
         Self {
             executor_selection,
@@ -530,6 +624,7 @@ impl StartRentalApiRequest {
             command: command.unwrap_or_default(),
             volumes: volumes.unwrap_or_default(),
             no_ssh,
+            idle_timeout_secs,
         }
     }
 }
@@ -541,15 +636,140 @@ impl From<StartRentalApiRequest> for SdkStartRentalApiRequest {
             container_image: req.container_image,
             ssh_public_key: req.ssh_public_key,
             environment: req.environment,
+            secrets: Vec::new(),
+            registry_auth: None,
             ports: req.ports.into_iter().map(Into::into).collect(),
             resources: req.resources.into(),
             command: req.command,
             volumes: req.volumes.into_iter().map(Into::into).collect(),
             no_ssh: req.no_ssh,
+            idle_timeout_secs: req.idle_timeout_secs,
+            log_format: Default::default(),
+            pre_stop_command: Vec::new(),
+            pre_stop_timeout_secs: None,
         }
     }
 }
 
+/// Fluent builder for StartRentalApiRequest that validates before any
+/// network call
+#[cfg_attr(feature = "stub-gen", gen_stub_pyclass)]
+#[pyclass]
+#[derive(Clone)]
+pub struct StartRentalApiRequestBuilder {
+    request: StartRentalApiRequest,
+}
+
+#[cfg_attr(feature = "stub-gen", gen_stub_pymethods)]
+#[pymethods]
+impl StartRentalApiRequestBuilder {
+    #[new]
+    fn new(container_image: String) -> Self {
+        Self {
+            request: StartRentalApiRequest {
+                executor_selection: ExecutorSelection::GpuRequirements {
+                    gpu_requirements: GpuRequirements {
+                        gpu_count: 1,
+                        gpu_type: Some("b200".to_string()),
+                        min_memory_gb: 0,
+                    },
+                },
+                container_image,
+                ssh_public_key: String::new(),
+                environment: HashMap::new(),
+                ports: Vec::new(),
+                resources: ResourceRequirementsRequest::default(),
+                command: Vec::new(),
+                volumes: Vec::new(),
+                no_ssh: false,
+                idle_timeout_secs: None,
+            },
+        }
+    }
+
+    fn executor_id(mut slf: PyRefMut<'_, Self>, executor_id: String) -> PyRefMut<'_, Self> {
+        slf.request.executor_selection = ExecutorSelection::ExecutorId { executor_id };
+        slf
+    }
+
+    fn gpu_requirements(
+        mut slf: PyRefMut<'_, Self>,
+        gpu_requirements: GpuRequirements,
+    ) -> PyRefMut<'_, Self> {
+        slf.request.executor_selection = ExecutorSelection::GpuRequirements { gpu_requirements };
+        slf
+    }
+
+    fn ssh_public_key(mut slf: PyRefMut<'_, Self>, key: String) -> PyRefMut<'_, Self> {
+        slf.request.ssh_public_key = key;
+        slf
+    }
+
+    fn env(mut slf: PyRefMut<'_, Self>, name: String, value: String) -> PyRefMut<'_, Self> {
+        slf.request.environment.insert(name, value);
+        slf
+    }
+
+    #[pyo3(signature = (container_port, host_port, protocol=None))]
+    fn port(
+        mut slf: PyRefMut<'_, Self>,
+        container_port: u32,
+        host_port: u32,
+        protocol: Option<String>,
+    ) -> PyRefMut<'_, Self> {
+        slf.request
+            .ports
+            .push(PortMappingRequest::new(container_port, host_port, protocol));
+        slf
+    }
+
+    fn resources(
+        mut slf: PyRefMut<'_, Self>,
+        resources: ResourceRequirementsRequest,
+    ) -> PyRefMut<'_, Self> {
+        slf.request.resources = resources;
+        slf
+    }
+
+    fn command(mut slf: PyRefMut<'_, Self>, command: Vec<String>) -> PyRefMut<'_, Self> {
+        slf.request.command = command;
+        slf
+    }
+
+    #[pyo3(signature = (host_path, container_path, read_only=false))]
+    fn volume(
+        mut slf: PyRefMut<'_, Self>,
+        host_path: String,
+        container_path: String,
+        read_only: bool,
+    ) -> PyRefMut<'_, Self> {
+        slf.request.volumes.push(VolumeMountRequest::new(
+            host_path,
+            container_path,
+            read_only,
+        ));
+        slf
+    }
+
+    fn no_ssh(mut slf: PyRefMut<'_, Self>, no_ssh: bool) -> PyRefMut<'_, Self> {
+        slf.request.no_ssh = no_ssh;
+        slf
+    }
+
+    fn idle_timeout_secs(mut slf: PyRefMut<'_, Self>, secs: u64) -> PyRefMut<'_, Self> {
+        slf.request.idle_timeout_secs = Some(secs);
+        slf
+    }
+
+    /// Validate and return the request, raising ValueError if it is invalid
+    fn build(&self) -> PyResult<StartRentalApiRequest> {
+        SdkStartRentalApiRequest::from(self.request.clone())
+            .validate()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(self.request.clone())
+    }
+}
+
 /// Query parameters for listing available executors
 #[cfg_attr(feature = "stub-gen", gen_stub_pyclass)]
 #[pyclass]
@@ -563,24 +783,28 @@ pub struct ListAvailableExecutorsQuery {
     pub gpu_type: Option<String>,
     #[pyo3(get, set)]
     pub min_gpu_count: Option<u32>,
+    #[pyo3(get, set)]
+    pub max_staleness: Option<u64>,
 }
 
 #[cfg_attr(feature = "stub-gen", gen_stub_pymethods)]
 #[pymethods]
 impl ListAvailableExecutorsQuery {
     #[new]
-    #[pyo3(signature = (available=None, min_gpu_memory=None, gpu_type=None, min_gpu_count=None))]
+    #[pyo3(signature = (available=None, min_gpu_memory=None, gpu_type=None, min_gpu_count=None, max_staleness=None))]
     fn new(
         available: Option<bool>,
         min_gpu_memory: Option<u32>,
         gpu_type: Option<String>,
         min_gpu_count: Option<u32>,
+        max_staleness: Option<u64>,
     ) -> Self {
         Self {
             available,
             min_gpu_memory,
             gpu_type,
             min_gpu_count,
+            max_staleness,
         }
     }
 }
@@ -592,18 +816,95 @@ impl From<ListAvailableExecutorsQuery> for SdkListAvailableExecutorsQuery {
             min_gpu_memory: query.min_gpu_memory,
             gpu_type: query.gpu_type,
             min_gpu_count: query.min_gpu_count,
+            mig_profile: None,
+            max_staleness: query.max_staleness,
             location: None, // Python SDK doesn't support location filtering yet
         }
     }
 }
 
+/// Rental state to filter `list_rentals` by
+#[cfg_attr(feature = "stub-gen", gen_stub_pyclass_enum)]
+#[pyclass(name = "RentalState", eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum RentalStateFilter {
+    Provisioning,
+    Active,
+    Unhealthy,
+    Stopping,
+    Stopped,
+    Failed,
+}
+
+impl From<RentalStateFilter> for RentalState {
+    fn from(state: RentalStateFilter) -> Self {
+        match state {
+            RentalStateFilter::Provisioning => RentalState::Provisioning,
+            RentalStateFilter::Active => RentalState::Active,
+            RentalStateFilter::Unhealthy => RentalState::Unhealthy,
+            RentalStateFilter::Stopping => RentalState::Stopping,
+            RentalStateFilter::Stopped => RentalState::Stopped,
+            RentalStateFilter::Failed => RentalState::Failed,
+        }
+    }
+}
+
+impl From<RentalState> for RentalStateFilter {
+    fn from(state: RentalState) -> Self {
+        match state {
+            RentalState::Provisioning => RentalStateFilter::Provisioning,
+            RentalState::Active => RentalStateFilter::Active,
+            RentalState::Unhealthy => RentalStateFilter::Unhealthy,
+            RentalState::Stopping => RentalStateFilter::Stopping,
+            RentalState::Stopped => RentalStateFilter::Stopped,
+            RentalState::Failed => RentalStateFilter::Failed,
+        }
+    }
+}
+
+/// Read a status filter given either as a `RentalState` or, deprecated, as a string
+///
+/// `"all"` is accepted as a string meaning no filter, which is what unknown
+/// strings used to fall back to.
+fn extract_status_filter(status: Option<&Bound<'_, PyAny>>) -> PyResult<Option<RentalStateFilter>> {
+    let Some(status) = status else {
+        return Ok(None);
+    };
+    if status.is_none() {
+        return Ok(None);
+    }
+    if let Ok(state) = status.extract::<RentalStateFilter>() {
+        return Ok(Some(state));
+    }
+
+    let name: String = status.extract().map_err(|_| {
+        pyo3::exceptions::PyTypeError::new_err("status must be a RentalState or None")
+    })?;
+    PyErr::warn(
+        status.py(),
+        status
+            .py()
+            .get_type::<pyo3::exceptions::PyDeprecationWarning>()
+            .as_any(),
+        c"passing the rental status as a string is deprecated, use RentalState instead",
+        2,
+    )?;
+    if name.trim().eq_ignore_ascii_case("all") {
+        return Ok(None);
+    }
+    name.parse::<RentalState>()
+        .map(|state| Some(state.into()))
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
 /// Query parameters for listing rentals
 #[cfg_attr(feature = "stub-gen", gen_stub_pyclass)]
 #[pyclass]
 #[derive(Clone, Default)]
 pub struct ListRentalsQuery {
-    #[pyo3(get, set)]
-    pub status: Option<String>, // We'll use String for the enum
+    /// Only list rentals in this state, or every rental when `None`
+    #[pyo3(get)]
+    pub status: Option<RentalStateFilter>,
     #[pyo3(get, set)]
     pub gpu_type: Option<String>,
     #[pyo3(get, set)]
@@ -615,28 +916,29 @@ pub struct ListRentalsQuery {
 impl ListRentalsQuery {
     #[new]
     #[pyo3(signature = (status=None, gpu_type=None, min_gpu_count=None))]
-    fn new(status: Option<String>, gpu_type: Option<String>, min_gpu_count: Option<u32>) -> Self {
-        Self {
-            status,
+    fn new(
+        status: Option<&Bound<'_, PyAny>>,
+        gpu_type: Option<String>,
+        min_gpu_count: Option<u32>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            status: extract_status_filter(status)?,
             gpu_type,
             min_gpu_count,
-        }
+        })
+    }
+
+    #[setter]
+    fn set_status(&mut self, status: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+        self.status = extract_status_filter(status)?;
+        Ok(())
     }
 }
 
 impl From<ListRentalsQuery> for SdkListRentalsQuery {
     fn from(query: ListRentalsQuery) -> Self {
-        let status = query.status.and_then(|s| match s.to_lowercase().as_str() {
-            "provisioning" => Some(RentalState::Provisioning),
-            "active" => Some(RentalState::Active),
-            "stopping" => Some(RentalState::Stopping),
-            "stopped" => Some(RentalState::Stopped),
-            "failed" => Some(RentalState::Failed),
-            _ => None,
-        });
-
         Self {
-            status,
+            status: query.status.map(Into::into),
             gpu_type: query.gpu_type,
             min_gpu_count: query.min_gpu_count,
         }
@@ -0,0 +1,51 @@
+"""Local validation of GPU requirements before they are sent to the API."""
+
+import pytest
+
+from basilica import GpuRequirements, executor_by_gpu
+
+
+def test_valid_requirements():
+    req = GpuRequirements(gpu_count=2, min_memory_gb=0, gpu_type="h100")
+    assert req.gpu_count == 2
+    assert req.min_memory_gb == 0
+    assert req.gpu_type == "h100"
+    executor_by_gpu(req)
+
+
+def test_any_gpu_type_when_none():
+    req = GpuRequirements(gpu_count=1, min_memory_gb=40)
+    assert req.gpu_type is None
+    executor_by_gpu(req)
+
+
+@pytest.mark.parametrize("gpu_count", [0, -1])
+def test_gpu_count_below_one_is_rejected(gpu_count):
+    with pytest.raises(ValueError, match="gpu_count"):
+        GpuRequirements(gpu_count=gpu_count, min_memory_gb=40)
+
+
+def test_negative_min_memory_is_rejected():
+    with pytest.raises(ValueError, match="min_memory_gb"):
+        GpuRequirements(gpu_count=1, min_memory_gb=-1)
+
+
+@pytest.mark.parametrize("gpu_type", ["", "   "])
+def test_empty_gpu_type_is_rejected(gpu_type):
+    with pytest.raises(ValueError, match="gpu_type"):
+        GpuRequirements(gpu_count=1, min_memory_gb=40, gpu_type=gpu_type)
+
+
+def test_setters_validate():
+    req = GpuRequirements(gpu_count=1, min_memory_gb=40, gpu_type="h100")
+    with pytest.raises(ValueError, match="gpu_count"):
+        req.gpu_count = 0
+    with pytest.raises(ValueError, match="min_memory_gb"):
+        req.min_memory_gb = -8
+    with pytest.raises(ValueError, match="gpu_type"):
+        req.gpu_type = ""
+    # Rejected assignments leave the requirements unchanged
+    assert (req.gpu_count, req.min_memory_gb, req.gpu_type) == (1, 40, "h100")
+
+    req.gpu_type = None
+    assert req.gpu_type is None
@@ -14,4 +14,4 @@ pub mod types;
 pub use refresh::refresh_access_token;
 pub use simple_manager::TokenManager;
 pub use token_store::TokenStore;
-pub use types::{AuthConfig, AuthError, AuthMethod, AuthResult, TokenSet};
+pub use types::{AuthConfig, AuthError, AuthMethod, AuthResult, TokenClaims, TokenSet};
@@ -58,6 +58,15 @@ impl TokenManager {
         }
     }
 
+    /// Create a token manager without credentials, so requests fail with
+    /// [`AuthError::AuthenticationRequired`] instead of using discovered tokens
+    pub fn new_unauthenticated() -> Self {
+        Self {
+            auth_method: Arc::new(Mutex::new(AuthMethod::None)),
+            api_key: None,
+        }
+    }
+
     /// Get valid access token (handles refresh automatically)
     pub async fn get_access_token(&self) -> AuthResult<String> {
         debug!("Getting access token from TokenManager");
@@ -104,6 +113,7 @@ impl TokenManager {
                     Ok(stored_tokens.access_token)
                 }
             }
+            AuthMethod::None => Err(AuthError::AuthenticationRequired),
         }
     }
 
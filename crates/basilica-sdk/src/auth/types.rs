@@ -54,21 +54,12 @@ impl TokenSet {
     /// Extract expiration from JWT token
     /// Returns the exp claim from the JWT if it can be decoded
     fn decode_jwt_exp(token: &str) -> Option<u64> {
-        // JWT has three parts: header.payload.signature
-        let parts: Vec<&str> = token.split('.').collect();
-        if parts.len() != 3 {
-            return None;
-        }
-
-        // Decode the payload (second part)
-        let payload = parts[1];
-
-        // Decode base64url without padding (JWT uses base64url encoding)
-        let decoded = URL_SAFE_NO_PAD.decode(payload).ok()?;
+        decode_jwt_payload(token).ok()?.get("exp")?.as_u64()
+    }
 
-        // Parse JSON and extract exp claim
-        let json: serde_json::Value = serde_json::from_slice(&decoded).ok()?;
-        json.get("exp")?.as_u64()
+    /// Decode the identity claims carried by the access token
+    pub fn claims(&self) -> AuthResult<TokenClaims> {
+        TokenClaims::from_jwt(&self.access_token)
     }
 
     /// Get the expiration time by decoding JWT
@@ -130,6 +121,107 @@ impl TokenSet {
     }
 }
 
+/// Decode the payload section of a JWT without verifying its signature
+fn decode_jwt_payload(token: &str) -> AuthResult<serde_json::Value> {
+    // JWT has three parts: header.payload.signature
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(AuthError::InvalidToken(
+            "Token is not a JWT (expected 3 segments)".to_string(),
+        ));
+    }
+
+    // Decode base64url without padding (JWT uses base64url encoding)
+    let decoded = URL_SAFE_NO_PAD
+        .decode(parts[1])
+        .map_err(|e| AuthError::InvalidToken(format!("Invalid JWT payload encoding: {}", e)))?;
+
+    serde_json::from_slice(&decoded)
+        .map_err(|e| AuthError::InvalidToken(format!("Invalid JWT payload: {}", e)))
+}
+
+/// Identity information decoded from a JWT access token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenClaims {
+    /// Subject (user identifier)
+    pub subject: String,
+    /// Email address, if present in the token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Scopes granted to the token
+    pub scopes: Vec<String>,
+    /// Expiration time as a Unix timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl TokenClaims {
+    /// Decode claims from a JWT access token
+    ///
+    /// The signature is not verified; this is meant for displaying the identity
+    /// of a locally stored token, not for authorization decisions.
+    pub fn from_jwt(token: &str) -> AuthResult<Self> {
+        let payload = decode_jwt_payload(token)?;
+
+        let subject = payload
+            .get("sub")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AuthError::InvalidToken("Token is missing 'sub' claim".to_string()))?
+            .to_string();
+
+        // Auth0 may put the email under a namespaced custom claim
+        let email = payload
+            .get("email")
+            .and_then(|v| v.as_str())
+            .or_else(|| {
+                payload.as_object().and_then(|claims| {
+                    claims
+                        .iter()
+                        .find(|(key, _)| key.ends_with("/email"))
+                        .and_then(|(_, v)| v.as_str())
+                })
+            })
+            .map(str::to_string);
+
+        // Scopes come either as a space-delimited `scope` string or a `permissions` array
+        let mut scopes: Vec<String> = payload
+            .get("scope")
+            .and_then(|v| v.as_str())
+            .map(|s| s.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default();
+        if let Some(permissions) = payload.get("permissions").and_then(|v| v.as_array()) {
+            for permission in permissions.iter().filter_map(|p| p.as_str()) {
+                if !scopes.iter().any(|s| s == permission) {
+                    scopes.push(permission.to_string());
+                }
+            }
+        }
+
+        let expires_at = payload.get("exp").and_then(|v| v.as_u64());
+
+        Ok(Self {
+            subject,
+            email,
+            scopes,
+            expires_at,
+        })
+    }
+
+    /// Check if the token these claims were decoded from has expired
+    pub fn is_expired(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                now >= expires_at
+            }
+            None => false,
+        }
+    }
+}
+
 /// Authentication method for the SDK
 #[derive(Debug, Clone)]
 pub enum AuthMethod {
@@ -139,6 +231,8 @@ pub enum AuthMethod {
     FileBased {
         store: crate::auth::token_store::TokenStore,
     },
+    /// No credentials; every authenticated call fails
+    None,
 }
 
 /// Authentication errors
@@ -219,3 +313,65 @@ pub fn get_sdk_data_dir() -> AuthResult<PathBuf> {
     // Use the same path as the CLI for consistency
     Ok(strategy.data_dir().join("basilica"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_jwt(payload: serde_json::Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"RS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(payload.to_string());
+        format!("{}.{}.signature", header, payload)
+    }
+
+    #[test]
+    fn test_claims_from_sample_token() {
+        let token = encode_jwt(serde_json::json!({
+            "sub": "auth0|user-123",
+            "email": "user@example.com",
+            "scope": "openid profile rentals:view",
+            "permissions": ["rentals:view", "rentals:create"],
+            "exp": 4102444800u64,
+        }));
+
+        let claims = TokenClaims::from_jwt(&token).unwrap();
+        assert_eq!(claims.subject, "auth0|user-123");
+        assert_eq!(claims.email.as_deref(), Some("user@example.com"));
+        assert_eq!(
+            claims.scopes,
+            vec!["openid", "profile", "rentals:view", "rentals:create"]
+        );
+        assert_eq!(claims.expires_at, Some(4102444800));
+        assert!(!claims.is_expired());
+    }
+
+    #[test]
+    fn test_claims_namespaced_email_and_expired() {
+        let token = encode_jwt(serde_json::json!({
+            "sub": "auth0|user-456",
+            "https://basilica.ai/email": "other@example.com",
+            "exp": 1000,
+        }));
+
+        let tokens = TokenSet::new(token, "refresh".to_string());
+        let claims = tokens.claims().unwrap();
+        assert_eq!(claims.email.as_deref(), Some("other@example.com"));
+        assert!(claims.scopes.is_empty());
+        assert!(claims.is_expired());
+        assert!(tokens.is_expired());
+    }
+
+    #[test]
+    fn test_claims_rejects_non_jwt() {
+        assert!(matches!(
+            TokenClaims::from_jwt("basilica_api_key"),
+            Err(AuthError::InvalidToken(_))
+        ));
+
+        let token = encode_jwt(serde_json::json!({ "email": "user@example.com" }));
+        assert!(matches!(
+            TokenClaims::from_jwt(&token),
+            Err(AuthError::InvalidToken(_))
+        ));
+    }
+}
@@ -36,12 +36,12 @@
 //! ```
 
 use crate::{
-    auth::TokenManager,
+    auth::{AuthError, TokenClaims, TokenManager},
     error::{ApiError, ErrorResponse, Result},
     types::{
-        ApiKeyInfo, ApiKeyResponse, ApiListRentalsResponse, CreateApiKeyRequest,
-        HealthCheckResponse, ListAvailableExecutorsQuery, ListRentalsQuery,
-        RentalStatusWithSshResponse,
+        ApiKeyInfo, ApiKeyResponse, ApiListRentalsResponse, AvailableExecutor, CreateApiKeyRequest,
+        HealthCheckResponse, ListAvailableExecutorsQuery, ListRentalsQuery, RentalLogLine,
+        RentalLogs, RentalState, RentalStatusWithSshResponse, TerminateRentalsReport,
     },
     StartRentalApiRequest,
 };
@@ -49,40 +49,99 @@ use crate::{
 /// Default API URL when not specified
 pub const DEFAULT_API_URL: &str = "https://api.basilica.ai";
 
+/// Container image used when a rental request does not name one
+pub use basilica_common::DEFAULT_CONTAINER_IMAGE;
+
 /// Default timeout in seconds for API requests
 pub const DEFAULT_TIMEOUT_SECS: u64 = 1200;
+
+/// Default timeout in seconds for establishing a connection
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Most log streams [`BasilicaClient::stream_logs_multi`] keeps open at once
+pub const MAX_CONCURRENT_LOG_STREAMS: usize = 16;
+
+/// Default time in seconds to wait for a streaming response to start or for
+/// its next chunk
+pub const DEFAULT_READ_TIMEOUT_SECS: u64 = 60;
+
+/// Default cap on log bytes buffered by [`BasilicaClient::get_logs`]
+pub const DEFAULT_MAX_LOG_BYTES: usize = 16 * 1024 * 1024;
+
+/// Stop requests [`BasilicaClient::terminate_rentals`] keeps in flight at once
+pub const MAX_CONCURRENT_TERMINATIONS: usize = 8;
+
+/// Default number of times a rate-limited request is sent again after
+/// waiting for the server's `Retry-After`
+pub const DEFAULT_RATE_LIMIT_RETRIES: u32 = 2;
+
+/// Longest `Retry-After` in seconds the client waits out; longer waits are
+/// returned to the caller as [`ApiError::RateLimitExceeded`]
+pub const MAX_RETRY_AFTER_SECS: u64 = 60;
+
+/// Media type of newline-delimited JSON listings, one object per line
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
 use basilica_common::ApiKeyName;
 use basilica_validator::api::types::ListAvailableExecutorsResponse;
 use basilica_validator::rental::RentalResponse;
+use bytes::Bytes;
+use eventsource_stream::Eventsource;
+use futures_util::stream::BoxStream;
+use futures_util::{Stream, StreamExt};
 use reqwest::{RequestBuilder, Response, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest};
 
 /// HTTP client for interacting with the Basilica API
-#[derive(Debug)]
+///
+/// Cloning is cheap and clones share the connection pool and tokens.
+#[derive(Debug, Clone)]
 pub struct BasilicaClient {
     http_client: reqwest::Client,
     base_url: String,
     token_manager: Arc<TokenManager>,
+    request_timeout: Duration,
+    read_timeout: Duration,
+    max_log_bytes: usize,
+    rate_limit_retries: u32,
+}
+
+/// Settings collected by [`ClientBuilder`]
+struct ClientOptions {
+    request_timeout: Duration,
+    connect_timeout: Duration,
+    read_timeout: Duration,
+    pool_max_idle_per_host: Option<usize>,
+    max_log_bytes: usize,
+    rate_limit_retries: u32,
 }
 
 impl BasilicaClient {
     /// Create a new client (private - use ClientBuilder instead)
+    ///
+    /// The request timeout is applied per request rather than on the HTTP
+    /// client so that streaming calls can outlive it.
     fn new(
         base_url: impl Into<String>,
-        timeout: Duration,
         token_manager: Arc<TokenManager>,
+        options: ClientOptions,
     ) -> Result<Self> {
-        let http_client = reqwest::Client::builder()
-            .timeout(timeout)
-            .build()
-            .map_err(ApiError::HttpClient)?;
+        let mut builder = reqwest::Client::builder().connect_timeout(options.connect_timeout);
+        if let Some(max) = options.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        let http_client = builder.build().map_err(ApiError::HttpClient)?;
 
         Ok(Self {
             http_client,
             base_url: base_url.into(),
             token_manager,
+            request_timeout: options.request_timeout,
+            read_timeout: options.read_timeout,
+            max_log_bytes: options.max_log_bytes,
+            rate_limit_retries: options.rate_limit_retries,
         })
     }
 
@@ -101,8 +160,57 @@ impl BasilicaClient {
 
     /// Stop a rental
     pub async fn stop_rental(&self, rental_id: &str) -> Result<()> {
-        let path = format!("/rentals/{rental_id}");
-        let response: Response = self.delete_empty(&path).await?;
+        self.terminate_rental(rental_id, None).await
+    }
+
+    /// Terminate every rental matching `filter`
+    ///
+    /// Matching rentals are stopped concurrently, at most
+    /// [`MAX_CONCURRENT_TERMINATIONS`] at a time. Only a failure to list
+    /// rentals is returned as an error; failures to stop individual rentals
+    /// are collected in the report. Without a status filter, rentals that are
+    /// already stopped or stopping are skipped.
+    pub async fn terminate_rentals(
+        &self,
+        filter: ListRentalsQuery,
+        reason: Option<&str>,
+    ) -> Result<TerminateRentalsReport> {
+        let status = filter.status.clone();
+        let rentals = self.list_rentals(Some(filter)).await?.rentals;
+
+        let targets = rentals.into_iter().filter(|rental| match &status {
+            Some(status) => rental.state == *status,
+            None => !matches!(rental.state, RentalState::Stopped | RentalState::Stopping),
+        });
+
+        let outcomes: Vec<(String, Result<()>)> = futures_util::stream::iter(targets)
+            .map(|rental| async move {
+                let result = self.terminate_rental(&rental.rental_id, reason).await;
+                (rental.rental_id, result)
+            })
+            .buffered(MAX_CONCURRENT_TERMINATIONS)
+            .collect()
+            .await;
+
+        let mut report = TerminateRentalsReport::default();
+        for (rental_id, result) in outcomes {
+            match result {
+                Ok(()) => report.terminated.push(rental_id),
+                Err(e) => report.failed.push((rental_id, e)),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Stop a rental, recording `reason` with the termination when given
+    async fn terminate_rental(&self, rental_id: &str, reason: Option<&str>) -> Result<()> {
+        let url = format!("{}/rentals/{rental_id}", self.base_url);
+        let mut request = self.http_client.delete(&url).timeout(self.request_timeout);
+        if let Some(reason) = reason {
+            request = request.query(&[("reason", reason)]);
+        }
+        let request = self.apply_auth(request).await?;
+        let response = self.send(request).await?;
         if response.status().is_success() {
             Ok(())
         } else {
@@ -118,6 +226,9 @@ impl BasilicaClient {
     }
 
     /// Get rental logs
+    ///
+    /// The response is a stream, so only the connect and read timeouts apply:
+    /// a followed log stream may stay open longer than the request timeout.
     pub async fn get_rental_logs(
         &self,
         rental_id: &str,
@@ -140,7 +251,242 @@ impl BasilicaClient {
         }
 
         let request = self.apply_auth(request).await?;
-        request.send().await.map_err(ApiError::HttpClient)
+        tokio::time::timeout(self.read_timeout, request.send())
+            .await
+            .map_err(|_| ApiError::Timeout)?
+            .map_err(ApiError::HttpClient)
+    }
+
+    /// Stream rental logs chunk by chunk without buffering the body
+    pub async fn get_logs_streaming(
+        &self,
+        rental_id: &str,
+        tail: Option<u32>,
+    ) -> Result<impl Stream<Item = Result<Bytes>>> {
+        let response = self.get_rental_logs(rental_id, false, tail).await?;
+        if !response.status().is_success() {
+            let err = self
+                .handle_error_response::<()>(response)
+                .await
+                .err()
+                .unwrap_or(ApiError::Internal {
+                    message: "Unknown error".into(),
+                });
+            return Err(err);
+        }
+        // Fail with a timeout if the body stalls for longer than the read
+        // timeout, then end the stream
+        let read_timeout = self.read_timeout;
+        let chunks = Box::pin(response.bytes_stream());
+        Ok(futures_util::stream::unfold(
+            Some(chunks),
+            move |chunks| async move {
+                let mut chunks = chunks?;
+                match tokio::time::timeout(read_timeout, chunks.next()).await {
+                    Ok(Some(chunk)) => Some((chunk.map_err(ApiError::HttpClient), Some(chunks))),
+                    Ok(None) => None,
+                    Err(_) => Some((Err(ApiError::Timeout), None)),
+                }
+            },
+        ))
+    }
+
+    /// Follow a rental's logs over server-sent events, yielding one entry per line.
+    ///
+    /// The stream stays open while the container runs, so no read timeout applies.
+    pub async fn follow_rental_logs(
+        &self,
+        rental_id: &str,
+        tail: Option<u32>,
+    ) -> Result<impl Stream<Item = Result<RentalLogLine>>> {
+        let response = self.get_rental_logs(rental_id, true, tail).await?;
+        if !response.status().is_success() {
+            let err = self
+                .handle_error_response::<()>(response)
+                .await
+                .err()
+                .unwrap_or(ApiError::Internal {
+                    message: "Unknown error".into(),
+                });
+            return Err(err);
+        }
+
+        Ok(response
+            .bytes_stream()
+            .eventsource()
+            .filter_map(|event| async move {
+                match event {
+                    Ok(event) => match serde_json::from_str::<RentalLogLine>(&event.data) {
+                        Ok(line) => Some(Ok(line)),
+                        Err(e) => {
+                            tracing::debug!(
+                                "Skipping unparseable log event {:?}: {}",
+                                event.data,
+                                e
+                            );
+                            None
+                        }
+                    },
+                    Err(e) => Some(Err(ApiError::Internal {
+                        message: format!("Log stream error: {e}"),
+                    })),
+                }
+            }))
+    }
+
+    /// Follow a rental's logs over a WebSocket, yielding one entry per line.
+    ///
+    /// Carries the same entries as [`Self::follow_rental_logs`] for networks
+    /// whose proxies cut long-lived server-sent event responses.
+    pub async fn follow_rental_logs_ws(
+        &self,
+        rental_id: &str,
+        tail: Option<u32>,
+    ) -> Result<impl Stream<Item = Result<RentalLogLine>>> {
+        let mut url = format!(
+            "{}/rentals/{}/logs/ws?follow=true",
+            websocket_base_url(&self.base_url),
+            rental_id
+        );
+        if let Some(tail_lines) = tail {
+            url.push_str(&format!("&tail={tail_lines}"));
+        }
+
+        let mut request = url
+            .into_client_request()
+            .map_err(|e| ApiError::InvalidRequest {
+                message: format!("Invalid log stream URL: {e}"),
+            })?;
+        let token = self
+            .token_manager
+            .get_access_token()
+            .await
+            .map_err(access_token_error)?;
+        let authorization =
+            format!("Bearer {token}")
+                .parse()
+                .map_err(|_| ApiError::InvalidRequest {
+                    message: "Access token is not a valid header value".into(),
+                })?;
+        request
+            .headers_mut()
+            .insert(tungstenite::http::header::AUTHORIZATION, authorization);
+
+        let (socket, _) =
+            tokio::time::timeout(self.read_timeout, tokio_tungstenite::connect_async(request))
+                .await
+                .map_err(|_| ApiError::Timeout)?
+                .map_err(|e| ApiError::Internal {
+                    message: format!("Failed to open log WebSocket: {e}"),
+                })?;
+
+        // Pings are answered by the socket itself; a close ends the stream
+        Ok(futures_util::stream::unfold(
+            Some(socket),
+            |socket| async move {
+                let mut socket = socket?;
+                loop {
+                    match socket.next().await? {
+                        Ok(tungstenite::Message::Text(text)) => {
+                            match serde_json::from_str::<RentalLogLine>(&text) {
+                                Ok(line) => return Some((Ok(line), Some(socket))),
+                                Err(e) => {
+                                    tracing::debug!(
+                                        "Skipping unparseable log frame {:?}: {}",
+                                        text,
+                                        e
+                                    )
+                                }
+                            }
+                        }
+                        Ok(tungstenite::Message::Close(_)) => return None,
+                        Ok(_) => {}
+                        Err(e) => {
+                            let err = ApiError::Internal {
+                                message: format!("Log stream error: {e}"),
+                            };
+                            return Some((Err(err), None));
+                        }
+                    }
+                }
+            },
+        ))
+    }
+
+    /// Follow a rental's logs over a WebSocket, falling back to server-sent
+    /// events when the WebSocket cannot be opened
+    pub async fn stream_rental_logs(
+        &self,
+        rental_id: &str,
+        tail: Option<u32>,
+    ) -> Result<BoxStream<'static, Result<RentalLogLine>>> {
+        match self.follow_rental_logs_ws(rental_id, tail).await {
+            Ok(lines) => Ok(lines.boxed()),
+            Err(e) => {
+                tracing::debug!(
+                    "Log WebSocket for {} unavailable, using server-sent events: {}",
+                    rental_id,
+                    e
+                );
+                Ok(self.follow_rental_logs(rental_id, tail).await?.boxed())
+            }
+        }
+    }
+
+    /// Follow the logs of several rentals at once, interleaving their lines as they
+    /// arrive. Each item is tagged with the rental it came from.
+    ///
+    /// At most [`MAX_CONCURRENT_LOG_STREAMS`] rentals are followed at a time; the rest
+    /// start as earlier streams end. A rental whose stream cannot be opened yields a
+    /// single error item and the others continue.
+    pub fn stream_logs_multi(
+        &self,
+        rental_ids: Vec<String>,
+    ) -> impl Stream<Item = (String, Result<String>)> + Send + 'static {
+        let client = self.clone();
+        futures_util::stream::iter(rental_ids)
+            .map(move |rental_id| {
+                let client = client.clone();
+                futures_util::stream::once(async move {
+                    let lines = client.follow_rental_logs(&rental_id, None).await;
+                    (rental_id, lines)
+                })
+                .flat_map(|(rental_id, lines)| match lines {
+                    Ok(lines) => lines
+                        .map(move |line| (rental_id.clone(), line.map(|l| l.message)))
+                        .left_stream(),
+                    Err(e) => futures_util::stream::once(async move { (rental_id, Err(e)) })
+                        .right_stream(),
+                })
+                .boxed()
+            })
+            .flatten_unordered(MAX_CONCURRENT_LOG_STREAMS)
+    }
+
+    /// Fetch rental logs into memory, keeping at most the configured log byte
+    /// limit. Longer logs are cut short and marked as truncated; use
+    /// [`Self::get_logs_streaming`] to read them in full.
+    pub async fn get_logs(&self, rental_id: &str, tail: Option<u32>) -> Result<RentalLogs> {
+        let stream = self.get_logs_streaming(rental_id, tail).await?;
+        futures_util::pin_mut!(stream);
+
+        let mut content = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            let remaining = self.max_log_bytes - content.len();
+            if chunk.len() > remaining {
+                content.extend_from_slice(&chunk[..remaining]);
+                truncated = true;
+                break;
+            }
+            content.extend_from_slice(&chunk);
+        }
+
+        Ok(RentalLogs {
+            content: String::from_utf8_lossy(&content).into_owned(),
+            truncated,
+        })
     }
 
     /// List rentals
@@ -149,14 +495,14 @@ impl BasilicaClient {
         query: Option<ListRentalsQuery>,
     ) -> Result<ApiListRentalsResponse> {
         let url = format!("{}/rentals", self.base_url);
-        let mut request = self.http_client.get(&url);
+        let mut request = self.http_client.get(&url).timeout(self.request_timeout);
 
         if let Some(q) = &query {
             request = request.query(&q);
         }
 
         let request = self.apply_auth(request).await?;
-        let response = request.send().await.map_err(ApiError::HttpClient)?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
@@ -166,17 +512,57 @@ impl BasilicaClient {
         query: Option<ListAvailableExecutorsQuery>,
     ) -> Result<ListAvailableExecutorsResponse> {
         let url = format!("{}/executors", self.base_url);
-        let mut request = self.http_client.get(&url);
+        let mut request = self.http_client.get(&url).timeout(self.request_timeout);
 
         if let Some(q) = &query {
             request = request.query(&q);
         }
 
         let request = self.apply_auth(request).await?;
-        let response = request.send().await.map_err(ApiError::HttpClient)?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
+    /// Stream available executors, parsing each one as it arrives
+    ///
+    /// The listing is requested as newline-delimited JSON, so large
+    /// inventories are never buffered whole. Only the connect and read
+    /// timeouts apply.
+    pub async fn stream_executors(
+        &self,
+        query: Option<ListAvailableExecutorsQuery>,
+    ) -> Result<impl Stream<Item = Result<AvailableExecutor>>> {
+        let url = format!("{}/executors", self.base_url);
+        let mut request = self
+            .http_client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, NDJSON_CONTENT_TYPE);
+
+        if let Some(q) = &query {
+            request = request.query(&q);
+        }
+
+        let request = self.apply_auth(request).await?;
+        let response = tokio::time::timeout(self.read_timeout, self.send(request))
+            .await
+            .map_err(|_| ApiError::Timeout)??;
+        if !response.status().is_success() {
+            let err = self
+                .handle_error_response::<()>(response)
+                .await
+                .err()
+                .unwrap_or(ApiError::Internal {
+                    message: "Unknown error".into(),
+                });
+            return Err(err);
+        }
+
+        let chunks = response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(ApiError::HttpClient));
+        Ok(ndjson_items(chunks, self.read_timeout))
+    }
+
     // ===== Health & Discovery =====
 
     /// Health check
@@ -184,6 +570,25 @@ impl BasilicaClient {
         self.get("/health").await
     }
 
+    // ===== Identity =====
+
+    /// Describe the identity carried by the current access token
+    ///
+    /// The token is obtained through the token manager, so an expired token is
+    /// refreshed before its claims are decoded. API keys are opaque and cannot
+    /// be introspected.
+    pub async fn whoami(&self) -> Result<TokenClaims> {
+        let token = self
+            .token_manager
+            .get_access_token()
+            .await
+            .map_err(access_token_error)?;
+
+        TokenClaims::from_jwt(&token).map_err(|e| ApiError::InvalidRequest {
+            message: format!("Current credentials cannot be introspected: {}", e),
+        })
+    }
+
     // ===== API Key Management =====
 
     /// Create a new API key (requires JWT authentication)
@@ -231,46 +636,70 @@ impl BasilicaClient {
     /// Apply authentication to request
     /// Uses TokenManager for automatic token refresh
     async fn apply_auth(&self, request: RequestBuilder) -> Result<RequestBuilder> {
-        let token =
-            self.token_manager
-                .get_access_token()
-                .await
-                .map_err(|e| ApiError::Internal {
-                    message: format!("Failed to get access token: {}", e),
-                })?;
+        let token = self
+            .token_manager
+            .get_access_token()
+            .await
+            .map_err(access_token_error)?;
         Ok(request.header("Authorization", format!("Bearer {}", token)))
     }
 
     /// Generic GET request
     async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
         let url = format!("{}{}", self.base_url, path);
-        let request = self.http_client.get(&url);
+        let request = self.http_client.get(&url).timeout(self.request_timeout);
         let request = self.apply_auth(request).await?;
 
-        let response = request.send().await.map_err(ApiError::HttpClient)?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
     /// Generic POST request
     async fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<T> {
         let url = format!("{}{}", self.base_url, path);
-        let request = self.http_client.post(&url).json(body);
+        let request = self
+            .http_client
+            .post(&url)
+            .timeout(self.request_timeout)
+            .json(body);
         let request = self.apply_auth(request).await?;
 
-        let response = request.send().await.map_err(ApiError::HttpClient)?;
+        let response = self.send(request).await?;
         self.handle_response(response).await
     }
 
     /// Generic DELETE request without body
     async fn delete_empty(&self, path: &str) -> Result<Response> {
         let url = format!("{}{}", self.base_url, path);
-        let request = self.http_client.delete(&url);
+        let request = self.http_client.delete(&url).timeout(self.request_timeout);
         let request = self.apply_auth(request).await?;
 
-        let response = request.send().await.map_err(ApiError::HttpClient)?;
+        let response = self.send(request).await?;
         Ok(response)
     }
 
+    /// Send a request, waiting out `429 Too Many Requests` responses for as
+    /// long as the server's `Retry-After` asks, up to the configured retries
+    async fn send(&self, mut request: RequestBuilder) -> Result<Response> {
+        let mut retries = 0;
+        loop {
+            let next = request.try_clone();
+            let response = request.send().await.map_err(ApiError::HttpClient)?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS
+                || retries >= self.rate_limit_retries
+            {
+                return Ok(response);
+            }
+            let (Some(next), Some(delay)) = (next, retry_after(&response)) else {
+                return Ok(response);
+            };
+            tracing::debug!("Rate limited, retrying in {}s", delay.as_secs());
+            tokio::time::sleep(delay).await;
+            request = next;
+            retries += 1;
+        }
+    }
+
     /// Handle successful response
     async fn handle_response<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
         if response.status().is_success() {
@@ -287,6 +716,15 @@ impl BasilicaClient {
 
         // Try to parse error response
         if let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&error_text) {
+            // Codes from behind the API (e.g. the validator) are kept as sent
+            if !error_response.error.code.starts_with("BASILICA_API_") {
+                return Err(ApiError::Upstream {
+                    status: status.as_u16(),
+                    code: error_response.error.code,
+                    message: error_response.error.message,
+                    retryable: error_response.error.retryable,
+                });
+            }
             match status {
                 StatusCode::UNAUTHORIZED => {
                     // Distinguish between missing auth and expired/invalid auth based on error code
@@ -312,6 +750,14 @@ impl BasilicaClient {
                 StatusCode::CONFLICT => Err(ApiError::Conflict {
                     message: error_response.error.message,
                 }),
+                StatusCode::GATEWAY_TIMEOUT
+                    if error_response.error.code == "BASILICA_API_DEPLOYMENT_TIMEOUT" =>
+                {
+                    Err(ApiError::DeploymentTimeout {
+                        phase: error_response.error.phase.unwrap_or_default(),
+                        message: error_response.error.message,
+                    })
+                }
                 _ => Err(ApiError::Internal {
                     message: error_response.error.message,
                 }),
@@ -343,7 +789,120 @@ impl BasilicaClient {
     }
 }
 
+/// Parse newline-delimited JSON from `chunks`, yielding each object as soon
+/// as its line is complete
+///
+/// A line that fails to parse or a body that stalls for longer than
+/// `read_timeout` yields an error and ends the stream.
+fn ndjson_items<T, S>(chunks: S, read_timeout: Duration) -> impl Stream<Item = Result<T>>
+where
+    T: DeserializeOwned,
+    S: Stream<Item = Result<Bytes>>,
+{
+    struct Lines<S> {
+        chunks: std::pin::Pin<Box<S>>,
+        buffer: Vec<u8>,
+        done: bool,
+    }
+
+    let lines = Lines {
+        chunks: Box::pin(chunks),
+        buffer: Vec::new(),
+        done: false,
+    };
+    futures_util::stream::unfold(Some(lines), move |lines| async move {
+        let mut lines = lines?;
+        loop {
+            let line = match lines.buffer.iter().position(|&b| b == b'\n') {
+                Some(end) => {
+                    let mut line: Vec<u8> = lines.buffer.drain(..=end).collect();
+                    line.pop();
+                    line
+                }
+                // The last line need not end with a newline
+                None if lines.done => std::mem::take(&mut lines.buffer),
+                None => {
+                    match tokio::time::timeout(read_timeout, lines.chunks.next()).await {
+                        Ok(Some(Ok(chunk))) => lines.buffer.extend_from_slice(&chunk),
+                        Ok(Some(Err(e))) => return Some((Err(e), None)),
+                        Ok(None) => lines.done = true,
+                        Err(_) => return Some((Err(ApiError::Timeout), None)),
+                    }
+                    continue;
+                }
+            };
+
+            if line.iter().all(u8::is_ascii_whitespace) {
+                if lines.done && lines.buffer.is_empty() {
+                    return None;
+                }
+                continue;
+            }
+            return match serde_json::from_slice(&line) {
+                Ok(item) => Some((Ok(item), Some(lines))),
+                Err(e) => Some((
+                    Err(ApiError::Internal {
+                        message: format!("Invalid line in executor stream: {e}"),
+                    }),
+                    None,
+                )),
+            };
+        }
+    })
+}
+
+/// WebSocket equivalent of an `http://` or `https://` base URL
+fn websocket_base_url(base_url: &str) -> String {
+    if let Some(rest) = base_url.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = base_url.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else {
+        base_url.to_string()
+    }
+}
+
+/// Delay asked for by a `Retry-After` header in seconds, if it is one the
+/// client is willing to wait
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|secs| *secs <= MAX_RETRY_AFTER_SECS)
+        .map(Duration::from_secs)
+}
+
+/// Report missing credentials as such; other token failures are internal
+fn access_token_error(error: AuthError) -> ApiError {
+    match error {
+        AuthError::AuthenticationRequired | AuthError::UserNotLoggedIn => {
+            ApiError::MissingAuthentication {
+                message: error.to_string(),
+            }
+        }
+        _ => ApiError::Internal {
+            message: format!("Failed to get access token: {}", error),
+        },
+    }
+}
+
 /// Builder for constructing a BasilicaClient with custom configuration
+///
+/// Credentials are chosen in this order:
+/// 1. An API key set with [`with_api_key`](Self::with_api_key) or [`from_env`](Self::from_env)
+/// 2. Tokens set with [`with_tokens`](Self::with_tokens)
+/// 3. Auto-detection: `BASILICA_API_TOKEN`, then the tokens saved by
+///    `basilica login` ([`with_file_auth`](Self::with_file_auth) or
+///    [`build_auto`](Self::build_auto))
+///
+/// [`without_auto_auth`](Self::without_auto_auth) skips step 3; a client
+/// built with no credentials then fails every call with
+/// [`ApiError::MissingAuthentication`].
 #[derive(Default)]
 pub struct ClientBuilder {
     base_url: Option<String>,
@@ -351,9 +910,13 @@ pub struct ClientBuilder {
     refresh_token: Option<String>,
     timeout: Option<Duration>,
     connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
     pool_max_idle_per_host: Option<usize>,
     use_file_auth: bool,
     api_key: Option<String>,
+    max_log_bytes: Option<usize>,
+    rate_limit_retries: Option<u32>,
+    no_auto_auth: bool,
 }
 
 impl ClientBuilder {
@@ -388,7 +951,7 @@ impl ClientBuilder {
         self
     }
 
-    /// Set the request timeout
+    /// Set the overall timeout for non-streaming requests
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
         self
@@ -400,21 +963,94 @@ impl ClientBuilder {
         self
     }
 
+    /// Set how long streaming requests wait for the response to start or
+    /// for the next chunk of a log body
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
     /// Set the maximum idle connections per host
     pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
         self.pool_max_idle_per_host = Some(max);
         self
     }
 
+    /// Set the maximum number of log bytes buffered by `get_logs`
+    pub fn max_log_bytes(mut self, max: usize) -> Self {
+        self.max_log_bytes = Some(max);
+        self
+    }
+
+    /// Set how many times a rate-limited request is retried after waiting
+    /// for the server's `Retry-After`; 0 returns the first 429 to the caller
+    pub fn rate_limit_retries(mut self, retries: u32) -> Self {
+        self.rate_limit_retries = Some(retries);
+        self
+    }
+
     /// Use API key for authentication (from provided string)
     pub fn with_api_key(mut self, api_key: &str) -> Self {
         self.api_key = Some(api_key.to_string());
         self
     }
 
+    /// Never look for credentials the caller did not provide: no
+    /// `BASILICA_API_TOKEN` lookup and no CLI token files
+    pub fn without_auto_auth(mut self) -> Self {
+        self.no_auto_auth = true;
+        self.use_file_auth = false;
+        self
+    }
+
+    /// Configure from `BASILICA_API_URL` and `BASILICA_API_TOKEN` only,
+    /// without touching the filesystem
+    ///
+    /// CLI token discovery is disabled, so if `BASILICA_API_TOKEN` is unset
+    /// calls fail with [`ApiError::MissingAuthentication`].
+    pub fn from_env() -> Self {
+        let mut builder = Self::new().without_auto_auth();
+        if let Ok(url) = std::env::var("BASILICA_API_URL") {
+            builder = builder.base_url(url);
+        }
+        if let Ok(api_key) = std::env::var("BASILICA_API_TOKEN") {
+            builder = builder.with_api_key(&api_key);
+        }
+        builder
+    }
+
+    fn has_explicit_credentials(&self) -> bool {
+        self.api_key.is_some() || (self.access_token.is_some() && self.refresh_token.is_some())
+    }
+
+    fn client_options(&self) -> ClientOptions {
+        ClientOptions {
+            request_timeout: self
+                .timeout
+                .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+            connect_timeout: self
+                .connect_timeout
+                .unwrap_or(Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS)),
+            read_timeout: self
+                .read_timeout
+                .unwrap_or(Duration::from_secs(DEFAULT_READ_TIMEOUT_SECS)),
+            pool_max_idle_per_host: self.pool_max_idle_per_host,
+            max_log_bytes: self.max_log_bytes.unwrap_or(DEFAULT_MAX_LOG_BYTES),
+            rate_limit_retries: self
+                .rate_limit_retries
+                .unwrap_or(DEFAULT_RATE_LIMIT_RETRIES),
+        }
+    }
+
     /// Build the client with automatic authentication detection
-    /// This will automatically find and use CLI tokens if available
+    /// This will automatically find and use CLI tokens if available, unless
+    /// credentials were given explicitly or auto-auth is disabled
     pub async fn build_auto(self) -> Result<BasilicaClient> {
+        if self.no_auto_auth || self.has_explicit_credentials() {
+            return self.build();
+        }
+
+        let options = self.client_options();
         let base_url = self.base_url.unwrap_or_else(|| DEFAULT_API_URL.to_string());
 
         // Always try file-based auth for auto mode
@@ -422,15 +1058,12 @@ impl ClientBuilder {
             message: format!("Failed to create file-based token manager: {}", e),
         })?;
 
-        let timeout = self
-            .timeout
-            .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
-
-        BasilicaClient::new(base_url, timeout, Arc::new(token_manager))
+        BasilicaClient::new(base_url, Arc::new(token_manager), options)
     }
 
     /// Build the client
     pub fn build(self) -> Result<BasilicaClient> {
+        let options = self.client_options();
         let base_url = self.base_url.unwrap_or_else(|| DEFAULT_API_URL.to_string());
 
         // Create token manager based on auth configuration
@@ -446,6 +1079,8 @@ impl ClientBuilder {
             (self.access_token, self.refresh_token)
         {
             TokenManager::new_direct(access_token, refresh_token)
+        } else if self.no_auto_auth {
+            TokenManager::new_unauthenticated()
         } else {
             return Err(ApiError::InvalidRequest {
                 message: "Either use with_tokens() with both access and refresh tokens, with_file_auth(), or with_api_key()"
@@ -453,11 +1088,7 @@ impl ClientBuilder {
             });
         };
 
-        let timeout = self
-            .timeout
-            .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
-
-        BasilicaClient::new(base_url, timeout, Arc::new(token_manager))
+        BasilicaClient::new(base_url, Arc::new(token_manager), options)
     }
 }
 
@@ -465,7 +1096,7 @@ impl ClientBuilder {
 mod tests {
     use super::*;
     use serde_json::json;
-    use wiremock::matchers::{header, method, path};
+    use wiremock::matchers::{header, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
@@ -555,6 +1186,786 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_disabled_auto_auth_without_token_is_missing_authentication() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        // build_auto would otherwise fall back to CLI tokens on disk
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .without_auto_auth()
+            .build_auto()
+            .await
+            .unwrap();
+
+        let err = client.health_check().await.unwrap_err();
+        assert!(
+            matches!(err, ApiError::MissingAuthentication { .. }),
+            "unexpected error: {err:?}"
+        );
+        assert!(matches!(
+            client.whoami().await.unwrap_err(),
+            ApiError::MissingAuthentication { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_explicit_api_key_takes_precedence_over_auto_auth() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .and(header("Authorization", "Bearer basilica_explicit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "healthy",
+                "version": "1.0.0",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "healthy_validators": 1,
+                "total_validators": 1,
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_api_key("basilica_explicit")
+            .build_auto()
+            .await
+            .unwrap();
+
+        client.health_check().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_waits_for_retry_after() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("Retry-After", "1")
+                    .set_body_json(json!({
+                        "error": {
+                            "code": "BASILICA_API_RATE_LIMIT",
+                            "message": "Too many requests. Please try again later.",
+                            "timestamp": "2024-01-01T00:00:00Z",
+                            "retryable": true,
+                        }
+                    })),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "healthy",
+                "version": "1.0.0",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "healthy_validators": 10,
+                "total_validators": 10,
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_api_key("test-token")
+            .build()
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        let health = client.health_check().await.unwrap();
+        assert_eq!(health.status, "healthy");
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_is_returned_to_caller() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "3600"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_api_key("test-token")
+            .build()
+            .unwrap();
+
+        let err = client.health_check().await.unwrap_err();
+        assert!(matches!(err, ApiError::RateLimitExceeded));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_deployment_timeout_carries_phase() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(504).set_body_json(json!({
+                "error": {
+                    "code": "BASILICA_API_DEPLOYMENT_TIMEOUT",
+                    "message": "Deployment timed out after 600s during image pull",
+                    "timestamp": "2024-01-01T00:00:00Z",
+                    "retryable": true,
+                    "phase": "pull",
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+
+        match client.health_check().await.unwrap_err() {
+            ApiError::DeploymentTimeout { phase, message } => {
+                assert_eq!(phase, "pull");
+                assert!(message.contains("image pull"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validator_error_code_reaches_client() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(409).set_body_json(json!({
+                "error": {
+                    "code": "BASILICA_VALIDATOR_EXECUTOR_BUSY",
+                    "message": "Executor exec-1 already has an active rental",
+                    "timestamp": "2024-01-01T00:00:00Z",
+                    "retryable": false,
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+
+        let error = client.health_check().await.unwrap_err();
+        assert_eq!(error.error_code(), "BASILICA_VALIDATOR_EXECUTOR_BUSY");
+        assert!(error.is_client_error());
+        assert!(!error.is_retryable());
+        assert_eq!(
+            error.to_string(),
+            "Executor exec-1 already has an active rental"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_whoami_decodes_token() {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+        let payload = URL_SAFE_NO_PAD.encode(
+            json!({
+                "sub": "auth0|user-123",
+                "email": "user@example.com",
+                "scope": "rentals:view",
+                "exp": 4102444800u64,
+            })
+            .to_string(),
+        );
+        let token = format!("header.{}.signature", payload);
+
+        let client = ClientBuilder::default()
+            .base_url("http://localhost")
+            .with_tokens(token, "refresh-token")
+            .build()
+            .unwrap();
+
+        let claims = client.whoami().await.unwrap();
+        assert_eq!(claims.subject, "auth0|user-123");
+        assert_eq!(claims.email.as_deref(), Some("user@example.com"));
+        assert_eq!(claims.scopes, vec!["rentals:view"]);
+
+        let api_key_client = ClientBuilder::default()
+            .with_api_key("basilica_opaque_key")
+            .build()
+            .unwrap();
+        assert!(matches!(
+            api_key_client.whoami().await,
+            Err(ApiError::InvalidRequest { .. })
+        ));
+    }
+
+    const LARGE_LOG_BYTES: usize = 8 * 1024 * 1024;
+
+    async fn mock_large_logs() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rentals/rental-1/logs"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![b'x'; LARGE_LOG_BYTES]))
+            .mount(&mock_server)
+            .await;
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_streaming_yields_chunks() {
+        let mock_server = mock_large_logs().await;
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+
+        let stream = client.get_logs_streaming("rental-1", None).await.unwrap();
+        futures_util::pin_mut!(stream);
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert!(!first.is_empty());
+        assert!(first.len() < LARGE_LOG_BYTES);
+
+        let mut total = first.len();
+        while let Some(chunk) = stream.next().await {
+            total += chunk.unwrap().len();
+        }
+        assert_eq!(total, LARGE_LOG_BYTES);
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_truncates_at_limit() {
+        let mock_server = mock_large_logs().await;
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .max_log_bytes(1024)
+            .build()
+            .unwrap();
+
+        let logs = client.get_logs("rental-1", None).await.unwrap();
+        assert!(logs.truncated);
+        assert_eq!(logs.content.len(), 1024);
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_within_limit_is_not_truncated() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rentals/rental-1/logs"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("line 1\nline 2\n"))
+            .mount(&mock_server)
+            .await;
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+
+        let logs = client.get_logs("rental-1", Some(2)).await.unwrap();
+        assert!(!logs.truncated);
+        assert_eq!(logs.content, "line 1\nline 2\n");
+    }
+
+    fn sse_log_body(messages: &[&str]) -> String {
+        messages
+            .iter()
+            .map(|m| {
+                format!(
+                    "data: {}\n\n",
+                    json!({
+                        "timestamp": "2024-01-01T00:00:00Z",
+                        "stream": "stdout",
+                        "message": m,
+                    })
+                )
+            })
+            .collect()
+    }
+
+    async fn mock_sse_logs(mock_server: &MockServer, rental_id: &str, messages: &[&str]) {
+        Mock::given(method("GET"))
+            .and(path(format!("/rentals/{rental_id}/logs")))
+            .and(query_param("follow", "true"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(sse_log_body(messages)),
+            )
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_follow_rental_logs_parses_sse_events() {
+        let mock_server = MockServer::start().await;
+        mock_sse_logs(&mock_server, "rental-1", &["hello", "world"]).await;
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+
+        let stream = client.follow_rental_logs("rental-1", None).await.unwrap();
+        let lines: Vec<_> = stream.map(|l| l.unwrap()).collect().await;
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].message, "hello");
+        assert_eq!(lines[1].stream, "stdout");
+        assert_eq!(lines[1].message, "world");
+    }
+
+    #[tokio::test]
+    async fn test_stream_rental_logs_falls_back_to_sse() {
+        // The mock server refuses the WebSocket upgrade with a 404
+        let mock_server = MockServer::start().await;
+        mock_sse_logs(&mock_server, "rental-1", &["hello", "world"]).await;
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+
+        assert!(client
+            .follow_rental_logs_ws("rental-1", None)
+            .await
+            .is_err());
+        let lines: Vec<_> = client
+            .stream_rental_logs("rental-1", None)
+            .await
+            .unwrap()
+            .map(|l| l.unwrap().message)
+            .collect()
+            .await;
+        assert_eq!(lines, vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn test_websocket_base_url() {
+        assert_eq!(
+            websocket_base_url("https://api.basilica.ai"),
+            "wss://api.basilica.ai"
+        );
+        assert_eq!(
+            websocket_base_url("http://127.0.0.1:8000"),
+            "ws://127.0.0.1:8000"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_logs_multi_merges_rentals() {
+        let mock_server = MockServer::start().await;
+        mock_sse_logs(&mock_server, "rental-a", &["a1", "a2"]).await;
+        mock_sse_logs(&mock_server, "rental-b", &["b1"]).await;
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+
+        let items: Vec<_> = client
+            .stream_logs_multi(vec!["rental-a".into(), "rental-b".into()])
+            .collect()
+            .await;
+
+        let mut lines: Vec<_> = items
+            .into_iter()
+            .map(|(id, line)| (id, line.unwrap()))
+            .collect();
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![
+                ("rental-a".to_string(), "a1".to_string()),
+                ("rental-a".to_string(), "a2".to_string()),
+                ("rental-b".to_string(), "b1".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_logs_multi_reports_failed_rental() {
+        let mock_server = MockServer::start().await;
+        mock_sse_logs(&mock_server, "rental-a", &["a1"]).await;
+        Mock::given(method("GET"))
+            .and(path("/rentals/missing/logs"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+
+        let items: Vec<_> = client
+            .stream_logs_multi(vec!["missing".into(), "rental-a".into()])
+            .collect()
+            .await;
+
+        assert_eq!(items.len(), 2);
+        assert!(items
+            .iter()
+            .any(|(id, line)| id == "missing" && matches!(line, Err(ApiError::NotFound { .. }))));
+        assert!(items
+            .iter()
+            .any(|(id, line)| id == "rental-a" && line.as_deref().ok() == Some("a1")));
+    }
+
+    fn slow_client(mock_server: &MockServer) -> ClientBuilder {
+        ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .connect_timeout(Duration::from_millis(50))
+    }
+
+    async fn mock_slow(mock_server: &MockServer, route: &str, delay: Duration) {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({
+                        "status": "healthy",
+                        "version": "1.0.0",
+                        "timestamp": "2024-01-01T00:00:00Z",
+                        "healthy_validators": 1,
+                        "total_validators": 1,
+                    }))
+                    .set_delay(delay),
+            )
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_connect_timeout_does_not_limit_slow_response() {
+        let mock_server = MockServer::start().await;
+        mock_slow(&mock_server, "/health", Duration::from_millis(300)).await;
+
+        let client = slow_client(&mock_server)
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        assert!(client.health_check().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_limits_slow_response() {
+        let mock_server = MockServer::start().await;
+        mock_slow(&mock_server, "/health", Duration::from_millis(500)).await;
+
+        let client = slow_client(&mock_server)
+            .timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        match client.health_check().await {
+            Err(ApiError::HttpClient(e)) => assert!(e.is_timeout()),
+            other => panic!("expected timeout, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_stream_exempt_from_request_timeout() {
+        let mock_server = MockServer::start().await;
+        mock_slow(
+            &mock_server,
+            "/rentals/rental-1/logs",
+            Duration::from_millis(300),
+        )
+        .await;
+
+        let client = slow_client(&mock_server)
+            .timeout(Duration::from_millis(100))
+            .read_timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let response = client
+            .get_rental_logs("rental-1", true, None)
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_log_stream_honors_read_timeout() {
+        let mock_server = MockServer::start().await;
+        mock_slow(
+            &mock_server,
+            "/rentals/rental-1/logs",
+            Duration::from_millis(500),
+        )
+        .await;
+
+        let client = slow_client(&mock_server)
+            .read_timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client.get_rental_logs("rental-1", true, None).await,
+            Err(ApiError::Timeout)
+        ));
+    }
+
+    fn rental_item(rental_id: &str, state: &str) -> serde_json::Value {
+        json!({
+            "rental_id": rental_id,
+            "executor_id": "exec-1",
+            "container_id": "container-1",
+            "state": state,
+            "created_at": "2024-01-01T00:00:00Z",
+            "miner_id": "miner-1",
+            "container_image": "ubuntu:22.04",
+            "gpu_specs": [],
+            "has_ssh": true,
+        })
+    }
+
+    async fn mock_rentals(mock_server: &MockServer, rentals: Vec<serde_json::Value>) {
+        let total_count = rentals.len();
+        Mock::given(method("GET"))
+            .and(path("/rentals"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "rentals": rentals,
+                "total_count": total_count,
+            })))
+            .mount(mock_server)
+            .await;
+    }
+
+    async fn mock_stop(mock_server: &MockServer, rental_id: &str, response: ResponseTemplate) {
+        Mock::given(method("DELETE"))
+            .and(path(format!("/rentals/{rental_id}")))
+            .respond_with(response)
+            .expect(1)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_terminate_rentals_reports_partial_failure() {
+        let mock_server = MockServer::start().await;
+        mock_rentals(
+            &mock_server,
+            vec![
+                rental_item("r1", "Active"),
+                rental_item("r2", "Active"),
+                rental_item("r3", "Failed"),
+                rental_item("r4", "Stopped"),
+            ],
+        )
+        .await;
+        mock_stop(&mock_server, "r1", ResponseTemplate::new(204)).await;
+        mock_stop(
+            &mock_server,
+            "r2",
+            ResponseTemplate::new(500).set_body_json(json!({
+                "error": {
+                    "code": "BASILICA_API_VALIDATOR_COMMUNICATION",
+                    "message": "validator unavailable",
+                    "timestamp": "2024-01-01T00:00:00Z",
+                    "retryable": true,
+                }
+            })),
+        )
+        .await;
+        mock_stop(&mock_server, "r3", ResponseTemplate::new(204)).await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+        let report = client
+            .terminate_rentals(ListRentalsQuery::default(), None)
+            .await
+            .unwrap();
+
+        // r4 is already stopped and is not touched
+        assert_eq!(report.total(), 3);
+        assert_eq!(report.terminated, vec!["r1", "r3"]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "r2");
+        assert!(
+            matches!(&report.failed[0].1, ApiError::Internal { message } if message == "validator unavailable")
+        );
+        assert!(!report.is_success());
+    }
+
+    #[tokio::test]
+    async fn test_terminate_rentals_by_status_sends_reason() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rentals"))
+            .and(query_param("status", "Failed"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "rentals": [rental_item("r3", "Failed")],
+                "total_count": 1,
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/rentals/r3"))
+            .and(query_param("reason", "cleanup"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+        let filter = ListRentalsQuery {
+            status: Some(RentalState::Failed),
+            ..Default::default()
+        };
+        let report = client
+            .terminate_rentals(filter, Some("cleanup"))
+            .await
+            .unwrap();
+
+        assert_eq!(report.terminated, vec!["r3"]);
+        assert!(report.is_success());
+    }
+
+    #[tokio::test]
+    async fn test_terminate_rentals_fails_when_listing_fails() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rentals"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+        let result = client
+            .terminate_rentals(ListRentalsQuery::default(), None)
+            .await;
+
+        assert!(matches!(result, Err(ApiError::Internal { .. })));
+    }
+
+    fn executor_json(id: &str) -> serde_json::Value {
+        json!({
+            "executor": {
+                "id": id,
+                "gpu_specs": [],
+                "cpu_specs": {"cores": 8, "model": "EPYC", "memory_gb": 64},
+                "location": null,
+            },
+            "availability": {
+                "available_until": null,
+                "verification_score": 1.0,
+                "uptime_percentage": 99.5,
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_stream_executors_requests_ndjson() {
+        let mock_server = MockServer::start().await;
+        let body = format!(
+            "{}\n{}\n\n{}",
+            executor_json("exec-1"),
+            executor_json("exec-2"),
+            executor_json("exec-3")
+        );
+
+        Mock::given(method("GET"))
+            .and(path("/executors"))
+            .and(header("Accept", NDJSON_CONTENT_TYPE))
+            .and(query_param("gpu_type", "h100"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, NDJSON_CONTENT_TYPE))
+            .mount(&mock_server)
+            .await;
+
+        let client = ClientBuilder::default()
+            .base_url(mock_server.uri())
+            .with_tokens("test-token", "refresh-token")
+            .build()
+            .unwrap();
+        let query = ListAvailableExecutorsQuery {
+            available: None,
+            min_gpu_memory: None,
+            gpu_type: Some("h100".to_string()),
+            min_gpu_count: None,
+            mig_profile: None,
+            max_staleness: None,
+            location: None,
+        };
+        let executors: Vec<_> = client
+            .stream_executors(Some(query))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        let ids: Vec<_> = executors
+            .into_iter()
+            .map(|executor| executor.unwrap().executor.id)
+            .collect();
+        assert_eq!(ids, vec!["exec-1", "exec-2", "exec-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_items_yield_before_body_ends() {
+        let first = executor_json("exec-1").to_string();
+        let (head, tail) = first.split_at(first.len() / 2);
+        let chunks: Vec<Result<Bytes>> = vec![
+            Ok(Bytes::from(head.to_string())),
+            Ok(Bytes::from(format!(
+                "{tail}\n{}\n",
+                executor_json("exec-2")
+            ))),
+        ];
+        // The rest of the listing never arrives
+        let body = futures_util::stream::iter(chunks).chain(futures_util::stream::pending());
+        let items = ndjson_items::<AvailableExecutor, _>(body, Duration::from_secs(3600));
+        futures_util::pin_mut!(items);
+
+        // A line split across chunks is parsed once complete
+        let next = tokio::time::timeout(Duration::from_secs(5), items.next()).await;
+        assert_eq!(next.unwrap().unwrap().unwrap().executor.id, "exec-1");
+        let next = tokio::time::timeout(Duration::from_secs(5), items.next()).await;
+        assert_eq!(next.unwrap().unwrap().unwrap().executor.id, "exec-2");
+
+        // Nothing more is yielded until more of the body arrives
+        let next = tokio::time::timeout(Duration::from_millis(50), items.next()).await;
+        assert!(next.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_invalid_line_ends_stream() {
+        let chunks: Vec<Result<Bytes>> = vec![Ok(Bytes::from("{not json}\n"))];
+        let items = ndjson_items::<AvailableExecutor, _>(
+            futures_util::stream::iter(chunks),
+            Duration::from_secs(5),
+        );
+        let items: Vec<_> = items.collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(ApiError::Internal { .. })));
+    }
+
     #[test]
     fn test_builder_requires_auth() {
         let result = ClientBuilder::default().build();
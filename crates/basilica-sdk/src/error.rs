@@ -42,6 +42,24 @@ pub enum ApiError {
     #[error("Conflict: {message}")]
     Conflict { message: String },
 
+    /// Error raised by a backend service with its own error code, e.g. a
+    /// validator error forwarded by the API
+    #[error("{message}")]
+    Upstream {
+        status: u16,
+        code: String,
+        message: String,
+        retryable: bool,
+    },
+
+    /// Rental deployment did not finish in time
+    #[error("{message}")]
+    DeploymentTimeout {
+        /// Deployment phase that timed out: `pull`, `create` or `start`
+        phase: String,
+        message: String,
+    },
+
     /// Internal server error
     #[error("Internal server error: {message}")]
     Internal { message: String },
@@ -64,7 +82,7 @@ pub type Result<T> = std::result::Result<T, ApiError>;
 
 impl ApiError {
     /// Get error code for this error
-    pub fn error_code(&self) -> &'static str {
+    pub fn error_code(&self) -> &str {
         match self {
             ApiError::HttpClient(_) => "BASILICA_API_HTTP_CLIENT_ERROR",
             ApiError::MissingAuthentication { .. } => "BASILICA_API_AUTH_MISSING",
@@ -75,6 +93,8 @@ impl ApiError {
             ApiError::NotFound { .. } => "BASILICA_API_NOT_FOUND",
             ApiError::BadRequest { .. } => "BASILICA_API_BAD_REQUEST",
             ApiError::Conflict { .. } => "BASILICA_API_CONFLICT",
+            ApiError::Upstream { code, .. } => code,
+            ApiError::DeploymentTimeout { .. } => "BASILICA_API_DEPLOYMENT_TIMEOUT",
             ApiError::Internal { .. } => "BASILICA_API_INTERNAL_ERROR",
             ApiError::ServiceUnavailable => "BASILICA_API_SERVICE_UNAVAILABLE",
             ApiError::Timeout => "BASILICA_API_TIMEOUT",
@@ -84,17 +104,25 @@ impl ApiError {
 
     /// Check if error is retryable
     pub fn is_retryable(&self) -> bool {
+        if let ApiError::Upstream { retryable, .. } = self {
+            return *retryable;
+        }
         matches!(
             self,
             ApiError::HttpClient(_)
                 | ApiError::ValidatorCommunication { .. }
+                | ApiError::RateLimitExceeded
                 | ApiError::Timeout
                 | ApiError::ServiceUnavailable
+                | ApiError::DeploymentTimeout { .. }
         )
     }
 
     /// Check if error is a client error
     pub fn is_client_error(&self) -> bool {
+        if let ApiError::Upstream { status, .. } = self {
+            return (400..500).contains(status);
+        }
         matches!(
             self,
             ApiError::MissingAuthentication { .. }
@@ -130,4 +158,8 @@ pub struct ErrorDetails {
 
     /// Whether the error is retryable
     pub retryable: bool,
+
+    /// Deployment phase that timed out, for deployment timeouts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
 }
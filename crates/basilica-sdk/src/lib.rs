@@ -8,6 +8,8 @@
 pub mod auth;
 pub mod client;
 pub mod error;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod types;
 
 // Re-export main types
@@ -0,0 +1,470 @@
+//! Fake API gateway for testing code built on the SDK
+//!
+//! [`MockGateway`] serves canned responses for the common endpoints on a
+//! local port, so applications can exercise a full rental lifecycle without
+//! a validator, billing or the real API:
+//!
+//! - `GET /health`
+//! - `GET /executors`, listing executors not currently rented
+//! - `POST /rentals`, `GET /rentals` and `GET /rentals/:id`
+//! - `DELETE /rentals/:id`
+//!
+//! Rentals start out active. Any credentials are accepted, and query filters
+//! other than the rental status are ignored. Requires the `testing` feature.
+//!
+//! ```rust,no_run
+//! use basilica_sdk::testing::MockGateway;
+//! use basilica_sdk::StartRentalApiRequestBuilder;
+//!
+//! # async fn example() -> basilica_sdk::Result<()> {
+//! let gateway = MockGateway::start().await;
+//! let client = gateway.client()?;
+//!
+//! let rental = client
+//!     .start_rental(StartRentalApiRequestBuilder::new("nvidia/cuda:12.2.0-base").build()?)
+//!     .await?;
+//! client.stop_rental(&rental.rental_id).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::{BasilicaClient, ClientBuilder};
+use crate::error::{ErrorDetails, ErrorResponse, Result};
+use crate::types::{
+    ApiListRentalsResponse, ApiRentalListItem, AvailabilityInfo, AvailableExecutor, CpuSpec,
+    ExecutorDetails, ExecutorSelection, GpuSpec, HealthCheckResponse,
+    ListAvailableExecutorsResponse, ListRentalsQuery, RentalState, RentalStatus,
+    RentalStatusWithSshResponse, StartRentalApiRequest, StateTransition,
+};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use basilica_validator::rental::{ContainerInfo, RentalResponse};
+use chrono::{DateTime, Utc};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::task::JoinHandle;
+
+/// API key the client returned by [`MockGateway::client`] sends
+pub const MOCK_API_KEY: &str = "mock-api-key";
+
+/// Local stand-in for the Basilica API gateway
+///
+/// The server runs until the gateway is dropped.
+pub struct MockGateway {
+    addr: SocketAddr,
+    state: Arc<Mutex<GatewayState>>,
+    server: JoinHandle<()>,
+}
+
+struct GatewayState {
+    executors: Vec<ExecutorDetails>,
+    rentals: Vec<MockRental>,
+}
+
+struct MockRental {
+    rental_id: String,
+    executor: ExecutorDetails,
+    container_image: String,
+    ssh_credentials: Option<String>,
+    state: RentalState,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    state_history: Vec<StateTransition>,
+}
+
+impl MockRental {
+    fn transition(&mut self, to: RentalState, reason: &str) {
+        let now = Utc::now();
+        self.state_history.push(StateTransition {
+            from: self.state.clone(),
+            to: to.clone(),
+            at: now,
+            reason: reason.to_string(),
+        });
+        self.state = to;
+        self.updated_at = now;
+    }
+
+    fn status(&self) -> RentalStatusWithSshResponse {
+        RentalStatusWithSshResponse {
+            rental_id: self.rental_id.clone(),
+            status: match self.state {
+                RentalState::Provisioning => RentalStatus::Pending,
+                RentalState::Active | RentalState::Unhealthy | RentalState::Stopping => {
+                    RentalStatus::Active
+                }
+                RentalState::Stopped => RentalStatus::Terminated,
+                RentalState::Failed => RentalStatus::Failed,
+            },
+            executor: self.executor.clone(),
+            ssh_credentials: self.ssh_credentials.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            gpu_usage: Vec::new(),
+            state_history: self.state_history.clone(),
+            cost_so_far: None,
+            energy_kwh: None,
+        }
+    }
+
+    fn list_item(&self) -> ApiRentalListItem {
+        ApiRentalListItem {
+            rental_id: self.rental_id.clone(),
+            executor_id: self.executor.id.clone(),
+            container_id: container_id(&self.rental_id),
+            state: self.state.clone(),
+            created_at: self.created_at.to_rfc3339(),
+            miner_id: "mock-miner".to_string(),
+            container_image: self.container_image.clone(),
+            gpu_specs: self.executor.gpu_specs.clone(),
+            has_ssh: self.ssh_credentials.is_some(),
+            cpu_specs: Some(self.executor.cpu_specs.clone()),
+            location: self.executor.location.clone(),
+            network_speed: None,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        !matches!(self.state, RentalState::Stopped | RentalState::Failed)
+    }
+}
+
+impl MockGateway {
+    /// Start a gateway offering [`MockGateway::default_executor`]
+    pub async fn start() -> Self {
+        Self::start_with_executors(vec![Self::default_executor()]).await
+    }
+
+    /// Start a gateway offering `executors` for rent
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port can be bound.
+    pub async fn start_with_executors(executors: Vec<ExecutorDetails>) -> Self {
+        let state = Arc::new(Mutex::new(GatewayState {
+            executors,
+            rentals: Vec::new(),
+        }));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind mock gateway");
+        let addr = listener
+            .local_addr()
+            .expect("mock gateway has no local address");
+        let router = router(state.clone());
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Self {
+            addr,
+            state,
+            server,
+        }
+    }
+
+    /// Executor with a single H100 offered by [`MockGateway::start`]
+    pub fn default_executor() -> ExecutorDetails {
+        ExecutorDetails {
+            id: "mock-executor-0".to_string(),
+            gpu_specs: vec![GpuSpec {
+                name: "NVIDIA H100 80GB HBM3".to_string(),
+                memory_gb: 80,
+                compute_capability: "9.0".to_string(),
+                uuid: None,
+                pci_bus_id: None,
+                numa_node: None,
+                mig_profile: None,
+            }],
+            cpu_specs: CpuSpec {
+                cores: 32,
+                model: "AMD EPYC 9354".to_string(),
+                memory_gb: 256,
+            },
+            location: Some("US".to_string()),
+            network_speed: None,
+        }
+    }
+
+    /// Base URL of the gateway, e.g. `http://127.0.0.1:41234`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Client pointed at the gateway, authenticated with [`MOCK_API_KEY`]
+    pub fn client(&self) -> Result<BasilicaClient> {
+        ClientBuilder::default()
+            .base_url(self.url())
+            .with_api_key(MOCK_API_KEY)
+            .build()
+    }
+
+    /// IDs of every rental started on the gateway, oldest first
+    pub fn rental_ids(&self) -> Vec<String> {
+        lock(&self.state)
+            .rentals
+            .iter()
+            .map(|rental| rental.rental_id.clone())
+            .collect()
+    }
+
+    /// Move a rental to `state`, e.g. to simulate a failure. Returns false
+    /// for an unknown rental.
+    pub fn set_rental_state(&self, rental_id: &str, state: RentalState) -> bool {
+        let mut gateway = lock(&self.state);
+        match gateway
+            .rentals
+            .iter_mut()
+            .find(|rental| rental.rental_id == rental_id)
+        {
+            Some(rental) => {
+                rental.transition(state, "set by test");
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Drop for MockGateway {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+type SharedState = Arc<Mutex<GatewayState>>;
+
+fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/executors", get(list_executors))
+        .route("/rentals", get(list_rentals).post(start_rental))
+        .route("/rentals/:id", get(rental_status).delete(stop_rental))
+        .with_state(state)
+}
+
+fn lock(state: &SharedState) -> MutexGuard<'_, GatewayState> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn container_id(rental_id: &str) -> String {
+    format!("mock-container-{rental_id}")
+}
+
+/// Error body the SDK maps to the matching [`crate::ApiError`]
+fn error(status: StatusCode, code: &str, message: String) -> Response {
+    let body = ErrorResponse {
+        error: ErrorDetails {
+            code: code.to_string(),
+            message,
+            timestamp: Utc::now(),
+            retryable: false,
+            phase: None,
+        },
+    };
+    (status, Json(body)).into_response()
+}
+
+fn rental_not_found(rental_id: &str) -> Response {
+    error(
+        StatusCode::NOT_FOUND,
+        "BASILICA_API_NOT_FOUND",
+        format!("Rental {rental_id} not found"),
+    )
+}
+
+async fn health() -> Json<HealthCheckResponse> {
+    Json(HealthCheckResponse {
+        status: "healthy".to_string(),
+        version: crate::VERSION.to_string(),
+        timestamp: Utc::now(),
+        healthy_validators: 1,
+        total_validators: 1,
+        build_info: None,
+    })
+}
+
+async fn list_executors(State(state): State<SharedState>) -> Json<ListAvailableExecutorsResponse> {
+    let gateway = lock(&state);
+    let available_executors: Vec<AvailableExecutor> = gateway
+        .executors
+        .iter()
+        .filter(|executor| {
+            !gateway
+                .rentals
+                .iter()
+                .any(|rental| rental.is_running() && rental.executor.id == executor.id)
+        })
+        .map(|executor| AvailableExecutor {
+            executor: executor.clone(),
+            availability: AvailabilityInfo {
+                available_until: None,
+                verification_score: 1.0,
+                uptime_percentage: 100.0,
+                as_of: Some(Utc::now()),
+            },
+            hourly_price: None,
+            currency: None,
+        })
+        .collect();
+    Json(ListAvailableExecutorsResponse {
+        total_count: available_executors.len(),
+        available_executors,
+    })
+}
+
+async fn start_rental(
+    State(state): State<SharedState>,
+    Json(request): Json<StartRentalApiRequest>,
+) -> Response {
+    let mut gateway = lock(&state);
+    let is_free = |executor: &&ExecutorDetails| {
+        !gateway
+            .rentals
+            .iter()
+            .any(|rental| rental.is_running() && rental.executor.id == executor.id)
+    };
+    let mut pin_honored = None;
+    let executor = match &request.executor_selection {
+        ExecutorSelection::ExecutorId { executor_id } => {
+            gateway.executors.iter().find(|e| &e.id == executor_id)
+        }
+        ExecutorSelection::GpuRequirements { gpu_requirements } => gateway
+            .executors
+            .iter()
+            .filter(is_free)
+            .find(|e| e.gpu_specs.len() as u32 >= gpu_requirements.gpu_count),
+        ExecutorSelection::Pinned {
+            executor_id,
+            require_exact,
+        } => {
+            let pinned = gateway.executors.iter().find(|e| &e.id == executor_id);
+            match pinned {
+                Some(executor) if is_free(&executor) => {
+                    pin_honored = Some(true);
+                    Some(executor)
+                }
+                Some(executor) if !require_exact => {
+                    pin_honored = Some(false);
+                    gateway
+                        .executors
+                        .iter()
+                        .filter(is_free)
+                        .find(|e| same_gpus(e, executor))
+                }
+                _ => None,
+            }
+        }
+    };
+    let Some(executor) = executor.cloned() else {
+        return error(
+            StatusCode::NOT_FOUND,
+            "BASILICA_API_NOT_FOUND",
+            "No matching executor available".to_string(),
+        );
+    };
+    if !is_free(&&executor) {
+        return error(
+            StatusCode::CONFLICT,
+            "BASILICA_API_CONFLICT",
+            format!("Executor {} is already rented", executor.id),
+        );
+    }
+
+    let rental_id = format!("00000000-0000-4000-8000-{:012}", gateway.rentals.len() + 1);
+    let ssh_credentials = (!request.no_ssh).then(|| "root@127.0.0.1:2222".to_string());
+    let now = Utc::now();
+    let executor_id = executor.id.clone();
+    let mut rental = MockRental {
+        rental_id: rental_id.clone(),
+        executor,
+        container_image: request.container_image,
+        ssh_credentials: ssh_credentials.clone(),
+        state: RentalState::Provisioning,
+        created_at: now,
+        updated_at: now,
+        state_history: Vec::new(),
+    };
+    rental.transition(RentalState::Active, "container started");
+    gateway.rentals.push(rental);
+
+    Json(RentalResponse {
+        rental_id: rental_id.clone(),
+        ssh_credentials,
+        container_info: ContainerInfo {
+            container_id: container_id(&rental_id),
+            container_name: format!("basilica-{rental_id}"),
+            mapped_ports: Vec::new(),
+            status: "running".to_string(),
+            labels: Default::default(),
+        },
+        executor_id,
+        pin_honored,
+    })
+    .into_response()
+}
+
+/// Whether two executors have the same GPUs
+fn same_gpus(a: &ExecutorDetails, b: &ExecutorDetails) -> bool {
+    let gpus = |e: &ExecutorDetails| {
+        let mut gpus: Vec<_> = e
+            .gpu_specs
+            .iter()
+            .map(|gpu| (gpu.name.clone(), gpu.memory_gb))
+            .collect();
+        gpus.sort();
+        gpus
+    };
+    gpus(a) == gpus(b)
+}
+
+async fn list_rentals(
+    State(state): State<SharedState>,
+    Query(query): Query<ListRentalsQuery>,
+) -> Json<ApiListRentalsResponse> {
+    let gateway = lock(&state);
+    let rentals: Vec<ApiRentalListItem> = gateway
+        .rentals
+        .iter()
+        .filter(|rental| query.status.as_ref().map_or(true, |s| &rental.state == s))
+        .map(MockRental::list_item)
+        .collect();
+    Json(ApiListRentalsResponse {
+        total_count: rentals.len(),
+        rentals,
+    })
+}
+
+async fn rental_status(
+    State(state): State<SharedState>,
+    Path(rental_id): Path<String>,
+) -> Response {
+    let gateway = lock(&state);
+    match gateway.rentals.iter().find(|r| r.rental_id == rental_id) {
+        Some(rental) => Json(rental.status()).into_response(),
+        None => rental_not_found(&rental_id),
+    }
+}
+
+async fn stop_rental(State(state): State<SharedState>, Path(rental_id): Path<String>) -> Response {
+    let mut gateway = lock(&state);
+    match gateway
+        .rentals
+        .iter_mut()
+        .find(|r| r.rental_id == rental_id)
+    {
+        Some(rental) => {
+            if rental.is_running() {
+                rental.transition(RentalState::Stopped, "stopped by user");
+            }
+            StatusCode::NO_CONTENT.into_response()
+        }
+        None => rental_not_found(&rental_id),
+    }
+}
@@ -1,5 +1,6 @@
 //! Type definitions for the Basilica SDK
 
+use crate::error::ApiError;
 use serde::{Deserialize, Serialize};
 
 // Re-export types from basilica-validator that are used by the client
@@ -11,6 +12,7 @@ pub use basilica_validator::api::types::{
 };
 
 // Re-export LocationProfile for SDK consumers
+pub use basilica_common::money::{Currency, Money};
 pub use basilica_common::LocationProfile;
 
 // Re-export rental-specific types from validator
@@ -19,7 +21,9 @@ pub use basilica_validator::api::rental_routes::{
 };
 
 // Re-export RentalState from validator for SDK consumers
-pub use basilica_validator::rental::types::RentalState;
+pub use basilica_validator::rental::types::{
+    GpuUsage, RegistryAuth, RentalState, SecretRef, StateTransition,
+};
 
 // SDK-specific types
 
@@ -40,6 +44,29 @@ pub struct HealthCheckResponse {
 
     /// Total validators count
     pub total_validators: usize,
+
+    /// What the gateway was built from; absent from older gateways
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_info: Option<BuildInfo>,
+}
+
+/// Build metadata of a running gateway
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// Crate version
+    pub version: String,
+
+    /// Protocol version for API compatibility
+    pub api_version: String,
+
+    /// Git commit the gateway was built from, or "unknown"
+    pub git_sha: String,
+
+    /// RFC 3339 time of the build
+    pub build_timestamp: String,
+
+    /// Compiler that built the gateway
+    pub rustc_version: String,
 }
 
 /// List rentals query
@@ -93,6 +120,27 @@ pub struct ApiListRentalsResponse {
     pub total_count: usize,
 }
 
+/// Per-rental outcome of a bulk termination
+#[derive(Debug, Default)]
+pub struct TerminateRentalsReport {
+    /// Rentals that were terminated
+    pub terminated: Vec<String>,
+    /// Rentals that could not be terminated, with the error for each
+    pub failed: Vec<(String, ApiError)>,
+}
+
+impl TerminateRentalsReport {
+    /// Number of rentals the termination was attempted for
+    pub fn total(&self) -> usize {
+        self.terminated.len() + self.failed.len()
+    }
+
+    /// Whether every matching rental was terminated
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
 /// Rental status query parameters
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RentalStatusQuery {
@@ -105,6 +153,160 @@ pub struct RentalStatusQuery {
 pub struct LogStreamQuery {
     pub follow: Option<bool>,
     pub tail: Option<u32>,
+    /// Only return entries at or after this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Buffered rental logs, capped at the client's log byte limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RentalLogs {
+    /// Raw log body as returned by the API
+    pub content: String,
+    /// Whether the body exceeded the limit and was cut short
+    pub truncated: bool,
+}
+
+/// One line from a followed rental log stream
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RentalLogLine {
+    /// When the container wrote the line
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Output stream the line came from (`stdout` or `stderr`)
+    pub stream: String,
+    /// Log line without a trailing newline
+    pub message: String,
+    /// Severity, for structured lines that carry one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<LogLevel>,
+}
+
+impl RentalLogLine {
+    /// Parse a JSON log line into its level, message and timestamp
+    ///
+    /// The message is taken from a `message` or `msg` field, the level from
+    /// `level`, `severity` or `lvl`, and the timestamp from an RFC 3339
+    /// `timestamp`, `time` or `ts` field. Fields that are missing or
+    /// unreadable keep their current value, and a line that is not a JSON
+    /// object is returned unchanged as plain text.
+    pub fn parse_json(mut self) -> Self {
+        let Ok(serde_json::Value::Object(fields)) =
+            serde_json::from_str::<serde_json::Value>(&self.message)
+        else {
+            return self;
+        };
+        let field = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| fields.get(*name).and_then(|value| value.as_str()))
+        };
+
+        if let Some(level) = field(&["level", "severity", "lvl"]).and_then(|l| l.parse().ok()) {
+            self.level = Some(level);
+        }
+        if let Some(timestamp) = field(&["timestamp", "time", "ts"])
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        {
+            self.timestamp = timestamp.with_timezone(&chrono::Utc);
+        }
+        if let Some(message) = field(&["message", "msg"]) {
+            self.message = message.to_string();
+        }
+        self
+    }
+
+    /// Whether the line has a level of at least `min`
+    ///
+    /// Lines without a level never match.
+    pub fn is_at_least(&self, min: LogLevel) -> bool {
+        self.level.is_some_and(|level| level >= min)
+    }
+}
+
+/// How a rental's container writes its log lines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Free-form text
+    #[default]
+    Plain,
+    /// One JSON object per line, parsed into structured log lines
+    Json,
+}
+
+impl LogFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Plain => "plain",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "plain" => Ok(LogFormat::Plain),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!(
+                "unknown log format '{other}', expected 'plain' or 'json'"
+            )),
+        }
+    }
+}
+
+/// Severity of a structured log line, from least to most severe
+///
+/// Parses the level names used by common logging libraries, in any case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" | "information" | "notice" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" | "err" | "fatal" | "critical" | "crit" | "panic" => Ok(LogLevel::Error),
+            other => Err(format!("unknown log level '{other}'")),
+        }
+    }
 }
 
 /// Executor selection strategy for rental requests
@@ -115,6 +317,15 @@ pub enum ExecutorSelection {
     ExecutorId { executor_id: String },
     /// Select best available executor based on GPU requirements
     GpuRequirements { gpu_requirements: GpuRequirements },
+    /// Prefer a specific executor, for reproducing an earlier run
+    ///
+    /// When the executor is unavailable the rental fails if `require_exact`
+    /// is set, and otherwise runs on an executor with the same GPUs.
+    Pinned {
+        executor_id: String,
+        #[serde(default)]
+        require_exact: bool,
+    },
 }
 
 /// Start rental request with flexible executor selection
@@ -133,6 +344,14 @@ pub struct StartRentalApiRequest {
     #[serde(default)]
     pub environment: std::collections::HashMap<String, String>,
 
+    /// Environment variables set from secrets stored on the validator
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<SecretRef>,
+
+    /// Credentials for pulling `container_image` from a private registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_auth: Option<RegistryAuth>,
+
     /// Port mappings
     #[serde(default)]
     pub ports: Vec<PortMappingRequest>,
@@ -152,6 +371,276 @@ pub struct StartRentalApiRequest {
     /// Disable SSH
     #[serde(default)]
     pub no_ssh: bool,
+
+    /// Warn about or stop the rental once its GPUs have been idle this many
+    /// seconds, depending on the validator's idle policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_timeout_secs: Option<u64>,
+
+    /// How the container writes its logs, so JSON lines can be parsed
+    #[serde(default)]
+    pub log_format: LogFormat,
+
+    /// Command run inside the container before it is stopped, e.g. to save
+    /// a checkpoint
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_stop_command: Vec<String>,
+
+    /// How long the pre-stop command may run before the container is
+    /// stopped anyway; the validator default applies when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_stop_timeout_secs: Option<u64>,
+}
+
+/// Port protocols accepted in rental port mappings
+pub const PORT_PROTOCOLS: &[&str] = &["tcp", "udp"];
+
+/// Protocol used when a port mapping does not name one
+pub const DEFAULT_PORT_PROTOCOL: &str = "tcp";
+
+impl StartRentalApiRequest {
+    /// Start building a request for `container_image`
+    pub fn builder(container_image: impl Into<String>) -> StartRentalApiRequestBuilder {
+        StartRentalApiRequestBuilder::new(container_image)
+    }
+
+    /// Check the request for mistakes the API would reject
+    pub fn validate(&self) -> Result<(), ApiError> {
+        let invalid = |message: String| Err(ApiError::InvalidRequest { message });
+
+        if self.container_image.trim().is_empty() {
+            return invalid("container image must not be empty".to_string());
+        }
+        if let ExecutorSelection::ExecutorId { executor_id }
+        | ExecutorSelection::Pinned { executor_id, .. } = &self.executor_selection
+        {
+            if executor_id.trim().is_empty() {
+                return invalid("executor id must not be empty".to_string());
+            }
+        }
+        if let ExecutorSelection::GpuRequirements { gpu_requirements } = &self.executor_selection {
+            if gpu_requirements.gpu_type.is_some() && gpu_requirements.gpu_count == 0 {
+                return invalid("gpu_count must be at least 1 when a GPU type is set".to_string());
+            }
+        }
+        if !self.resources.gpu_types.is_empty() && self.resources.gpu_count == 0 {
+            return invalid("gpu_count must be at least 1 when GPU types are set".to_string());
+        }
+        if self.idle_timeout_secs == Some(0) {
+            return invalid("idle timeout must be greater than zero".to_string());
+        }
+        if let Err(message) = basilica_validator::rental::types::validate_environment(
+            &self.environment,
+            &self.secrets,
+        ) {
+            return invalid(message);
+        }
+        if let Some(Err(message)) = self
+            .registry_auth
+            .as_ref()
+            .map(|auth| auth.registry_for(&self.container_image))
+        {
+            return invalid(message);
+        }
+        if self.pre_stop_command.is_empty() && self.pre_stop_timeout_secs.is_some() {
+            return invalid("pre-stop timeout set without a pre-stop command".to_string());
+        }
+        if self.pre_stop_timeout_secs == Some(0) {
+            return invalid("pre-stop timeout must be greater than zero".to_string());
+        }
+        for port in &self.ports {
+            if !PORT_PROTOCOLS.contains(&port.protocol.as_str()) {
+                return invalid(format!(
+                    "port {} has unsupported protocol '{}' (expected one of: {})",
+                    port.container_port,
+                    port.protocol,
+                    PORT_PROTOCOLS.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fluent builder for [`StartRentalApiRequest`]
+///
+/// `build()` validates the request locally so mistakes surface before any
+/// network call.
+#[derive(Debug)]
+pub struct StartRentalApiRequestBuilder {
+    request: StartRentalApiRequest,
+}
+
+impl StartRentalApiRequestBuilder {
+    /// Create a builder that picks the best executor for the default GPU
+    /// requirements
+    pub fn new(container_image: impl Into<String>) -> Self {
+        Self {
+            request: StartRentalApiRequest {
+                executor_selection: ExecutorSelection::GpuRequirements {
+                    gpu_requirements: GpuRequirements::default(),
+                },
+                container_image: container_image.into(),
+                ssh_public_key: String::new(),
+                environment: Default::default(),
+                secrets: Vec::new(),
+                registry_auth: None,
+                ports: Vec::new(),
+                resources: ResourceRequirementsRequest::default(),
+                command: Vec::new(),
+                volumes: Vec::new(),
+                no_ssh: false,
+                idle_timeout_secs: None,
+                log_format: LogFormat::Plain,
+                pre_stop_command: Vec::new(),
+                pre_stop_timeout_secs: None,
+            },
+        }
+    }
+
+    /// Rent a specific executor
+    pub fn executor_id(mut self, executor_id: impl Into<String>) -> Self {
+        self.request.executor_selection = ExecutorSelection::ExecutorId {
+            executor_id: executor_id.into(),
+        };
+        self
+    }
+
+    /// Rent `executor_id` again, such as the executor an earlier run used
+    ///
+    /// See [`ExecutorSelection::Pinned`] for what happens when it is unavailable.
+    pub fn pinned_executor(mut self, executor_id: impl Into<String>, require_exact: bool) -> Self {
+        self.request.executor_selection = ExecutorSelection::Pinned {
+            executor_id: executor_id.into(),
+            require_exact,
+        };
+        self
+    }
+
+    /// Rent the best executor matching `gpu_requirements`
+    pub fn gpu_requirements(mut self, gpu_requirements: GpuRequirements) -> Self {
+        self.request.executor_selection = ExecutorSelection::GpuRequirements { gpu_requirements };
+        self
+    }
+
+    /// Set the SSH public key installed in the container
+    pub fn ssh_public_key(mut self, key: impl Into<String>) -> Self {
+        self.request.ssh_public_key = key.into();
+        self
+    }
+
+    /// Set an environment variable
+    pub fn env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.request.environment.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set an environment variable from the validator's secret `name`
+    pub fn secret_env(mut self, env: impl Into<String>, name: impl Into<String>) -> Self {
+        self.request.secrets.push(SecretRef {
+            env: env.into(),
+            name: name.into(),
+        });
+        self
+    }
+
+    /// Pull the image from a private registry with these credentials
+    pub fn registry_auth(
+        mut self,
+        registry: impl Into<String>,
+        username: impl Into<String>,
+        token: impl Into<String>,
+    ) -> Self {
+        self.request.registry_auth = Some(RegistryAuth {
+            registry: registry.into(),
+            username: username.into(),
+            token: token.into(),
+        });
+        self
+    }
+
+    /// Map a container port to a host port
+    pub fn port(
+        mut self,
+        container_port: u32,
+        host_port: u32,
+        protocol: impl Into<String>,
+    ) -> Self {
+        self.request.ports.push(PortMappingRequest {
+            container_port,
+            host_port,
+            protocol: protocol.into(),
+        });
+        self
+    }
+
+    /// Set the resource requirements
+    pub fn resources(mut self, resources: ResourceRequirementsRequest) -> Self {
+        self.request.resources = resources;
+        self
+    }
+
+    /// Set the command run in the container
+    pub fn command<I, S>(mut self, command: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.request.command = command.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Mount a host path into the container
+    pub fn volume(
+        mut self,
+        host_path: impl Into<String>,
+        container_path: impl Into<String>,
+        read_only: bool,
+    ) -> Self {
+        self.request.volumes.push(VolumeMountRequest {
+            host_path: host_path.into(),
+            container_path: container_path.into(),
+            read_only,
+        });
+        self
+    }
+
+    /// Disable SSH access to the rental
+    pub fn no_ssh(mut self, no_ssh: bool) -> Self {
+        self.request.no_ssh = no_ssh;
+        self
+    }
+
+    /// Apply the validator's idle policy after the GPUs have been idle this
+    /// long
+    pub fn idle_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.request.idle_timeout_secs = Some(timeout.as_secs());
+        self
+    }
+
+    /// Run `command` inside the container before it is stopped, allowing it
+    /// up to `timeout` to finish, e.g. to save a checkpoint
+    pub fn pre_stop<I, S>(mut self, command: I, timeout: std::time::Duration) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.request.pre_stop_command = command.into_iter().map(Into::into).collect();
+        self.request.pre_stop_timeout_secs = Some(timeout.as_secs());
+        self
+    }
+
+    /// Declare how the container writes its logs
+    pub fn log_format(mut self, format: LogFormat) -> Self {
+        self.request.log_format = format;
+        self
+    }
+
+    /// Validate and return the request
+    pub fn build(self) -> Result<StartRentalApiRequest, ApiError> {
+        self.request.validate()?;
+        Ok(self.request)
+    }
 }
 
 /// Extended rental status response that includes SSH credentials from the database
@@ -175,6 +664,40 @@ pub struct RentalStatusWithSshResponse {
 
     /// Last update timestamp
     pub updated_at: chrono::DateTime<chrono::Utc>,
+
+    /// Per-GPU utilization reported by the validator
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpu_usage: Vec<GpuUsage>,
+
+    /// Every state change of the rental, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub state_history: Vec<StateTransition>,
+
+    /// Cost accrued so far, when the API is connected to billing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_so_far: Option<CostBreakdown>,
+
+    /// GPU energy used so far in kWh, when the executor reports it to billing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub energy_kwh: Option<f64>,
+}
+
+/// Cost a rental has accrued, split by resource
+///
+/// All amounts are in the same currency; `total` is the sum of the others.
+/// Memory, network and storage are `None` when they are not billed
+/// separately from GPU time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    /// GPU time
+    pub compute: Money,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<Money>,
+    pub total: Money,
 }
 
 impl RentalStatusWithSshResponse {
@@ -190,6 +713,10 @@ impl RentalStatusWithSshResponse {
             ssh_credentials,
             created_at: response.created_at,
             updated_at: response.updated_at,
+            gpu_usage: response.gpu_usage,
+            state_history: response.state_history,
+            cost_so_far: None,
+            energy_kwh: None,
         }
     }
 }
@@ -235,3 +762,209 @@ pub struct ApiKeyInfo {
     /// Last usage timestamp
     pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_invalid(result: Result<StartRentalApiRequest, ApiError>, needle: &str) {
+        match result {
+            Err(ApiError::InvalidRequest { message }) => {
+                assert!(message.contains(needle), "unexpected message: {message}")
+            }
+            other => panic!("expected invalid request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_builder_builds_valid_request() {
+        let request = StartRentalApiRequest::builder("nvidia/cuda:12.2.0-base-ubuntu22.04")
+            .executor_id("executor-1")
+            .ssh_public_key("ssh-ed25519 AAAA")
+            .env("MODE", "train")
+            .port(8080, 80, "udp")
+            .command(["python", "train.py"])
+            .volume("/data", "/mnt/data", true)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            request.executor_selection,
+            ExecutorSelection::ExecutorId { ref executor_id } if executor_id == "executor-1"
+        ));
+        assert_eq!(request.environment["MODE"], "train");
+        assert_eq!(request.ports[0].protocol, "udp");
+        assert_eq!(request.command, vec!["python", "train.py"]);
+        assert!(request.volumes[0].read_only);
+    }
+
+    #[test]
+    fn test_builder_rejects_empty_image() {
+        assert_invalid(
+            StartRentalApiRequest::builder("  ").build(),
+            "container image",
+        );
+    }
+
+    #[test]
+    fn test_builder_rejects_zero_gpus_with_gpu_type() {
+        let requirements = GpuRequirements {
+            min_memory_gb: 0,
+            gpu_type: Some("h100".to_string()),
+            gpu_count: 0,
+            mig_profile: None,
+        };
+        assert_invalid(
+            StartRentalApiRequest::builder("ubuntu")
+                .gpu_requirements(requirements)
+                .build(),
+            "gpu_count",
+        );
+
+        let resources = ResourceRequirementsRequest {
+            gpu_types: vec!["h100".to_string()],
+            ..Default::default()
+        };
+        assert_invalid(
+            StartRentalApiRequest::builder("ubuntu")
+                .resources(resources)
+                .build(),
+            "gpu_count",
+        );
+    }
+
+    #[test]
+    fn test_builder_sets_idle_timeout() {
+        let request = StartRentalApiRequest::builder("ubuntu")
+            .idle_timeout(std::time::Duration::from_secs(1800))
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["idle_timeout_secs"], 1800);
+
+        assert_invalid(
+            StartRentalApiRequest::builder("ubuntu")
+                .idle_timeout(std::time::Duration::ZERO)
+                .build(),
+            "idle timeout",
+        );
+    }
+
+    #[test]
+    fn test_builder_rejects_unknown_port_protocol() {
+        assert_invalid(
+            StartRentalApiRequest::builder("ubuntu")
+                .port(22, 2222, "sctp")
+                .build(),
+            "sctp",
+        );
+        assert!(StartRentalApiRequest::builder("ubuntu")
+            .port(22, 2222, DEFAULT_PORT_PROTOCOL)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_environment() {
+        assert_invalid(
+            StartRentalApiRequest::builder("ubuntu")
+                .env("1ST_RUN", "yes")
+                .build(),
+            "cannot start with a digit",
+        );
+        assert_invalid(
+            StartRentalApiRequest::builder("ubuntu")
+                .env("HF_TOKEN", "plain")
+                .secret_env("HF_TOKEN", "huggingface")
+                .build(),
+            "set more than once",
+        );
+
+        let request = StartRentalApiRequest::builder("ubuntu")
+            .env("MODEL", "llama")
+            .secret_env("HF_TOKEN", "huggingface")
+            .build()
+            .unwrap();
+        assert_eq!(request.secrets[0].name, "huggingface");
+        // Only the secret's name is sent
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["secrets"],
+            serde_json::json!([{ "env": "HF_TOKEN", "name": "huggingface" }])
+        );
+    }
+
+    fn log_line(message: &str) -> RentalLogLine {
+        RentalLogLine {
+            timestamp: "2024-01-01T00:00:00Z".parse().unwrap(),
+            stream: "stdout".to_string(),
+            message: message.to_string(),
+            level: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_mixed_json_and_plain_logs() {
+        let lines: Vec<RentalLogLine> = [
+            r#"{"level":"INFO","msg":"epoch 1","ts":"2024-01-01T00:00:05Z","loss":0.5}"#,
+            "Traceback (most recent call last):",
+            r#"{"severity":"warning","message":"low memory"}"#,
+            r#"{"level":"error","message":"truncated"#,
+            r#"["not", "an", "object"]"#,
+            r#"{"level":"verbose","step":3}"#,
+        ]
+        .into_iter()
+        .map(|message| log_line(message).parse_json())
+        .collect();
+
+        assert_eq!(lines[0].level, Some(LogLevel::Info));
+        assert_eq!(lines[0].message, "epoch 1");
+        assert_eq!(lines[0].timestamp.to_rfc3339(), "2024-01-01T00:00:05+00:00");
+
+        assert_eq!(lines[1], log_line("Traceback (most recent call last):"));
+
+        assert_eq!(lines[2].level, Some(LogLevel::Warn));
+        assert_eq!(lines[2].message, "low memory");
+        assert_eq!(lines[2].timestamp, log_line("").timestamp);
+
+        // Malformed JSON and non-objects pass through as plain text
+        assert_eq!(
+            lines[3],
+            log_line(r#"{"level":"error","message":"truncated"#)
+        );
+        assert_eq!(lines[4], log_line(r#"["not", "an", "object"]"#));
+
+        // Unknown levels and missing messages keep the raw line
+        assert_eq!(lines[5].level, None);
+        assert_eq!(lines[5].message, r#"{"level":"verbose","step":3}"#);
+    }
+
+    #[test]
+    fn test_filter_log_lines_by_level() {
+        let lines: Vec<RentalLogLine> = [
+            r#"{"level":"debug","msg":"batch loaded"}"#,
+            r#"{"level":"info","msg":"epoch 1"}"#,
+            "plain progress bar",
+            r#"{"level":"WARN","msg":"lr decayed"}"#,
+            r#"{"level":"fatal","msg":"CUDA out of memory"}"#,
+        ]
+        .into_iter()
+        .map(|message| log_line(message).parse_json())
+        .collect();
+
+        let at_least = |min: LogLevel| -> Vec<&str> {
+            lines
+                .iter()
+                .filter(|line| line.is_at_least(min))
+                .map(|line| line.message.as_str())
+                .collect()
+        };
+        assert_eq!(
+            at_least(LogLevel::Warn),
+            ["lr decayed", "CUDA out of memory"]
+        );
+        assert_eq!(at_least(LogLevel::Debug).len(), 4);
+        assert_eq!("Warning".parse::<LogLevel>(), Ok(LogLevel::Warn));
+        assert!("loud".parse::<LogLevel>().is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! Drives a full rental lifecycle through the SDK against `MockGateway`
+
+use basilica_sdk::testing::MockGateway;
+use basilica_sdk::{
+    ApiError, ListRentalsQuery, RentalState, RentalStatus, StartRentalApiRequestBuilder,
+};
+
+#[tokio::test]
+async fn test_rental_lifecycle_against_mock_gateway() {
+    let gateway = MockGateway::start().await;
+    let client = gateway.client().unwrap();
+
+    let health = client.health_check().await.unwrap();
+    assert_eq!(health.status, "healthy");
+
+    let executors = client.list_available_executors(None).await.unwrap();
+    assert_eq!(executors.total_count, 1);
+    let executor_id = executors.available_executors[0].executor.id.clone();
+
+    let request = StartRentalApiRequestBuilder::new("nvidia/cuda:12.2.0-base")
+        .executor_id(&executor_id)
+        .ssh_public_key("ssh-ed25519 AAAA test@example")
+        .build()
+        .unwrap();
+    let rental = client.start_rental(request).await.unwrap();
+    assert!(rental.ssh_credentials.is_some());
+    assert_eq!(gateway.rental_ids(), vec![rental.rental_id.clone()]);
+
+    // The rented executor is no longer offered
+    let executors = client.list_available_executors(None).await.unwrap();
+    assert_eq!(executors.total_count, 0);
+
+    let status = client.get_rental_status(&rental.rental_id).await.unwrap();
+    assert!(matches!(status.status, RentalStatus::Active));
+    assert_eq!(status.executor.id, executor_id);
+
+    let active = client
+        .list_rentals(Some(ListRentalsQuery {
+            status: Some(RentalState::Active),
+            ..Default::default()
+        }))
+        .await
+        .unwrap();
+    assert_eq!(active.total_count, 1);
+
+    client.stop_rental(&rental.rental_id).await.unwrap();
+    let status = client.get_rental_status(&rental.rental_id).await.unwrap();
+    assert!(matches!(status.status, RentalStatus::Terminated));
+    let states: Vec<_> = status.state_history.iter().map(|t| t.to.clone()).collect();
+    assert_eq!(states, vec![RentalState::Active, RentalState::Stopped]);
+
+    let executors = client.list_available_executors(None).await.unwrap();
+    assert_eq!(executors.total_count, 1);
+
+    let err = client.get_rental_status("missing").await.unwrap_err();
+    assert!(matches!(err, ApiError::NotFound { .. }));
+}
+
+#[tokio::test]
+async fn test_simulated_rental_failure() {
+    let gateway = MockGateway::start().await;
+    let client = gateway.client().unwrap();
+
+    let request = StartRentalApiRequestBuilder::new("nvidia/cuda:12.2.0-base")
+        .build()
+        .unwrap();
+    let rental = client.start_rental(request).await.unwrap();
+
+    assert!(gateway.set_rental_state(&rental.rental_id, RentalState::Failed));
+    let status = client.get_rental_status(&rental.rental_id).await.unwrap();
+    assert!(matches!(status.status, RentalStatus::Failed));
+    assert!(!gateway.set_rental_state("missing", RentalState::Failed));
+}
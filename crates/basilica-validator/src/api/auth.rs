@@ -0,0 +1,400 @@
+//! Authentication for mutating API routes
+//!
+//! Reads stay public, but requests that start or stop rentals or change
+//! registered miners must carry either the configured API key as a bearer
+//! token or a signature from the validator's own hotkey. Signed requests set
+//! the [`HOTKEY_HEADER`], [`TIMESTAMP_HEADER`] and [`SIGNATURE_HEADER`]
+//! headers, the signature covering [`signing_payload`]. Each signed request
+//! is accepted once; replays within the clock skew window are rejected.
+
+use crate::api::types::ApiError;
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderMap, Method},
+    middleware::Next,
+    response::Response,
+};
+use basilica_common::crypto::{secure_compare, verify_bittensor_signature};
+use basilica_common::identity::Hotkey;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+pub const HOTKEY_HEADER: &str = "x-hotkey";
+pub const TIMESTAMP_HEADER: &str = "x-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+/// How far a signed request's timestamp may be from the validator's clock
+const MAX_CLOCK_SKEW_SECS: i64 = 300;
+
+/// Signed requests already accepted, keyed by (hotkey, timestamp, SHA-256
+/// of the signed payload)
+///
+/// Keying on what was signed rather than on the signature header means a
+/// re-encoded copy of an accepted signature (e.g. upper-cased hex) is still
+/// a replay. Entries are dropped once their timestamp falls outside the
+/// clock skew window, after which the request would be rejected as stale
+/// anyway.
+#[derive(Debug, Default)]
+struct ReplayCache {
+    seen: Mutex<HashSet<(String, i64, [u8; 32])>>,
+}
+
+impl ReplayCache {
+    /// Record a signed request, returning `false` if it was already used
+    fn insert(&self, hotkey: &str, timestamp: i64, payload: &str, now: i64) -> bool {
+        let digest: [u8; 32] = Sha256::digest(payload.as_bytes()).into();
+        let mut seen = match self.seen.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        seen.retain(|(_, seen_at, _)| (now - seen_at).abs() <= MAX_CLOCK_SKEW_SECS);
+        seen.insert((hotkey.to_string(), timestamp, digest))
+    }
+}
+
+/// Credentials accepted for mutating requests
+#[derive(Debug, Clone)]
+pub struct ApiAuth {
+    api_key: Option<String>,
+    validator_hotkey: Hotkey,
+    max_body_size: usize,
+    replays: Arc<ReplayCache>,
+}
+
+impl ApiAuth {
+    /// Accept `api_key` as a bearer token, when set, and signatures from
+    /// `validator_hotkey` over bodies of at most `max_body_size` bytes
+    pub fn new(api_key: Option<String>, validator_hotkey: Hotkey, max_body_size: usize) -> Self {
+        Self {
+            api_key: api_key.filter(|key| !key.is_empty()),
+            validator_hotkey,
+            max_body_size,
+            replays: Arc::default(),
+        }
+    }
+
+    /// Check the credentials of a `method` request for `path_and_query`
+    /// carrying `body`
+    ///
+    /// Missing, invalid or replayed credentials are unauthorized; a valid
+    /// signature from a hotkey other than the validator's is forbidden.
+    pub fn authorize(
+        &self,
+        method: &Method,
+        path_and_query: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), ApiError> {
+        if let Some(token) = bearer_token(headers) {
+            return match &self.api_key {
+                Some(api_key) if secure_compare(token.as_bytes(), api_key.as_bytes()) => Ok(()),
+                _ => Err(ApiError::Unauthorized),
+            };
+        }
+
+        let (Some(hotkey), Some(timestamp), Some(signature)) = (
+            header_str(headers, HOTKEY_HEADER),
+            header_str(headers, TIMESTAMP_HEADER),
+            header_str(headers, SIGNATURE_HEADER),
+        ) else {
+            return Err(ApiError::Unauthorized);
+        };
+
+        let hotkey = Hotkey::new(hotkey.to_string()).map_err(|_| ApiError::Unauthorized)?;
+        let timestamp: i64 = timestamp.parse().map_err(|_| ApiError::Unauthorized)?;
+        if (now - timestamp).abs() > MAX_CLOCK_SKEW_SECS {
+            return Err(ApiError::Unauthorized);
+        }
+        let payload = signing_payload(method, path_and_query, timestamp, body);
+        verify_bittensor_signature(&hotkey, signature, payload.as_bytes())
+            .map_err(|_| ApiError::Unauthorized)?;
+
+        if hotkey != self.validator_hotkey {
+            return Err(ApiError::Forbidden(format!(
+                "Hotkey {} may not modify this validator",
+                hotkey
+            )));
+        }
+
+        if !self
+            .replays
+            .insert(hotkey.as_str(), timestamp, &payload, now)
+        {
+            return Err(ApiError::Unauthorized);
+        }
+        Ok(())
+    }
+}
+
+/// Message a caller signs to authenticate a `method` request for
+/// `path_and_query` (the path plus any `?query`) carrying `body`
+///
+/// The body is covered by its hex-encoded SHA-256, so an empty body still
+/// contributes the digest of no bytes.
+pub fn signing_payload(
+    method: &Method,
+    path_and_query: &str,
+    timestamp: i64,
+    body: &[u8],
+) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        method,
+        path_and_query,
+        timestamp,
+        hex::encode(Sha256::digest(body))
+    )
+}
+
+/// Require credentials on every request that is not a read
+pub async fn require_auth_for_mutations(
+    State(auth): State<Arc<ApiAuth>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if is_read(request.method()) {
+        return Ok(next.run(request).await);
+    }
+
+    // The signature covers the body, so it is read here and handed on
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, auth.max_body_size)
+        .await
+        .map_err(|_| ApiError::BadRequest("Request body too large".to_string()))?;
+
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map_or_else(|| parts.uri.path(), |pq| pq.as_str());
+    let now = chrono::Utc::now().timestamp();
+    if let Err(e) = auth.authorize(&parts.method, path_and_query, &parts.headers, &body, now) {
+        warn!(
+            "Rejected unauthenticated {} {}",
+            parts.method,
+            parts.uri.path()
+        );
+        return Err(e);
+    }
+    Ok(next.run(Request::from_parts(parts, Body::from(body))).await)
+}
+
+fn is_read(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    header_str(headers, header::AUTHORIZATION.as_str())?.strip_prefix("Bearer ")
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::{get, post},
+        Router,
+    };
+    use basilica_common::crypto::wallet::{
+        generate_sr25519_wallet_from_mnemonic, sign_with_sr25519, sr25519_pair_from_mnemonic,
+    };
+    use tower::ServiceExt;
+
+    const VALIDATOR_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const OTHER_MNEMONIC: &str =
+        "legal winner thank year wave sausage worth useful legal winner thank yellow";
+    const API_KEY: &str = "validator-api-key";
+    const MAX_BODY_SIZE: usize = 1024 * 1024;
+    const BODY: &str = r#"{"executor_id":"executor-1"}"#;
+
+    fn hotkey(mnemonic: &str) -> Hotkey {
+        Hotkey::new(
+            generate_sr25519_wallet_from_mnemonic(mnemonic, 42)
+                .unwrap()
+                .address,
+        )
+        .unwrap()
+    }
+
+    fn app() -> Router {
+        let auth = Arc::new(ApiAuth::new(
+            Some(API_KEY.to_string()),
+            hotkey(VALIDATOR_MNEMONIC),
+            MAX_BODY_SIZE,
+        ));
+        Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .route("/rentals", post(|| async { "started" }))
+            .layer(middleware::from_fn_with_state(
+                auth,
+                require_auth_for_mutations,
+            ))
+    }
+
+    fn start_rental() -> axum::http::request::Builder {
+        Request::builder().method(Method::POST).uri("/rentals")
+    }
+
+    fn signed(mnemonic: &str, timestamp: i64) -> Request<Body> {
+        let pair = sr25519_pair_from_mnemonic(mnemonic).unwrap();
+        let payload = signing_payload(&Method::POST, "/rentals", timestamp, BODY.as_bytes());
+        start_rental()
+            .header(HOTKEY_HEADER, hotkey(mnemonic).as_str())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                sign_with_sr25519(&pair, payload.as_bytes()),
+            )
+            .body(Body::from(BODY))
+            .unwrap()
+    }
+
+    fn validator_auth() -> ApiAuth {
+        ApiAuth::new(None, hotkey(VALIDATOR_MNEMONIC), MAX_BODY_SIZE)
+    }
+
+    async fn status(request: Request<Body>) -> StatusCode {
+        app().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_unauthenticated_mutation_rejected() {
+        let request = start_rental().body(Body::empty()).unwrap();
+        assert_eq!(status(request).await, StatusCode::UNAUTHORIZED);
+
+        let request = start_rental()
+            .header(header::AUTHORIZATION, "Bearer wrong-key")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status(request).await, StatusCode::UNAUTHORIZED);
+
+        // Reads stay public
+        let request = Request::builder()
+            .uri("/health")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status(request).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_mutation_passes() {
+        let request = start_rental()
+            .header(header::AUTHORIZATION, format!("Bearer {}", API_KEY))
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(status(request).await, StatusCode::OK);
+
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(
+            status(signed(VALIDATOR_MNEMONIC, now)).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signature_checks() {
+        let now = chrono::Utc::now().timestamp();
+
+        // Valid signatures from other hotkeys are forbidden
+        assert_eq!(
+            status(signed(OTHER_MNEMONIC, now)).await,
+            StatusCode::FORBIDDEN
+        );
+        // Stale signatures cannot be replayed
+        assert_eq!(
+            status(signed(VALIDATOR_MNEMONIC, now - 3600)).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        // The signature covers the path, the query string and the body
+        let request = signed(VALIDATOR_MNEMONIC, now);
+        let headers = request.headers();
+        let body = BODY.as_bytes();
+        for (path, body) in [
+            ("/rentals/other", body),
+            ("/rentals?force=true", body),
+            ("/rentals", br#"{"executor_id":"executor-2"}"#.as_slice()),
+        ] {
+            assert!(matches!(
+                validator_auth().authorize(&Method::POST, path, headers, body, now),
+                Err(ApiError::Unauthorized)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replayed_signature_rejected() {
+        let now = chrono::Utc::now().timestamp();
+        let app = app();
+
+        let response = app
+            .clone()
+            .oneshot(signed(VALIDATOR_MNEMONIC, now))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(signed(VALIDATOR_MNEMONIC, now)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_reencoded_signature_is_a_replay() {
+        let now = chrono::Utc::now().timestamp();
+        let auth = validator_auth();
+        let request = signed(VALIDATOR_MNEMONIC, now);
+        let body = BODY.as_bytes();
+
+        assert!(auth
+            .authorize(&Method::POST, "/rentals", request.headers(), body, now)
+            .is_ok());
+
+        // Hex decoding ignores case, so the upper-cased signature still
+        // verifies but must not be accepted a second time
+        let mut headers = request.headers().clone();
+        let upper = headers[SIGNATURE_HEADER]
+            .to_str()
+            .unwrap()
+            .to_ascii_uppercase();
+        headers.insert(SIGNATURE_HEADER, upper.parse().unwrap());
+        assert!(matches!(
+            auth.authorize(&Method::POST, "/rentals", &headers, body, now),
+            Err(ApiError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn test_replay_cache_forgets_expired_entries() {
+        let cache = ReplayCache::default();
+        assert!(cache.insert("hotkey", 1_000, "payload", 1_000));
+        assert!(!cache.insert("hotkey", 1_000, "payload", 1_010));
+        assert!(cache.insert("hotkey", 1_001, "payload", 1_010));
+
+        cache.insert("hotkey", 2_000, "payload", 2_000);
+        assert_eq!(cache.seen.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_bearer_tokens_rejected_without_api_key() {
+        let auth = ApiAuth::new(
+            Some(String::new()),
+            hotkey(VALIDATOR_MNEMONIC),
+            MAX_BODY_SIZE,
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer ".parse().unwrap());
+        assert!(matches!(
+            auth.authorize(&Method::POST, "/rentals", &headers, b"", 0),
+            Err(ApiError::Unauthorized)
+        ));
+    }
+}
@@ -9,43 +9,160 @@ use anyhow::{Context, Result};
 use eventsource_stream::Eventsource;
 use futures::StreamExt;
 use futures_util::Stream;
-use reqwest::Client;
+use reqwest::{Client, Method, RequestBuilder};
 use std::{pin::Pin, time::Duration};
 
+/// Non-success response from the validator API
+///
+/// Keeps the validator's error code when the body carried one, so callers
+/// such as the gateway can pass the error on unchanged.
+#[derive(Debug, thiserror::Error)]
+#[error("{operation}: {status} - {body}")]
+pub struct ValidatorResponseError {
+    operation: &'static str,
+    pub status: reqwest::StatusCode,
+    /// Raw response body
+    pub body: String,
+    /// Structured error, when the body was an [`ErrorResponse`]
+    pub details: Option<ErrorDetails>,
+}
+
+impl ValidatorResponseError {
+    fn new(operation: &'static str, status: reqwest::StatusCode, body: String) -> Self {
+        let details = serde_json::from_str::<ErrorResponse>(&body)
+            .ok()
+            .map(|response| response.error);
+        Self {
+            operation,
+            status,
+            body,
+            details,
+        }
+    }
+
+    /// The validator's message, or the raw body when it sent none
+    pub fn message(&self) -> &str {
+        match &self.details {
+            Some(details) => &details.message,
+            None if self.body.is_empty() => {
+                self.status.canonical_reason().unwrap_or("Unknown error")
+            }
+            None => &self.body,
+        }
+    }
+}
+
 /// HTTP client for the Validator API
 #[derive(Clone, Debug)]
 pub struct ValidatorClient {
     base_url: String,
     http_client: Client,
+    /// Overall timeout for non-streaming requests
+    request_timeout: Option<Duration>,
+    /// How long a log stream may wait for its response to start
+    read_timeout: Option<Duration>,
+    /// Bearer token for routes that modify the validator
+    api_key: Option<String>,
 }
 
 impl ValidatorClient {
     /// Create a new ValidatorClient instance
+    ///
+    /// `timeout` bounds every non-streaming request; log streams are left
+    /// open for as long as the validator keeps them alive.
     pub fn new(base_url: impl Into<String>, timeout: Duration) -> Result<Self> {
         let http_client = Client::builder()
-            .timeout(timeout)
             .build()
             .context("Failed to build HTTP client")?;
 
         Ok(Self {
             base_url: base_url.into(),
             http_client,
+            request_timeout: Some(timeout),
+            read_timeout: None,
+            api_key: None,
+        })
+    }
+
+    /// Create a new ValidatorClient with separate connect, request and read
+    /// timeouts
+    ///
+    /// The request timeout does not apply to log streams, which are bounded
+    /// by the read timeout until the response starts instead.
+    pub fn with_timeouts(
+        base_url: impl Into<String>,
+        connect_timeout: Duration,
+        request_timeout: Duration,
+        read_timeout: Duration,
+    ) -> Result<Self> {
+        let http_client = Client::builder()
+            .connect_timeout(connect_timeout)
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            base_url: base_url.into(),
+            http_client,
+            request_timeout: Some(request_timeout),
+            read_timeout: Some(read_timeout),
+            api_key: None,
         })
     }
 
     /// Create a new ValidatorClient with a custom HTTP client
+    ///
+    /// Timeouts are left to the configuration of `http_client`.
     pub fn with_client(base_url: impl Into<String>, http_client: Client) -> Self {
         Self {
             base_url: base_url.into(),
             http_client,
+            request_timeout: None,
+            read_timeout: None,
+            api_key: None,
+        }
+    }
+
+    /// Authenticate requests with the validator's API key
+    ///
+    /// Starting and stopping rentals and changing miners require it.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Build a non-streaming request bounded by the request timeout
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let mut req = self.http_client.request(method, url);
+        if let Some(api_key) = &self.api_key {
+            req = req.bearer_auth(api_key);
+        }
+        match self.request_timeout {
+            Some(timeout) => req.timeout(timeout),
+            None => req,
         }
     }
 
     /// List rentals with optional state filter
     pub async fn list_rentals(&self, filter: Option<RentalState>) -> Result<ListRentalsResponse> {
+        // Callers filter the full history themselves
+        self.list(true, filter).await
+    }
+
+    /// List rentals that have not stopped or failed
+    pub async fn list_live_rentals(&self) -> Result<ListRentalsResponse> {
+        self.list(false, None).await
+    }
+
+    async fn list(
+        &self,
+        include_stopped: bool,
+        filter: Option<RentalState>,
+    ) -> Result<ListRentalsResponse> {
         let url = format!("{}/rentals", self.base_url);
 
-        let mut req = self.http_client.get(&url);
+        let mut req = self
+            .request(Method::GET, &url)
+            .query(&[("include_stopped", include_stopped.to_string())]);
         if let Some(state_filter) = filter {
             // Serialize the enum value as lowercase string for the query parameter
             let state_str = state_filter.to_string();
@@ -57,7 +174,9 @@ impl ValidatorClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to list rentals: {} - {}", status, error_body);
+            return Err(
+                ValidatorResponseError::new("Failed to list rentals", status, error_body).into(),
+            );
         }
 
         let json = response
@@ -76,8 +195,7 @@ impl ValidatorClient {
         let url = format!("{}/rentals", self.base_url);
 
         let response = self
-            .http_client
-            .post(&url)
+            .request(Method::POST, &url)
             .json(&request)
             .send()
             .await
@@ -86,7 +204,21 @@ impl ValidatorClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to start rental: {} - {}", status, error_body);
+            if status == reqwest::StatusCode::GATEWAY_TIMEOUT {
+                if let Ok(body) = serde_json::from_str::<
+                    crate::api::rental_routes::DeploymentTimeoutBody,
+                >(&error_body)
+                {
+                    return Err(crate::rental::DeploymentTimeoutError {
+                        phase: body.phase,
+                        timeout: Duration::from_secs(body.timeout_secs),
+                    }
+                    .into());
+                }
+            }
+            return Err(
+                ValidatorResponseError::new("Failed to start rental", status, error_body).into(),
+            );
         }
 
         response
@@ -100,8 +232,7 @@ impl ValidatorClient {
         let url = format!("{}/rentals/{}", self.base_url, rental_id);
 
         let response = self
-            .http_client
-            .get(&url)
+            .request(Method::GET, &url)
             .send()
             .await
             .context("Failed to send status request")?;
@@ -109,7 +240,12 @@ impl ValidatorClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to get rental status: {} - {}", status, error_body);
+            return Err(ValidatorResponseError::new(
+                "Failed to get rental status",
+                status,
+                error_body,
+            )
+            .into());
         }
 
         response
@@ -127,8 +263,7 @@ impl ValidatorClient {
         let url = format!("{}/rentals/{}", self.base_url, rental_id);
 
         let response = self
-            .http_client
-            .delete(&url)
+            .request(Method::DELETE, &url)
             .send()
             .await
             .context("Failed to send termination request")?;
@@ -138,7 +273,10 @@ impl ValidatorClient {
         } else {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to terminate rental: {} - {}", status, error_body)
+            Err(
+                ValidatorResponseError::new("Failed to terminate rental", status, error_body)
+                    .into(),
+            )
         }
     }
 
@@ -150,18 +288,21 @@ impl ValidatorClient {
     ) -> Result<Pin<Box<dyn Stream<Item = Result<Event>> + Send>>> {
         let url = format!("{}/rentals/{}/logs", self.base_url, rental_id);
 
-        let response = self
-            .http_client
-            .get(&url)
-            .query(&query)
-            .send()
-            .await
-            .context("Failed to send log request")?;
+        let send = self.http_client.get(&url).query(&query).send();
+        let response = match self.read_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, send)
+                .await
+                .context("Timed out waiting for log stream")?,
+            None => send.await,
+        }
+        .context("Failed to send log request")?;
 
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Failed to stream logs: {} - {}", status, error_body);
+            return Err(
+                ValidatorResponseError::new("Failed to stream logs", status, error_body).into(),
+            );
         }
 
         // Use eventsource-stream to parse SSE
@@ -198,7 +339,7 @@ impl ValidatorClient {
     ) -> Result<ListAvailableExecutorsResponse> {
         let url = format!("{}/executors", self.base_url);
 
-        let mut req = self.http_client.get(&url);
+        let mut req = self.request(Method::GET, &url);
 
         if let Some(query_params) = query {
             req = req.query(&query_params);
@@ -212,11 +353,12 @@ impl ValidatorClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Failed to list available executors: {} - {}",
+            return Err(ValidatorResponseError::new(
+                "Failed to list available executors",
                 status,
-                error_body
-            );
+                error_body,
+            )
+            .into());
         }
 
         let json = response
@@ -251,5 +393,19 @@ mod tests {
         let http_client = Client::new();
         let client = ValidatorClient::with_client("http://localhost:8080", http_client);
         assert_eq!(client.base_url, "http://localhost:8080");
+        assert!(client.request_timeout.is_none());
+    }
+
+    #[test]
+    fn test_client_with_timeouts() {
+        let client = ValidatorClient::with_timeouts(
+            "http://localhost:8080",
+            Duration::from_secs(5),
+            Duration::from_secs(30),
+            Duration::from_secs(60),
+        )
+        .unwrap();
+        assert_eq!(client.request_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(client.read_timeout, Some(Duration::from_secs(60)));
     }
 }
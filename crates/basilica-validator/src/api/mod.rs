@@ -6,6 +6,7 @@
 #[cfg(feature = "client")]
 pub mod client;
 
+pub mod auth;
 pub mod rental_routes;
 pub mod routes;
 pub mod types;
@@ -14,11 +15,16 @@ use crate::config::ApiConfig;
 use crate::rental;
 use anyhow::Result;
 use axum::{
+    http::HeaderName,
+    middleware,
     routing::{delete, get, post, put},
     Router,
 };
+use basilica_common::config::CorsConfig;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::net::TcpListener;
+use tokio::sync::RwLock;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::info;
 
@@ -35,8 +41,8 @@ pub struct ApiState {
     rental_manager: Option<Arc<rental::RentalManager>>,
     #[allow(dead_code)]
     miner_client: Option<Arc<crate::miner_prover::miner_client::MinerClient>>,
-    #[allow(dead_code)]
     validator_hotkey: basilica_common::identity::Hotkey,
+    capacity_cache: Arc<RwLock<Option<(Instant, types::CapacitySummaryResponse)>>>,
 }
 
 impl ApiState {
@@ -57,6 +63,7 @@ impl ApiState {
             rental_manager: None,
             miner_client: None,
             validator_hotkey,
+            capacity_cache: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -118,7 +125,7 @@ impl ApiHandler {
 
     /// Start the API server
     pub async fn start(&self) -> Result<()> {
-        let app = self.create_router();
+        let app = self.create_router()?;
 
         let listener = TcpListener::bind(&self.state.config.bind_address).await?;
         info!("API server listening on {}", self.state.config.bind_address);
@@ -130,14 +137,24 @@ impl ApiHandler {
 
     /// Create the Axum router with all endpoints
     /// Follows Open/Closed Principle - easy to extend with new routes
-    fn create_router(&self) -> Router {
-        Router::new()
+    ///
+    /// Every route that is not a read requires authentication; see [`auth`].
+    fn create_router(&self) -> Result<Router> {
+        let cors = cors_layer(&self.state.config.cors)?;
+        let auth = Arc::new(auth::ApiAuth::new(
+            self.state.config.api_key.clone(),
+            self.state.validator_hotkey.clone(),
+            self.state.config.max_body_size,
+        ));
+
+        Ok(Router::new()
             .route("/rentals", get(rental_routes::list_rentals))
             .route("/rentals", post(rental_routes::start_rental))
             .route("/rentals/:id", get(rental_routes::get_rental_status))
             .route("/rentals/:id", delete(rental_routes::stop_rental))
             .route("/rentals/:id/logs", get(rental_routes::stream_rental_logs))
             .route("/executors", get(routes::list_available_executors))
+            .route("/capacity/summary", get(routes::get_capacity_summary))
             // Existing miner routes
             .route("/miners", get(routes::list_miners))
             .route("/miners/register", post(routes::register_miner))
@@ -172,8 +189,118 @@ impl ApiHandler {
             .route("/config", get(routes::get_config))
             .route("/config/verification", get(routes::get_verification_config))
             .route("/config/emission", get(routes::get_emission_config))
+            .layer(middleware::from_fn_with_state(
+                auth,
+                auth::require_auth_for_mutations,
+            ))
             .layer(TraceLayer::new_for_http())
-            .layer(CorsLayer::permissive())
-            .with_state(self.state.clone())
+            .layer(cors)
+            .with_state(self.state.clone()))
+    }
+}
+
+/// Build the CORS layer from the configured allowlist
+///
+/// Browsers may also send the signed request headers mutations are
+/// authenticated with.
+fn cors_layer(config: &CorsConfig) -> Result<CorsLayer> {
+    config
+        .layer(
+            &[
+                HeaderName::from_static(auth::HOTKEY_HEADER),
+                HeaderName::from_static(auth::TIMESTAMP_HEADER),
+                HeaderName::from_static(auth::SIGNATURE_HEADER),
+            ],
+            &[],
+        )
+        .map_err(|e| anyhow::anyhow!(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Request},
+    };
+    use tower::ServiceExt;
+
+    fn app(allowed_origins: &[&str]) -> Router {
+        let config = CorsConfig {
+            allowed_origins: allowed_origins.iter().map(|o| o.to_string()).collect(),
+            allow_credentials: false,
+        };
+        Router::new()
+            .route("/rentals", get(|| async { "ok" }))
+            .layer(cors_layer(&config).unwrap())
+    }
+
+    fn request(origin: &str) -> Request<Body> {
+        Request::builder()
+            .uri("/rentals")
+            .header(header::ORIGIN, origin)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cors_headers_only_for_allowed_origins() {
+        let app = app(&["https://dashboard.example.com"]);
+
+        let allowed = app
+            .clone()
+            .oneshot(request("https://dashboard.example.com"))
+            .await
+            .unwrap();
+        assert_eq!(
+            allowed.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://dashboard.example.com"
+        );
+
+        let rejected = app
+            .oneshot(request("https://evil.example.com"))
+            .await
+            .unwrap();
+        assert!(rejected
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_preflight_allows_signed_request_headers() {
+        let response = app(&["https://dashboard.example.com"])
+            .oneshot(
+                Request::builder()
+                    .method("OPTIONS")
+                    .uri("/rentals")
+                    .header(header::ORIGIN, "https://dashboard.example.com")
+                    .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let allowed = response.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS]
+            .to_str()
+            .unwrap();
+        for name in [
+            auth::HOTKEY_HEADER,
+            auth::TIMESTAMP_HEADER,
+            auth::SIGNATURE_HEADER,
+            "content-type",
+        ] {
+            assert!(allowed.contains(name), "{name} missing from {allowed}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_cors_config_is_an_error() {
+        let config = CorsConfig {
+            allowed_origins: vec!["https://dashboard.example.com/app".to_string()],
+            allow_credentials: false,
+        };
+        assert!(cors_layer(&config).is_err());
     }
 }
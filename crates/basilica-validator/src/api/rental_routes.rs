@@ -12,12 +12,16 @@ use axum::{
 use basilica_common::utils::validate_docker_image;
 use futures::stream::Stream;
 use serde::Deserialize;
-use tracing::{error, info};
+use tracing::{error, info, instrument, warn};
 
 use crate::{
-    api::types::{ListRentalsResponse, RentalStatusResponse},
-    persistence::validator_persistence::ValidatorPersistence,
-    rental::{RentalInfo, RentalRequest, RentalState},
+    api::types::{
+        ErrorDetails, ErrorResponse, ListRentalsResponse, RentalStatusResponse,
+        EXECUTOR_BUSY_ERROR_CODE, EXECUTOR_DRAINING_ERROR_CODE, IDEMPOTENCY_KEY_IN_USE_ERROR_CODE,
+        INSUFFICIENT_COLLATERAL_ERROR_CODE, SECRET_ACCESS_DENIED_ERROR_CODE,
+    },
+    persistence::{validator_persistence::ValidatorPersistence, RentalHistoryFilter},
+    rental::{RentalRequest, RentalState},
 };
 use crate::{
     api::{types::RentalListItem, ApiState},
@@ -32,6 +36,16 @@ pub struct StartRentalRequest {
     pub ssh_public_key: String,
     #[serde(default)]
     pub environment: std::collections::HashMap<String, String>,
+    /// Environment variables set from secrets stored on the validator
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<crate::rental::SecretRef>,
+    /// User the rental is started for; `secrets` may only name secrets
+    /// stored for this user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secrets_owner: Option<String>,
+    /// Credentials for pulling `container_image` from a private registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_auth: Option<crate::rental::RegistryAuth>,
     #[serde(default)]
     pub ports: Vec<PortMappingRequest>,
     #[serde(default)]
@@ -42,6 +56,31 @@ pub struct StartRentalRequest {
     pub volumes: Vec<VolumeMountRequest>,
     #[serde(default)]
     pub no_ssh: bool,
+    /// Retrying with the same key returns the existing rental
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
+    /// Apply the validator's idle policy once the rental's GPUs have been
+    /// idle for this many seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_timeout_secs: Option<u64>,
+    /// When repeated health check failures mark the rental unhealthy, and
+    /// whether it is then terminated; the validator default applies when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_policy: Option<crate::rental::HealthEscalationPolicy>,
+    /// Command run inside the container before it is stopped, e.g. to save
+    /// a checkpoint
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_stop_command: Vec<String>,
+    /// How long the pre-stop command may run before the container is
+    /// stopped anyway; defaults to 30 seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_stop_timeout_secs: Option<u64>,
+    /// Docker runtime the container runs under; defaults to nvidia
+    #[serde(default)]
+    pub runtime: crate::rental::DockerRuntime,
+    /// Host GPU indices to expose; all GPUs reserved for the rental when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpu_device_ids: Vec<u32>,
 }
 
 fn default_command() -> Vec<String> {
@@ -52,14 +91,24 @@ impl Default for StartRentalRequest {
     fn default() -> Self {
         Self {
             executor_id: String::new(),
-            container_image: "nvidia/cuda:12.2.0-base-ubuntu22.04".to_string(),
+            container_image: basilica_common::DEFAULT_CONTAINER_IMAGE.to_string(),
             ssh_public_key: String::new(),
             environment: std::collections::HashMap::new(),
+            secrets: Vec::new(),
+            secrets_owner: None,
+            registry_auth: None,
             ports: Vec::new(),
             resources: ResourceRequirementsRequest::default(),
             command: default_command(),
             volumes: Vec::new(),
             no_ssh: false,
+            idempotency_key: None,
+            idle_timeout_secs: None,
+            health_policy: None,
+            pre_stop_command: Vec::new(),
+            pre_stop_timeout_secs: None,
+            runtime: Default::default(),
+            gpu_device_ids: Vec::new(),
         }
     }
 }
@@ -173,6 +222,8 @@ pub struct RentalStatusQuery {
 pub struct LogStreamQuery {
     pub follow: Option<bool>,
     pub tail: Option<u32>,
+    /// Only return entries at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// List rentals query parameters
@@ -186,8 +237,25 @@ pub struct ListRentalsQuery {
     pub gpu_type: Option<String>,
     pub min_gpu_count: Option<u32>,
     pub max_cost_per_hour: Option<f64>,
+    /// Include stopped and failed rentals
+    #[serde(default)]
+    pub include_stopped: bool,
+    pub executor_id: Option<String>,
+    /// Only rentals created at or after this time
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only rentals created before this time
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// 1-based page number; all matching rentals are returned when neither
+    /// `page` nor `page_size` is set
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
 }
 
+/// Largest page a rental listing may request
+const MAX_RENTALS_PAGE_SIZE: u32 = 500;
+/// Page size used when only `page` is given
+const DEFAULT_RENTALS_PAGE_SIZE: u32 = 50;
+
 /// Validate SSH public key
 fn is_valid_ssh_public_key(key: &str) -> bool {
     if key.trim().is_empty() {
@@ -209,10 +277,16 @@ fn is_valid_ssh_public_key(key: &str) -> bool {
 }
 
 /// Start a new rental
+///
+/// The `rental_id` span field is recorded once the rental manager assigns it.
+#[instrument(
+    skip_all,
+    fields(executor_id = %request.executor_id, rental_id = tracing::field::Empty)
+)]
 pub async fn start_rental(
     State(state): State<ApiState>,
     Json(request): Json<StartRentalRequest>,
-) -> Result<Json<RentalResponse>, StatusCode> {
+) -> Result<Json<RentalResponse>, axum::response::Response> {
     let miner_id = state
         .persistence
         .get_miner_id_by_executor(&request.executor_id)
@@ -222,7 +296,7 @@ pub async fn start_rental(
                 "Failed to get miner ID for executor {}: {}",
                 request.executor_id, e
             );
-            StatusCode::INTERNAL_SERVER_ERROR
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
         })?;
 
     let miner_data = state
@@ -231,11 +305,11 @@ pub async fn start_rental(
         .await
         .map_err(|e| {
             error!("Failed to look up miner: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
         })?
         .ok_or_else(|| {
             error!("Miner with ID {} not found", miner_id);
-            StatusCode::NOT_FOUND
+            StatusCode::NOT_FOUND.into_response()
         })?;
 
     info!(
@@ -245,22 +319,72 @@ pub async fn start_rental(
 
     if !is_valid_ssh_public_key(&request.ssh_public_key) {
         error!("Invalid SSH public key provided");
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into_response());
     }
 
     if let Err(e) = validate_docker_image(&request.container_image) {
         error!("Invalid container image provided: {}", e);
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    if request.idle_timeout_secs == Some(0) {
+        error!("Idle timeout must be greater than zero");
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    if let Some(Err(e)) = request.health_policy.as_ref().map(|p| p.validate()) {
+        error!("Invalid health policy: {}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    if let Some(Err(e)) = request
+        .registry_auth
+        .as_ref()
+        .map(|auth| auth.registry_for(&request.container_image))
+    {
+        error!("Invalid registry auth: {}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
     }
 
+    if let Err(e) = crate::rental::validate_environment(&request.environment, &request.secrets) {
+        error!("Invalid environment: {}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    if let Err(e) = crate::rental::validate_gpu_devices(
+        &request.gpu_device_ids,
+        request.resources.gpu_count,
+        &request.environment,
+    ) {
+        error!("Invalid GPU device selection: {}", e);
+        return Err(StatusCode::BAD_REQUEST.into_response());
+    }
+
+    let pre_stop = crate::rental::PreStopHook::from_request(
+        request.pre_stop_command,
+        request.pre_stop_timeout_secs,
+    )
+    .map_err(|e| {
+        error!("Invalid pre-stop hook: {}", e);
+        StatusCode::BAD_REQUEST.into_response()
+    })?;
+
     let rental_manager = state.rental_manager.as_ref().ok_or_else(|| {
         error!("Rental manager not initialized");
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
+    // Refuse secrets the user does not own before anything is reserved
+    if let Err(e) =
+        rental_manager.check_secret_access(request.secrets_owner.as_deref(), &request.secrets)
+    {
+        warn!("Rejected rental: {}", e);
+        return Err(secret_access_denied_response(&e));
+    }
+
     let miner_client = state.miner_client.as_ref().ok_or_else(|| {
         error!("Miner client not initialized");
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
     info!("Connecting to miner at endpoint: {}", miner_data.endpoint);
@@ -271,7 +395,7 @@ pub async fn start_rental(
         .await
         .map_err(|e| {
             error!("Failed to connect to miner: {}", e);
-            StatusCode::BAD_GATEWAY
+            StatusCode::BAD_GATEWAY.into_response()
         })?;
 
     // Filter out any user-specified SSH port mappings and prepare port list
@@ -316,9 +440,20 @@ pub async fn start_rental(
                 dns: Vec::new(),
                 extra_hosts: std::collections::HashMap::new(),
             },
+            pre_stop,
+            secrets: request.secrets,
+            secrets_owner: request.secrets_owner,
+            registry_auth: request.registry_auth,
+            runtime: request.runtime,
+            gpu_device_ids: request.gpu_device_ids,
         },
         ssh_public_key: request.ssh_public_key,
         metadata: std::collections::HashMap::new(),
+        idempotency_key: request.idempotency_key,
+        idle_timeout: request
+            .idle_timeout_secs
+            .map(std::time::Duration::from_secs),
+        health_policy: request.health_policy,
     };
 
     // Start rental
@@ -326,14 +461,89 @@ pub async fn start_rental(
         .start_rental(rental_request, &mut miner_connection)
         .await
         .map_err(|e| {
+            let rejection = if e.downcast_ref::<crate::rental::ExecutorBusy>().is_some() {
+                Some((EXECUTOR_BUSY_ERROR_CODE, false))
+            } else if e
+                .downcast_ref::<crate::rental::ExecutorDraining>()
+                .is_some()
+            {
+                Some((EXECUTOR_DRAINING_ERROR_CODE, false))
+            } else if e
+                .downcast_ref::<crate::collateral::InsufficientCollateral>()
+                .is_some()
+            {
+                Some((INSUFFICIENT_COLLATERAL_ERROR_CODE, false))
+            } else if e
+                .downcast_ref::<crate::rental::IdempotencyKeyInUse>()
+                .is_some()
+            {
+                Some((IDEMPOTENCY_KEY_IN_USE_ERROR_CODE, true))
+            } else {
+                None
+            };
+            if let Some((code, retryable)) = rejection {
+                warn!("Rejected rental: {}", e);
+                let body = ErrorResponse {
+                    error: ErrorDetails {
+                        code: code.to_string(),
+                        message: e.to_string(),
+                        retryable,
+                    },
+                };
+                return (StatusCode::CONFLICT, Json(body)).into_response();
+            }
+            if let Some(denied) = e.downcast_ref::<crate::rental::SecretAccessDenied>() {
+                warn!("Rejected rental: {}", denied);
+                return secret_access_denied_response(denied);
+            }
+            // Tell the caller which phase stalled so it can retry or pick another executor
+            if let Some(timeout) = e.downcast_ref::<crate::rental::DeploymentTimeoutError>() {
+                warn!("Rental deployment timed out: {}", timeout);
+                return deployment_timeout_response(timeout);
+            }
             error!("Failed to start rental: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
         })?;
 
+    tracing::Span::current().record("rental_id", rental_response.rental_id.as_str());
+
     Ok(Json(rental_response))
 }
 
+fn secret_access_denied_response(
+    denied: &crate::rental::SecretAccessDenied,
+) -> axum::response::Response {
+    let body = ErrorResponse {
+        error: ErrorDetails {
+            code: SECRET_ACCESS_DENIED_ERROR_CODE.to_string(),
+            message: denied.to_string(),
+            retryable: false,
+        },
+    };
+    (StatusCode::FORBIDDEN, Json(body)).into_response()
+}
+
+/// Body returned with 504 when a deployment times out
+#[derive(Debug, serde::Serialize, Deserialize)]
+pub struct DeploymentTimeoutBody {
+    pub error: String,
+    pub phase: crate::rental::DeployPhase,
+    pub timeout_secs: u64,
+}
+
+fn deployment_timeout_response(
+    timeout: &crate::rental::DeploymentTimeoutError,
+) -> axum::response::Response {
+    let body = DeploymentTimeoutBody {
+        error: timeout.to_string(),
+        phase: timeout.phase,
+        timeout_secs: timeout.timeout.as_secs(),
+    };
+    (StatusCode::GATEWAY_TIMEOUT, Json(body)).into_response()
+}
+
 /// Get rental status
+#[instrument(skip_all, fields(rental_id = %rental_id))]
 pub async fn get_rental_status(
     State(state): State<ApiState>,
     Path(rental_id): Path<String>,
@@ -377,19 +587,26 @@ pub async fn get_rental_status(
         rental_id: status.rental_id,
         status: match status.state {
             RentalState::Provisioning => ApiRentalStatus::Pending,
-            RentalState::Active => ApiRentalStatus::Active,
+            // The container still exists; health events carry the escalation
+            RentalState::Active | RentalState::Unhealthy => ApiRentalStatus::Active,
             RentalState::Stopping | RentalState::Stopped => ApiRentalStatus::Terminated,
             RentalState::Failed => ApiRentalStatus::Failed,
         },
         executor,
         created_at: status.created_at,
-        updated_at: status.created_at, // Use created_at for now
+        updated_at: status
+            .state_history
+            .last()
+            .map_or(status.created_at, |transition| transition.at),
+        gpu_usage: status.resource_usage.gpu_usage,
+        state_history: status.state_history,
     };
 
     Ok(Json(response))
 }
 
 /// Stop a rental
+#[instrument(skip_all, fields(rental_id = %rental_id))]
 pub async fn stop_rental(
     State(state): State<ApiState>,
     Path(rental_id): Path<String>,
@@ -413,6 +630,7 @@ pub async fn stop_rental(
 }
 
 /// Stream rental logs
+#[instrument(skip_all, fields(rental_id = %rental_id))]
 pub async fn stream_rental_logs(
     State(state): State<ApiState>,
     Path(rental_id): Path<String>,
@@ -429,7 +647,7 @@ pub async fn stream_rental_logs(
     let tail_lines = query.tail;
 
     let mut log_receiver = rental_manager
-        .stream_logs(&rental_id, follow, tail_lines)
+        .stream_logs(&rental_id, follow, tail_lines, query.since)
         .await
         .map_err(|e| {
             error!("Failed to stream logs: {}", e);
@@ -459,33 +677,44 @@ pub async fn list_rentals(
 ) -> Result<Json<ListRentalsResponse>, StatusCode> {
     info!("Listing rentals with filter: {:?}", query.state);
 
-    let validator_hotkey = state.validator_hotkey.to_string();
+    let states = match query.state {
+        Some(state_filter) => Some(vec![state_filter]),
+        None if query.include_stopped => None,
+        None => Some(vec![
+            RentalState::Provisioning,
+            RentalState::Active,
+            RentalState::Unhealthy,
+            RentalState::Stopping,
+        ]),
+    };
+    let filter = RentalHistoryFilter {
+        states,
+        executor_id: query.executor_id,
+        validator_hotkey: Some(state.validator_hotkey.to_string()),
+        created_after: query.since,
+        created_before: query.until,
+    };
 
-    // Get all rentals for this validator via rental manager
-    let rental_manager = state
-        .rental_manager
-        .as_ref()
-        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-    let rentals = rental_manager
-        .list_rentals(&validator_hotkey)
+    let paginated = query.page.is_some() || query.page_size.is_some();
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = match query.page_size {
+        Some(size) => size.clamp(1, MAX_RENTALS_PAGE_SIZE),
+        None if paginated => DEFAULT_RENTALS_PAGE_SIZE,
+        None => u32::MAX,
+    };
+
+    let rental_page = state
+        .persistence
+        .list_rentals(&filter, page, page_size)
         .await
         .map_err(|e| {
             error!("Failed to list rentals: {}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
-    // Filter by state if specified
-    let filtered_rentals: Vec<RentalInfo> = if let Some(state_filter) = query.state {
-        rentals
-            .into_iter()
-            .filter(|r| r.state == state_filter)
-            .collect()
-    } else {
-        rentals // No filter shows all rentals
-    };
-
     // Convert to API response format
-    let rental_list: Vec<RentalListItem> = filtered_rentals
+    let rental_list: Vec<RentalListItem> = rental_page
+        .rentals
         .iter()
         .map(|r| RentalListItem {
             rental_id: r.rental_id.clone(),
@@ -506,10 +735,56 @@ pub async fn list_rentals(
         })
         .collect();
 
-    let total_count = filtered_rentals.len();
-
     Ok(Json(ListRentalsResponse {
         rentals: rental_list,
-        total_count,
+        total_count: rental_page.total_count as usize,
+        page: paginated.then_some(page),
+        page_size: paginated.then_some(page_size),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ValidatorConfig;
+    use crate::persistence::{gpu_profile_repository::GpuProfileRepository, SimplePersistence};
+    use basilica_common::logging::capture::CapturedLogs;
+    use std::sync::Arc;
+
+    async fn test_state() -> ApiState {
+        let persistence = Arc::new(SimplePersistence::for_testing().await.unwrap());
+        let gpu_profile_repo = Arc::new(GpuProfileRepository::new(persistence.pool().clone()));
+        let config = ValidatorConfig::default();
+        ApiState::new(
+            config.api.clone(),
+            persistence,
+            gpu_profile_repo,
+            basilica_common::MemoryStorage::new().await.unwrap(),
+            config,
+            basilica_common::identity::Hotkey::new(
+                "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            )
+            .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rental_handler_logs_carry_rental_id() {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // No rental manager is configured, so the handler logs and fails
+        let result = stop_rental(State(test_state().await), Path("rental-abc".to_string())).await;
+        assert_eq!(result.err(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+
+        let output = logs.contents();
+        assert!(
+            output.contains("stop_rental{rental_id=rental-abc}"),
+            "missing rental_id span field in: {output}"
+        );
+    }
+}
@@ -2,13 +2,20 @@
 
 use crate::api::types::*;
 use crate::api::ApiState;
+use crate::persistence::ExecutorGpuInventory;
 use axum::{
     extract::{Query, State},
     http::Uri,
     Json,
 };
+use basilica_common::MigProfile;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 use tracing::{error, info};
 
+/// How long a computed capacity summary is served before recomputing
+pub const CAPACITY_SUMMARY_TTL: Duration = Duration::from_secs(15);
+
 /// List available executors for rental
 pub async fn list_available_executors(
     State(state): State<ApiState>,
@@ -22,6 +29,13 @@ pub async fn list_available_executors(
 
     info!("Listing executors with filters: {:?}", query);
 
+    let mig_profile = query
+        .mig_profile
+        .as_deref()
+        .map(str::parse::<MigProfile>)
+        .transpose()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
     // Get available executors from the database
     // Note: The persistence layer currently treats all queries as "available=true"
     // The 'available' parameter is handled by our endpoint logic above
@@ -32,6 +46,10 @@ pub async fn list_available_executors(
             query.gpu_type.clone(),
             query.min_gpu_count,
             query.location.clone(),
+            // A threshold too large to represent cannot exclude anything
+            query
+                .max_staleness
+                .and_then(|secs| chrono::Duration::from_std(Duration::from_secs(secs)).ok()),
         )
         .await
     {
@@ -39,6 +57,12 @@ pub async fn list_available_executors(
             let mut available_executors = Vec::new();
 
             for executor in executor_data {
+                if let Some(profile) = mig_profile {
+                    if !offers_mig_profile(&executor.gpu_specs, profile) {
+                        continue;
+                    }
+                }
+
                 // Convert to API response format
                 let network_speed =
                     if executor.download_mbps.is_some() || executor.upload_mbps.is_some() {
@@ -65,7 +89,11 @@ pub async fn list_available_executors(
                         available_until: None, // Could be calculated based on rental patterns
                         verification_score: executor.verification_score,
                         uptime_percentage: executor.uptime_percentage,
+                        as_of: executor.last_seen,
                     },
+                    // Priced by the API gateway from billing packages
+                    hourly_price: None,
+                    currency: None,
                 });
             }
 
@@ -82,3 +110,80 @@ pub async fn list_available_executors(
         }
     }
 }
+
+/// Whether any of `gpus` is a MIG slice with `profile`
+fn offers_mig_profile(gpus: &[GpuSpec], profile: MigProfile) -> bool {
+    gpus.iter().any(|gpu| {
+        gpu.mig_profile
+            .as_deref()
+            .and_then(|mig| mig.parse::<MigProfile>().ok())
+            == Some(profile)
+    })
+}
+
+/// Summarize GPU capacity by model across all known executors
+pub async fn get_capacity_summary(
+    State(state): State<ApiState>,
+) -> Result<Json<CapacitySummaryResponse>, ApiError> {
+    if let Some((computed_at, ref summary)) = *state.capacity_cache.read().await {
+        if computed_at.elapsed() < CAPACITY_SUMMARY_TTL {
+            return Ok(Json(summary.clone()));
+        }
+    }
+
+    let inventory = state.persistence.get_gpu_inventory().await.map_err(|e| {
+        error!("Failed to query GPU inventory: {}", e);
+        ApiError::InternalError("Failed to retrieve GPU capacity".to_string())
+    })?;
+
+    let summary = summarize_gpu_capacity(&inventory);
+    *state.capacity_cache.write().await = Some((Instant::now(), summary.clone()));
+
+    Ok(Json(summary))
+}
+
+/// Aggregate per-executor GPU inventory into per-model totals.
+///
+/// A rented executor reserves all of its GPUs; utilization is the share of
+/// GPUs reserved.
+pub fn summarize_gpu_capacity(inventory: &[ExecutorGpuInventory]) -> CapacitySummaryResponse {
+    let mut by_model: BTreeMap<&str, (u32, u32)> = BTreeMap::new();
+    for entry in inventory {
+        let (total, reserved) = by_model.entry(entry.gpu_name.as_str()).or_default();
+        *total += entry.gpu_count;
+        if entry.rented {
+            *reserved += entry.gpu_count;
+        }
+    }
+
+    let gpu_models: Vec<GpuModelCapacity> = by_model
+        .into_iter()
+        .map(|(model, (total, reserved))| GpuModelCapacity {
+            gpu_model: model.to_string(),
+            total,
+            reserved,
+            available: total - reserved,
+            mean_utilization_percent: utilization_percent(reserved, total),
+        })
+        .collect();
+
+    let total_gpus = gpu_models.iter().map(|m| m.total).sum();
+    let reserved_gpus = gpu_models.iter().map(|m| m.reserved).sum();
+
+    CapacitySummaryResponse {
+        gpu_models,
+        total_gpus,
+        reserved_gpus,
+        available_gpus: total_gpus - reserved_gpus,
+        mean_utilization_percent: utilization_percent(reserved_gpus, total_gpus),
+        generated_at: chrono::Utc::now(),
+    }
+}
+
+fn utilization_percent(reserved: u32, total: u32) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        reserved as f64 / total as f64 * 100.0
+    }
+}
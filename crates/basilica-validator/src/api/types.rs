@@ -2,7 +2,7 @@
 //!
 //! All request/response types, enums, and shared data structures for the validator API
 
-use crate::rental::RentalState;
+use crate::rental::{GpuUsage, RentalState, StateTransition};
 use basilica_common::LocationProfile;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -22,6 +22,9 @@ pub struct GpuRequirements {
     pub min_memory_gb: u32,
     pub gpu_type: Option<String>,
     pub gpu_count: u32,
+    /// MIG profile the GPUs must be sliced to, e.g. `1g.10gb`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mig_profile: Option<String>,
 }
 
 impl Default for GpuRequirements {
@@ -30,6 +33,7 @@ impl Default for GpuRequirements {
             min_memory_gb: 0,
             gpu_type: Some("b200".to_string()),
             gpu_count: 1,
+            mig_profile: None,
         }
     }
 }
@@ -64,6 +68,18 @@ pub struct GpuSpec {
     pub name: String,
     pub memory_gb: u32,
     pub compute_capability: String,
+    /// Device UUID reported by the executor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    /// PCI bus ID, for placing processes next to the device
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pci_bus_id: Option<String>,
+    /// NUMA node the device is attached to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub numa_node: Option<i32>,
+    /// MIG profile when the device is a MIG slice of a larger GPU
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mig_profile: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -94,6 +110,12 @@ pub struct RentalStatusResponse {
     pub executor: ExecutorDetails,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Per-GPU utilization sampled from the container when the status was requested
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpu_usage: Vec<GpuUsage>,
+    /// Every state change of the rental, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub state_history: Vec<StateTransition>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -116,6 +138,13 @@ pub struct ListAvailableExecutorsResponse {
 pub struct AvailableExecutor {
     pub executor: ExecutorDetails,
     pub availability: AvailabilityInfo,
+    /// Price per hour of renting the executor as a decimal string, when a
+    /// billing package covers its GPUs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hourly_price: Option<String>,
+    /// Currency `hourly_price` is quoted in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -123,6 +152,31 @@ pub struct AvailabilityInfo {
     pub available_until: Option<chrono::DateTime<chrono::Utc>>,
     pub verification_score: f64,
     pub uptime_percentage: f64,
+    /// When the executor's availability was last confirmed by the validator
+    #[serde(default)]
+    pub as_of: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// GPU capacity summarized by model across all known executors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacitySummaryResponse {
+    pub gpu_models: Vec<GpuModelCapacity>,
+    pub total_gpus: u32,
+    pub reserved_gpus: u32,
+    pub available_gpus: u32,
+    /// Mean utilization across all GPUs, counting rented GPUs as fully used
+    pub mean_utilization_percent: f64,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Capacity for a single GPU model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GpuModelCapacity {
+    pub gpu_model: String,
+    pub total: u32,
+    pub reserved: u32,
+    pub available: u32,
+    pub mean_utilization_percent: f64,
 }
 
 /// Query parameters for listing available executors
@@ -137,6 +191,12 @@ pub struct ListAvailableExecutorsQuery {
     pub gpu_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub min_gpu_count: Option<u32>,
+    /// Only executors offering MIG slices with this profile, e.g. `1g.10gb`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mig_profile: Option<String>,
+    /// Exclude executors whose availability data is older than this many seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_staleness: Option<u64>,
     /// Filter by location (city/region/country)
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub location: Option<LocationProfile>,
@@ -147,6 +207,9 @@ pub struct ListAvailableExecutorsQuery {
 pub struct LogQuery {
     pub follow: Option<bool>,
     pub tail: Option<u32>,
+    /// Only return entries at or after this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Miner registration request
@@ -325,7 +388,47 @@ pub struct RentalListItem {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ListRentalsResponse {
     pub rentals: Vec<RentalListItem>,
+    /// Rentals matching the filter across all pages
     pub total_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+}
+
+/// Error code sent when the executor already has a rental or one is being
+/// deployed onto it
+pub const EXECUTOR_BUSY_ERROR_CODE: &str = "BASILICA_VALIDATOR_EXECUTOR_BUSY";
+
+/// Error code sent when the executor is draining for maintenance and takes
+/// no new rentals
+pub const EXECUTOR_DRAINING_ERROR_CODE: &str = "BASILICA_VALIDATOR_EXECUTOR_DRAINING";
+
+/// Error code sent when the executor's collateral is below the validator's
+/// minimum
+pub const INSUFFICIENT_COLLATERAL_ERROR_CODE: &str = "BASILICA_VALIDATOR_INSUFFICIENT_COLLATERAL";
+
+/// Error code sent when a rental with the same idempotency key is still
+/// being deployed; retrying later returns that rental
+pub const IDEMPOTENCY_KEY_IN_USE_ERROR_CODE: &str = "BASILICA_VALIDATOR_IDEMPOTENCY_KEY_IN_USE";
+
+/// Error code sent when a rental references a secret the requesting user
+/// does not own
+pub const SECRET_ACCESS_DENIED_ERROR_CODE: &str = "BASILICA_VALIDATOR_SECRET_ACCESS_DENIED";
+
+/// Error body for failures with a stable error code, in the same shape the
+/// gateway uses so it can forward the error unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: ErrorDetails,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorDetails {
+    pub code: String,
+    pub message: String,
+    #[serde(default)]
+    pub retryable: bool,
 }
 
 /// API error type
@@ -334,6 +437,7 @@ pub enum ApiError {
     NotFound(String),
     BadRequest(String),
     Unauthorized,
+    Forbidden(String),
     InternalError(String),
 }
 
@@ -346,6 +450,7 @@ impl axum::response::IntoResponse for ApiError {
             ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized".to_string()),
+            ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
             ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
 
@@ -27,7 +27,7 @@ pub async fn create_rental_manager(
     persistence: Arc<crate::persistence::SimplePersistence>,
     bittensor_service: Arc<bittensor::Service>,
     metrics: Arc<crate::metrics::ValidatorPrometheusMetrics>,
-) -> Result<crate::rental::RentalManager> {
+) -> Result<Arc<crate::rental::RentalManager>> {
     use crate::miner_prover::miner_client::{
         BittensorServiceSigner, MinerClient, MinerClientConfig,
     };
@@ -57,7 +57,34 @@ pub async fn create_rental_manager(
     let ssh_key_manager = Arc::new(ssh_key_manager);
 
     // Create rental manager
-    let rental_manager = RentalManager::new(miner_client, persistence, ssh_key_manager, metrics);
+    let mut rental_manager = RentalManager::new(
+        miner_client,
+        persistence,
+        ssh_key_manager,
+        metrics,
+        config.rental_idle.clone(),
+        config.rental_health,
+        crate::rental::DeploymentConfig {
+            deploy_timeout: std::time::Duration::from_secs(config.rental_deploy_timeout_secs),
+            disk_headroom_gb: config.rental_disk_headroom_gb,
+            secret_store: crate::rental::RentalSecretStore::from_config(&config.rental_secrets)?,
+            image_policy: config.rental_image_policy.clone(),
+            image_cache: config.rental_image_cache.clone(),
+            ..Default::default()
+        },
+    );
+    if let Some(min_collateral) = config.rental_collateral.min_collateral_wei()? {
+        use crate::collateral::{CollateralGate, OnChainCollateral};
+        use collateral_contract::config::CollateralNetworkConfig;
+
+        let query = Arc::new(OnChainCollateral::new(CollateralNetworkConfig::default()));
+        rental_manager = rental_manager.with_collateral_gate(Arc::new(CollateralGate::new(
+            query,
+            min_collateral,
+            std::time::Duration::from_secs(config.rental_collateral.cache_ttl_secs),
+        )));
+    }
+    let rental_manager = Arc::new(rental_manager);
     rental_manager.start_monitor();
 
     // Initialize metrics for existing rentals
@@ -92,7 +119,12 @@ fn create_api_client(config: &ValidatorConfig, api_url: Option<String>) -> Resul
     });
 
     // Create client with 30 second timeout
-    ValidatorClient::new(base_url, Duration::from_secs(30)).context("Failed to create API client")
+    let client = ValidatorClient::new(base_url, Duration::from_secs(30))
+        .context("Failed to create API client")?;
+    Ok(match &config.api.api_key {
+        Some(api_key) => client.with_api_key(api_key),
+        None => client,
+    })
 }
 
 /// Handle rental commands via the Validator API
@@ -179,6 +211,9 @@ async fn handle_start_rental(
         container_image: image,
         ssh_public_key,
         environment,
+        secrets: Vec::new(),
+        secrets_owner: None,
+        registry_auth: None,
         ports: port_mappings,
         resources: ResourceRequirementsRequest {
             cpu_cores: cpu_cores.unwrap_or(0.0),
@@ -190,6 +225,13 @@ async fn handle_start_rental(
         command,
         volumes: Vec::new(),
         no_ssh: false,
+        idempotency_key: None,
+        idle_timeout_secs: None,
+        health_policy: None,
+        pre_stop_command: Vec::new(),
+        pre_stop_timeout_secs: None,
+        runtime: Default::default(),
+        gpu_device_ids: Vec::new(),
     };
 
     // Call API to start rental
@@ -263,6 +305,7 @@ async fn handle_rental_logs(
     let query = LogQuery {
         follow: Some(follow),
         tail,
+        since: None,
     };
 
     // Stream logs via API
@@ -325,6 +368,8 @@ async fn handle_ls_executors(
         min_gpu_memory: memory_min,
         gpu_type,
         min_gpu_count: gpu_min,
+        mig_profile: None,
+        max_staleness: None,
         location: None,
     };
 
@@ -409,6 +454,7 @@ async fn handle_ps_rentals(client: ValidatorClient, state_filter: String) -> Res
     // Parse state filter
     let filter = match state_filter.as_str() {
         "active" => Some(RentalState::Active),
+        "unhealthy" => Some(RentalState::Unhealthy),
         "stopped" => Some(RentalState::Stopped),
         _ => None, // "all" or any other value shows all rentals
     };
@@ -419,7 +419,7 @@ async fn start_validator_services(
     api_handler = api_handler.with_miner_client(Arc::new(miner_client));
 
     if let Some(rental_manager) = rental_manager {
-        api_handler = api_handler.with_rental_manager(Arc::new(rental_manager));
+        api_handler = api_handler.with_rental_manager(rental_manager);
     }
 
     // Store metrics for cleanup (if needed)
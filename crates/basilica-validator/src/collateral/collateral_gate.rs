@@ -0,0 +1,240 @@
+//! Minimum collateral check for rentals
+//!
+//! Before deploying a rental the validator can require that the executor's
+//! miner has posted at least a configured amount of collateral on chain, so
+//! there is slashable stake behind every rental. Queried amounts are cached
+//! briefly to keep the RPC off the hot path of every rental.
+
+use alloy_primitives::U256;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use basilica_common::identity::Hotkey;
+use collateral_contract::{amount::parse_amount, config::CollateralNetworkConfig, CollateralError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Collateral requirement for rentals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RentalCollateralConfig {
+    /// Minimum collateral per executor, e.g. `"10tao"` or an amount in wei.
+    /// Rentals are not checked when unset.
+    pub min_collateral: Option<String>,
+    /// Seconds a queried collateral amount is reused before asking the chain again
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for RentalCollateralConfig {
+    fn default() -> Self {
+        Self {
+            min_collateral: None,
+            cache_ttl_secs: 60,
+        }
+    }
+}
+
+impl RentalCollateralConfig {
+    /// Minimum collateral in wei, if a minimum is configured
+    pub fn min_collateral_wei(&self) -> Result<Option<U256>> {
+        self.min_collateral.as_deref().map(parse_amount).transpose()
+    }
+}
+
+/// Error returned when the executor's collateral is below the configured minimum
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Executor {executor_id} has {collateral} wei of collateral, below the required {required} wei"
+)]
+pub struct InsufficientCollateral {
+    pub executor_id: String,
+    pub collateral: U256,
+    pub required: U256,
+}
+
+/// Source of executor collateral amounts
+#[async_trait]
+pub trait CollateralQuery: Send + Sync {
+    /// Collateral posted for `executor_id` under the miner `hotkey`, in wei
+    async fn collateral(
+        &self,
+        hotkey: [u8; 32],
+        executor_id: [u8; 16],
+    ) -> Result<U256, CollateralError>;
+}
+
+/// Reads collateral from the collateral contract
+pub struct OnChainCollateral {
+    network: CollateralNetworkConfig,
+}
+
+impl OnChainCollateral {
+    pub fn new(network: CollateralNetworkConfig) -> Self {
+        Self { network }
+    }
+}
+
+#[async_trait]
+impl CollateralQuery for OnChainCollateral {
+    async fn collateral(
+        &self,
+        hotkey: [u8; 32],
+        executor_id: [u8; 16],
+    ) -> Result<U256, CollateralError> {
+        collateral_contract::collaterals(hotkey, executor_id, &self.network).await
+    }
+}
+
+/// Refuses rentals on executors without enough collateral
+pub struct CollateralGate {
+    query: Arc<dyn CollateralQuery>,
+    min_collateral: U256,
+    cache_ttl: Duration,
+    cache: Mutex<HashMap<(String, String), (U256, Instant)>>,
+}
+
+impl CollateralGate {
+    pub fn new(query: Arc<dyn CollateralQuery>, min_collateral: U256, cache_ttl: Duration) -> Self {
+        Self {
+            query,
+            min_collateral,
+            cache_ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check that `executor_id`, owned by the miner with SS58 `miner_hotkey`,
+    /// has at least the minimum collateral
+    ///
+    /// Fails with [`InsufficientCollateral`] when it does not.
+    pub async fn check(&self, miner_hotkey: &str, executor_id: &str) -> Result<()> {
+        let collateral = self.collateral(miner_hotkey, executor_id).await?;
+        if collateral < self.min_collateral {
+            return Err(InsufficientCollateral {
+                executor_id: executor_id.to_string(),
+                collateral,
+                required: self.min_collateral,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    async fn collateral(&self, miner_hotkey: &str, executor_id: &str) -> Result<U256> {
+        let key = (miner_hotkey.to_string(), executor_id.to_string());
+        if let Some((amount, fetched_at)) = self.cache.lock().unwrap().get(&key) {
+            if fetched_at.elapsed() < self.cache_ttl {
+                return Ok(*amount);
+            }
+        }
+
+        let hotkey = Hotkey::new(miner_hotkey.to_string())
+            .map_err(|e| anyhow!("Invalid miner hotkey {miner_hotkey}: {e}"))?;
+        let hotkey = bittensor::hotkey_to_account_id(&hotkey)?;
+        let amount = self
+            .query
+            .collateral(hotkey.0, executor_uuid(executor_id)?.into_bytes())
+            .await
+            .with_context(|| format!("Failed to query collateral for executor {executor_id}"))?;
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(key, (amount, Instant::now()));
+        Ok(amount)
+    }
+}
+
+/// Executor IDs are UUIDs, optionally prefixed with `miner_<uid>__`
+fn executor_uuid(executor_id: &str) -> Result<Uuid> {
+    let id = executor_id
+        .split_once("__")
+        .map_or(executor_id, |(_, id)| id);
+    Uuid::parse_str(id).with_context(|| format!("Executor ID {executor_id} is not a UUID"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const HOTKEY: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const EXECUTOR: &str = "6f1c1d9e-2b2a-4c7e-9d53-0a8f5c3b7e21";
+
+    struct FixedCollateral {
+        amount: U256,
+        queries: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CollateralQuery for FixedCollateral {
+        async fn collateral(
+            &self,
+            _hotkey: [u8; 32],
+            executor_id: [u8; 16],
+        ) -> Result<U256, CollateralError> {
+            assert_eq!(Uuid::from_bytes(executor_id).to_string(), EXECUTOR);
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            Ok(self.amount)
+        }
+    }
+
+    fn gate(amount: u64, cache_ttl: Duration) -> (CollateralGate, Arc<FixedCollateral>) {
+        let query = Arc::new(FixedCollateral {
+            amount: U256::from(amount),
+            queries: AtomicUsize::new(0),
+        });
+        let gate = CollateralGate::new(query.clone(), U256::from(100u64), cache_ttl);
+        (gate, query)
+    }
+
+    #[tokio::test]
+    async fn test_collateral_below_minimum_is_refused() {
+        let (gate, _) = gate(99, Duration::from_secs(60));
+        let err = gate.check(HOTKEY, EXECUTOR).await.unwrap_err();
+        let insufficient = err.downcast_ref::<InsufficientCollateral>().unwrap();
+        assert_eq!(insufficient.collateral, U256::from(99u64));
+        assert_eq!(insufficient.required, U256::from(100u64));
+    }
+
+    #[tokio::test]
+    async fn test_collateral_at_or_above_minimum_is_accepted() {
+        let (gate, query) = gate(100, Duration::from_secs(60));
+        gate.check(HOTKEY, EXECUTOR).await.unwrap();
+        gate.check(HOTKEY, &format!("miner_7__{EXECUTOR}"))
+            .await
+            .unwrap();
+        gate.check(HOTKEY, EXECUTOR).await.unwrap();
+
+        // The repeated check is served from the cache
+        assert_eq!(query.queries.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_cache_entries_are_refreshed() {
+        let (gate, query) = gate(150, Duration::ZERO);
+        gate.check(HOTKEY, EXECUTOR).await.unwrap();
+        gate.check(HOTKEY, EXECUTOR).await.unwrap();
+        assert_eq!(query.queries.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_min_collateral_parsing() {
+        let config = RentalCollateralConfig {
+            min_collateral: Some("1.5tao".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.min_collateral_wei().unwrap(),
+            Some(U256::from(1_500_000_000_000_000_000u64))
+        );
+        assert_eq!(
+            RentalCollateralConfig::default()
+                .min_collateral_wei()
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use crate::persistence::SimplePersistence;
 use anyhow::Result;
-use collateral_contract::{config::CollateralNetworkConfig, CollateralEvent};
+use collateral_contract::{config::CollateralNetworkConfig, CollateralError, CollateralEvent};
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub struct Collateral {
     config: crate::config::VerificationConfig,
@@ -29,7 +29,12 @@ impl Collateral {
             tokio::select! {
                 _ = interval.tick() => {
                     if let Err(e) = self.scan_handle_collateral_events().await {
-                        error!("Collateral event scan failed: {}", e);
+                        match e.downcast_ref::<CollateralError>() {
+                            Some(err) if err.is_retryable() => {
+                                warn!("Collateral event scan failed, retrying next interval: {}", err);
+                            }
+                            _ => error!("Collateral event scan failed: {}", e),
+                        }
                     }
                 }
             }
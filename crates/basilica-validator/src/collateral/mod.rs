@@ -1 +1,7 @@
+pub mod collateral_gate;
 pub mod collateral_scan;
+
+pub use collateral_gate::{
+    CollateralGate, CollateralQuery, InsufficientCollateral, OnChainCollateral,
+    RentalCollateralConfig,
+};
@@ -8,8 +8,8 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use basilica_common::config::{
-    loader, BittensorConfig, ConfigValidation, DatabaseConfig, LoggingConfig, MetricsConfig,
-    ServerConfig,
+    loader, BittensorConfig, ConfigValidation, CorsConfig, DatabaseConfig, LoggingConfig,
+    MetricsConfig, ServerConfig,
 };
 use basilica_common::error::ConfigurationError;
 
@@ -74,6 +74,40 @@ pub struct ValidatorConfig {
     /// Database cleanup configuration
     #[serde(default)]
     pub cleanup: crate::persistence::cleanup_task::CleanupConfig,
+
+    /// Policy for rentals created with an idle timeout
+    #[serde(default)]
+    pub rental_idle: crate::rental::IdlePolicyConfig,
+
+    /// Health check escalation for rentals that do not set their own policy
+    #[serde(default)]
+    pub rental_health: crate::rental::HealthEscalationPolicy,
+
+    /// Seconds a rental deployment may spend pulling, creating and starting
+    /// its container before it is aborted
+    #[serde(default = "default_rental_deploy_timeout_secs")]
+    pub rental_deploy_timeout_secs: u64,
+
+    /// Free disk space, in GB, an executor must keep beyond a rental's
+    /// requested storage for the deployment to go ahead
+    #[serde(default = "default_rental_disk_headroom_gb")]
+    pub rental_disk_headroom_gb: u64,
+
+    /// Minimum on-chain collateral an executor needs before it can be rented
+    #[serde(default)]
+    pub rental_collateral: crate::collateral::RentalCollateralConfig,
+
+    /// Encrypted secrets rentals may inject into their containers
+    #[serde(default)]
+    pub rental_secrets: crate::rental::RentalSecretsConfig,
+
+    /// Images rentals may run; the same allow/deny patterns executors apply
+    #[serde(default)]
+    pub rental_image_policy: basilica_common::config::ImagePolicyConfig,
+
+    /// Images pre-pulled on executors and digest pinning for rental images
+    #[serde(default)]
+    pub rental_image_cache: basilica_common::config::ImageCacheConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -162,6 +196,14 @@ fn default_enable_worker_queue() -> bool {
     false // Disabled by default until fully tested
 }
 
+fn default_rental_deploy_timeout_secs() -> u64 {
+    600
+}
+
+fn default_rental_disk_headroom_gb() -> u64 {
+    20
+}
+
 fn default_max_concurrent_full_validations() -> usize {
     1024 // Allow up to 1024 concurrent validation requests to the server
 }
@@ -472,7 +514,9 @@ pub struct StorageConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiConfig {
-    /// API key for external authentication
+    /// Bearer token accepted on routes that modify rentals or miners
+    ///
+    /// Without one, only requests signed by the validator hotkey may.
     pub api_key: Option<String>,
     /// Maximum request body size in bytes
     pub max_body_size: usize,
@@ -481,6 +525,9 @@ pub struct ApiConfig {
     /// Default port for miner connections
     #[serde(default = "default_miner_port")]
     pub miner_port: u16,
+    /// Browser origins allowed to call the API
+    #[serde(default)]
+    pub cors: CorsConfig,
 }
 
 fn default_miner_port() -> u16 {
@@ -646,10 +693,19 @@ impl Default for ValidatorConfig {
                 max_body_size: 1024 * 1024, // 1MB
                 bind_address: "0.0.0.0:8080".to_string(),
                 miner_port: default_miner_port(),
+                cors: CorsConfig::default(),
             },
             ssh_session: SshSessionConfig::default(),
             emission: super::emission::EmissionConfig::default(),
             cleanup: crate::persistence::cleanup_task::CleanupConfig::default(),
+            rental_idle: crate::rental::IdlePolicyConfig::default(),
+            rental_health: crate::rental::HealthEscalationPolicy::default(),
+            rental_deploy_timeout_secs: default_rental_deploy_timeout_secs(),
+            rental_disk_headroom_gb: default_rental_disk_headroom_gb(),
+            rental_collateral: crate::collateral::RentalCollateralConfig::default(),
+            rental_secrets: crate::rental::RentalSecretsConfig::default(),
+            rental_image_policy: basilica_common::config::ImagePolicyConfig::default(),
+            rental_image_cache: basilica_common::config::ImageCacheConfig::default(),
         }
     }
 }
@@ -715,6 +771,14 @@ impl ConfigValidation for ValidatorConfig {
             });
         }
 
+        if let Err(reason) = self.api.cors.validate() {
+            return Err(ConfigurationError::InvalidValue {
+                key: "api.cors.allowed_origins".to_string(),
+                value: self.api.cors.allowed_origins.join(","),
+                reason,
+            });
+        }
+
         // Validate emission configuration
         if let Err(e) = self.emission.validate() {
             return Err(ConfigurationError::InvalidValue {
@@ -724,6 +788,34 @@ impl ConfigValidation for ValidatorConfig {
             });
         }
 
+        if let Err(reason) = self.rental_health.validate() {
+            return Err(ConfigurationError::InvalidValue {
+                key: "rental_health".to_string(),
+                value: format!("{:?}", self.rental_health),
+                reason,
+            });
+        }
+
+        if self.rental_deploy_timeout_secs == 0 {
+            return Err(ConfigurationError::InvalidValue {
+                key: "rental_deploy_timeout_secs".to_string(),
+                value: "0".to_string(),
+                reason: "Deployment timeout must be greater than zero".to_string(),
+            });
+        }
+
+        if let Err(e) = self.rental_collateral.min_collateral_wei() {
+            return Err(ConfigurationError::InvalidValue {
+                key: "rental_collateral.min_collateral".to_string(),
+                value: self
+                    .rental_collateral
+                    .min_collateral
+                    .clone()
+                    .unwrap_or_default(),
+                reason: e.to_string(),
+            });
+        }
+
         Ok(())
     }
 
@@ -731,8 +823,10 @@ impl ConfigValidation for ValidatorConfig {
         let mut warnings = Vec::new();
 
         if self.api.api_key.is_none() {
-            warnings
-                .push("No API key configured - external API access will be disabled".to_string());
+            warnings.push(
+                "No API key configured - only requests signed by the validator hotkey can modify rentals and miners"
+                    .to_string(),
+            );
         }
 
         if self.verification.min_score_threshold < 0.1 {
@@ -8,6 +8,7 @@ use tokio::sync::RwLock;
 use tracing::{debug, error, info};
 
 use crate::persistence::SimplePersistence;
+use crate::rental::RentalStartPhase;
 
 /// Core Prometheus metrics collector for Validator
 pub struct ValidatorPrometheusMetrics {
@@ -166,6 +167,22 @@ impl ValidatorPrometheusMetrics {
             "basilica_validator_rentals_created_total",
             "Total number of rentals created"
         );
+        describe_counter!(
+            "basilica_validator_resource_enforcement_mismatches_total",
+            "Deployments rejected because container limits did not match the reservation"
+        );
+        describe_histogram!(
+            "basilica_validator_rental_start_duration_seconds",
+            "Time from rental request to SSH-ready container, by phase and in total"
+        );
+        describe_counter!(
+            "basilica_validator_rental_start_failures_total",
+            "Failed rental starts by the phase that failed"
+        );
+        describe_counter!(
+            "basilica_validator_scheduled_verifications_total",
+            "Scheduled miner verifications by validation type and result"
+        );
 
         Ok(Self {
             last_collection: Arc::new(RwLock::new(SystemTime::now())),
@@ -348,6 +365,50 @@ impl ValidatorPrometheusMetrics {
         .increment(1);
     }
 
+    /// Record a container whose applied limits did not match its reservation
+    pub fn record_resource_enforcement_mismatch(&self, executor_id: &str, resource: &str) {
+        counter!("basilica_validator_resource_enforcement_mismatches_total",
+            "executor_id" => executor_id.to_string(),
+            "resource" => resource.to_string()
+        )
+        .increment(1);
+    }
+
+    /// Record how long a successful rental start took, per phase and overall
+    pub fn record_rental_start_duration(
+        &self,
+        phases: &[(RentalStartPhase, Duration)],
+        total: Duration,
+    ) {
+        for (phase, duration) in phases {
+            histogram!("basilica_validator_rental_start_duration_seconds",
+                "phase" => phase.as_str()
+            )
+            .record(duration.as_secs_f64());
+        }
+        histogram!("basilica_validator_rental_start_duration_seconds",
+            "phase" => "total"
+        )
+        .record(total.as_secs_f64());
+    }
+
+    /// Record a rental start that failed during `stage`
+    pub fn record_rental_start_failure(&self, stage: RentalStartPhase) {
+        counter!("basilica_validator_rental_start_failures_total",
+            "stage" => stage.as_str()
+        )
+        .increment(1);
+    }
+
+    /// Record the result of a scheduled miner verification
+    pub fn record_scheduled_verification(&self, validation_type: &str, success: bool) {
+        counter!("basilica_validator_scheduled_verifications_total",
+            "validation_type" => validation_type.to_string(),
+            "result" => if success { "pass" } else { "fail" }
+        )
+        .increment(1);
+    }
+
     /// Collect system metrics periodically
     pub async fn collect_system_metrics(&self) {
         if let Err(e) = self.try_collect_system_metrics().await {
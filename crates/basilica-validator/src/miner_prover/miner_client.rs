@@ -107,6 +107,11 @@ impl MinerClient {
         }
     }
 
+    /// The validator's signer, if one was provided
+    pub fn signer(&self) -> Option<&dyn ValidatorSigner> {
+        self.signer.as_deref()
+    }
+
     /// Get the configured rental session duration
     pub fn get_rental_session_duration(&self) -> u64 {
         self.config.rental_session_duration
@@ -356,6 +361,15 @@ pub struct AuthenticatedMinerConnection {
 }
 
 impl AuthenticatedMinerConnection {
+    /// Connection over `channel` that skips the authentication handshake
+    #[cfg(test)]
+    pub(crate) fn unauthenticated(channel: Channel) -> Self {
+        Self {
+            client: MinerDiscoveryClient::new(channel),
+            session_token: String::new(),
+        }
+    }
+
     /// Request available executors from the miner
     pub async fn request_executors(
         &mut self,
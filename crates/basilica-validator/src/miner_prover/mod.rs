@@ -69,7 +69,7 @@ impl MinerProver {
                 ssh_session_config.clone(),
                 validator_hotkey,
                 persistence,
-                metrics,
+                metrics.clone(),
             )
             .with_bittensor_service(bittensor_service.clone())
             .with_ssh_client(Arc::new(ValidatorSshClient::new()));
@@ -87,7 +87,7 @@ impl MinerProver {
         })?;
 
         // Create scheduler with automatic verification configuration
-        let scheduler = VerificationScheduler::new(config.clone());
+        let scheduler = VerificationScheduler::new(config.clone()).with_metrics(metrics);
 
         Ok(Self {
             discovery,
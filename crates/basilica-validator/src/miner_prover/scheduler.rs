@@ -7,12 +7,15 @@ use super::discovery::MinerDiscovery;
 use super::types::{MinerInfo, ValidationType};
 use super::verification::VerificationEngine;
 use crate::config::VerificationConfig;
+use crate::metrics::{ValidatorMetrics, ValidatorPrometheusMetrics};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use futures::stream::{self, StreamExt};
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
 use tokio::task::JoinHandle;
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
@@ -24,14 +27,19 @@ struct SchedulerSharedState {
         Arc<RwLock<HashMap<Uuid, JoinHandle<Result<super::verification::VerificationResult>>>>>,
     active_full_tasks: Arc<RwLock<HashMap<Uuid, VerificationTask>>>,
     active_lightweight_tasks: Arc<RwLock<HashMap<Uuid, VerificationTask>>>,
+    /// Caps the verification workflows running at once across both loops
+    verification_permits: Arc<Semaphore>,
+    metrics: Option<Arc<ValidatorPrometheusMetrics>>,
 }
 
 impl SchedulerSharedState {
-    fn new() -> Self {
+    fn new(max_concurrent_verifications: usize) -> Self {
         Self {
             verification_handles: Arc::new(RwLock::new(HashMap::new())),
             active_full_tasks: Arc::new(RwLock::new(HashMap::new())),
             active_lightweight_tasks: Arc::new(RwLock::new(HashMap::new())),
+            verification_permits: Arc::new(Semaphore::new(max_concurrent_verifications.max(1))),
+            metrics: None,
         }
     }
 }
@@ -44,12 +52,19 @@ pub struct VerificationScheduler {
 impl VerificationScheduler {
     pub fn new(config: VerificationConfig) -> Self {
         info!("[EVAL_FLOW] Initializing VerificationScheduler");
+        let shared_state = SchedulerSharedState::new(config.max_concurrent_verifications);
         Self {
             config,
-            shared_state: SchedulerSharedState::new(),
+            shared_state,
         }
     }
 
+    /// Record pass and fail counts of scheduled verifications
+    pub fn with_metrics(mut self, metrics: Option<Arc<ValidatorMetrics>>) -> Self {
+        self.shared_state.metrics = metrics.map(|metrics| metrics.prometheus());
+        self
+    }
+
     /// Start the verification scheduling loop
     pub async fn start(
         self,
@@ -86,8 +101,10 @@ impl VerificationScheduler {
 
         info!("Starting verification scheduler");
         info!(
-            "Verification interval: {}s, Cleanup interval: {}s",
+            "Verification interval: {}s, Re-check interval: {}s, Max concurrent verifications: {}, Cleanup interval: {}s",
             config.verification_interval.as_secs(),
+            config.min_verification_interval.as_secs(),
+            config.max_concurrent_verifications,
             900
         );
 
@@ -197,6 +214,13 @@ async fn run_full_validation(
         .into_iter()
         .filter(|miner| can_schedule(shared_state, miner, ValidationType::Full))
         .collect();
+    let schedulable_miners = select_due_miners(
+        config,
+        verification,
+        schedulable_miners,
+        ValidationType::Full,
+    )
+    .await?;
 
     if schedulable_miners.is_empty() {
         return Ok(());
@@ -242,6 +266,13 @@ async fn run_lightweight_validation(
         .into_iter()
         .filter(|miner| can_schedule(shared_state, miner, ValidationType::Lightweight))
         .collect();
+    let schedulable_miners = select_due_miners(
+        config,
+        verification,
+        schedulable_miners,
+        ValidationType::Lightweight,
+    )
+    .await?;
 
     if schedulable_miners.is_empty() {
         return Ok(());
@@ -265,6 +296,60 @@ async fn run_lightweight_validation(
     Ok(())
 }
 
+/// Drop the miners verified with `strategy` more recently than the re-check interval
+async fn select_due_miners(
+    config: &VerificationConfig,
+    verification: &VerificationEngine,
+    miners: Vec<MinerInfo>,
+    strategy: ValidationType,
+) -> Result<Vec<MinerInfo>> {
+    let last_verified = verification
+        .persistence()
+        .get_last_verification_times(&strategy.to_string())
+        .await?;
+    let candidates = miners.len();
+    let due = miners_due_for_verification(
+        miners,
+        &last_verified,
+        config.min_verification_interval,
+        Utc::now(),
+    );
+
+    debug!(
+        intended_strategy = ?strategy,
+        "[EVAL_FLOW] {} of {} miners are due for {:?} validation",
+        due.len(),
+        candidates,
+        strategy
+    );
+
+    Ok(due)
+}
+
+/// Keep the miners never verified, or last verified at least `min_interval`
+/// before `now`, filling in their last verification time
+fn miners_due_for_verification(
+    miners: Vec<MinerInfo>,
+    last_verified: &HashMap<String, DateTime<Utc>>,
+    min_interval: Duration,
+    now: DateTime<Utc>,
+) -> Vec<MinerInfo> {
+    let min_interval = chrono::Duration::from_std(min_interval).unwrap_or(chrono::Duration::MAX);
+
+    miners
+        .into_iter()
+        .filter_map(|mut miner| {
+            miner.last_verified = last_verified
+                .get(&format!("miner_{}", miner.uid.as_u16()))
+                .copied();
+            match miner.last_verified {
+                Some(at) if now.signed_duration_since(at) < min_interval => None,
+                _ => Some(miner),
+            }
+        })
+        .collect()
+}
+
 async fn spawn_validation_pipeline(
     shared_state: &SchedulerSharedState,
     config: &VerificationConfig,
@@ -370,57 +455,45 @@ async fn spawn_verification_task(
         );
     }
 
-    let verification_handle = tokio::spawn(async move {
-        let workflow_start = std::time::Instant::now();
-        info!(
+    let miner_id = format!("miner_{}", task.miner_uid);
+    let validation_type = task.intended_validation_strategy.to_string();
+    if let Err(e) = verification_engine
+        .persistence()
+        .schedule_verification(&miner_id, &task_id.to_string(), &validation_type, None)
+        .await
+    {
+        warn!(
             miner_uid = task.miner_uid,
             task_id = %task_id,
-            miner_endpoint = %task.miner_endpoint,
-            verification_type = ?task.verification_type,
-            intended_strategy = ?task.intended_validation_strategy,
-            workflow_start = ?workflow_start,
-            has_worker_queue = has_worker_queue,
-            "[EVAL_FLOW] Starting verification workflow",
+            "[EVAL_FLOW] Failed to record scheduled verification: {}", e
         );
+    }
 
-        let result = verification_engine
-            .execute_verification_workflow(&task)
-            .await;
+    let verification_permits = shared_state.verification_permits.clone();
+    let metrics = shared_state.metrics.clone();
+    let verification_handle = tokio::spawn(async move {
+        let result = with_verification_permit(&verification_permits, async {
+            run_verification_workflow(&verification_engine, &task, task_id, has_worker_queue).await
+        })
+        .await;
 
-        match result {
-            Ok(verification_result) => {
-                info!(
-                    miner_uid = task.miner_uid,
-                    task_id = %task_id,
-                    "[EVAL_FLOW] Automated verification completed for miner {} in {:?}: score={:.2} (task: {})",
-                    task.miner_uid, workflow_start.elapsed(), verification_result.overall_score, task_id
-                );
-                debug!(
-                    miner_uid = task.miner_uid,
-                    task_id = %task_id,
-                    "[EVAL_FLOW] Verification steps completed: {}",
-                    verification_result.verification_steps.len()
-                );
-                for step in &verification_result.verification_steps {
-                    debug!(
-                        miner_uid = task.miner_uid,
-                        task_id = %task_id,
-                        "[EVAL_FLOW]   Step: {} - {:?} - {}",
-                        step.step_name, step.status, step.details
-                    );
-                }
-                Ok(verification_result)
-            }
-            Err(e) => {
-                error!(
-                    miner_uid = task.miner_uid,
-                    task_id = %task_id,
-                    "[EVAL_FLOW] Automated verification failed for miner {} after {:?} (task: {}): {}",
-                    task.miner_uid, workflow_start.elapsed(), task_id, e
-                );
-                Err(e)
-            }
+        let success = result.is_ok();
+        if let Err(e) = verification_engine
+            .persistence()
+            .complete_verification(&task_id.to_string(), success)
+            .await
+        {
+            warn!(
+                miner_uid = task.miner_uid,
+                task_id = %task_id,
+                "[EVAL_FLOW] Failed to record verification result: {}", e
+            );
+        }
+        if let Some(metrics) = &metrics {
+            metrics.record_scheduled_verification(&validation_type, success);
         }
+
+        result
     });
 
     {
@@ -440,6 +513,76 @@ async fn spawn_verification_task(
     Ok(())
 }
 
+/// Run `work` once one of `permits` is free
+async fn with_verification_permit<T>(
+    permits: &Semaphore,
+    work: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let _permit = permits
+        .acquire()
+        .await
+        .map_err(|e| anyhow::anyhow!("Verification permits closed: {}", e))?;
+    work.await
+}
+
+async fn run_verification_workflow(
+    verification_engine: &VerificationEngine,
+    task: &VerificationTask,
+    task_id: Uuid,
+    has_worker_queue: bool,
+) -> Result<super::verification::VerificationResult> {
+    let workflow_start = std::time::Instant::now();
+    info!(
+        miner_uid = task.miner_uid,
+        task_id = %task_id,
+        miner_endpoint = %task.miner_endpoint,
+        verification_type = ?task.verification_type,
+        intended_strategy = ?task.intended_validation_strategy,
+        workflow_start = ?workflow_start,
+        has_worker_queue = has_worker_queue,
+        "[EVAL_FLOW] Starting verification workflow",
+    );
+
+    let result = verification_engine
+        .execute_verification_workflow(task)
+        .await;
+
+    match result {
+        Ok(verification_result) => {
+            info!(
+                miner_uid = task.miner_uid,
+                task_id = %task_id,
+                "[EVAL_FLOW] Automated verification completed for miner {} in {:?}: score={:.2} (task: {})",
+                task.miner_uid, workflow_start.elapsed(), verification_result.overall_score, task_id
+            );
+            debug!(
+                miner_uid = task.miner_uid,
+                task_id = %task_id,
+                "[EVAL_FLOW] Verification steps completed: {}",
+                verification_result.verification_steps.len()
+            );
+            for step in &verification_result.verification_steps {
+                debug!(
+                    miner_uid = task.miner_uid,
+                    task_id = %task_id,
+                    "[EVAL_FLOW]   Step: {} - {:?} - {}",
+                    step.step_name, step.status, step.details
+                );
+            }
+            Ok(verification_result)
+        }
+        Err(e) => {
+            error!(
+                miner_uid = task.miner_uid,
+                task_id = %task_id,
+                "[EVAL_FLOW] Automated verification failed for miner {} after {:?} (task: {}): {}",
+                task.miner_uid, workflow_start.elapsed(), task_id, e
+            );
+            Err(e)
+        }
+    }
+}
+
 fn can_schedule(
     shared_state: &SchedulerSharedState,
     miner: &MinerInfo,
@@ -556,14 +699,88 @@ mod tests {
         }
     }
 
+    fn miner(uid: u16) -> MinerInfo {
+        MinerInfo {
+            uid: basilica_common::identity::MinerUid::new(uid),
+            hotkey: basilica_common::identity::Hotkey::new(
+                "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+            )
+            .unwrap(),
+            endpoint: format!("http://127.0.0.1:{}", 8090 + uid),
+            is_validator: false,
+            stake_tao: 0.0,
+            last_verified: None,
+            verification_score: 0.0,
+        }
+    }
+
     #[tokio::test]
     async fn test_scheduler_shared_state_initialization() {
-        let shared_state = SchedulerSharedState::new();
+        let shared_state = SchedulerSharedState::new(4);
 
         // Verify components are initialized
         assert_eq!(shared_state.verification_handles.read().await.len(), 0);
         assert_eq!(shared_state.active_full_tasks.read().await.len(), 0);
         assert_eq!(shared_state.active_lightweight_tasks.read().await.len(), 0);
+        assert_eq!(shared_state.verification_permits.available_permits(), 4);
+    }
+
+    #[test]
+    fn test_miners_past_recheck_interval_are_due() {
+        let now = Utc::now();
+        let last_verified = HashMap::from([
+            // Verified recently, not due yet
+            ("miner_1".to_string(), now - chrono::Duration::minutes(10)),
+            // Past the 30 minute re-check interval
+            ("miner_2".to_string(), now - chrono::Duration::minutes(45)),
+        ]);
+
+        let due = miners_due_for_verification(
+            vec![miner(1), miner(2), miner(3)],
+            &last_verified,
+            Duration::from_secs(1800),
+            now,
+        );
+
+        let due_uids: Vec<u16> = due.iter().map(|m| m.uid.as_u16()).collect();
+        assert_eq!(due_uids, vec![2, 3]);
+        assert_eq!(due[0].last_verified, Some(last_verified["miner_2"]));
+        // Never verified
+        assert_eq!(due[1].last_verified, None);
+    }
+
+    #[tokio::test]
+    async fn test_verification_permits_cap_concurrency() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let shared_state = SchedulerSharedState::new(3);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let permits = shared_state.verification_permits.clone();
+                let running = running.clone();
+                let peak = peak.clone();
+                tokio::spawn(async move {
+                    with_verification_permit(&permits, async {
+                        let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now_running, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    })
+                    .await
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(shared_state.verification_permits.available_permits(), 3);
     }
 
     #[tokio::test]
@@ -109,6 +109,23 @@ impl VerificationEngine {
                 Vec::new()
             });
 
+        // Draining executors are still verified, since they keep serving
+        // their rentals, but are no longer offered for new ones
+        let miner_id = format!("miner_{}", task.miner_uid);
+        for executor in &discovered_executors {
+            let executor_id = executor.id.to_string();
+            if let Err(e) = self
+                .persistence
+                .set_executor_draining(&miner_id, &executor_id, executor.status == "draining")
+                .await
+            {
+                warn!(
+                    "Failed to record drain state of executor {}: {}",
+                    executor_id, e
+                );
+            }
+        }
+
         let known_executor_data = self
             .persistence
             .get_known_executors_for_miner(task.miner_uid)
@@ -532,7 +549,12 @@ impl VerificationEngine {
                     id: ExecutorId::from_str(&details.executor_id).map_err(|e| {
                         anyhow::anyhow!("Invalid executor ID '{}': {}", details.executor_id, e)
                     })?,
-                    status: "available".to_string(),
+                    status: if details.status == "draining" {
+                        "draining"
+                    } else {
+                        "available"
+                    }
+                    .to_string(),
                     capabilities: vec!["gpu".to_string()],
                     grpc_endpoint: details.grpc_endpoint,
                 })
@@ -1906,6 +1928,11 @@ impl VerificationEngine {
         &self.bittensor_service
     }
 
+    /// Get persistence reference
+    pub fn persistence(&self) -> &Arc<SimplePersistence> {
+        &self.persistence
+    }
+
     /// Get SSH key path reference
     pub fn ssh_key_path(&self) -> &Option<PathBuf> {
         &self.ssh_key_path
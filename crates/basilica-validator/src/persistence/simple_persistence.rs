@@ -1,3 +1,4 @@
+use basilica_common::MigProfile;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
 use sqlx::{QueryBuilder, Row, SqlitePool};
@@ -6,12 +7,20 @@ use uuid::Uuid;
 
 use crate::persistence::entities::{Rental, RentalStatus, VerificationLog};
 use crate::persistence::ValidatorPersistence;
-use crate::rental::{RentalInfo, RentalState};
+use crate::rental::{
+    ExecutorReservation, IdempotencyClaim, RentalInfo, RentalResponse, RentalState, StateTransition,
+};
 
 /// Extract GPU memory size in GB from GPU name string
+///
+/// MIG slices are sized by their profile rather than their parent GPU.
 fn extract_gpu_memory_gb(gpu_name: &str) -> u32 {
     use regex::Regex;
 
+    if let Some(profile) = MigProfile::from_device_name(gpu_name) {
+        return profile.memory_gb;
+    }
+
     let re = Regex::new(r"(\d+)GB").unwrap();
     if let Some(captures) = re.captures(gpu_name) {
         captures[1].parse().unwrap_or(0)
@@ -29,6 +38,27 @@ pub struct RentalFilter {
     pub order_by_created_desc: bool,
 }
 
+/// Filter criteria for paginated rental history
+#[derive(Debug, Clone, Default)]
+pub struct RentalHistoryFilter {
+    /// Only rentals in one of these states; all states when unset
+    pub states: Option<Vec<RentalState>>,
+    pub executor_id: Option<String>,
+    pub validator_hotkey: Option<String>,
+    /// Only rentals created at or after this time
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only rentals created before this time
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+/// One page of rental history
+#[derive(Debug, Clone)]
+pub struct RentalPage {
+    pub rentals: Vec<RentalInfo>,
+    /// Number of rentals matching the filter across all pages
+    pub total_count: u64,
+}
+
 /// Simplified persistence implementation for quick testing
 #[derive(Debug, Clone)]
 pub struct SimplePersistence {
@@ -452,6 +482,36 @@ impl SimplePersistence {
         .execute(&self.pool)
         .await?;
 
+        // Executors held by an in-flight rental deployment
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS executor_reservations (
+                executor_id TEXT PRIMARY KEY,
+                rental_id TEXT NOT NULL,
+                reserved_at TEXT NOT NULL
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Idempotency keys of rental requests; the row is claimed before the
+        // rental is deployed and carries its response once it is saved
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS rental_idempotency_keys (
+                validator_hotkey TEXT NOT NULL,
+                idempotency_key TEXT NOT NULL,
+                rental_id TEXT NOT NULL,
+                rental_response TEXT,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (validator_hotkey, idempotency_key)
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
         // Check if gpu_memory_gb column exists in gpu_uuid_assignments
         let gpu_memory_gb_exists: bool = sqlx::query_scalar(
             r#"
@@ -488,6 +548,10 @@ impl SimplePersistence {
             CREATE INDEX IF NOT EXISTS idx_gpu_assignments_miner_executor ON gpu_uuid_assignments(miner_id, executor_id);
             CREATE INDEX IF NOT EXISTS idx_miner_executors_status ON miner_executors(status);
             CREATE INDEX IF NOT EXISTS idx_miner_executors_health_check ON miner_executors(last_health_check);
+            CREATE INDEX IF NOT EXISTS idx_rentals_state ON rentals(state);
+            CREATE INDEX IF NOT EXISTS idx_rentals_executor ON rentals(executor_id);
+            CREATE INDEX IF NOT EXISTS idx_rentals_validator_created ON rentals(validator_hotkey, created_at);
+            CREATE INDEX IF NOT EXISTS idx_rentals_created ON rentals(created_at);
             "#,
         )
         .execute(&self.pool)
@@ -518,6 +582,129 @@ impl SimplePersistence {
             info!("Added miner_id column to rentals table");
         }
 
+        let idempotency_key_exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) > 0
+            FROM pragma_table_info('rentals')
+            WHERE name = 'idempotency_key'
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or(false);
+
+        if !idempotency_key_exists {
+            sqlx::query(
+                r#"
+                ALTER TABLE rentals ADD COLUMN idempotency_key TEXT;
+                ALTER TABLE rentals ADD COLUMN rental_response TEXT;
+                "#,
+            )
+            .execute(&self.pool)
+            .await?;
+
+            info!("Added idempotency_key and rental_response columns to rentals table");
+        }
+
+        let idle_timeout_exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) > 0
+            FROM pragma_table_info('rentals')
+            WHERE name = 'idle_timeout_secs'
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or(false);
+
+        if !idle_timeout_exists {
+            sqlx::query("ALTER TABLE rentals ADD COLUMN idle_timeout_secs INTEGER;")
+                .execute(&self.pool)
+                .await?;
+
+            info!("Added idle_timeout_secs column to rentals table");
+        }
+
+        let health_policy_exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) > 0
+            FROM pragma_table_info('rentals')
+            WHERE name = 'health_policy'
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or(false);
+
+        if !health_policy_exists {
+            sqlx::query("ALTER TABLE rentals ADD COLUMN health_policy TEXT;")
+                .execute(&self.pool)
+                .await?;
+
+            info!("Added health_policy column to rentals table");
+        }
+
+        let draining_exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) > 0
+            FROM pragma_table_info('miner_executors')
+            WHERE name = 'draining'
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or(false);
+
+        if !draining_exists {
+            sqlx::query(
+                "ALTER TABLE miner_executors ADD COLUMN draining INTEGER NOT NULL DEFAULT 0;",
+            )
+            .execute(&self.pool)
+            .await?;
+
+            info!("Added draining column to miner_executors table");
+        }
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS rental_state_transitions (
+                rental_id TEXT NOT NULL,
+                seq INTEGER NOT NULL,
+                from_state TEXT NOT NULL,
+                to_state TEXT NOT NULL,
+                transitioned_at TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                PRIMARY KEY (rental_id, seq)
+            );
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_rentals_idempotency_key
+            ON rentals(validator_hotkey, idempotency_key)
+            WHERE idempotency_key IS NOT NULL;
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // Keys recorded on the rentals table before they had their own table
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO rental_idempotency_keys (
+                validator_hotkey, idempotency_key, rental_id, rental_response, created_at
+            )
+            SELECT validator_hotkey, idempotency_key, id, rental_response, created_at
+            FROM rentals
+            WHERE idempotency_key IS NOT NULL AND rental_response IS NOT NULL;
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
         self.create_collateral_scanned_blocks_table().await?;
         self.add_binary_validation_columns().await?;
 
@@ -729,19 +916,21 @@ impl SimplePersistence {
         gpu_type: Option<String>,
         min_gpu_count: Option<u32>,
         location: Option<basilica_common::LocationProfile>,
+        max_staleness: Option<chrono::Duration>,
     ) -> Result<Vec<AvailableExecutorData>, anyhow::Error> {
         // Build the base query with LEFT JOIN to find executors without active rentals
         // Also join with gpu_uuid_assignments to get actual GPU data
         // And join with hardware profile to get CPU/RAM information
         // And join with network profile to get location information
         // And join with speedtest profile to get network speed information
-        let mut query_str = String::from(
+        let mut query_str = format!(
             "SELECT
                 me.executor_id,
                 me.miner_id,
                 me.location,
                 me.status,
                 me.gpu_count,
+                COALESCE(me.last_health_check, me.updated_at) as last_seen,
                 m.verification_score,
                 m.uptime_percentage,
                 GROUP_CONCAT(gua.gpu_name) as gpu_names,
@@ -758,13 +947,14 @@ impl SimplePersistence {
             JOIN miners m ON me.miner_id = m.id
             LEFT JOIN rentals r ON me.executor_id = r.executor_id
                 AND r.miner_id = me.miner_id
-                AND r.state IN ('Active', 'Provisioning', 'active', 'provisioning')
+                AND r.state IN ({BUSY_RENTAL_STATES})
             LEFT JOIN gpu_uuid_assignments gua ON me.executor_id = gua.executor_id AND gua.miner_id = me.miner_id
             LEFT JOIN executor_hardware_profile ehp ON me.executor_id = ehp.executor_id AND me.miner_id = 'miner_' || ehp.miner_uid
             LEFT JOIN executor_network_profile enp ON me.executor_id = enp.executor_id AND me.miner_id = 'miner_' || enp.miner_uid
             LEFT JOIN executor_speedtest_profile esp ON me.executor_id = esp.executor_id AND me.miner_id = 'miner_' || esp.miner_uid
             WHERE r.id IS NULL
-                AND (me.status IS NULL OR me.status != 'offline')",
+                AND (me.status IS NULL OR me.status != 'offline')
+                AND me.draining = 0",
         );
 
         // Add location filters if specified (case-insensitive comparison)
@@ -788,9 +978,24 @@ impl SimplePersistence {
         }
 
         let rows = sqlx::query(&query_str).fetch_all(&self.pool).await?;
+        let stale_before =
+            max_staleness.and_then(|staleness| Utc::now().checked_sub_signed(staleness));
 
         let mut executors = Vec::new();
         for row in rows {
+            // Timestamps are written both as RFC 3339 and by SQLite's datetime()
+            let last_seen = row
+                .get::<Option<String>, _>("last_seen")
+                .and_then(|ts| parse_db_timestamp(&ts));
+
+            // Skip executors whose availability data is older than allowed;
+            // an executor never seen cannot be shown to be fresh
+            if let Some(stale_before) = stale_before {
+                if last_seen.map_or(true, |seen| seen < stale_before) {
+                    continue;
+                }
+            }
+
             // Get GPU data from gpu_uuid_assignments join
             let gpu_names: Option<String> = row.get("gpu_names");
 
@@ -808,6 +1013,11 @@ impl SimplePersistence {
                             name: gpu_name.to_string(),
                             memory_gb,
                             compute_capability: "8.0".to_string(), // Default, could be parsed from prover results
+                            uuid: None,
+                            pci_bus_id: None,
+                            numa_node: None,
+                            mig_profile: MigProfile::from_device_name(gpu_name)
+                                .map(|profile| profile.to_string()),
                         });
                     }
                 }
@@ -876,12 +1086,258 @@ impl SimplePersistence {
                 download_mbps,
                 upload_mbps,
                 speed_test_timestamp,
+                last_seen,
             });
         }
 
         Ok(executors)
     }
 
+    /// Get GPU inventory per executor and model for online executors,
+    /// flagging executors that currently have an active rental
+    pub async fn get_gpu_inventory(&self) -> Result<Vec<ExecutorGpuInventory>, anyhow::Error> {
+        let rows = sqlx::query(&format!(
+            "SELECT
+                gua.executor_id,
+                gua.gpu_name,
+                COUNT(*) as gpu_count,
+                EXISTS (
+                    SELECT 1 FROM rentals r
+                    WHERE r.executor_id = gua.executor_id
+                        AND r.miner_id = gua.miner_id
+                        AND r.state IN ({BUSY_RENTAL_STATES})
+                ) as rented
+            FROM gpu_uuid_assignments gua
+            JOIN miner_executors me ON me.executor_id = gua.executor_id AND me.miner_id = gua.miner_id
+            WHERE gua.gpu_name IS NOT NULL
+                AND (me.status IS NULL OR me.status != 'offline')
+            GROUP BY gua.miner_id, gua.executor_id, gua.gpu_name",
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ExecutorGpuInventory {
+                executor_id: row.get("executor_id"),
+                gpu_name: row.get("gpu_name"),
+                gpu_count: row.get::<i64, _>("gpu_count") as u32,
+                rented: row.get::<i64, _>("rented") != 0,
+            })
+            .collect())
+    }
+
+    /// Reserve an executor for a rental that is about to be deployed.
+    ///
+    /// Returns [`ExecutorReservation::Draining`] if the executor is
+    /// draining, and [`ExecutorReservation::Busy`] if it already has a
+    /// non-terminal rental or is reserved by another deployment. The checks
+    /// and insert happen in a single statement, so concurrent callers cannot
+    /// both succeed and a drain cannot slip in between check and insert.
+    /// Reservations older than `stale_after` are assumed abandoned by a
+    /// crashed deployment and are replaced.
+    pub async fn reserve_executor(
+        &self,
+        executor_id: &str,
+        rental_id: &str,
+        stale_after: chrono::Duration,
+    ) -> Result<ExecutorReservation, anyhow::Error> {
+        let now = Utc::now();
+
+        sqlx::query("DELETE FROM executor_reservations WHERE executor_id = ? AND reserved_at < ?")
+            .bind(executor_id)
+            .bind((now - stale_after).to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+
+        let result = sqlx::query(&format!(
+            "INSERT INTO executor_reservations (executor_id, rental_id, reserved_at)
+            SELECT ?, ?, ?
+            WHERE NOT EXISTS (
+                SELECT 1 FROM rentals
+                WHERE executor_id = ?
+                    AND state IN ({BUSY_RENTAL_STATES})
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM miner_executors
+                WHERE executor_id = ? AND draining != 0
+            )
+            ON CONFLICT(executor_id) DO NOTHING",
+        ))
+        .bind(executor_id)
+        .bind(rental_id)
+        .bind(now.to_rfc3339())
+        .bind(executor_id)
+        .bind(executor_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 1 {
+            return Ok(ExecutorReservation::Reserved);
+        }
+
+        let draining: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+                SELECT 1 FROM miner_executors WHERE executor_id = ? AND draining != 0
+            )",
+        )
+        .bind(executor_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(if draining {
+            ExecutorReservation::Draining
+        } else {
+            ExecutorReservation::Busy
+        })
+    }
+
+    /// Claim `idempotency_key` for the rental `rental_id` before it is
+    /// deployed.
+    ///
+    /// The insert is guarded by the key's primary key, so of several
+    /// concurrent requests with the same key exactly one gets
+    /// [`IdempotencyClaim::Claimed`]. The others see the finished rental or
+    /// learn that it is still being deployed. Claims that never got a
+    /// response within `stale_after` are assumed abandoned by a crashed
+    /// deployment and are replaced.
+    pub async fn claim_rental_idempotency_key(
+        &self,
+        validator_hotkey: &str,
+        idempotency_key: &str,
+        rental_id: &str,
+        stale_after: chrono::Duration,
+    ) -> Result<IdempotencyClaim, anyhow::Error> {
+        let now = Utc::now();
+
+        sqlx::query(
+            "DELETE FROM rental_idempotency_keys
+            WHERE validator_hotkey = ? AND idempotency_key = ?
+                AND rental_response IS NULL AND created_at < ?",
+        )
+        .bind(validator_hotkey)
+        .bind(idempotency_key)
+        .bind((now - stale_after).to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        let result = sqlx::query(
+            "INSERT INTO rental_idempotency_keys (
+                validator_hotkey, idempotency_key, rental_id, created_at
+            ) VALUES (?, ?, ?, ?)
+            ON CONFLICT(validator_hotkey, idempotency_key) DO NOTHING",
+        )
+        .bind(validator_hotkey)
+        .bind(idempotency_key)
+        .bind(rental_id)
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 1 {
+            return Ok(IdempotencyClaim::Claimed);
+        }
+
+        Ok(
+            match self
+                .find_rental_by_idempotency_key(validator_hotkey, idempotency_key)
+                .await?
+            {
+                Some(response) => IdempotencyClaim::Completed(response),
+                None => IdempotencyClaim::InProgress,
+            },
+        )
+    }
+
+    /// Response of the rental started with `idempotency_key`, if it has
+    /// finished deploying
+    pub async fn find_rental_by_idempotency_key(
+        &self,
+        validator_hotkey: &str,
+        idempotency_key: &str,
+    ) -> Result<Option<RentalResponse>, anyhow::Error> {
+        let response: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT rental_response FROM rental_idempotency_keys
+            WHERE validator_hotkey = ? AND idempotency_key = ?",
+        )
+        .bind(validator_hotkey)
+        .bind(idempotency_key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        response
+            .flatten()
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Record the response of the rental that claimed `idempotency_key`.
+    /// Fails if the key is not claimed by this rental.
+    pub async fn save_rental_idempotency_key(
+        &self,
+        validator_hotkey: &str,
+        idempotency_key: &str,
+        response: &RentalResponse,
+    ) -> Result<(), anyhow::Error> {
+        let result = sqlx::query(
+            "UPDATE rental_idempotency_keys SET rental_response = ?
+            WHERE validator_hotkey = ? AND idempotency_key = ? AND rental_id = ?",
+        )
+        .bind(serde_json::to_string(response)?)
+        .bind(validator_hotkey)
+        .bind(idempotency_key)
+        .bind(&response.rental_id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            anyhow::bail!(
+                "Idempotency key {} is not claimed by rental {}",
+                idempotency_key,
+                response.rental_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Release the claim `rental_id` holds on `idempotency_key` after its
+    /// deployment failed, so the request can be retried
+    pub async fn release_rental_idempotency_key(
+        &self,
+        validator_hotkey: &str,
+        idempotency_key: &str,
+        rental_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            "DELETE FROM rental_idempotency_keys
+            WHERE validator_hotkey = ? AND idempotency_key = ? AND rental_id = ?
+                AND rental_response IS NULL",
+        )
+        .bind(validator_hotkey)
+        .bind(idempotency_key)
+        .bind(rental_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Release an executor reservation held by `rental_id`
+    pub async fn release_executor_reservation(
+        &self,
+        executor_id: &str,
+        rental_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query("DELETE FROM executor_reservations WHERE executor_id = ? AND rental_id = ?")
+            .bind(executor_id)
+            .bind(rental_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
     /// Helper function to convert database row to VerificationLog
     fn row_to_verification_log(
         &self,
@@ -1012,21 +1468,21 @@ impl SimplePersistence {
         Ok(())
     }
 
-    /// Check if an executor has an active rental
+    /// Check if an executor has a rental that still occupies it
     pub async fn has_active_rental(
         &self,
         executor_id: &str,
         miner_id: &str,
     ) -> Result<bool, anyhow::Error> {
-        let query = r#"
-            SELECT COUNT(*) as count
+        let query = format!(
+            "SELECT COUNT(*) as count
             FROM rentals
             WHERE executor_id = ?
                 AND miner_id = ?
-                AND state = 'active'
-        "#;
+                AND state IN ({BUSY_RENTAL_STATES})"
+        );
 
-        let row = sqlx::query(query)
+        let row = sqlx::query(&query)
             .bind(executor_id)
             .bind(miner_id)
             .fetch_one(&self.pool)
@@ -1041,6 +1497,7 @@ impl SimplePersistence {
         match state_str {
             "provisioning" => RentalState::Provisioning,
             "active" => RentalState::Active,
+            "unhealthy" => RentalState::Unhealthy,
             "stopping" => RentalState::Stopping,
             "stopped" => RentalState::Stopped,
             "failed" => RentalState::Failed,
@@ -1081,9 +1538,50 @@ impl SimplePersistence {
             container_spec: serde_json::from_str(&container_spec_str)?,
             miner_id: row.get::<String, _>("miner_id"),
             executor_details,
+            idle_timeout: row
+                .try_get::<Option<i64>, _>("idle_timeout_secs")
+                .ok()
+                .flatten()
+                .map(|secs| std::time::Duration::from_secs(secs as u64)),
+            health_policy: row
+                .try_get::<Option<String>, _>("health_policy")
+                .ok()
+                .flatten()
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            state_history: Vec::new(),
         })
     }
 
+    /// State transitions recorded for a rental, oldest first
+    async fn load_state_history(
+        &self,
+        rental_id: &str,
+    ) -> Result<Vec<StateTransition>, anyhow::Error> {
+        let rows = sqlx::query(
+            "SELECT from_state, to_state, transitioned_at, reason
+             FROM rental_state_transitions
+             WHERE rental_id = ?
+             ORDER BY seq",
+        )
+        .bind(rental_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let from: String = row.get("from_state");
+                let to: String = row.get("to_state");
+                let at: String = row.get("transitioned_at");
+                Ok(StateTransition {
+                    from: Self::parse_rental_state(&from, rental_id),
+                    to: Self::parse_rental_state(&to, rental_id),
+                    at: DateTime::parse_from_rfc3339(&at)?.with_timezone(&Utc),
+                    reason: row.get("reason"),
+                })
+            })
+            .collect()
+    }
+
     /// Query rentals with flexible filtering criteria
     async fn query_rentals(&self, filter: RentalFilter) -> Result<Vec<RentalInfo>, anyhow::Error> {
         let mut builder = QueryBuilder::new("SELECT * FROM rentals");
@@ -1115,6 +1613,7 @@ impl SimplePersistence {
                     builder.push_bind(match state {
                         RentalState::Provisioning => "provisioning",
                         RentalState::Active => "active",
+                        RentalState::Unhealthy => "unhealthy",
                         RentalState::Stopping => "stopping",
                         RentalState::Stopped => "stopped",
                         RentalState::Failed => "failed",
@@ -1131,6 +1630,82 @@ impl SimplePersistence {
         let query = builder.build();
         let rows = query.fetch_all(&self.pool).await?;
 
+        self.rows_to_rentals(rows).await
+    }
+
+    /// List rentals matching `filter`, newest first, including stopped and
+    /// failed ones. `page` is 1-based; `total_count` covers all pages.
+    pub async fn list_rentals(
+        &self,
+        filter: &RentalHistoryFilter,
+        page: u32,
+        page_size: u32,
+    ) -> Result<RentalPage, anyhow::Error> {
+        let mut count_query = QueryBuilder::new("SELECT COUNT(*) FROM rentals");
+        Self::push_rental_history_filter(&mut count_query, filter);
+        let total_count: i64 = count_query
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await?;
+
+        let offset = u64::from(page.saturating_sub(1)) * u64::from(page_size);
+        let mut builder = QueryBuilder::new("SELECT * FROM rentals");
+        Self::push_rental_history_filter(&mut builder, filter);
+        builder.push(" ORDER BY created_at DESC, id LIMIT ");
+        builder.push_bind(i64::from(page_size));
+        builder.push(" OFFSET ");
+        builder.push_bind(offset as i64);
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+
+        Ok(RentalPage {
+            rentals: self.rows_to_rentals(rows).await?,
+            total_count: total_count as u64,
+        })
+    }
+
+    fn push_rental_history_filter(
+        builder: &mut QueryBuilder<'_, sqlx::Sqlite>,
+        filter: &RentalHistoryFilter,
+    ) {
+        builder.push(" WHERE 1 = 1");
+
+        if let Some(states) = &filter.states {
+            builder.push(" AND state IN (");
+            let mut separated = builder.separated(", ");
+            for state in states {
+                // States are stored lowercase
+                separated.push_bind(state.to_string().to_lowercase());
+            }
+            builder.push(")");
+        }
+
+        if let Some(executor_id) = &filter.executor_id {
+            builder.push(" AND executor_id = ");
+            builder.push_bind(executor_id.clone());
+        }
+
+        if let Some(validator_hotkey) = &filter.validator_hotkey {
+            builder.push(" AND validator_hotkey = ");
+            builder.push_bind(validator_hotkey.clone());
+        }
+
+        if let Some(created_after) = filter.created_after {
+            builder.push(" AND created_at >= ");
+            builder.push_bind(created_after.to_rfc3339());
+        }
+
+        if let Some(created_before) = filter.created_before {
+            builder.push(" AND created_at < ");
+            builder.push_bind(created_before.to_rfc3339());
+        }
+    }
+
+    /// Parse rental rows, attaching executor details to each
+    async fn rows_to_rentals(
+        &self,
+        rows: Vec<sqlx::sqlite::SqliteRow>,
+    ) -> Result<Vec<RentalInfo>, anyhow::Error> {
         // Parse all rows and fetch executor details for each
         let mut rentals = Vec::new();
         for row in rows {
@@ -1157,7 +1732,9 @@ impl SimplePersistence {
                 }
             };
 
-            rentals.push(self.parse_rental_row(row, executor_details)?);
+            let mut rental = self.parse_rental_row(row, executor_details)?;
+            rental.state_history = self.load_state_history(&rental.rental_id).await?;
+            rentals.push(rental);
         }
 
         Ok(rentals)
@@ -1585,12 +2162,62 @@ impl SimplePersistence {
         Ok(())
     }
 
-    /// Get miner executors
-    pub async fn get_miner_executors(
+    /// Mark a scheduled verification as finished
+    pub async fn complete_verification(
         &self,
-        miner_id: &str,
-    ) -> Result<Vec<ExecutorData>, anyhow::Error> {
-        let rows = sqlx::query(
+        verification_id: &str,
+        success: bool,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query("UPDATE verification_requests SET status = ?, completed_at = ? WHERE id = ?")
+            .bind(if success { "completed" } else { "failed" })
+            .bind(Utc::now().to_rfc3339())
+            .bind(verification_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// When each miner last finished a verification of `verification_type`,
+    /// whether it passed or failed
+    pub async fn get_last_verification_times(
+        &self,
+        verification_type: &str,
+    ) -> Result<std::collections::HashMap<String, DateTime<Utc>>, anyhow::Error> {
+        let rows = sqlx::query(
+            "SELECT miner_id, MAX(completed_at) AS completed_at
+             FROM verification_requests
+             WHERE verification_type = ? AND completed_at IS NOT NULL
+             GROUP BY miner_id",
+        )
+        .bind(verification_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut last_verified = std::collections::HashMap::new();
+        for row in rows {
+            let miner_id: String = row.get("miner_id");
+            let completed_at: String = row.get("completed_at");
+            match DateTime::parse_from_rfc3339(&completed_at) {
+                Ok(at) => {
+                    last_verified.insert(miner_id, at.with_timezone(&Utc));
+                }
+                Err(e) => warn!(
+                    "Ignoring invalid completion time '{}' for miner {}: {}",
+                    completed_at, miner_id, e
+                ),
+            }
+        }
+
+        Ok(last_verified)
+    }
+
+    /// Get miner executors
+    pub async fn get_miner_executors(
+        &self,
+        miner_id: &str,
+    ) -> Result<Vec<ExecutorData>, anyhow::Error> {
+        let rows = sqlx::query(
             "SELECT
                 me.executor_id,
                 me.gpu_specs,
@@ -1674,6 +2301,23 @@ impl SimplePersistence {
     }
 
     /// Get detailed executor information including GPU and CPU specs
+    /// Get the gRPC address an executor registered with its miner
+    pub async fn get_executor_grpc_address(
+        &self,
+        executor_id: &str,
+        miner_id: &str,
+    ) -> Result<Option<String>, anyhow::Error> {
+        let row = sqlx::query(
+            "SELECT grpc_address FROM miner_executors WHERE executor_id = ? AND miner_id = ?",
+        )
+        .bind(executor_id)
+        .bind(miner_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| row.get("grpc_address")))
+    }
+
     pub async fn get_executor_details(
         &self,
         executor_id: &str,
@@ -1732,6 +2376,11 @@ impl SimplePersistence {
                             name: gpu_name.to_string(),
                             memory_gb,
                             compute_capability: "8.0".to_string(),
+                            uuid: None,
+                            pci_bus_id: None,
+                            numa_node: None,
+                            mig_profile: MigProfile::from_device_name(gpu_name)
+                                .map(|profile| profile.to_string()),
                         });
                     }
                 }
@@ -2111,6 +2760,25 @@ impl SimplePersistence {
         Ok(count as u32)
     }
 
+    /// Record whether an executor is draining for maintenance, as reported
+    /// by its miner; draining executors are not offered for rental
+    pub async fn set_executor_draining(
+        &self,
+        miner_id: &str,
+        executor_id: &str,
+        draining: bool,
+    ) -> Result<(), anyhow::Error> {
+        sqlx::query(
+            "UPDATE miner_executors SET draining = ? WHERE miner_id = ? AND executor_id = ?",
+        )
+        .bind(draining)
+        .bind(miner_id)
+        .bind(executor_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Get known executors from database for a miner
     pub async fn get_known_executors_for_miner(
         &self,
@@ -2646,8 +3314,9 @@ impl ValidatorPersistence for SimplePersistence {
         sqlx::query(
             "INSERT INTO rentals (
                 id, validator_hotkey, executor_id, container_id, ssh_session_id,
-                ssh_credentials, state, created_at, container_spec, miner_id
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ssh_credentials, state, created_at, container_spec, miner_id,
+                idle_timeout_secs, health_policy
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(id) DO UPDATE SET
                 state = excluded.state,
                 container_id = excluded.container_id,
@@ -2661,19 +3330,38 @@ impl ValidatorPersistence for SimplePersistence {
         .bind(&rental.container_id)
         .bind(&rental.ssh_session_id)
         .bind(&rental.ssh_credentials)
-        .bind(match &rental.state {
-            RentalState::Provisioning => "provisioning",
-            RentalState::Active => "active",
-            RentalState::Stopping => "stopping",
-            RentalState::Stopped => "stopped",
-            RentalState::Failed => "failed",
-        })
+        .bind(rental_state_str(&rental.state))
         .bind(rental.created_at.to_rfc3339())
         .bind(serde_json::to_string(&rental.container_spec)?)
         .bind(&rental.miner_id)
+        .bind(rental.idle_timeout.map(|t| t.as_secs() as i64))
+        .bind(
+            rental
+                .health_policy
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?,
+        )
         .execute(&self.pool)
         .await?;
 
+        // History is append-only: transitions already stored are left untouched
+        for (seq, transition) in rental.state_history.iter().enumerate() {
+            sqlx::query(
+                "INSERT OR IGNORE INTO rental_state_transitions (
+                    rental_id, seq, from_state, to_state, transitioned_at, reason
+                ) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&rental.rental_id)
+            .bind(seq as i64)
+            .bind(rental_state_str(&transition.from))
+            .bind(rental_state_str(&transition.to))
+            .bind(transition.at.to_rfc3339())
+            .bind(&transition.reason)
+            .execute(&self.pool)
+            .await?;
+        }
+
         Ok(())
     }
 
@@ -2713,11 +3401,30 @@ impl ValidatorPersistence for SimplePersistence {
             .bind(rental_id)
             .execute(&self.pool)
             .await?;
+        sqlx::query("DELETE FROM rental_state_transitions WHERE rental_id = ?")
+            .bind(rental_id)
+            .execute(&self.pool)
+            .await?;
 
         Ok(())
     }
 }
 
+/// Stored rental states that keep an executor occupied, as an SQL list
+const BUSY_RENTAL_STATES: &str = "'provisioning', 'active', 'unhealthy', 'stopping'";
+
+/// Database representation of a rental state
+fn rental_state_str(state: &RentalState) -> &'static str {
+    match state {
+        RentalState::Provisioning => "provisioning",
+        RentalState::Active => "active",
+        RentalState::Unhealthy => "unhealthy",
+        RentalState::Stopping => "stopping",
+        RentalState::Stopped => "stopped",
+        RentalState::Failed => "failed",
+    }
+}
+
 /// Executor statistics derived from verification logs
 #[derive(Debug, Clone)]
 pub struct ExecutorStats {
@@ -2751,6 +3458,15 @@ pub struct CapacityEntry {
     pub total_verifications: u64,
 }
 
+/// GPUs of one model on one executor
+#[derive(Debug, Clone)]
+pub struct ExecutorGpuInventory {
+    pub executor_id: String,
+    pub gpu_name: String,
+    pub gpu_count: u32,
+    pub rented: bool,
+}
+
 /// Miner data for listings
 #[derive(Debug, Clone)]
 pub struct MinerData {
@@ -2802,6 +3518,19 @@ pub struct AvailableExecutorData {
     pub download_mbps: Option<f64>,
     pub upload_mbps: Option<f64>,
     pub speed_test_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the executor's status was last confirmed
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+/// Parse a timestamp stored either as RFC 3339 or in SQLite's `datetime()` format
+fn parse_db_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|_| {
+            chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc))
+        })
+        .ok()
 }
 
 #[cfg(test)]
@@ -2809,6 +3538,59 @@ mod tests {
     use super::*;
     use crate::api::types::{CpuSpec, ExecutorRegistration, GpuSpec, UpdateMinerRequest};
 
+    #[tokio::test]
+    async fn test_last_verification_times_track_finished_verifications() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+        for (miner_id, hotkey) in [("miner_1", "hotkey1"), ("miner_2", "hotkey2")] {
+            persistence
+                .register_miner(miner_id, hotkey, "http://miner.example.com", &[])
+                .await
+                .unwrap();
+        }
+
+        persistence
+            .schedule_verification("miner_1", "v1", "full", None)
+            .await
+            .unwrap();
+        persistence
+            .schedule_verification("miner_1", "v2", "lightweight", None)
+            .await
+            .unwrap();
+        persistence
+            .schedule_verification("miner_2", "v3", "full", None)
+            .await
+            .unwrap();
+
+        // Nothing has finished yet
+        assert!(persistence
+            .get_last_verification_times("full")
+            .await
+            .unwrap()
+            .is_empty());
+
+        let before = Utc::now();
+        persistence
+            .complete_verification("v1", false)
+            .await
+            .unwrap();
+        persistence.complete_verification("v2", true).await.unwrap();
+
+        let full = persistence
+            .get_last_verification_times("full")
+            .await
+            .unwrap();
+        assert_eq!(full.len(), 1);
+        assert!(full["miner_1"] >= before);
+
+        let lightweight = persistence
+            .get_last_verification_times("lightweight")
+            .await
+            .unwrap();
+        assert_eq!(lightweight.keys().collect::<Vec<_>>(), vec!["miner_1"]);
+    }
+
     #[tokio::test]
     async fn test_prevent_duplicate_grpc_address_registration() {
         let db_path = ":memory:";
@@ -2825,6 +3607,10 @@ mod tests {
                 name: "RTX 4090".to_string(),
                 memory_gb: 24,
                 compute_capability: "8.9".to_string(),
+                uuid: None,
+                pci_bus_id: None,
+                numa_node: None,
+                mig_profile: None,
             }],
             cpu_specs: CpuSpec {
                 cores: 16,
@@ -2848,6 +3634,10 @@ mod tests {
                 name: "RTX 3090".to_string(),
                 memory_gb: 24,
                 compute_capability: "8.6".to_string(),
+                uuid: None,
+                pci_bus_id: None,
+                numa_node: None,
+                mig_profile: None,
             }],
             cpu_specs: CpuSpec {
                 cores: 8,
@@ -3128,7 +3918,7 @@ mod tests {
 
         // Test get_available_executors with hardware profile
         let available = persistence
-            .get_available_executors(None, None, None, None)
+            .get_available_executors(None, None, None, None, None)
             .await
             .unwrap();
 
@@ -3142,4 +3932,611 @@ mod tests {
             Some("San Francisco/California/US".to_string())
         );
     }
+
+    #[tokio::test]
+    async fn test_capacity_summary_aggregates_seeded_inventory() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+
+        let executors: Vec<ExecutorRegistration> = (1..=4)
+            .map(|i| ExecutorRegistration {
+                executor_id: format!("exec{i}"),
+                grpc_address: format!("http://192.168.1.{i}:50051"),
+                gpu_count: 0,
+                gpu_specs: vec![],
+                cpu_specs: CpuSpec {
+                    cores: 8,
+                    model: "Intel i7".to_string(),
+                    memory_gb: 32,
+                },
+            })
+            .collect();
+        persistence
+            .register_miner("miner1", "hotkey1", "http://miner1.com", &executors)
+            .await
+            .unwrap();
+
+        // exec1: 2x H100 (rented), exec2: 4x H100, exec3: 1x A100, exec4: 8x A100 (offline)
+        let inventory = [
+            ("exec1", "H100", 2),
+            ("exec2", "H100", 4),
+            ("exec3", "A100", 1),
+            ("exec4", "A100", 8),
+        ];
+        for (executor_id, gpu_name, count) in inventory {
+            for index in 0..count {
+                sqlx::query(
+                    "INSERT INTO gpu_uuid_assignments
+                        (gpu_uuid, gpu_index, executor_id, miner_id, gpu_name, last_verified)
+                     VALUES (?, ?, ?, 'miner1', ?, ?)",
+                )
+                .bind(format!("GPU-{executor_id}-{index}"))
+                .bind(index)
+                .bind(executor_id)
+                .bind(gpu_name)
+                .bind(Utc::now().to_rfc3339())
+                .execute(&persistence.pool)
+                .await
+                .unwrap();
+            }
+        }
+
+        sqlx::query("UPDATE miner_executors SET status = 'offline' WHERE executor_id = 'exec4'")
+            .execute(&persistence.pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO rentals (
+                id, validator_hotkey, executor_id, container_id, ssh_session_id,
+                ssh_credentials, state, created_at, container_spec, miner_id
+            ) VALUES ('rental1', 'validator', 'exec1', 'c1', 's1', '', 'active', ?, '{}', 'miner1')",
+        )
+        .bind(Utc::now().to_rfc3339())
+        .execute(&persistence.pool)
+        .await
+        .unwrap();
+
+        let inventory = persistence.get_gpu_inventory().await.unwrap();
+        let summary = crate::api::routes::summarize_gpu_capacity(&inventory);
+
+        assert_eq!(summary.total_gpus, 7);
+        assert_eq!(summary.reserved_gpus, 2);
+        assert_eq!(summary.available_gpus, 5);
+        assert!((summary.mean_utilization_percent - 200.0 / 7.0).abs() < 1e-9);
+
+        assert_eq!(summary.gpu_models.len(), 2);
+        let a100 = &summary.gpu_models[0];
+        assert_eq!(a100.gpu_model, "A100");
+        assert_eq!((a100.total, a100.reserved, a100.available), (1, 0, 1));
+        assert_eq!(a100.mean_utilization_percent, 0.0);
+
+        let h100 = &summary.gpu_models[1];
+        assert_eq!(h100.gpu_model, "H100");
+        assert_eq!((h100.total, h100.reserved, h100.available), (6, 2, 4));
+        assert!((h100.mean_utilization_percent - 100.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_available_executors_exclude_draining() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+
+        let executors: Vec<ExecutorRegistration> = ["serving", "draining"]
+            .iter()
+            .enumerate()
+            .map(|(i, id)| ExecutorRegistration {
+                executor_id: id.to_string(),
+                grpc_address: format!("http://192.168.1.{}:50051", i + 1),
+                gpu_count: 0,
+                gpu_specs: vec![],
+                cpu_specs: CpuSpec {
+                    cores: 8,
+                    model: "Intel i7".to_string(),
+                    memory_gb: 32,
+                },
+            })
+            .collect();
+        persistence
+            .register_miner("miner1", "hotkey1", "http://miner1.com", &executors)
+            .await
+            .unwrap();
+
+        persistence
+            .set_executor_draining("miner1", "draining", true)
+            .await
+            .unwrap();
+        let available = persistence
+            .get_available_executors(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(available.len(), 1);
+        assert_eq!(available[0].executor_id, "serving");
+
+        // A draining executor cannot be reserved either
+        let ttl = chrono::Duration::minutes(15);
+        assert_eq!(
+            persistence
+                .reserve_executor("draining", "rental-a", ttl)
+                .await
+                .unwrap(),
+            ExecutorReservation::Draining
+        );
+        assert_eq!(
+            persistence
+                .reserve_executor("serving", "rental-b", ttl)
+                .await
+                .unwrap(),
+            ExecutorReservation::Reserved
+        );
+
+        persistence
+            .set_executor_draining("miner1", "draining", false)
+            .await
+            .unwrap();
+        let available = persistence
+            .get_available_executors(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(available.len(), 2);
+        assert_eq!(
+            persistence
+                .reserve_executor("draining", "rental-a", ttl)
+                .await
+                .unwrap(),
+            ExecutorReservation::Reserved
+        );
+    }
+
+    #[tokio::test]
+    async fn test_available_executors_exclude_stale_availability() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+
+        let executors: Vec<ExecutorRegistration> = ["fresh", "stale"]
+            .iter()
+            .enumerate()
+            .map(|(i, id)| ExecutorRegistration {
+                executor_id: id.to_string(),
+                grpc_address: format!("http://192.168.1.{}:50051", i + 1),
+                gpu_count: 0,
+                gpu_specs: vec![],
+                cpu_specs: CpuSpec {
+                    cores: 8,
+                    model: "Intel i7".to_string(),
+                    memory_gb: 32,
+                },
+            })
+            .collect();
+        persistence
+            .register_miner("miner1", "hotkey1", "http://miner1.com", &executors)
+            .await
+            .unwrap();
+
+        let fresh_check = Utc::now() - chrono::Duration::seconds(30);
+        sqlx::query("UPDATE miner_executors SET last_health_check = ? WHERE executor_id = 'fresh'")
+            .bind(fresh_check.to_rfc3339())
+            .execute(&persistence.pool)
+            .await
+            .unwrap();
+        // Written the way the verification loop marks executors, in SQLite's format
+        sqlx::query(
+            "UPDATE miner_executors SET last_health_check = datetime('now', '-2 hours')
+             WHERE executor_id = 'stale'",
+        )
+        .execute(&persistence.pool)
+        .await
+        .unwrap();
+
+        let all = persistence
+            .get_available_executors(None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+        let stale = all.iter().find(|e| e.executor_id == "stale").unwrap();
+        let stale_age = Utc::now() - stale.last_seen.expect("stale executor has a timestamp");
+        assert!(stale_age >= chrono::Duration::minutes(119));
+
+        let fresh_only = persistence
+            .get_available_executors(None, None, None, None, Some(chrono::Duration::minutes(10)))
+            .await
+            .unwrap();
+        assert_eq!(fresh_only.len(), 1);
+        assert_eq!(fresh_only[0].executor_id, "fresh");
+        assert_eq!(
+            fresh_only[0].last_seen.map(|ts| ts.timestamp()),
+            Some(fresh_check.timestamp())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reservations_on_same_executor() {
+        let persistence = std::sync::Arc::new(
+            SimplePersistence::new(":memory:", "test_validator".to_string())
+                .await
+                .expect("Failed to create persistence"),
+        );
+        let ttl = chrono::Duration::minutes(15);
+
+        let attempts: Vec<_> = ["rental-a", "rental-b"]
+            .into_iter()
+            .map(|rental_id| {
+                let persistence = persistence.clone();
+                tokio::spawn(async move {
+                    persistence
+                        .reserve_executor("exec1", rental_id, ttl)
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+
+        let mut granted = 0;
+        for attempt in attempts {
+            if attempt.await.unwrap() == ExecutorReservation::Reserved {
+                granted += 1;
+            }
+        }
+        assert_eq!(granted, 1, "exactly one rental may reserve the executor");
+
+        // A different executor is unaffected
+        assert_eq!(
+            persistence
+                .reserve_executor("exec2", "rental-c", ttl)
+                .await
+                .unwrap(),
+            ExecutorReservation::Reserved
+        );
+
+        // Once released, an active rental still keeps the executor busy
+        for rental_id in ["rental-a", "rental-b"] {
+            persistence
+                .release_executor_reservation("exec1", rental_id)
+                .await
+                .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO rentals (
+                id, validator_hotkey, executor_id, container_id, ssh_session_id,
+                ssh_credentials, state, created_at, container_spec, miner_id
+            ) VALUES ('rental-a', 'validator', 'exec1', 'c1', 's1', '', 'active', ?, '{}', 'miner1')",
+        )
+        .bind(Utc::now().to_rfc3339())
+        .execute(&persistence.pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            persistence
+                .reserve_executor("exec1", "rental-d", ttl)
+                .await
+                .unwrap(),
+            ExecutorReservation::Busy
+        );
+
+        // An unhealthy rental still holds its executor
+        sqlx::query("UPDATE rentals SET state = 'unhealthy' WHERE id = 'rental-a'")
+            .execute(&persistence.pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            persistence
+                .reserve_executor("exec1", "rental-d", ttl)
+                .await
+                .unwrap(),
+            ExecutorReservation::Busy
+        );
+        assert!(persistence
+            .has_active_rental("exec1", "miner1")
+            .await
+            .unwrap());
+
+        sqlx::query("UPDATE rentals SET state = 'stopped' WHERE id = 'rental-a'")
+            .execute(&persistence.pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            persistence
+                .reserve_executor("exec1", "rental-d", ttl)
+                .await
+                .unwrap(),
+            ExecutorReservation::Reserved
+        );
+    }
+
+    /// Seed five rentals created one hour apart, oldest first:
+    /// r0..r4 on alternating executors, r3 under another validator,
+    /// r1 stopped and r4 failed
+    async fn seed_rental_history(persistence: &SimplePersistence) -> DateTime<Utc> {
+        let base = Utc::now() - chrono::Duration::hours(10);
+        let rentals = [
+            ("r0", "exec1", "validator", "active"),
+            ("r1", "exec2", "validator", "stopped"),
+            ("r2", "exec1", "validator", "provisioning"),
+            ("r3", "exec2", "other", "active"),
+            ("r4", "exec1", "validator", "failed"),
+        ];
+        for (i, (id, executor_id, hotkey, state)) in rentals.into_iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO rentals (
+                    id, validator_hotkey, executor_id, container_id, ssh_session_id,
+                    ssh_credentials, state, created_at, container_spec, miner_id
+                ) VALUES (?, ?, ?, 'c', 's', '', ?, ?, ?, 'miner1')",
+            )
+            .bind(id)
+            .bind(hotkey)
+            .bind(executor_id)
+            .bind(state)
+            .bind((base + chrono::Duration::hours(i as i64)).to_rfc3339())
+            .bind(
+                r#"{"image":"ubuntu:22.04","environment":{},"ports":[],"resources":{"cpu_cores":1.0,"memory_mb":1024,"storage_mb":1024,"gpu_count":0,"gpu_types":[]},"command":[],"volumes":[],"labels":{},"capabilities":[],"network":{"mode":"bridge","dns":[],"extra_hosts":{}}}"#,
+            )
+            .execute(&persistence.pool)
+            .await
+            .unwrap();
+        }
+        base
+    }
+
+    fn rental_ids(page: &RentalPage) -> Vec<&str> {
+        page.rentals.iter().map(|r| r.rental_id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_list_rentals_filters() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+        let base = seed_rental_history(&persistence).await;
+
+        // No filter returns everything, including stopped and failed, newest first
+        let all = persistence
+            .list_rentals(&RentalHistoryFilter::default(), 1, 100)
+            .await
+            .unwrap();
+        assert_eq!(rental_ids(&all), vec!["r4", "r3", "r2", "r1", "r0"]);
+        assert_eq!(all.total_count, 5);
+        assert_eq!(all.rentals[3].state, RentalState::Stopped);
+
+        let by_state = RentalHistoryFilter {
+            states: Some(vec![RentalState::Stopped, RentalState::Failed]),
+            ..Default::default()
+        };
+        let page = persistence.list_rentals(&by_state, 1, 100).await.unwrap();
+        assert_eq!(rental_ids(&page), vec!["r4", "r1"]);
+
+        let by_executor = RentalHistoryFilter {
+            executor_id: Some("exec2".to_string()),
+            ..Default::default()
+        };
+        let page = persistence
+            .list_rentals(&by_executor, 1, 100)
+            .await
+            .unwrap();
+        assert_eq!(rental_ids(&page), vec!["r3", "r1"]);
+
+        let by_validator = RentalHistoryFilter {
+            validator_hotkey: Some("other".to_string()),
+            ..Default::default()
+        };
+        let page = persistence
+            .list_rentals(&by_validator, 1, 100)
+            .await
+            .unwrap();
+        assert_eq!(rental_ids(&page), vec!["r3"]);
+
+        // created_after is inclusive, created_before exclusive
+        let by_time = RentalHistoryFilter {
+            created_after: Some(base + chrono::Duration::hours(1)),
+            created_before: Some(base + chrono::Duration::hours(3)),
+            ..Default::default()
+        };
+        let page = persistence.list_rentals(&by_time, 1, 100).await.unwrap();
+        assert_eq!(rental_ids(&page), vec!["r2", "r1"]);
+        assert_eq!(page.total_count, 2);
+
+        let combined = RentalHistoryFilter {
+            states: Some(vec![RentalState::Active, RentalState::Provisioning]),
+            executor_id: Some("exec1".to_string()),
+            validator_hotkey: Some("validator".to_string()),
+            ..Default::default()
+        };
+        let page = persistence.list_rentals(&combined, 1, 100).await.unwrap();
+        assert_eq!(rental_ids(&page), vec!["r2", "r0"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_rentals_pagination_boundaries() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+        seed_rental_history(&persistence).await;
+        let filter = RentalHistoryFilter::default();
+
+        let first = persistence.list_rentals(&filter, 1, 2).await.unwrap();
+        assert_eq!(rental_ids(&first), vec!["r4", "r3"]);
+        assert_eq!(first.total_count, 5);
+
+        let last = persistence.list_rentals(&filter, 3, 2).await.unwrap();
+        assert_eq!(rental_ids(&last), vec!["r0"]);
+        assert_eq!(last.total_count, 5);
+
+        let past_end = persistence.list_rentals(&filter, 4, 2).await.unwrap();
+        assert!(past_end.rentals.is_empty());
+        assert_eq!(past_end.total_count, 5);
+
+        // Page 0 is treated as the first page
+        let zero = persistence.list_rentals(&filter, 0, 2).await.unwrap();
+        assert_eq!(rental_ids(&zero), vec!["r4", "r3"]);
+
+        let exact = persistence.list_rentals(&filter, 1, 5).await.unwrap();
+        assert_eq!(exact.rentals.len(), 5);
+
+        let unbounded = persistence
+            .list_rentals(&filter, 1, u32::MAX)
+            .await
+            .unwrap();
+        assert_eq!(unbounded.rentals.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_state_transitions_are_recorded_in_order() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+        seed_rental_history(&persistence).await;
+
+        let mut rental = persistence.load_rental("r2").await.unwrap().unwrap();
+        assert_eq!(rental.state, RentalState::Provisioning);
+        assert!(rental.state_history.is_empty());
+
+        let steps = [
+            (RentalState::Active, "Container deployed"),
+            (RentalState::Unhealthy, "Repeated health check failures"),
+            (RentalState::Stopped, "Stopped on request"),
+        ];
+        for (state, reason) in steps.clone() {
+            rental.transition_to(state, reason);
+            persistence.save_rental(&rental).await.unwrap();
+        }
+        // Saving again must not duplicate the history
+        persistence.save_rental(&rental).await.unwrap();
+
+        let loaded = persistence.load_rental("r2").await.unwrap().unwrap();
+        assert_eq!(loaded.state, RentalState::Stopped);
+        assert_eq!(loaded.state_history.len(), 3);
+
+        let mut from = RentalState::Provisioning;
+        for (transition, (to, reason)) in loaded.state_history.iter().zip(steps) {
+            assert_eq!(transition.from, from);
+            assert_eq!(transition.to, to);
+            assert_eq!(transition.reason, reason);
+            from = to;
+        }
+        assert!(loaded
+            .state_history
+            .windows(2)
+            .all(|pair| pair[0].at <= pair[1].at));
+
+        // Other rentals are unaffected
+        let other = persistence.load_rental("r0").await.unwrap().unwrap();
+        assert!(other.state_history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_same_idempotency_key_yields_one_rental() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+        let ttl = chrono::Duration::minutes(15);
+
+        let response = |rental_id: &str| RentalResponse {
+            rental_id: rental_id.to_string(),
+            ssh_credentials: Some("root@10.0.0.1:32768".to_string()),
+            container_info: crate::rental::ContainerInfo {
+                container_id: "c".to_string(),
+                container_name: "basilica-c".to_string(),
+                mapped_ports: vec![],
+                status: "running".to_string(),
+                labels: Default::default(),
+            },
+            executor_id: "executor-1".to_string(),
+            pin_honored: None,
+        };
+
+        assert!(persistence
+            .find_rental_by_idempotency_key("validator", "retry-key")
+            .await
+            .unwrap()
+            .is_none());
+
+        assert!(matches!(
+            persistence
+                .claim_rental_idempotency_key("validator", "retry-key", "r0", ttl)
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        ));
+
+        // A second request while the first is deploying does not get the key
+        assert!(matches!(
+            persistence
+                .claim_rental_idempotency_key("validator", "retry-key", "r2", ttl)
+                .await
+                .unwrap(),
+            IdempotencyClaim::InProgress
+        ));
+
+        persistence
+            .save_rental_idempotency_key("validator", "retry-key", &response("r0"))
+            .await
+            .unwrap();
+
+        // A retry with the same key finds the first rental
+        let existing = persistence
+            .find_rental_by_idempotency_key("validator", "retry-key")
+            .await
+            .unwrap()
+            .expect("rental for key");
+        assert_eq!(existing.rental_id, "r0");
+        assert_eq!(
+            existing.ssh_credentials.as_deref(),
+            Some("root@10.0.0.1:32768")
+        );
+        match persistence
+            .claim_rental_idempotency_key("validator", "retry-key", "r2", ttl)
+            .await
+            .unwrap()
+        {
+            IdempotencyClaim::Completed(existing) => assert_eq!(existing.rental_id, "r0"),
+            other => panic!("expected the first rental, got {other:?}"),
+        }
+
+        // The key cannot be attached to a second rental
+        assert!(persistence
+            .save_rental_idempotency_key("validator", "retry-key", &response("r2"))
+            .await
+            .is_err());
+
+        // Keys are scoped per validator
+        assert!(persistence
+            .find_rental_by_idempotency_key("other", "retry-key")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_released_or_stale_idempotency_claims_can_be_retaken() {
+        let persistence = SimplePersistence::new(":memory:", "test_validator".to_string())
+            .await
+            .expect("Failed to create persistence");
+        let ttl = chrono::Duration::minutes(15);
+
+        persistence
+            .claim_rental_idempotency_key("validator", "key", "r0", ttl)
+            .await
+            .unwrap();
+        persistence
+            .release_rental_idempotency_key("validator", "key", "r0")
+            .await
+            .unwrap();
+        assert!(matches!(
+            persistence
+                .claim_rental_idempotency_key("validator", "key", "r1", ttl)
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        ));
+
+        // A claim left behind by a crashed deployment expires
+        assert!(matches!(
+            persistence
+                .claim_rental_idempotency_key("validator", "key", "r2", chrono::Duration::zero())
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        ));
+    }
 }
@@ -4,14 +4,24 @@
 //! to manage containers on remote executor machines.
 
 use anyhow::{Context, Result};
+use basilica_common::ssh::parse_credentials;
 use serde_json::Value;
 use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 use tracing::{debug, info};
 
-use super::types::{ContainerInfo, ContainerSpec, ContainerStatus, PortMapping, ResourceUsage};
+use super::secrets::SecretEnv;
+use super::types::{
+    AppliedResourceLimits, ContainerInfo, ContainerSpec, ContainerStatus, DockerRuntime,
+    GpuAllocation, GpuUsage, PortMapping, RegistryAuth, ResourceUsage, NVIDIA_VISIBLE_DEVICES,
+};
 use std::path::PathBuf;
 
+/// Label naming the validator that deployed a rental container; executors
+/// only serve a container's logs to this validator
+pub const VALIDATOR_HOTKEY_LABEL: &str = "basilica.validator_hotkey";
+
 /// SSH-based Docker client for container management
 #[derive(Clone)]
 pub struct ContainerClient {
@@ -28,22 +38,13 @@ pub struct ContainerClient {
 }
 
 impl ContainerClient {
-    /// Parse SSH connection string to extract host and port
-    /// Handles formats like "user@host:port" or "user@host"
-    fn parse_ssh_connection(connection: &str) -> (String, Option<u16>) {
-        if let Some(at_pos) = connection.rfind('@') {
-            let user_part = &connection[..=at_pos];
-            let host_port = &connection[at_pos + 1..];
-
-            if let Some(colon_pos) = host_port.rfind(':') {
-                let host = &host_port[..colon_pos];
-                let port_str = &host_port[colon_pos + 1..];
-                if let Ok(port_num) = port_str.parse::<u16>() {
-                    return (format!("{user_part}{host}"), Some(port_num));
-                }
-            }
-        }
-        (connection.to_string(), None)
+    /// `user@host` destination and port for `ssh`, from the connection string
+    fn ssh_target(&self) -> Result<(String, u16)> {
+        let credentials = parse_credentials(&self.ssh_connection)?;
+        Ok((
+            format!("{}@{}", credentials.username, credentials.host),
+            credentials.port,
+        ))
     }
 
     /// Create a new container client with validator's private key
@@ -81,6 +82,18 @@ impl ContainerClient {
 
     /// Execute a command over SSH
     pub async fn execute_ssh_command(&self, command: &str) -> Result<String> {
+        self.execute_ssh_command_with_stdin(command, None).await
+    }
+
+    /// Execute a command over SSH, feeding it `stdin`
+    ///
+    /// Values passed this way never appear on the local or the remote
+    /// command line, so they are not visible in `ps` on either host.
+    async fn execute_ssh_command_with_stdin(
+        &self,
+        command: &str,
+        stdin: Option<&[u8]>,
+    ) -> Result<String> {
         let mut ssh_cmd = Command::new("ssh");
 
         // Add SSH options based on configuration
@@ -97,6 +110,9 @@ impl ContainerClient {
             ssh_cmd.arg("-o").arg("UserKnownHostsFile=/dev/null");
         }
 
+        // Abandoning the future (e.g. on a deployment timeout) must not leave ssh running
+        ssh_cmd.kill_on_drop(true);
+
         ssh_cmd.arg("-o").arg("ConnectTimeout=10");
         ssh_cmd.arg("-o").arg("BatchMode=yes");
 
@@ -110,22 +126,33 @@ impl ContainerClient {
             ssh_cmd.arg("-i").arg(key_path);
         }
 
-        // Parse connection string to handle user@host:port format
-        let (connection_str, port) = Self::parse_ssh_connection(&self.ssh_connection);
-
-        // Add port if specified
-        if let Some(port) = port {
-            ssh_cmd.arg("-p").arg(port.to_string());
-        }
+        let (destination, port) = self.ssh_target()?;
+        ssh_cmd.arg("-p").arg(port.to_string());
 
         // Add connection and command
-        ssh_cmd.arg(&connection_str);
+        ssh_cmd.arg(&destination);
         ssh_cmd.arg(command);
 
         debug!("Executing SSH command: {}", command);
 
-        let output = ssh_cmd
-            .output()
+        ssh_cmd.stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        });
+        ssh_cmd.stdout(Stdio::piped());
+        ssh_cmd.stderr(Stdio::piped());
+
+        let mut child = ssh_cmd.spawn().context("Failed to execute SSH command")?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(input)
+                .await
+                .context("Failed to write SSH command input")?;
+            // Closing stdin lets the remote command see end of input
+            drop(pipe);
+        }
+        let output = child
+            .wait_with_output()
             .await
             .context("Failed to execute SSH command")?;
 
@@ -137,16 +164,81 @@ impl ContainerClient {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    /// Deploy a container based on the specification
-    pub async fn deploy_container(
+    /// Free bytes on the filesystem holding Docker's data root
+    pub async fn available_disk_bytes(&self) -> Result<u64> {
+        let output = self
+            .execute_ssh_command(
+                "df -B1 --output=avail \"$(docker info --format '{{.DockerRootDir}}')\" | tail -n 1",
+            )
+            .await
+            .context("Failed to query free disk space")?;
+        output
+            .trim()
+            .parse()
+            .with_context(|| format!("Unexpected df output: {}", output.trim()))
+    }
+
+    /// Indices of the GPUs on the executor host
+    pub async fn gpu_device_ids(&self) -> Result<Vec<u32>> {
+        let output = self
+            .execute_ssh_command("nvidia-smi --query-gpu=index --format=csv,noheader")
+            .await
+            .context("Failed to list executor GPUs")?;
+        parse_gpu_indices(&output)
+    }
+
+    /// Pull the image a rental runs on the executor
+    ///
+    /// With `auth` the pull logs in to the image's registry using a
+    /// throwaway Docker config and logs out again afterwards; the token is
+    /// sent on SSH stdin.
+    pub async fn pull_image(&self, image: &str, auth: Option<&RegistryAuth>) -> Result<()> {
+        info!("Pulling image {image}");
+        let pulled = match auth {
+            None => {
+                self.execute_ssh_command(&format!("docker pull {image}"))
+                    .await
+            }
+            Some(auth) => {
+                let registry = auth.registry_for(image).map_err(anyhow::Error::msg)?;
+                self.execute_ssh_command_with_stdin(
+                    &authenticated_pull_command(&registry, &auth.username, image),
+                    Some(auth.token.as_bytes()),
+                )
+                .await
+            }
+        };
+        pulled.with_context(|| format!("Failed to pull image {image}"))?;
+        Ok(())
+    }
+
+    /// Repository digests of an image on the executor, or `None` if the
+    /// image is not there
+    pub async fn image_digests(&self, image: &str) -> Result<Option<Vec<String>>> {
+        let output = self
+            .execute_ssh_command(&format!(
+                "docker image inspect --format '{{{{json .RepoDigests}}}}' {} 2>/dev/null || true",
+                shell_quote(image)
+            ))
+            .await
+            .with_context(|| format!("Failed to inspect image {image}"))?;
+        parse_repo_digests(&output)
+    }
+
+    /// Create (but do not start) the container for a rental, returning its ID
+    ///
+    /// `secrets` are added to the container environment through an env file
+    /// sent over SSH stdin, so their values never reach a command line.
+    pub async fn create_container(
         &self,
         spec: &ContainerSpec,
+        secrets: &SecretEnv,
         rental_id: &str,
-    ) -> Result<ContainerInfo> {
-        info!("Deploying container for rental {rental_id}");
+    ) -> Result<String> {
+        info!("Creating container for rental {rental_id}");
 
-        // Build docker run command as a string directly
-        let mut docker_cmd_parts = vec!["docker", "run", "-d"];
+        // Build docker create command as a string directly
+        let mut docker_cmd_parts = vec!["docker", "create"];
 
         // Add interactive and TTY flags if command is /bin/bash
         if spec.command.len() == 1 && spec.command[0] == "/bin/bash" {
@@ -155,7 +247,7 @@ impl ContainerClient {
 
         // Add container name with sanitized rental ID
         let sanitized_rental_id = self.sanitize_rental_id(rental_id);
-        let container_name = format!("basilica-rental-{sanitized_rental_id}");
+        let container_name = self.rental_container_name(rental_id);
         docker_cmd_parts.push("--name");
         docker_cmd_parts.push(&container_name);
 
@@ -175,7 +267,7 @@ impl ContainerClient {
         let env_strings: Vec<String> = spec
             .environment
             .iter()
-            .flat_map(|(key, value)| vec!["-e".to_string(), format!("{key}={value}")])
+            .flat_map(|(key, value)| vec!["-e".to_string(), shell_quote(&format!("{key}={value}"))])
             .collect();
 
         // Collect all port mappings
@@ -205,10 +297,7 @@ impl ContainerClient {
             resource_strings.push(format!("{}m", spec.resources.memory_mb));
         }
 
-        resource_strings.push("--gpus".to_string());
-        resource_strings.push("all".to_string());
-        resource_strings.push("--runtime".to_string());
-        resource_strings.push("nvidia".to_string());
+        resource_strings.extend(gpu_args(spec));
 
         // Volumes
         let volume_strings: Vec<String> = spec
@@ -266,6 +355,7 @@ impl ContainerClient {
             final_cmd.push(' ');
             final_cmd.push_str(s);
         }
+        let secrets_at = final_cmd.len();
         for s in &port_strings {
             final_cmd.push(' ');
             final_cmd.push_str(s);
@@ -321,22 +411,47 @@ impl ContainerClient {
             }
         }
 
-        // Execute docker run
-        let command = final_cmd;
-        let container_id = self
-            .execute_ssh_command(&command)
+        // Execute docker create
+        let container_id = if secrets.is_empty() {
+            self.execute_ssh_command(&final_cmd).await
+        } else {
+            let env_file = secrets.to_env_file()?;
+            let (head, tail) = final_cmd.split_at(secrets_at);
+            self.execute_ssh_command_with_stdin(
+                &with_stdin_env_file(head, tail),
+                Some(env_file.as_bytes()),
+            )
             .await
+        };
+        let container_id = container_id
             .context("Failed to create container")?
             .trim()
             .to_string();
+        self.validate_container_id(&container_id)?;
 
         info!(
             "Container {} created with ID: {}",
             container_name, container_id
         );
+        Ok(container_id)
+    }
+
+    /// Start a created rental container and report its info
+    pub async fn start_container(
+        &self,
+        container_id: &str,
+        spec: &ContainerSpec,
+        rental_id: &str,
+    ) -> Result<ContainerInfo> {
+        let validated_container_id = self.validate_container_id(container_id)?;
+        self.execute_ssh_command(&format!("docker start {validated_container_id}"))
+            .await
+            .context("Failed to start container")?;
+
+        let container_name = self.rental_container_name(rental_id);
+        info!("Container {} started", container_name);
 
         // Get container info
-        let validated_container_id = self.validate_container_id(&container_id)?;
         let inspect_cmd = format!("docker inspect {validated_container_id}");
         let inspect_output = self
             .execute_ssh_command(&inspect_cmd)
@@ -380,7 +495,7 @@ impl ContainerClient {
         }
 
         Ok(ContainerInfo {
-            container_id: container_id.clone(),
+            container_id: container_id.to_string(),
             container_name,
             mapped_ports,
             status: "running".to_string(),
@@ -424,6 +539,26 @@ impl ContainerClient {
         })
     }
 
+    /// Get the resource limits the container runtime applied to a container
+    pub async fn inspect_resource_limits(
+        &self,
+        container_id: &str,
+    ) -> Result<AppliedResourceLimits> {
+        let validated_container_id = self.validate_container_id(container_id)?;
+        let inspect_cmd = format!("docker inspect {validated_container_id}");
+        let output = self
+            .execute_ssh_command(&inspect_cmd)
+            .await
+            .context("Failed to inspect container")?;
+
+        let data: Vec<Value> = serde_json::from_str(&output)?;
+        let container = data
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("Container not found"))?;
+
+        Ok(parse_applied_resource_limits(container))
+    }
+
     /// Get container resource usage
     pub async fn get_resource_usage(&self, container_id: &str) -> Result<ResourceUsage> {
         let validated_container_id = self.validate_container_id(container_id)?;
@@ -452,8 +587,19 @@ impl ContainerClient {
         let block_io = stats["BlockIO"].as_str().unwrap_or("0B / 0B");
         let (disk_read_bytes, disk_write_bytes) = self.parse_block_io(block_io);
 
-        // TODO: Get GPU usage if available
-        let gpu_usage = Vec::new();
+        // Containers without GPUs or nvidia-smi report no GPU usage
+        let gpu_cmd = format!(
+            "docker exec {validated_container_id} nvidia-smi \
+             --query-gpu=index,utilization.gpu,memory.used,temperature.gpu \
+             --format=csv,noheader,nounits"
+        );
+        let gpu_usage = match self.execute_ssh_command(&gpu_cmd).await {
+            Ok(output) => parse_gpu_usage(&output),
+            Err(e) => {
+                debug!("No GPU usage for container {}: {}", container_id, e);
+                Vec::new()
+            }
+        };
 
         Ok(ResourceUsage {
             cpu_percent,
@@ -483,6 +629,39 @@ impl ContainerClient {
         Ok(())
     }
 
+    /// Send SIGTERM to a container, then SIGKILL if it is still running
+    /// after `timeout`
+    pub async fn terminate_container(
+        &self,
+        container_id: &str,
+        timeout: std::time::Duration,
+    ) -> Result<()> {
+        let validated_container_id = self.validate_container_id(container_id)?;
+        let stop_cmd = format!(
+            "docker stop --time {} {validated_container_id}",
+            timeout.as_secs()
+        );
+
+        self.execute_ssh_command(&stop_cmd)
+            .await
+            .context("Failed to stop container")?;
+
+        info!("Container {} stopped", container_id);
+        Ok(())
+    }
+
+    /// Run `command` inside a container and wait for it to exit
+    pub async fn exec_in_container(&self, container_id: &str, command: &[String]) -> Result<()> {
+        let validated_container_id = self.validate_container_id(container_id)?;
+        let args: Vec<String> = command.iter().map(|arg| shell_quote(arg)).collect();
+        let exec_cmd = format!("docker exec {validated_container_id} {}", args.join(" "));
+
+        self.execute_ssh_command(&exec_cmd)
+            .await
+            .context("Command failed inside container")?;
+        Ok(())
+    }
+
     /// Remove a container
     pub async fn remove_container(&self, container_id: &str) -> Result<()> {
         let validated_container_id = self.validate_container_id(container_id)?;
@@ -496,12 +675,25 @@ impl ContainerClient {
         Ok(())
     }
 
+    /// Force-remove the container created for a rental, addressed by name so
+    /// it works even when the container ID was never reported back
+    pub async fn remove_rental_container(&self, rental_id: &str) -> Result<()> {
+        let container_name = self.rental_container_name(rental_id);
+        self.execute_ssh_command(&format!("docker rm -f {container_name}"))
+            .await
+            .context("Failed to remove container")?;
+
+        info!("Container {} removed", container_name);
+        Ok(())
+    }
+
     /// Stream container logs
     pub async fn stream_logs(
         &self,
         container_id: &str,
         follow: bool,
         tail_lines: Option<u32>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<tokio::process::Child> {
         let mut docker_cmd_parts = vec!["docker".to_string(), "logs".to_string()];
 
@@ -514,6 +706,11 @@ impl ContainerClient {
             docker_cmd_parts.push(lines.to_string());
         }
 
+        if let Some(since) = since {
+            docker_cmd_parts.push("--since".to_string());
+            docker_cmd_parts.push(since.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true));
+        }
+
         docker_cmd_parts.push("--timestamps".to_string());
 
         // Validate container ID before using it
@@ -549,15 +746,10 @@ impl ContainerClient {
             ssh_cmd.arg("-i").arg(key_path);
         }
 
-        // Parse connection string to handle user@host:port format
-        let (connection_str, port) = Self::parse_ssh_connection(&self.ssh_connection);
-
-        // Add port if specified
-        if let Some(port) = port {
-            ssh_cmd.arg("-p").arg(port.to_string());
-        }
+        let (destination, port) = self.ssh_target()?;
+        ssh_cmd.arg("-p").arg(port.to_string());
 
-        ssh_cmd.arg(&connection_str);
+        ssh_cmd.arg(&destination);
         ssh_cmd.arg(docker_cmd);
 
         ssh_cmd.stdout(Stdio::piped());
@@ -613,6 +805,11 @@ impl ContainerClient {
         Ok(container_id)
     }
 
+    /// Name of the container deployed for a rental
+    fn rental_container_name(&self, rental_id: &str) -> String {
+        format!("basilica-rental-{}", self.sanitize_rental_id(rental_id))
+    }
+
     /// Sanitize rental ID for use in container names
     fn sanitize_rental_id(&self, rental_id: &str) -> String {
         rental_id
@@ -646,3 +843,294 @@ impl ContainerClient {
         (num * multiplier as f64) as i64
     }
 }
+
+/// Quote a value for the remote POSIX shell
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// `docker create` arguments selecting the runtime and the GPUs exposed to
+/// the container
+fn gpu_args(spec: &ContainerSpec) -> Vec<String> {
+    let mut args = vec!["--gpus".to_string()];
+    match spec.gpu_allocation() {
+        GpuAllocation::Devices(ids) => {
+            let ids = ids.iter().map(u32::to_string).collect::<Vec<_>>().join(",");
+            // Docker splits an unquoted device list at the comma
+            args.push(shell_quote(&format!("\"device={ids}\"")));
+            args.push("-e".to_string());
+            args.push(shell_quote(&format!("{NVIDIA_VISIBLE_DEVICES}={ids}")));
+        }
+        _ => args.push("all".to_string()),
+    }
+    if spec.runtime == DockerRuntime::Nvidia {
+        args.push("--runtime".to_string());
+        args.push("nvidia".to_string());
+    }
+    args
+}
+
+/// Parse `nvidia-smi --query-gpu=index --format=csv,noheader` output
+pub(crate) fn parse_gpu_indices(output: &str) -> Result<Vec<u32>> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.parse()
+                .with_context(|| format!("Unexpected nvidia-smi output: {line}"))
+        })
+        .collect()
+}
+
+/// Parse `docker image inspect --format '{{json .RepoDigests}}'` output;
+/// no output means the image does not exist
+pub(crate) fn parse_repo_digests(output: &str) -> Result<Option<Vec<String>>> {
+    let output = output.trim();
+    if output.is_empty() {
+        return Ok(None);
+    }
+    let digests: Option<Vec<String>> = serde_json::from_str(output)
+        .with_context(|| format!("Unexpected docker image inspect output: {output}"))?;
+    Ok(Some(digests.unwrap_or_default()))
+}
+
+/// Remote command that logs in to `registry` with the password on stdin,
+/// pulls `image` and logs out, keeping the login in a temporary Docker
+/// config so the executor's own credentials are left alone
+fn authenticated_pull_command(registry: &str, username: &str, image: &str) -> String {
+    let registry = shell_quote(registry);
+    format!(
+        "d=$(mktemp -d) && trap 'rm -rf \"$d\"' EXIT && export DOCKER_CONFIG=\"$d\" && \
+         docker login --username {} --password-stdin {registry} >/dev/null && \
+         docker pull {image}; rc=$?; docker logout {registry} >/dev/null 2>&1; exit $rc",
+        shell_quote(username)
+    )
+}
+
+/// Remote command that saves stdin to a private temporary file and runs
+/// `head --env-file <file> tail` with it, removing the file afterwards
+fn with_stdin_env_file(head: &str, tail: &str) -> String {
+    format!(
+        "f=$(mktemp) && trap 'rm -f \"$f\"' EXIT && cat > \"$f\" && {head} --env-file \"$f\"{tail}"
+    )
+}
+
+/// Extract applied resource limits from a single `docker inspect` entry
+pub(crate) fn parse_applied_resource_limits(container: &Value) -> AppliedResourceLimits {
+    let host_config = &container["HostConfig"];
+
+    // `--cpus` sets NanoCpus; fall back to the quota/period pair for other runtimes
+    let nano_cpus = host_config["NanoCpus"].as_i64().unwrap_or(0);
+    let cpu_quota = host_config["CpuQuota"].as_i64().unwrap_or(0);
+    let cpu_period = host_config["CpuPeriod"].as_i64().unwrap_or(0);
+    let cpu_cores = if nano_cpus > 0 {
+        Some(nano_cpus as f64 / 1_000_000_000.0)
+    } else if cpu_quota > 0 && cpu_period > 0 {
+        Some(cpu_quota as f64 / cpu_period as f64)
+    } else {
+        None
+    };
+
+    let memory_mb = host_config["Memory"]
+        .as_i64()
+        .filter(|bytes| *bytes > 0)
+        .map(|bytes| bytes / (1024 * 1024));
+
+    let mut gpus = GpuAllocation::None;
+    if let Some(requests) = host_config["DeviceRequests"].as_array() {
+        for request in requests {
+            let is_gpu = request["Capabilities"]
+                .as_array()
+                .map(|caps| {
+                    caps.iter()
+                        .filter_map(|set| set.as_array())
+                        .flatten()
+                        .any(|cap| cap.as_str() == Some("gpu"))
+                })
+                .unwrap_or(false);
+            if !is_gpu {
+                continue;
+            }
+
+            let device_ids: Vec<&str> = request["DeviceIDs"]
+                .as_array()
+                .map(|ids| ids.iter().filter_map(|id| id.as_str()).collect())
+                .unwrap_or_default();
+            let count = request["Count"].as_i64().unwrap_or(0);
+
+            gpus = if !device_ids.is_empty() {
+                // Devices are selected by index; anything else (e.g. UUIDs)
+                // is only counted
+                match device_ids.iter().map(|id| id.parse::<u32>()).collect() {
+                    Ok(ids) => GpuAllocation::Devices(ids),
+                    Err(_) => GpuAllocation::Count(device_ids.len() as u32),
+                }
+            } else if count < 0 {
+                GpuAllocation::All
+            } else {
+                GpuAllocation::Count(count as u32)
+            };
+        }
+    }
+
+    AppliedResourceLimits {
+        cpu_cores,
+        memory_mb,
+        gpus,
+    }
+}
+
+/// Parse `nvidia-smi --query-gpu=index,utilization.gpu,memory.used,temperature.gpu
+/// --format=csv,noheader,nounits` output, skipping lines that do not parse
+pub(crate) fn parse_gpu_usage(output: &str) -> Vec<GpuUsage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [index, utilization, memory, temperature] = fields.as_slice() else {
+                return None;
+            };
+            Some(GpuUsage {
+                gpu_index: index.parse().ok()?,
+                utilization_percent: utilization.parse().ok()?,
+                memory_mb: memory.parse().ok()?,
+                temperature_celsius: temperature.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssh_target_reads_ipv6_hosts() {
+        let target = |connection: &str| {
+            ContainerClient::new(connection.to_string(), None)
+                .unwrap()
+                .ssh_target()
+                .unwrap()
+        };
+        assert_eq!(
+            target("ubuntu@10.0.0.1:2222"),
+            ("ubuntu@10.0.0.1".to_string(), 2222)
+        );
+        assert_eq!(target("10.0.0.1"), ("root@10.0.0.1".to_string(), 22));
+        assert_eq!(
+            target("root@[2001:db8::1]:2222"),
+            ("root@2001:db8::1".to_string(), 2222)
+        );
+        assert_eq!(
+            target("root@2001:db8::1"),
+            ("root@2001:db8::1".to_string(), 22)
+        );
+    }
+
+    #[test]
+    fn test_registry_token_stays_off_the_command_line() {
+        let command = authenticated_pull_command("ghcr.io", "ci", "ghcr.io/acme/trainer:1.0");
+        assert_eq!(
+            command,
+            "d=$(mktemp -d) && trap 'rm -rf \"$d\"' EXIT && export DOCKER_CONFIG=\"$d\" && \
+             docker login --username 'ci' --password-stdin 'ghcr.io' >/dev/null && \
+             docker pull ghcr.io/acme/trainer:1.0; rc=$?; docker logout 'ghcr.io' >/dev/null 2>&1; exit $rc"
+        );
+
+        let auth = RegistryAuth {
+            registry: "https://ghcr.io".to_string(),
+            username: "ci".to_string(),
+            token: "s3cr3t-token".to_string(),
+        };
+        assert!(!format!("{auth:?}").contains("s3cr3t-token"));
+        assert_eq!(
+            auth.registry_for("ghcr.io/acme/trainer:1.0").unwrap(),
+            "ghcr.io"
+        );
+        assert!(auth.registry_for("nvidia/cuda:12.2").is_err());
+    }
+
+    #[test]
+    fn test_secrets_are_passed_in_an_env_file() {
+        let command = with_stdin_env_file("docker create --name c", " ubuntu:22.04");
+        assert_eq!(
+            command,
+            r#"f=$(mktemp) && trap 'rm -f "$f"' EXIT && cat > "$f" && docker create --name c --env-file "$f" ubuntu:22.04"#
+        );
+    }
+
+    fn gpu_spec(runtime: DockerRuntime, gpu_device_ids: Vec<u32>) -> ContainerSpec {
+        let mut spec: ContainerSpec = serde_json::from_value(serde_json::json!({
+            "image": "ubuntu:22.04",
+            "environment": {},
+            "ports": [],
+            "resources": {
+                "cpu_cores": 1.0,
+                "memory_mb": 1024,
+                "storage_mb": 0,
+                "gpu_count": 2,
+                "gpu_types": []
+            },
+            "command": [],
+            "volumes": [],
+            "labels": {},
+            "capabilities": [],
+            "network": {"mode": "bridge", "dns": [], "extra_hosts": {}}
+        }))
+        .unwrap();
+        spec.runtime = runtime;
+        spec.gpu_device_ids = gpu_device_ids;
+        spec
+    }
+
+    #[test]
+    fn test_gpu_args_select_runtime_and_devices() {
+        // Specs from before device selection expose every reserved GPU
+        let spec = gpu_spec(DockerRuntime::default(), Vec::new());
+        assert_eq!(spec.runtime, DockerRuntime::Nvidia);
+        assert_eq!(gpu_args(&spec), ["--gpus", "all", "--runtime", "nvidia"]);
+
+        let spec = gpu_spec(DockerRuntime::Nvidia, vec![1, 3]);
+        assert_eq!(
+            gpu_args(&spec),
+            [
+                "--gpus",
+                r#"'"device=1,3"'"#,
+                "-e",
+                "'NVIDIA_VISIBLE_DEVICES=1,3'",
+                "--runtime",
+                "nvidia"
+            ]
+        );
+
+        let spec = gpu_spec(DockerRuntime::Default, vec![0]);
+        assert_eq!(
+            gpu_args(&spec),
+            [
+                "--gpus",
+                r#"'"device=0"'"#,
+                "-e",
+                "'NVIDIA_VISIBLE_DEVICES=0'"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_repo_digests() {
+        assert_eq!(parse_repo_digests("").unwrap(), None);
+        assert_eq!(parse_repo_digests("null\n").unwrap(), Some(vec![]));
+        assert_eq!(
+            parse_repo_digests("[\"nvidia/cuda@sha256:0123\"]\n").unwrap(),
+            Some(vec!["nvidia/cuda@sha256:0123".to_string()])
+        );
+        assert!(parse_repo_digests("Error: No such image").is_err());
+    }
+
+    #[test]
+    fn test_parse_gpu_indices() {
+        assert_eq!(parse_gpu_indices("0\n1\n 2 \n\n").unwrap(), vec![0, 1, 2]);
+        assert!(parse_gpu_indices("").unwrap().is_empty());
+        assert!(parse_gpu_indices("NVIDIA-SMI has failed").is_err());
+    }
+}
@@ -4,16 +4,239 @@
 //! including validation, resource allocation, and lifecycle management.
 
 use anyhow::{Context, Result};
-use basilica_common::utils::validate_docker_image;
-use tracing::{debug, info, warn};
+use async_trait::async_trait;
+use basilica_common::config::{ImageCacheConfig, ImagePolicyConfig};
+use basilica_common::utils::{select_repo_digest, validate_docker_image};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+use tracing::{debug, error, info, warn};
 
 use super::container_client::ContainerClient;
-use super::types::{ContainerInfo, ContainerSpec};
+use super::secrets::{RentalSecretStore, SecretAccessDenied, SecretEnv};
+use super::types::{
+    AppliedResourceLimits, ContainerInfo, ContainerSpec, GpuAllocation, PreStopHook, RegistryAuth,
+    ResourceRequirements, SecretRef,
+};
+
+/// Tolerance when comparing fractional CPU limits
+const CPU_LIMIT_TOLERANCE: f64 = 0.01;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// A single resource whose applied limit differs from the reservation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceMismatch {
+    /// Resource name (`cpu`, `memory`, `gpu`)
+    pub resource: &'static str,
+    /// Reserved value
+    pub expected: String,
+    /// Value applied by the container runtime
+    pub actual: String,
+}
+
+impl fmt::Display for ResourceMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} reserved {} but container got {}",
+            self.resource, self.expected, self.actual
+        )
+    }
+}
+
+/// Error returned when a deployed container is not constrained to its reservation
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Container {container_id} resource limits do not match reservation: {}",
+    format_mismatches(.mismatches)
+)]
+pub struct ResourceEnforcementError {
+    pub container_id: String,
+    pub mismatches: Vec<ResourceMismatch>,
+}
+
+/// Step of a deployment that runs under the deployment timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeployPhase {
+    /// Pulling the image onto the executor
+    Pull,
+    /// Creating the container
+    Create,
+    /// Starting the created container
+    Start,
+}
+
+impl DeployPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeployPhase::Pull => "pull",
+            DeployPhase::Create => "create",
+            DeployPhase::Start => "start",
+        }
+    }
+}
+
+impl fmt::Display for DeployPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DeployPhase::Pull => "image pull",
+            DeployPhase::Create => "container create",
+            DeployPhase::Start => "container start",
+        })
+    }
+}
+
+/// Error returned when a deployment does not finish within the deployment timeout
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Deployment timed out after {}s during {phase}", .timeout.as_secs())]
+pub struct DeploymentTimeoutError {
+    /// Phase that was still running when the timeout expired
+    pub phase: DeployPhase,
+    pub timeout: Duration,
+}
+
+/// Error returned when the executor lacks the disk space a deployment needs
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(
+    "Insufficient disk space on executor: {} MB free but {} MB needed ({} MB requested storage plus {} MB headroom); free space on the executor or request less storage",
+    .available_bytes / BYTES_PER_MB,
+    .required_bytes() / BYTES_PER_MB,
+    .requested_bytes / BYTES_PER_MB,
+    .headroom_bytes / BYTES_PER_MB
+)]
+pub struct InsufficientDiskError {
+    /// Storage reserved by the container spec
+    pub requested_bytes: u64,
+    /// Margin kept free for image layers and container writes
+    pub headroom_bytes: u64,
+    /// Free space reported by the executor
+    pub available_bytes: u64,
+}
+
+impl InsufficientDiskError {
+    pub fn required_bytes(&self) -> u64 {
+        self.requested_bytes.saturating_add(self.headroom_bytes)
+    }
+}
+
+/// Container runtime steps a deployment is made of
+#[async_trait]
+pub trait ContainerRuntime: Send + Sync {
+    /// Pull `image`, logging in to its registry with `auth` when given
+    async fn pull_image(&self, image: &str, auth: Option<&RegistryAuth>) -> Result<()>;
+    /// Repository digests of a local image, or `None` if it is not on the host
+    async fn image_digests(&self, image: &str) -> Result<Option<Vec<String>>>;
+    /// Create the container with `secrets` added to its environment
+    async fn create_container(
+        &self,
+        spec: &ContainerSpec,
+        secrets: &SecretEnv,
+        rental_id: &str,
+    ) -> Result<String>;
+    async fn start_container(
+        &self,
+        container_id: &str,
+        spec: &ContainerSpec,
+        rental_id: &str,
+    ) -> Result<ContainerInfo>;
+    /// Remove whatever container exists for the rental
+    async fn remove_rental_container(&self, rental_id: &str) -> Result<()>;
+    /// Free space on the filesystem holding container images and layers
+    async fn available_disk_bytes(&self) -> Result<u64>;
+    /// Indices of the GPUs on the host
+    async fn gpu_device_ids(&self) -> Result<Vec<u32>>;
+}
+
+#[async_trait]
+impl ContainerRuntime for ContainerClient {
+    async fn pull_image(&self, image: &str, auth: Option<&RegistryAuth>) -> Result<()> {
+        ContainerClient::pull_image(self, image, auth).await
+    }
+
+    async fn image_digests(&self, image: &str) -> Result<Option<Vec<String>>> {
+        ContainerClient::image_digests(self, image).await
+    }
+
+    async fn create_container(
+        &self,
+        spec: &ContainerSpec,
+        secrets: &SecretEnv,
+        rental_id: &str,
+    ) -> Result<String> {
+        ContainerClient::create_container(self, spec, secrets, rental_id).await
+    }
+
+    async fn start_container(
+        &self,
+        container_id: &str,
+        spec: &ContainerSpec,
+        rental_id: &str,
+    ) -> Result<ContainerInfo> {
+        ContainerClient::start_container(self, container_id, spec, rental_id).await
+    }
+
+    async fn remove_rental_container(&self, rental_id: &str) -> Result<()> {
+        ContainerClient::remove_rental_container(self, rental_id).await
+    }
+
+    async fn available_disk_bytes(&self) -> Result<u64> {
+        ContainerClient::available_disk_bytes(self).await
+    }
+
+    async fn gpu_device_ids(&self) -> Result<Vec<u32>> {
+        ContainerClient::gpu_device_ids(self).await
+    }
+}
+
+/// Time a container gets to exit after SIGTERM before it is killed
+pub const CONTAINER_STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Container operations used to stop a rental's container
+#[async_trait]
+pub trait ContainerStopOps: Send + Sync {
+    /// Run `command` inside the container and wait for it to exit
+    async fn exec_in_container(&self, container_id: &str, command: &[String]) -> Result<()>;
+    /// Send SIGTERM, then SIGKILL if the container outlives `timeout`, as
+    /// [`crate::os_process::ProcessTerminator::terminate`] does for processes
+    async fn terminate_container(&self, container_id: &str, timeout: Duration) -> Result<()>;
+    /// Send SIGKILL
+    async fn kill_container(&self, container_id: &str) -> Result<()>;
+    async fn remove_container(&self, container_id: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl ContainerStopOps for ContainerClient {
+    async fn exec_in_container(&self, container_id: &str, command: &[String]) -> Result<()> {
+        ContainerClient::exec_in_container(self, container_id, command).await
+    }
+
+    async fn terminate_container(&self, container_id: &str, timeout: Duration) -> Result<()> {
+        ContainerClient::terminate_container(self, container_id, timeout).await
+    }
+
+    async fn kill_container(&self, container_id: &str) -> Result<()> {
+        ContainerClient::stop_container(self, container_id, true).await
+    }
+
+    async fn remove_container(&self, container_id: &str) -> Result<()> {
+        ContainerClient::remove_container(self, container_id).await
+    }
+}
 
 /// Container deployment manager
 pub struct DeploymentManager {
     /// Deployment configuration
     config: DeploymentConfig,
+    /// Requested image -> digest reference recorded on its first pull
+    pinned_images: Arc<RwLock<HashMap<String, String>>>,
+    /// Executors the configured images have been pre-pulled on
+    prepulled_executors: Mutex<HashSet<String>>,
 }
 
 /// Deployment configuration
@@ -27,6 +250,16 @@ pub struct DeploymentConfig {
     pub default_resource_limits: DefaultResourceLimits,
     /// Network policies
     pub network_policies: NetworkPolicies,
+    /// Time allowed to pull, create and start the container
+    pub deploy_timeout: Duration,
+    /// Free disk space required on the executor beyond the requested storage
+    pub disk_headroom_gb: u64,
+    /// Secrets container specs may reference by name
+    pub secret_store: RentalSecretStore,
+    /// Images rentals may run
+    pub image_policy: ImagePolicyConfig,
+    /// Images pre-pulled on executors and digest pinning
+    pub image_cache: ImageCacheConfig,
 }
 
 /// Default resource limits
@@ -66,6 +299,11 @@ impl Default for DeploymentConfig {
                 blocked_ports: vec![22, 111, 2049],
                 require_network_isolation: false,
             },
+            deploy_timeout: Duration::from_secs(600),
+            disk_headroom_gb: 20,
+            secret_store: RentalSecretStore::default(),
+            image_policy: ImagePolicyConfig::default(),
+            image_cache: ImageCacheConfig::default(),
         }
     }
 }
@@ -79,14 +317,30 @@ impl Default for DeploymentManager {
 impl DeploymentManager {
     /// Create a new deployment manager
     pub fn new() -> Self {
-        Self {
-            config: DeploymentConfig::default(),
-        }
+        Self::with_config(DeploymentConfig::default())
     }
 
     /// Create with custom configuration
     pub fn with_config(config: DeploymentConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            pinned_images: Arc::new(RwLock::new(HashMap::new())),
+            prepulled_executors: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Digest reference recorded for `image`, if pinned
+    pub async fn pinned_image(&self, image: &str) -> Option<String> {
+        self.pinned_images.read().await.get(image).cloned()
+    }
+
+    /// Check that `owner` owns every secret the references name
+    pub fn check_secret_access(
+        &self,
+        owner: Option<&str>,
+        refs: &[SecretRef],
+    ) -> std::result::Result<(), SecretAccessDenied> {
+        self.config.secret_store.check_access(owner, refs)
     }
 
     /// Deploy a container
@@ -107,11 +361,25 @@ impl DeploymentManager {
         let secured_spec = self.apply_security_policies(spec)?;
 
         // Deploy the container
-        let container_info = client
-            .deploy_container(&secured_spec, rental_id)
+        let container_info = self
+            .run_deploy_phases(client, &secured_spec, rental_id)
             .await
             .context("Failed to deploy container")?;
 
+        // Confirm the runtime constrained the container to what was reserved
+        if let Err(e) = self
+            .verify_container_resources(client, &container_info.container_id, &secured_spec)
+            .await
+        {
+            if let Err(cleanup_err) = client.remove_container(&container_info.container_id).await {
+                warn!(
+                    "Failed to remove container {} after resource verification failure: {}",
+                    container_info.container_id, cleanup_err
+                );
+            }
+            return Err(e);
+        }
+
         // Only configure SSH if the container is expected to stay running
         let has_interactive_entrypoint = secured_spec.entrypoint.is_empty()
             || secured_spec
@@ -149,21 +417,328 @@ impl DeploymentManager {
             container_info.container_id, rental_id
         );
 
+        self.spawn_prepull(client);
+
         Ok(container_info)
     }
 
+    /// Pull, create and start the container within the deployment timeout.
+    ///
+    /// Images the image policy does not allow are refused before anything
+    /// else happens. The image is then made available as described in
+    /// [`ensure_image`](Self::ensure_image). The executor's free disk space is checked next;
+    /// deployments that would leave less than the configured headroom fail
+    /// with an [`InsufficientDiskError`] before anything is pulled. On
+    /// timeout the phase in progress is abandoned, any container created
+    /// for the rental is removed and a [`DeploymentTimeoutError`] naming the
+    /// phase is returned.
+    pub async fn run_deploy_phases(
+        &self,
+        runtime: &dyn ContainerRuntime,
+        spec: &ContainerSpec,
+        rental_id: &str,
+    ) -> Result<ContainerInfo> {
+        self.config
+            .image_policy
+            .check(&spec.image)
+            .map_err(anyhow::Error::msg)?;
+
+        let secrets = self
+            .config
+            .secret_store
+            .resolve(spec.secrets_owner.as_deref(), &spec.secrets)
+            .context("Failed to resolve rental secrets")?;
+
+        self.check_disk_space(runtime, spec, rental_id).await?;
+        self.check_gpu_devices(runtime, spec).await?;
+
+        let timeout = self.config.deploy_timeout;
+        let deadline = Instant::now() + timeout;
+        let timed_out = |phase| DeploymentTimeoutError { phase, timeout };
+
+        let phase = DeployPhase::Pull;
+        let result = tokio::time::timeout_at(deadline, self.ensure_image(runtime, spec)).await;
+        let image = match result {
+            Ok(image) => image?,
+            Err(_) => {
+                // Nothing exists on the executor yet
+                warn!(
+                    "Deployment of rental {} timed out during {}",
+                    rental_id, phase
+                );
+                return Err(timed_out(phase).into());
+            }
+        };
+        let pinned_spec;
+        let spec = if image == spec.image {
+            spec
+        } else {
+            pinned_spec = ContainerSpec {
+                image,
+                ..spec.clone()
+            };
+            &pinned_spec
+        };
+
+        let phase = DeployPhase::Create;
+        let result = tokio::time::timeout_at(
+            deadline,
+            runtime.create_container(spec, &secrets, rental_id),
+        )
+        .await;
+        let container_id = match result {
+            Ok(created) => created?,
+            Err(_) => {
+                self.cleanup_after_timeout(runtime, rental_id, phase).await;
+                return Err(timed_out(phase).into());
+            }
+        };
+
+        let phase = DeployPhase::Start;
+        let result = tokio::time::timeout_at(
+            deadline,
+            runtime.start_container(&container_id, spec, rental_id),
+        )
+        .await;
+        match result {
+            Ok(Ok(info)) => Ok(info),
+            Ok(Err(e)) => {
+                // Do not leave a created container behind for a failed start
+                if let Err(cleanup_err) = runtime.remove_rental_container(rental_id).await {
+                    warn!(
+                        "Failed to remove container for rental {} after start failure: {}",
+                        rental_id, cleanup_err
+                    );
+                }
+                Err(e)
+            }
+            Err(_) => {
+                self.cleanup_after_timeout(runtime, rental_id, phase).await;
+                Err(timed_out(phase).into())
+            }
+        }
+    }
+
+    /// Make the rental's image available on the executor, returning the
+    /// reference to create the container from
+    ///
+    /// Pre-pulled images already on the executor are not pulled again. With
+    /// digest pinning the digest an `image:tag` resolves to on first pull is
+    /// recorded, and later rentals of the same reference run that digest.
+    /// Images pulled with registry credentials are always pulled, so a copy
+    /// cached on the host never bypasses the registry's access check.
+    async fn ensure_image(
+        &self,
+        runtime: &dyn ContainerRuntime,
+        spec: &ContainerSpec,
+    ) -> Result<String> {
+        let image = spec.image.as_str();
+        if let Some(auth) = spec.registry_auth.as_ref() {
+            runtime.pull_image(image, Some(auth)).await?;
+            return Ok(image.to_string());
+        }
+
+        if let Some(pinned) = self.pinned_image(image).await {
+            if runtime.image_digests(&pinned).await?.is_none() {
+                info!(
+                    "Pinned image {} for {} is not on the executor, pulling",
+                    pinned, image
+                );
+                runtime.pull_image(&pinned, None).await?;
+            }
+            return Ok(pinned);
+        }
+
+        let prepulled = self.config.image_cache.prepull.iter().any(|p| p == image);
+        let digests = match runtime.image_digests(image).await? {
+            Some(digests) if prepulled => {
+                debug!("Image {} was pre-pulled on the executor", image);
+                digests
+            }
+            _ => {
+                runtime.pull_image(image, None).await?;
+                runtime.image_digests(image).await?.unwrap_or_default()
+            }
+        };
+
+        if !self.config.image_cache.pin_digests {
+            return Ok(image.to_string());
+        }
+
+        match select_repo_digest(image, digests) {
+            Some(digest) => {
+                info!("Pinned {} to {}", image, digest);
+                self.pinned_images
+                    .write()
+                    .await
+                    .insert(image.to_string(), digest.clone());
+                Ok(digest)
+            }
+            None => {
+                debug!("Image {} has no repository digest, not pinning", image);
+                Ok(image.to_string())
+            }
+        }
+    }
+
+    /// Pre-pull the configured images on `client`'s executor in the
+    /// background, once per executor
+    fn spawn_prepull(&self, client: &ContainerClient) {
+        if self.config.image_cache.prepull.is_empty() {
+            return;
+        }
+        let first_deploy = self
+            .prepulled_executors
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(client.ssh_connection.clone());
+        if !first_deploy {
+            return;
+        }
+
+        let client = client.clone();
+        let images = self.config.image_cache.prepull.clone();
+        tokio::spawn(async move { prepull_images(&client, &images).await });
+    }
+
+    /// Refuse the deployment if the executor cannot fit the requested storage
+    /// plus the configured headroom
+    ///
+    /// Executors whose free space cannot be read are let through, as before.
+    async fn check_disk_space(
+        &self,
+        runtime: &dyn ContainerRuntime,
+        spec: &ContainerSpec,
+        rental_id: &str,
+    ) -> Result<()> {
+        let available_bytes = match runtime.available_disk_bytes().await {
+            Ok(available) => available,
+            Err(e) => {
+                warn!(
+                    "Could not read free disk space for rental {}, skipping disk preflight: {}",
+                    rental_id, e
+                );
+                return Ok(());
+            }
+        };
+
+        let err = InsufficientDiskError {
+            requested_bytes: (spec.resources.storage_mb.max(0) as u64).saturating_mul(BYTES_PER_MB),
+            headroom_bytes: self
+                .config
+                .disk_headroom_gb
+                .saturating_mul(1024 * BYTES_PER_MB),
+            available_bytes,
+        };
+        if available_bytes >= err.required_bytes() {
+            debug!(
+                "Executor has {} MB free for rental {}",
+                available_bytes / BYTES_PER_MB,
+                rental_id
+            );
+            return Ok(());
+        }
+
+        error!("Refusing deployment of rental {}: {}", rental_id, err);
+        Err(err.into())
+    }
+
+    /// Refuse the deployment if a requested GPU device is not on the host
+    async fn check_gpu_devices(
+        &self,
+        runtime: &dyn ContainerRuntime,
+        spec: &ContainerSpec,
+    ) -> Result<()> {
+        if spec.gpu_device_ids.is_empty() {
+            return Ok(());
+        }
+
+        let host_gpus = runtime.gpu_device_ids().await?;
+        let missing: Vec<String> = spec
+            .gpu_device_ids
+            .iter()
+            .filter(|id| !host_gpus.contains(id))
+            .map(u32::to_string)
+            .collect();
+        if !missing.is_empty() {
+            return Err(anyhow::anyhow!(
+                "GPU devices {} do not exist on the executor, which has {} GPUs",
+                missing.join(", "),
+                host_gpus.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Remove the rental's container after a timed out create or start
+    async fn cleanup_after_timeout(
+        &self,
+        runtime: &dyn ContainerRuntime,
+        rental_id: &str,
+        phase: DeployPhase,
+    ) {
+        warn!(
+            "Deployment of rental {} timed out during {}, removing its container",
+            rental_id, phase
+        );
+        if let Err(e) = runtime.remove_rental_container(rental_id).await {
+            warn!(
+                "Failed to remove container for rental {} after deployment timeout: {}",
+                rental_id, e
+            );
+        }
+    }
+
+    /// Inspect a deployed container and check its limits against the reservation
+    async fn verify_container_resources(
+        &self,
+        client: &ContainerClient,
+        container_id: &str,
+        spec: &ContainerSpec,
+    ) -> Result<()> {
+        let applied = client
+            .inspect_resource_limits(container_id)
+            .await
+            .context("Failed to inspect container resource limits")?;
+
+        let mismatches = verify_resource_limits(&spec.resources, &spec.gpu_allocation(), &applied);
+        if mismatches.is_empty() {
+            debug!("Container {} resource limits verified", container_id);
+            return Ok(());
+        }
+
+        let err = ResourceEnforcementError {
+            container_id: container_id.to_string(),
+            mismatches,
+        };
+        error!("{}", err);
+        Err(err.into())
+    }
+
     /// Stop a container
+    ///
+    /// A graceful stop first runs the `pre_stop` hook, waiting for it up to
+    /// its timeout, then sends SIGTERM and, after [`CONTAINER_STOP_TIMEOUT`],
+    /// SIGKILL. A forced stop kills the container straight away.
     pub async fn stop_container(
         &self,
-        client: &ContainerClient,
+        client: &dyn ContainerStopOps,
         container_id: &str,
+        pre_stop: Option<&PreStopHook>,
         force: bool,
     ) -> Result<()> {
         info!("Stopping container {}", container_id);
 
         // First try graceful stop
         if !force {
-            match client.stop_container(container_id, false).await {
+            if let Some(hook) = pre_stop {
+                Self::run_pre_stop_hook(client, container_id, hook).await;
+            }
+
+            match client
+                .terminate_container(container_id, CONTAINER_STOP_TIMEOUT)
+                .await
+            {
                 Ok(_) => {
                     info!("Container {} stopped gracefully", container_id);
                     return Ok(());
@@ -179,7 +754,7 @@ impl DeploymentManager {
 
         // Force stop if needed
         client
-            .stop_container(container_id, true)
+            .kill_container(container_id)
             .await
             .context("Failed to force stop container")?;
 
@@ -193,6 +768,37 @@ impl DeploymentManager {
         Ok(())
     }
 
+    /// Run a pre-stop hook, giving up once its timeout passes
+    ///
+    /// The container is stopped either way, so failures are only logged.
+    async fn run_pre_stop_hook(
+        client: &dyn ContainerStopOps,
+        container_id: &str,
+        hook: &PreStopHook,
+    ) {
+        info!(
+            "Running pre-stop command in container {} (timeout {:?})",
+            container_id, hook.timeout
+        );
+
+        match tokio::time::timeout(
+            hook.timeout,
+            client.exec_in_container(container_id, &hook.command),
+        )
+        .await
+        {
+            Ok(Ok(())) => info!("Pre-stop command finished in container {}", container_id),
+            Ok(Err(e)) => warn!(
+                "Pre-stop command failed in container {}: {}",
+                container_id, e
+            ),
+            Err(_) => warn!(
+                "Pre-stop command in container {} timed out after {:?}",
+                container_id, hook.timeout
+            ),
+        }
+    }
+
     /// Validate container specification
     fn validate_container_spec(&self, spec: &ContainerSpec) -> Result<()> {
         // Validate image
@@ -210,6 +816,11 @@ impl DeploymentManager {
         // Validate ports
         self.validate_ports(spec)?;
 
+        // Validate environment variable and secret names
+        spec.validate_environment().map_err(anyhow::Error::msg)?;
+
+        spec.validate_gpu_devices().map_err(anyhow::Error::msg)?;
+
         Ok(())
     }
 
@@ -575,3 +1186,776 @@ impl DeploymentManager {
         Ok(())
     }
 }
+
+/// Pull every image in `images` that is not on the executor yet, logging
+/// failures instead of aborting
+pub async fn prepull_images(runtime: &dyn ContainerRuntime, images: &[String]) {
+    for image in images {
+        match runtime.image_digests(image).await {
+            Ok(Some(_)) => debug!("Image {} already on the executor", image),
+            Ok(None) => match runtime.pull_image(image, None).await {
+                Ok(()) => info!("Pre-pulled image {}", image),
+                Err(e) => warn!("Failed to pre-pull image {}: {:#}", image, e),
+            },
+            Err(e) => warn!("Failed to check image {} on the executor: {:#}", image, e),
+        }
+    }
+}
+
+fn format_mismatches(mismatches: &[ResourceMismatch]) -> String {
+    mismatches
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Compare reserved resources against the limits applied to a container
+///
+/// Zero-valued reservations mean "no limit requested" and are not checked.
+/// GPUs reserved for the rental must be exposed exactly as `gpus`, the
+/// allocation the container was created with.
+pub fn verify_resource_limits(
+    requested: &ResourceRequirements,
+    gpus: &GpuAllocation,
+    applied: &AppliedResourceLimits,
+) -> Vec<ResourceMismatch> {
+    let mut mismatches = Vec::new();
+
+    if requested.cpu_cores > 0.0 {
+        let matches = applied
+            .cpu_cores
+            .map(|cores| (cores - requested.cpu_cores).abs() < CPU_LIMIT_TOLERANCE)
+            .unwrap_or(false);
+        if !matches {
+            mismatches.push(ResourceMismatch {
+                resource: "cpu",
+                expected: format!("{} cores", requested.cpu_cores),
+                actual: applied
+                    .cpu_cores
+                    .map(|cores| format!("{cores} cores"))
+                    .unwrap_or_else(|| "unlimited".to_string()),
+            });
+        }
+    }
+
+    if requested.memory_mb > 0 && applied.memory_mb != Some(requested.memory_mb) {
+        mismatches.push(ResourceMismatch {
+            resource: "memory",
+            expected: format!("{} MB", requested.memory_mb),
+            actual: applied
+                .memory_mb
+                .map(|mb| format!("{mb} MB"))
+                .unwrap_or_else(|| "unlimited".to_string()),
+        });
+    }
+
+    if requested.gpu_count > 0 && applied.gpus != *gpus {
+        mismatches.push(ResourceMismatch {
+            resource: "gpu",
+            expected: gpus.to_string(),
+            actual: applied.gpus.to_string(),
+        });
+    }
+
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rental::container_client::parse_applied_resource_limits;
+
+    fn requirements(cpu_cores: f64, memory_mb: i64, gpu_count: u32) -> ResourceRequirements {
+        ResourceRequirements {
+            cpu_cores,
+            memory_mb,
+            storage_mb: 0,
+            gpu_count,
+            gpu_types: vec![],
+        }
+    }
+
+    fn inspect_entry(nano_cpus: i64, memory_bytes: i64, gpu_count: i64) -> serde_json::Value {
+        serde_json::json!({
+            "HostConfig": {
+                "NanoCpus": nano_cpus,
+                "Memory": memory_bytes,
+                "DeviceRequests": [{
+                    "Driver": "",
+                    "Count": gpu_count,
+                    "DeviceIDs": null,
+                    "Capabilities": [["gpu"]],
+                }],
+            }
+        })
+    }
+
+    #[test]
+    fn test_matching_limits_pass() {
+        let applied =
+            parse_applied_resource_limits(&inspect_entry(4_000_000_000, 8192 * 1024 * 1024, -1));
+        assert_eq!(applied.cpu_cores, Some(4.0));
+        assert_eq!(applied.memory_mb, Some(8192));
+        assert_eq!(applied.gpus, GpuAllocation::All);
+
+        assert!(
+            verify_resource_limits(&requirements(4.0, 8192, 2), &GpuAllocation::All, &applied)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_mismatched_limits_are_reported() {
+        let applied =
+            parse_applied_resource_limits(&inspect_entry(8_000_000_000, 4096 * 1024 * 1024, 1));
+
+        let mismatches =
+            verify_resource_limits(&requirements(4.0, 8192, 2), &GpuAllocation::All, &applied);
+        let resources: Vec<&str> = mismatches.iter().map(|m| m.resource).collect();
+        assert_eq!(resources, vec!["cpu", "memory", "gpu"]);
+
+        let err = ResourceEnforcementError {
+            container_id: "abc123".to_string(),
+            mismatches,
+        };
+        assert!(err
+            .to_string()
+            .contains("memory reserved 8192 MB but container got 4096 MB"));
+    }
+
+    #[test]
+    fn test_unlimited_container_fails_limited_reservation() {
+        let applied = parse_applied_resource_limits(&serde_json::json!({ "HostConfig": {} }));
+        assert_eq!(applied.cpu_cores, None);
+        assert_eq!(applied.memory_mb, None);
+        assert_eq!(applied.gpus, GpuAllocation::None);
+
+        // Nothing reserved, nothing to enforce
+        assert!(
+            verify_resource_limits(&requirements(0.0, 0, 0), &GpuAllocation::All, &applied)
+                .is_empty()
+        );
+        assert_eq!(
+            verify_resource_limits(&requirements(2.0, 0, 1), &GpuAllocation::All, &applied).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_gpu_devices_must_match_selection() {
+        let applied = parse_applied_resource_limits(&serde_json::json!({
+            "HostConfig": {
+                "NanoCpus": 4_000_000_000i64,
+                "DeviceRequests": [{
+                    "Driver": "",
+                    "Count": 0,
+                    "DeviceIDs": ["1", "3"],
+                    "Capabilities": [["gpu"]],
+                }],
+            }
+        }));
+        assert_eq!(applied.gpus, GpuAllocation::Devices(vec![1, 3]));
+
+        let requested = requirements(4.0, 0, 2);
+        assert!(
+            verify_resource_limits(&requested, &GpuAllocation::Devices(vec![1, 3]), &applied)
+                .is_empty()
+        );
+
+        // The right number of GPUs, but not the selected ones
+        let mismatches =
+            verify_resource_limits(&requested, &GpuAllocation::Devices(vec![0, 1]), &applied);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(
+            mismatches[0].to_string(),
+            "gpu reserved GPU devices 0,1 but container got GPU devices 1,3"
+        );
+
+        // A container created with `--gpus all` must not come up with a subset
+        assert_eq!(
+            verify_resource_limits(&requested, &GpuAllocation::All, &applied).len(),
+            1
+        );
+    }
+
+    const DIGEST: &str = "ubuntu@sha256:0123456789abcdef";
+
+    /// Runtime that never finishes `stall_in` and records cleanups
+    struct StallingRuntime {
+        stall_in: Option<DeployPhase>,
+        available_disk: u64,
+        pulled: std::sync::Mutex<Vec<String>>,
+        pulled_with_auth: std::sync::Mutex<Vec<Option<String>>>,
+        removed: std::sync::Mutex<Vec<String>>,
+        created_with: std::sync::Mutex<Vec<SecretEnv>>,
+        created_gpu_ids: std::sync::Mutex<Vec<Vec<u32>>>,
+        created_images: std::sync::Mutex<Vec<String>>,
+        /// Images on the host and their repository digests
+        local_images: std::sync::Mutex<HashMap<String, Vec<String>>>,
+    }
+
+    impl StallingRuntime {
+        fn new(stall_in: Option<DeployPhase>) -> Self {
+            Self {
+                stall_in,
+                available_disk: u64::MAX,
+                pulled: std::sync::Mutex::new(Vec::new()),
+                pulled_with_auth: std::sync::Mutex::new(Vec::new()),
+                removed: std::sync::Mutex::new(Vec::new()),
+                created_with: std::sync::Mutex::new(Vec::new()),
+                created_gpu_ids: std::sync::Mutex::new(Vec::new()),
+                created_images: std::sync::Mutex::new(Vec::new()),
+                local_images: std::sync::Mutex::new(HashMap::new()),
+            }
+        }
+
+        fn with_local_image(self, image: &str, digest: &str) -> Self {
+            self.local_images
+                .lock()
+                .unwrap()
+                .insert(image.to_string(), vec![digest.to_string()]);
+            self
+        }
+
+        fn with_available_disk_gb(mut self, gb: u64) -> Self {
+            self.available_disk = gb * 1024 * BYTES_PER_MB;
+            self
+        }
+
+        async fn step(&self, phase: DeployPhase) {
+            if Some(phase) == self.stall_in {
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ContainerRuntime for StallingRuntime {
+        async fn pull_image(&self, image: &str, auth: Option<&RegistryAuth>) -> Result<()> {
+            self.pulled.lock().unwrap().push(image.to_string());
+            self.pulled_with_auth
+                .lock()
+                .unwrap()
+                .push(auth.map(|auth| auth.username.clone()));
+            self.step(DeployPhase::Pull).await;
+            let mut local = self.local_images.lock().unwrap();
+            local.insert(image.to_string(), vec![DIGEST.to_string()]);
+            local.insert(DIGEST.to_string(), vec![DIGEST.to_string()]);
+            Ok(())
+        }
+
+        async fn image_digests(&self, image: &str) -> Result<Option<Vec<String>>> {
+            Ok(self.local_images.lock().unwrap().get(image).cloned())
+        }
+
+        async fn create_container(
+            &self,
+            spec: &ContainerSpec,
+            secrets: &SecretEnv,
+            _rental_id: &str,
+        ) -> Result<String> {
+            self.created_with.lock().unwrap().push(secrets.clone());
+            self.created_images.lock().unwrap().push(spec.image.clone());
+            self.created_gpu_ids
+                .lock()
+                .unwrap()
+                .push(spec.gpu_device_ids.clone());
+            self.step(DeployPhase::Create).await;
+            Ok("abc123".to_string())
+        }
+
+        async fn start_container(
+            &self,
+            container_id: &str,
+            _spec: &ContainerSpec,
+            _rental_id: &str,
+        ) -> Result<ContainerInfo> {
+            self.step(DeployPhase::Start).await;
+            Ok(ContainerInfo {
+                container_id: container_id.to_string(),
+                container_name: "basilica-rental-rental-1".to_string(),
+                mapped_ports: Vec::new(),
+                status: "running".to_string(),
+                labels: Default::default(),
+            })
+        }
+
+        async fn remove_rental_container(&self, rental_id: &str) -> Result<()> {
+            self.removed.lock().unwrap().push(rental_id.to_string());
+            Ok(())
+        }
+
+        async fn available_disk_bytes(&self) -> Result<u64> {
+            Ok(self.available_disk)
+        }
+
+        async fn gpu_device_ids(&self) -> Result<Vec<u32>> {
+            Ok(vec![0, 1, 2, 3])
+        }
+    }
+
+    fn spec() -> ContainerSpec {
+        ContainerSpec {
+            image: "ubuntu:22.04".to_string(),
+            environment: Default::default(),
+            ports: Vec::new(),
+            resources: requirements(1.0, 1024, 0),
+            entrypoint: Vec::new(),
+            command: Vec::new(),
+            volumes: Vec::new(),
+            labels: Default::default(),
+            capabilities: Vec::new(),
+            network: crate::rental::NetworkConfig {
+                mode: "bridge".to_string(),
+                dns: Vec::new(),
+                extra_hosts: Default::default(),
+            },
+            pre_stop: None,
+            secrets: Vec::new(),
+            secrets_owner: None,
+            registry_auth: None,
+            runtime: Default::default(),
+            gpu_device_ids: Vec::new(),
+        }
+    }
+
+    fn manager(deploy_timeout: Duration) -> DeploymentManager {
+        DeploymentManager::with_config(DeploymentConfig {
+            deploy_timeout,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_deploy_timeout_reports_stalled_phase() {
+        for phase in [DeployPhase::Pull, DeployPhase::Create, DeployPhase::Start] {
+            let runtime = StallingRuntime::new(Some(phase));
+            let err = manager(Duration::from_millis(50))
+                .run_deploy_phases(&runtime, &spec(), "rental-1")
+                .await
+                .unwrap_err();
+
+            let timeout = err
+                .downcast_ref::<DeploymentTimeoutError>()
+                .unwrap_or_else(|| panic!("expected a timeout error, got {err:#}"));
+            assert_eq!(timeout.phase, phase);
+            assert!(err.to_string().contains(&phase.to_string()));
+
+            // A container only exists to clean up once create has been attempted
+            let removed = runtime.removed.lock().unwrap().clone();
+            if phase == DeployPhase::Pull {
+                assert!(removed.is_empty());
+            } else {
+                assert_eq!(removed, vec!["rental-1".to_string()]);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deploy_within_timeout_succeeds() {
+        let runtime = StallingRuntime::new(None);
+        let info = manager(Duration::from_secs(5))
+            .run_deploy_phases(&runtime, &spec(), "rental-1")
+            .await
+            .unwrap();
+
+        assert_eq!(info.container_id, "abc123");
+        assert!(runtime.removed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_low_disk_executor_rejects_deploy() {
+        // 5 GB requested storage plus 20 GB headroom does not fit in 24 GB
+        let runtime = StallingRuntime::new(None).with_available_disk_gb(24);
+        let mut spec = spec();
+        spec.resources.storage_mb = 5 * 1024;
+
+        let err = manager(Duration::from_secs(5))
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap_err();
+
+        let disk = err
+            .downcast_ref::<InsufficientDiskError>()
+            .unwrap_or_else(|| panic!("expected a disk space error, got {err:#}"));
+        assert_eq!(disk.required_bytes(), 25 * 1024 * BYTES_PER_MB);
+        assert!(err
+            .to_string()
+            .contains("24576 MB free but 25600 MB needed"));
+
+        // Nothing is pulled onto a full executor
+        assert!(runtime.pulled.lock().unwrap().is_empty());
+        assert!(runtime.removed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sufficient_disk_executor_allows_deploy() {
+        let runtime = StallingRuntime::new(None).with_available_disk_gb(25);
+        let mut spec = spec();
+        spec.resources.storage_mb = 5 * 1024;
+
+        let info = manager(Duration::from_secs(5))
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap();
+
+        assert_eq!(info.container_id, "abc123");
+        assert_eq!(*runtime.pulled.lock().unwrap(), vec!["ubuntu:22.04"]);
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_resolved_at_deploy_time() {
+        let aead = basilica_common::crypto::Aead::new(
+            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        )
+        .unwrap();
+        let stored = std::collections::HashMap::from([(
+            "alice".to_string(),
+            std::collections::HashMap::from([(
+                "wandb".to_string(),
+                aead.encrypt("wandb-s3cr3t").unwrap(),
+            )]),
+        )]);
+        let manager = DeploymentManager::with_config(DeploymentConfig {
+            secret_store: RentalSecretStore::new(Some(aead), stored),
+            ..Default::default()
+        });
+        let mut spec = spec();
+        spec.secrets = vec![crate::rental::SecretRef {
+            env: "WANDB_API_KEY".to_string(),
+            name: "wandb".to_string(),
+        }];
+        spec.secrets_owner = Some("alice".to_string());
+
+        let runtime = StallingRuntime::new(None);
+        manager
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap();
+        let created_with = runtime.created_with.lock().unwrap();
+        assert_eq!(
+            created_with[0].vars().collect::<Vec<_>>(),
+            [("WANDB_API_KEY", "wandb-s3cr3t")]
+        );
+
+        // Another user's secret fails the deployment before anything is pulled
+        spec.secrets_owner = Some("mallory".to_string());
+        let runtime = StallingRuntime::new(None);
+        let err = manager
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap_err();
+        assert!(
+            err.downcast_ref::<SecretAccessDenied>().is_some(),
+            "{err:#}"
+        );
+        assert!(runtime.pulled.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_denied_image_is_not_deployed() {
+        let manager = DeploymentManager::with_config(DeploymentConfig {
+            image_policy: ImagePolicyConfig {
+                allow: Vec::new(),
+                deny: vec!["docker.io/library/ubuntu:*".to_string()],
+            },
+            ..Default::default()
+        });
+
+        let runtime = StallingRuntime::new(None);
+        let err = manager
+            .run_deploy_phases(&runtime, &spec(), "rental-1")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("denied by image policy"), "{err}");
+        assert!(runtime.pulled.lock().unwrap().is_empty());
+        assert!(runtime.created_with.lock().unwrap().is_empty());
+
+        // Images the policy allows still deploy
+        let mut spec = spec();
+        spec.image = "nvidia/cuda:12.2.0-base-ubuntu22.04".to_string();
+        manager
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_registry_auth_is_used_for_the_pull() {
+        let mut spec = spec();
+        spec.image = "ghcr.io/acme/trainer:1.0".to_string();
+        spec.registry_auth = Some(RegistryAuth {
+            registry: "ghcr.io".to_string(),
+            username: "ci".to_string(),
+            token: "s3cr3t-token".to_string(),
+        });
+
+        let runtime = StallingRuntime::new(None);
+        manager(Duration::from_secs(5))
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap();
+        assert_eq!(
+            *runtime.pulled_with_auth.lock().unwrap(),
+            vec![Some("ci".to_string())]
+        );
+
+        // The credentials are not persisted with the rental's spec
+        let stored = serde_json::to_value(&spec).unwrap();
+        assert!(stored.get("registry_auth").is_none());
+    }
+
+    fn caching_manager(image_cache: ImageCacheConfig) -> DeploymentManager {
+        DeploymentManager::with_config(DeploymentConfig {
+            deploy_timeout: Duration::from_secs(5),
+            image_cache,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_prepulled_image_skips_pull_on_rental_start() {
+        let manager = caching_manager(ImageCacheConfig {
+            prepull: vec!["ubuntu:22.04".to_string()],
+            pin_digests: false,
+        });
+        let runtime = StallingRuntime::new(None).with_local_image("ubuntu:22.04", DIGEST);
+
+        manager
+            .run_deploy_phases(&runtime, &spec(), "rental-1")
+            .await
+            .unwrap();
+        assert!(runtime.pulled.lock().unwrap().is_empty());
+        assert_eq!(
+            *runtime.created_images.lock().unwrap(),
+            vec!["ubuntu:22.04".to_string()]
+        );
+
+        // Pre-pulling skips images the executor already has
+        let runtime = StallingRuntime::new(None).with_local_image("ubuntu:22.04", DIGEST);
+        prepull_images(
+            &runtime,
+            &["ubuntu:22.04".to_string(), "ubuntu:24.04".to_string()],
+        )
+        .await;
+        assert_eq!(
+            *runtime.pulled.lock().unwrap(),
+            vec!["ubuntu:24.04".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pinned_digest_is_reused() {
+        let manager = caching_manager(ImageCacheConfig {
+            prepull: Vec::new(),
+            pin_digests: true,
+        });
+        let runtime = StallingRuntime::new(None);
+
+        manager
+            .run_deploy_phases(&runtime, &spec(), "rental-1")
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.pinned_image("ubuntu:22.04").await.as_deref(),
+            Some(DIGEST)
+        );
+
+        // The tag moving on the host does not change what rentals get
+        runtime.local_images.lock().unwrap().insert(
+            "ubuntu:22.04".to_string(),
+            vec!["ubuntu@sha256:ffff".to_string()],
+        );
+        manager
+            .run_deploy_phases(&runtime, &spec(), "rental-2")
+            .await
+            .unwrap();
+        assert_eq!(runtime.pulled.lock().unwrap().len(), 1);
+        assert_eq!(
+            *runtime.created_images.lock().unwrap(),
+            vec![DIGEST.to_string(), DIGEST.to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_pull_is_never_skipped() {
+        let image = "ghcr.io/acme/trainer:1.0";
+        let manager = caching_manager(ImageCacheConfig {
+            prepull: vec![image.to_string()],
+            pin_digests: true,
+        });
+        let runtime = StallingRuntime::new(None).with_local_image(image, DIGEST);
+        let mut spec = spec();
+        spec.image = image.to_string();
+        spec.registry_auth = Some(RegistryAuth {
+            registry: "ghcr.io".to_string(),
+            username: "ci".to_string(),
+            token: "s3cr3t-token".to_string(),
+        });
+
+        manager
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap();
+        assert_eq!(*runtime.pulled.lock().unwrap(), vec![image.to_string()]);
+        assert_eq!(manager.pinned_image(image).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_gpu_device_ids_are_passed_to_create() {
+        let mut spec = spec();
+        spec.resources.gpu_count = 2;
+        spec.gpu_device_ids = vec![1, 3];
+
+        let runtime = StallingRuntime::new(None);
+        manager(Duration::from_secs(5))
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap();
+        assert_eq!(*runtime.created_gpu_ids.lock().unwrap(), vec![vec![1, 3]]);
+
+        // A device the host does not have fails before anything is pulled
+        spec.gpu_device_ids = vec![1, 7];
+        let runtime = StallingRuntime::new(None);
+        let err = manager(Duration::from_secs(5))
+            .run_deploy_phases(&runtime, &spec, "rental-1")
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("GPU devices 7 do not exist on the executor, which has 4 GPUs"),
+            "{err}"
+        );
+        assert!(runtime.pulled.lock().unwrap().is_empty());
+        assert!(runtime.created_gpu_ids.lock().unwrap().is_empty());
+    }
+
+    /// Container whose pre-stop command takes `exec_duration`, recording
+    /// each stop step with the time it happened
+    struct RecordingStopOps {
+        exec_duration: Duration,
+        started: Instant,
+        steps: std::sync::Mutex<Vec<(&'static str, Duration)>>,
+    }
+
+    impl RecordingStopOps {
+        fn new(exec_duration: Duration) -> Self {
+            Self {
+                exec_duration,
+                started: Instant::now(),
+                steps: std::sync::Mutex::new(Vec::new()),
+            }
+        }
+
+        fn record(&self, step: &'static str) {
+            let at = self.started.elapsed();
+            self.steps.lock().unwrap().push((step, at));
+        }
+
+        fn steps(&self) -> Vec<(&'static str, Duration)> {
+            self.steps.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ContainerStopOps for RecordingStopOps {
+        async fn exec_in_container(&self, _container_id: &str, command: &[String]) -> Result<()> {
+            assert_eq!(command, ["python", "checkpoint.py"]);
+            self.record("exec");
+            tokio::time::sleep(self.exec_duration).await;
+            self.record("exec done");
+            Ok(())
+        }
+
+        async fn terminate_container(&self, _container_id: &str, timeout: Duration) -> Result<()> {
+            assert_eq!(timeout, CONTAINER_STOP_TIMEOUT);
+            self.record("terminate");
+            Ok(())
+        }
+
+        async fn kill_container(&self, _container_id: &str) -> Result<()> {
+            self.record("kill");
+            Ok(())
+        }
+
+        async fn remove_container(&self, _container_id: &str) -> Result<()> {
+            self.record("remove");
+            Ok(())
+        }
+    }
+
+    fn checkpoint_hook(timeout: Duration) -> PreStopHook {
+        PreStopHook {
+            command: vec!["python".to_string(), "checkpoint.py".to_string()],
+            timeout,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_container_stopped_after_pre_stop_command_completes() {
+        let ops = RecordingStopOps::new(Duration::from_secs(5));
+        let hook = checkpoint_hook(Duration::from_secs(30));
+
+        DeploymentManager::new()
+            .stop_container(&ops, "abc123", Some(&hook), false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ops.steps(),
+            vec![
+                ("exec", Duration::ZERO),
+                ("exec done", Duration::from_secs(5)),
+                ("terminate", Duration::from_secs(5)),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_container_stopped_when_pre_stop_command_times_out() {
+        let ops = RecordingStopOps::new(Duration::from_secs(3600));
+        let hook = checkpoint_hook(Duration::from_secs(30));
+
+        DeploymentManager::new()
+            .stop_container(&ops, "abc123", Some(&hook), false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ops.steps(),
+            vec![
+                ("exec", Duration::ZERO),
+                ("terminate", Duration::from_secs(30)),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_forced_stop_skips_pre_stop_command() {
+        let ops = RecordingStopOps::new(Duration::from_secs(5));
+        let hook = checkpoint_hook(Duration::from_secs(30));
+
+        DeploymentManager::new()
+            .stop_container(&ops, "abc123", Some(&hook), true)
+            .await
+            .unwrap();
+
+        let steps: Vec<&str> = ops.steps().into_iter().map(|(step, _)| step).collect();
+        assert_eq!(steps, vec!["kill", "remove"]);
+    }
+
+    #[test]
+    fn test_pre_stop_hook_from_request() {
+        assert_eq!(PreStopHook::from_request(Vec::new(), None), Ok(None));
+        assert!(PreStopHook::from_request(Vec::new(), Some(10)).is_err());
+
+        let hook = PreStopHook::from_request(vec!["sync".to_string()], None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(hook.timeout, crate::rental::DEFAULT_PRE_STOP_TIMEOUT);
+
+        assert!(PreStopHook::from_request(vec!["sync".to_string()], Some(0)).is_err());
+        assert!(PreStopHook::from_request(vec!["sync".to_string()], Some(601)).is_err());
+    }
+}
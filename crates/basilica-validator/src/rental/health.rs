@@ -0,0 +1,643 @@
+//! Escalation of repeated health check failures
+//!
+//! A single failed health check does not end an active rental. Failures are
+//! counted per rental, and once `failure_threshold` checks in a row have
+//! failed within `failure_window_secs`, the rental is marked unhealthy and a
+//! [`RentalHealthEvent`] is emitted. With `auto_terminate`, the rental is
+//! also stopped through its [`RentalStopper`] so the renter is not billed for
+//! a dead container. A passing check resets the count and returns an
+//! unhealthy rental to active.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, error, warn};
+
+use super::container_client::ContainerClient;
+use super::types::{ContainerStatus, RentalInfo, RentalState};
+
+/// Capacity of the health event channel; slow subscribers miss older events
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// When repeated health check failures escalate, set globally or per rental
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthEscalationPolicy {
+    /// Consecutive failed checks that mark the rental unhealthy
+    pub failure_threshold: u32,
+    /// Failures older than this no longer count towards the threshold
+    pub failure_window_secs: u64,
+    /// Stop the container once the rental is marked unhealthy
+    pub auto_terminate: bool,
+}
+
+impl Default for HealthEscalationPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 3,
+            failure_window_secs: 300,
+            auto_terminate: false,
+        }
+    }
+}
+
+impl HealthEscalationPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.failure_threshold == 0 {
+            return Err("failure_threshold must be at least 1".to_string());
+        }
+        if self.failure_window_secs == 0 {
+            return Err("failure_window_secs must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+
+    fn failure_window(&self) -> Duration {
+        Duration::from_secs(self.failure_window_secs)
+    }
+}
+
+/// Health transitions published by the health monitor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RentalHealthEvent {
+    /// The rental crossed its failure threshold
+    Unhealthy {
+        rental_id: String,
+        executor_id: String,
+        consecutive_failures: u32,
+        last_error: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// The container of an unhealthy rental was stopped
+    Terminated {
+        rental_id: String,
+        executor_id: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// An unhealthy rental passed a health check again
+    Recovered {
+        rental_id: String,
+        executor_id: String,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+/// Reason recorded in the state history for a transition made by
+/// [`HealthEscalator::check`]
+pub fn health_transition_reason(from: &RentalState, to: &RentalState) -> &'static str {
+    match (from, to) {
+        (RentalState::Provisioning, _) => "Health check failed while provisioning",
+        (RentalState::Stopping, RentalState::Failed) => "Health check timed out while stopping",
+        (RentalState::Stopping, _) => "Container stopped",
+        (RentalState::Unhealthy, RentalState::Active) => "Health check passed again",
+        (_, RentalState::Unhealthy) => "Repeated health check failures",
+        (_, RentalState::Stopped) => "Terminated after repeated health check failures",
+        _ => "Health check",
+    }
+}
+
+/// Container operations needed to check rental health
+#[async_trait]
+pub trait ContainerHealthOps: Send + Sync {
+    async fn get_container_status(&self, container_id: &str) -> Result<ContainerStatus>;
+}
+
+#[async_trait]
+impl ContainerHealthOps for ContainerClient {
+    async fn get_container_status(&self, container_id: &str) -> Result<ContainerStatus> {
+        ContainerClient::get_container_status(self, container_id).await
+    }
+}
+
+/// Stops rentals the monitor gives up on the same way a renter's stop
+/// request does: pre-stop hook, container, SSH session, state and metrics
+#[async_trait]
+pub trait RentalStopper: Send + Sync {
+    /// Stop `rental_id` and record it as stopped with `reason`
+    async fn terminate_rental(&self, rental_id: &str, reason: &str) -> Result<()>;
+}
+
+/// Counts health check failures per rental and decides state transitions
+pub struct HealthEscalator {
+    default_policy: HealthEscalationPolicy,
+    check_timeout: Duration,
+    failures: Mutex<HashMap<String, VecDeque<Instant>>>,
+    events: broadcast::Sender<RentalHealthEvent>,
+}
+
+impl HealthEscalator {
+    pub fn new(default_policy: HealthEscalationPolicy, check_timeout: Duration) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            default_policy,
+            check_timeout,
+            failures: Mutex::new(HashMap::new()),
+            events,
+        }
+    }
+
+    /// Receive health events for every monitored rental
+    pub fn subscribe(&self) -> broadcast::Receiver<RentalHealthEvent> {
+        self.events.subscribe()
+    }
+
+    /// Policy in force for a rental
+    pub fn policy_for(&self, rental: &RentalInfo) -> HealthEscalationPolicy {
+        rental.health_policy.unwrap_or(self.default_policy)
+    }
+
+    /// Health-check a rental's container at `now` and return its new state, if
+    /// it changed
+    ///
+    /// A terminated rental yields no transition: `stopper` records the
+    /// stopped state itself.
+    pub async fn check(
+        &self,
+        client: &dyn ContainerHealthOps,
+        stopper: &dyn RentalStopper,
+        rental: &RentalInfo,
+        now: Instant,
+    ) -> Option<RentalState> {
+        let result = tokio::time::timeout(
+            self.check_timeout,
+            perform_health_check(client, &rental.container_id),
+        )
+        .await;
+
+        let (failure, timed_out) = match result {
+            Err(_) => (Some("health check timed out".to_string()), true),
+            Ok(Err(e)) => (Some(e.to_string()), false),
+            Ok(Ok(false)) => (
+                Some("container is not running or reports unhealthy".to_string()),
+                false,
+            ),
+            Ok(Ok(true)) => (None, false),
+        };
+
+        match rental.state {
+            // Provisioning and stopping rentals are not given a grace period
+            RentalState::Provisioning => failure.map(|reason| {
+                warn!(
+                    "Health check failed for provisioning rental {}: {}",
+                    rental.rental_id, reason
+                );
+                RentalState::Failed
+            }),
+            RentalState::Stopping => failure.map(|_| {
+                if timed_out {
+                    RentalState::Failed
+                } else {
+                    RentalState::Stopped
+                }
+            }),
+            RentalState::Active | RentalState::Unhealthy => match failure {
+                Some(reason) => self.on_failure(stopper, rental, reason, now).await,
+                None => self.on_success(rental),
+            },
+            RentalState::Stopped | RentalState::Failed => None,
+        }
+    }
+
+    async fn on_failure(
+        &self,
+        stopper: &dyn RentalStopper,
+        rental: &RentalInfo,
+        reason: String,
+        now: Instant,
+    ) -> Option<RentalState> {
+        let policy = self.policy_for(rental);
+        let consecutive = self.record_failure(&rental.rental_id, &policy, now);
+
+        if rental.state == RentalState::Unhealthy {
+            // Already escalated; keep trying to terminate if a previous stop failed
+            if policy.auto_terminate {
+                self.terminate(stopper, rental).await;
+            }
+            return None;
+        }
+
+        if consecutive < policy.failure_threshold {
+            warn!(
+                "Health check failed for rental {} ({}/{}): {}",
+                rental.rental_id, consecutive, policy.failure_threshold, reason
+            );
+            return None;
+        }
+
+        error!(
+            "Rental {} failed {} consecutive health checks, marking unhealthy: {}",
+            rental.rental_id, consecutive, reason
+        );
+        self.emit(RentalHealthEvent::Unhealthy {
+            rental_id: rental.rental_id.clone(),
+            executor_id: rental.executor_id.clone(),
+            consecutive_failures: consecutive,
+            last_error: reason,
+            timestamp: Utc::now(),
+        });
+
+        if policy.auto_terminate && self.terminate(stopper, rental).await {
+            None
+        } else {
+            Some(RentalState::Unhealthy)
+        }
+    }
+
+    fn on_success(&self, rental: &RentalInfo) -> Option<RentalState> {
+        self.failures.lock().unwrap().remove(&rental.rental_id);
+        if rental.state != RentalState::Unhealthy {
+            debug!("Rental {} is healthy", rental.rental_id);
+            return None;
+        }
+
+        warn!(
+            "Unhealthy rental {} passed a health check",
+            rental.rental_id
+        );
+        self.emit(RentalHealthEvent::Recovered {
+            rental_id: rental.rental_id.clone(),
+            executor_id: rental.executor_id.clone(),
+            timestamp: Utc::now(),
+        });
+        Some(RentalState::Active)
+    }
+
+    /// Stop an unhealthy rental; returns whether it was stopped
+    async fn terminate(&self, stopper: &dyn RentalStopper, rental: &RentalInfo) -> bool {
+        let reason = health_transition_reason(&RentalState::Unhealthy, &RentalState::Stopped);
+        match stopper.terminate_rental(&rental.rental_id, reason).await {
+            Ok(()) => {
+                warn!("Terminated unhealthy rental {}", rental.rental_id);
+                self.forget(&rental.rental_id);
+                self.emit(RentalHealthEvent::Terminated {
+                    rental_id: rental.rental_id.clone(),
+                    executor_id: rental.executor_id.clone(),
+                    timestamp: Utc::now(),
+                });
+                true
+            }
+            Err(e) => {
+                error!(
+                    "Failed to terminate unhealthy rental {}: {}",
+                    rental.rental_id, e
+                );
+                false
+            }
+        }
+    }
+
+    /// Record a failure and return how many consecutive failures fall within
+    /// the policy window
+    fn record_failure(
+        &self,
+        rental_id: &str,
+        policy: &HealthEscalationPolicy,
+        now: Instant,
+    ) -> u32 {
+        let mut failures = self.failures.lock().unwrap();
+        let history = failures.entry(rental_id.to_string()).or_default();
+
+        history.push_back(now);
+        while history
+            .front()
+            .is_some_and(|&first| now.saturating_duration_since(first) > policy.failure_window())
+        {
+            history.pop_front();
+        }
+        history.len() as u32
+    }
+
+    fn emit(&self, event: RentalHealthEvent) {
+        // Sending only fails when nobody is subscribed
+        let _ = self.events.send(event);
+    }
+
+    /// Drop failure history for a rental that is no longer monitored
+    pub fn forget(&self, rental_id: &str) {
+        self.failures.lock().unwrap().remove(rental_id);
+    }
+}
+
+/// Perform a health check on a container
+async fn perform_health_check(client: &dyn ContainerHealthOps, container_id: &str) -> Result<bool> {
+    // Get container status
+    let status = client.get_container_status(container_id).await?;
+
+    // Check if container is running
+    if status.state != "running" {
+        return Ok(false);
+    }
+
+    // Check container health status if available
+    if status.health != "none" {
+        return Ok(status.health == "healthy");
+    }
+
+    // Container is running and no specific health check configured
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::types::{CpuSpec, ExecutorDetails};
+    use crate::rental::types::{ContainerSpec, NetworkConfig, ResourceRequirements};
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    /// Container client whose status calls fail until told otherwise
+    #[derive(Default)]
+    struct StubClient {
+        healthy: AtomicBool,
+    }
+
+    /// Counts the rentals the escalator terminates
+    #[derive(Default)]
+    struct StubStopper {
+        stops: AtomicU32,
+    }
+
+    #[async_trait]
+    impl RentalStopper for StubStopper {
+        async fn terminate_rental(&self, _rental_id: &str, _reason: &str) -> Result<()> {
+            self.stops.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ContainerHealthOps for StubClient {
+        async fn get_container_status(&self, container_id: &str) -> Result<ContainerStatus> {
+            if !self.healthy.load(Ordering::SeqCst) {
+                anyhow::bail!("container {} not found", container_id);
+            }
+            Ok(ContainerStatus {
+                container_id: container_id.to_string(),
+                state: "running".to_string(),
+                exit_code: None,
+                health: "none".to_string(),
+                started_at: None,
+                finished_at: None,
+            })
+        }
+    }
+
+    fn rental(policy: Option<HealthEscalationPolicy>) -> RentalInfo {
+        RentalInfo {
+            rental_id: "rental-1".to_string(),
+            validator_hotkey: "validator".to_string(),
+            executor_id: "executor-1".to_string(),
+            container_id: "container-1".to_string(),
+            ssh_session_id: String::new(),
+            ssh_credentials: String::new(),
+            state: RentalState::Active,
+            created_at: Utc::now(),
+            container_spec: ContainerSpec {
+                image: "ubuntu:22.04".to_string(),
+                environment: HashMap::new(),
+                ports: Vec::new(),
+                resources: ResourceRequirements {
+                    cpu_cores: 1.0,
+                    memory_mb: 1024,
+                    storage_mb: 1024,
+                    gpu_count: 0,
+                    gpu_types: Vec::new(),
+                },
+                entrypoint: Vec::new(),
+                command: Vec::new(),
+                volumes: Vec::new(),
+                labels: HashMap::new(),
+                capabilities: Vec::new(),
+                network: NetworkConfig {
+                    mode: "bridge".to_string(),
+                    dns: Vec::new(),
+                    extra_hosts: HashMap::new(),
+                },
+                pre_stop: None,
+                secrets: Vec::new(),
+                secrets_owner: None,
+                registry_auth: None,
+                runtime: Default::default(),
+                gpu_device_ids: Vec::new(),
+            },
+            miner_id: "miner_1".to_string(),
+            executor_details: ExecutorDetails {
+                id: "executor-1".to_string(),
+                gpu_specs: Vec::new(),
+                cpu_specs: CpuSpec {
+                    cores: 8,
+                    model: "test".to_string(),
+                    memory_gb: 32,
+                },
+                location: None,
+                network_speed: None,
+            },
+            idle_timeout: None,
+            health_policy: policy,
+            state_history: Vec::new(),
+        }
+    }
+
+    fn escalator() -> HealthEscalator {
+        HealthEscalator::new(HealthEscalationPolicy::default(), Duration::from_secs(1))
+    }
+
+    /// Run one check and apply the resulting transition, as the monitor would
+    async fn check(
+        escalator: &HealthEscalator,
+        client: &StubClient,
+        rental: &mut RentalInfo,
+        now: Instant,
+    ) -> Option<RentalState> {
+        check_with(escalator, client, &StubStopper::default(), rental, now).await
+    }
+
+    async fn check_with(
+        escalator: &HealthEscalator,
+        client: &StubClient,
+        stopper: &StubStopper,
+        rental: &mut RentalInfo,
+        now: Instant,
+    ) -> Option<RentalState> {
+        let new_state = escalator.check(client, stopper, rental, now).await;
+        if let Some(ref state) = new_state {
+            rental.state = state.clone();
+        }
+        new_state
+    }
+
+    #[tokio::test]
+    async fn test_escalates_exactly_at_threshold() {
+        let escalator = escalator();
+        let mut events = escalator.subscribe();
+        let client = StubClient::default();
+        let stopper = StubStopper::default();
+        let mut rental = rental(Some(HealthEscalationPolicy {
+            failure_threshold: 4,
+            failure_window_secs: 600,
+            auto_terminate: false,
+        }));
+        let start = Instant::now();
+
+        for i in 0..3 {
+            let now = start + Duration::from_secs(30 * i);
+            assert_eq!(check(&escalator, &client, &mut rental, now).await, None);
+            assert!(
+                events.try_recv().is_err(),
+                "escalated early at check {}",
+                i + 1
+            );
+        }
+
+        let now = start + Duration::from_secs(90);
+        assert_eq!(
+            check(&escalator, &client, &mut rental, now).await,
+            Some(RentalState::Unhealthy)
+        );
+        match events.try_recv().unwrap() {
+            RentalHealthEvent::Unhealthy {
+                rental_id,
+                consecutive_failures,
+                ..
+            } => {
+                assert_eq!(rental_id, "rental-1");
+                assert_eq!(consecutive_failures, 4);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+
+        // Further failures neither re-escalate nor terminate
+        let now = start + Duration::from_secs(120);
+        assert_eq!(
+            check_with(&escalator, &client, &stopper, &mut rental, now).await,
+            None
+        );
+        assert!(events.try_recv().is_err());
+        assert_eq!(stopper.stops.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_auto_terminate_stops_rental_at_threshold() {
+        let escalator = escalator();
+        let mut events = escalator.subscribe();
+        let client = StubClient::default();
+        let stopper = StubStopper::default();
+        let mut rental = rental(Some(HealthEscalationPolicy {
+            failure_threshold: 2,
+            failure_window_secs: 600,
+            auto_terminate: true,
+        }));
+        let start = Instant::now();
+
+        assert_eq!(
+            check_with(&escalator, &client, &stopper, &mut rental, start).await,
+            None
+        );
+        assert_eq!(stopper.stops.load(Ordering::SeqCst), 0);
+
+        // The stopper records the stopped state, so no transition is returned
+        let now = start + Duration::from_secs(30);
+        assert_eq!(
+            check_with(&escalator, &client, &stopper, &mut rental, now).await,
+            None
+        );
+        assert_eq!(stopper.stops.load(Ordering::SeqCst), 1);
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            RentalHealthEvent::Unhealthy { .. }
+        ));
+        assert!(matches!(
+            events.try_recv().unwrap(),
+            RentalHealthEvent::Terminated { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failures_outside_window_do_not_escalate() {
+        let escalator = escalator();
+        let client = StubClient::default();
+        let mut rental = rental(Some(HealthEscalationPolicy {
+            failure_threshold: 2,
+            failure_window_secs: 60,
+            auto_terminate: false,
+        }));
+        let start = Instant::now();
+
+        for i in 0..5 {
+            let now = start + Duration::from_secs(90 * i);
+            assert_eq!(check(&escalator, &client, &mut rental, now).await, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_success_resets_count_and_recovers() {
+        let escalator = escalator();
+        let client = StubClient::default();
+        let mut rental = rental(None);
+        let start = Instant::now();
+        let threshold = HealthEscalationPolicy::default().failure_threshold as u64;
+
+        // One failure short of the default threshold, then a passing check
+        for i in 0..threshold - 1 {
+            check(
+                &escalator,
+                &client,
+                &mut rental,
+                start + Duration::from_secs(i),
+            )
+            .await;
+        }
+        client.healthy.store(true, Ordering::SeqCst);
+        assert_eq!(
+            check(
+                &escalator,
+                &client,
+                &mut rental,
+                start + Duration::from_secs(10)
+            )
+            .await,
+            None
+        );
+
+        // The count starts over
+        client.healthy.store(false, Ordering::SeqCst);
+        for i in 0..threshold - 1 {
+            let now = start + Duration::from_secs(20 + i);
+            assert_eq!(check(&escalator, &client, &mut rental, now).await, None);
+        }
+        let now = start + Duration::from_secs(30);
+        assert_eq!(
+            check(&escalator, &client, &mut rental, now).await,
+            Some(RentalState::Unhealthy)
+        );
+
+        client.healthy.store(true, Ordering::SeqCst);
+        let now = start + Duration::from_secs(40);
+        assert_eq!(
+            check(&escalator, &client, &mut rental, now).await,
+            Some(RentalState::Active)
+        );
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        assert!(HealthEscalationPolicy::default().validate().is_ok());
+        assert!(HealthEscalationPolicy {
+            failure_threshold: 0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(HealthEscalationPolicy {
+            failure_window_secs: 0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
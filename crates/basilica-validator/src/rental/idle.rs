@@ -0,0 +1,243 @@
+//! Idle detection for rentals
+//!
+//! Rentals created with an idle timeout are watched by the health monitor.
+//! Once every GPU in the container has stayed at or below the utilization
+//! threshold for the whole timeout, the validator logs a warning and, when
+//! the policy says so, stops the rental after a grace period. A single busy
+//! sample resets the clock, so brief dips never trigger the policy.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::types::GpuUsage;
+
+/// What the validator does once a rental has been idle for its timeout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleAction {
+    /// Only log a warning
+    #[default]
+    Warn,
+    /// Warn, then stop the rental after the grace period
+    Stop,
+}
+
+/// Idle policy settings shared by all rentals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdlePolicyConfig {
+    /// GPU utilization in percent at or below which a GPU counts as idle
+    pub gpu_utilization_threshold: f64,
+    /// Action taken once a rental has been idle for its timeout
+    pub action: IdleAction,
+    /// With `action = "stop"`, seconds between the warning and the stop
+    pub stop_grace_secs: u64,
+}
+
+impl Default for IdlePolicyConfig {
+    fn default() -> Self {
+        Self {
+            gpu_utilization_threshold: 5.0,
+            action: IdleAction::Warn,
+            stop_grace_secs: 300,
+        }
+    }
+}
+
+/// Outcome of feeding a utilization sample to the [`IdleTracker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleVerdict {
+    /// At least one GPU is busy, or there is no GPU telemetry
+    Busy,
+    /// Idle, but not yet for long enough to act
+    Idle,
+    /// Idle for the whole timeout; reported once per idle period
+    Warn,
+    /// Idle past the grace period and the policy is to stop
+    Stop,
+}
+
+#[derive(Debug)]
+struct IdleState {
+    idle_since: Instant,
+    warned: bool,
+}
+
+/// Tracks how long each rental has been continuously idle
+#[derive(Debug, Default)]
+pub struct IdleTracker {
+    config: IdlePolicyConfig,
+    rentals: Mutex<HashMap<String, IdleState>>,
+}
+
+impl IdleTracker {
+    pub fn new(config: IdlePolicyConfig) -> Self {
+        Self {
+            config,
+            rentals: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record a utilization sample taken at `now` and decide what to do
+    pub fn observe(
+        &self,
+        rental_id: &str,
+        idle_timeout: Duration,
+        gpu_usage: &[GpuUsage],
+        now: Instant,
+    ) -> IdleVerdict {
+        let mut rentals = self.rentals.lock().unwrap();
+
+        let idle = !gpu_usage.is_empty()
+            && gpu_usage
+                .iter()
+                .all(|gpu| gpu.utilization_percent <= self.config.gpu_utilization_threshold);
+        if !idle {
+            rentals.remove(rental_id);
+            return IdleVerdict::Busy;
+        }
+
+        let state = rentals
+            .entry(rental_id.to_string())
+            .or_insert_with(|| IdleState {
+                idle_since: now,
+                warned: false,
+            });
+        let idle_for = now.saturating_duration_since(state.idle_since);
+
+        if idle_for < idle_timeout {
+            return IdleVerdict::Idle;
+        }
+        if !state.warned {
+            state.warned = true;
+            return IdleVerdict::Warn;
+        }
+        let grace = Duration::from_secs(self.config.stop_grace_secs);
+        if self.config.action == IdleAction::Stop && idle_for >= idle_timeout + grace {
+            rentals.remove(rental_id);
+            return IdleVerdict::Stop;
+        }
+        IdleVerdict::Idle
+    }
+
+    /// Drop tracking state for a rental that is no longer active
+    pub fn forget(&self, rental_id: &str) {
+        self.rentals.lock().unwrap().remove(rental_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rental::container_client::parse_gpu_usage;
+
+    const TIMEOUT: Duration = Duration::from_secs(600);
+
+    fn usage(utilization: &[f64]) -> Vec<GpuUsage> {
+        utilization
+            .iter()
+            .enumerate()
+            .map(|(i, &utilization_percent)| GpuUsage {
+                gpu_index: i as u32,
+                utilization_percent,
+                memory_mb: 0,
+                temperature_celsius: 40.0,
+            })
+            .collect()
+    }
+
+    fn stop_tracker() -> IdleTracker {
+        IdleTracker::new(IdlePolicyConfig {
+            action: IdleAction::Stop,
+            stop_grace_secs: 120,
+            ..Default::default()
+        })
+    }
+
+    /// Feed one sample per minute and collect the verdicts
+    fn run(tracker: &IdleTracker, samples: &[Vec<GpuUsage>]) -> Vec<IdleVerdict> {
+        let start = Instant::now();
+        samples
+            .iter()
+            .enumerate()
+            .map(|(minute, sample)| {
+                let now = start + Duration::from_secs(60 * minute as u64);
+                tracker.observe("rental-1", TIMEOUT, sample, now)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sustained_low_utilization_warns_then_stops() {
+        let tracker = stop_tracker();
+        let verdicts = run(&tracker, &vec![usage(&[1.0, 0.0]); 14]);
+
+        assert!(verdicts[..10].iter().all(|v| *v == IdleVerdict::Idle));
+        assert_eq!(verdicts[10], IdleVerdict::Warn);
+        assert_eq!(verdicts[11], IdleVerdict::Idle);
+        assert_eq!(verdicts[12], IdleVerdict::Stop);
+    }
+
+    #[test]
+    fn test_warn_only_policy_never_stops() {
+        let tracker = IdleTracker::new(IdlePolicyConfig::default());
+        let verdicts = run(&tracker, &vec![usage(&[0.0]); 60]);
+
+        assert_eq!(
+            verdicts.iter().filter(|v| **v == IdleVerdict::Warn).count(),
+            1
+        );
+        assert!(!verdicts.contains(&IdleVerdict::Stop));
+    }
+
+    #[test]
+    fn test_brief_dips_do_not_trigger() {
+        let tracker = stop_tracker();
+        // Idle for nine minutes at a time, with one busy sample in between
+        let mut samples = Vec::new();
+        for _ in 0..4 {
+            samples.extend(vec![usage(&[2.0]); 9]);
+            samples.push(usage(&[85.0]));
+        }
+        let verdicts = run(&tracker, &samples);
+
+        assert!(verdicts
+            .iter()
+            .all(|v| matches!(v, IdleVerdict::Idle | IdleVerdict::Busy)));
+    }
+
+    #[test]
+    fn test_one_busy_gpu_keeps_rental_active() {
+        let tracker = stop_tracker();
+        let verdicts = run(&tracker, &vec![usage(&[0.0, 0.0, 70.0]); 20]);
+        assert!(verdicts.iter().all(|v| *v == IdleVerdict::Busy));
+    }
+
+    #[test]
+    fn test_nvidia_smi_samples_feed_tracker() {
+        let sample = parse_gpu_usage("0, 3, 1024, 41\n1, 0, 512, 39\nNo devices were found\n");
+        assert_eq!(sample.len(), 2);
+        assert_eq!(sample[1].gpu_index, 1);
+        assert_eq!(sample[0].memory_mb, 1024);
+
+        let tracker = stop_tracker();
+        let start = Instant::now();
+        assert_eq!(
+            tracker.observe("rental-1", TIMEOUT, &sample, start),
+            IdleVerdict::Idle
+        );
+        assert_eq!(
+            tracker.observe("rental-1", TIMEOUT, &sample, start + TIMEOUT),
+            IdleVerdict::Warn
+        );
+    }
+
+    #[test]
+    fn test_missing_telemetry_is_not_idle() {
+        let tracker = stop_tracker();
+        let verdicts = run(&tracker, &vec![Vec::new(); 20]);
+        assert!(verdicts.iter().all(|v| *v == IdleVerdict::Busy));
+    }
+}
@@ -4,25 +4,51 @@
 //! and deploy containers on executor machines.
 
 use anyhow::{Context, Result};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 pub mod container_client;
 pub mod deployment;
+pub mod health;
+pub mod idle;
 pub mod monitoring;
+pub mod secrets;
+pub mod timing;
 pub mod types;
 
 pub use container_client::ContainerClient;
-pub use deployment::DeploymentManager;
-pub use monitoring::{DatabaseHealthMonitor, LogStreamer};
+pub use deployment::{
+    ContainerStopOps, DeployPhase, DeploymentConfig, DeploymentManager, DeploymentTimeoutError,
+    InsufficientDiskError, ResourceEnforcementError, CONTAINER_STOP_TIMEOUT,
+};
+pub use health::{HealthEscalationPolicy, HealthEscalator, RentalHealthEvent, RentalStopper};
+pub use idle::{IdleAction, IdlePolicyConfig, IdleTracker, IdleVerdict};
+pub use monitoring::{DatabaseHealthMonitor, HealthCheckConfig, LogStreamer};
+pub use secrets::{
+    OwnedSecrets, RentalSecretStore, RentalSecretsConfig, SecretAccessDenied, SecretEnv,
+};
+pub use timing::{Clock, RentalStartPhase, RentalStartTimer, SystemClock};
 pub use types::*;
 
+use crate::collateral::CollateralGate;
 use crate::metrics::ValidatorPrometheusMetrics;
 use crate::miner_prover::miner_client::{AuthenticatedMinerConnection, MinerClient};
 use crate::persistence::{SimplePersistence, ValidatorPersistence};
 use crate::ssh::ValidatorSshKeyManager;
+use basilica_common::ssh::{parse_credentials, SshCredentials, DEFAULT_SSH_USERNAME};
 use basilica_protocol::basilca::miner::v1::CloseSshSessionRequest;
 
+/// Age after which an executor reservation is considered abandoned
+const EXECUTOR_RESERVATION_TTL_MINUTES: i64 = 15;
+
+/// Time a deployed container gets to report running
+const HEALTH_READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Time between container status checks while waiting for it to run
+const HEALTH_READY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 /// Rental manager for coordinating container deployments
 pub struct RentalManager {
     /// Persistence layer
@@ -39,21 +65,62 @@ pub struct RentalManager {
     ssh_key_manager: Option<Arc<ValidatorSshKeyManager>>,
     /// Metrics for tracking rental status (required)
     metrics: Arc<ValidatorPrometheusMetrics>,
+    /// Clock used to time rental start phases and their deadlines
+    clock: Arc<dyn Clock>,
+    /// Minimum collateral check, when one is configured
+    collateral_gate: Option<Arc<CollateralGate>>,
 }
 
-/// Parse SSH host from credentials string format "user@host:port"
-fn parse_ssh_host(credentials: &str) -> Result<&str> {
-    let (_, host_port) = credentials
-        .split_once('@')
-        .context("Invalid SSH credentials format: missing '@' separator")?;
-
-    let host = host_port
-        .split(':')
-        .next()
-        .filter(|h| !h.is_empty())
-        .context("Invalid SSH credentials format: empty host")?;
+/// Renter credentials for a container: root on the executor's host, at the
+/// port the container's SSH port is mapped to
+fn container_ssh_credentials(access_credentials: &str, host_port: u32) -> Result<String> {
+    let executor = parse_credentials(access_credentials)?;
+    let port =
+        u16::try_from(host_port).with_context(|| format!("Invalid mapped SSH port {host_port}"))?;
+    Ok(SshCredentials {
+        username: DEFAULT_SSH_USERNAME.to_string(),
+        host: executor.host,
+        port,
+    }
+    .to_string())
+}
 
-    Ok(host)
+/// Poll `status` until the container reports running
+///
+/// Fails once `timeout` has passed on `clock`, or at once if the container
+/// has already exited.
+async fn wait_until_running<F, Fut>(
+    clock: &dyn Clock,
+    timeout: Duration,
+    poll_interval: Duration,
+    mut status: F,
+) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<ContainerStatus>>,
+{
+    let deadline = clock.now() + timeout;
+    loop {
+        let state = match status().await {
+            Ok(status) if status.state == "running" => return Ok(()),
+            Ok(status) if matches!(status.state.as_str(), "exited" | "dead") => {
+                anyhow::bail!(
+                    "Container {} with exit code {:?}",
+                    status.state,
+                    status.exit_code
+                );
+            }
+            Ok(status) => status.state,
+            Err(e) => {
+                tracing::debug!("Failed to get container status: {}", e);
+                "unknown".to_string()
+            }
+        };
+        if clock.now() >= deadline {
+            anyhow::bail!("Container is still {} after {:?}", state, timeout);
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
 }
 
 /// Extract miner UID from miner_id format: "miner_{uid}"
@@ -97,15 +164,23 @@ impl RentalManager {
         persistence: Arc<SimplePersistence>,
         ssh_key_manager: Arc<ValidatorSshKeyManager>,
         metrics: Arc<ValidatorPrometheusMetrics>,
+        idle_policy: IdlePolicyConfig,
+        health_policy: HealthEscalationPolicy,
+        deployment: DeploymentConfig,
     ) -> Self {
-        let deployment_manager = Arc::new(DeploymentManager::new());
+        let deployment_manager = Arc::new(DeploymentManager::with_config(deployment));
         let log_streamer = Arc::new(LogStreamer::new());
 
         // Create health monitor with SSH key manager and metrics
-        let health_monitor = Arc::new(DatabaseHealthMonitor::new(
+        let health_monitor = Arc::new(DatabaseHealthMonitor::with_config(
             persistence.clone(),
             ssh_key_manager.clone(),
             metrics.clone(),
+            HealthCheckConfig {
+                idle: idle_policy,
+                escalation: health_policy,
+                ..Default::default()
+            },
         ));
 
         Self {
@@ -116,12 +191,37 @@ impl RentalManager {
             miner_client,
             ssh_key_manager: Some(ssh_key_manager),
             metrics,
+            clock: Arc::new(SystemClock),
+            collateral_gate: None,
         }
     }
 
-    // Start the monitoring loop
-    pub fn start_monitor(&self) {
-        self.health_monitor.start_monitoring_loop();
+    /// Time rental starts against `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Refuse rentals on executors whose collateral is below the gate's minimum
+    pub fn with_collateral_gate(mut self, gate: Arc<CollateralGate>) -> Self {
+        self.collateral_gate = Some(gate);
+        self
+    }
+
+    /// Check that `owner` owns every secret a rental references
+    pub fn check_secret_access(
+        &self,
+        owner: Option<&str>,
+        refs: &[SecretRef],
+    ) -> std::result::Result<(), SecretAccessDenied> {
+        self.deployment_manager.check_secret_access(owner, refs)
+    }
+
+    /// Start the monitoring loop; rentals it gives up on are stopped
+    /// through [`Self::stop_rental`]
+    pub fn start_monitor(self: &Arc<Self>) {
+        let stopper: std::sync::Weak<dyn RentalStopper> = Arc::downgrade(self);
+        self.health_monitor.start_monitoring_loop(stopper);
     }
 
     /// Initialize metrics for all existing rentals on startup
@@ -140,7 +240,10 @@ impl RentalManager {
                 // Set metric based on rental state
                 let is_rented = matches!(
                     rental.state,
-                    RentalState::Active | RentalState::Provisioning | RentalState::Stopping
+                    RentalState::Active
+                        | RentalState::Unhealthy
+                        | RentalState::Provisioning
+                        | RentalState::Stopping
                 );
 
                 self.metrics.record_executor_rental_status(
@@ -164,13 +267,151 @@ impl RentalManager {
     }
 
     /// Start a new rental
+    ///
+    /// A request carrying an idempotency key claims the key before anything
+    /// is reserved or deployed: a retry returns the rental the first request
+    /// created, and a request racing one that is still deploying fails with
+    /// [`IdempotencyKeyInUse`]. The claim is released if the rental fails.
+    ///
+    /// Fails with [`ExecutorBusy`] if the executor already has an active
+    /// rental, with [`ExecutorDraining`] if its miner is draining it, and
+    /// with [`crate::collateral::InsufficientCollateral`] if a collateral
+    /// gate is configured and the executor's collateral is below its
+    /// minimum. The executor is reserved in persistence for the duration of
+    /// the deployment; once the rental is saved its own row keeps the
+    /// executor busy, so the reservation is released either way.
     pub async fn start_rental(
         &self,
         request: RentalRequest,
         miner_connection: &mut AuthenticatedMinerConnection,
     ) -> Result<RentalResponse> {
-        // Generate rental ID
+        // Generate rental ID and attach it to the caller's span, if it has the field
         let rental_id = format!("rental-{}", Uuid::new_v4());
+        let validator_hotkey = request.validator_hotkey.clone();
+        let idempotency_key = request.idempotency_key.clone();
+
+        if let Some(key) = &idempotency_key {
+            match self
+                .persistence
+                .claim_rental_idempotency_key(
+                    &validator_hotkey,
+                    key,
+                    &rental_id,
+                    chrono::Duration::minutes(EXECUTOR_RESERVATION_TTL_MINUTES),
+                )
+                .await
+                .context("Failed to claim idempotency key")?
+            {
+                IdempotencyClaim::Claimed => {}
+                IdempotencyClaim::Completed(existing) => {
+                    tracing::Span::current().record("rental_id", existing.rental_id.as_str());
+                    tracing::info!(
+                        "Returning existing rental {} for idempotency key {}",
+                        existing.rental_id,
+                        key
+                    );
+                    return Ok(existing);
+                }
+                IdempotencyClaim::InProgress => {
+                    return Err(IdempotencyKeyInUse {
+                        idempotency_key: key.clone(),
+                    }
+                    .into());
+                }
+            }
+        }
+        tracing::Span::current().record("rental_id", rental_id.as_str());
+
+        let result = self
+            .reserve_and_deploy(rental_id.clone(), request, miner_connection)
+            .await;
+
+        if let Some(key) = &idempotency_key {
+            let recorded = match &result {
+                Ok(response) => {
+                    self.persistence
+                        .save_rental_idempotency_key(&validator_hotkey, key, response)
+                        .await
+                }
+                Err(_) => {
+                    self.persistence
+                        .release_rental_idempotency_key(&validator_hotkey, key, &rental_id)
+                        .await
+                }
+            };
+            if let Err(e) = recorded {
+                tracing::error!(
+                    "Failed to record idempotency key {} for rental {}: {}",
+                    key,
+                    rental_id,
+                    e
+                );
+            }
+        }
+
+        result
+    }
+
+    /// Check collateral, reserve the executor and deploy the rental onto it
+    async fn reserve_and_deploy(
+        &self,
+        rental_id: String,
+        request: RentalRequest,
+        miner_connection: &mut AuthenticatedMinerConnection,
+    ) -> Result<RentalResponse> {
+        let executor_id = request.executor_id.clone();
+
+        if let Some(gate) = &self.collateral_gate {
+            let miner = self
+                .persistence
+                .get_miner_by_id(&request.miner_id)
+                .await?
+                .with_context(|| format!("Miner {} not found", request.miner_id))?;
+            gate.check(&miner.hotkey, &executor_id).await?;
+        }
+
+        let reservation = self
+            .persistence
+            .reserve_executor(
+                &executor_id,
+                &rental_id,
+                chrono::Duration::minutes(EXECUTOR_RESERVATION_TTL_MINUTES),
+            )
+            .await?;
+        match reservation {
+            ExecutorReservation::Reserved => {}
+            ExecutorReservation::Busy => return Err(ExecutorBusy { executor_id }.into()),
+            ExecutorReservation::Draining => return Err(ExecutorDraining { executor_id }.into()),
+        }
+
+        let result = self
+            .deploy_rental(rental_id.clone(), request, miner_connection)
+            .await;
+
+        if let Err(e) = self
+            .persistence
+            .release_executor_reservation(&executor_id, &rental_id)
+            .await
+        {
+            tracing::error!(
+                "Failed to release reservation on executor {} for rental {}: {}",
+                executor_id,
+                rental_id,
+                e
+            );
+        }
+
+        result
+    }
+
+    /// Deploy a rental onto an executor already reserved for it
+    async fn deploy_rental(
+        &self,
+        rental_id: String,
+        request: RentalRequest,
+        miner_connection: &mut AuthenticatedMinerConnection,
+    ) -> Result<RentalResponse> {
+        let mut timer = RentalStartTimer::start(self.clock.as_ref());
 
         let (validator_public_key, _validator_private_key_path) = self
             .ssh_key_manager
@@ -192,16 +433,28 @@ impl RentalManager {
                 &rental_id,
                 session_duration,
             )
-            .await?;
+            .await
+            .inspect_err(|_| {
+                self.metrics
+                    .record_rental_start_failure(RentalStartPhase::SshSession)
+            })?;
+        timer.finish_phase(RentalStartPhase::SshSession);
 
         let container_client = self.create_container_client(&ssh_session.access_credentials)?;
 
+        // Record the deploying validator so the executor can check who reads the logs
+        let mut container_spec = request.container_spec.clone();
+        container_spec.labels.insert(
+            container_client::VALIDATOR_HOTKEY_LABEL.to_string(),
+            request.validator_hotkey.clone(),
+        );
+
         // Deploy container with end-user's SSH public key
         let container_info = match self
             .deployment_manager
             .deploy_container(
                 &container_client,
-                &request.container_spec,
+                &container_spec,
                 &rental_id,
                 &request.ssh_public_key,
             )
@@ -209,6 +462,17 @@ impl RentalManager {
         {
             Ok(info) => info,
             Err(e) => {
+                self.metrics
+                    .record_rental_start_failure(RentalStartPhase::Deploy);
+                if let Some(enforcement_err) = e.downcast_ref::<ResourceEnforcementError>() {
+                    for mismatch in &enforcement_err.mismatches {
+                        self.metrics.record_resource_enforcement_mismatch(
+                            &request.executor_id,
+                            mismatch.resource,
+                        );
+                    }
+                }
+
                 let close_request = CloseSshSessionRequest {
                     session_id: ssh_session.session_id.clone(),
                     validator_hotkey: request.validator_hotkey.clone(),
@@ -223,6 +487,54 @@ impl RentalManager {
                 return Err(e);
             }
         };
+        timer.finish_phase(RentalStartPhase::Deploy);
+
+        // The container must be running before it is handed to the renter
+        if let Err(e) = wait_until_running(
+            self.clock.as_ref(),
+            HEALTH_READY_TIMEOUT,
+            HEALTH_READY_POLL_INTERVAL,
+            || container_client.get_container_status(&container_info.container_id),
+        )
+        .await
+        {
+            self.metrics
+                .record_rental_start_failure(RentalStartPhase::HealthReady);
+            tracing::error!(
+                "Container {} for rental {} did not start: {}",
+                container_info.container_id,
+                rental_id,
+                e
+            );
+
+            if let Err(cleanup_err) = self
+                .deployment_manager
+                .stop_container(&container_client, &container_info.container_id, None, true)
+                .await
+            {
+                tracing::error!(
+                    "Failed to remove container {} that did not start: {}",
+                    container_info.container_id,
+                    cleanup_err
+                );
+            }
+            let close_request = CloseSshSessionRequest {
+                session_id: ssh_session.session_id.clone(),
+                validator_hotkey: request.validator_hotkey.clone(),
+                reason: "Container did not start".to_string(),
+            };
+            if let Err(cleanup_err) = miner_connection.close_ssh_session(close_request).await {
+                tracing::error!(
+                    "Failed to cleanup SSH session after container start failure: {}",
+                    cleanup_err
+                );
+            }
+            return Err(e.context(format!(
+                "Container {} did not start",
+                container_info.container_id
+            )));
+        }
+        timer.finish_phase(RentalStartPhase::HealthReady);
 
         // Check if SSH port is mapped and construct proper SSH credentials for end-user
         let ssh_credentials = container_info
@@ -230,13 +542,11 @@ impl RentalManager {
             .iter()
             .find(|p| p.container_port == 22)
             .map(|ssh_mapping| {
-                // Parse host from original credentials (format: "user@host:port")
-                let host = parse_ssh_host(&ssh_session.access_credentials).unwrap_or_else(|e| {
-                    tracing::warn!("Failed to parse SSH host from credentials: {}", e);
-                    "localhost"
-                });
-                // Always use root as username for containers with the mapped port
-                format!("root@{}:{}", host, ssh_mapping.host_port)
+                container_ssh_credentials(&ssh_session.access_credentials, ssh_mapping.host_port)
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to parse SSH host from credentials: {}", e);
+                        format!("root@localhost:{}", ssh_mapping.host_port)
+                    })
             });
 
         // Fetch executor details from persistence
@@ -270,27 +580,42 @@ impl RentalManager {
                     request.executor_id,
                     e
                 );
+                self.metrics
+                    .record_rental_start_failure(RentalStartPhase::Persist);
                 return Err(anyhow::anyhow!("Failed to fetch executor details: {}", e));
             }
         };
 
         // Store rental info
-        let rental_info = RentalInfo {
+        let mut rental_info = RentalInfo {
             rental_id: rental_id.clone(),
             validator_hotkey: request.validator_hotkey.clone(),
             executor_id: request.executor_id.clone(),
             container_id: container_info.container_id.clone(),
             ssh_session_id: ssh_session.session_id.clone(),
             ssh_credentials: ssh_session.access_credentials.clone(), // Store validator's SSH credentials for operations
-            state: RentalState::Active,
+            state: RentalState::Provisioning,
             created_at: chrono::Utc::now(),
             container_spec: request.container_spec.clone(),
             miner_id: request.miner_id.clone(),
             executor_details,
+            idle_timeout: request.idle_timeout,
+            health_policy: request.health_policy,
+            state_history: Vec::new(),
         };
+        rental_info.transition_to(RentalState::Active, "Container deployed");
 
         // Save to persistence
-        self.persistence.save_rental(&rental_info).await?;
+        self.persistence
+            .save_rental(&rental_info)
+            .await
+            .inspect_err(|_| {
+                self.metrics
+                    .record_rental_start_failure(RentalStartPhase::Persist)
+            })?;
+        timer.finish_phase(RentalStartPhase::Persist);
+        self.metrics
+            .record_rental_start_duration(timer.phases(), timer.total());
 
         // Record rental metrics
         let miner_uid = extract_miner_uid(&rental_info.miner_id);
@@ -323,6 +648,8 @@ impl RentalManager {
             rental_id,
             ssh_credentials,
             container_info,
+            executor_id: request.executor_id.clone(),
+            pin_honored: None,
         })
     }
 
@@ -352,11 +679,27 @@ impl RentalManager {
             container_status,
             created_at: rental_info.created_at,
             resource_usage,
+            state_history: rental_info.state_history,
         })
     }
 
     /// Stop a rental
     pub async fn stop_rental(&self, rental_id: &str, force: bool) -> Result<()> {
+        let reason = if force {
+            "Force-stopped on request"
+        } else {
+            "Stopped on request"
+        };
+        self.stop_rental_with_reason(rental_id, force, reason).await
+    }
+
+    /// Stop a rental, recording `reason` in its state history
+    async fn stop_rental_with_reason(
+        &self,
+        rental_id: &str,
+        force: bool,
+        reason: &str,
+    ) -> Result<()> {
         let rental_info = self
             .persistence
             .load_rental(rental_id)
@@ -367,7 +710,12 @@ impl RentalManager {
         let container_client = self.create_container_client(&rental_info.ssh_credentials)?;
 
         self.deployment_manager
-            .stop_container(&container_client, &rental_info.container_id, force)
+            .stop_container(
+                &container_client,
+                &rental_info.container_id,
+                rental_info.container_spec.pre_stop.as_ref(),
+                force,
+            )
             .await?;
 
         // Close SSH session through miner connection
@@ -383,7 +731,7 @@ impl RentalManager {
 
         // Update rental state
         let mut updated_rental = rental_info.clone();
-        updated_rental.state = RentalState::Stopped;
+        updated_rental.transition_to(RentalState::Stopped, reason);
         self.persistence.save_rental(&updated_rental).await?;
 
         // Clear rental metric
@@ -414,6 +762,7 @@ impl RentalManager {
         rental_id: &str,
         follow: bool,
         tail_lines: Option<u32>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<tokio::sync::mpsc::Receiver<LogEntry>> {
         let rental_info = self
             .persistence
@@ -421,6 +770,50 @@ impl RentalManager {
             .await?
             .ok_or_else(|| anyhow::anyhow!("Rental not found"))?;
 
+        // Prefer the executor's native log stream, falling back to SSH
+        match self
+            .persistence
+            .get_executor_grpc_address(&rental_info.executor_id, &rental_info.miner_id)
+            .await
+        {
+            Ok(Some(grpc_address)) => match self.miner_client.signer() {
+                Some(signer) => {
+                    match self
+                        .log_streamer
+                        .stream_logs_grpc(
+                            &grpc_address,
+                            &rental_info.validator_hotkey,
+                            signer,
+                            &rental_info.container_id,
+                            follow,
+                            tail_lines,
+                            since,
+                        )
+                        .await
+                    {
+                        Ok(rx) => return Ok(rx),
+                        Err(e) => tracing::warn!(
+                            "gRPC log stream unavailable for rental {}, falling back to SSH: {:#}",
+                            rental_id,
+                            e
+                        ),
+                    }
+                }
+                None => tracing::debug!(
+                    "No validator signer for executor log requests, streaming logs over SSH"
+                ),
+            },
+            Ok(None) => tracing::debug!(
+                "No gRPC address for executor {}, streaming logs over SSH",
+                rental_info.executor_id
+            ),
+            Err(e) => tracing::warn!(
+                "Failed to look up gRPC address for executor {}: {}",
+                rental_info.executor_id,
+                e
+            ),
+        }
+
         let container_client = self.create_container_client(&rental_info.ssh_credentials)?;
 
         self.log_streamer
@@ -429,6 +822,7 @@ impl RentalManager {
                 &rental_info.container_id,
                 follow,
                 tail_lines,
+                since,
             )
             .await
     }
@@ -474,6 +868,13 @@ impl RentalManager {
     }
 }
 
+#[async_trait::async_trait]
+impl RentalStopper for RentalManager {
+    async fn terminate_rental(&self, rental_id: &str, reason: &str) -> Result<()> {
+        self.stop_rental_with_reason(rental_id, false, reason).await
+    }
+}
+
 impl Drop for RentalManager {
     fn drop(&mut self) {
         self.health_monitor.stop();
@@ -484,25 +885,235 @@ impl Drop for RentalManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::collateral::{CollateralQuery, InsufficientCollateral};
+    use crate::miner_prover::miner_client::MinerClientConfig;
+    use crate::rental::timing::FakeClock;
+    use alloy_primitives::U256;
+    use basilica_common::identity::Hotkey;
+    use collateral_contract::CollateralError;
+    use tokio::sync::Notify;
+
+    const MINER_HOTKEY: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const EXECUTOR: &str = "6f1c1d9e-2b2a-4c7e-9d53-0a8f5c3b7e21";
+
+    /// Collateral lookup that blocks until released and then reports no
+    /// collateral, holding a rental between claiming its idempotency key and
+    /// reserving the executor
+    struct HeldCollateral {
+        release: Notify,
+    }
+
+    #[async_trait::async_trait]
+    impl CollateralQuery for HeldCollateral {
+        async fn collateral(
+            &self,
+            _hotkey: [u8; 32],
+            _executor_id: [u8; 16],
+        ) -> std::result::Result<U256, CollateralError> {
+            self.release.notified().await;
+            Ok(U256::ZERO)
+        }
+    }
+
+    fn rental_request(idempotency_key: &str) -> RentalRequest {
+        RentalRequest {
+            validator_hotkey: "validator".to_string(),
+            miner_id: "miner_1".to_string(),
+            executor_id: EXECUTOR.to_string(),
+            container_spec: serde_json::from_str(
+                r#"{"image":"ubuntu:22.04","environment":{},"ports":[],"resources":{"cpu_cores":1.0,"memory_mb":1024,"storage_mb":1024,"gpu_count":0,"gpu_types":[]},"command":[],"volumes":[],"labels":{},"capabilities":[],"network":{"mode":"bridge","dns":[],"extra_hosts":{}}}"#,
+            )
+            .unwrap(),
+            ssh_public_key: "ssh-ed25519 AAAA renter".to_string(),
+            metadata: Default::default(),
+            idempotency_key: Some(idempotency_key.to_string()),
+            idle_timeout: None,
+            health_policy: None,
+        }
+    }
+
+    fn miner_connection() -> AuthenticatedMinerConnection {
+        let channel = tonic::transport::Endpoint::from_static("http://127.0.0.1:1").connect_lazy();
+        AuthenticatedMinerConnection::unauthenticated(channel)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_with_one_idempotency_key_start_one_rental() {
+        let persistence = Arc::new(
+            SimplePersistence::new(":memory:", "validator".to_string())
+                .await
+                .unwrap(),
+        );
+        persistence
+            .register_miner("miner_1", MINER_HOTKEY, "http://127.0.0.1:8091", &[])
+            .await
+            .unwrap();
+
+        let key_dir = tempfile::tempdir().unwrap();
+        let collateral = Arc::new(HeldCollateral {
+            release: Notify::new(),
+        });
+        let manager = RentalManager::new(
+            Arc::new(MinerClient::new(
+                MinerClientConfig::default(),
+                Hotkey::new(MINER_HOTKEY.to_string()).unwrap(),
+            )),
+            persistence.clone(),
+            Arc::new(
+                ValidatorSshKeyManager::new(key_dir.path().to_path_buf())
+                    .await
+                    .unwrap(),
+            ),
+            Arc::new(ValidatorPrometheusMetrics::new(persistence.clone()).unwrap()),
+            IdlePolicyConfig::default(),
+            HealthEscalationPolicy::default(),
+            DeploymentConfig::default(),
+        )
+        .with_collateral_gate(Arc::new(CollateralGate::new(
+            collateral.clone(),
+            U256::from(1u64),
+            std::time::Duration::from_secs(60),
+        )));
+
+        // Whichever request loses the claim returns at once and lets the
+        // winner continue
+        let start = || async {
+            let result = manager
+                .start_rental(rental_request("retry-key"), &mut miner_connection())
+                .await;
+            collateral.release.notify_one();
+            result
+        };
+        let (first, second) = tokio::join!(start(), start());
+
+        let errors = [first.unwrap_err(), second.unwrap_err()];
+        assert_eq!(
+            errors
+                .iter()
+                .filter(|e| e.downcast_ref::<IdempotencyKeyInUse>().is_some())
+                .count(),
+            1
+        );
+        assert_eq!(
+            errors
+                .iter()
+                .filter(|e| e.downcast_ref::<InsufficientCollateral>().is_some())
+                .count(),
+            1
+        );
+
+        // The failed rental released its claim, so the key can be retried
+        assert!(matches!(
+            persistence
+                .claim_rental_idempotency_key(
+                    "validator",
+                    "retry-key",
+                    "rental-retry",
+                    chrono::Duration::minutes(EXECUTOR_RESERVATION_TTL_MINUTES),
+                )
+                .await
+                .unwrap(),
+            IdempotencyClaim::Claimed
+        ));
+    }
+
+    fn status(state: &str) -> ContainerStatus {
+        ContainerStatus {
+            container_id: "container-1".to_string(),
+            state: state.to_string(),
+            exit_code: (state == "exited").then_some(1),
+            health: String::new(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_running_polls_until_running() {
+        let clock = FakeClock::new();
+        let mut states = vec!["created", "created", "running"].into_iter();
+        let mut polls = 0;
+
+        wait_until_running(&clock, Duration::from_secs(60), Duration::ZERO, || {
+            polls += 1;
+            clock.advance(Duration::from_secs(2));
+            let state = states.next().unwrap();
+            async move { Ok::<_, anyhow::Error>(status(state)) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(polls, 3);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_running_gives_up_at_deadline() {
+        let clock = FakeClock::new();
+        let mut polls = 0;
+
+        let err = wait_until_running(&clock, Duration::from_secs(60), Duration::ZERO, || {
+            polls += 1;
+            clock.advance(Duration::from_secs(10));
+            async { Ok::<_, anyhow::Error>(status("created")) }
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(polls, 6);
+        assert!(err.to_string().contains("still created"), "{err}");
+
+        // Status errors are retried until the deadline too
+        let err = wait_until_running(&clock, Duration::from_secs(60), Duration::ZERO, || {
+            clock.advance(Duration::from_secs(30));
+            async { Err::<ContainerStatus, _>(anyhow::anyhow!("ssh failed")) }
+        })
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("still unknown"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_running_fails_fast_on_exit() {
+        let clock = FakeClock::new();
+        let mut polls = 0;
+
+        let err = wait_until_running(&clock, Duration::from_secs(60), Duration::ZERO, || {
+            polls += 1;
+            async { Ok::<_, anyhow::Error>(status("exited")) }
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(polls, 1);
+        assert!(err.to_string().contains("exited"), "{err}");
+    }
 
     #[test]
-    fn test_parse_ssh_host() {
+    fn test_container_ssh_credentials() {
         // Valid formats
         assert_eq!(
-            parse_ssh_host("user@example.com:22").unwrap(),
-            "example.com"
+            container_ssh_credentials("user@example.com:22", 40022).unwrap(),
+            "root@example.com:40022"
+        );
+        assert_eq!(
+            container_ssh_credentials("root@192.168.1.1:2222", 40022).unwrap(),
+            "root@192.168.1.1:40022"
+        );
+        assert_eq!(
+            container_ssh_credentials("admin@host", 40022).unwrap(),
+            "root@host:40022"
+        );
+        assert_eq!(
+            container_ssh_credentials("root@[2001:db8::1]:2222", 40022).unwrap(),
+            "root@[2001:db8::1]:40022"
         );
         assert_eq!(
-            parse_ssh_host("root@192.168.1.1:2222").unwrap(),
-            "192.168.1.1"
+            container_ssh_credentials("root@2001:db8::1", 40022).unwrap(),
+            "root@[2001:db8::1]:40022"
         );
-        assert_eq!(parse_ssh_host("admin@host").unwrap(), "host");
 
         // Invalid formats should return errors
-        assert!(parse_ssh_host("no-at-sign").is_err());
-        assert!(parse_ssh_host("@:22").is_err());
-        assert!(parse_ssh_host("user@").is_err());
-        assert!(parse_ssh_host("user@:22").is_err());
-        assert!(parse_ssh_host("").is_err());
+        assert!(container_ssh_credentials("@:22", 40022).is_err());
+        assert!(container_ssh_credentials("user@", 40022).is_err());
+        assert!(container_ssh_credentials("user@:22", 40022).is_err());
+        assert!(container_ssh_credentials("", 40022).is_err());
+        assert!(container_ssh_credentials("user@host:22", 70000).is_err());
     }
 }
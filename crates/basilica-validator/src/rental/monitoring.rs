@@ -4,17 +4,26 @@
 //! for deployed containers.
 
 use anyhow::{Context, Result};
-use chrono::Utc;
-use std::sync::Arc;
+use basilica_protocol::executor_control::{
+    executor_control_client::ExecutorControlClient, ContainerLogsRequest, ValidatorAuthentication,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use std::sync::{Arc, Weak};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::sync::mpsc;
 use tokio::time::{interval, Duration};
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 
 use super::container_client::ContainerClient;
+use super::health::{
+    health_transition_reason, HealthEscalationPolicy, HealthEscalator, RentalHealthEvent,
+    RentalStopper,
+};
+use super::idle::{IdlePolicyConfig, IdleTracker, IdleVerdict};
 use super::types::{LogEntry, RentalInfo, RentalState};
 use crate::metrics::ValidatorPrometheusMetrics;
+use crate::miner_prover::miner_client::ValidatorSigner;
 use crate::persistence::{SimplePersistence, ValidatorPersistence};
 use crate::ssh::ValidatorSshKeyManager;
 
@@ -29,6 +38,10 @@ pub struct DatabaseHealthMonitor {
     metrics: Arc<ValidatorPrometheusMetrics>,
     /// Health check configuration
     config: HealthCheckConfig,
+    /// Idle time of rentals with an idle timeout
+    idle_tracker: Arc<IdleTracker>,
+    /// Consecutive health check failures and their escalation
+    escalator: Arc<HealthEscalator>,
     /// Cancellation token for the monitoring loop
     cancellation_token: CancellationToken,
 }
@@ -40,6 +53,10 @@ pub struct HealthCheckConfig {
     pub check_interval: Duration,
     /// Timeout for health check commands
     pub check_timeout: Duration,
+    /// Policy applied to rentals created with an idle timeout
+    pub idle: IdlePolicyConfig,
+    /// Escalation for rentals that did not set their own policy
+    pub escalation: HealthEscalationPolicy,
 }
 
 impl Default for HealthCheckConfig {
@@ -47,6 +64,8 @@ impl Default for HealthCheckConfig {
         Self {
             check_interval: Duration::from_secs(30),
             check_timeout: Duration::from_secs(10),
+            idle: IdlePolicyConfig::default(),
+            escalation: HealthEscalationPolicy::default(),
         }
     }
 }
@@ -58,13 +77,12 @@ impl DatabaseHealthMonitor {
         ssh_key_manager: Arc<ValidatorSshKeyManager>,
         metrics: Arc<ValidatorPrometheusMetrics>,
     ) -> Self {
-        Self {
+        Self::with_config(
             persistence,
             ssh_key_manager,
             metrics,
-            config: HealthCheckConfig::default(),
-            cancellation_token: CancellationToken::new(),
-        }
+            HealthCheckConfig::default(),
+        )
     }
 
     /// Create with custom configuration
@@ -78,26 +96,37 @@ impl DatabaseHealthMonitor {
             persistence,
             ssh_key_manager,
             metrics,
+            idle_tracker: Arc::new(IdleTracker::new(config.idle.clone())),
+            escalator: Arc::new(HealthEscalator::new(
+                config.escalation,
+                config.check_timeout,
+            )),
             config,
             cancellation_token: CancellationToken::new(),
         }
     }
 
-    /// Start the monitoring loop
-    pub fn start_monitoring_loop(&self) {
+    /// Start the monitoring loop, stopping rentals through `stopper`. The
+    /// loop ends once the stopper is dropped.
+    pub fn start_monitoring_loop(&self, stopper: Weak<dyn RentalStopper>) {
         let monitor = self.clone();
         tokio::spawn(async move {
-            monitor.monitoring_loop().await;
+            monitor.monitoring_loop(stopper).await;
         });
     }
 
+    /// Receive health escalation events for monitored rentals
+    pub fn subscribe_health_events(&self) -> tokio::sync::broadcast::Receiver<RentalHealthEvent> {
+        self.escalator.subscribe()
+    }
+
     /// Stop the monitoring loop
     pub fn stop(&self) {
         self.cancellation_token.cancel();
     }
 
     /// Main monitoring loop
-    async fn monitoring_loop(&self) {
+    async fn monitoring_loop(&self, stopper: Weak<dyn RentalStopper>) {
         let mut check_interval = interval(self.config.check_interval);
         info!("Database health monitor started");
 
@@ -108,7 +137,11 @@ impl DatabaseHealthMonitor {
                     break;
                 }
                 _ = check_interval.tick() => {
-                    if let Err(e) = self.check_all_rentals().await {
+                    let Some(stopper) = stopper.upgrade() else {
+                        info!("Rental manager dropped, database health monitor stopped");
+                        break;
+                    };
+                    if let Err(e) = self.check_all_rentals(stopper.as_ref()).await {
                         error!("Error checking rental health: {}", e);
                     }
                 }
@@ -117,7 +150,7 @@ impl DatabaseHealthMonitor {
     }
 
     /// Check health status of all non-terminal rentals
-    async fn check_all_rentals(&self) -> Result<()> {
+    async fn check_all_rentals(&self, stopper: &dyn RentalStopper) -> Result<()> {
         // Query all rentals that are not in terminal states
         let rentals = self
             .persistence
@@ -129,7 +162,7 @@ impl DatabaseHealthMonitor {
 
         // TODO: this can be done in parallel
         for rental in rentals {
-            if let Err(e) = self.check_rental_health(&rental).await {
+            if let Err(e) = self.check_rental_health(&rental, stopper).await {
                 error!(
                     "Failed to check health for rental {}: {}",
                     rental.rental_id, e
@@ -142,7 +175,11 @@ impl DatabaseHealthMonitor {
     }
 
     /// Check health of a single rental
-    async fn check_rental_health(&self, rental: &RentalInfo) -> Result<()> {
+    async fn check_rental_health(
+        &self,
+        rental: &RentalInfo,
+        stopper: &dyn RentalStopper,
+    ) -> Result<()> {
         debug!("Checking health for rental {}", rental.rental_id);
 
         // Get validator's private key path
@@ -159,65 +196,37 @@ impl DatabaseHealthMonitor {
             Some(validator_private_key_path),
         )?;
 
-        // Perform health check
-        let health_result = tokio::time::timeout(
-            self.config.check_timeout,
-            Self::perform_health_check(&container_client, &rental.container_id),
-        )
-        .await;
-
-        // Determine new state based on current state and health result
-        let new_state = match (rental.state.clone(), health_result) {
-            // Timeout or error during health check
-            (_, Err(_)) => {
-                warn!(
-                    "Health check timeout for rental {} in state {:?}",
-                    rental.rental_id, rental.state
-                );
-                Some(RentalState::Failed)
-            }
-            // Health check returned an error
-            (current_state, Ok(Err(e))) => {
-                error!(
-                    "Health check error for rental {} in state {:?}: {}",
-                    rental.rental_id, current_state, e
-                );
-                match current_state {
-                    RentalState::Provisioning => Some(RentalState::Failed),
-                    RentalState::Active => Some(RentalState::Stopped),
-                    RentalState::Stopping => Some(RentalState::Stopped),
-                    _ => None,
-                }
-            }
-            // Health check succeeded
-            (current_state, Ok(Ok(healthy))) => {
-                if healthy {
-                    debug!("Rental {} is healthy", rental.rental_id);
-                    None // No state change needed
-                } else {
-                    warn!(
-                        "Rental {} is unhealthy in state {:?}",
-                        rental.rental_id, current_state
-                    );
-                    match current_state {
-                        RentalState::Provisioning => Some(RentalState::Failed),
-                        RentalState::Active => Some(RentalState::Stopped),
-                        RentalState::Stopping => Some(RentalState::Stopped),
-                        _ => None,
-                    }
-                }
+        let new_state = self
+            .escalator
+            .check(
+                &container_client,
+                stopper,
+                rental,
+                std::time::Instant::now(),
+            )
+            .await
+            .map(|state| {
+                let reason = health_transition_reason(&rental.state, &state);
+                (state, reason)
+            });
+
+        if new_state.is_none() && rental.state == RentalState::Active {
+            if let Some(idle_timeout) = rental.idle_timeout {
+                self.check_idle(&container_client, stopper, rental, idle_timeout)
+                    .await;
             }
-        };
+        }
 
         // Update rental state if needed
-        if let Some(new_state) = new_state {
+        if let Some((new_state, reason)) = new_state {
+            self.idle_tracker.forget(&rental.rental_id);
             info!(
                 "Updating rental {} state from {:?} to {:?}",
                 rental.rental_id, rental.state, new_state
             );
 
             let mut updated_rental = rental.clone();
-            updated_rental.state = new_state.clone();
+            updated_rental.transition_to(new_state.clone(), reason);
 
             self.persistence
                 .save_rental(&updated_rental)
@@ -226,6 +235,7 @@ impl DatabaseHealthMonitor {
 
             // Update metrics when state changes to terminal states
             if matches!(new_state, RentalState::Stopped | RentalState::Failed) {
+                self.escalator.forget(&rental.rental_id);
                 let miner_uid = super::extract_miner_uid(&rental.miner_id);
 
                 if let Some(miner_uid) = miner_uid {
@@ -250,23 +260,62 @@ impl DatabaseHealthMonitor {
         Ok(())
     }
 
-    /// Perform a health check on a container
-    async fn perform_health_check(client: &ContainerClient, container_id: &str) -> Result<bool> {
-        // Get container status
-        let status = client.get_container_status(container_id).await?;
-
-        // Check if container is running
-        if status.state != "running" {
-            return Ok(false);
-        }
+    /// Apply the idle policy to a healthy active rental
+    ///
+    /// A rental past its idle timeout is stopped through `stopper`, which
+    /// records the stopped state. Telemetry failures are not treated as
+    /// idleness.
+    async fn check_idle(
+        &self,
+        client: &ContainerClient,
+        stopper: &dyn RentalStopper,
+        rental: &RentalInfo,
+        idle_timeout: Duration,
+    ) {
+        let usage = match client.get_resource_usage(&rental.container_id).await {
+            Ok(usage) => usage,
+            Err(e) => {
+                debug!(
+                    "Skipping idle check for rental {}: failed to read resource usage: {}",
+                    rental.rental_id, e
+                );
+                return;
+            }
+        };
 
-        // Check container health status if available
-        if status.health != "none" {
-            return Ok(status.health == "healthy");
+        let verdict = self.idle_tracker.observe(
+            &rental.rental_id,
+            idle_timeout,
+            &usage.gpu_usage,
+            std::time::Instant::now(),
+        );
+        match verdict {
+            IdleVerdict::Busy | IdleVerdict::Idle => {}
+            IdleVerdict::Warn => {
+                warn!(
+                    "Rental {} has been idle for {}s (GPU utilization <= {}%)",
+                    rental.rental_id,
+                    idle_timeout.as_secs(),
+                    self.config.idle.gpu_utilization_threshold
+                );
+            }
+            IdleVerdict::Stop => {
+                warn!(
+                    "Stopping rental {} after exceeding its idle timeout",
+                    rental.rental_id
+                );
+                match stopper
+                    .terminate_rental(&rental.rental_id, "Idle timeout exceeded")
+                    .await
+                {
+                    Ok(()) => {
+                        self.idle_tracker.forget(&rental.rental_id);
+                        self.escalator.forget(&rental.rental_id);
+                    }
+                    Err(e) => error!("Failed to stop idle rental {}: {}", rental.rental_id, e),
+                }
+            }
         }
-
-        // Container is running and no specific health check configured
-        Ok(true)
     }
 }
 
@@ -320,6 +369,7 @@ impl LogStreamer {
         container_id: &str,
         follow: bool,
         tail_lines: Option<u32>,
+        since: Option<DateTime<Utc>>,
     ) -> Result<mpsc::Receiver<LogEntry>> {
         let (tx, rx) = mpsc::channel(self.config.buffer_size);
 
@@ -328,7 +378,7 @@ impl LogStreamer {
 
         // Start log streaming process
         let mut child = client
-            .stream_logs(&container_id, follow, tail_lines)
+            .stream_logs(&container_id, follow, tail_lines, since)
             .await
             .context("Failed to start log streaming")?;
 
@@ -350,6 +400,9 @@ impl LogStreamer {
                             &container_id_stdout,
                             max_line_length,
                         );
+                        if !Self::is_since(&log_entry, since) {
+                            continue;
+                        }
 
                         if tx_stdout.send(log_entry).await.is_err() {
                             break;
@@ -374,6 +427,9 @@ impl LogStreamer {
                             &container_id_stderr,
                             max_line_length,
                         );
+                        if !Self::is_since(&log_entry, since) {
+                            continue;
+                        }
 
                         if tx_stderr.send(log_entry).await.is_err() {
                             break;
@@ -389,6 +445,108 @@ impl LogStreamer {
         Ok(rx)
     }
 
+    /// Stream logs from a container through the executor's gRPC service
+    ///
+    /// The executor reads the logs from the Docker API and applies `since`
+    /// itself. The request is signed with `signer`, the key of
+    /// `validator_hotkey`, and the executor only serves it for containers
+    /// labelled with that hotkey. Fails before returning if the executor
+    /// cannot be reached or refuses the request, so callers can fall back to
+    /// [`Self::stream_logs`] over SSH.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn stream_logs_grpc(
+        &self,
+        grpc_address: &str,
+        validator_hotkey: &str,
+        signer: &dyn ValidatorSigner,
+        container_id: &str,
+        follow: bool,
+        tail_lines: Option<u32>,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<mpsc::Receiver<LogEntry>> {
+        let mut client = ExecutorControlClient::connect(grpc_address.to_string())
+            .await
+            .with_context(|| format!("Failed to connect to executor at {grpc_address}"))?;
+
+        let mut request = ContainerLogsRequest {
+            container_id: container_id.to_string(),
+            follow,
+            tail_lines: tail_lines.unwrap_or(0),
+            since: since.map(|since| basilica_protocol::common::Timestamp {
+                value: Some(prost_types::Timestamp {
+                    seconds: since.timestamp(),
+                    nanos: since.timestamp_subsec_nanos() as i32,
+                }),
+            }),
+            validator_hotkey: validator_hotkey.to_string(),
+            auth: None,
+            validator_auth: Some(ValidatorAuthentication {
+                timestamp_ms: Utc::now().timestamp_millis() as u64,
+                nonce: uuid::Uuid::new_v4().to_string().into_bytes(),
+                signature: Vec::new(),
+            }),
+        };
+        let signature = signer
+            .sign(&request.validator_signing_payload())
+            .context("Failed to sign log stream request")?;
+        if let Some(auth) = request.validator_auth.as_mut() {
+            auth.signature = signature;
+        }
+
+        let mut stream = client
+            .stream_container_logs(request)
+            .await
+            .context("Executor rejected log stream request")?
+            .into_inner();
+
+        let (tx, rx) = mpsc::channel(self.config.buffer_size);
+        let container_id = container_id.to_string();
+        let max_line_length = self.config.max_line_length;
+
+        tokio::spawn(
+            async move {
+                loop {
+                    let line = match stream.message().await {
+                        Ok(Some(line)) => line,
+                        Ok(None) => break,
+                        Err(e) => {
+                            warn!("Executor log stream for {} ended: {}", container_id, e);
+                            break;
+                        }
+                    };
+
+                    let timestamp = line
+                        .timestamp
+                        .and_then(|ts| ts.value)
+                        .and_then(|ts| {
+                            Utc.timestamp_opt(ts.seconds, ts.nanos.max(0) as u32)
+                                .single()
+                        })
+                        .unwrap_or_else(Utc::now);
+                    let log_entry = LogEntry {
+                        timestamp,
+                        stream: line.stream,
+                        message: Self::truncate_message(line.message, max_line_length),
+                        container_id: container_id.clone(),
+                    };
+
+                    if tx.send(log_entry).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+
+        Ok(rx)
+    }
+
+    /// Whether an entry is at or after `since`. Docker applies `--since`
+    /// remotely, but its precision varies by version, so filter here too.
+    fn is_since(entry: &LogEntry, since: Option<DateTime<Utc>>) -> bool {
+        since.map_or(true, |since| entry.timestamp >= since)
+    }
+
     /// Parse a log line into a LogEntry
     fn parse_log_line(line: &str, stream: &str, container_id: &str, max_length: usize) -> LogEntry {
         // Docker logs with timestamps format: "2024-01-01T00:00:00.000000000Z message"
@@ -403,18 +561,47 @@ impl LogStreamer {
             (Utc::now(), line.to_string())
         };
 
-        // Truncate message if too long
-        let message = if message.len() > max_length {
-            format!("{}... (truncated)", &message[..max_length])
-        } else {
-            message
-        };
-
         LogEntry {
             timestamp,
             stream: stream.to_string(),
-            message,
+            message: Self::truncate_message(message, max_length),
             container_id: container_id.to_string(),
         }
     }
+
+    /// Truncate a message longer than `max_length` bytes
+    fn truncate_message(message: String, max_length: usize) -> String {
+        if message.len() > max_length {
+            format!("{}... (truncated)", &message[..max_length])
+        } else {
+            message
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_before_since_are_excluded() {
+        let since = DateTime::parse_from_rfc3339("2024-01-01T00:00:10Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let before = LogStreamer::parse_log_line(
+            "2024-01-01T00:00:09.999999999Z early",
+            "stdout",
+            "c1",
+            1024,
+        );
+        let at = LogStreamer::parse_log_line("2024-01-01T00:00:10Z on time", "stdout", "c1", 1024);
+        let after = LogStreamer::parse_log_line("2024-01-01T00:01:00Z late", "stderr", "c1", 1024);
+
+        assert_eq!(before.message, "early");
+        assert!(!LogStreamer::is_since(&before, Some(since)));
+        assert!(LogStreamer::is_since(&at, Some(since)));
+        assert!(LogStreamer::is_since(&after, Some(since)));
+        assert!(LogStreamer::is_since(&before, None));
+    }
 }
@@ -0,0 +1,287 @@
+//! Secrets injected into rental containers as environment variables
+//!
+//! A container spec names secrets with [`SecretRef`]s instead of carrying
+//! their values. Values are kept encrypted with the shared [`Aead`] in the
+//! validator config, grouped by the user who owns them, and decrypted only
+//! while the container is created, so they never reach the rental records,
+//! the logs or a command line.
+
+use super::types::SecretRef;
+use anyhow::{anyhow, bail, Context, Result};
+use basilica_common::crypto::Aead;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Secrets of one owner by name, encrypted in the `Aead` format
+/// (`<base64_nonce>:<base64_ciphertext>`)
+pub type OwnedSecrets = HashMap<String, String>;
+
+/// Encrypted secrets rentals may reference by name
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RentalSecretsConfig {
+    /// Hex-encoded AES-256 key the secrets are encrypted with
+    pub secrets_key_hex: Option<String>,
+    /// Secrets by the ID of the user who owns them; a rental can only
+    /// reference secrets of the user it is started for
+    pub secrets: HashMap<String, OwnedSecrets>,
+}
+
+impl fmt::Debug for RentalSecretsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RentalSecretsConfig")
+            .field(
+                "secrets_key_hex",
+                &self.secrets_key_hex.as_ref().map(|_| "<redacted>"),
+            )
+            .field("secrets", &secret_names(&self.secrets))
+            .finish()
+    }
+}
+
+/// Owners and the names of their secrets, without the values
+fn secret_names(secrets: &HashMap<String, OwnedSecrets>) -> HashMap<&str, Vec<&str>> {
+    secrets
+        .iter()
+        .map(|(owner, owned)| {
+            (
+                owner.as_str(),
+                owned.keys().map(String::as_str).collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+/// Error returned when a rental references a secret its owner does not have
+///
+/// Unknown names and names belonging to another user are reported the same
+/// way so the error does not reveal which secrets exist.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Rental secret '{name}' does not exist or is not owned by the requesting user")]
+pub struct SecretAccessDenied {
+    pub name: String,
+}
+
+/// Secret environment variables resolved for a single deployment
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretEnv {
+    vars: Vec<(String, String)>,
+}
+
+impl SecretEnv {
+    /// Variable names and their plaintext values
+    pub fn vars(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// The variables in Docker `--env-file` format, one `KEY=value` per line
+    ///
+    /// The file is read without any quoting, so values may contain anything
+    /// but a line break.
+    pub fn to_env_file(&self) -> Result<String> {
+        let mut file = String::new();
+        for (key, value) in &self.vars {
+            if value.contains(['\n', '\r']) {
+                bail!("Rental secret for {key} contains a line break");
+            }
+            file.push_str(key);
+            file.push('=');
+            file.push_str(value);
+            file.push('\n');
+        }
+        Ok(file)
+    }
+}
+
+impl fmt::Debug for SecretEnv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.vars.iter().map(|(k, _)| (k, "<redacted>")))
+            .finish()
+    }
+}
+
+/// Resolves the secret references of a container spec
+#[derive(Clone, Default)]
+pub struct RentalSecretStore {
+    aead: Option<Arc<Aead>>,
+    secrets: HashMap<String, OwnedSecrets>,
+}
+
+impl fmt::Debug for RentalSecretStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RentalSecretStore")
+            .field("secrets", &secret_names(&self.secrets))
+            .finish_non_exhaustive()
+    }
+}
+
+impl RentalSecretStore {
+    pub fn new(aead: Option<Aead>, secrets: HashMap<String, OwnedSecrets>) -> Self {
+        Self {
+            aead: aead.map(Arc::new),
+            secrets,
+        }
+    }
+
+    /// Build the store from the validator config, failing on a malformed key
+    pub fn from_config(config: &RentalSecretsConfig) -> Result<Self> {
+        let aead = config
+            .secrets_key_hex
+            .as_deref()
+            .map(Aead::new)
+            .transpose()
+            .context("Invalid rental secrets key")?;
+        if aead.is_none() && config.secrets.values().any(|owned| !owned.is_empty()) {
+            bail!("Rental secrets are configured but no secrets key is set");
+        }
+        Ok(Self::new(aead, config.secrets.clone()))
+    }
+
+    /// Check that `owner` owns every secret the references name
+    pub fn check_access(
+        &self,
+        owner: Option<&str>,
+        refs: &[SecretRef],
+    ) -> std::result::Result<(), SecretAccessDenied> {
+        for secret in refs {
+            self.lookup(owner, &secret.name)?;
+        }
+        Ok(())
+    }
+
+    fn lookup(
+        &self,
+        owner: Option<&str>,
+        name: &str,
+    ) -> std::result::Result<&String, SecretAccessDenied> {
+        owner
+            .and_then(|owner| self.secrets.get(owner))
+            .and_then(|owned| owned.get(name))
+            .ok_or_else(|| SecretAccessDenied {
+                name: name.to_string(),
+            })
+    }
+
+    /// Decrypt the secret each reference names from those `owner` owns
+    pub fn resolve(&self, owner: Option<&str>, refs: &[SecretRef]) -> Result<SecretEnv> {
+        if refs.is_empty() {
+            return Ok(SecretEnv::default());
+        }
+        self.check_access(owner, refs)?;
+        let aead = self
+            .aead
+            .as_ref()
+            .ok_or_else(|| anyhow!("No rental secrets key is configured"))?;
+
+        let vars = refs
+            .iter()
+            .map(|secret| {
+                let encrypted = self.lookup(owner, &secret.name)?;
+                // The decryption error never includes the plaintext
+                let value = aead.decrypt(encrypted).with_context(|| {
+                    format!("Failed to decrypt rental secret '{}'", secret.name)
+                })?;
+                Ok((secret.env.clone(), value))
+            })
+            .collect::<Result<_>>()?;
+        Ok(SecretEnv { vars })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn store() -> RentalSecretStore {
+        let aead = Aead::new(KEY).unwrap();
+        let secrets = HashMap::from([
+            (
+                "alice".to_string(),
+                HashMap::from([(
+                    "huggingface".to_string(),
+                    aead.encrypt("hf_s3cr3t").unwrap(),
+                )]),
+            ),
+            (
+                "bob".to_string(),
+                HashMap::from([("wandb".to_string(), aead.encrypt("wandb_s3cr3t").unwrap())]),
+            ),
+        ]);
+        RentalSecretStore::new(Some(aead), secrets)
+    }
+
+    fn secret_ref(env: &str, name: &str) -> SecretRef {
+        SecretRef {
+            env: env.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_secret_is_resolved_and_redacted() {
+        let env = store()
+            .resolve(Some("alice"), &[secret_ref("HF_TOKEN", "huggingface")])
+            .unwrap();
+        assert_eq!(env.vars().collect::<Vec<_>>(), [("HF_TOKEN", "hf_s3cr3t")]);
+        assert_eq!(env.to_env_file().unwrap(), "HF_TOKEN=hf_s3cr3t\n");
+
+        let debug = format!("{env:?}");
+        assert!(debug.contains("HF_TOKEN"), "{debug}");
+        assert!(!debug.contains("hf_s3cr3t"), "{debug}");
+    }
+
+    #[test]
+    fn test_unknown_or_unowned_secret_is_rejected() {
+        let store = store();
+        for (owner, name) in [
+            (Some("alice"), "missing"),
+            (Some("alice"), "wandb"),
+            (Some("mallory"), "huggingface"),
+            (None, "huggingface"),
+        ] {
+            let err = store
+                .resolve(owner, &[secret_ref("TOKEN", name)])
+                .unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<SecretAccessDenied>(),
+                Some(&SecretAccessDenied {
+                    name: name.to_string()
+                }),
+                "{owner:?} {name}"
+            );
+        }
+        assert!(store
+            .check_access(Some("bob"), &[secret_ref("TOKEN", "wandb")])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_multiline_secret_is_not_written_to_env_file() {
+        let env = SecretEnv {
+            vars: vec![("KEY".to_string(), "line1\nOTHER=injected".to_string())],
+        };
+        assert!(env.to_env_file().is_err());
+    }
+
+    #[test]
+    fn test_secrets_without_key_are_rejected() {
+        let config = RentalSecretsConfig {
+            secrets_key_hex: None,
+            secrets: HashMap::from([(
+                "alice".to_string(),
+                HashMap::from([("huggingface".to_string(), "x:y".to_string())]),
+            )]),
+        };
+        assert!(RentalSecretStore::from_config(&config).is_err());
+        assert!(RentalSecretStore::from_config(&RentalSecretsConfig::default()).is_ok());
+    }
+}
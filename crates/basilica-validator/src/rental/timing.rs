@@ -0,0 +1,165 @@
+//! Rental start latency tracking
+//!
+//! Splits the time from rental request to SSH-ready container into phases
+//! so slow starts can be attributed to the miner, the executor or the
+//! container itself.
+
+use std::time::{Duration, Instant};
+
+/// Source of monotonic time, replaceable in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// Clock backed by [`Instant::now`]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Phases of starting a rental, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RentalStartPhase {
+    /// Negotiating the SSH session with the miner
+    SshSession,
+    /// Deploying and verifying the container on the executor
+    Deploy,
+    /// Waiting for the deployed container to report running
+    HealthReady,
+    /// Recording the rental in persistence
+    Persist,
+}
+
+impl RentalStartPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SshSession => "ssh_session",
+            Self::Deploy => "deploy",
+            Self::HealthReady => "health_ready",
+            Self::Persist => "persist",
+        }
+    }
+}
+
+/// Measures consecutive rental start phases against a [`Clock`]
+pub struct RentalStartTimer<'a> {
+    clock: &'a dyn Clock,
+    started: Instant,
+    phase_started: Instant,
+    phases: Vec<(RentalStartPhase, Duration)>,
+}
+
+impl<'a> RentalStartTimer<'a> {
+    pub fn start(clock: &'a dyn Clock) -> Self {
+        let now = clock.now();
+        Self {
+            clock,
+            started: now,
+            phase_started: now,
+            phases: Vec::new(),
+        }
+    }
+
+    /// Close `phase`, which started when the previous phase ended
+    pub fn finish_phase(&mut self, phase: RentalStartPhase) -> Duration {
+        let now = self.clock.now();
+        let elapsed = now.saturating_duration_since(self.phase_started);
+        self.phase_started = now;
+        self.phases.push((phase, elapsed));
+        elapsed
+    }
+
+    /// Durations of the phases finished so far
+    pub fn phases(&self) -> &[(RentalStartPhase, Duration)] {
+        &self.phases
+    }
+
+    /// Time since the timer started
+    pub fn total(&self) -> Duration {
+        self.clock.now().saturating_duration_since(self.started)
+    }
+}
+
+/// Clock that only moves when advanced
+#[cfg(test)]
+pub(crate) struct FakeClock {
+    base: Instant,
+    offset: std::sync::Mutex<Duration>,
+}
+
+#[cfg(test)]
+impl FakeClock {
+    pub(crate) fn new() -> Self {
+        Self {
+            base: Instant::now(),
+            offset: std::sync::Mutex::new(Duration::ZERO),
+        }
+    }
+
+    pub(crate) fn advance(&self, by: Duration) {
+        *self.offset.lock().unwrap() += by;
+    }
+}
+
+#[cfg(test)]
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.base + *self.offset.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_sum_to_total() {
+        let clock = FakeClock::new();
+        let mut timer = RentalStartTimer::start(&clock);
+
+        clock.advance(Duration::from_millis(1500));
+        timer.finish_phase(RentalStartPhase::SshSession);
+        clock.advance(Duration::from_secs(42));
+        timer.finish_phase(RentalStartPhase::Deploy);
+        clock.advance(Duration::from_millis(250));
+        timer.finish_phase(RentalStartPhase::HealthReady);
+        clock.advance(Duration::from_millis(10));
+        timer.finish_phase(RentalStartPhase::Persist);
+
+        let phases: Vec<_> = timer
+            .phases()
+            .iter()
+            .map(|(phase, duration)| (phase.as_str(), duration.as_millis()))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                ("ssh_session", 1500),
+                ("deploy", 42_000),
+                ("health_ready", 250),
+                ("persist", 10),
+            ]
+        );
+
+        let sum: Duration = timer.phases().iter().map(|(_, d)| *d).sum();
+        assert_eq!(sum, timer.total());
+        assert_eq!(timer.total(), Duration::from_millis(43_760));
+    }
+
+    #[test]
+    fn test_total_includes_unfinished_phase() {
+        let clock = FakeClock::new();
+        let mut timer = RentalStartTimer::start(&clock);
+
+        clock.advance(Duration::from_secs(2));
+        timer.finish_phase(RentalStartPhase::SshSession);
+        clock.advance(Duration::from_secs(3));
+
+        assert_eq!(timer.phases().len(), 1);
+        assert_eq!(timer.total(), Duration::from_secs(5));
+    }
+}
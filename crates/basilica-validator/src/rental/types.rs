@@ -4,6 +4,53 @@ use chrono::{DateTime, Utc};
 use core::fmt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Error returned when the target executor already has an active rental or
+/// another rental is being deployed onto it
+#[derive(Debug, thiserror::Error)]
+#[error("Executor {executor_id} already has an active rental")]
+pub struct ExecutorBusy {
+    pub executor_id: String,
+}
+
+/// Error returned when the target executor is draining for maintenance and
+/// takes no new rentals
+#[derive(Debug, thiserror::Error)]
+#[error("Executor {executor_id} is draining for maintenance")]
+pub struct ExecutorDraining {
+    pub executor_id: String,
+}
+
+/// Outcome of reserving an executor for a rental about to be deployed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutorReservation {
+    /// The executor is now held by the new rental
+    Reserved,
+    /// The executor has a rental or another deployment holds it
+    Busy,
+    /// The executor's miner is draining it
+    Draining,
+}
+
+/// Error returned when a rental with the same idempotency key is still
+/// being deployed
+#[derive(Debug, thiserror::Error)]
+#[error("A rental with idempotency key {idempotency_key} is already being started")]
+pub struct IdempotencyKeyInUse {
+    pub idempotency_key: String,
+}
+
+/// Outcome of claiming an idempotency key for a new rental
+#[derive(Debug, Clone)]
+pub enum IdempotencyClaim {
+    /// The key is now held by the new rental
+    Claimed,
+    /// A rental already finished with this key
+    Completed(RentalResponse),
+    /// Another request holding the key is still deploying
+    InProgress,
+}
 
 /// Rental request from validator
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +61,16 @@ pub struct RentalRequest {
     pub container_spec: ContainerSpec,
     pub ssh_public_key: String,
     pub metadata: HashMap<String, String>,
+    /// Client-supplied key; retrying with the same key returns the rental
+    /// created by the first request instead of deploying another one
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Act on the rental once its GPUs have been idle this long
+    #[serde(default)]
+    pub idle_timeout: Option<std::time::Duration>,
+    /// Health check escalation for this rental; the validator default applies when unset
+    #[serde(default)]
+    pub health_policy: Option<super::health::HealthEscalationPolicy>,
 }
 
 /// Container specification
@@ -30,6 +87,223 @@ pub struct ContainerSpec {
     pub labels: HashMap<String, String>,
     pub capabilities: Vec<String>,
     pub network: NetworkConfig,
+    /// Command run inside the container before it is stopped
+    #[serde(default)]
+    pub pre_stop: Option<PreStopHook>,
+    /// Environment variables whose values are looked up in the validator's
+    /// secret store when the container is deployed
+    #[serde(default)]
+    pub secrets: Vec<SecretRef>,
+    /// User whose secrets `secrets` may reference
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secrets_owner: Option<String>,
+    /// Credentials for pulling `image` from a private registry; never
+    /// written to the rental records
+    #[serde(default, skip_serializing)]
+    pub registry_auth: Option<RegistryAuth>,
+    /// Docker runtime the container runs under
+    #[serde(default)]
+    pub runtime: DockerRuntime,
+    /// Host GPU indices exposed to the container; empty exposes every GPU
+    /// reserved for the rental
+    #[serde(default)]
+    pub gpu_device_ids: Vec<u32>,
+}
+
+impl ContainerSpec {
+    /// Check environment variable and secret names before deployment
+    pub fn validate_environment(&self) -> Result<(), String> {
+        validate_environment(&self.environment, &self.secrets)
+    }
+
+    /// GPUs the container is created with: the selected devices, or every
+    /// GPU on the host when none are selected
+    pub fn gpu_allocation(&self) -> GpuAllocation {
+        if self.gpu_device_ids.is_empty() {
+            GpuAllocation::All
+        } else {
+            GpuAllocation::Devices(self.gpu_device_ids.clone())
+        }
+    }
+
+    /// Check the requested GPU devices before deployment
+    pub fn validate_gpu_devices(&self) -> Result<(), String> {
+        validate_gpu_devices(
+            &self.gpu_device_ids,
+            self.resources.gpu_count,
+            &self.environment,
+        )
+    }
+}
+
+/// Check that GPU devices are requested at most once each and match the
+/// requested GPU count
+///
+/// Whether the devices exist is checked against the executor at deploy time.
+pub fn validate_gpu_devices(
+    gpu_device_ids: &[u32],
+    gpu_count: u32,
+    environment: &HashMap<String, String>,
+) -> Result<(), String> {
+    if gpu_device_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    if let Some(id) = gpu_device_ids.iter().find(|id| !seen.insert(**id)) {
+        return Err(format!("GPU device {id} is requested more than once"));
+    }
+    if gpu_count > 0 && gpu_device_ids.len() != gpu_count as usize {
+        return Err(format!(
+            "{} GPU devices requested for a rental of {} GPUs",
+            gpu_device_ids.len(),
+            gpu_count
+        ));
+    }
+    // The device selection sets this variable itself
+    if environment.contains_key(NVIDIA_VISIBLE_DEVICES) {
+        return Err(format!(
+            "{NVIDIA_VISIBLE_DEVICES} cannot be set together with GPU device IDs"
+        ));
+    }
+    Ok(())
+}
+
+/// Variable the NVIDIA container runtime reads the exposed GPUs from
+pub const NVIDIA_VISIBLE_DEVICES: &str = "NVIDIA_VISIBLE_DEVICES";
+
+/// Docker runtime a rental's container runs under
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DockerRuntime {
+    /// The NVIDIA container runtime (`--runtime nvidia`)
+    #[default]
+    Nvidia,
+    /// Docker's default runtime; GPUs are still requested with `--gpus`
+    Default,
+}
+
+/// Check that every variable name is valid and that each variable is set
+/// either as a plain value or from a single secret
+pub fn validate_environment(
+    environment: &HashMap<String, String>,
+    secrets: &[SecretRef],
+) -> Result<(), String> {
+    for key in environment.keys() {
+        basilica_common::utils::validate_env_key(key).map_err(|e| e.to_string())?;
+    }
+
+    let mut secret_vars = std::collections::HashSet::new();
+    for secret in secrets {
+        basilica_common::utils::validate_env_key(&secret.env).map_err(|e| e.to_string())?;
+        if secret.name.trim().is_empty() {
+            return Err(format!("secret for {} has no name", secret.env));
+        }
+        if environment.contains_key(&secret.env) || !secret_vars.insert(&secret.env) {
+            return Err(format!(
+                "environment variable {} is set more than once",
+                secret.env
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Environment variable set from a stored secret rather than a plain value,
+/// so the value never appears in the rental request or its records
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretRef {
+    /// Variable name inside the container
+    pub env: String,
+    /// Name of the secret in the validator's rental secret store
+    pub name: String,
+}
+
+/// Credentials for pulling a rental's image from a private registry
+///
+/// They are only ever sent to the registry the image lives in, over TLS,
+/// and reach the executor on SSH stdin rather than a command line.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryAuth {
+    /// Registry host, e.g. `ghcr.io`; an `https://` scheme is accepted
+    pub registry: String,
+    pub username: String,
+    /// Password or access token
+    pub token: String,
+}
+
+impl RegistryAuth {
+    /// Check the credentials can be used to pull `image`, returning the
+    /// registry host they are for
+    pub fn registry_for(&self, image: &str) -> Result<String, String> {
+        let registry =
+            basilica_common::utils::tls_registry_host(&self.registry).map_err(|e| e.to_string())?;
+        if self.username.is_empty() || self.token.is_empty() {
+            return Err(format!(
+                "Registry credentials for {registry} need both a username and a token"
+            ));
+        }
+        let image_registry = basilica_common::utils::image_registry_host(image);
+        if image_registry != registry {
+            return Err(format!(
+                "Registry credentials for {registry} cannot be used to pull {image} from {image_registry}"
+            ));
+        }
+        Ok(registry)
+    }
+}
+
+impl fmt::Debug for RegistryAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistryAuth")
+            .field("registry", &self.registry)
+            .field("username", &self.username)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Time a pre-stop hook gets when the rental does not ask for one
+pub const DEFAULT_PRE_STOP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Longest time a rental may give its pre-stop hook
+pub const MAX_PRE_STOP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Command run inside a rental's container before it is stopped, so
+/// long-running jobs such as training can save a checkpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreStopHook {
+    /// Command and its arguments, run with `docker exec`
+    pub command: Vec<String>,
+    /// How long the command may run before the container is stopped anyway
+    pub timeout: std::time::Duration,
+}
+
+impl PreStopHook {
+    /// Build the hook a rental request asks for, if any
+    pub fn from_request(
+        command: Vec<String>,
+        timeout_secs: Option<u64>,
+    ) -> Result<Option<Self>, String> {
+        if command.is_empty() {
+            return match timeout_secs {
+                Some(_) => Err("pre-stop timeout set without a pre-stop command".to_string()),
+                None => Ok(None),
+            };
+        }
+
+        let timeout = timeout_secs
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(DEFAULT_PRE_STOP_TIMEOUT);
+        if timeout.is_zero() || timeout > MAX_PRE_STOP_TIMEOUT {
+            return Err(format!(
+                "pre-stop timeout must be between 1 and {} seconds",
+                MAX_PRE_STOP_TIMEOUT.as_secs()
+            ));
+        }
+
+        Ok(Some(Self { command, timeout }))
+    }
 }
 
 /// Port mapping configuration
@@ -51,6 +325,44 @@ pub struct ResourceRequirements {
     pub gpu_types: Vec<String>,
 }
 
+/// GPU allocation requested from the container runtime
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GpuAllocation {
+    /// No GPU device request
+    None,
+    /// All GPUs on the host (`--gpus all`)
+    All,
+    /// A specific number of GPUs
+    Count(u32),
+    /// Specific host GPU indices (`--gpus "device=..."`)
+    Devices(Vec<u32>),
+}
+
+impl fmt::Display for GpuAllocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "no GPUs"),
+            Self::All => write!(f, "all GPUs"),
+            Self::Count(count) => write!(f, "{count} GPUs"),
+            Self::Devices(ids) => {
+                let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+                write!(f, "GPU devices {}", ids.join(","))
+            }
+        }
+    }
+}
+
+/// Resource limits actually applied to a container, as reported by `docker inspect`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedResourceLimits {
+    /// CPU limit in cores (`None` when unlimited)
+    pub cpu_cores: Option<f64>,
+    /// Memory limit in MB (`None` when unlimited)
+    pub memory_mb: Option<i64>,
+    /// GPU devices exposed to the container
+    pub gpus: GpuAllocation,
+}
+
 /// Volume mount configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VolumeMount {
@@ -73,6 +385,12 @@ pub struct RentalResponse {
     pub rental_id: String,
     pub ssh_credentials: Option<String>,
     pub container_info: ContainerInfo,
+    /// Executor the rental was started on
+    #[serde(default)]
+    pub executor_id: String,
+    /// Whether the pinned executor was used, for pinned executor selections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_honored: Option<bool>,
 }
 
 /// Container information
@@ -93,6 +411,8 @@ pub struct ContainerInfo {
 pub enum RentalState {
     Provisioning,
     Active,
+    /// Running, but failed enough consecutive health checks to escalate
+    Unhealthy,
     Stopping,
     Stopped,
     Failed,
@@ -104,6 +424,45 @@ impl fmt::Display for RentalState {
     }
 }
 
+impl RentalState {
+    /// Every state, in lifecycle order
+    pub const ALL: [RentalState; 6] = [
+        RentalState::Provisioning,
+        RentalState::Active,
+        RentalState::Unhealthy,
+        RentalState::Stopping,
+        RentalState::Stopped,
+        RentalState::Failed,
+    ];
+}
+
+impl FromStr for RentalState {
+    type Err = String;
+
+    /// Parse a state name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|state| state.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                let valid: Vec<String> = Self::ALL.iter().map(ToString::to_string).collect();
+                format!(
+                    "unknown rental state '{s}', expected one of: {}",
+                    valid.join(", ")
+                )
+            })
+    }
+}
+
+/// A change of a rental's state, kept for post-mortems
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub from: RentalState,
+    pub to: RentalState,
+    pub at: DateTime<Utc>,
+    pub reason: String,
+}
+
 /// Rental information stored in memory and persistence
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RentalInfo {
@@ -118,6 +477,28 @@ pub struct RentalInfo {
     pub container_spec: ContainerSpec,
     pub miner_id: String,
     pub executor_details: crate::api::types::ExecutorDetails,
+    /// Idle timeout requested at creation, enforced by the health monitor
+    #[serde(default)]
+    pub idle_timeout: Option<std::time::Duration>,
+    /// Health check escalation requested at creation
+    #[serde(default)]
+    pub health_policy: Option<super::health::HealthEscalationPolicy>,
+    /// Every state change so far, oldest first; only ever appended to
+    #[serde(default)]
+    pub state_history: Vec<StateTransition>,
+}
+
+impl RentalInfo {
+    /// Move the rental to `to`, recording the transition in its history
+    pub fn transition_to(&mut self, to: RentalState, reason: impl Into<String>) {
+        self.state_history.push(StateTransition {
+            from: self.state.clone(),
+            to: to.clone(),
+            at: Utc::now(),
+            reason: reason.into(),
+        });
+        self.state = to;
+    }
 }
 
 /// Rental status
@@ -128,6 +509,8 @@ pub struct RentalStatus {
     pub container_status: ContainerStatus,
     pub created_at: DateTime<Utc>,
     pub resource_usage: ResourceUsage,
+    #[serde(default)]
+    pub state_history: Vec<StateTransition>,
 }
 
 /// Container status
@@ -170,3 +553,124 @@ pub struct LogEntry {
     pub message: String,
     pub container_id: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec_with_env(environment: &[(&str, &str)], secrets: &[(&str, &str)]) -> ContainerSpec {
+        ContainerSpec {
+            image: "ubuntu:22.04".to_string(),
+            environment: environment
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ports: Vec::new(),
+            resources: ResourceRequirements {
+                cpu_cores: 1.0,
+                memory_mb: 1024,
+                storage_mb: 0,
+                gpu_count: 0,
+                gpu_types: Vec::new(),
+            },
+            entrypoint: Vec::new(),
+            command: Vec::new(),
+            volumes: Vec::new(),
+            labels: HashMap::new(),
+            capabilities: Vec::new(),
+            network: NetworkConfig {
+                mode: "bridge".to_string(),
+                dns: Vec::new(),
+                extra_hosts: HashMap::new(),
+            },
+            pre_stop: None,
+            secrets: secrets
+                .iter()
+                .map(|(env, name)| SecretRef {
+                    env: env.to_string(),
+                    name: name.to_string(),
+                })
+                .collect(),
+            secrets_owner: None,
+            registry_auth: None,
+            runtime: DockerRuntime::default(),
+            gpu_device_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_valid_environment_is_accepted() {
+        let spec = spec_with_env(
+            &[("MODEL", "llama"), ("_DEBUG", "1")],
+            &[("HF_TOKEN", "huggingface")],
+        );
+        assert!(spec.validate_environment().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_environment_is_rejected() {
+        let cases = [
+            spec_with_env(&[("1BAD", "x")], &[]),
+            spec_with_env(&[("A=B", "x")], &[]),
+            spec_with_env(&[], &[("2FA_CODE", "otp")]),
+            spec_with_env(&[], &[("TOKEN", " ")]),
+            // A variable may only come from one place
+            spec_with_env(&[("TOKEN", "plain")], &[("TOKEN", "stored")]),
+            spec_with_env(&[], &[("TOKEN", "a"), ("TOKEN", "b")]),
+        ];
+        for spec in cases {
+            assert!(
+                spec.validate_environment().is_err(),
+                "{:?} {:?}",
+                spec.environment,
+                spec.secrets
+            );
+        }
+    }
+
+    #[test]
+    fn test_gpu_devices_must_match_request() {
+        let mut spec = spec_with_env(&[], &[]);
+        assert!(spec.validate_gpu_devices().is_ok());
+
+        spec.resources.gpu_count = 2;
+        spec.gpu_device_ids = vec![1, 3];
+        assert!(spec.validate_gpu_devices().is_ok());
+
+        spec.gpu_device_ids = vec![1, 1];
+        let err = spec.validate_gpu_devices().unwrap_err();
+        assert!(err.contains("more than once"), "{err}");
+
+        spec.gpu_device_ids = vec![1, 2, 3];
+        let err = spec.validate_gpu_devices().unwrap_err();
+        assert!(
+            err.contains("3 GPU devices requested for a rental of 2"),
+            "{err}"
+        );
+
+        spec.gpu_device_ids = vec![1, 3];
+        spec.environment
+            .insert(NVIDIA_VISIBLE_DEVICES.to_string(), "all".to_string());
+        assert!(spec.validate_gpu_devices().is_err());
+    }
+
+    #[test]
+    fn test_rental_state_parses_every_variant() {
+        for state in RentalState::ALL {
+            assert_eq!(state.to_string().parse::<RentalState>(), Ok(state.clone()));
+            assert_eq!(
+                state.to_string().to_lowercase().parse::<RentalState>(),
+                Ok(state)
+            );
+        }
+        assert_eq!(" ACTIVE ".parse::<RentalState>(), Ok(RentalState::Active));
+    }
+
+    #[test]
+    fn test_rental_state_rejects_unknown_names() {
+        let err = "actve".parse::<RentalState>().unwrap_err();
+        assert!(err.contains("actve"));
+        assert!(err.contains("Provisioning, Active, Unhealthy, Stopping, Stopped, Failed"));
+        assert!("".parse::<RentalState>().is_err());
+    }
+}
@@ -6,7 +6,7 @@
 
 use anyhow::{Context, Result};
 use basilica_common::identity::Hotkey;
-use basilica_common::ssh::SshConnectionDetails;
+use basilica_common::ssh::{parse_credentials, SshConnectionDetails, SshCredentials};
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -104,42 +104,19 @@ pub struct SshSessionHelper;
 
 impl SshSessionHelper {
     /// Parse SSH credentials string into connection details
+    ///
+    /// See [`parse_credentials`] for the accepted formats.
     pub fn parse_ssh_credentials(
         credentials: &str,
         key_path: Option<PathBuf>,
         default_key_path: Option<PathBuf>,
         timeout: Duration,
     ) -> Result<SshConnectionDetails> {
-        let parts: Vec<&str> = credentials.split('@').collect();
-        if parts.len() != 2 {
-            return Err(anyhow::anyhow!(
-                "Invalid SSH credentials format: expected username@host[:port]"
-            ));
-        }
-
-        let username = parts[0].to_string();
-        let host_port = parts[1];
-
-        let (host, port) = if let Some(rest) = host_port.strip_prefix('[') {
-            // IPv6: [addr]:port or [addr]
-            let end = rest
-                .find(']')
-                .ok_or_else(|| anyhow::anyhow!("Invalid IPv6 address in credentials"))?;
-            let host = rest[..end].to_string();
-            let port = rest[end + 1..]
-                .strip_prefix(':')
-                .map(|p| p.parse::<u16>().context("Invalid port number"))
-                .transpose()?
-                .unwrap_or(22);
-            (host, port)
-        } else if let Some((h, p)) = host_port.rsplit_once(':') {
-            (
-                h.to_string(),
-                p.parse::<u16>().context("Invalid port number")?,
-            )
-        } else {
-            (host_port.to_string(), 22)
-        };
+        let SshCredentials {
+            username,
+            host,
+            port,
+        } = parse_credentials(credentials)?;
 
         let private_key_path = key_path
             .or(default_key_path)
@@ -534,18 +511,14 @@ mod tests {
         let key_path = PathBuf::from("/path/to/key");
         let timeout = Duration::from_secs(30);
 
-        let result = SshSessionHelper::parse_ssh_credentials(
-            "invalid_format",
-            Some(key_path),
-            None,
-            timeout,
-        );
+        let result =
+            SshSessionHelper::parse_ssh_credentials("user@", Some(key_path), None, timeout);
 
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Invalid SSH credentials format"));
+            .contains("Invalid SSH credentials"));
     }
 
     #[test]
@@ -564,7 +537,32 @@ mod tests {
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Invalid port number"));
+            .contains("invalid port number"));
+    }
+
+    #[test]
+    fn test_parse_ssh_credentials_agrees_with_common_parser() {
+        for input in ["10.0.0.1:2222", "ubuntu@host", "ssh ubuntu@host -p 2200"] {
+            let expected = parse_credentials(input).unwrap();
+            let details = SshSessionHelper::parse_ssh_credentials(
+                input,
+                Some(PathBuf::from("/path/to/key")),
+                None,
+                Duration::from_secs(30),
+            )
+            .unwrap();
+            assert_eq!(details.username, expected.username);
+            assert_eq!(details.host, expected.host);
+            assert_eq!(details.port, expected.port);
+        }
+        let details = SshSessionHelper::parse_ssh_credentials(
+            "10.0.0.1:2222",
+            Some(PathBuf::from("/path/to/key")),
+            None,
+            Duration::from_secs(30),
+        )
+        .unwrap();
+        assert_eq!(details.username, "root");
     }
 
     #[test]
@@ -583,7 +581,7 @@ mod tests {
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Invalid IPv6 address"));
+            .contains("invalid IPv6 address"));
     }
 
     // Test for SSH session cleanup functionality
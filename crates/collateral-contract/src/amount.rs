@@ -0,0 +1,166 @@
+//! TAO amount parsing and formatting
+//!
+//! The contract works in wei, with 18 decimals per TAO. The CLI accepts
+//! amounts such as `1.5tao` or `500000000000000000wei`, and a bare integer
+//! is read as wei so existing scripts keep working.
+
+use alloy_primitives::U256;
+use clap::ValueEnum;
+use std::str::FromStr;
+
+/// Number of decimals in one TAO
+pub const TAO_DECIMALS: usize = 18;
+
+/// Wei in one TAO (10^18)
+pub const WEI_PER_TAO: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// How amounts are displayed
+#[derive(Debug, Clone, Copy, ValueEnum, Default, PartialEq, Eq)]
+pub enum AmountUnit {
+    /// TAO only
+    Tao,
+    /// Wei only
+    Wei,
+    /// TAO followed by the exact wei amount (default)
+    #[default]
+    Both,
+}
+
+/// Parse an amount in wei from `1.5tao`, `1.5 TAO`, `500wei` or a bare
+/// integer, which is taken as wei
+pub fn parse_amount(value: &str) -> Result<U256, anyhow::Error> {
+    let value = value.trim();
+    let lower = value.to_ascii_lowercase();
+
+    if let Some(tao) = lower.strip_suffix("tao") {
+        return parse_tao(tao.trim_end());
+    }
+    let wei = lower.strip_suffix("wei").map_or(value, str::trim_end);
+    if wei.is_empty() {
+        return Err(anyhow::anyhow!("Amount must not be empty"));
+    }
+    U256::from_str(wei).map_err(|e| anyhow::anyhow!("Invalid wei amount '{value}': {e}"))
+}
+
+/// Convert a decimal TAO amount to wei, rejecting anything finer than 1 wei
+pub fn parse_tao(value: &str) -> Result<U256, anyhow::Error> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+        return Err(anyhow::anyhow!("Invalid TAO amount '{value}'"));
+    }
+    if fraction.len() > TAO_DECIMALS {
+        return Err(anyhow::anyhow!(
+            "TAO amount '{value}' has more than {TAO_DECIMALS} decimal places"
+        ));
+    }
+
+    let whole = if whole.is_empty() {
+        U256::ZERO
+    } else {
+        U256::from_str_radix(whole, 10)?
+    };
+    let fraction = format!("{fraction:0<width$}", width = TAO_DECIMALS);
+    let fraction = U256::from(fraction.parse::<u64>()?);
+
+    whole
+        .checked_mul(WEI_PER_TAO)
+        .and_then(|wei| wei.checked_add(fraction))
+        .ok_or_else(|| anyhow::anyhow!("TAO amount '{value}' is too large"))
+}
+
+/// Format a wei amount as TAO without rounding, e.g. `1.5`
+pub fn format_tao(wei: U256) -> String {
+    let whole = wei / WEI_PER_TAO;
+    let fraction = (wei % WEI_PER_TAO).to::<u64>();
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{fraction:0width$}", width = TAO_DECIMALS);
+    format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+/// Format a wei amount for display in `unit`
+pub fn format_amount(wei: U256, unit: AmountUnit) -> String {
+    match unit {
+        AmountUnit::Tao => format!("{} TAO", format_tao(wei)),
+        AmountUnit::Wei => format!("{wei} wei"),
+        AmountUnit::Both => format!("{} TAO ({wei} wei)", format_tao(wei)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fractional_tao() {
+        assert_eq!(
+            parse_amount("1.5tao").unwrap(),
+            U256::from(1_500_000_000_000_000_000u64)
+        );
+        assert_eq!(
+            parse_amount("0.000000000000000001 TAO").unwrap(),
+            U256::from(1u64)
+        );
+        assert_eq!(
+            parse_amount(".25tao").unwrap(),
+            U256::from(250_000_000_000_000_000u64)
+        );
+        assert_eq!(
+            parse_amount("2tao").unwrap(),
+            WEI_PER_TAO * U256::from(2u64)
+        );
+    }
+
+    #[test]
+    fn test_parse_wei_and_raw_integers() {
+        assert_eq!(
+            parse_amount("500000000000000000wei").unwrap(),
+            U256::from(500_000_000_000_000_000u64)
+        );
+        assert_eq!(parse_amount("1000000000000000000").unwrap(), WEI_PER_TAO);
+        assert_eq!(parse_amount("0x2a").unwrap(), U256::from(42u64));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_amounts() {
+        assert!(parse_amount("").is_err());
+        assert!(parse_amount("tao").is_err());
+        assert!(parse_amount("1.5wei").is_err());
+        assert!(parse_amount("-1tao").is_err());
+        assert!(parse_amount("1.2.3tao").is_err());
+        assert!(parse_amount("0.0000000000000000001tao").is_err());
+    }
+
+    #[test]
+    fn test_round_trip_without_precision_loss() {
+        for input in [
+            "0",
+            "1",
+            "1.5",
+            "0.000000000000000001",
+            "123456789.123456789123456789",
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+        ] {
+            let wei = parse_tao(input).unwrap();
+            assert_eq!(format_tao(wei), input);
+            assert_eq!(parse_tao(&format_tao(wei)).unwrap(), wei);
+        }
+        assert_eq!(parse_tao(&format_tao(U256::MAX)).unwrap(), U256::MAX);
+    }
+
+    #[test]
+    fn test_format_amount_units() {
+        let wei = U256::from(1_500_000_000_000_000_000u64);
+        assert_eq!(format_amount(wei, AmountUnit::Tao), "1.5 TAO");
+        assert_eq!(
+            format_amount(wei, AmountUnit::Wei),
+            "1500000000000000000 wei"
+        );
+        assert_eq!(
+            format_amount(wei, AmountUnit::Both),
+            "1.5 TAO (1500000000000000000 wei)"
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! Audit trail for collateral transactions
+//!
+//! Every state-changing collateral call is recorded in an append-only JSON
+//! Lines file: once before the transaction is broadcast and again with its
+//! outcome, both records sharing an ID. A transaction is never broadcast if
+//! its pending record cannot be written. Records are also emitted as tracing
+//! events under the [`AUDIT_TARGET`] target and can be read back with
+//! [`AuditLog::query`] for reconciliation and disputes.
+
+use crate::CollateralError;
+use alloy_primitives::{Address, U256};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Tracing target audit records are logged under
+pub const AUDIT_TARGET: &str = "collateral_audit";
+
+/// Collateral contract call being audited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    Deposit,
+    Reclaim,
+    FinalizeReclaim,
+    DenyReclaim,
+    Slash,
+}
+
+impl fmt::Display for AuditOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Deposit => "deposit",
+            Self::Reclaim => "reclaim",
+            Self::FinalizeReclaim => "finalize_reclaim",
+            Self::DenyReclaim => "deny_reclaim",
+            Self::Slash => "slash",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Stage or result of an audited transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// About to be broadcast
+    Pending,
+    /// Mined successfully
+    Succeeded,
+    /// Rejected by the contract, either when submitted or once mined
+    Reverted,
+    /// Failed for another reason, such as an RPC error
+    Failed,
+}
+
+/// What an audited call does and who makes it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub operation: AuditOperation,
+    /// Hex-encoded miner hotkey
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hotkey: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reclaim_request_id: Option<String>,
+    /// Amount in wei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+    /// Address signing the transaction
+    pub caller: String,
+}
+
+impl AuditEntry {
+    pub fn new(operation: AuditOperation, caller: Address) -> Self {
+        Self {
+            operation,
+            hotkey: None,
+            executor_id: None,
+            reclaim_request_id: None,
+            amount: None,
+            caller: caller.to_string(),
+        }
+    }
+
+    pub fn executor(mut self, hotkey: [u8; 32], executor_id: [u8; 16]) -> Self {
+        self.hotkey = Some(hex::encode(hotkey));
+        self.executor_id = Some(Uuid::from_bytes(executor_id).to_string());
+        self
+    }
+
+    pub fn reclaim_request(mut self, reclaim_request_id: U256) -> Self {
+        self.reclaim_request_id = Some(reclaim_request_id.to_string());
+        self
+    }
+
+    pub fn amount(mut self, amount: U256) -> Self {
+        self.amount = Some(amount.to_string());
+        self
+    }
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Shared by the pending record and the outcome of the same call
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub entry: AuditEntry,
+    pub outcome: AuditOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Mined transaction as seen by the audit log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastTx {
+    pub tx_hash: String,
+    pub block_number: Option<u64>,
+    /// Receipt status; `false` when the transaction reverted on chain
+    pub success: bool,
+}
+
+/// Criteria for [`AuditLog::query`]; unset fields match every record
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub operation: Option<AuditOperation>,
+    pub hotkey: Option<String>,
+    pub executor_id: Option<String>,
+    pub tx_hash: Option<String>,
+}
+
+impl AuditFilter {
+    fn matches(&self, record: &AuditRecord) -> bool {
+        let matches = |wanted: &Option<String>, actual: &Option<String>| {
+            wanted.as_deref().map_or(true, |wanted| {
+                actual.as_deref().is_some_and(|actual| {
+                    normalize_hex(actual).eq_ignore_ascii_case(normalize_hex(wanted))
+                })
+            })
+        };
+        self.operation
+            .map_or(true, |operation| record.entry.operation == operation)
+            && matches(&self.hotkey, &record.entry.hotkey)
+            && matches(&self.executor_id, &record.entry.executor_id)
+            && matches(&self.tx_hash, &record.tx_hash)
+    }
+}
+
+fn normalize_hex(value: &str) -> &str {
+    value.strip_prefix("0x").unwrap_or(value)
+}
+
+/// Append-only JSON Lines audit log
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record `entry`, run `broadcast` and record its outcome
+    ///
+    /// Fails without calling `broadcast` if the pending record cannot be
+    /// written. Failing to write the outcome only logs an error, since the
+    /// transaction has been sent by then.
+    pub async fn record_broadcast<F>(
+        &self,
+        entry: AuditEntry,
+        broadcast: F,
+    ) -> Result<BroadcastTx, CollateralError>
+    where
+        F: Future<Output = Result<BroadcastTx, CollateralError>>,
+    {
+        let pending = AuditRecord {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            entry,
+            outcome: AuditOutcome::Pending,
+            tx_hash: None,
+            block_number: None,
+            error: None,
+        };
+        self.append(&pending)?;
+
+        let result = broadcast.await;
+
+        let mut record = AuditRecord {
+            timestamp: Utc::now(),
+            ..pending
+        };
+        match &result {
+            Ok(tx) => {
+                record.outcome = if tx.success {
+                    AuditOutcome::Succeeded
+                } else {
+                    AuditOutcome::Reverted
+                };
+                record.tx_hash = Some(tx.tx_hash.clone());
+                record.block_number = tx.block_number;
+            }
+            Err(e) => {
+                record.outcome = if e.is_revert() {
+                    AuditOutcome::Reverted
+                } else {
+                    AuditOutcome::Failed
+                };
+                record.tx_hash = e.broadcast_tx_hash().map(str::to_string);
+                record.error = Some(e.to_string());
+            }
+        }
+        if let Err(e) = self.append(&record) {
+            error!(
+                target: AUDIT_TARGET,
+                "Failed to record outcome of {} {}: {}", record.entry.operation, record.id, e
+            );
+        }
+
+        result
+    }
+
+    /// Records matching `filter`, oldest first
+    pub fn query(&self, filter: &AuditFilter) -> Result<Vec<AuditRecord>, CollateralError> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(self.io_error(e)),
+        };
+
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| self.io_error(e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: AuditRecord = serde_json::from_str(&line).map_err(|e| {
+                CollateralError::AuditLog(format!(
+                    "Malformed record in {}: {}",
+                    self.path.display(),
+                    e
+                ))
+            })?;
+            if filter.matches(&record) {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+
+    fn append(&self, record: &AuditRecord) -> Result<(), CollateralError> {
+        let line =
+            serde_json::to_string(record).map_err(|e| CollateralError::AuditLog(e.to_string()))?;
+
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| self.io_error(e))?;
+        writeln!(file, "{line}").map_err(|e| self.io_error(e))?;
+        file.sync_data().map_err(|e| self.io_error(e))?;
+
+        info!(target: AUDIT_TARGET, "{line}");
+        Ok(())
+    }
+
+    fn io_error(&self, e: std::io::Error) -> CollateralError {
+        CollateralError::AuditLog(format!("{}: {}", self.path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOTKEY: [u8; 32] = [7; 32];
+    const EXECUTOR_ID: [u8; 16] = [9; 16];
+
+    fn deposit_entry() -> AuditEntry {
+        AuditEntry::new(AuditOperation::Deposit, Address::repeat_byte(0x11))
+            .executor(HOTKEY, EXECUTOR_ID)
+            .amount(U256::from(1_000u64))
+    }
+
+    fn mined(success: bool) -> BroadcastTx {
+        BroadcastTx {
+            tx_hash: "0xabc".to_string(),
+            block_number: Some(42),
+            success,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_successful_tx_is_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = AuditLog::new(dir.path().join("audit.jsonl"));
+
+        let tx = audit
+            .record_broadcast(deposit_entry(), async { Ok(mined(true)) })
+            .await
+            .unwrap();
+        assert!(tx.success);
+
+        let records = audit.query(&AuditFilter::default()).unwrap();
+        assert_eq!(records.len(), 2);
+        let (pending, done) = (&records[0], &records[1]);
+        assert_eq!(pending.outcome, AuditOutcome::Pending);
+        assert_eq!(pending.tx_hash, None);
+        assert_eq!(done.id, pending.id);
+        assert_eq!(done.outcome, AuditOutcome::Succeeded);
+        assert_eq!(done.tx_hash.as_deref(), Some("0xabc"));
+        assert_eq!(done.block_number, Some(42));
+        assert_eq!(done.entry.amount.as_deref(), Some("1000"));
+        assert_eq!(done.entry.hotkey, Some(hex::encode(HOTKEY)));
+        assert_eq!(
+            done.entry.executor_id,
+            Some(Uuid::from_bytes(EXECUTOR_ID).to_string())
+        );
+        assert_eq!(done.entry.caller, Address::repeat_byte(0x11).to_string());
+    }
+
+    #[tokio::test]
+    async fn test_reverted_tx_is_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = AuditLog::new(dir.path().join("audit.jsonl"));
+
+        // Reverted once mined
+        audit
+            .record_broadcast(deposit_entry(), async { Ok(mined(false)) })
+            .await
+            .unwrap();
+        // Rejected by the contract when submitted
+        let slash = AuditEntry::new(AuditOperation::Slash, Address::repeat_byte(0x22))
+            .executor(HOTKEY, EXECUTOR_ID);
+        let result = audit
+            .record_broadcast(slash, async { Err(CollateralError::NotTrustee) })
+            .await;
+        assert!(matches!(result, Err(CollateralError::NotTrustee)));
+
+        let outcomes: Vec<_> = audit
+            .query(&AuditFilter::default())
+            .unwrap()
+            .into_iter()
+            .map(|record| (record.entry.operation, record.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (AuditOperation::Deposit, AuditOutcome::Pending),
+                (AuditOperation::Deposit, AuditOutcome::Reverted),
+                (AuditOperation::Slash, AuditOutcome::Pending),
+                (AuditOperation::Slash, AuditOutcome::Reverted),
+            ]
+        );
+
+        let slashes = audit
+            .query(&AuditFilter {
+                operation: Some(AuditOperation::Slash),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            slashes[1].error.as_deref(),
+            Some("Caller is not the trustee")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unwritable_log_blocks_broadcast() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = AuditLog::new(dir.path().join("missing").join("audit.jsonl"));
+
+        let mut broadcast = false;
+        let result = audit
+            .record_broadcast(deposit_entry(), async {
+                broadcast = true;
+                Ok(mined(true))
+            })
+            .await;
+
+        assert!(matches!(result, Err(CollateralError::AuditLog(_))));
+        assert!(!broadcast);
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = AuditLog::new(dir.path().join("audit.jsonl"));
+        assert!(audit.query(&AuditFilter::default()).unwrap().is_empty());
+
+        let other = AuditEntry::new(AuditOperation::FinalizeReclaim, Address::ZERO)
+            .reclaim_request(U256::from(3u64));
+        for (entry, tx_hash) in [(deposit_entry(), "0xaaa"), (other, "0xbbb")] {
+            audit
+                .append(&AuditRecord {
+                    id: Uuid::new_v4(),
+                    timestamp: Utc::now(),
+                    entry,
+                    outcome: AuditOutcome::Succeeded,
+                    tx_hash: Some(tx_hash.to_string()),
+                    block_number: None,
+                    error: None,
+                })
+                .unwrap();
+        }
+
+        let by_hotkey = audit
+            .query(&AuditFilter {
+                hotkey: Some(format!("0x{}", hex::encode(HOTKEY))),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_hotkey.len(), 1);
+        assert_eq!(by_hotkey[0].entry.operation, AuditOperation::Deposit);
+
+        let by_tx = audit
+            .query(&AuditFilter {
+                tx_hash: Some("0xBBB".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_tx.len(), 1);
+        assert_eq!(by_tx[0].entry.reclaim_request_id.as_deref(), Some("3"));
+    }
+}
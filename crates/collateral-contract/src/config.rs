@@ -2,6 +2,7 @@
 use alloy_primitives::{address, Address};
 use clap::ValueEnum;
 use std::str::FromStr;
+use std::time::Duration;
 // Deployed Collateral contract address in product environment, will be updated after deployment
 pub const COLLATERAL_ADDRESS: Address = address!("0x0000000000000000000000000000000000000000");
 pub const PROXY_ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
@@ -26,6 +27,39 @@ pub const CONTRACT_DEPLOYED_BLOCK_NUMBER: u64 = 0;
 pub const DEFAULT_CONTRACT_ADDRESS: Address =
     address!("0x0000000000000000000000000000000000000002");
 
+/// Default time a single RPC request may take
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time to wait for a broadcast transaction to be mined
+pub const DEFAULT_RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Default number of times a failed RPC request is retried
+pub const DEFAULT_RPC_RETRIES: u32 = 3;
+
+/// Timeouts and retries for sending transactions
+#[derive(Debug, Clone)]
+pub struct TxOptions {
+    /// Longest a single RPC request may take
+    pub rpc_timeout: Duration,
+    /// Longest to wait for a broadcast transaction to be mined
+    pub receipt_timeout: Duration,
+    /// How many times a failed RPC request is retried
+    pub retries: u32,
+    /// Delay between receipt lookups and between retries
+    pub poll_interval: Duration,
+}
+
+impl Default for TxOptions {
+    fn default() -> Self {
+        Self {
+            rpc_timeout: DEFAULT_RPC_TIMEOUT,
+            receipt_timeout: DEFAULT_RECEIPT_TIMEOUT,
+            retries: DEFAULT_RPC_RETRIES,
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
 #[derive(Debug, Clone, ValueEnum, Default)]
 pub enum Network {
     /// Mainnet (default)
@@ -42,6 +76,7 @@ pub struct CollateralNetworkConfig {
     pub chain_id: u64,
     pub rpc_url: String,
     pub contract_address: Address,
+    pub tx_options: TxOptions,
 }
 
 impl Default for CollateralNetworkConfig {
@@ -68,16 +103,19 @@ impl CollateralNetworkConfig {
                 chain_id: CHAIN_ID,
                 rpc_url: RPC_URL.to_string(),
                 contract_address: parsed_addr.unwrap_or(COLLATERAL_ADDRESS),
+                tx_options: TxOptions::default(),
             }),
             Network::Testnet => Ok(CollateralNetworkConfig {
                 chain_id: TEST_CHAIN_ID,
                 rpc_url: TEST_RPC_URL.to_string(),
                 contract_address: parsed_addr.unwrap_or(DEFAULT_CONTRACT_ADDRESS),
+                tx_options: TxOptions::default(),
             }),
             Network::Local => Ok(CollateralNetworkConfig {
                 chain_id: LOCAL_CHAIN_ID,
                 rpc_url: LOCAL_RPC_URL.to_string(),
                 contract_address: parsed_addr.unwrap_or(DEFAULT_CONTRACT_ADDRESS),
+                tx_options: TxOptions::default(),
             }),
         }
     }
@@ -0,0 +1,243 @@
+//! Errors returned by collateral contract calls
+//!
+//! Contract reverts are decoded from their custom error selectors so callers
+//! can tell a business rule violation (e.g. `PastDenyTimeout`) apart from an
+//! RPC failure that is worth retrying.
+
+use alloy::signers::local::LocalSignerError;
+use alloy::transports::TransportError;
+use alloy_primitives::{Address, Bytes, FixedBytes};
+use alloy_provider::PendingTransactionError;
+use alloy_sol_types::{Revert, SolError, SolInterface};
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::CollateralUpgradeable::CollateralUpgradeableErrors;
+
+#[derive(Debug, Error)]
+pub enum CollateralError {
+    #[error("Deposit amount is zero")]
+    AmountZero,
+
+    #[error("Amount is below the minimum collateral increase")]
+    InsufficientAmount,
+
+    #[error("Collateral is insufficient for the reclaim")]
+    InsufficientCollateralForReclaim,
+
+    #[error("Executor is owned by another miner")]
+    ExecutorNotOwned,
+
+    #[error("Collateral must be deposited through deposit()")]
+    InvalidDepositMethod,
+
+    #[error("Caller is not the trustee")]
+    NotTrustee,
+
+    #[error("Reclaim request not found")]
+    ReclaimNotFound,
+
+    #[error("Deny timeout for the reclaim request has passed")]
+    PastDenyTimeout,
+
+    #[error("Deny timeout for the reclaim request has not passed yet")]
+    BeforeDenyTimeout,
+
+    #[error("Collateral transfer failed")]
+    TransferFailed,
+
+    #[error("Account {account} is missing role {role}")]
+    Unauthorized {
+        account: Address,
+        role: FixedBytes<32>,
+    },
+
+    #[error("Contract reverted: {0}")]
+    Reverted(String),
+
+    #[error("Contract reverted with unrecognized data {0}")]
+    UnknownRevert(Bytes),
+
+    #[error("RPC error: {0}")]
+    RpcError(String),
+
+    #[error("RPC request timed out after {0:?}")]
+    RpcTimeout(Duration),
+
+    #[error("Transaction {tx_hash} was broadcast but not confirmed: {reason}")]
+    Unconfirmed { tx_hash: String, reason: String },
+
+    #[error("Signing error: {0}")]
+    SigningError(String),
+
+    #[error("Failed to decode contract data: {0}")]
+    DecodeError(String),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Audit log error: {0}")]
+    AuditLog(String),
+}
+
+impl CollateralError {
+    /// Decode contract revert data into the matching variant
+    pub fn from_revert_data(data: &[u8]) -> Self {
+        use CollateralUpgradeableErrors as E;
+
+        if let Ok(error) = CollateralUpgradeableErrors::abi_decode(data) {
+            return match error {
+                E::AmountZero(_) => Self::AmountZero,
+                E::InsufficientAmount(_) => Self::InsufficientAmount,
+                E::InsufficientCollateralForReclaim(_) => Self::InsufficientCollateralForReclaim,
+                E::ExecutorNotOwned(_) => Self::ExecutorNotOwned,
+                E::InvalidDepositMethod(_) => Self::InvalidDepositMethod,
+                E::NotTrustee(_) => Self::NotTrustee,
+                E::ReclaimNotFound(_) => Self::ReclaimNotFound,
+                E::PastDenyTimeout(_) => Self::PastDenyTimeout,
+                E::BeforeDenyTimeout(_) => Self::BeforeDenyTimeout,
+                E::TransferFailed(_) => Self::TransferFailed,
+                E::AccessControlUnauthorizedAccount(e) => Self::Unauthorized {
+                    account: e.account,
+                    role: e.neededRole,
+                },
+                other => Self::Reverted(format!(
+                    "error selector 0x{}",
+                    alloy_primitives::hex::encode(other.selector())
+                )),
+            };
+        }
+
+        match Revert::abi_decode(data) {
+            Ok(revert) => Self::Reverted(revert.reason),
+            Err(_) => Self::UnknownRevert(Bytes::copy_from_slice(data)),
+        }
+    }
+
+    /// Whether the failure is transient and the call may succeed if retried
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::RpcError(_) | Self::RpcTimeout(_))
+    }
+
+    /// Hash of a transaction that was broadcast before the failure, which
+    /// may still be mined
+    pub fn broadcast_tx_hash(&self) -> Option<&str> {
+        match self {
+            Self::Unconfirmed { tx_hash, .. } => Some(tx_hash),
+            _ => None,
+        }
+    }
+
+    /// Whether the contract rejected the call
+    pub fn is_revert(&self) -> bool {
+        matches!(
+            self,
+            Self::AmountZero
+                | Self::InsufficientAmount
+                | Self::InsufficientCollateralForReclaim
+                | Self::ExecutorNotOwned
+                | Self::InvalidDepositMethod
+                | Self::NotTrustee
+                | Self::ReclaimNotFound
+                | Self::PastDenyTimeout
+                | Self::BeforeDenyTimeout
+                | Self::TransferFailed
+                | Self::Unauthorized { .. }
+                | Self::Reverted(_)
+                | Self::UnknownRevert(_)
+        )
+    }
+}
+
+impl From<alloy_contract::Error> for CollateralError {
+    fn from(err: alloy_contract::Error) -> Self {
+        match err.as_revert_data() {
+            Some(data) => Self::from_revert_data(&data),
+            None => Self::RpcError(err.to_string()),
+        }
+    }
+}
+
+impl From<TransportError> for CollateralError {
+    fn from(err: TransportError) -> Self {
+        match err.as_error_resp().and_then(|resp| resp.as_revert_data()) {
+            Some(data) => Self::from_revert_data(&data),
+            None => Self::RpcError(err.to_string()),
+        }
+    }
+}
+
+impl From<PendingTransactionError> for CollateralError {
+    fn from(err: PendingTransactionError) -> Self {
+        Self::RpcError(err.to_string())
+    }
+}
+
+impl From<LocalSignerError> for CollateralError {
+    fn from(err: LocalSignerError) -> Self {
+        Self::SigningError(err.to_string())
+    }
+}
+
+impl From<alloy_sol_types::Error> for CollateralError {
+    fn from(err: alloy_sol_types::Error) -> Self {
+        Self::DecodeError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CollateralUpgradeable;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn test_decodes_known_revert_selector() {
+        let selector = CollateralUpgradeable::PastDenyTimeout::SELECTOR;
+        // keccak256("PastDenyTimeout()")[..4]
+        assert_eq!(
+            selector,
+            hex!("fc9e5c02"),
+            "selector changed, update the ABI test"
+        );
+        assert!(matches!(
+            CollateralError::from_revert_data(&selector),
+            CollateralError::PastDenyTimeout
+        ));
+    }
+
+    #[test]
+    fn test_decodes_error_with_arguments() {
+        let error = CollateralUpgradeable::AccessControlUnauthorizedAccount {
+            account: Address::repeat_byte(0x11),
+            neededRole: FixedBytes::repeat_byte(0x22),
+        };
+        let data = error.abi_encode();
+        match CollateralError::from_revert_data(&data) {
+            CollateralError::Unauthorized { account, role } => {
+                assert_eq!(account, Address::repeat_byte(0x11));
+                assert_eq!(role, FixedBytes::repeat_byte(0x22));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decodes_string_revert_and_unknown_data() {
+        let data = Revert {
+            reason: "paused".into(),
+        }
+        .abi_encode();
+        assert!(matches!(
+            CollateralError::from_revert_data(&data),
+            CollateralError::Reverted(reason) if reason == "paused"
+        ));
+
+        let unknown = CollateralError::from_revert_data(&hex!("deadbeef"));
+        assert!(matches!(unknown, CollateralError::UnknownRevert(_)));
+        assert!(!unknown.is_retryable());
+        assert!(CollateralError::RpcError("timeout".into()).is_retryable());
+        assert!(unknown.is_revert());
+        assert!(!CollateralError::RpcError("timeout".into()).is_revert());
+    }
+}
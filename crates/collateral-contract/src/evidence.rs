@@ -0,0 +1,105 @@
+//! Evidence URL checksums for slash, reclaim and deny transactions
+//!
+//! The contract stores a URL and the MD5 of its content as proof. Nothing
+//! on-chain checks that the two agree, so the checksum is computed from the
+//! URL here before a transaction is sent.
+
+use md5::{Digest, Md5};
+use std::time::Duration;
+
+/// How long to wait for the evidence URL to respond
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// MD5 of `content` in the contract's `bytes16` representation
+pub fn md5_checksum(content: &[u8]) -> u128 {
+    u128::from_be_bytes(Md5::digest(content).into())
+}
+
+/// Fetch `url` and return the MD5 of its content. When `expected` is given
+/// the computed checksum must match it, so mismatched evidence is rejected
+/// before any transaction is submitted.
+pub async fn compute_and_verify_url_md5(
+    url: &str,
+    expected: Option<u128>,
+) -> Result<u128, anyhow::Error> {
+    let client = reqwest::Client::builder().timeout(FETCH_TIMEOUT).build()?;
+    let content = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| anyhow::anyhow!("Failed to fetch evidence URL {url}: {e}"))?
+        .bytes()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read evidence URL {url}: {e}"))?;
+
+    let computed = md5_checksum(&content);
+    match expected {
+        Some(expected) if expected != computed => Err(anyhow::anyhow!(
+            "MD5 checksum mismatch for {url}: expected {expected:032x}, content hashes to {computed:032x}"
+        )),
+        _ => Ok(computed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve `body` once over HTTP and return the URL
+    async fn serve_once(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+        format!("http://{addr}/evidence.txt")
+    }
+
+    #[test]
+    fn test_md5_checksum_known_values() {
+        assert_eq!(md5_checksum(b""), 0xd41d8cd98f00b204e9800998ecf8427e);
+        assert_eq!(
+            md5_checksum(b"The quick brown fox jumps over the lazy dog"),
+            0x9e107d9d372bb6826bd81d3542a419d6
+        );
+    }
+
+    #[tokio::test]
+    async fn test_computes_checksum_without_expected_value() {
+        let url = serve_once("slash evidence").await;
+        let checksum = compute_and_verify_url_md5(&url, None).await.unwrap();
+        assert_eq!(checksum, md5_checksum(b"slash evidence"));
+    }
+
+    #[tokio::test]
+    async fn test_matching_checksum_is_accepted() {
+        let url = serve_once("slash evidence").await;
+        let expected = md5_checksum(b"slash evidence");
+        assert_eq!(
+            compute_and_verify_url_md5(&url, Some(expected))
+                .await
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_checksum_is_rejected() {
+        let url = serve_once("slash evidence").await;
+        let err = compute_and_verify_url_md5(&url, Some(md5_checksum(b"other")))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("MD5 checksum mismatch"));
+    }
+}
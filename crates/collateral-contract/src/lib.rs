@@ -2,18 +2,27 @@ use std::collections::HashMap;
 
 use alloy::rpc::types::Filter;
 use alloy::signers::{local::PrivateKeySigner, Signer};
+use alloy_contract::{CallBuilder, CallDecoder};
 use alloy_primitives::{Address, FixedBytes, U256};
 use alloy_provider::{Provider, ProviderBuilder};
 use alloy_sol_types::{sol, SolEvent};
+pub mod amount;
+pub mod audit;
 pub mod config;
+pub mod error;
+pub mod evidence;
+pub mod keystore;
 pub mod proxy;
+pub mod retry;
+pub use error::CollateralError;
 use tracing::info;
 pub use CollateralUpgradeable::{Deposit, Reclaimed, Slashed};
 
 #[cfg(test)]
 mod tests;
 
-use config::{CollateralNetworkConfig, MAX_BLOCKS_PER_SCAN};
+use audit::{AuditEntry, AuditLog, AuditOperation, BroadcastTx};
+use config::{CollateralNetworkConfig, TxOptions, MAX_BLOCKS_PER_SCAN};
 
 sol!(
     #[allow(missing_docs)]
@@ -66,7 +75,7 @@ pub async fn get_collateral(
     network_config: &CollateralNetworkConfig,
 ) -> Result<
     CollateralUpgradeable::CollateralUpgradeableInstance<impl alloy_provider::Provider>,
-    anyhow::Error,
+    CollateralError,
 > {
     let mut signer: PrivateKeySigner = private_key.parse()?;
     signer.set_chain_id(Some(network_config.chain_id));
@@ -84,15 +93,15 @@ pub async fn get_collateral(
 pub async fn scan_events(
     from_block: u64,
     network_config: &CollateralNetworkConfig,
-) -> Result<(u64, HashMap<u64, Vec<CollateralEvent>>), anyhow::Error> {
+) -> Result<(u64, HashMap<u64, Vec<CollateralEvent>>), CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
     let current_block = provider.get_block_number().await?.saturating_sub(1);
 
     if from_block > current_block {
-        return Err(anyhow::anyhow!(
-            "from_block must be less than current_block"
+        return Err(CollateralError::InvalidArgument(
+            "from_block must be less than current_block".to_string(),
         ));
     }
 
@@ -108,7 +117,7 @@ pub async fn scan_events_with_scope(
     from_block: u64,
     to_block: u64,
     network_config: &CollateralNetworkConfig,
-) -> Result<(u64, HashMap<u64, Vec<CollateralEvent>>), anyhow::Error> {
+) -> Result<(u64, HashMap<u64, Vec<CollateralEvent>>), CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
@@ -129,9 +138,9 @@ pub async fn scan_events_with_scope(
 
         let topics = log.inner.topics();
         let topic0 = topics.first();
-        let block_number = log
-            .block_number
-            .ok_or(anyhow::anyhow!("Block number not available in event"))?;
+        let block_number = log.block_number.ok_or_else(|| {
+            CollateralError::DecodeError("Block number not available in event".to_string())
+        })?;
 
         let block_result = result.get_mut(&block_number);
 
@@ -181,24 +190,89 @@ pub async fn scan_events_with_scope(
     Ok((to_block, result))
 }
 // transactions
+
+/// Address of the account signing with `private_key`
+fn signer_address(private_key: &str) -> Result<Address, CollateralError> {
+    let signer: PrivateKeySigner = private_key.parse()?;
+    Ok(signer.address())
+}
+
+/// Send a contract call, or only simulate it with `eth_call` when `simulate`
+/// is set. A call that would revert fails with the decoded contract error in
+/// both modes; a simulation never broadcasts and returns no transaction.
+/// Broadcasts are recorded in `audit` as `entry`, and a transaction mined
+/// with a failed status is returned as reverted.
+///
+/// RPC requests are bounded and retried as set in `options`, except the
+/// broadcast itself, which is never repeated. A transaction not mined within
+/// the receipt timeout fails with its hash.
+async fn submit<P: Provider, D: CallDecoder>(
+    tx: CallBuilder<P, D>,
+    simulate: bool,
+    options: &TxOptions,
+    audit: &AuditLog,
+    entry: AuditEntry,
+) -> Result<Option<BroadcastTx>, CollateralError> {
+    if simulate {
+        let call = &tx;
+        retry::with_retries(options, "Simulating transaction", move || async move {
+            call.call().await?;
+            Ok(())
+        })
+        .await?;
+        return Ok(None);
+    }
+    let mined = audit
+        .record_broadcast(entry, async {
+            let pending =
+                retry::with_timeout(options.rpc_timeout, async { Ok(tx.send().await?) }).await?;
+            let tx_hash = *pending.tx_hash();
+            info!("Transaction {} broadcast", tx_hash);
+            let provider = pending.provider();
+            retry::wait_for_receipt(&tx_hash.to_string(), options, move || async move {
+                let receipt = provider.get_transaction_receipt(tx_hash).await?;
+                Ok(receipt.map(|receipt| BroadcastTx {
+                    tx_hash: receipt.transaction_hash.to_string(),
+                    block_number: receipt.block_number,
+                    success: receipt.status(),
+                }))
+            })
+            .await
+        })
+        .await?;
+    if !mined.success {
+        return Err(CollateralError::Reverted(format!(
+            "transaction {} reverted",
+            mined.tx_hash
+        )));
+    }
+    Ok(Some(mined))
+}
+
 pub async fn deposit(
     private_key: &str,
     hotkey: [u8; 32],
     executor_id: [u8; 16],
     amount: U256,
+    simulate: bool,
     network_config: &CollateralNetworkConfig,
-) -> Result<(), anyhow::Error> {
+    audit: &AuditLog,
+) -> Result<(), CollateralError> {
     let contract = get_collateral(private_key, network_config).await?;
-
+    let caller = signer_address(private_key)?;
     let tx = contract
         .deposit(
             FixedBytes::from_slice(&hotkey),
             FixedBytes::from_slice(&executor_id),
         )
-        .value(amount);
-    let tx = tx.send().await?;
-    let receipt = tx.get_receipt().await?;
-    tracing::info!("{receipt:?}");
+        .value(amount)
+        .from(caller);
+    let entry = AuditEntry::new(AuditOperation::Deposit, caller)
+        .executor(hotkey, executor_id)
+        .amount(amount);
+    if let Some(mined) = submit(tx, simulate, &network_config.tx_options, audit, entry).await? {
+        info!("Deposit mined in transaction {}", mined.tx_hash);
+    }
     Ok(())
 }
 
@@ -207,53 +281,61 @@ pub async fn deposit_with_config(
     hotkey: [u8; 32],
     executor_id: [u8; 16],
     amount: U256,
+    simulate: bool,
     network_config: &CollateralNetworkConfig,
-) -> Result<(), anyhow::Error> {
-    let contract = get_collateral(private_key, network_config).await?;
-
-    let tx = contract
-        .deposit(
-            FixedBytes::from_slice(&hotkey),
-            FixedBytes::from_slice(&executor_id),
-        )
-        .value(amount);
-    let tx = tx.send().await?;
-    let receipt = tx.get_receipt().await?;
-    tracing::info!("{receipt:?}");
-    Ok(())
+    audit: &AuditLog,
+) -> Result<(), CollateralError> {
+    deposit(
+        private_key,
+        hotkey,
+        executor_id,
+        amount,
+        simulate,
+        network_config,
+        audit,
+    )
+    .await
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn reclaim_collateral(
     private_key: &str,
     hotkey: [u8; 32],
     executor_id: [u8; 16],
     url: &str,
     url_content_md5_checksum: u128,
+    simulate: bool,
     network_config: &CollateralNetworkConfig,
-) -> Result<(), anyhow::Error> {
+    audit: &AuditLog,
+) -> Result<(), CollateralError> {
     let contract = get_collateral(private_key, network_config).await?;
-
-    let tx = contract.reclaimCollateral(
-        FixedBytes::from_slice(&hotkey),
-        FixedBytes::from_slice(&executor_id),
-        url.to_string(),
-        FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
-    );
-    let tx = tx.send().await?;
-    tx.get_receipt().await?;
+    let caller = signer_address(private_key)?;
+    let tx = contract
+        .reclaimCollateral(
+            FixedBytes::from_slice(&hotkey),
+            FixedBytes::from_slice(&executor_id),
+            url.to_string(),
+            FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
+        )
+        .from(caller);
+    let entry = AuditEntry::new(AuditOperation::Reclaim, caller).executor(hotkey, executor_id);
+    submit(tx, simulate, &network_config.tx_options, audit, entry).await?;
     Ok(())
 }
 
 pub async fn finalize_reclaim(
     private_key: &str,
     reclaim_request_id: U256,
+    simulate: bool,
     network_config: &CollateralNetworkConfig,
-) -> Result<(), anyhow::Error> {
+    audit: &AuditLog,
+) -> Result<(), CollateralError> {
     let contract = get_collateral(private_key, network_config).await?;
-
-    let tx = contract.finalizeReclaim(reclaim_request_id);
-    let tx = tx.send().await?;
-    tx.get_receipt().await?;
+    let caller = signer_address(private_key)?;
+    let tx = contract.finalizeReclaim(reclaim_request_id).from(caller);
+    let entry = AuditEntry::new(AuditOperation::FinalizeReclaim, caller)
+        .reclaim_request(reclaim_request_id);
+    submit(tx, simulate, &network_config.tx_options, audit, entry).await?;
     Ok(())
 }
 
@@ -262,44 +344,54 @@ pub async fn deny_reclaim(
     reclaim_request_id: U256,
     url: &str,
     url_content_md5_checksum: u128,
+    simulate: bool,
     network_config: &CollateralNetworkConfig,
-) -> Result<(), anyhow::Error> {
+    audit: &AuditLog,
+) -> Result<(), CollateralError> {
     let contract = get_collateral(private_key, network_config).await?;
-
-    let tx = contract.denyReclaimRequest(
-        reclaim_request_id,
-        url.to_string(),
-        FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
-    );
-    let tx = tx.send().await?;
-    tx.get_receipt().await?;
+    let caller = signer_address(private_key)?;
+    let tx = contract
+        .denyReclaimRequest(
+            reclaim_request_id,
+            url.to_string(),
+            FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
+        )
+        .from(caller);
+    let entry =
+        AuditEntry::new(AuditOperation::DenyReclaim, caller).reclaim_request(reclaim_request_id);
+    submit(tx, simulate, &network_config.tx_options, audit, entry).await?;
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn slash_collateral(
     private_key: &str,
     hotkey: [u8; 32],
     executor_id: [u8; 16],
     url: &str,
     url_content_md5_checksum: u128,
+    simulate: bool,
     network_config: &CollateralNetworkConfig,
-) -> Result<(), anyhow::Error> {
+    audit: &AuditLog,
+) -> Result<(), CollateralError> {
     let contract = get_collateral(private_key, network_config).await?;
-
-    let tx = contract.slashCollateral(
-        FixedBytes::from_slice(&hotkey),
-        FixedBytes::from_slice(&executor_id),
-        url.to_string(),
-        FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
-    );
-    let tx = tx.send().await?;
-    tx.get_receipt().await?;
+    let caller = signer_address(private_key)?;
+    let tx = contract
+        .slashCollateral(
+            FixedBytes::from_slice(&hotkey),
+            FixedBytes::from_slice(&executor_id),
+            url.to_string(),
+            FixedBytes::from_slice(&url_content_md5_checksum.to_be_bytes()),
+        )
+        .from(caller);
+    let entry = AuditEntry::new(AuditOperation::Slash, caller).executor(hotkey, executor_id);
+    submit(tx, simulate, &network_config.tx_options, audit, entry).await?;
     Ok(())
 }
 
 // Get methods
 
-pub async fn netuid(network_config: &CollateralNetworkConfig) -> Result<u16, anyhow::Error> {
+pub async fn netuid(network_config: &CollateralNetworkConfig) -> Result<u16, CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
@@ -308,7 +400,7 @@ pub async fn netuid(network_config: &CollateralNetworkConfig) -> Result<u16, any
     Ok(netuid)
 }
 
-pub async fn trustee(network_config: &CollateralNetworkConfig) -> Result<Address, anyhow::Error> {
+pub async fn trustee(network_config: &CollateralNetworkConfig) -> Result<Address, CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
@@ -319,7 +411,7 @@ pub async fn trustee(network_config: &CollateralNetworkConfig) -> Result<Address
 
 pub async fn decision_timeout(
     network_config: &CollateralNetworkConfig,
-) -> Result<u64, anyhow::Error> {
+) -> Result<u64, CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
@@ -330,7 +422,7 @@ pub async fn decision_timeout(
 
 pub async fn min_collateral_increase(
     network_config: &CollateralNetworkConfig,
-) -> Result<U256, anyhow::Error> {
+) -> Result<U256, CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
@@ -343,7 +435,7 @@ pub async fn executor_to_miner(
     hotkey: [u8; 32],
     executor_id: [u8; 16],
     network_config: &CollateralNetworkConfig,
-) -> Result<Address, anyhow::Error> {
+) -> Result<Address, CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
@@ -363,7 +455,7 @@ pub async fn collaterals(
     hotkey: [u8; 32],
     executor_id: [u8; 16],
     network_config: &CollateralNetworkConfig,
-) -> Result<U256, anyhow::Error> {
+) -> Result<U256, CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
@@ -381,7 +473,7 @@ pub async fn collaterals(
 pub async fn reclaims(
     reclaim_request_id: U256,
     network_config: &CollateralNetworkConfig,
-) -> Result<Reclaim, anyhow::Error> {
+) -> Result<Reclaim, CollateralError> {
     let provider = ProviderBuilder::new()
         .connect(&network_config.rpc_url)
         .await?;
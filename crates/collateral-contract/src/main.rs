@@ -1,14 +1,21 @@
 use alloy_primitives::U256;
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use clap_verbosity_flag::{InfoLevel, Verbosity};
 use collateral_contract::{
-    config::{CollateralNetworkConfig, Network},
-    CollateralEvent,
+    amount::{format_amount, format_tao, parse_amount, AmountUnit},
+    audit::{AuditFilter, AuditLog, AuditOperation, AuditRecord},
+    config::{
+        CollateralNetworkConfig, Network, TxOptions, DEFAULT_RECEIPT_TIMEOUT, DEFAULT_RPC_RETRIES,
+        DEFAULT_RPC_TIMEOUT,
+    },
+    keystore, CollateralError, CollateralEvent,
 };
 use hex::FromHex;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[derive(Parser)]
@@ -24,6 +31,32 @@ struct Cli {
     #[arg(long)]
     contract_address: Option<String>,
 
+    /// Unit for displaying amounts
+    #[arg(long, value_enum, default_value_t = AmountUnit::Both, global = true)]
+    unit: AmountUnit,
+
+    /// File transactions are recorded in before and after broadcast
+    #[arg(
+        long,
+        env = "COLLATERAL_AUDIT_LOG",
+        default_value = "collateral-audit.jsonl",
+        global = true
+    )]
+    audit_log: PathBuf,
+
+    /// Seconds a single RPC request may take before it is retried
+    #[arg(long, default_value_t = DEFAULT_RPC_TIMEOUT.as_secs(), global = true)]
+    rpc_timeout: u64,
+
+    /// Seconds to wait for a broadcast transaction to be mined
+    #[arg(long, default_value_t = DEFAULT_RECEIPT_TIMEOUT.as_secs(), global = true)]
+    receipt_timeout: u64,
+
+    /// How many times a failed RPC request is retried; a broadcast is never
+    /// repeated
+    #[arg(long, default_value_t = DEFAULT_RPC_RETRIES, global = true)]
+    retries: u32,
+
     #[command(flatten)]
     verbosity: Verbosity<InfoLevel>,
 
@@ -44,28 +77,46 @@ enum Commands {
     Events(EventCommands),
 }
 
+/// Where the key that signs a transaction comes from
+#[derive(Args)]
+struct SignerArgs {
+    /// Encrypted JSON keystore holding the signing key
+    #[arg(long, env = "COLLATERAL_KEYSTORE", conflicts_with = "private_key")]
+    keystore: Option<PathBuf>,
+    /// File containing the keystore password; prompted for when omitted
+    #[arg(long, env = "COLLATERAL_KEYSTORE_PASSWORD_FILE", requires = "keystore")]
+    keystore_password_file: Option<PathBuf>,
+    /// Private key for signing the transaction (hex string); deprecated in
+    /// favour of --keystore, as it ends up in shell history
+    #[arg(long, env = "PRIVATE_KEY")]
+    private_key: Option<String>,
+}
+
 #[derive(Subcommand)]
 enum TxCommands {
     /// Deposit collateral for an executor
     Deposit {
-        /// Private key for signing the transaction (hex string)
-        #[arg(long, env = "PRIVATE_KEY")]
-        private_key: String,
+        #[command(flatten)]
+        signer: SignerArgs,
         /// Hotkey as hex string (32 bytes)
         #[arg(long)]
         hotkey: String,
         /// Executor ID as string
         #[arg(long)]
         executor_id: String,
-        /// Amount to deposit in wei
+        /// Amount to deposit, e.g. 1.5tao or 500000000000000000wei; a bare
+        /// integer is taken as wei
         #[arg(long)]
         amount: String,
+        /// Simulate the transaction and report whether it would revert,
+        /// without broadcasting it
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Reclaim collateral for an executor
     ReclaimCollateral {
-        /// Private key for signing the transaction (hex string)
-        #[arg(long, env = "PRIVATE_KEY")]
-        private_key: String,
+        #[command(flatten)]
+        signer: SignerArgs,
         /// Hotkey as hex string (32 bytes)
         #[arg(long)]
         hotkey: String,
@@ -75,39 +126,56 @@ enum TxCommands {
         /// URL for proof of reclaim
         #[arg(long)]
         url: String,
-        /// MD5 checksum of URL content as hex string (16 bytes)
+        /// MD5 checksum of URL content as hex string (16 bytes); computed
+        /// from the URL when omitted with --verify-url
+        #[arg(long, required_unless_present = "verify_url")]
+        url_content_md5_checksum: Option<String>,
+        /// Fetch the URL and check its content against the checksum
+        #[arg(long)]
+        verify_url: bool,
+        /// Simulate the transaction and report whether it would revert,
+        /// without broadcasting it
         #[arg(long)]
-        url_content_md5_checksum: String,
+        dry_run: bool,
     },
     /// Finalize a reclaim request
     FinalizeReclaim {
-        /// Private key for signing the transaction (hex string)
-        #[arg(long, env = "PRIVATE_KEY")]
-        private_key: String,
+        #[command(flatten)]
+        signer: SignerArgs,
         /// Reclaim request ID
         #[arg(long)]
         reclaim_request_id: String,
+        /// Simulate the transaction and report whether it would revert,
+        /// without broadcasting it
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Deny a reclaim request
     DenyReclaim {
-        /// Private key for signing the transaction (hex string)
-        #[arg(long, env = "PRIVATE_KEY")]
-        private_key: String,
+        #[command(flatten)]
+        signer: SignerArgs,
         /// Reclaim request ID
         #[arg(long)]
         reclaim_request_id: String,
         /// URL for proof of denial
         #[arg(long)]
         url: String,
-        /// MD5 checksum of URL content as hex string (16 bytes)
+        /// MD5 checksum of URL content as hex string (16 bytes); computed
+        /// from the URL when omitted with --verify-url
+        #[arg(long, required_unless_present = "verify_url")]
+        url_content_md5_checksum: Option<String>,
+        /// Fetch the URL and check its content against the checksum
+        #[arg(long)]
+        verify_url: bool,
+        /// Simulate the transaction and report whether it would revert,
+        /// without broadcasting it
         #[arg(long)]
-        url_content_md5_checksum: String,
+        dry_run: bool,
     },
     /// Slash collateral for an executor
     SlashCollateral {
-        /// Private key for signing the transaction (hex string)
-        #[arg(long, env = "PRIVATE_KEY")]
-        private_key: String,
+        #[command(flatten)]
+        signer: SignerArgs,
         /// Hotkey as hex string (32 bytes)
         #[arg(long)]
         hotkey: String,
@@ -117,9 +185,17 @@ enum TxCommands {
         /// URL for proof of slashing
         #[arg(long)]
         url: String,
-        /// MD5 checksum of URL content as hex string (16 bytes)
+        /// MD5 checksum of URL content as hex string (16 bytes); computed
+        /// from the URL when omitted with --verify-url
+        #[arg(long, required_unless_present = "verify_url")]
+        url_content_md5_checksum: Option<String>,
+        /// Fetch the URL and check its content against the checksum
+        #[arg(long)]
+        verify_url: bool,
+        /// Simulate the transaction and report whether it would revert,
+        /// without broadcasting it
         #[arg(long)]
-        url_content_md5_checksum: String,
+        dry_run: bool,
     },
 }
 
@@ -157,6 +233,24 @@ enum QueryCommands {
         #[arg(long)]
         reclaim_request_id: String,
     },
+    /// Show recorded collateral transactions from the audit log
+    Audit {
+        /// Only show this operation
+        #[arg(long, value_enum)]
+        operation: Option<AuditOperation>,
+        /// Hotkey as hex string (32 bytes)
+        #[arg(long)]
+        hotkey: Option<String>,
+        /// Executor ID as string
+        #[arg(long)]
+        executor_id: Option<String>,
+        /// Transaction hash
+        #[arg(long)]
+        tx_hash: Option<String>,
+        /// Output format: json or pretty
+        #[arg(long, default_value = "pretty")]
+        format: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -181,132 +275,186 @@ async fn main() -> Result<()> {
 
     // Initialize logging using the unified system
     let binary_name = env!("CARGO_BIN_NAME").replace("-", "_");
-    let base_filter = format!("basilica_protocol=info,{}", binary_name);
-    let default_filter = format!("basilica_protocol=info,{}=info", binary_name);
+    let base_filter = format!(
+        "basilica_protocol=info,collateral_contract=info,{}",
+        binary_name
+    );
+    let default_filter = format!(
+        "basilica_protocol=info,collateral_contract=info,{}=info",
+        binary_name
+    );
     basilica_common::logging::init_logging(&cli.verbosity, &base_filter, &default_filter)?;
-    let network_config = CollateralNetworkConfig::from_network(&cli.network, cli.contract_address)?;
+    let mut network_config =
+        CollateralNetworkConfig::from_network(&cli.network, cli.contract_address)?;
+    network_config.tx_options = TxOptions {
+        rpc_timeout: Duration::from_secs(cli.rpc_timeout),
+        receipt_timeout: Duration::from_secs(cli.receipt_timeout),
+        retries: cli.retries,
+        ..TxOptions::default()
+    };
 
     println!("Using network: {:?}", cli.network);
     println!("Contract address: {}", network_config.contract_address);
     println!("RPC URL: {}", network_config.rpc_url);
 
+    let audit = AuditLog::new(cli.audit_log);
+
     match cli.command {
-        Commands::Tx(tx_cmd) => handle_tx_command(tx_cmd, &network_config).await,
-        Commands::Query(query_cmd) => handle_query_command(query_cmd, &network_config).await,
-        Commands::Events(event_cmd) => handle_event_command(event_cmd, &network_config).await,
+        Commands::Tx(tx_cmd) => handle_tx_command(tx_cmd, &network_config, &audit, cli.unit).await,
+        Commands::Query(query_cmd) => {
+            handle_query_command(query_cmd, &network_config, &audit, cli.unit).await
+        }
+        Commands::Events(event_cmd) => {
+            handle_event_command(event_cmd, &network_config, cli.unit).await
+        }
     }
 }
 
 async fn handle_tx_command(
     cmd: TxCommands,
     network_config: &CollateralNetworkConfig,
+    audit: &AuditLog,
+    unit: AmountUnit,
 ) -> Result<()> {
     match cmd {
         TxCommands::Deposit {
-            private_key,
+            signer,
             hotkey,
             executor_id,
             amount,
+            dry_run,
         } => {
+            let private_key = resolve_private_key(&signer)?;
             let hotkey_bytes = parse_hotkey(&hotkey)?;
-            let amount_u256 = parse_u256(&amount)?;
+            let amount_u256 = parse_amount(&amount)?;
             let executor_uuid = Uuid::parse_str(&executor_id)?;
 
             println!(
-                "Depositing {} wei for executor {} with hotkey {}",
-                amount, executor_id, hotkey
+                "Depositing {} for executor {} with hotkey {}",
+                format_amount(amount_u256, unit),
+                executor_id,
+                hotkey
             );
-            collateral_contract::deposit(
+            let result = collateral_contract::deposit(
                 &private_key,
                 hotkey_bytes,
                 executor_uuid.into_bytes(),
                 amount_u256,
+                dry_run,
                 network_config,
+                audit,
             )
-            .await?;
-            println!("Deposit transaction completed successfully!");
+            .await;
+            report_tx("Deposit", dry_run, result)?;
         }
         TxCommands::ReclaimCollateral {
-            private_key,
+            signer,
             hotkey,
             executor_id,
             url,
             url_content_md5_checksum,
+            verify_url,
+            dry_run,
         } => {
+            let private_key = resolve_private_key(&signer)?;
             let hotkey_bytes = parse_hotkey(&hotkey)?;
-            let checksum = parse_md5_checksum(&url_content_md5_checksum)?;
+            let checksum =
+                resolve_url_checksum(&url, url_content_md5_checksum.as_deref(), verify_url).await?;
             let executor_uuid = Uuid::parse_str(&executor_id)?;
 
             println!(
                 "Reclaiming collateral for executor {} with hotkey {}",
                 executor_id, hotkey
             );
-            collateral_contract::reclaim_collateral(
+            let result = collateral_contract::reclaim_collateral(
                 &private_key,
                 hotkey_bytes,
                 executor_uuid.into_bytes(),
                 &url,
                 checksum,
+                dry_run,
                 network_config,
+                audit,
             )
-            .await?;
-            println!("Reclaim collateral transaction completed successfully!");
+            .await;
+            report_tx("Reclaim collateral", dry_run, result)?;
         }
         TxCommands::FinalizeReclaim {
-            private_key,
+            signer,
             reclaim_request_id,
+            dry_run,
         } => {
+            let private_key = resolve_private_key(&signer)?;
             let request_id = parse_u256(&reclaim_request_id)?;
 
             println!("Finalizing reclaim request {}", reclaim_request_id);
-            collateral_contract::finalize_reclaim(&private_key, request_id, network_config).await?;
-            println!("Finalize reclaim transaction completed successfully!");
+            let result = collateral_contract::finalize_reclaim(
+                &private_key,
+                request_id,
+                dry_run,
+                network_config,
+                audit,
+            )
+            .await;
+            report_tx("Finalize reclaim", dry_run, result)?;
         }
         TxCommands::DenyReclaim {
-            private_key,
+            signer,
             reclaim_request_id,
             url,
             url_content_md5_checksum,
+            verify_url,
+            dry_run,
         } => {
+            let private_key = resolve_private_key(&signer)?;
             let request_id = parse_u256(&reclaim_request_id)?;
-            let checksum = parse_md5_checksum(&url_content_md5_checksum)?;
+            let checksum =
+                resolve_url_checksum(&url, url_content_md5_checksum.as_deref(), verify_url).await?;
 
             println!("Denying reclaim request {}", reclaim_request_id);
-            collateral_contract::deny_reclaim(
+            let result = collateral_contract::deny_reclaim(
                 &private_key,
                 request_id,
                 &url,
                 checksum,
+                dry_run,
                 network_config,
+                audit,
             )
-            .await?;
-            println!("Deny reclaim transaction completed successfully!");
+            .await;
+            report_tx("Deny reclaim", dry_run, result)?;
         }
         TxCommands::SlashCollateral {
-            private_key,
+            signer,
             hotkey,
             executor_id,
             url,
             url_content_md5_checksum,
+            verify_url,
+            dry_run,
         } => {
+            let private_key = resolve_private_key(&signer)?;
             let hotkey_bytes = parse_hotkey(&hotkey)?;
-            let checksum = parse_md5_checksum(&url_content_md5_checksum)?;
+            let checksum =
+                resolve_url_checksum(&url, url_content_md5_checksum.as_deref(), verify_url).await?;
             let executor_uuid = Uuid::parse_str(&executor_id)?;
 
             println!(
                 "Slashing collateral for executor {} with hotkey {}",
                 executor_id, hotkey
             );
-            collateral_contract::slash_collateral(
+            let result = collateral_contract::slash_collateral(
                 &private_key,
                 hotkey_bytes,
                 executor_uuid.into_bytes(),
                 &url,
                 checksum,
+                dry_run,
                 network_config,
+                audit,
             )
-            .await?;
-            println!("Slash collateral transaction completed successfully!");
+            .await;
+            report_tx("Slash collateral", dry_run, result)?;
         }
     }
     Ok(())
@@ -315,6 +463,8 @@ async fn handle_tx_command(
 async fn handle_query_command(
     cmd: QueryCommands,
     network_config: &CollateralNetworkConfig,
+    audit: &AuditLog,
+    unit: AmountUnit,
 ) -> Result<()> {
     match cmd {
         QueryCommands::Netuid => {
@@ -331,7 +481,10 @@ async fn handle_query_command(
         }
         QueryCommands::MinCollateralIncrease => {
             let result = collateral_contract::min_collateral_increase(network_config).await?;
-            println!("Minimum collateral increase: {} wei", result);
+            println!(
+                "Minimum collateral increase: {}",
+                format_amount(result, unit)
+            );
         }
         QueryCommands::ExecutorToMiner {
             hotkey,
@@ -365,8 +518,9 @@ async fn handle_query_command(
             )
             .await?;
             println!(
-                "Collateral for executor {}: {} wei",
-                executor_id_clone, result
+                "Collateral for executor {}: {}",
+                executor_id_clone,
+                format_amount(result, unit)
             );
         }
         QueryCommands::Reclaims { reclaim_request_id } => {
@@ -376,9 +530,28 @@ async fn handle_query_command(
             println!("  Hotkey: {}", hex::encode(result.hotkey));
             println!("  Executor ID: {}", Uuid::from_bytes(result.executor_id));
             println!("  Miner: {}", result.miner);
-            println!("  Amount: {} wei", result.amount);
+            println!("  Amount: {}", format_amount(result.amount, unit));
             println!("  Deny timeout: {}", result.deny_timeout);
         }
+        QueryCommands::Audit {
+            operation,
+            hotkey,
+            executor_id,
+            tx_hash,
+            format,
+        } => {
+            let records = audit.query(&AuditFilter {
+                operation,
+                hotkey,
+                executor_id,
+                tx_hash,
+            })?;
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&records)?);
+            } else {
+                print_audit_records(&records, unit);
+            }
+        }
     }
     Ok(())
 }
@@ -386,6 +559,7 @@ async fn handle_query_command(
 async fn handle_event_command(
     cmd: EventCommands,
     network_config: &CollateralNetworkConfig,
+    unit: AmountUnit,
 ) -> Result<()> {
     match cmd {
         EventCommands::Scan {
@@ -403,7 +577,7 @@ async fn handle_event_command(
             if format == "json" {
                 print_events_json(&events)?;
             } else {
-                print_events_pretty(&events);
+                print_events_pretty(&events, unit);
             }
         }
     }
@@ -412,6 +586,35 @@ async fn handle_event_command(
 
 // Helper functions for parsing inputs
 
+/// Private key to sign with: decrypted from the keystore when one is given,
+/// otherwise the plain --private-key
+fn resolve_private_key(signer: &SignerArgs) -> Result<String> {
+    if let Some(path) = &signer.keystore {
+        let password = match &signer.keystore_password_file {
+            Some(file) => keystore::read_password_file(file)?,
+            None => dialoguer::Password::new()
+                .with_prompt(format!("Password for {}", path.display()))
+                .interact()?,
+        };
+        let key = keystore::decrypt_keystore(path, &password)?;
+        println!("Signing with {}", key.address());
+        return Ok(keystore::private_key_hex(&key));
+    }
+
+    match &signer.private_key {
+        Some(private_key) => {
+            eprintln!(
+                "Warning: --private-key and PRIVATE_KEY expose the key in shell history and \
+                 the process environment; use --keystore instead"
+            );
+            Ok(private_key.clone())
+        }
+        None => Err(anyhow::anyhow!(
+            "No signing key given; pass --keystore or --private-key"
+        )),
+    }
+}
+
 fn parse_hotkey(hotkey: &str) -> Result<[u8; 32]> {
     let hotkey = hotkey.strip_prefix("0x").unwrap_or(hotkey);
     if hotkey.len() != 64 {
@@ -429,6 +632,86 @@ fn parse_u256(value: &str) -> Result<U256> {
     Ok(U256::from_str(value)?)
 }
 
+/// Report a transaction outcome; with `dry_run` it is the simulated
+/// outcome and nothing was broadcast
+fn report_tx(action: &str, dry_run: bool, result: Result<(), CollateralError>) -> Result<()> {
+    match result {
+        Ok(()) if dry_run => {
+            println!("Dry run: {action} transaction would succeed, nothing was broadcast")
+        }
+        Ok(()) => println!("{action} transaction completed successfully!"),
+        Err(e) if dry_run => {
+            return Err(
+                anyhow::Error::new(e).context(format!("Dry run: {action} transaction would fail"))
+            );
+        }
+        Err(e) => {
+            if let Some(tx_hash) = e.broadcast_tx_hash() {
+                println!(
+                    "{action} transaction {tx_hash} was broadcast but not confirmed; \
+                     check its status before sending it again"
+                );
+            }
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+fn print_audit_records(records: &[AuditRecord], unit: AmountUnit) {
+    println!("Found {} audit records in the audit log", records.len());
+    for record in records {
+        let entry = &record.entry;
+        println!(
+            "  {} {} {:?} ({})",
+            record.timestamp.to_rfc3339(),
+            entry.operation,
+            record.outcome,
+            record.id
+        );
+        println!("    Caller: {}", entry.caller);
+        if let Some(hotkey) = &entry.hotkey {
+            println!("    Hotkey: {}", hotkey);
+        }
+        if let Some(executor_id) = &entry.executor_id {
+            println!("    Executor ID: {}", executor_id);
+        }
+        if let Some(request_id) = &entry.reclaim_request_id {
+            println!("    Reclaim request ID: {}", request_id);
+        }
+        if let Some(amount) = entry.amount.as_deref() {
+            match U256::from_str(amount) {
+                Ok(amount) => println!("    Amount: {}", format_amount(amount, unit)),
+                Err(_) => println!("    Amount: {} wei", amount),
+            }
+        }
+        if let Some(tx_hash) = &record.tx_hash {
+            println!("    Transaction: {}", tx_hash);
+        }
+        if let Some(block) = record.block_number {
+            println!("    Block: {}", block);
+        }
+        if let Some(error) = &record.error {
+            println!("    Error: {}", error);
+        }
+    }
+}
+
+/// Checksum to submit for an evidence URL: the given one, checked against
+/// the URL content when `verify_url` is set, or computed from the URL
+async fn resolve_url_checksum(url: &str, checksum: Option<&str>, verify_url: bool) -> Result<u128> {
+    let expected = checksum.map(parse_md5_checksum).transpose()?;
+    match expected {
+        Some(expected) if !verify_url => Ok(expected),
+        _ => {
+            let checksum =
+                collateral_contract::evidence::compute_and_verify_url_md5(url, expected).await?;
+            println!("Verified URL content MD5: {checksum:032x}");
+            Ok(checksum)
+        }
+    }
+}
+
 fn parse_md5_checksum(checksum: &str) -> Result<u128> {
     let checksum = checksum.strip_prefix("0x").unwrap_or(checksum);
     if checksum.len() != 32 {
@@ -442,7 +725,7 @@ fn parse_md5_checksum(checksum: &str) -> Result<u128> {
     Ok(u128::from_be_bytes(array))
 }
 
-fn print_events_pretty(events: &HashMap<u64, Vec<CollateralEvent>>) {
+fn print_events_pretty(events: &HashMap<u64, Vec<CollateralEvent>>, unit: AmountUnit) {
     if events.is_empty() {
         println!("No events found");
         return;
@@ -461,7 +744,7 @@ fn print_events_pretty(events: &HashMap<u64, Vec<CollateralEvent>>) {
                         hex::encode(deposit.executorId.as_slice())
                     );
                     println!("    Miner: {}", deposit.miner);
-                    println!("    Amount: {} wei", deposit.amount);
+                    println!("    Amount: {}", format_amount(deposit.amount, unit));
                 }
                 CollateralEvent::Reclaimed(reclaimed) => {
                     println!("    Type: Reclaimed");
@@ -472,7 +755,7 @@ fn print_events_pretty(events: &HashMap<u64, Vec<CollateralEvent>>) {
                         hex::encode(reclaimed.executorId.as_slice())
                     );
                     println!("    Miner: {}", reclaimed.miner);
-                    println!("    Amount: {} wei", reclaimed.amount);
+                    println!("    Amount: {}", format_amount(reclaimed.amount, unit));
                 }
                 CollateralEvent::Slashed(slashed) => {
                     println!("    Type: Slashed");
@@ -482,7 +765,7 @@ fn print_events_pretty(events: &HashMap<u64, Vec<CollateralEvent>>) {
                         hex::encode(slashed.executorId.as_slice())
                     );
                     println!("    Miner: {}", slashed.miner);
-                    println!("    Amount: {} wei", slashed.amount);
+                    println!("    Amount: {}", format_amount(slashed.amount, unit));
                     println!("    URL: {}", slashed.url);
                     println!(
                         "    URL Content MD5: {}",
@@ -508,7 +791,8 @@ fn print_events_json(events: &HashMap<u64, Vec<CollateralEvent>>) -> Result<()>
                         "hotkey": hex::encode(deposit.hotkey.as_slice()),
                         "executorId": hex::encode(deposit.executorId.as_slice()),
                         "miner": deposit.miner.to_string(),
-                        "amount": deposit.amount.to_string()
+                        "amount": deposit.amount.to_string(),
+                        "amountTao": format_tao(deposit.amount)
                     })
                 }
                 CollateralEvent::Reclaimed(reclaimed) => {
@@ -518,7 +802,8 @@ fn print_events_json(events: &HashMap<u64, Vec<CollateralEvent>>) -> Result<()>
                         "hotkey": hex::encode(reclaimed.hotkey.as_slice()),
                         "executorId": hex::encode(reclaimed.executorId.as_slice()),
                         "miner": reclaimed.miner.to_string(),
-                        "amount": reclaimed.amount.to_string()
+                        "amount": reclaimed.amount.to_string(),
+                        "amountTao": format_tao(reclaimed.amount)
                     })
                 }
                 CollateralEvent::Slashed(slashed) => {
@@ -528,6 +813,7 @@ fn print_events_json(events: &HashMap<u64, Vec<CollateralEvent>>) -> Result<()>
                         "executorId": hex::encode(slashed.executorId.as_slice()),
                         "miner": slashed.miner.to_string(),
                         "amount": slashed.amount.to_string(),
+                        "amountTao": format_tao(slashed.amount),
                         "url": slashed.url,
                         "urlContentMd5Checksum": hex::encode(slashed.urlContentMd5Checksum.as_slice())
                     })
@@ -0,0 +1,213 @@
+//! Timeouts and retries for the RPC requests made while sending transactions
+//!
+//! Only requests that are safe to repeat are retried. Broadcasting is not:
+//! a send that timed out may still have reached the node, and sending again
+//! would submit a second transaction with the next nonce.
+
+use crate::audit::BroadcastTx;
+use crate::config::TxOptions;
+use crate::CollateralError;
+use std::future::Future;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Run `request`, failing with [`CollateralError::RpcTimeout`] if it takes
+/// longer than `timeout`
+pub async fn with_timeout<T>(
+    timeout: Duration,
+    request: impl Future<Output = Result<T, CollateralError>>,
+) -> Result<T, CollateralError> {
+    tokio::time::timeout(timeout, request)
+        .await
+        .map_err(|_| CollateralError::RpcTimeout(timeout))?
+}
+
+/// Run `request` with the RPC timeout, retrying retryable failures up to
+/// `options.retries` times
+pub async fn with_retries<T, F, Fut>(
+    options: &TxOptions,
+    what: &str,
+    mut request: F,
+) -> Result<T, CollateralError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, CollateralError>>,
+{
+    let attempts = options.retries + 1;
+    let mut attempt = 1;
+    loop {
+        match with_timeout(options.rpc_timeout, request()).await {
+            Err(e) if e.is_retryable() && attempt < attempts => {
+                warn!("{what} failed (attempt {attempt} of {attempts}), retrying: {e}");
+                tokio::time::sleep(options.poll_interval).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Poll `fetch_receipt` until broadcast transaction `tx_hash` is mined
+///
+/// `fetch_receipt` returns `None` while the transaction is pending. Gives up
+/// once `options.receipt_timeout` has passed, or after a lookup has failed
+/// more than `options.retries` times in a row; either way the error carries
+/// `tx_hash` so the transaction can be checked by hand.
+pub async fn wait_for_receipt<F, Fut>(
+    tx_hash: &str,
+    options: &TxOptions,
+    mut fetch_receipt: F,
+) -> Result<BroadcastTx, CollateralError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<BroadcastTx>, CollateralError>>,
+{
+    let poll = async {
+        let mut attempt = 0;
+        let mut failures = 0;
+        loop {
+            attempt += 1;
+            info!("Waiting for confirmation of {tx_hash}, attempt {attempt}");
+            match with_timeout(options.rpc_timeout, fetch_receipt()).await {
+                Ok(Some(receipt)) => return Ok(receipt),
+                Ok(None) => failures = 0,
+                Err(e) if e.is_retryable() && failures < options.retries => {
+                    failures += 1;
+                    warn!("Receipt lookup for {tx_hash} failed, retrying: {e}");
+                }
+                Err(e) => {
+                    return Err(CollateralError::Unconfirmed {
+                        tx_hash: tx_hash.to_string(),
+                        reason: format!("receipt lookup failed: {e}"),
+                    });
+                }
+            }
+            tokio::time::sleep(options.poll_interval).await;
+        }
+    };
+
+    tokio::time::timeout(options.receipt_timeout, poll)
+        .await
+        .unwrap_or_else(|_| {
+            Err(CollateralError::Unconfirmed {
+                tx_hash: tx_hash.to_string(),
+                reason: format!("no receipt after {:?}", options.receipt_timeout),
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    const TX_HASH: &str = "0xfeed";
+
+    fn options() -> TxOptions {
+        TxOptions {
+            rpc_timeout: Duration::from_secs(5),
+            receipt_timeout: Duration::from_secs(60),
+            retries: 2,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    fn mined() -> BroadcastTx {
+        BroadcastTx {
+            tx_hash: TX_HASH.to_string(),
+            block_number: Some(7),
+            success: true,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_receipt_is_returned_once_mined() {
+        let lookups = Cell::new(0);
+        let receipt = wait_for_receipt(TX_HASH, &options(), || {
+            lookups.set(lookups.get() + 1);
+            let pending = lookups.get() < 3;
+            async move { Ok((!pending).then(mined)) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(receipt.block_number, Some(7));
+        assert_eq!(lookups.get(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_receipt_wait_times_out_with_tx_hash() {
+        let lookups = Cell::new(0);
+        let err = wait_for_receipt(TX_HASH, &options(), || {
+            lookups.set(lookups.get() + 1);
+            async { Ok(None) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.broadcast_tx_hash(), Some(TX_HASH));
+        assert!(err.to_string().contains("no receipt after 60s"), "{err}");
+        // About one lookup per second until the minute is up
+        assert!(lookups.get() >= 60, "{} lookups", lookups.get());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_hung_lookup_counts_as_failure() {
+        let err = wait_for_receipt(TX_HASH, &options(), || {
+            std::future::pending::<Result<Option<BroadcastTx>, CollateralError>>()
+        })
+        .await
+        .unwrap_err();
+
+        // Three 5 second timeouts exhaust the retries well before the minute
+        assert_eq!(err.broadcast_tx_hash(), Some(TX_HASH));
+        assert!(err.to_string().contains("timed out after 5s"), "{err}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_receipt_lookup_retries_are_exhausted() {
+        let lookups = Cell::new(0);
+        let err = wait_for_receipt(TX_HASH, &options(), || {
+            lookups.set(lookups.get() + 1);
+            async { Err(CollateralError::RpcError("connection reset".into())) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(lookups.get(), 3);
+        assert_eq!(err.broadcast_tx_hash(), Some(TX_HASH));
+        assert!(err.to_string().contains("connection reset"), "{err}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_requests_are_retried_until_success() {
+        let calls = Cell::new(0);
+        let result = with_retries(&options(), "Simulating deposit", || {
+            calls.set(calls.get() + 1);
+            let call = calls.get();
+            async move {
+                if call < 3 {
+                    Err(CollateralError::RpcError("busy".into()))
+                } else {
+                    Ok(call)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reverts_are_not_retried() {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = with_retries(&options(), "Simulating deposit", || {
+            calls.set(calls.get() + 1);
+            async { Err(CollateralError::PastDenyTimeout) }
+        })
+        .await;
+
+        assert!(matches!(result, Err(CollateralError::PastDenyTimeout)));
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -215,3 +215,54 @@ async fn test_deploy_proxy_in_testnet() {
 
     println!("Deployed proxy at: {:?}", contract.address());
 }
+
+fn mocked_collateral(
+    asserter: &alloy::transports::mock::Asserter,
+) -> CollateralUpgradeable::CollateralUpgradeableInstance<impl Provider> {
+    let provider = ProviderBuilder::new()
+        .disable_recommended_fillers()
+        .connect_mocked_client(asserter.clone());
+    CollateralUpgradeable::new(Address::repeat_byte(0x02), provider)
+}
+
+#[tokio::test]
+async fn test_simulated_revert_is_reported_without_broadcast() {
+    let asserter = alloy::transports::mock::Asserter::new();
+    // eth_call reverting with PastDenyTimeout(); no response is queued for a
+    // broadcast, so sending would fail with a different error
+    asserter.push_failure(
+        serde_json::from_value(serde_json::json!({
+            "code": 3,
+            "message": "execution reverted",
+            "data": "0xfc9e5c02"
+        }))
+        .unwrap(),
+    );
+
+    let contract = mocked_collateral(&asserter);
+    let tx = contract
+        .denyReclaimRequest(
+            U256::from(1),
+            "https://example.com/evidence".to_string(),
+            FixedBytes::ZERO,
+        )
+        .from(Address::repeat_byte(0x01));
+
+    let result = submit(tx, true).await;
+    assert!(matches!(result, Err(CollateralError::PastDenyTimeout)));
+}
+
+#[tokio::test]
+async fn test_successful_simulation_does_not_broadcast() {
+    let asserter = alloy::transports::mock::Asserter::new();
+    asserter.push_success(&Bytes::new());
+
+    let contract = mocked_collateral(&asserter);
+    let tx = contract
+        .finalizeReclaim(U256::from(1))
+        .from(Address::repeat_byte(0x01));
+
+    // Only the eth_call response is queued, so a broadcast would error
+    let receipt = submit(tx, true).await.unwrap();
+    assert!(receipt.is_none());
+}
@@ -0,0 +1,217 @@
+//! Validator ↔ executor container log streaming over gRPC
+//!
+//! The validator signs log requests with its own hotkey instead of going
+//! through the miner. These tests run the validator's log streamer against
+//! an executor service that verifies requests with the executor's own
+//! authentication code.
+
+use anyhow::Result;
+use basilica_common::crypto::wallet::{
+    generate_sr25519_wallet_from_mnemonic, sign_with_sr25519, sr25519_pair_from_mnemonic,
+};
+use basilica_common::identity::Hotkey;
+use basilica_executor::miner_auth::{verify_logs_request, MinerAuthConfig, MinerAuthService};
+use basilica_protocol::common::LogEntry;
+use basilica_protocol::executor_control::{
+    executor_control_server::{ExecutorControl, ExecutorControlServer},
+    BenchmarkRequest, BenchmarkResponse, ContainerLogLine, ContainerLogsRequest,
+    ContainerOpRequest, ContainerOpResponse, HealthCheckRequest, HealthCheckResponse,
+    LogSubscriptionRequest, ProvisionAccessRequest, ProvisionAccessResponse, SystemProfileRequest,
+    SystemProfileResponse,
+};
+use basilica_validator::miner_prover::miner_client::ValidatorSigner;
+use basilica_validator::rental::LogStreamer;
+use integration_tests::test_hotkeys;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::Iter;
+use tonic::{Request, Response, Status};
+
+const VALIDATOR_MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+const OTHER_MNEMONIC: &str =
+    "legal winner thank year wave sausage worth useful legal winner thank yellow";
+const CONTAINER_ID: &str = "container-1";
+
+type LineStream<T> = Iter<std::vec::IntoIter<Result<T, Status>>>;
+
+fn hotkey(mnemonic: &str) -> String {
+    generate_sr25519_wallet_from_mnemonic(mnemonic, 42)
+        .unwrap()
+        .address
+}
+
+/// Signs with the key derived from a mnemonic, like the validator's wallet
+struct MnemonicSigner(&'static str);
+
+impl ValidatorSigner for MnemonicSigner {
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let pair = sr25519_pair_from_mnemonic(self.0)?;
+        Ok(hex::decode(sign_with_sr25519(&pair, data))?)
+    }
+}
+
+/// Executor whose only rental is `CONTAINER_ID`, deployed by `owner`
+struct LogsExecutor {
+    auth: MinerAuthService,
+    owner: String,
+}
+
+#[tonic::async_trait]
+impl ExecutorControl for LogsExecutor {
+    async fn provision_validator_access(
+        &self,
+        _request: Request<ProvisionAccessRequest>,
+    ) -> Result<Response<ProvisionAccessResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn execute_system_profile(
+        &self,
+        _request: Request<SystemProfileRequest>,
+    ) -> Result<Response<SystemProfileResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn execute_benchmark(
+        &self,
+        _request: Request<BenchmarkRequest>,
+    ) -> Result<Response<BenchmarkResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    async fn manage_container(
+        &self,
+        _request: Request<ContainerOpRequest>,
+    ) -> Result<Response<ContainerOpResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    type StreamLogsStream = LineStream<LogEntry>;
+
+    async fn stream_logs(
+        &self,
+        _request: Request<LogSubscriptionRequest>,
+    ) -> Result<Response<Self::StreamLogsStream>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+
+    type StreamContainerLogsStream = LineStream<ContainerLogLine>;
+
+    async fn stream_container_logs(
+        &self,
+        request: Request<ContainerLogsRequest>,
+    ) -> Result<Response<Self::StreamContainerLogsStream>, Status> {
+        let req = request.into_inner();
+        verify_logs_request(&self.auth, &req).await?;
+
+        if req.container_id != CONTAINER_ID || req.validator_hotkey != self.owner {
+            return Err(Status::permission_denied("not a rental of this validator"));
+        }
+
+        let lines = ["first line", "second line"]
+            .into_iter()
+            .map(|message| {
+                Ok(ContainerLogLine {
+                    timestamp: None,
+                    stream: "stdout".to_string(),
+                    message: message.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(Response::new(tokio_stream::iter(lines)))
+    }
+
+    async fn health_check(
+        &self,
+        _request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        Err(Status::unimplemented("not used"))
+    }
+}
+
+async fn start_executor(owner: String) -> String {
+    let miner_hotkey = Hotkey::new(test_hotkeys::MINER_HOTKEY_1.to_string()).unwrap();
+    let executor = LogsExecutor {
+        auth: MinerAuthService::new(MinerAuthConfig::new(miner_hotkey)),
+        owner,
+    };
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .add_service(ExecutorControlServer::new(executor))
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .unwrap();
+    });
+
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn test_signed_log_request_streams_over_grpc() {
+    let validator_hotkey = hotkey(VALIDATOR_MNEMONIC);
+    let endpoint = start_executor(validator_hotkey.clone()).await;
+
+    let mut rx = LogStreamer::new()
+        .stream_logs_grpc(
+            &endpoint,
+            &validator_hotkey,
+            &MnemonicSigner(VALIDATOR_MNEMONIC),
+            CONTAINER_ID,
+            false,
+            None,
+            None,
+        )
+        .await
+        .expect("executor should accept the signed request");
+
+    let mut messages = Vec::new();
+    while let Some(entry) = rx.recv().await {
+        assert_eq!(entry.container_id, CONTAINER_ID);
+        messages.push(entry.message);
+    }
+    assert_eq!(messages, ["first line", "second line"]);
+}
+
+#[tokio::test]
+async fn test_log_request_signed_by_another_key_is_rejected() {
+    let validator_hotkey = hotkey(VALIDATOR_MNEMONIC);
+    let endpoint = start_executor(validator_hotkey.clone()).await;
+
+    let result = LogStreamer::new()
+        .stream_logs_grpc(
+            &endpoint,
+            &validator_hotkey,
+            &MnemonicSigner(OTHER_MNEMONIC),
+            CONTAINER_ID,
+            false,
+            None,
+            None,
+        )
+        .await;
+
+    let status = result
+        .unwrap_err()
+        .downcast::<Status>()
+        .expect("executor should answer with a status");
+    assert_eq!(status.code(), tonic::Code::Unauthenticated);
+}
+
+#[tokio::test]
+async fn test_unsigned_log_request_is_rejected() {
+    let validator_hotkey = hotkey(VALIDATOR_MNEMONIC);
+    let auth = MinerAuthService::new(MinerAuthConfig::new(
+        Hotkey::new(test_hotkeys::MINER_HOTKEY_1.to_string()).unwrap(),
+    ));
+    let request = ContainerLogsRequest {
+        container_id: CONTAINER_ID.to_string(),
+        validator_hotkey,
+        ..Default::default()
+    };
+
+    let status = verify_logs_request(&auth, &request).await.unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Unauthenticated);
+}